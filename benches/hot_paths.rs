@@ -0,0 +1,222 @@
+//! Benchmarks for the paths the TUI runs every frame or every poll.
+//!
+//! Run with `cargo bench`. Compare runs before and after a performance change with
+//! `cargo bench -- --save-baseline before` and `cargo bench -- --baseline before`.
+
+use std::fmt::Write as _;
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use criterion::{Criterion, criterion_group, criterion_main};
+use tenex::agent::{Agent, ChildConfig, Status, Storage};
+use tenex::app::Settings;
+use tenex::git::{DiffGenerator, Repository};
+use tenex::{App, Config, Tab};
+
+/// Agents in the agent list benchmarks: 50 roots with 9 children each.
+const ROOTS: usize = 50;
+const CHILDREN_PER_ROOT: usize = 9;
+
+/// Files and lines per file in the diff benchmarks.
+const DIFF_FILES: usize = 40;
+const DIFF_LINES: usize = 200;
+
+/// A scratch directory removed when dropped.
+struct ScratchDir(PathBuf);
+
+impl ScratchDir {
+    fn new(name: &str) -> Result<Self> {
+        let path = std::env::temp_dir().join(format!("tenex-bench-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path)?;
+        Ok(Self(path))
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+fn app_with(storage: Storage) -> App {
+    App::new(Config::default(), storage, Settings::default(), false)
+}
+
+/// Storage with `ROOTS` root agents, each with `CHILDREN_PER_ROOT` children, across five
+/// projects.
+fn storage_with_agents() -> Storage {
+    let mut storage = Storage::new();
+    for root_index in 0..ROOTS {
+        let repo = PathBuf::from(format!("/work/project-{}", root_index % 5));
+        let mut root = Agent::new(
+            format!("Root agent {root_index}"),
+            "claude".to_string(),
+            format!("tenex/root-{root_index}"),
+            repo.join(format!(".worktrees/root-{root_index}")),
+        );
+        root.repo_root = Some(repo.clone());
+        root.status = Status::Running;
+        let parent_id = root.id;
+        let mux_session = root.mux_session.clone();
+        let worktree = root.worktree_path.clone();
+        let branch = root.branch.clone();
+        storage.add(root);
+
+        for child_index in 0..CHILDREN_PER_ROOT {
+            let mut child = Agent::new_child(
+                format!("Child {child_index} of root {root_index}"),
+                "claude".to_string(),
+                branch.clone(),
+                worktree.clone(),
+                ChildConfig {
+                    parent_id,
+                    mux_session: mux_session.clone(),
+                    window_index: u32::try_from(child_index + 2).unwrap_or(u32::MAX),
+                    repo_root: Some(repo.clone()),
+                },
+            );
+            child.status = Status::Running;
+            storage.add(child);
+        }
+    }
+    storage
+}
+
+/// Captured pane output: a few thousand lines of colored, agent-like output.
+fn pane_capture() -> String {
+    let mut capture = String::new();
+    for line in 0..3000 {
+        let _ = writeln!(
+            capture,
+            "\x1b[1;34m●\x1b[0m Reading \x1b[32msrc/module_{line}.rs\x1b[0m \
+             \x1b[2m({} lines)\x1b[0m \x1b[38;5;208mwarning\x1b[0m: unused variable `x{line}`",
+            line * 7
+        );
+    }
+    capture
+}
+
+fn bench_pane_capture(c: &mut Criterion) {
+    let capture = pane_capture();
+    let mut app = app_with(Storage::new());
+    c.bench_function("pane_capture/parse_3000_lines", |b| {
+        b.iter(|| app.data.ui.set_preview_content(black_box(capture.as_str())));
+    });
+}
+
+/// A repository with `DIFF_FILES` committed files, every fourth line of which is changed in
+/// the working tree.
+fn diff_repository(dir: &Path) -> Result<Repository> {
+    let repo = Repository::init(dir)?;
+    let mut index = repo.index()?;
+    for file in 0..DIFF_FILES {
+        let name = format!("file_{file}.rs");
+        let mut content = String::new();
+        for line in 0..DIFF_LINES {
+            let _ = writeln!(content, "fn item_{file}_{line}() -> usize {{ {line} }}");
+        }
+        std::fs::write(dir.join(&name), content)?;
+        index.add_path(Path::new(&name))?;
+    }
+    index.write()?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let signature = git2::Signature::now("Bench", "bench@example.com")?;
+    repo.commit(Some("HEAD"), &signature, &signature, "Initial", &tree, &[])?;
+    drop(tree);
+
+    for file in 0..DIFF_FILES {
+        let mut content = String::new();
+        for line in 0..DIFF_LINES {
+            if line % 4 == 0 {
+                let _ = writeln!(content, "fn item_{file}_{line}() -> usize {{ {line} + 1 }}");
+            } else {
+                let _ = writeln!(content, "fn item_{file}_{line}() -> usize {{ {line} }}");
+            }
+        }
+        std::fs::write(dir.join(format!("file_{file}.rs")), content)?;
+    }
+    Ok(repo)
+}
+
+fn bench_diff(c: &mut Criterion) {
+    let setup = ScratchDir::new("diff").and_then(|dir| {
+        let repo = diff_repository(&dir.0)?;
+        Ok((dir, repo))
+    });
+    let (_dir, repo) = match setup {
+        Ok(setup) => setup,
+        Err(err) => {
+            eprintln!("Skipping diff benchmarks: {err:#}");
+            return;
+        }
+    };
+    let generator = DiffGenerator::new(&repo);
+    let Ok(model) = generator.uncommitted_model() else {
+        eprintln!("Skipping diff benchmarks: the diff could not be generated");
+        return;
+    };
+    let mut app = app_with(Storage::new());
+
+    c.bench_function("diff/generate_model", |b| {
+        b.iter(|| generator.uncommitted_model());
+    });
+    c.bench_function("diff/build_view", |b| {
+        b.iter(|| app.data.ui.build_diff_view(black_box(&model)));
+    });
+
+    // Lines are colored as the diff tab draws them.
+    let (content, meta) = app.data.ui.build_diff_view(&model);
+    app.data.ui.set_diff_view(content, meta);
+    app.data.active_tab = Tab::Diff;
+    c.bench_function("diff/render_colored_frame", |b| {
+        b.iter(|| tenex::tui::snapshot::render(black_box(&app), 160, 50));
+    });
+}
+
+fn bench_agent_list(c: &mut Criterion) {
+    let app = app_with(storage_with_agents());
+    c.bench_function("agent_list/visible_500", |b| {
+        b.iter(|| {
+            black_box(&app.data.storage)
+                .visible_agents_with_info()
+                .len()
+        });
+    });
+    c.bench_function("agent_list/render_frame_500", |b| {
+        b.iter(|| tenex::tui::snapshot::render(black_box(&app), 160, 50));
+    });
+}
+
+fn bench_storage(c: &mut Criterion) {
+    let dir = match ScratchDir::new("storage") {
+        Ok(dir) => dir,
+        Err(err) => {
+            eprintln!("Skipping storage benchmarks: {err:#}");
+            return;
+        }
+    };
+    let path = dir.0.join("state.json");
+    let mut storage = storage_with_agents();
+    if let Err(err) = storage.save_to(&path) {
+        eprintln!("Skipping storage benchmarks: {err:#}");
+        return;
+    }
+
+    c.bench_function("storage/save_500", |b| {
+        b.iter(|| storage.save_to(&path));
+    });
+    c.bench_function("storage/load_500", |b| {
+        b.iter(|| Storage::load_at(&path));
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_pane_capture,
+    bench_diff,
+    bench_agent_list,
+    bench_storage
+);
+criterion_main!(benches);
@@ -48,6 +48,46 @@ impl ValidIn<ScrollingMode> for NewAgentWithPromptAction {
     }
 }
 
+/// Normal-mode action: pick a GitHub issue and create an agent for it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NewAgentFromIssueAction;
+
+impl ValidIn<NormalMode> for NewAgentFromIssueAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: NormalMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(Actions::start_issue_picker(app_data))
+    }
+}
+
+impl ValidIn<ScrollingMode> for NewAgentFromIssueAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ScrollingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(Actions::start_issue_picker(app_data))
+    }
+}
+
+/// Normal-mode action: pick a lost commit and recreate a killed agent's branch at it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RecoverBranchAction;
+
+impl ValidIn<NormalMode> for RecoverBranchAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: NormalMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(Actions::start_recovery(app_data))
+    }
+}
+
+impl ValidIn<ScrollingMode> for RecoverBranchAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ScrollingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(Actions::start_recovery(app_data))
+    }
+}
+
 /// Normal-mode action: kill the selected agent (enters confirmation).
 #[derive(Debug, Clone, Copy, Default)]
 pub struct KillAction;
@@ -82,6 +122,186 @@ impl ValidIn<ScrollingMode> for KillAction {
     }
 }
 
+/// Normal-mode action: show the selected agent's details.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AgentInfoAction;
+
+impl ValidIn<NormalMode> for AgentInfoAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: NormalMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Actions::show_agent_info(app_data)
+    }
+}
+
+impl ValidIn<ScrollingMode> for AgentInfoAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ScrollingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Actions::show_agent_info(app_data)
+    }
+}
+
+/// Normal-mode action: show the prompt the selected agent was started with.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ShowPromptAction;
+
+impl ValidIn<NormalMode> for ShowPromptAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: NormalMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(Actions::show_agent_prompt(app_data))
+    }
+}
+
+impl ValidIn<ScrollingMode> for ShowPromptAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ScrollingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(Actions::show_agent_prompt(app_data))
+    }
+}
+
+/// Normal-mode action: edit the selected agent's task before re-sending it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EditTaskAction;
+
+impl ValidIn<NormalMode> for EditTaskAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: NormalMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(Actions::start_edit_task(app_data).unwrap_or_else(AppMode::normal))
+    }
+}
+
+impl ValidIn<ScrollingMode> for EditTaskAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ScrollingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(Actions::start_edit_task(app_data).unwrap_or_else(|| ScrollingMode.into()))
+    }
+}
+
+/// Normal-mode action: send the selected agent's output to another agent.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SendToAction;
+
+impl ValidIn<NormalMode> for SendToAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: NormalMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(Actions::start_send_to(app_data).unwrap_or_else(AppMode::normal))
+    }
+}
+
+impl ValidIn<ScrollingMode> for SendToAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ScrollingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(Actions::start_send_to(app_data).unwrap_or_else(|| ScrollingMode.into()))
+    }
+}
+
+/// Normal-mode action: open the questions inbox.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InboxAction;
+
+impl ValidIn<NormalMode> for InboxAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: NormalMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(Actions::open_inbox(app_data))
+    }
+}
+
+impl ValidIn<ScrollingMode> for InboxAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ScrollingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(Actions::open_inbox(app_data))
+    }
+}
+
+/// Normal-mode action: type a short reply to the selected agent.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReplyAction;
+
+impl ValidIn<NormalMode> for ReplyAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: NormalMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(Actions::start_reply(app_data).unwrap_or_else(AppMode::normal))
+    }
+}
+
+impl ValidIn<ScrollingMode> for ReplyAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ScrollingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(Actions::start_reply(app_data).unwrap_or_else(|| ScrollingMode.into()))
+    }
+}
+
+/// Normal-mode action: pick a canned response to send to the selected agent.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CannedResponsesAction;
+
+impl ValidIn<NormalMode> for CannedResponsesAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: NormalMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(Actions::start_canned_responses(app_data).unwrap_or_else(AppMode::normal))
+    }
+}
+
+impl ValidIn<ScrollingMode> for CannedResponsesAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ScrollingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(Actions::start_canned_responses(app_data).unwrap_or_else(|| ScrollingMode.into()))
+    }
+}
+
+/// Normal-mode action: restart the selected agent from a summary (enters confirmation).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CompactAction;
+
+impl ValidIn<NormalMode> for CompactAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: NormalMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(Actions::start_compact(app_data).unwrap_or_else(AppMode::normal))
+    }
+}
+
+impl ValidIn<ScrollingMode> for CompactAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ScrollingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(Actions::start_compact(app_data).unwrap_or_else(|| ScrollingMode.into()))
+    }
+}
+
+/// Normal-mode action: type the program to relaunch the selected agent with.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SwitchModelAction;
+
+impl ValidIn<NormalMode> for SwitchModelAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: NormalMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(Actions::start_switch_model(app_data).unwrap_or_else(AppMode::normal))
+    }
+}
+
+impl ValidIn<ScrollingMode> for SwitchModelAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ScrollingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(Actions::start_switch_model(app_data).unwrap_or_else(|| ScrollingMode.into()))
+    }
+}
+
 /// Normal-mode action: start spawning child agents from the root.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct SpawnChildrenAction;
@@ -201,32 +421,29 @@ impl ValidIn<NormalMode> for SynthesizeAction {
         };
 
         if agent.is_terminal_agent() {
-            return Ok(ErrorModalMode {
-                message: "Cannot synthesize into a terminal agent".to_string(),
-            }
-            .into());
+            return Ok(ErrorModalMode::new("Cannot synthesize into a terminal agent").into());
         }
 
-        if !app_data.storage.has_children(agent.id) {
-            Ok(ErrorModalMode {
-                message: "Selected agent has no children to synthesize".to_string(),
-            }
-            .into())
-        } else if app_data
-            .storage
-            .descendants(agent.id)
-            .into_iter()
-            .any(|a| !a.is_terminal_agent())
+        // Roots the agent's swarm started in other repositories are synthesized with it.
+        let has_linked_roots = !app_data.storage.linked_roots(agent.id).is_empty();
+        if !app_data.storage.has_children(agent.id) && !has_linked_roots {
+            Ok(ErrorModalMode::new("Selected agent has no children to synthesize").into())
+        } else if has_linked_roots
+            || app_data
+                .storage
+                .descendants(agent.id)
+                .into_iter()
+                .any(|a| !a.is_terminal_agent())
         {
             Ok(ConfirmingMode {
                 action: ConfirmAction::Synthesize,
             }
             .into())
         } else {
-            Ok(ErrorModalMode {
-                message: "Selected agent has no non-terminal children to synthesize".to_string(),
-            }
-            .into())
+            Ok(
+                ErrorModalMode::new("Selected agent has no non-terminal children to synthesize")
+                    .into(),
+            )
         }
     }
 }
@@ -240,32 +457,29 @@ impl ValidIn<ScrollingMode> for SynthesizeAction {
         };
 
         if agent.is_terminal_agent() {
-            return Ok(ErrorModalMode {
-                message: "Cannot synthesize into a terminal agent".to_string(),
-            }
-            .into());
+            return Ok(ErrorModalMode::new("Cannot synthesize into a terminal agent").into());
         }
 
-        if !app_data.storage.has_children(agent.id) {
-            Ok(ErrorModalMode {
-                message: "Selected agent has no children to synthesize".to_string(),
-            }
-            .into())
-        } else if app_data
-            .storage
-            .descendants(agent.id)
-            .into_iter()
-            .any(|a| !a.is_terminal_agent())
+        // Roots the agent's swarm started in other repositories are synthesized with it.
+        let has_linked_roots = !app_data.storage.linked_roots(agent.id).is_empty();
+        if !app_data.storage.has_children(agent.id) && !has_linked_roots {
+            Ok(ErrorModalMode::new("Selected agent has no children to synthesize").into())
+        } else if has_linked_roots
+            || app_data
+                .storage
+                .descendants(agent.id)
+                .into_iter()
+                .any(|a| !a.is_terminal_agent())
         {
             Ok(ConfirmingMode {
                 action: ConfirmAction::Synthesize,
             }
             .into())
         } else {
-            Ok(ErrorModalMode {
-                message: "Selected agent has no non-terminal children to synthesize".to_string(),
-            }
-            .into())
+            Ok(
+                ErrorModalMode::new("Selected agent has no non-terminal children to synthesize")
+                    .into(),
+            )
         }
     }
 }
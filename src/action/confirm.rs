@@ -1,11 +1,12 @@
 //! Confirmation-mode action types (new architecture).
 
 use crate::action::{BackspaceAction, CancelAction, CharInputAction, SubmitAction, ValidIn};
-use crate::app::{Actions, AppData};
+use crate::app::{Actions, AppData, SynthesisOptions};
+use crate::error::TenexError;
 use crate::state::{
     AppMode, ConfirmAction, ConfirmPushForPRMode, ConfirmPushMode, ConfirmingMode, ErrorModalMode,
     KeyboardRemapPromptMode, PreviewFocusedMode, ReconnectPromptMode, RenameBranchMode,
-    SynthesisPromptMode, UpdatePromptMode, UpdateRequestedMode,
+    SynthesisStrategyMode, UpdatePromptMode, UpdateRequestedMode,
 };
 use anyhow::Result;
 use tracing::warn;
@@ -18,6 +19,18 @@ pub struct ConfirmYesAction;
 #[derive(Debug, Clone, Copy, Default)]
 pub struct ConfirmNoAction;
 
+/// Kill confirmation action: stop the agent but keep its branch and worktree (S/s).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KillStopAction;
+
+/// Synthesize confirmation action: synthesize but keep the agents running (K/k).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SynthesizeKeepAction;
+
+/// Quit confirmation action: stop every agent before quitting (S/s).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QuitStopAllAction;
+
 /// Worktree conflict action: reconnect to existing worktree (R/r).
 #[derive(Debug, Clone, Copy, Default)]
 pub struct WorktreeReconnectAction;
@@ -32,7 +45,7 @@ impl ValidIn<ConfirmingMode> for ConfirmYesAction {
     fn execute(self, state: ConfirmingMode, app_data: &mut AppData) -> Result<Self::NextState> {
         match state.action {
             ConfirmAction::Kill => {
-                Actions::new().kill_agent(app_data)?;
+                Actions::new().kill_agent(app_data, false)?;
             }
             ConfirmAction::InterruptAgent => {
                 if let Some(agent) = app_data.selected_agent()
@@ -52,14 +65,18 @@ impl ValidIn<ConfirmingMode> for ConfirmYesAction {
 
                 return Ok(PreviewFocusedMode.into());
             }
+            ConfirmAction::Compact => {
+                Actions::new().compact_agent(app_data)?;
+            }
             ConfirmAction::Reset => {
                 Actions::new().reset_all(app_data)?;
             }
             ConfirmAction::RestartMuxDaemon => {
                 if let Err(err) = Actions::new().restart_mux_daemon(app_data) {
-                    return Ok(ErrorModalMode {
-                        message: format!("Failed to restart mux daemon: {err}"),
-                    }
+                    return Ok(ErrorModalMode::new(TenexError::wrap(
+                        "Failed to restart mux daemon",
+                        &err,
+                    ))
                     .into());
                 }
             }
@@ -67,12 +84,19 @@ impl ValidIn<ConfirmingMode> for ConfirmYesAction {
                 app_data.should_quit = true;
             }
             ConfirmAction::Synthesize => {
-                return Ok(SynthesisPromptMode.into());
+                app_data.synthesis_options = SynthesisOptions {
+                    strategy: app_data.settings.synthesis_strategy,
+                    keep_children: false,
+                };
+                return Ok(SynthesisStrategyMode.into());
             }
             ConfirmAction::WorktreeConflict => {}
             ConfirmAction::SwitchBranch => {
                 return Actions::new().switch_branch(app_data);
             }
+            ConfirmAction::ResumeOverBudget => {
+                Actions::new().resume_over_budget(app_data);
+            }
         }
 
         Ok(AppMode::normal())
@@ -100,6 +124,8 @@ impl ValidIn<ConfirmingMode> for CancelAction {
     fn execute(self, state: ConfirmingMode, app_data: &mut AppData) -> Result<Self::NextState> {
         if state.action == ConfirmAction::WorktreeConflict {
             app_data.spawn.worktree_conflict = None;
+            app_data.spawn.ticket = None;
+            app_data.spawn.program = None;
             return Ok(AppMode::normal());
         }
         if state.action == ConfirmAction::InterruptAgent {
@@ -113,6 +139,45 @@ impl ValidIn<ConfirmingMode> for CancelAction {
     }
 }
 
+impl ValidIn<ConfirmingMode> for KillStopAction {
+    type NextState = AppMode;
+
+    fn execute(self, state: ConfirmingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        if state.action != ConfirmAction::Kill {
+            return Ok(state.into());
+        }
+        Actions::new().kill_agent(app_data, true)?;
+        Ok(AppMode::normal())
+    }
+}
+
+impl ValidIn<ConfirmingMode> for SynthesizeKeepAction {
+    type NextState = AppMode;
+
+    fn execute(self, state: ConfirmingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        if state.action != ConfirmAction::Synthesize {
+            return Ok(state.into());
+        }
+        app_data.synthesis_options = SynthesisOptions {
+            strategy: app_data.settings.synthesis_strategy,
+            keep_children: true,
+        };
+        Ok(SynthesisStrategyMode.into())
+    }
+}
+
+impl ValidIn<ConfirmingMode> for QuitStopAllAction {
+    type NextState = AppMode;
+
+    fn execute(self, state: ConfirmingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        if state.action != ConfirmAction::Quit {
+            return Ok(state.into());
+        }
+        Actions::new().stop_all_agents(app_data)?;
+        Ok(AppMode::normal())
+    }
+}
+
 impl ValidIn<ConfirmingMode> for WorktreeReconnectAction {
     type NextState = AppMode;
 
@@ -122,8 +187,9 @@ impl ValidIn<ConfirmingMode> for WorktreeReconnectAction {
         }
 
         if let Some(conflict) = app_data.spawn.worktree_conflict.as_ref() {
-            app_data.input.buffer = conflict.prompt.clone().unwrap_or_default();
-            app_data.input.cursor = app_data.input.buffer.len();
+            app_data
+                .input
+                .set(conflict.prompt.clone().unwrap_or_default());
         }
 
         Ok(ReconnectPromptMode.into())
@@ -1,5 +1,6 @@
 use crate::action::ValidIn;
 use crate::app::{Actions, AppData};
+use crate::error::TenexError;
 use crate::git;
 use crate::state::{
     AppMode, ConfirmPushForPRMode, ConfirmPushMode, ErrorModalMode, MergeBranchSelectorMode,
@@ -20,12 +21,7 @@ impl ValidIn<NormalMode> for PushAction {
             .selected_agent()
             .ok_or_else(|| anyhow::anyhow!("No agent selected"))?;
         if !agent.is_git_workspace() {
-            return Ok(ErrorModalMode {
-                message:
-                    "Push requires a git repository. Start Tenex in a git repo to use worktrees."
-                        .to_string(),
-            }
-            .into());
+            return Ok(ErrorModalMode::new(TenexError::NotGitRepository { action: "Push" }).into());
         }
 
         let agent_id = agent.id;
@@ -44,12 +40,7 @@ impl ValidIn<ScrollingMode> for PushAction {
             .selected_agent()
             .ok_or_else(|| anyhow::anyhow!("No agent selected"))?;
         if !agent.is_git_workspace() {
-            return Ok(ErrorModalMode {
-                message:
-                    "Push requires a git repository. Start Tenex in a git repo to use worktrees."
-                        .to_string(),
-            }
-            .into());
+            return Ok(ErrorModalMode::new(TenexError::NotGitRepository { action: "Push" }).into());
         }
 
         let agent_id = agent.id;
@@ -79,8 +70,7 @@ impl ValidIn<NormalMode> for RenameBranchAction {
         app_data
             .git_op
             .start_rename(agent_id, current_name.clone(), is_root);
-        app_data.input.buffer = current_name;
-        app_data.input.cursor = app_data.input.buffer.len();
+        app_data.input.set(current_name);
 
         Ok(RenameBranchMode.into())
     }
@@ -101,8 +91,7 @@ impl ValidIn<ScrollingMode> for RenameBranchAction {
         app_data
             .git_op
             .start_rename(agent_id, current_name.clone(), is_root);
-        app_data.input.buffer = current_name;
-        app_data.input.cursor = app_data.input.buffer.len();
+        app_data.input.set(current_name);
 
         Ok(RenameBranchMode.into())
     }
@@ -120,12 +109,9 @@ impl ValidIn<NormalMode> for OpenPRAction {
             .selected_agent()
             .ok_or_else(|| anyhow::anyhow!("No agent selected"))?;
         if !agent.is_git_workspace() {
-            return Ok(ErrorModalMode {
-                message:
-                    "Open PR requires a git repository. Start Tenex in a git repo to use worktrees."
-                        .to_string(),
-            }
-            .into());
+            return Ok(
+                ErrorModalMode::new(TenexError::NotGitRepository { action: "Open PR" }).into(),
+            );
         }
 
         let agent_id = agent.id;
@@ -156,12 +142,9 @@ impl ValidIn<ScrollingMode> for OpenPRAction {
             .selected_agent()
             .ok_or_else(|| anyhow::anyhow!("No agent selected"))?;
         if !agent.is_git_workspace() {
-            return Ok(ErrorModalMode {
-                message:
-                    "Open PR requires a git repository. Start Tenex in a git repo to use worktrees."
-                        .to_string(),
-            }
-            .into());
+            return Ok(
+                ErrorModalMode::new(TenexError::NotGitRepository { action: "Open PR" }).into(),
+            );
         }
 
         let agent_id = agent.id;
@@ -193,18 +176,14 @@ impl ValidIn<NormalMode> for RebaseAction {
 
     fn execute(self, _state: NormalMode, app_data: &mut AppData) -> Result<Self::NextState> {
         let Some(agent) = app_data.selected_agent() else {
-            return Ok(ErrorModalMode {
-                message: "No agent selected. Select an agent first to rebase.".to_string(),
-            }
-            .into());
+            return Ok(
+                ErrorModalMode::new(TenexError::NoAgentSelected { action: "rebase" }).into(),
+            );
         };
         if !agent.is_git_workspace() {
-            return Ok(ErrorModalMode {
-                message:
-                    "Rebase requires a git repository. Start Tenex in a git repo to use worktrees."
-                        .to_string(),
-            }
-            .into());
+            return Ok(
+                ErrorModalMode::new(TenexError::NotGitRepository { action: "Rebase" }).into(),
+            );
         }
 
         let agent_id = agent.id;
@@ -227,18 +206,14 @@ impl ValidIn<ScrollingMode> for RebaseAction {
 
     fn execute(self, _state: ScrollingMode, app_data: &mut AppData) -> Result<Self::NextState> {
         let Some(agent) = app_data.selected_agent() else {
-            return Ok(ErrorModalMode {
-                message: "No agent selected. Select an agent first to rebase.".to_string(),
-            }
-            .into());
+            return Ok(
+                ErrorModalMode::new(TenexError::NoAgentSelected { action: "rebase" }).into(),
+            );
         };
         if !agent.is_git_workspace() {
-            return Ok(ErrorModalMode {
-                message:
-                    "Rebase requires a git repository. Start Tenex in a git repo to use worktrees."
-                        .to_string(),
-            }
-            .into());
+            return Ok(
+                ErrorModalMode::new(TenexError::NotGitRepository { action: "Rebase" }).into(),
+            );
         }
 
         let agent_id = agent.id;
@@ -265,18 +240,12 @@ impl ValidIn<NormalMode> for MergeAction {
 
     fn execute(self, _state: NormalMode, app_data: &mut AppData) -> Result<Self::NextState> {
         let Some(agent) = app_data.selected_agent() else {
-            return Ok(ErrorModalMode {
-                message: "No agent selected. Select an agent first to merge.".to_string(),
-            }
-            .into());
+            return Ok(ErrorModalMode::new(TenexError::NoAgentSelected { action: "merge" }).into());
         };
         if !agent.is_git_workspace() {
-            return Ok(ErrorModalMode {
-                message:
-                    "Merge requires a git repository. Start Tenex in a git repo to use worktrees."
-                        .to_string(),
-            }
-            .into());
+            return Ok(
+                ErrorModalMode::new(TenexError::NotGitRepository { action: "Merge" }).into(),
+            );
         }
 
         let agent_id = agent.id;
@@ -299,18 +268,12 @@ impl ValidIn<ScrollingMode> for MergeAction {
 
     fn execute(self, _state: ScrollingMode, app_data: &mut AppData) -> Result<Self::NextState> {
         let Some(agent) = app_data.selected_agent() else {
-            return Ok(ErrorModalMode {
-                message: "No agent selected. Select an agent first to merge.".to_string(),
-            }
-            .into());
+            return Ok(ErrorModalMode::new(TenexError::NoAgentSelected { action: "merge" }).into());
         };
         if !agent.is_git_workspace() {
-            return Ok(ErrorModalMode {
-                message:
-                    "Merge requires a git repository. Start Tenex in a git repo to use worktrees."
-                        .to_string(),
-            }
-            .into());
+            return Ok(
+                ErrorModalMode::new(TenexError::NotGitRepository { action: "Merge" }).into(),
+            );
         }
 
         let agent_id = agent.id;
@@ -337,16 +300,15 @@ impl ValidIn<NormalMode> for SwitchBranchAction {
 
     fn execute(self, _state: NormalMode, app_data: &mut AppData) -> Result<Self::NextState> {
         let Some(agent) = app_data.selected_agent() else {
-            return Ok(ErrorModalMode {
-                message: "No agent selected. Select an agent first to switch branches.".to_string(),
-            }
+            return Ok(ErrorModalMode::new(TenexError::NoAgentSelected {
+                action: "switch branches",
+            })
             .into());
         };
         if !agent.is_git_workspace() {
-            return Ok(ErrorModalMode {
-                message: "Switch branch requires a git repository. Start Tenex in a git repo to use worktrees."
-                    .to_string(),
-            }
+            return Ok(ErrorModalMode::new(TenexError::NotGitRepository {
+                action: "Switch branch",
+            })
             .into());
         }
 
@@ -371,16 +333,15 @@ impl ValidIn<ScrollingMode> for SwitchBranchAction {
 
     fn execute(self, _state: ScrollingMode, app_data: &mut AppData) -> Result<Self::NextState> {
         let Some(agent) = app_data.selected_agent() else {
-            return Ok(ErrorModalMode {
-                message: "No agent selected. Select an agent first to switch branches.".to_string(),
-            }
+            return Ok(ErrorModalMode::new(TenexError::NoAgentSelected {
+                action: "switch branches",
+            })
             .into());
         };
         if !agent.is_git_workspace() {
-            return Ok(ErrorModalMode {
-                message: "Switch branch requires a git repository. Start Tenex in a git repo to use worktrees."
-                    .to_string(),
-            }
+            return Ok(ErrorModalMode::new(TenexError::NotGitRepository {
+                action: "Switch branch",
+            })
             .into());
         }
 
@@ -399,3 +360,23 @@ impl ValidIn<ScrollingMode> for SwitchBranchAction {
         Ok(SwitchBranchSelectorMode.into())
     }
 }
+
+/// Normal-mode action: bring the selected agent's branch up to date with its base branch.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UpdateFromBaseAction;
+
+impl ValidIn<NormalMode> for UpdateFromBaseAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: NormalMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Actions::update_from_base(app_data)
+    }
+}
+
+impl ValidIn<ScrollingMode> for UpdateFromBaseAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ScrollingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Actions::update_from_base(app_data)
+    }
+}
@@ -83,7 +83,7 @@ impl ValidIn<ScrollingMode> for CommandPaletteAction {
     }
 }
 
-/// Normal-mode action: cancel/escape (no-op in normal; clears input state).
+/// Normal-mode action: cancel/escape (cancels the latest in-flight git operation; clears input state).
 #[derive(Debug, Clone, Copy, Default)]
 pub struct CancelAction;
 
@@ -93,6 +93,17 @@ impl ValidIn<NormalMode> for CancelAction {
     fn execute(self, _state: NormalMode, app_data: &mut AppData) -> Result<Self::NextState> {
         // Match legacy behavior (Esc in Normal clears any leftover input state).
         app_data.input.clear();
+
+        if let Some(label) = app_data.background_ops.cancel_latest() {
+            let message = format!("Cancelling: {label}");
+            app_data.set_status(message);
+        } else if let Some(op) = app_data.background_ops.in_flight().last()
+            && !op.is_cancelling()
+        {
+            let message = format!("{} can't be cancelled safely", op.label);
+            app_data.set_status(message);
+        }
+
         Ok(AppMode::normal())
     }
 }
@@ -25,17 +25,21 @@ pub use picker::*;
 pub use preview::{ForwardKeystrokeAction, UnfocusPreviewAction};
 pub use text_input::*;
 
-use crate::app::{App, AppData};
+use crate::app::{Actions, App, AppData, PromptReply};
 use crate::config::Action as KeyAction;
 use crate::state::{
-    AppMode, BranchSelectorMode, BroadcastingMode, ChildCountMode, ChildPromptMode,
-    CommandPaletteMode, ConfirmAction, ConfirmPushForPRMode, ConfirmPushMode, ConfirmingMode,
-    CreatingMode, CustomAgentCommandMode, DiffFocusedMode, ErrorModalMode, HelpMode,
-    KeyboardRemapPromptMode, MergeBranchSelectorMode, ModelSelectorMode, NormalMode,
-    PreviewFocusedMode, PromptingMode, RebaseBranchSelectorMode, ReconnectPromptMode,
-    RenameBranchMode, ReviewChildCountMode, ReviewInfoMode, ScrollingMode, SettingsMenuMode,
-    SuccessModalMode, SwitchBranchSelectorMode, SynthesisPromptMode, TerminalPromptMode,
-    UpdatePromptMode,
+    AddRepoMode, AppMode, AutoConnectPickerMode, BranchSelectorMode, BroadcastingMode,
+    CannedResponsesMode, ChildCountMode, ChildPromptMode, CommandPaletteMode, ConfirmAction,
+    ConfirmPushForPRMode, ConfirmPushMode, ConfirmingMode, ContextFilePickerMode, CreatingMode,
+    CustomAgentCommandMode, DependencyMessageMode, DependencyPickerMode, DiffFocusedMode,
+    EditScratchpadMode, EditTaskMode, ErrorModalMode, GitAuthFailedMode, HelpMode, InboxMode,
+    IssuePickerMode, KeyboardRemapPromptMode, MergeBranchSelectorMode, ModelSelectorMode,
+    NormalMode, PrePushFailedMode, PreviewFocusedMode, PromptingMode, RebaseBranchSelectorMode,
+    ReconnectPromptMode, RecoverPickerMode, RenameBranchMode, ReplyMode, RepoPickerMode,
+    ReviewChildCountMode, ReviewFocusMode, ReviewInfoMode, ScopeMode, ScrollingMode, SendToMode,
+    SettingsMenuMode, SuccessModalMode, SwitchBranchSelectorMode, SwitchModelMode,
+    SynthesisBrowserMode, SynthesisPromptMode, SynthesisStrategyMode, TerminalPromptMode,
+    TmuxImportPickerMode, UpdatePromptMode,
 };
 use crate::update::UpdateInfo;
 use anyhow::Result;
@@ -65,13 +69,25 @@ pub trait ValidIn<State>: Sized {
 ///
 /// Returns an error if the dispatched action fails.
 pub fn dispatch_normal_mode(app: &mut App, action: KeyAction) -> Result<()> {
+    crate::metrics::record_action(&format!("{action:?}"));
     let app_data = &mut app.data;
     let next = match action {
         KeyAction::NewAgent => NewAgentAction.execute(NormalMode, app_data),
         KeyAction::NewAgentWithPrompt => NewAgentWithPromptAction.execute(NormalMode, app_data),
+        KeyAction::NewAgentFromIssue => NewAgentFromIssueAction.execute(NormalMode, app_data),
         KeyAction::Help => HelpAction.execute(NormalMode, app_data),
         KeyAction::Quit => QuitAction.execute(NormalMode, app_data),
         KeyAction::Kill => KillAction.execute(NormalMode, app_data),
+        KeyAction::RecoverBranch => RecoverBranchAction.execute(NormalMode, app_data),
+        KeyAction::AgentInfo => AgentInfoAction.execute(NormalMode, app_data),
+        KeyAction::ShowPrompt => ShowPromptAction.execute(NormalMode, app_data),
+        KeyAction::EditTask => EditTaskAction.execute(NormalMode, app_data),
+        KeyAction::SendTo => SendToAction.execute(NormalMode, app_data),
+        KeyAction::Inbox => InboxAction.execute(NormalMode, app_data),
+        KeyAction::Reply => ReplyAction.execute(NormalMode, app_data),
+        KeyAction::CannedResponses => CannedResponsesAction.execute(NormalMode, app_data),
+        KeyAction::Compact => CompactAction.execute(NormalMode, app_data),
+        KeyAction::SwitchModel => SwitchModelAction.execute(NormalMode, app_data),
         KeyAction::SwitchTab => SwitchTabAction.execute(NormalMode, app_data),
         KeyAction::NextAgent => NextAgentAction.execute(NormalMode, app_data),
         KeyAction::PrevAgent => PrevAgentAction.execute(NormalMode, app_data),
@@ -102,6 +118,7 @@ pub fn dispatch_normal_mode(app: &mut App, action: KeyAction) -> Result<()> {
         KeyAction::Rebase => RebaseAction.execute(NormalMode, app_data),
         KeyAction::Merge => MergeAction.execute(NormalMode, app_data),
         KeyAction::SwitchBranch => SwitchBranchAction.execute(NormalMode, app_data),
+        KeyAction::UpdateFromBase => UpdateFromBaseAction.execute(NormalMode, app_data),
         KeyAction::CommandPalette => CommandPaletteAction.execute(NormalMode, app_data),
         KeyAction::Cancel => CancelAction.execute(NormalMode, app_data),
 
@@ -127,13 +144,25 @@ pub fn dispatch_normal_mode(app: &mut App, action: KeyAction) -> Result<()> {
 ///
 /// Returns an error if the dispatched action fails.
 pub fn dispatch_scrolling_mode(app: &mut App, action: KeyAction) -> Result<()> {
+    crate::metrics::record_action(&format!("{action:?}"));
     let app_data = &mut app.data;
     let next = match action {
         KeyAction::NewAgent => NewAgentAction.execute(ScrollingMode, app_data),
         KeyAction::NewAgentWithPrompt => NewAgentWithPromptAction.execute(ScrollingMode, app_data),
+        KeyAction::NewAgentFromIssue => NewAgentFromIssueAction.execute(ScrollingMode, app_data),
         KeyAction::Help => HelpAction.execute(ScrollingMode, app_data),
         KeyAction::Quit => QuitAction.execute(ScrollingMode, app_data),
         KeyAction::Kill => KillAction.execute(ScrollingMode, app_data),
+        KeyAction::RecoverBranch => RecoverBranchAction.execute(ScrollingMode, app_data),
+        KeyAction::AgentInfo => AgentInfoAction.execute(ScrollingMode, app_data),
+        KeyAction::ShowPrompt => ShowPromptAction.execute(ScrollingMode, app_data),
+        KeyAction::EditTask => EditTaskAction.execute(ScrollingMode, app_data),
+        KeyAction::SendTo => SendToAction.execute(ScrollingMode, app_data),
+        KeyAction::Inbox => InboxAction.execute(ScrollingMode, app_data),
+        KeyAction::Reply => ReplyAction.execute(ScrollingMode, app_data),
+        KeyAction::CannedResponses => CannedResponsesAction.execute(ScrollingMode, app_data),
+        KeyAction::Compact => CompactAction.execute(ScrollingMode, app_data),
+        KeyAction::SwitchModel => SwitchModelAction.execute(ScrollingMode, app_data),
         KeyAction::SwitchTab => SwitchTabAction.execute(ScrollingMode, app_data),
         KeyAction::NextAgent => NextAgentAction.execute(ScrollingMode, app_data),
         KeyAction::PrevAgent => PrevAgentAction.execute(ScrollingMode, app_data),
@@ -168,6 +197,7 @@ pub fn dispatch_scrolling_mode(app: &mut App, action: KeyAction) -> Result<()> {
         KeyAction::Rebase => RebaseAction.execute(ScrollingMode, app_data),
         KeyAction::Merge => MergeAction.execute(ScrollingMode, app_data),
         KeyAction::SwitchBranch => SwitchBranchAction.execute(ScrollingMode, app_data),
+        KeyAction::UpdateFromBase => UpdateFromBaseAction.execute(ScrollingMode, app_data),
         KeyAction::CommandPalette => CommandPaletteAction.execute(ScrollingMode, app_data),
         KeyAction::Cancel => CancelAction.execute(ScrollingMode, app_data),
 
@@ -288,6 +318,18 @@ pub fn dispatch_changelog_mode(
         (KeyCode::Char('G') | KeyCode::End, _) => {
             app.data.ui.changelog_scroll = max_scroll;
         }
+        (KeyCode::Char('n'), _) => {
+            if let AppMode::Changelog(state) = &app.mode {
+                let next = Actions::cycle_agent_notifications(&mut app.data, state.clone());
+                app.apply_mode(next);
+            }
+        }
+        (KeyCode::Char('r'), _) => {
+            if let AppMode::Changelog(state) = &app.mode {
+                let next = Actions::cycle_agent_restart(&mut app.data, state.clone());
+                app.apply_mode(next);
+            }
+        }
         (KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q' | 'Q'), _) => {
             if let Some(version) = mark_seen_version
                 && let Err(e) = app.data.settings.set_last_seen_version(&version)
@@ -306,9 +348,38 @@ pub fn dispatch_changelog_mode(
 /// # Errors
 ///
 /// Returns an error if executing the dispatched action fails.
-pub fn dispatch_error_modal_mode(app: &mut App, message: String) -> Result<()> {
-    let next = DismissAction.execute(ErrorModalMode { message }, &mut app.data)?;
-    app.apply_mode(next);
+pub fn dispatch_error_modal_mode(
+    app: &mut App,
+    state: ErrorModalMode,
+    code: KeyCode,
+) -> Result<()> {
+    let ui = &mut app.data.ui;
+    match code {
+        KeyCode::Char('d' | 'D') => {
+            ui.error_details_expanded = !ui.error_details_expanded;
+            ui.error_details_scroll = 0;
+        }
+        KeyCode::Char('c' | 'C') => {
+            ui.pending_clipboard = Some(ui.error_report(&state.error.describe()));
+            app.data.set_status("Copied the error to the clipboard");
+        }
+        KeyCode::Up | KeyCode::Char('k') if ui.error_details_expanded => {
+            ui.error_details_scroll = ui.error_details_scroll.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j') if ui.error_details_expanded => {
+            ui.error_details_scroll = ui.error_details_scroll.saturating_add(1);
+        }
+        KeyCode::PageUp if ui.error_details_expanded => {
+            ui.error_details_scroll = ui.error_details_scroll.saturating_sub(10);
+        }
+        KeyCode::PageDown if ui.error_details_expanded => {
+            ui.error_details_scroll = ui.error_details_scroll.saturating_add(10);
+        }
+        _ => {
+            let next = DismissAction.execute(state, &mut app.data)?;
+            app.apply_mode(next);
+        }
+    }
     Ok(())
 }
 
@@ -444,6 +515,14 @@ pub fn dispatch_diff_focused_mode(
 ///
 /// Returns an error if the dispatched action fails.
 pub fn dispatch_creating_mode(app: &mut App, code: KeyCode, modifiers: KeyModifiers) -> Result<()> {
+    if app.data.editing_new_agent_command() {
+        return dispatch_program_field(app, CreatingMode, code, modifiers);
+    }
+    if is_edit_program_key(code, modifiers) {
+        let next = EditProgramAction.execute(CreatingMode, &mut app.data)?;
+        app.apply_mode(next);
+        return Ok(());
+    }
     dispatch_text_input_mode(app, CreatingMode, code, modifiers)
 }
 
@@ -457,9 +536,60 @@ pub fn dispatch_prompting_mode(
     code: KeyCode,
     modifiers: KeyModifiers,
 ) -> Result<()> {
+    if app.data.editing_new_agent_command() {
+        return dispatch_program_field(app, PromptingMode, code, modifiers);
+    }
+    if is_attach_context_key(code, modifiers) {
+        let next = AttachContextFileAction.execute(PromptingMode, &mut app.data)?;
+        app.apply_mode(next);
+        return Ok(());
+    }
+    if is_edit_program_key(code, modifiers) {
+        let next = EditProgramAction.execute(PromptingMode, &mut app.data)?;
+        app.apply_mode(next);
+        return Ok(());
+    }
     dispatch_text_input_mode(app, PromptingMode, code, modifiers)
 }
 
+/// Dispatch a raw key event while the command field of a new-agent input is open: `Enter`
+/// and `Ctrl+P` keep the command, `Esc` discards it, and other keys edit it.
+fn dispatch_program_field<State>(
+    app: &mut App,
+    state: State,
+    code: KeyCode,
+    modifiers: KeyModifiers,
+) -> Result<()>
+where
+    State: Copy,
+    AppMode: From<State>,
+    FinishProgramAction: ValidIn<State, NextState = AppMode>,
+    CancelAction: ValidIn<State, NextState = AppMode>,
+    SubmitAction: ValidIn<State, NextState = AppMode>,
+    CharInputAction: ValidIn<State, NextState = AppMode>,
+    BackspaceAction: ValidIn<State, NextState = AppMode>,
+    DeleteAction: ValidIn<State, NextState = AppMode>,
+    CursorLeftAction: ValidIn<State, NextState = AppMode>,
+    CursorRightAction: ValidIn<State, NextState = AppMode>,
+    CursorUpAction: ValidIn<State, NextState = AppMode>,
+    CursorDownAction: ValidIn<State, NextState = AppMode>,
+    CursorHomeAction: ValidIn<State, NextState = AppMode>,
+    CursorEndAction: ValidIn<State, NextState = AppMode>,
+    ClearLineAction: ValidIn<State, NextState = AppMode>,
+    DeleteWordAction: ValidIn<State, NextState = AppMode>,
+    LineEditAction: ValidIn<State, NextState = AppMode>,
+{
+    let keep = match code {
+        KeyCode::Enter if !modifiers.contains(KeyModifiers::ALT) => true,
+        KeyCode::Esc => false,
+        _ if is_edit_program_key(code, modifiers) => true,
+        _ => return dispatch_text_input_mode(app, state, code, modifiers),
+    };
+    let next = FinishProgramAction(keep).execute(state, &mut app.data)?;
+    app.apply_mode(next);
+    Ok(())
+}
+
 /// Dispatch a raw key event while in `ChildPromptMode`, using typed actions.
 ///
 /// # Errors
@@ -470,9 +600,24 @@ pub fn dispatch_child_prompt_mode(
     code: KeyCode,
     modifiers: KeyModifiers,
 ) -> Result<()> {
+    if is_attach_context_key(code, modifiers) {
+        let next = AttachContextFileAction.execute(ChildPromptMode, &mut app.data)?;
+        app.apply_mode(next);
+        return Ok(());
+    }
     dispatch_text_input_mode(app, ChildPromptMode, code, modifiers)
 }
 
+/// `Ctrl+O` opens the context file picker from the new-agent prompts.
+const fn is_attach_context_key(code: KeyCode, modifiers: KeyModifiers) -> bool {
+    matches!(code, KeyCode::Char('o' | 'O')) && modifiers.contains(KeyModifiers::CONTROL)
+}
+
+/// `Ctrl+P` opens and closes the command field of the new-agent inputs.
+const fn is_edit_program_key(code: KeyCode, modifiers: KeyModifiers) -> bool {
+    matches!(code, KeyCode::Char('p' | 'P')) && modifiers.contains(KeyModifiers::CONTROL)
+}
+
 /// Dispatch a raw key event while in `BroadcastingMode`, using typed actions.
 ///
 /// # Errors
@@ -538,6 +683,85 @@ pub fn dispatch_synthesis_prompt_mode(
     dispatch_text_input_mode(app, SynthesisPromptMode, code, modifiers)
 }
 
+/// Dispatch a raw key event while in `EditTaskMode`, using typed actions.
+///
+/// # Errors
+///
+/// Returns an error if the dispatched action fails.
+pub fn dispatch_edit_task_mode(
+    app: &mut App,
+    code: KeyCode,
+    modifiers: KeyModifiers,
+) -> Result<()> {
+    dispatch_text_input_mode(app, EditTaskMode, code, modifiers)
+}
+
+/// Dispatch a raw key event while in `EditScratchpadMode`, using typed actions.
+///
+/// # Errors
+///
+/// Returns an error if the dispatched action fails.
+pub fn dispatch_edit_scratchpad_mode(
+    app: &mut App,
+    code: KeyCode,
+    modifiers: KeyModifiers,
+) -> Result<()> {
+    dispatch_text_input_mode(app, EditScratchpadMode, code, modifiers)
+}
+
+/// Dispatch a raw key event while in `AddRepoMode`, using typed actions.
+///
+/// # Errors
+///
+/// Returns an error if the dispatched action fails.
+pub fn dispatch_add_repo_mode(app: &mut App, code: KeyCode, modifiers: KeyModifiers) -> Result<()> {
+    dispatch_text_input_mode(app, AddRepoMode, code, modifiers)
+}
+
+/// Dispatch a raw key event while in `ScopeMode`, using typed actions.
+///
+/// # Errors
+///
+/// Returns an error if the dispatched action fails.
+pub fn dispatch_scope_mode(app: &mut App, code: KeyCode, modifiers: KeyModifiers) -> Result<()> {
+    dispatch_text_input_mode(app, ScopeMode, code, modifiers)
+}
+
+/// Dispatch a raw key event while in `SwitchModelMode`, using typed actions.
+///
+/// # Errors
+///
+/// Returns an error if the dispatched action fails.
+pub fn dispatch_switch_model_mode(
+    app: &mut App,
+    code: KeyCode,
+    modifiers: KeyModifiers,
+) -> Result<()> {
+    dispatch_text_input_mode(app, SwitchModelMode, code, modifiers)
+}
+
+/// Dispatch a raw key event while in `ReplyMode`, using typed actions.
+///
+/// # Errors
+///
+/// Returns an error if the dispatched action fails.
+pub fn dispatch_reply_mode(app: &mut App, code: KeyCode, modifiers: KeyModifiers) -> Result<()> {
+    dispatch_text_input_mode(app, ReplyMode, code, modifiers)
+}
+
+/// Dispatch a raw key event while in `DependencyMessageMode`, using typed actions.
+///
+/// # Errors
+///
+/// Returns an error if the dispatched action fails.
+pub fn dispatch_dependency_message_mode(
+    app: &mut App,
+    code: KeyCode,
+    modifiers: KeyModifiers,
+) -> Result<()> {
+    dispatch_text_input_mode(app, DependencyMessageMode, code, modifiers)
+}
+
 /// Dispatch a raw key event while in `ChildCountMode`, using typed actions.
 ///
 /// # Errors
@@ -580,6 +804,31 @@ pub fn dispatch_review_child_count_mode(app: &mut App, code: KeyCode) -> Result<
     Ok(())
 }
 
+/// Dispatch a raw key event while in `ReviewFocusMode`, using typed actions.
+///
+/// # Errors
+///
+/// Returns an error if the dispatched action fails.
+pub fn dispatch_review_focus_mode(app: &mut App, code: KeyCode) -> Result<()> {
+    let next = {
+        let app_data = &mut app.data;
+        match code {
+            KeyCode::Enter => SelectAction.execute(ReviewFocusMode, app_data),
+            KeyCode::Esc => CancelAction.execute(ReviewFocusMode, app_data),
+            KeyCode::Up => NavigateUpAction.execute(ReviewFocusMode, app_data),
+            KeyCode::Down => NavigateDownAction.execute(ReviewFocusMode, app_data),
+            KeyCode::Right | KeyCode::Char(' ') => {
+                IncrementAction.execute(ReviewFocusMode, app_data)
+            }
+            KeyCode::Left => DecrementAction.execute(ReviewFocusMode, app_data),
+            _ => Ok(ReviewFocusMode.into()),
+        }?
+    };
+
+    app.apply_mode(next);
+    Ok(())
+}
+
 /// Dispatch a raw key event while in `ReviewInfoMode`, using typed actions.
 ///
 /// # Errors
@@ -706,6 +955,278 @@ pub fn dispatch_model_selector_mode(app: &mut App, code: KeyCode) -> Result<()>
     Ok(())
 }
 
+/// Dispatch a raw key event while in `ContextFilePickerMode`, using typed actions.
+///
+/// # Errors
+///
+/// Returns an error if the dispatched action fails.
+pub fn dispatch_context_file_picker_mode(app: &mut App, code: KeyCode) -> Result<()> {
+    let next = {
+        let app_data = &mut app.data;
+        match code {
+            KeyCode::Esc => CancelAction.execute(ContextFilePickerMode, app_data),
+            KeyCode::Enter => SelectAction.execute(ContextFilePickerMode, app_data),
+            KeyCode::Up => NavigateUpAction.execute(ContextFilePickerMode, app_data),
+            KeyCode::Down => NavigateDownAction.execute(ContextFilePickerMode, app_data),
+            KeyCode::Char(c) => CharInputAction(c).execute(ContextFilePickerMode, app_data),
+            KeyCode::Backspace => BackspaceAction.execute(ContextFilePickerMode, app_data),
+            _ => Ok(ContextFilePickerMode.into()),
+        }?
+    };
+
+    app.apply_mode(next);
+    Ok(())
+}
+
+/// Dispatch a raw key event while in `IssuePickerMode`, using typed actions.
+///
+/// # Errors
+///
+/// Returns an error if the dispatched action fails.
+pub fn dispatch_issue_picker_mode(app: &mut App, code: KeyCode) -> Result<()> {
+    let next = {
+        let app_data = &mut app.data;
+        match code {
+            KeyCode::Esc => CancelAction.execute(IssuePickerMode, app_data),
+            KeyCode::Enter => SelectAction.execute(IssuePickerMode, app_data),
+            KeyCode::Up => NavigateUpAction.execute(IssuePickerMode, app_data),
+            KeyCode::Down => NavigateDownAction.execute(IssuePickerMode, app_data),
+            KeyCode::Char(c) => CharInputAction(c).execute(IssuePickerMode, app_data),
+            KeyCode::Backspace => BackspaceAction.execute(IssuePickerMode, app_data),
+            _ => Ok(IssuePickerMode.into()),
+        }?
+    };
+
+    app.apply_mode(next);
+    Ok(())
+}
+
+/// Dispatch a raw key event while in `AutoConnectPickerMode`, using typed actions.
+///
+/// # Errors
+///
+/// Returns an error if the dispatched action fails.
+pub fn dispatch_auto_connect_picker_mode(app: &mut App, code: KeyCode) -> Result<()> {
+    let next = {
+        let app_data = &mut app.data;
+        match code {
+            KeyCode::Esc => CancelAction.execute(AutoConnectPickerMode, app_data),
+            KeyCode::Enter => SelectAction.execute(AutoConnectPickerMode, app_data),
+            KeyCode::Up => NavigateUpAction.execute(AutoConnectPickerMode, app_data),
+            KeyCode::Down => NavigateDownAction.execute(AutoConnectPickerMode, app_data),
+            KeyCode::Char(c) => CharInputAction(c).execute(AutoConnectPickerMode, app_data),
+            _ => Ok(AutoConnectPickerMode.into()),
+        }?
+    };
+
+    app.apply_mode(next);
+    Ok(())
+}
+
+/// Dispatch a raw key event while in `TmuxImportPickerMode`, using typed actions.
+///
+/// # Errors
+///
+/// Returns an error if the dispatched action fails.
+pub fn dispatch_tmux_import_picker_mode(app: &mut App, code: KeyCode) -> Result<()> {
+    let next = {
+        let app_data = &mut app.data;
+        match code {
+            KeyCode::Esc => CancelAction.execute(TmuxImportPickerMode, app_data),
+            KeyCode::Enter => SelectAction.execute(TmuxImportPickerMode, app_data),
+            KeyCode::Up => NavigateUpAction.execute(TmuxImportPickerMode, app_data),
+            KeyCode::Down => NavigateDownAction.execute(TmuxImportPickerMode, app_data),
+            KeyCode::Char(c) => CharInputAction(c).execute(TmuxImportPickerMode, app_data),
+            _ => Ok(TmuxImportPickerMode.into()),
+        }?
+    };
+
+    app.apply_mode(next);
+    Ok(())
+}
+
+/// Dispatch a raw key event while in `RecoverPickerMode`, using typed actions.
+///
+/// # Errors
+///
+/// Returns an error if the dispatched action fails.
+pub fn dispatch_recover_picker_mode(app: &mut App, code: KeyCode) -> Result<()> {
+    let next = {
+        let app_data = &mut app.data;
+        match code {
+            KeyCode::Esc => CancelAction.execute(RecoverPickerMode, app_data),
+            KeyCode::Enter => SelectAction.execute(RecoverPickerMode, app_data),
+            KeyCode::Up => NavigateUpAction.execute(RecoverPickerMode, app_data),
+            KeyCode::Down => NavigateDownAction.execute(RecoverPickerMode, app_data),
+            KeyCode::Char(c) => CharInputAction(c).execute(RecoverPickerMode, app_data),
+            KeyCode::Backspace => BackspaceAction.execute(RecoverPickerMode, app_data),
+            _ => Ok(RecoverPickerMode.into()),
+        }?
+    };
+
+    app.apply_mode(next);
+    Ok(())
+}
+
+/// Dispatch a raw key event while in `SynthesisBrowserMode`, using typed actions.
+///
+/// # Errors
+///
+/// Returns an error if the dispatched action fails.
+pub fn dispatch_synthesis_browser_mode(app: &mut App, code: KeyCode) -> Result<()> {
+    let next = {
+        let app_data = &mut app.data;
+        match code {
+            KeyCode::Esc => CancelAction.execute(SynthesisBrowserMode, app_data),
+            KeyCode::Enter => SelectAction.execute(SynthesisBrowserMode, app_data),
+            KeyCode::Up => NavigateUpAction.execute(SynthesisBrowserMode, app_data),
+            KeyCode::Down => NavigateDownAction.execute(SynthesisBrowserMode, app_data),
+            KeyCode::Char(c) => CharInputAction(c).execute(SynthesisBrowserMode, app_data),
+            _ => Ok(SynthesisBrowserMode.into()),
+        }?
+    };
+
+    app.apply_mode(next);
+    Ok(())
+}
+
+/// Dispatch a raw key event while in `SendToMode`, using typed actions.
+///
+/// # Errors
+///
+/// Returns an error if the dispatched action fails.
+pub fn dispatch_send_to_mode(app: &mut App, code: KeyCode) -> Result<()> {
+    let next = {
+        let app_data = &mut app.data;
+        match code {
+            KeyCode::Esc => CancelAction.execute(SendToMode, app_data),
+            KeyCode::Enter => SelectAction.execute(SendToMode, app_data),
+            KeyCode::Up => NavigateUpAction.execute(SendToMode, app_data),
+            KeyCode::Down => NavigateDownAction.execute(SendToMode, app_data),
+            KeyCode::Char(c) => CharInputAction(c).execute(SendToMode, app_data),
+            KeyCode::Backspace => BackspaceAction.execute(SendToMode, app_data),
+            _ => Ok(SendToMode.into()),
+        }?
+    };
+
+    app.apply_mode(next);
+    Ok(())
+}
+
+/// Dispatch a raw key event while in `InboxMode`, using typed actions.
+///
+/// # Errors
+///
+/// Returns an error if the dispatched action fails.
+pub fn dispatch_inbox_mode(app: &mut App, code: KeyCode) -> Result<()> {
+    let next = {
+        let app_data = &mut app.data;
+        match code {
+            KeyCode::Esc => CancelAction.execute(InboxMode, app_data),
+            KeyCode::Enter => SelectAction.execute(InboxMode, app_data),
+            KeyCode::Up => NavigateUpAction.execute(InboxMode, app_data),
+            KeyCode::Down => NavigateDownAction.execute(InboxMode, app_data),
+            KeyCode::Char(c) => CharInputAction(c).execute(InboxMode, app_data),
+            _ => Ok(InboxMode.into()),
+        }?
+    };
+
+    app.apply_mode(next);
+    Ok(())
+}
+
+/// Dispatch a raw key event while in `CannedResponsesMode`, using typed actions.
+///
+/// # Errors
+///
+/// Returns an error if the dispatched action fails.
+pub fn dispatch_canned_responses_mode(app: &mut App, code: KeyCode) -> Result<()> {
+    let next = {
+        let app_data = &mut app.data;
+        match code {
+            KeyCode::Esc => CancelAction.execute(CannedResponsesMode, app_data),
+            KeyCode::Enter => SelectAction.execute(CannedResponsesMode, app_data),
+            KeyCode::Up => NavigateUpAction.execute(CannedResponsesMode, app_data),
+            KeyCode::Down => NavigateDownAction.execute(CannedResponsesMode, app_data),
+            KeyCode::Left => DecrementAction.execute(CannedResponsesMode, app_data),
+            KeyCode::Right => IncrementAction.execute(CannedResponsesMode, app_data),
+            KeyCode::Char(c) => CharInputAction(c).execute(CannedResponsesMode, app_data),
+            _ => Ok(CannedResponsesMode.into()),
+        }?
+    };
+
+    app.apply_mode(next);
+    Ok(())
+}
+
+/// Dispatch a raw key event while in `RepoPickerMode`, using typed actions.
+///
+/// # Errors
+///
+/// Returns an error if the dispatched action fails.
+pub fn dispatch_repo_picker_mode(app: &mut App, code: KeyCode) -> Result<()> {
+    let next = {
+        let app_data = &mut app.data;
+        match code {
+            KeyCode::Esc => CancelAction.execute(RepoPickerMode, app_data),
+            KeyCode::Enter => SelectAction.execute(RepoPickerMode, app_data),
+            KeyCode::Up => NavigateUpAction.execute(RepoPickerMode, app_data),
+            KeyCode::Down => NavigateDownAction.execute(RepoPickerMode, app_data),
+            KeyCode::Char(c) => CharInputAction(c).execute(RepoPickerMode, app_data),
+            _ => Ok(RepoPickerMode.into()),
+        }?
+    };
+
+    app.apply_mode(next);
+    Ok(())
+}
+
+/// Dispatch a raw key event while in `DependencyPickerMode`, using typed actions.
+///
+/// # Errors
+///
+/// Returns an error if the dispatched action fails.
+pub fn dispatch_dependency_picker_mode(app: &mut App, code: KeyCode) -> Result<()> {
+    let next = {
+        let app_data = &mut app.data;
+        match code {
+            KeyCode::Esc => CancelAction.execute(DependencyPickerMode, app_data),
+            KeyCode::Enter => SelectAction.execute(DependencyPickerMode, app_data),
+            KeyCode::Up => NavigateUpAction.execute(DependencyPickerMode, app_data),
+            KeyCode::Down => NavigateDownAction.execute(DependencyPickerMode, app_data),
+            KeyCode::Left => DecrementAction.execute(DependencyPickerMode, app_data),
+            KeyCode::Right => IncrementAction.execute(DependencyPickerMode, app_data),
+            KeyCode::Char(c) => CharInputAction(c).execute(DependencyPickerMode, app_data),
+            KeyCode::Backspace => BackspaceAction.execute(DependencyPickerMode, app_data),
+            _ => Ok(DependencyPickerMode.into()),
+        }?
+    };
+
+    app.apply_mode(next);
+    Ok(())
+}
+
+/// Dispatch a raw key event while in `SynthesisStrategyMode`, using typed actions.
+///
+/// # Errors
+///
+/// Returns an error if the dispatched action fails.
+pub fn dispatch_synthesis_strategy_mode(app: &mut App, code: KeyCode) -> Result<()> {
+    let next = {
+        let app_data = &mut app.data;
+        match code {
+            KeyCode::Esc => CancelAction.execute(SynthesisStrategyMode, app_data),
+            KeyCode::Enter => SelectAction.execute(SynthesisStrategyMode, app_data),
+            KeyCode::Up => NavigateUpAction.execute(SynthesisStrategyMode, app_data),
+            KeyCode::Down => NavigateDownAction.execute(SynthesisStrategyMode, app_data),
+            _ => Ok(SynthesisStrategyMode.into()),
+        }?
+    };
+
+    app.apply_mode(next);
+    Ok(())
+}
+
 /// Dispatch a raw key event while in `SettingsMenuMode`, using typed actions.
 ///
 /// # Errors
@@ -789,6 +1310,91 @@ pub fn dispatch_confirm_push_for_pr_mode(app: &mut App, code: KeyCode) -> Result
     Ok(())
 }
 
+/// Dispatch a raw key event while in `PrePushFailedMode`: push anyway or cancel.
+///
+/// # Errors
+///
+/// Returns an error if the push cannot be started.
+pub fn dispatch_pre_push_failed_mode(
+    app: &mut App,
+    state: PrePushFailedMode,
+    code: KeyCode,
+) -> Result<()> {
+    let next = match code {
+        KeyCode::Char('p' | 'P') => Actions::push_anyway(&mut app.data, state)?,
+        KeyCode::Char('n' | 'N') | KeyCode::Esc => {
+            app.data.git_op.clear();
+            AppMode::normal()
+        }
+        _ => state.into(),
+    };
+
+    app.apply_mode(next);
+    Ok(())
+}
+
+/// Dispatch a raw key event while in `AuthPromptMode`: type the answer, then send it with
+/// `Enter` or decline the prompt with `Esc`.
+///
+/// # Errors
+///
+/// Never fails; returns `Result` like the other dispatchers.
+pub fn dispatch_auth_prompt_mode(
+    app: &mut App,
+    code: KeyCode,
+    modifiers: KeyModifiers,
+) -> Result<()> {
+    match code {
+        KeyCode::Enter => {
+            let answer = std::mem::take(&mut app.data.input.buffer);
+            app.data.input.clear();
+            app.data
+                .background_ops
+                .reply_to_prompt(PromptReply::Answer(answer));
+            app.apply_mode(AppMode::normal());
+        }
+        KeyCode::Esc => {
+            app.data.input.clear();
+            app.data
+                .background_ops
+                .reply_to_prompt(PromptReply::Declined);
+            app.apply_mode(AppMode::normal());
+        }
+        KeyCode::Char('u') if modifiers.contains(KeyModifiers::CONTROL) => {
+            app.data.input.clear_line();
+        }
+        KeyCode::Char(c) if !modifiers.contains(KeyModifiers::CONTROL) => {
+            app.data.input.insert_char(c);
+        }
+        KeyCode::Backspace => app.data.input.backspace(),
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Dispatch a raw key event while in `GitAuthFailedMode`: retry the operation or close.
+///
+/// # Errors
+///
+/// Returns an error if the retried operation cannot be started.
+pub fn dispatch_git_auth_failed_mode(
+    app: &mut App,
+    state: GitAuthFailedMode,
+    code: KeyCode,
+) -> Result<()> {
+    let next = match code {
+        KeyCode::Char('r' | 'R') => Actions::retry_after_auth_failure(&mut app.data, state.retry)?,
+        KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q' | 'Q') => {
+            app.data.git_op.clear();
+            AppMode::normal()
+        }
+        _ => state.into(),
+    };
+
+    app.apply_mode(next);
+    Ok(())
+}
+
 /// Dispatch a raw key event while in `RenameBranchMode`, using typed actions.
 ///
 /// # Errors
@@ -864,6 +1470,30 @@ pub fn dispatch_confirming_mode(app: &mut App, action: ConfirmAction, code: KeyC
             KeyCode::Esc => CancelAction.execute(state, &mut app.data),
             _ => Ok(state.into()),
         }?
+    } else if action == ConfirmAction::Quit {
+        match code {
+            KeyCode::Char('d' | 'D') => ConfirmYesAction.execute(state, &mut app.data),
+            KeyCode::Char('s' | 'S') => QuitStopAllAction.execute(state, &mut app.data),
+            KeyCode::Char('n' | 'N') => ConfirmNoAction.execute(state, &mut app.data),
+            KeyCode::Esc => CancelAction.execute(state, &mut app.data),
+            _ => Ok(state.into()),
+        }?
+    } else if action == ConfirmAction::Kill && app.data.kill_offers_stop() {
+        match code {
+            KeyCode::Char('s' | 'S') => KillStopAction.execute(state, &mut app.data),
+            KeyCode::Char('d' | 'D') => ConfirmYesAction.execute(state, &mut app.data),
+            KeyCode::Char('n' | 'N') => ConfirmNoAction.execute(state, &mut app.data),
+            KeyCode::Esc => CancelAction.execute(state, &mut app.data),
+            _ => Ok(state.into()),
+        }?
+    } else if action == ConfirmAction::Synthesize {
+        match code {
+            KeyCode::Char('y' | 'Y') => ConfirmYesAction.execute(state, &mut app.data),
+            KeyCode::Char('k' | 'K') => SynthesizeKeepAction.execute(state, &mut app.data),
+            KeyCode::Char('n' | 'N') => ConfirmNoAction.execute(state, &mut app.data),
+            KeyCode::Esc => CancelAction.execute(state, &mut app.data),
+            _ => Ok(state.into()),
+        }?
     } else {
         match code {
             KeyCode::Char('y' | 'Y') => ConfirmYesAction.execute(state, &mut app.data),
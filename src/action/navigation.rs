@@ -1,5 +1,5 @@
 use crate::action::ValidIn;
-use crate::app::{AppData, Tab};
+use crate::app::{Actions, AppData, Tab};
 use crate::state::{AppMode, DiffFocusedMode, NormalMode, PreviewFocusedMode, ScrollingMode};
 use anyhow::Result;
 
@@ -259,6 +259,9 @@ impl ValidIn<NormalMode> for FocusPreviewAction {
                 Tab::Preview => Ok(PreviewFocusedMode.into()),
                 Tab::Diff => Ok(DiffFocusedMode.into()),
                 Tab::Commits => Ok(AppMode::normal()),
+                Tab::Scratchpad => {
+                    Ok(Actions::start_edit_scratchpad(app_data).unwrap_or_else(AppMode::normal))
+                }
             }
         } else {
             Ok(AppMode::normal())
@@ -275,6 +278,8 @@ impl ValidIn<ScrollingMode> for FocusPreviewAction {
                 Tab::Preview => Ok(PreviewFocusedMode.into()),
                 Tab::Diff => Ok(DiffFocusedMode.into()),
                 Tab::Commits => Ok(ScrollingMode.into()),
+                Tab::Scratchpad => Ok(Actions::start_edit_scratchpad(app_data)
+                    .unwrap_or_else(|| ScrollingMode.into())),
             }
         } else {
             Ok(ScrollingMode.into())
@@ -4,12 +4,16 @@ use crate::action::{
     BackspaceAction, CancelAction, CharInputAction, CursorEndAction, CursorHomeAction,
     CursorLeftAction, CursorRightAction, DeleteAction, ValidIn,
 };
-use crate::app::{Actions, AppData};
+use crate::app::{Actions, AppData, AutoConnectPolicy};
+use crate::error::TenexError;
 use crate::state::{
-    AppMode, BranchSelectorMode, ChildCountMode, ChildPromptMode, CommandPaletteMode,
-    ConfirmAction, ConfirmingMode, ErrorModalMode, MergeBranchSelectorMode, ModelSelectorMode,
-    RebaseBranchSelectorMode, ReviewChildCountMode, ReviewInfoMode, SettingsMenuMode,
-    SwitchBranchSelectorMode,
+    AppMode, AutoConnectPickerMode, BranchSelectorMode, CannedResponsesMode, ChildCountMode,
+    ChildPromptMode, CommandPaletteMode, ConfirmAction, ConfirmingMode, ContextFilePickerMode,
+    DependencyPickerMode, ErrorModalMode, InboxMode, IssuePickerMode, MergeBranchSelectorMode,
+    ModelSelectorMode, RebaseBranchSelectorMode, RecoverPickerMode, RepoPickerMode,
+    ReviewChildCountMode, ReviewFocusMode, ReviewInfoMode, SendToMode, SettingsMenuMode,
+    SwitchBranchSelectorMode, SynthesisBrowserMode, SynthesisPromptMode, SynthesisStrategyMode,
+    TmuxImportPickerMode,
 };
 use anyhow::Result;
 
@@ -81,6 +85,76 @@ impl ValidIn<ReviewChildCountMode> for DecrementAction {
     }
 }
 
+impl ValidIn<ReviewFocusMode> for IncrementAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ReviewFocusMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.review.cycle_selected_focus(true);
+        Ok(ReviewFocusMode.into())
+    }
+}
+
+impl ValidIn<ReviewFocusMode> for DecrementAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ReviewFocusMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.review.cycle_selected_focus(false);
+        Ok(ReviewFocusMode.into())
+    }
+}
+
+impl ValidIn<CannedResponsesMode> for IncrementAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: CannedResponsesMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.canned_responses.toggle_broadcast();
+        Ok(CannedResponsesMode.into())
+    }
+}
+
+impl ValidIn<CannedResponsesMode> for DecrementAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: CannedResponsesMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.canned_responses.toggle_broadcast();
+        Ok(CannedResponsesMode.into())
+    }
+}
+
+impl ValidIn<DependencyPickerMode> for IncrementAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: DependencyPickerMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.dependency_picker.toggle_trigger();
+        Ok(DependencyPickerMode.into())
+    }
+}
+
+impl ValidIn<DependencyPickerMode> for DecrementAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: DependencyPickerMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.dependency_picker.toggle_trigger();
+        Ok(DependencyPickerMode.into())
+    }
+}
+
 impl ValidIn<ChildCountMode> for SelectAction {
     type NextState = AppMode;
 
@@ -95,8 +169,23 @@ impl ValidIn<ReviewChildCountMode> for SelectAction {
     fn execute(
         self,
         _state: ReviewChildCountMode,
-        _app_data: &mut AppData,
+        app_data: &mut AppData,
     ) -> Result<Self::NextState> {
+        let focuses = app_data.settings.review_focuses();
+        if focuses.is_empty() {
+            return Ok(BranchSelectorMode.into());
+        }
+        app_data
+            .review
+            .assign_focuses(focuses, app_data.spawn.child_count);
+        Ok(ReviewFocusMode.into())
+    }
+}
+
+impl ValidIn<ReviewFocusMode> for SelectAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ReviewFocusMode, _app_data: &mut AppData) -> Result<Self::NextState> {
         Ok(BranchSelectorMode.into())
     }
 }
@@ -129,6 +218,15 @@ impl ValidIn<ReviewChildCountMode> for CancelAction {
     }
 }
 
+impl ValidIn<ReviewFocusMode> for CancelAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ReviewFocusMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.review.clear();
+        Ok(AppMode::normal())
+    }
+}
+
 impl ValidIn<ReviewInfoMode> for CancelAction {
     type NextState = AppMode;
 
@@ -201,6 +299,165 @@ impl ValidIn<ModelSelectorMode> for CancelAction {
     }
 }
 
+impl ValidIn<ContextFilePickerMode> for CancelAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: ContextFilePickerMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        Ok(Actions::close_context_file_picker(app_data))
+    }
+}
+
+impl ValidIn<SendToMode> for CancelAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: SendToMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.send_to.clear();
+        Ok(AppMode::normal())
+    }
+}
+
+impl ValidIn<DependencyPickerMode> for CancelAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: DependencyPickerMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.dependency_picker.clear();
+        Ok(AppMode::normal())
+    }
+}
+
+impl ValidIn<CannedResponsesMode> for CancelAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: CannedResponsesMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.canned_responses.clear();
+        Ok(AppMode::normal())
+    }
+}
+
+impl ValidIn<RepoPickerMode> for CancelAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: RepoPickerMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.repo_picker.clear();
+        Ok(AppMode::normal())
+    }
+}
+
+impl ValidIn<RepoPickerMode> for NavigateUpAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: RepoPickerMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.repo_picker.select_prev();
+        Ok(RepoPickerMode.into())
+    }
+}
+
+impl ValidIn<RepoPickerMode> for NavigateDownAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: RepoPickerMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.repo_picker.select_next();
+        Ok(RepoPickerMode.into())
+    }
+}
+
+impl ValidIn<RepoPickerMode> for SelectAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: RepoPickerMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(Actions::switch_repo(app_data))
+    }
+}
+
+impl ValidIn<RepoPickerMode> for CharInputAction {
+    type NextState = AppMode;
+
+    fn execute(self, state: RepoPickerMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(match self.0 {
+            'a' => Actions::start_add_repo(app_data),
+            'd' => Actions::remove_repo(app_data),
+            _ => state.into(),
+        })
+    }
+}
+
+impl ValidIn<InboxMode> for CancelAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: InboxMode, _app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(AppMode::normal())
+    }
+}
+
+impl ValidIn<IssuePickerMode> for CancelAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: IssuePickerMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.issue_picker.clear();
+        Ok(AppMode::normal())
+    }
+}
+
+impl ValidIn<AutoConnectPickerMode> for CancelAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: AutoConnectPickerMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.auto_connect_picker.clear();
+        Ok(AppMode::normal())
+    }
+}
+
+impl ValidIn<TmuxImportPickerMode> for CancelAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: TmuxImportPickerMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.tmux_import_picker.clear();
+        Ok(AppMode::normal())
+    }
+}
+
+impl ValidIn<RecoverPickerMode> for CancelAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: RecoverPickerMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.recover_picker.clear();
+        Ok(AppMode::normal())
+    }
+}
+
+impl ValidIn<SynthesisBrowserMode> for CancelAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: SynthesisBrowserMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.synthesis_browser.clear();
+        Ok(AppMode::normal())
+    }
+}
+
 impl ValidIn<CommandPaletteMode> for CancelAction {
     type NextState = AppMode;
 
@@ -312,60 +569,356 @@ impl ValidIn<SwitchBranchSelectorMode> for NavigateUpAction {
     }
 }
 
-impl ValidIn<SwitchBranchSelectorMode> for NavigateDownAction {
+impl ValidIn<SwitchBranchSelectorMode> for NavigateDownAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: SwitchBranchSelectorMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.select_next_branch();
+        Ok(SwitchBranchSelectorMode.into())
+    }
+}
+
+impl ValidIn<ModelSelectorMode> for NavigateUpAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ModelSelectorMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.select_prev_model_program();
+        Ok(ModelSelectorMode.into())
+    }
+}
+
+impl ValidIn<ModelSelectorMode> for NavigateDownAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ModelSelectorMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.select_next_model_program();
+        Ok(ModelSelectorMode.into())
+    }
+}
+
+impl ValidIn<ContextFilePickerMode> for NavigateUpAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: ContextFilePickerMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.context_picker.select_prev();
+        Ok(ContextFilePickerMode.into())
+    }
+}
+
+impl ValidIn<ContextFilePickerMode> for NavigateDownAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: ContextFilePickerMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.context_picker.select_next();
+        Ok(ContextFilePickerMode.into())
+    }
+}
+
+impl ValidIn<SendToMode> for NavigateUpAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: SendToMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.send_to.select_prev();
+        Ok(SendToMode.into())
+    }
+}
+
+impl ValidIn<SendToMode> for NavigateDownAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: SendToMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.send_to.select_next();
+        Ok(SendToMode.into())
+    }
+}
+
+impl ValidIn<CannedResponsesMode> for NavigateUpAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: CannedResponsesMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.canned_responses.select_prev();
+        Ok(CannedResponsesMode.into())
+    }
+}
+
+impl ValidIn<CannedResponsesMode> for NavigateDownAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: CannedResponsesMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.canned_responses.select_next();
+        Ok(CannedResponsesMode.into())
+    }
+}
+
+impl ValidIn<InboxMode> for NavigateUpAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: InboxMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.inbox.select_prev();
+        Ok(InboxMode.into())
+    }
+}
+
+impl ValidIn<InboxMode> for NavigateDownAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: InboxMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.inbox.select_next();
+        Ok(InboxMode.into())
+    }
+}
+
+impl ValidIn<DependencyPickerMode> for NavigateUpAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: DependencyPickerMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.dependency_picker.select_prev();
+        Ok(DependencyPickerMode.into())
+    }
+}
+
+impl ValidIn<DependencyPickerMode> for NavigateDownAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: DependencyPickerMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.dependency_picker.select_next();
+        Ok(DependencyPickerMode.into())
+    }
+}
+
+impl ValidIn<IssuePickerMode> for NavigateUpAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: IssuePickerMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.issue_picker.select_prev();
+        Ok(IssuePickerMode.into())
+    }
+}
+
+impl ValidIn<IssuePickerMode> for NavigateDownAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: IssuePickerMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.issue_picker.select_next();
+        Ok(IssuePickerMode.into())
+    }
+}
+
+impl ValidIn<AutoConnectPickerMode> for NavigateUpAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: AutoConnectPickerMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.auto_connect_picker.select_prev();
+        Ok(AutoConnectPickerMode.into())
+    }
+}
+
+impl ValidIn<AutoConnectPickerMode> for NavigateDownAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: AutoConnectPickerMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.auto_connect_picker.select_next();
+        Ok(AutoConnectPickerMode.into())
+    }
+}
+
+impl ValidIn<ReviewFocusMode> for NavigateUpAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ReviewFocusMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.review.select_prev_reviewer();
+        Ok(ReviewFocusMode.into())
+    }
+}
+
+impl ValidIn<ReviewFocusMode> for NavigateDownAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ReviewFocusMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.review.select_next_reviewer();
+        Ok(ReviewFocusMode.into())
+    }
+}
+
+impl ValidIn<TmuxImportPickerMode> for NavigateUpAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: TmuxImportPickerMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.tmux_import_picker.select_prev();
+        Ok(TmuxImportPickerMode.into())
+    }
+}
+
+impl ValidIn<TmuxImportPickerMode> for NavigateDownAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: TmuxImportPickerMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.tmux_import_picker.select_next();
+        Ok(TmuxImportPickerMode.into())
+    }
+}
+
+impl ValidIn<RecoverPickerMode> for NavigateUpAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: RecoverPickerMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.recover_picker.select_prev();
+        Ok(RecoverPickerMode.into())
+    }
+}
+
+impl ValidIn<RecoverPickerMode> for NavigateDownAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: RecoverPickerMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.recover_picker.select_next();
+        Ok(RecoverPickerMode.into())
+    }
+}
+
+impl ValidIn<SynthesisBrowserMode> for NavigateUpAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: SynthesisBrowserMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.synthesis_browser.select_prev();
+        Ok(SynthesisBrowserMode.into())
+    }
+}
+
+impl ValidIn<SynthesisBrowserMode> for NavigateDownAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: SynthesisBrowserMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.synthesis_browser.select_next();
+        Ok(SynthesisBrowserMode.into())
+    }
+}
+
+impl ValidIn<CommandPaletteMode> for NavigateUpAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: CommandPaletteMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.select_prev_slash_command();
+        Ok(CommandPaletteMode.into())
+    }
+}
+
+impl ValidIn<CommandPaletteMode> for NavigateDownAction {
     type NextState = AppMode;
 
     fn execute(
         self,
-        _state: SwitchBranchSelectorMode,
+        _state: CommandPaletteMode,
         app_data: &mut AppData,
     ) -> Result<Self::NextState> {
-        app_data.select_next_branch();
-        Ok(SwitchBranchSelectorMode.into())
+        app_data.select_next_slash_command();
+        Ok(CommandPaletteMode.into())
     }
 }
 
-impl ValidIn<ModelSelectorMode> for NavigateUpAction {
+impl ValidIn<SynthesisStrategyMode> for NavigateUpAction {
     type NextState = AppMode;
 
-    fn execute(self, _state: ModelSelectorMode, app_data: &mut AppData) -> Result<Self::NextState> {
-        app_data.select_prev_model_program();
-        Ok(ModelSelectorMode.into())
+    fn execute(
+        self,
+        _state: SynthesisStrategyMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.select_prev_synthesis_strategy();
+        Ok(SynthesisStrategyMode.into())
     }
 }
 
-impl ValidIn<ModelSelectorMode> for NavigateDownAction {
+impl ValidIn<SynthesisStrategyMode> for NavigateDownAction {
     type NextState = AppMode;
 
-    fn execute(self, _state: ModelSelectorMode, app_data: &mut AppData) -> Result<Self::NextState> {
-        app_data.select_next_model_program();
-        Ok(ModelSelectorMode.into())
+    fn execute(
+        self,
+        _state: SynthesisStrategyMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.select_next_synthesis_strategy();
+        Ok(SynthesisStrategyMode.into())
     }
 }
 
-impl ValidIn<CommandPaletteMode> for NavigateUpAction {
+impl ValidIn<SynthesisStrategyMode> for SelectAction {
     type NextState = AppMode;
 
     fn execute(
         self,
-        _state: CommandPaletteMode,
-        app_data: &mut AppData,
+        _state: SynthesisStrategyMode,
+        _app_data: &mut AppData,
     ) -> Result<Self::NextState> {
-        app_data.select_prev_slash_command();
-        Ok(CommandPaletteMode.into())
+        Ok(SynthesisPromptMode.into())
     }
 }
 
-impl ValidIn<CommandPaletteMode> for NavigateDownAction {
+impl ValidIn<SynthesisStrategyMode> for CancelAction {
     type NextState = AppMode;
 
     fn execute(
         self,
-        _state: CommandPaletteMode,
-        app_data: &mut AppData,
+        _state: SynthesisStrategyMode,
+        _app_data: &mut AppData,
     ) -> Result<Self::NextState> {
-        app_data.select_next_slash_command();
-        Ok(CommandPaletteMode.into())
+        Ok(AppMode::normal())
     }
 }
 
@@ -398,9 +951,10 @@ impl ValidIn<BranchSelectorMode> for SelectAction {
         if app_data.confirm_branch_selection()
             && let Err(err) = Actions::new().spawn_review_agents(app_data)
         {
-            return Ok(ErrorModalMode {
-                message: format!("Failed to spawn review agents: {err:#}"),
-            }
+            return Ok(ErrorModalMode::new(TenexError::wrap(
+                "Failed to spawn review agents",
+                &err,
+            ))
             .into());
         }
 
@@ -420,12 +974,8 @@ impl ValidIn<RebaseBranchSelectorMode> for SelectAction {
             return Ok(state.into());
         }
 
-        Actions::execute_rebase(app_data).or_else(|err| {
-            Ok(ErrorModalMode {
-                message: format!("Rebase failed: {err:#}"),
-            }
-            .into())
-        })
+        Actions::execute_rebase(app_data)
+            .or_else(|err| Ok(ErrorModalMode::new(TenexError::wrap("Rebase failed", &err)).into()))
     }
 }
 
@@ -441,12 +991,8 @@ impl ValidIn<MergeBranchSelectorMode> for SelectAction {
             return Ok(state.into());
         }
 
-        Actions::execute_merge(app_data).or_else(|err| {
-            Ok(ErrorModalMode {
-                message: format!("Merge failed: {err:#}"),
-            }
-            .into())
-        })
+        Actions::execute_merge(app_data)
+            .or_else(|err| Ok(ErrorModalMode::new(TenexError::wrap("Merge failed", &err)).into()))
     }
 }
 
@@ -477,6 +1023,193 @@ impl ValidIn<ModelSelectorMode> for SelectAction {
     }
 }
 
+impl ValidIn<ContextFilePickerMode> for SelectAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        state: ContextFilePickerMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        if app_data.context_picker.selected_path().is_none() {
+            return Ok(state.into());
+        }
+        Ok(Actions::toggle_context_file(app_data))
+    }
+}
+
+impl ValidIn<SendToMode> for SelectAction {
+    type NextState = AppMode;
+
+    fn execute(self, state: SendToMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        if app_data.send_to.selected_target().is_none() {
+            return Ok(state.into());
+        }
+
+        match Actions::new().send_output_to_target(app_data) {
+            Ok(next) => Ok(next),
+            Err(err) => {
+                app_data.send_to.clear();
+                Ok(ErrorModalMode::new(TenexError::wrap("Failed to send output", &err)).into())
+            }
+        }
+    }
+}
+
+impl ValidIn<DependencyPickerMode> for SelectAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: DependencyPickerMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        Ok(Actions::choose_dependency(app_data))
+    }
+}
+
+impl ValidIn<CannedResponsesMode> for SelectAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: CannedResponsesMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        let index = app_data.canned_responses.selected;
+        send_canned_response_or_error(app_data, index)
+    }
+}
+
+impl ValidIn<InboxMode> for SelectAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: InboxMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(Actions::jump_to_question(app_data))
+    }
+}
+
+impl ValidIn<IssuePickerMode> for SelectAction {
+    type NextState = AppMode;
+
+    fn execute(self, state: IssuePickerMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        let Some(issue) = app_data.issue_picker.selected_issue().cloned() else {
+            return Ok(state.into());
+        };
+        app_data.issue_picker.clear();
+
+        match Actions::new().create_issue_agent(app_data, &issue) {
+            Ok(next) => Ok(next),
+            Err(err) => Ok(ErrorModalMode::new(format!(
+                "Failed to create agent for issue #{}: {err:#}",
+                issue.number
+            ))
+            .into()),
+        }
+    }
+}
+
+impl ValidIn<AutoConnectPickerMode> for SelectAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: AutoConnectPickerMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        let checked = app_data.auto_connect_picker.take_checked();
+        if checked.is_empty() {
+            return Ok(AppMode::normal());
+        }
+        let count = checked.len();
+
+        match Actions::new().connect_worktrees(app_data, checked, AutoConnectPolicy::Reconnect) {
+            Ok(()) => {
+                app_data.set_status(format!("Connected {count} worktree(s)"));
+                Ok(AppMode::normal())
+            }
+            Err(err) => Ok(ErrorModalMode::new(TenexError::wrap(
+                "Failed to connect worktrees",
+                &err,
+            ))
+            .into()),
+        }
+    }
+}
+
+impl ValidIn<TmuxImportPickerMode> for SelectAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: TmuxImportPickerMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        let sessions = app_data.tmux_import_picker.take_checked();
+        if sessions.is_empty() {
+            return Ok(AppMode::normal());
+        }
+        let count = sessions.len();
+
+        match Actions::new().import_tmux_sessions(app_data, sessions) {
+            Ok(()) => {
+                app_data.set_status(format!("Imported {count} tmux session(s)"));
+                Ok(AppMode::normal())
+            }
+            Err(err) => Ok(ErrorModalMode::new(TenexError::wrap(
+                "Failed to import tmux sessions",
+                &err,
+            ))
+            .into()),
+        }
+    }
+}
+
+impl ValidIn<RecoverPickerMode> for SelectAction {
+    type NextState = AppMode;
+
+    fn execute(self, state: RecoverPickerMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        let Some(candidate) = app_data.recover_picker.selected_candidate().cloned() else {
+            return Ok(state.into());
+        };
+        app_data.recover_picker.clear();
+
+        match Actions::recover_branch(&candidate) {
+            Ok(next) => Ok(next),
+            Err(err) => Ok(ErrorModalMode::new(format!(
+                "Failed to recover {}: {err:#}",
+                candidate.branch
+            ))
+            .into()),
+        }
+    }
+}
+
+impl ValidIn<SynthesisBrowserMode> for SelectAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        state: SynthesisBrowserMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        let Some(file) = app_data.synthesis_browser.selected_file().cloned() else {
+            return Ok(state.into());
+        };
+
+        let result = Actions::new().resend_synthesis(app_data, &file);
+        app_data.synthesis_browser.clear();
+        match result {
+            Ok(()) => Ok(AppMode::normal()),
+            Err(err) => Ok(ErrorModalMode::new(format!(
+                "Failed to send synthesis {}: {err:#}",
+                file.short_id()
+            ))
+            .into()),
+        }
+    }
+}
+
 impl ValidIn<CommandPaletteMode> for SelectAction {
     type NextState = AppMode;
 
@@ -619,6 +1352,218 @@ impl ValidIn<ModelSelectorMode> for BackspaceAction {
     }
 }
 
+impl ValidIn<ContextFilePickerMode> for CharInputAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: ContextFilePickerMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.context_picker.handle_filter_char(self.0);
+        Ok(ContextFilePickerMode.into())
+    }
+}
+
+impl ValidIn<ContextFilePickerMode> for BackspaceAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: ContextFilePickerMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.context_picker.handle_filter_backspace();
+        Ok(ContextFilePickerMode.into())
+    }
+}
+
+impl ValidIn<IssuePickerMode> for CharInputAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: IssuePickerMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.issue_picker.handle_filter_char(self.0);
+        Ok(IssuePickerMode.into())
+    }
+}
+
+impl ValidIn<IssuePickerMode> for BackspaceAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: IssuePickerMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.issue_picker.handle_filter_backspace();
+        Ok(IssuePickerMode.into())
+    }
+}
+
+impl ValidIn<AutoConnectPickerMode> for CharInputAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: AutoConnectPickerMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        match self.0 {
+            ' ' => app_data.auto_connect_picker.toggle_selected(),
+            'a' => app_data.auto_connect_picker.toggle_all(),
+            _ => {}
+        }
+        Ok(AutoConnectPickerMode.into())
+    }
+}
+
+impl ValidIn<TmuxImportPickerMode> for CharInputAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: TmuxImportPickerMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        match self.0 {
+            ' ' => app_data.tmux_import_picker.toggle_selected(),
+            'a' => app_data.tmux_import_picker.toggle_all(),
+            _ => {}
+        }
+        Ok(TmuxImportPickerMode.into())
+    }
+}
+
+impl ValidIn<SynthesisBrowserMode> for CharInputAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: SynthesisBrowserMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        let Some(file) = app_data.synthesis_browser.selected_file().cloned() else {
+            return Ok(SynthesisBrowserMode.into());
+        };
+
+        match self.0 {
+            'e' => match Actions::export_synthesis(app_data, &file) {
+                Ok(path) => app_data.set_status(format!("Exported to {}", path.display())),
+                Err(err) => {
+                    return Ok(ErrorModalMode::new(TenexError::wrap(
+                        "Failed to export synthesis",
+                        &err,
+                    ))
+                    .into());
+                }
+            },
+            'd' => {
+                if let Err(err) = Actions::delete_synthesis(&file) {
+                    return Ok(ErrorModalMode::new(TenexError::wrap(
+                        "Failed to delete synthesis",
+                        &err,
+                    ))
+                    .into());
+                }
+                app_data.synthesis_browser.remove_selected();
+                app_data.set_status(format!("Deleted synthesis {}", file.short_id()));
+                if app_data.synthesis_browser.files.is_empty() {
+                    app_data.synthesis_browser.clear();
+                    return Ok(AppMode::normal());
+                }
+            }
+            _ => {}
+        }
+        Ok(SynthesisBrowserMode.into())
+    }
+}
+
+impl ValidIn<RecoverPickerMode> for CharInputAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: RecoverPickerMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.recover_picker.handle_filter_char(self.0);
+        Ok(RecoverPickerMode.into())
+    }
+}
+
+impl ValidIn<RecoverPickerMode> for BackspaceAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: RecoverPickerMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.recover_picker.handle_filter_backspace();
+        Ok(RecoverPickerMode.into())
+    }
+}
+
+impl ValidIn<SendToMode> for CharInputAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: SendToMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.send_to.handle_filter_char(self.0);
+        Ok(SendToMode.into())
+    }
+}
+
+impl ValidIn<SendToMode> for BackspaceAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: SendToMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.send_to.handle_filter_backspace();
+        Ok(SendToMode.into())
+    }
+}
+
+impl ValidIn<CannedResponsesMode> for CharInputAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        state: CannedResponsesMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        // `1`-`9` send the numbered response straight away.
+        match self.0.to_digit(10) {
+            Some(digit @ 1..=9) => send_canned_response_or_error(app_data, digit as usize - 1),
+            _ => Ok(state.into()),
+        }
+    }
+}
+
+impl ValidIn<InboxMode> for CharInputAction {
+    type NextState = AppMode;
+
+    fn execute(self, state: InboxMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(match self.0 {
+            'r' => Actions::reply_to_question(app_data),
+            'd' => Actions::dismiss_question(app_data),
+            _ => state.into(),
+        })
+    }
+}
+
+impl ValidIn<DependencyPickerMode> for CharInputAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: DependencyPickerMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.dependency_picker.handle_filter_char(self.0);
+        Ok(DependencyPickerMode.into())
+    }
+}
+
+impl ValidIn<DependencyPickerMode> for BackspaceAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: DependencyPickerMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.dependency_picker.handle_filter_backspace();
+        Ok(DependencyPickerMode.into())
+    }
+}
+
 impl ValidIn<CommandPaletteMode> for CharInputAction {
     type NextState = AppMode;
 
@@ -720,3 +1665,15 @@ impl ValidIn<CommandPaletteMode> for CursorEndAction {
         Ok(CommandPaletteMode.into())
     }
 }
+
+/// Send canned response `index`, showing failures in an error modal.
+fn send_canned_response_or_error(app_data: &mut AppData, index: usize) -> Result<AppMode> {
+    Actions::new()
+        .send_canned_response(app_data, index)
+        .or_else(|err| {
+            Ok(
+                ErrorModalMode::new(TenexError::wrap("Failed to send canned response", &err))
+                    .into(),
+            )
+        })
+}
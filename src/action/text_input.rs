@@ -1,8 +1,11 @@
 use crate::action::{CancelAction, ValidIn};
-use crate::app::{Actions, App, AppData};
+use crate::app::{Actions, App, AppData, InputHistoryKind, LineEdit};
+use crate::error::TenexError;
 use crate::state::{
-    AppMode, BroadcastingMode, ChildPromptMode, CreatingMode, CustomAgentCommandMode,
-    ErrorModalMode, PromptingMode, ReconnectPromptMode, SynthesisPromptMode, TerminalPromptMode,
+    AddRepoMode, AppMode, BroadcastingMode, ChildPromptMode, CreatingMode, CustomAgentCommandMode,
+    DependencyMessageMode, EditScratchpadMode, EditTaskMode, ErrorModalMode, PromptingMode,
+    ReconnectPromptMode, ReplyMode, ScopeMode, SwitchModelMode, SynthesisPromptMode,
+    TerminalPromptMode,
 };
 use anyhow::Result;
 use ratatui::crossterm::event::{KeyCode, KeyModifiers};
@@ -44,18 +47,35 @@ pub struct CursorHomeAction;
 #[derive(Debug, Clone, Copy, Default)]
 pub struct CursorEndAction;
 
-/// Text-input action: clear the current input line/buffer.
+/// Text-input action: kill from the start of the line to the cursor.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct ClearLineAction;
 
-/// Text-input action: delete the previous word.
+/// Text-input action: kill the previous word.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct DeleteWordAction;
 
+/// Text-input action: a readline-style word motion, kill, or yank.
+#[derive(Debug, Clone, Copy)]
+pub struct LineEditAction(pub LineEdit);
+
 /// Text-input action: submit (Enter) in the current mode.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct SubmitAction;
 
+/// Text-input action: open the context file picker (`Ctrl+O` in the new-agent prompts).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AttachContextFileAction;
+
+/// Text-input action: open the command field of the new-agent inputs (`Ctrl+P`).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EditProgramAction;
+
+/// Text-input action: close the command field of the new-agent inputs, running the typed
+/// command for the new agent (`true`, `Enter` or `Ctrl+P`) or discarding it (`false`, `Esc`).
+#[derive(Debug, Clone, Copy)]
+pub struct FinishProgramAction(pub bool);
+
 /// Dispatch a raw key event in a text input state via typed actions.
 ///
 /// This keeps raw key handling (Enter/Esc/backspace/cursor keys) out of the
@@ -86,6 +106,7 @@ where
     CursorEndAction: ValidIn<State, NextState = AppMode>,
     ClearLineAction: ValidIn<State, NextState = AppMode>,
     DeleteWordAction: ValidIn<State, NextState = AppMode>,
+    LineEditAction: ValidIn<State, NextState = AppMode>,
 {
     let app_data = &mut app.data;
     let next = match (code, modifiers) {
@@ -100,7 +121,10 @@ where
         (KeyCode::Char('w' | 'W'), mods) if mods.contains(KeyModifiers::CONTROL) => {
             DeleteWordAction.execute(state, app_data)
         }
-        (KeyCode::Char(c), _) => CharInputAction(c).execute(state, app_data),
+        (KeyCode::Char(c), mods) => match line_edit_for_key(c, mods) {
+            Some(edit) => LineEditAction(edit).execute(state, app_data),
+            None => CharInputAction(c).execute(state, app_data),
+        },
         (KeyCode::Backspace, _) => BackspaceAction.execute(state, app_data),
         (KeyCode::Delete, _) => DeleteAction.execute(state, app_data),
         (KeyCode::Left, _) => CursorLeftAction.execute(state, app_data),
@@ -116,13 +140,30 @@ where
     Ok(())
 }
 
+/// The readline edit bound to `c` with `modifiers`, if any.
+const fn line_edit_for_key(c: char, modifiers: KeyModifiers) -> Option<LineEdit> {
+    if modifiers.contains(KeyModifiers::CONTROL) {
+        return match c {
+            'k' | 'K' => Some(LineEdit::KillToEnd),
+            'y' | 'Y' => Some(LineEdit::Yank),
+            'z' if modifiers.contains(KeyModifiers::SHIFT) => Some(LineEdit::Redo),
+            'Z' => Some(LineEdit::Redo),
+            'z' => Some(LineEdit::Undo),
+            _ => None,
+        };
+    }
+    if modifiers.contains(KeyModifiers::ALT) {
+        return match c {
+            'b' | 'B' => Some(LineEdit::WordLeft),
+            'f' | 'F' => Some(LineEdit::WordRight),
+            _ => None,
+        };
+    }
+    None
+}
+
 fn ok_or_error_modal(result: Result<AppMode>) -> Result<AppMode> {
-    result.or_else(|err| {
-        Ok(ErrorModalMode {
-            message: format!("Failed: {err:#}"),
-        }
-        .into())
-    })
+    result.or_else(|err| Ok(ErrorModalMode::new(TenexError::wrap("Failed", &err)).into()))
 }
 
 impl ValidIn<CreatingMode> for CharInputAction {
@@ -213,6 +254,77 @@ impl ValidIn<SynthesisPromptMode> for CharInputAction {
     }
 }
 
+impl ValidIn<EditTaskMode> for CharInputAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: EditTaskMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.handle_char(self.0);
+        Ok(EditTaskMode.into())
+    }
+}
+
+impl ValidIn<EditScratchpadMode> for CharInputAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: EditScratchpadMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.handle_char(self.0);
+        Ok(EditScratchpadMode.into())
+    }
+}
+
+impl ValidIn<DependencyMessageMode> for CharInputAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: DependencyMessageMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.handle_char(self.0);
+        Ok(DependencyMessageMode.into())
+    }
+}
+
+impl ValidIn<ReplyMode> for CharInputAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ReplyMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.handle_char(self.0);
+        Ok(ReplyMode.into())
+    }
+}
+
+impl ValidIn<ScopeMode> for CharInputAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ScopeMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.handle_char(self.0);
+        Ok(ScopeMode.into())
+    }
+}
+
+impl ValidIn<SwitchModelMode> for CharInputAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: SwitchModelMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.handle_char(self.0);
+        Ok(SwitchModelMode.into())
+    }
+}
+
+impl ValidIn<AddRepoMode> for CharInputAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: AddRepoMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.handle_char(self.0);
+        Ok(AddRepoMode.into())
+    }
+}
+
 impl ValidIn<CreatingMode> for BackspaceAction {
     type NextState = AppMode;
 
@@ -301,6 +413,77 @@ impl ValidIn<SynthesisPromptMode> for BackspaceAction {
     }
 }
 
+impl ValidIn<EditTaskMode> for BackspaceAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: EditTaskMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.handle_backspace();
+        Ok(EditTaskMode.into())
+    }
+}
+
+impl ValidIn<EditScratchpadMode> for BackspaceAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: EditScratchpadMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.handle_backspace();
+        Ok(EditScratchpadMode.into())
+    }
+}
+
+impl ValidIn<DependencyMessageMode> for BackspaceAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: DependencyMessageMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.handle_backspace();
+        Ok(DependencyMessageMode.into())
+    }
+}
+
+impl ValidIn<ReplyMode> for BackspaceAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ReplyMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.handle_backspace();
+        Ok(ReplyMode.into())
+    }
+}
+
+impl ValidIn<ScopeMode> for BackspaceAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ScopeMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.handle_backspace();
+        Ok(ScopeMode.into())
+    }
+}
+
+impl ValidIn<SwitchModelMode> for BackspaceAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: SwitchModelMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.handle_backspace();
+        Ok(SwitchModelMode.into())
+    }
+}
+
+impl ValidIn<AddRepoMode> for BackspaceAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: AddRepoMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.handle_backspace();
+        Ok(AddRepoMode.into())
+    }
+}
+
 impl ValidIn<CreatingMode> for DeleteAction {
     type NextState = AppMode;
 
@@ -389,6 +572,77 @@ impl ValidIn<SynthesisPromptMode> for DeleteAction {
     }
 }
 
+impl ValidIn<EditTaskMode> for DeleteAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: EditTaskMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.handle_delete();
+        Ok(EditTaskMode.into())
+    }
+}
+
+impl ValidIn<EditScratchpadMode> for DeleteAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: EditScratchpadMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.handle_delete();
+        Ok(EditScratchpadMode.into())
+    }
+}
+
+impl ValidIn<DependencyMessageMode> for DeleteAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: DependencyMessageMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.handle_delete();
+        Ok(DependencyMessageMode.into())
+    }
+}
+
+impl ValidIn<ReplyMode> for DeleteAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ReplyMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.handle_delete();
+        Ok(ReplyMode.into())
+    }
+}
+
+impl ValidIn<ScopeMode> for DeleteAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ScopeMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.handle_delete();
+        Ok(ScopeMode.into())
+    }
+}
+
+impl ValidIn<SwitchModelMode> for DeleteAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: SwitchModelMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.handle_delete();
+        Ok(SwitchModelMode.into())
+    }
+}
+
+impl ValidIn<AddRepoMode> for DeleteAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: AddRepoMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.handle_delete();
+        Ok(AddRepoMode.into())
+    }
+}
+
 impl ValidIn<CreatingMode> for CursorLeftAction {
     type NextState = AppMode;
 
@@ -477,6 +731,77 @@ impl ValidIn<SynthesisPromptMode> for CursorLeftAction {
     }
 }
 
+impl ValidIn<EditTaskMode> for CursorLeftAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: EditTaskMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_left();
+        Ok(EditTaskMode.into())
+    }
+}
+
+impl ValidIn<EditScratchpadMode> for CursorLeftAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: EditScratchpadMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.cursor_left();
+        Ok(EditScratchpadMode.into())
+    }
+}
+
+impl ValidIn<DependencyMessageMode> for CursorLeftAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: DependencyMessageMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.cursor_left();
+        Ok(DependencyMessageMode.into())
+    }
+}
+
+impl ValidIn<ReplyMode> for CursorLeftAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ReplyMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_left();
+        Ok(ReplyMode.into())
+    }
+}
+
+impl ValidIn<ScopeMode> for CursorLeftAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ScopeMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_left();
+        Ok(ScopeMode.into())
+    }
+}
+
+impl ValidIn<SwitchModelMode> for CursorLeftAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: SwitchModelMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_left();
+        Ok(SwitchModelMode.into())
+    }
+}
+
+impl ValidIn<AddRepoMode> for CursorLeftAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: AddRepoMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_left();
+        Ok(AddRepoMode.into())
+    }
+}
+
 impl ValidIn<CreatingMode> for CursorRightAction {
     type NextState = AppMode;
 
@@ -565,64 +890,153 @@ impl ValidIn<SynthesisPromptMode> for CursorRightAction {
     }
 }
 
-impl ValidIn<CreatingMode> for CursorUpAction {
+impl ValidIn<EditTaskMode> for CursorRightAction {
     type NextState = AppMode;
 
-    fn execute(self, _state: CreatingMode, app_data: &mut AppData) -> Result<Self::NextState> {
-        app_data.input.cursor_up();
-        Ok(CreatingMode.into())
+    fn execute(self, _state: EditTaskMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_right();
+        Ok(EditTaskMode.into())
     }
 }
 
-impl ValidIn<PromptingMode> for CursorUpAction {
+impl ValidIn<EditScratchpadMode> for CursorRightAction {
     type NextState = AppMode;
 
-    fn execute(self, _state: PromptingMode, app_data: &mut AppData) -> Result<Self::NextState> {
-        app_data.input.cursor_up();
-        Ok(PromptingMode.into())
+    fn execute(
+        self,
+        _state: EditScratchpadMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.cursor_right();
+        Ok(EditScratchpadMode.into())
     }
 }
 
-impl ValidIn<ChildPromptMode> for CursorUpAction {
+impl ValidIn<DependencyMessageMode> for CursorRightAction {
     type NextState = AppMode;
 
-    fn execute(self, _state: ChildPromptMode, app_data: &mut AppData) -> Result<Self::NextState> {
-        app_data.input.cursor_up();
-        Ok(ChildPromptMode.into())
+    fn execute(
+        self,
+        _state: DependencyMessageMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.cursor_right();
+        Ok(DependencyMessageMode.into())
     }
 }
 
-impl ValidIn<BroadcastingMode> for CursorUpAction {
+impl ValidIn<ReplyMode> for CursorRightAction {
     type NextState = AppMode;
 
-    fn execute(self, _state: BroadcastingMode, app_data: &mut AppData) -> Result<Self::NextState> {
-        app_data.input.cursor_up();
-        Ok(BroadcastingMode.into())
+    fn execute(self, _state: ReplyMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_right();
+        Ok(ReplyMode.into())
     }
 }
 
-impl ValidIn<ReconnectPromptMode> for CursorUpAction {
+impl ValidIn<ScopeMode> for CursorRightAction {
     type NextState = AppMode;
 
-    fn execute(
-        self,
-        _state: ReconnectPromptMode,
-        app_data: &mut AppData,
-    ) -> Result<Self::NextState> {
-        app_data.input.cursor_up();
-        Ok(ReconnectPromptMode.into())
+    fn execute(self, _state: ScopeMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_right();
+        Ok(ScopeMode.into())
     }
 }
 
-impl ValidIn<TerminalPromptMode> for CursorUpAction {
+impl ValidIn<SwitchModelMode> for CursorRightAction {
     type NextState = AppMode;
 
-    fn execute(
-        self,
-        _state: TerminalPromptMode,
-        app_data: &mut AppData,
-    ) -> Result<Self::NextState> {
+    fn execute(self, _state: SwitchModelMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_right();
+        Ok(SwitchModelMode.into())
+    }
+}
+
+impl ValidIn<AddRepoMode> for CursorRightAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: AddRepoMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_right();
+        Ok(AddRepoMode.into())
+    }
+}
+
+impl ValidIn<CreatingMode> for CursorUpAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: CreatingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_up();
+        Ok(CreatingMode.into())
+    }
+}
+
+impl ValidIn<PromptingMode> for CursorUpAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: PromptingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        let history = app_data
+            .settings
+            .input_history
+            .entries(InputHistoryKind::Prompt);
+        if !app_data.input.history_previous(history) {
+            app_data.input.cursor_up();
+        }
+        Ok(PromptingMode.into())
+    }
+}
+
+impl ValidIn<ChildPromptMode> for CursorUpAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ChildPromptMode, app_data: &mut AppData) -> Result<Self::NextState> {
         app_data.input.cursor_up();
+        Ok(ChildPromptMode.into())
+    }
+}
+
+impl ValidIn<BroadcastingMode> for CursorUpAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: BroadcastingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        let history = app_data
+            .settings
+            .input_history
+            .entries(InputHistoryKind::Broadcast);
+        if !app_data.input.history_previous(history) {
+            app_data.input.cursor_up();
+        }
+        Ok(BroadcastingMode.into())
+    }
+}
+
+impl ValidIn<ReconnectPromptMode> for CursorUpAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: ReconnectPromptMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.cursor_up();
+        Ok(ReconnectPromptMode.into())
+    }
+}
+
+impl ValidIn<TerminalPromptMode> for CursorUpAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: TerminalPromptMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        let history = app_data
+            .settings
+            .input_history
+            .entries(InputHistoryKind::TerminalCommand);
+        if !app_data.input.history_previous(history) {
+            app_data.input.cursor_up();
+        }
         Ok(TerminalPromptMode.into())
     }
 }
@@ -653,6 +1067,77 @@ impl ValidIn<SynthesisPromptMode> for CursorUpAction {
     }
 }
 
+impl ValidIn<EditTaskMode> for CursorUpAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: EditTaskMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_up();
+        Ok(EditTaskMode.into())
+    }
+}
+
+impl ValidIn<EditScratchpadMode> for CursorUpAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: EditScratchpadMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.cursor_up();
+        Ok(EditScratchpadMode.into())
+    }
+}
+
+impl ValidIn<DependencyMessageMode> for CursorUpAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: DependencyMessageMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.cursor_up();
+        Ok(DependencyMessageMode.into())
+    }
+}
+
+impl ValidIn<ReplyMode> for CursorUpAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ReplyMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_up();
+        Ok(ReplyMode.into())
+    }
+}
+
+impl ValidIn<ScopeMode> for CursorUpAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ScopeMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_up();
+        Ok(ScopeMode.into())
+    }
+}
+
+impl ValidIn<SwitchModelMode> for CursorUpAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: SwitchModelMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_up();
+        Ok(SwitchModelMode.into())
+    }
+}
+
+impl ValidIn<AddRepoMode> for CursorUpAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: AddRepoMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_up();
+        Ok(AddRepoMode.into())
+    }
+}
+
 impl ValidIn<CreatingMode> for CursorDownAction {
     type NextState = AppMode;
 
@@ -666,7 +1151,13 @@ impl ValidIn<PromptingMode> for CursorDownAction {
     type NextState = AppMode;
 
     fn execute(self, _state: PromptingMode, app_data: &mut AppData) -> Result<Self::NextState> {
-        app_data.input.cursor_down();
+        let history = app_data
+            .settings
+            .input_history
+            .entries(InputHistoryKind::Prompt);
+        if !app_data.input.history_next(history) {
+            app_data.input.cursor_down();
+        }
         Ok(PromptingMode.into())
     }
 }
@@ -684,7 +1175,13 @@ impl ValidIn<BroadcastingMode> for CursorDownAction {
     type NextState = AppMode;
 
     fn execute(self, _state: BroadcastingMode, app_data: &mut AppData) -> Result<Self::NextState> {
-        app_data.input.cursor_down();
+        let history = app_data
+            .settings
+            .input_history
+            .entries(InputHistoryKind::Broadcast);
+        if !app_data.input.history_next(history) {
+            app_data.input.cursor_down();
+        }
         Ok(BroadcastingMode.into())
     }
 }
@@ -710,7 +1207,13 @@ impl ValidIn<TerminalPromptMode> for CursorDownAction {
         _state: TerminalPromptMode,
         app_data: &mut AppData,
     ) -> Result<Self::NextState> {
-        app_data.input.cursor_down();
+        let history = app_data
+            .settings
+            .input_history
+            .entries(InputHistoryKind::TerminalCommand);
+        if !app_data.input.history_next(history) {
+            app_data.input.cursor_down();
+        }
         Ok(TerminalPromptMode.into())
     }
 }
@@ -741,6 +1244,77 @@ impl ValidIn<SynthesisPromptMode> for CursorDownAction {
     }
 }
 
+impl ValidIn<EditTaskMode> for CursorDownAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: EditTaskMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_down();
+        Ok(EditTaskMode.into())
+    }
+}
+
+impl ValidIn<EditScratchpadMode> for CursorDownAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: EditScratchpadMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.cursor_down();
+        Ok(EditScratchpadMode.into())
+    }
+}
+
+impl ValidIn<DependencyMessageMode> for CursorDownAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: DependencyMessageMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.cursor_down();
+        Ok(DependencyMessageMode.into())
+    }
+}
+
+impl ValidIn<ReplyMode> for CursorDownAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ReplyMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_down();
+        Ok(ReplyMode.into())
+    }
+}
+
+impl ValidIn<ScopeMode> for CursorDownAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ScopeMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_down();
+        Ok(ScopeMode.into())
+    }
+}
+
+impl ValidIn<SwitchModelMode> for CursorDownAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: SwitchModelMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_down();
+        Ok(SwitchModelMode.into())
+    }
+}
+
+impl ValidIn<AddRepoMode> for CursorDownAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: AddRepoMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_down();
+        Ok(AddRepoMode.into())
+    }
+}
+
 impl ValidIn<CreatingMode> for CursorHomeAction {
     type NextState = AppMode;
 
@@ -829,6 +1403,77 @@ impl ValidIn<SynthesisPromptMode> for CursorHomeAction {
     }
 }
 
+impl ValidIn<EditTaskMode> for CursorHomeAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: EditTaskMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_home();
+        Ok(EditTaskMode.into())
+    }
+}
+
+impl ValidIn<EditScratchpadMode> for CursorHomeAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: EditScratchpadMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.cursor_home();
+        Ok(EditScratchpadMode.into())
+    }
+}
+
+impl ValidIn<DependencyMessageMode> for CursorHomeAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: DependencyMessageMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.cursor_home();
+        Ok(DependencyMessageMode.into())
+    }
+}
+
+impl ValidIn<ReplyMode> for CursorHomeAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ReplyMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_home();
+        Ok(ReplyMode.into())
+    }
+}
+
+impl ValidIn<ScopeMode> for CursorHomeAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ScopeMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_home();
+        Ok(ScopeMode.into())
+    }
+}
+
+impl ValidIn<SwitchModelMode> for CursorHomeAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: SwitchModelMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_home();
+        Ok(SwitchModelMode.into())
+    }
+}
+
+impl ValidIn<AddRepoMode> for CursorHomeAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: AddRepoMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_home();
+        Ok(AddRepoMode.into())
+    }
+}
+
 impl ValidIn<CreatingMode> for CursorEndAction {
     type NextState = AppMode;
 
@@ -883,77 +1528,466 @@ impl ValidIn<TerminalPromptMode> for CursorEndAction {
 
     fn execute(
         self,
-        _state: TerminalPromptMode,
+        _state: TerminalPromptMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.cursor_end();
+        Ok(TerminalPromptMode.into())
+    }
+}
+
+impl ValidIn<CustomAgentCommandMode> for CursorEndAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: CustomAgentCommandMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.cursor_end();
+        Ok(CustomAgentCommandMode.into())
+    }
+}
+
+impl ValidIn<SynthesisPromptMode> for CursorEndAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: SynthesisPromptMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.cursor_end();
+        Ok(SynthesisPromptMode.into())
+    }
+}
+
+impl ValidIn<EditTaskMode> for CursorEndAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: EditTaskMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_end();
+        Ok(EditTaskMode.into())
+    }
+}
+
+impl ValidIn<EditScratchpadMode> for CursorEndAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: EditScratchpadMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.cursor_end();
+        Ok(EditScratchpadMode.into())
+    }
+}
+
+impl ValidIn<DependencyMessageMode> for CursorEndAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: DependencyMessageMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.cursor_end();
+        Ok(DependencyMessageMode.into())
+    }
+}
+
+impl ValidIn<ReplyMode> for CursorEndAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ReplyMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_end();
+        Ok(ReplyMode.into())
+    }
+}
+
+impl ValidIn<ScopeMode> for CursorEndAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ScopeMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_end();
+        Ok(ScopeMode.into())
+    }
+}
+
+impl ValidIn<SwitchModelMode> for CursorEndAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: SwitchModelMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_end();
+        Ok(SwitchModelMode.into())
+    }
+}
+
+impl ValidIn<AddRepoMode> for CursorEndAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: AddRepoMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.cursor_end();
+        Ok(AddRepoMode.into())
+    }
+}
+
+impl ValidIn<CreatingMode> for ClearLineAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: CreatingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.clear_line();
+        Ok(CreatingMode.into())
+    }
+}
+
+impl ValidIn<PromptingMode> for ClearLineAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: PromptingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.clear_line();
+        Ok(PromptingMode.into())
+    }
+}
+
+impl ValidIn<ChildPromptMode> for ClearLineAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ChildPromptMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.clear_line();
+        Ok(ChildPromptMode.into())
+    }
+}
+
+impl ValidIn<BroadcastingMode> for ClearLineAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: BroadcastingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.clear_line();
+        Ok(BroadcastingMode.into())
+    }
+}
+
+impl ValidIn<ReconnectPromptMode> for ClearLineAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: ReconnectPromptMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.clear_line();
+        Ok(ReconnectPromptMode.into())
+    }
+}
+
+impl ValidIn<TerminalPromptMode> for ClearLineAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: TerminalPromptMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.clear_line();
+        Ok(TerminalPromptMode.into())
+    }
+}
+
+impl ValidIn<CustomAgentCommandMode> for ClearLineAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: CustomAgentCommandMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.clear_line();
+        Ok(CustomAgentCommandMode.into())
+    }
+}
+
+impl ValidIn<SynthesisPromptMode> for ClearLineAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: SynthesisPromptMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.clear_line();
+        Ok(SynthesisPromptMode.into())
+    }
+}
+
+impl ValidIn<EditTaskMode> for ClearLineAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: EditTaskMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.clear_line();
+        Ok(EditTaskMode.into())
+    }
+}
+
+impl ValidIn<EditScratchpadMode> for ClearLineAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: EditScratchpadMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.clear_line();
+        Ok(EditScratchpadMode.into())
+    }
+}
+
+impl ValidIn<DependencyMessageMode> for ClearLineAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: DependencyMessageMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.clear_line();
+        Ok(DependencyMessageMode.into())
+    }
+}
+
+impl ValidIn<ReplyMode> for ClearLineAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ReplyMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.clear_line();
+        Ok(ReplyMode.into())
+    }
+}
+
+impl ValidIn<ScopeMode> for ClearLineAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ScopeMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.clear_line();
+        Ok(ScopeMode.into())
+    }
+}
+
+impl ValidIn<SwitchModelMode> for ClearLineAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: SwitchModelMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.clear_line();
+        Ok(SwitchModelMode.into())
+    }
+}
+
+impl ValidIn<AddRepoMode> for ClearLineAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: AddRepoMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.clear_line();
+        Ok(AddRepoMode.into())
+    }
+}
+
+impl ValidIn<CreatingMode> for DeleteWordAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: CreatingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.delete_word();
+        Ok(CreatingMode.into())
+    }
+}
+
+impl ValidIn<PromptingMode> for DeleteWordAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: PromptingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.delete_word();
+        Ok(PromptingMode.into())
+    }
+}
+
+impl ValidIn<ChildPromptMode> for DeleteWordAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ChildPromptMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.delete_word();
+        Ok(ChildPromptMode.into())
+    }
+}
+
+impl ValidIn<BroadcastingMode> for DeleteWordAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: BroadcastingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.delete_word();
+        Ok(BroadcastingMode.into())
+    }
+}
+
+impl ValidIn<ReconnectPromptMode> for DeleteWordAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: ReconnectPromptMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.delete_word();
+        Ok(ReconnectPromptMode.into())
+    }
+}
+
+impl ValidIn<TerminalPromptMode> for DeleteWordAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: TerminalPromptMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.delete_word();
+        Ok(TerminalPromptMode.into())
+    }
+}
+
+impl ValidIn<CustomAgentCommandMode> for DeleteWordAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: CustomAgentCommandMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.delete_word();
+        Ok(CustomAgentCommandMode.into())
+    }
+}
+
+impl ValidIn<SynthesisPromptMode> for DeleteWordAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: SynthesisPromptMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.delete_word();
+        Ok(SynthesisPromptMode.into())
+    }
+}
+
+impl ValidIn<EditTaskMode> for DeleteWordAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: EditTaskMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.delete_word();
+        Ok(EditTaskMode.into())
+    }
+}
+
+impl ValidIn<EditScratchpadMode> for DeleteWordAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: EditScratchpadMode,
         app_data: &mut AppData,
     ) -> Result<Self::NextState> {
-        app_data.input.cursor_end();
-        Ok(TerminalPromptMode.into())
+        app_data.input.delete_word();
+        Ok(EditScratchpadMode.into())
     }
 }
 
-impl ValidIn<CustomAgentCommandMode> for CursorEndAction {
+impl ValidIn<DependencyMessageMode> for DeleteWordAction {
     type NextState = AppMode;
 
     fn execute(
         self,
-        _state: CustomAgentCommandMode,
+        _state: DependencyMessageMode,
         app_data: &mut AppData,
     ) -> Result<Self::NextState> {
-        app_data.input.cursor_end();
-        Ok(CustomAgentCommandMode.into())
+        app_data.input.delete_word();
+        Ok(DependencyMessageMode.into())
     }
 }
 
-impl ValidIn<SynthesisPromptMode> for CursorEndAction {
+impl ValidIn<ReplyMode> for DeleteWordAction {
     type NextState = AppMode;
 
-    fn execute(
-        self,
-        _state: SynthesisPromptMode,
-        app_data: &mut AppData,
-    ) -> Result<Self::NextState> {
-        app_data.input.cursor_end();
-        Ok(SynthesisPromptMode.into())
+    fn execute(self, _state: ReplyMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.delete_word();
+        Ok(ReplyMode.into())
     }
 }
 
-impl ValidIn<CreatingMode> for ClearLineAction {
+impl ValidIn<ScopeMode> for DeleteWordAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ScopeMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.delete_word();
+        Ok(ScopeMode.into())
+    }
+}
+
+impl ValidIn<SwitchModelMode> for DeleteWordAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: SwitchModelMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.delete_word();
+        Ok(SwitchModelMode.into())
+    }
+}
+
+impl ValidIn<AddRepoMode> for DeleteWordAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: AddRepoMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.delete_word();
+        Ok(AddRepoMode.into())
+    }
+}
+
+impl ValidIn<CreatingMode> for LineEditAction {
     type NextState = AppMode;
 
     fn execute(self, _state: CreatingMode, app_data: &mut AppData) -> Result<Self::NextState> {
-        app_data.input.clear_line();
+        app_data.input.apply(self.0);
         Ok(CreatingMode.into())
     }
 }
 
-impl ValidIn<PromptingMode> for ClearLineAction {
+impl ValidIn<PromptingMode> for LineEditAction {
     type NextState = AppMode;
 
     fn execute(self, _state: PromptingMode, app_data: &mut AppData) -> Result<Self::NextState> {
-        app_data.input.clear_line();
+        app_data.input.apply(self.0);
         Ok(PromptingMode.into())
     }
 }
 
-impl ValidIn<ChildPromptMode> for ClearLineAction {
+impl ValidIn<ChildPromptMode> for LineEditAction {
     type NextState = AppMode;
 
     fn execute(self, _state: ChildPromptMode, app_data: &mut AppData) -> Result<Self::NextState> {
-        app_data.input.clear_line();
+        app_data.input.apply(self.0);
         Ok(ChildPromptMode.into())
     }
 }
 
-impl ValidIn<BroadcastingMode> for ClearLineAction {
+impl ValidIn<BroadcastingMode> for LineEditAction {
     type NextState = AppMode;
 
     fn execute(self, _state: BroadcastingMode, app_data: &mut AppData) -> Result<Self::NextState> {
-        app_data.input.clear_line();
+        app_data.input.apply(self.0);
         Ok(BroadcastingMode.into())
     }
 }
 
-impl ValidIn<ReconnectPromptMode> for ClearLineAction {
+impl ValidIn<ReconnectPromptMode> for LineEditAction {
     type NextState = AppMode;
 
     fn execute(
@@ -961,12 +1995,12 @@ impl ValidIn<ReconnectPromptMode> for ClearLineAction {
         _state: ReconnectPromptMode,
         app_data: &mut AppData,
     ) -> Result<Self::NextState> {
-        app_data.input.clear_line();
+        app_data.input.apply(self.0);
         Ok(ReconnectPromptMode.into())
     }
 }
 
-impl ValidIn<TerminalPromptMode> for ClearLineAction {
+impl ValidIn<TerminalPromptMode> for LineEditAction {
     type NextState = AppMode;
 
     fn execute(
@@ -974,12 +2008,12 @@ impl ValidIn<TerminalPromptMode> for ClearLineAction {
         _state: TerminalPromptMode,
         app_data: &mut AppData,
     ) -> Result<Self::NextState> {
-        app_data.input.clear_line();
+        app_data.input.apply(self.0);
         Ok(TerminalPromptMode.into())
     }
 }
 
-impl ValidIn<CustomAgentCommandMode> for ClearLineAction {
+impl ValidIn<CustomAgentCommandMode> for LineEditAction {
     type NextState = AppMode;
 
     fn execute(
@@ -987,12 +2021,12 @@ impl ValidIn<CustomAgentCommandMode> for ClearLineAction {
         _state: CustomAgentCommandMode,
         app_data: &mut AppData,
     ) -> Result<Self::NextState> {
-        app_data.input.clear_line();
+        app_data.input.apply(self.0);
         Ok(CustomAgentCommandMode.into())
     }
 }
 
-impl ValidIn<SynthesisPromptMode> for ClearLineAction {
+impl ValidIn<SynthesisPromptMode> for LineEditAction {
     type NextState = AppMode;
 
     fn execute(
@@ -1000,96 +2034,79 @@ impl ValidIn<SynthesisPromptMode> for ClearLineAction {
         _state: SynthesisPromptMode,
         app_data: &mut AppData,
     ) -> Result<Self::NextState> {
-        app_data.input.clear_line();
+        app_data.input.apply(self.0);
         Ok(SynthesisPromptMode.into())
     }
 }
 
-impl ValidIn<CreatingMode> for DeleteWordAction {
-    type NextState = AppMode;
-
-    fn execute(self, _state: CreatingMode, app_data: &mut AppData) -> Result<Self::NextState> {
-        app_data.input.delete_word();
-        Ok(CreatingMode.into())
-    }
-}
-
-impl ValidIn<PromptingMode> for DeleteWordAction {
+impl ValidIn<EditTaskMode> for LineEditAction {
     type NextState = AppMode;
 
-    fn execute(self, _state: PromptingMode, app_data: &mut AppData) -> Result<Self::NextState> {
-        app_data.input.delete_word();
-        Ok(PromptingMode.into())
+    fn execute(self, _state: EditTaskMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.apply(self.0);
+        Ok(EditTaskMode.into())
     }
 }
 
-impl ValidIn<ChildPromptMode> for DeleteWordAction {
+impl ValidIn<EditScratchpadMode> for LineEditAction {
     type NextState = AppMode;
 
-    fn execute(self, _state: ChildPromptMode, app_data: &mut AppData) -> Result<Self::NextState> {
-        app_data.input.delete_word();
-        Ok(ChildPromptMode.into())
+    fn execute(
+        self,
+        _state: EditScratchpadMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.apply(self.0);
+        Ok(EditScratchpadMode.into())
     }
 }
 
-impl ValidIn<BroadcastingMode> for DeleteWordAction {
+impl ValidIn<DependencyMessageMode> for LineEditAction {
     type NextState = AppMode;
 
-    fn execute(self, _state: BroadcastingMode, app_data: &mut AppData) -> Result<Self::NextState> {
-        app_data.input.delete_word();
-        Ok(BroadcastingMode.into())
+    fn execute(
+        self,
+        _state: DependencyMessageMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.input.apply(self.0);
+        Ok(DependencyMessageMode.into())
     }
 }
 
-impl ValidIn<ReconnectPromptMode> for DeleteWordAction {
+impl ValidIn<ReplyMode> for LineEditAction {
     type NextState = AppMode;
 
-    fn execute(
-        self,
-        _state: ReconnectPromptMode,
-        app_data: &mut AppData,
-    ) -> Result<Self::NextState> {
-        app_data.input.delete_word();
-        Ok(ReconnectPromptMode.into())
+    fn execute(self, _state: ReplyMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.apply(self.0);
+        Ok(ReplyMode.into())
     }
 }
 
-impl ValidIn<TerminalPromptMode> for DeleteWordAction {
+impl ValidIn<ScopeMode> for LineEditAction {
     type NextState = AppMode;
 
-    fn execute(
-        self,
-        _state: TerminalPromptMode,
-        app_data: &mut AppData,
-    ) -> Result<Self::NextState> {
-        app_data.input.delete_word();
-        Ok(TerminalPromptMode.into())
+    fn execute(self, _state: ScopeMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.apply(self.0);
+        Ok(ScopeMode.into())
     }
 }
 
-impl ValidIn<CustomAgentCommandMode> for DeleteWordAction {
+impl ValidIn<SwitchModelMode> for LineEditAction {
     type NextState = AppMode;
 
-    fn execute(
-        self,
-        _state: CustomAgentCommandMode,
-        app_data: &mut AppData,
-    ) -> Result<Self::NextState> {
-        app_data.input.delete_word();
-        Ok(CustomAgentCommandMode.into())
+    fn execute(self, _state: SwitchModelMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.apply(self.0);
+        Ok(SwitchModelMode.into())
     }
 }
 
-impl ValidIn<SynthesisPromptMode> for DeleteWordAction {
+impl ValidIn<AddRepoMode> for LineEditAction {
     type NextState = AppMode;
 
-    fn execute(
-        self,
-        _state: SynthesisPromptMode,
-        app_data: &mut AppData,
-    ) -> Result<Self::NextState> {
-        app_data.input.delete_word();
-        Ok(SynthesisPromptMode.into())
+    fn execute(self, _state: AddRepoMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.apply(self.0);
+        Ok(AddRepoMode.into())
     }
 }
 
@@ -1111,7 +2128,11 @@ impl ValidIn<PromptingMode> for SubmitAction {
     type NextState = AppMode;
 
     fn execute(self, _state: PromptingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        if app_data.hold_prompt_for_review(true) {
+            return Ok(PromptingMode.into());
+        }
         let input = app_data.input.buffer.clone();
+        app_data.record_input_history(InputHistoryKind::Prompt, &input);
         let short_id = &Uuid::new_v4().to_string()[..8];
         let title = format!("Agent ({short_id})");
         let prompt = if input.is_empty() {
@@ -1128,6 +2149,9 @@ impl ValidIn<ChildPromptMode> for SubmitAction {
     type NextState = AppMode;
 
     fn execute(self, _state: ChildPromptMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        if app_data.hold_prompt_for_review(true) {
+            return Ok(ChildPromptMode.into());
+        }
         let input = app_data.input.buffer.clone();
         let prompt = if input.is_empty() {
             None
@@ -1148,7 +2172,11 @@ impl ValidIn<BroadcastingMode> for SubmitAction {
         if input.is_empty() {
             return Ok(AppMode::normal());
         }
+        if app_data.hold_prompt_for_review(false) {
+            return Ok(BroadcastingMode.into());
+        }
 
+        app_data.record_input_history(InputHistoryKind::Broadcast, &input);
         ok_or_error_modal(Actions::new().broadcast_to_leaves(app_data, &input))
     }
 }
@@ -1161,6 +2189,9 @@ impl ValidIn<ReconnectPromptMode> for SubmitAction {
         _state: ReconnectPromptMode,
         app_data: &mut AppData,
     ) -> Result<Self::NextState> {
+        if app_data.hold_prompt_for_review(false) {
+            return Ok(ReconnectPromptMode.into());
+        }
         let input = app_data.input.buffer.clone();
 
         if let Some(ref mut conflict) = app_data.spawn.worktree_conflict {
@@ -1180,6 +2211,7 @@ impl ValidIn<TerminalPromptMode> for SubmitAction {
         app_data: &mut AppData,
     ) -> Result<Self::NextState> {
         let input = app_data.input.buffer.clone();
+        app_data.record_input_history(InputHistoryKind::TerminalCommand, &input);
         let command = if input.is_empty() {
             None
         } else {
@@ -1224,9 +2256,9 @@ impl ValidIn<CustomAgentCommandMode> for SubmitAction {
         }
 
         if let Err(err) = app_data.settings.save() {
-            return Ok(ErrorModalMode {
-                message: format!("Failed to save settings: {err}"),
-            }
+            return Ok(ErrorModalMode::new(TenexError::SettingsSave {
+                detail: err.to_string(),
+            })
             .into());
         }
 
@@ -1243,6 +2275,9 @@ impl ValidIn<SynthesisPromptMode> for SubmitAction {
         _state: SynthesisPromptMode,
         app_data: &mut AppData,
     ) -> Result<Self::NextState> {
+        if app_data.hold_prompt_for_review(false) {
+            return Ok(SynthesisPromptMode.into());
+        }
         let input = app_data.input.buffer.clone();
         let prompt = input.trim();
         let prompt = if prompt.is_empty() {
@@ -1251,7 +2286,72 @@ impl ValidIn<SynthesisPromptMode> for SubmitAction {
             Some(prompt)
         };
 
-        ok_or_error_modal(Actions::new().synthesize_with_prompt(app_data, prompt))
+        let options = app_data.synthesis_options;
+        ok_or_error_modal(Actions::new().synthesize_with_prompt(app_data, options, prompt))
+    }
+}
+
+impl ValidIn<EditTaskMode> for SubmitAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: EditTaskMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        let input = app_data.input.buffer.clone();
+        let task = input.trim();
+        if task.is_empty() {
+            return Ok(EditTaskMode.into());
+        }
+        if app_data.hold_prompt_for_review(false) {
+            return Ok(EditTaskMode.into());
+        }
+
+        ok_or_error_modal(Actions::new().send_revised_task(app_data, task))
+    }
+}
+
+impl ValidIn<EditScratchpadMode> for SubmitAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: EditScratchpadMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        let content = app_data.input.buffer.clone();
+        ok_or_error_modal(Actions::save_scratchpad(app_data, &content))
+    }
+}
+
+impl ValidIn<DependencyMessageMode> for SubmitAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: DependencyMessageMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        let message = app_data.input.buffer.trim().to_string();
+        if message.is_empty() {
+            return Ok(DependencyMessageMode.into());
+        }
+        if app_data.hold_prompt_for_review(false) {
+            return Ok(DependencyMessageMode.into());
+        }
+        ok_or_error_modal(Actions::save_dependency(app_data, &message))
+    }
+}
+
+impl ValidIn<ReplyMode> for SubmitAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ReplyMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        let reply = app_data.input.buffer.trim().to_string();
+        if reply.is_empty() {
+            return Ok(ReplyMode.into());
+        }
+        if app_data.hold_prompt_for_review(false) {
+            return Ok(ReplyMode.into());
+        }
+        ok_or_error_modal(Actions::new().send_reply(app_data, &reply))
     }
 }
 
@@ -1266,7 +2366,8 @@ impl ValidIn<CreatingMode> for CancelAction {
 impl ValidIn<PromptingMode> for CancelAction {
     type NextState = AppMode;
 
-    fn execute(self, _state: PromptingMode, _app_data: &mut AppData) -> Result<Self::NextState> {
+    fn execute(self, _state: PromptingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.spawn.context_files.clear();
         Ok(AppMode::normal())
     }
 }
@@ -1274,11 +2375,64 @@ impl ValidIn<PromptingMode> for CancelAction {
 impl ValidIn<ChildPromptMode> for CancelAction {
     type NextState = AppMode;
 
-    fn execute(self, _state: ChildPromptMode, _app_data: &mut AppData) -> Result<Self::NextState> {
+    fn execute(self, _state: ChildPromptMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.spawn.context_files.clear();
         Ok(AppMode::normal())
     }
 }
 
+impl ValidIn<PromptingMode> for AttachContextFileAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: PromptingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(Actions::start_context_file_picker(app_data, false))
+    }
+}
+
+impl ValidIn<CreatingMode> for EditProgramAction {
+    type NextState = AppMode;
+
+    fn execute(self, state: CreatingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.edit_new_agent_command();
+        Ok(state.into())
+    }
+}
+
+impl ValidIn<PromptingMode> for EditProgramAction {
+    type NextState = AppMode;
+
+    fn execute(self, state: PromptingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.edit_new_agent_command();
+        Ok(state.into())
+    }
+}
+
+impl ValidIn<CreatingMode> for FinishProgramAction {
+    type NextState = AppMode;
+
+    fn execute(self, state: CreatingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.finish_new_agent_command(self.0);
+        Ok(state.into())
+    }
+}
+
+impl ValidIn<PromptingMode> for FinishProgramAction {
+    type NextState = AppMode;
+
+    fn execute(self, state: PromptingMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.finish_new_agent_command(self.0);
+        Ok(state.into())
+    }
+}
+
+impl ValidIn<ChildPromptMode> for AttachContextFileAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ChildPromptMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(Actions::start_context_file_picker(app_data, true))
+    }
+}
+
 impl ValidIn<BroadcastingMode> for CancelAction {
     type NextState = AppMode;
 
@@ -1296,6 +2450,8 @@ impl ValidIn<ReconnectPromptMode> for CancelAction {
         app_data: &mut AppData,
     ) -> Result<Self::NextState> {
         app_data.spawn.worktree_conflict = None;
+        app_data.spawn.ticket = None;
+        app_data.spawn.program = None;
         Ok(AppMode::normal())
     }
 }
@@ -1335,3 +2491,102 @@ impl ValidIn<SynthesisPromptMode> for CancelAction {
         Ok(AppMode::normal())
     }
 }
+
+impl ValidIn<EditTaskMode> for CancelAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: EditTaskMode, _app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(AppMode::normal())
+    }
+}
+
+impl ValidIn<EditScratchpadMode> for CancelAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: EditScratchpadMode,
+        _app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        Ok(AppMode::normal())
+    }
+}
+
+impl ValidIn<DependencyMessageMode> for CancelAction {
+    type NextState = AppMode;
+
+    fn execute(
+        self,
+        _state: DependencyMessageMode,
+        app_data: &mut AppData,
+    ) -> Result<Self::NextState> {
+        app_data.dependency_picker.clear();
+        app_data.input.clear();
+        Ok(AppMode::normal())
+    }
+}
+
+impl ValidIn<AddRepoMode> for SubmitAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: AddRepoMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        let path = app_data.input.buffer.trim().to_string();
+        if path.is_empty() {
+            return Ok(AddRepoMode.into());
+        }
+        ok_or_error_modal(Actions::add_repo(app_data, &path))
+    }
+}
+
+impl ValidIn<ReplyMode> for CancelAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ReplyMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.clear();
+        Ok(AppMode::normal())
+    }
+}
+
+impl ValidIn<ScopeMode> for SubmitAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ScopeMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        let input = app_data.input.buffer.clone();
+        ok_or_error_modal(Actions::new().set_scope(app_data, &input))
+    }
+}
+
+impl ValidIn<SwitchModelMode> for SubmitAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: SwitchModelMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        let input = app_data.input.buffer.clone();
+        ok_or_error_modal(Actions::new().switch_model(app_data, &input))
+    }
+}
+
+impl ValidIn<ScopeMode> for CancelAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: ScopeMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.clear();
+        Ok(AppMode::normal())
+    }
+}
+
+impl ValidIn<SwitchModelMode> for CancelAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: SwitchModelMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        app_data.input.clear();
+        Ok(AppMode::normal())
+    }
+}
+
+impl ValidIn<AddRepoMode> for CancelAction {
+    type NextState = AppMode;
+
+    fn execute(self, _state: AddRepoMode, app_data: &mut AppData) -> Result<Self::NextState> {
+        Ok(Actions::open_repo_picker(app_data))
+    }
+}
@@ -0,0 +1,64 @@
+//! Dependencies between agents: a message held for one agent until another reaches a status
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// What the agent being waited for has to do before the held message is sent.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyTrigger {
+    /// It stops producing output and waits for input (it finished its turn).
+    #[default]
+    Waiting,
+    /// It is killed, for example when a synthesis collects it.
+    Gone,
+}
+
+impl DependencyTrigger {
+    /// Phrase completing "Wait until <agent> ...".
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Waiting => "finishes its turn",
+            Self::Gone => "is killed",
+        }
+    }
+
+    /// The other trigger.
+    #[must_use]
+    pub const fn toggled(self) -> Self {
+        match self {
+            Self::Waiting => Self::Gone,
+            Self::Gone => Self::Waiting,
+        }
+    }
+}
+
+/// A message held for an agent until the agent it depends on reaches `trigger`.
+///
+/// An agent that is killed always releases the agents waiting for it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentDependency {
+    /// Agent being waited for.
+    pub agent_id: Uuid,
+    /// When the message is sent.
+    #[serde(default)]
+    pub trigger: DependencyTrigger,
+    /// Message sent to the waiting agent once it is released.
+    pub message: String,
+}
+
+impl AgentDependency {
+    /// Notice sent by default when the agent titled `title` reaches `trigger`.
+    #[must_use]
+    pub fn default_message(title: &str, trigger: DependencyTrigger) -> String {
+        match trigger {
+            DependencyTrigger::Waiting => format!(
+                "The agent \"{title}\" you were waiting for has finished its turn. Check its work and continue."
+            ),
+            DependencyTrigger::Gone => format!(
+                "The agent \"{title}\" you were waiting for is done and has been closed. Check its work and continue."
+            ),
+        }
+    }
+}
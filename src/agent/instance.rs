@@ -1,9 +1,10 @@
 //! Agent instance definition
 
-use super::Status;
+use super::{AgentDependency, RestartPolicy, Status};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 /// What kind of workspace an agent runs in.
@@ -31,6 +32,128 @@ pub enum AgentRuntime {
     Docker,
 }
 
+/// Optional sandbox Tenex wraps around a host agent process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentSandbox {
+    /// Run the agent without a sandbox.
+    #[default]
+    Off,
+    /// Only allow writes inside the agent's worktree (and its git metadata); network allowed.
+    Worktree,
+    /// Like `Worktree`, but also deny network access.
+    Offline,
+}
+
+impl AgentSandbox {
+    /// All sandbox policies, in cycle order.
+    pub const ALL: &'static [Self] = &[Self::Off, Self::Worktree, Self::Offline];
+
+    /// Lowercase label shown in the UI.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Worktree => "worktree",
+            Self::Offline => "offline",
+        }
+    }
+
+    /// Whether the agent runs without a sandbox.
+    #[must_use]
+    pub const fn is_off(&self) -> bool {
+        matches!(self, Self::Off)
+    }
+
+    /// Whether network access is denied inside the sandbox.
+    #[must_use]
+    pub const fn denies_network(self) -> bool {
+        matches!(self, Self::Offline)
+    }
+
+    /// The next policy in cycle order.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Off => Self::Worktree,
+            Self::Worktree => Self::Offline,
+            Self::Offline => Self::Off,
+        }
+    }
+}
+
+/// Which of an agent's events raise a notice in the status bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentNotifications {
+    /// Notify when the agent asks a question, following the `inbox_scope` setting.
+    #[default]
+    Default,
+    /// Never notify; questions still reach the inbox.
+    Muted,
+    /// Notify when the agent finishes its turn, but not about its questions.
+    FinishOnly,
+    /// Treat every stop to wait for input as a question, whatever the agent printed.
+    AnyQuestion,
+}
+
+impl AgentNotifications {
+    /// Lowercase label shown in the UI.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Default => "questions",
+            Self::Muted => "muted",
+            Self::FinishOnly => "finish only",
+            Self::AnyQuestion => "any question",
+        }
+    }
+
+    /// Whether the agent follows the default preference.
+    #[must_use]
+    pub const fn is_default(&self) -> bool {
+        matches!(self, Self::Default)
+    }
+
+    /// The next preference in cycle order.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Default => Self::Muted,
+            Self::Muted => Self::FinishOnly,
+            Self::FinishOnly => Self::AnyQuestion,
+            Self::AnyQuestion => Self::Default,
+        }
+    }
+}
+
+/// Where an agent stands against its budgets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetState {
+    /// Running within its budgets.
+    #[default]
+    Within,
+    /// Its processes are stopped because it, or all agents together, reached a budget.
+    Paused,
+    /// Going on past its own budget, as confirmed after it was paused.
+    Overridden,
+}
+
+impl BudgetState {
+    /// Whether the agent is within its budgets.
+    #[must_use]
+    pub const fn is_within(&self) -> bool {
+        matches!(self, Self::Within)
+    }
+
+    /// Whether the agent's processes are stopped over budget.
+    #[must_use]
+    pub const fn is_paused(self) -> bool {
+        matches!(self, Self::Paused)
+    }
+}
+
 /// A single agent instance
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Agent {
@@ -78,6 +201,10 @@ pub struct Agent {
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub runtime_scope: String,
 
+    /// Sandbox policy applied to host agent processes.
+    #[serde(default, skip_serializing_if = "AgentSandbox::is_off")]
+    pub sandbox: AgentSandbox,
+
     /// Mux session name
     #[serde(alias = "tmux_session")]
     pub mux_session: String,
@@ -88,11 +215,19 @@ pub struct Agent {
     /// When the agent was last updated
     pub updated_at: DateTime<Utc>,
 
+    /// When the agent's program exited or was stopped for running too long (`None` while it
+    /// runs). Its elapsed and idle time stop here.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+
     /// Parent agent ID (None for root agents)
     #[serde(default)]
     pub parent_id: Option<Uuid>,
 
     /// Window index within the root ancestor's session (None for root agents)
+    ///
+    /// The mux daemon never reuses or renumbers indices, so this stays valid when sibling
+    /// windows are killed.
     #[serde(default)]
     pub window_index: Option<u32>,
 
@@ -103,6 +238,105 @@ pub struct Agent {
     /// Whether this is a terminal (not a Claude agent) - excluded from broadcast
     #[serde(default)]
     pub is_terminal: bool,
+
+    /// Total time (milliseconds) the agent's pane was actively producing output.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub active_ms: u64,
+
+    /// Branch the agent's worktree branch was created from (roots only; `None` if unknown).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_branch: Option<String>,
+
+    /// Prompt the agent was started with (`None` if it was started without one).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+
+    /// Ticket the agent works on (`PROJ-123`, or `#123` for a GitHub issue; roots only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ticket: Option<String>,
+
+    /// tmux session this agent was imported from (roots only; `None` for native agents).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub imported_tmux_session: Option<String>,
+
+    /// Swarm file this agent was created from by `tenex apply` (roots only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub swarm_file: Option<PathBuf>,
+
+    /// Message held for this agent until another agent reaches a status.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blocked_by: Option<AgentDependency>,
+
+    /// Root agent of the swarm this root was started for in another repository (`tenex apply`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub swarm_root: Option<Uuid>,
+
+    /// Directory of the repository this agent is told to keep its changes to (`services/api/`),
+    /// relative to the repository root. Its diff view shows only that subtree.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+
+    /// Which of this agent's events raise a notice in the status bar.
+    #[serde(default, skip_serializing_if = "AgentNotifications::is_default")]
+    pub notifications: AgentNotifications,
+
+    /// Whether the agent is restarted after its program exits (`None` follows the
+    /// `restart_policy` setting).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restart_policy: Option<RestartPolicy>,
+
+    /// Times the agent was restarted after its program exited.
+    #[serde(default, skip_serializing_if = "is_zero_u32")]
+    pub restarts: u32,
+
+    /// Longest the agent may run, in minutes, before it is asked to wrap up and then stopped
+    /// (`None` follows the `max_runtime_mins` setting; `0` means no limit).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_runtime_mins: Option<u64>,
+
+    /// When the agent was asked to wrap up because it reached its maximum runtime.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wrap_up_requested_at: Option<DateTime<Utc>>,
+
+    /// Estimated spend of each of the agent's conversations, in millionths of a US dollar,
+    /// keyed by conversation id.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub costs: BTreeMap<String, u64>,
+
+    /// Where the agent stands against its budgets.
+    #[serde(default, skip_serializing_if = "BudgetState::is_within")]
+    pub budget: BudgetState,
+}
+
+/// Serde helper to omit zero counters.
+#[expect(
+    clippy::trivially_copy_pass_by_ref,
+    reason = "serde's skip_serializing_if passes fields by reference"
+)]
+const fn is_zero(value: &u64) -> bool {
+    *value == 0
+}
+
+/// Serde helper to omit zero counters.
+#[expect(
+    clippy::trivially_copy_pass_by_ref,
+    reason = "serde's skip_serializing_if passes fields by reference"
+)]
+const fn is_zero_u32(value: &u32) -> bool {
+    *value == 0
+}
+
+/// Format a duration compactly using its largest unit (e.g. `3d`, `2h`, `5m`, `12s`).
+fn compact_duration(duration: chrono::Duration) -> String {
+    if duration.num_days() > 0 {
+        format!("{}d", duration.num_days())
+    } else if duration.num_hours() > 0 {
+        format!("{}h", duration.num_hours())
+    } else if duration.num_minutes() > 0 {
+        format!("{}m", duration.num_minutes())
+    } else {
+        format!("{}s", duration.num_seconds().max(0))
+    }
 }
 
 /// Default value for collapsed field
@@ -143,13 +377,31 @@ impl Agent {
             workspace_kind: WorkspaceKind::GitWorktree,
             runtime: AgentRuntime::Host,
             runtime_scope: String::new(),
+            sandbox: AgentSandbox::Off,
             mux_session,
             created_at: now,
             updated_at: now,
+            finished_at: None,
             parent_id: None,
             window_index: None,
             collapsed: true,
             is_terminal: false,
+            active_ms: 0,
+            base_branch: None,
+            prompt: None,
+            ticket: None,
+            imported_tmux_session: None,
+            swarm_file: None,
+            blocked_by: None,
+            swarm_root: None,
+            scope: None,
+            notifications: AgentNotifications::Default,
+            restart_policy: None,
+            restarts: 0,
+            max_runtime_mins: None,
+            wrap_up_requested_at: None,
+            costs: BTreeMap::new(),
+            budget: BudgetState::Within,
         }
     }
 
@@ -177,13 +429,31 @@ impl Agent {
             workspace_kind: WorkspaceKind::GitWorktree,
             runtime: AgentRuntime::Host,
             runtime_scope: String::new(),
+            sandbox: AgentSandbox::Off,
             mux_session: config.mux_session,
             created_at: now,
             updated_at: now,
+            finished_at: None,
             parent_id: Some(config.parent_id),
             window_index: Some(config.window_index),
             collapsed: true,
             is_terminal: false,
+            active_ms: 0,
+            base_branch: None,
+            prompt: None,
+            ticket: None,
+            imported_tmux_session: None,
+            swarm_file: None,
+            blocked_by: None,
+            swarm_root: None,
+            scope: None,
+            notifications: AgentNotifications::Default,
+            restart_policy: None,
+            restarts: 0,
+            max_runtime_mins: None,
+            wrap_up_requested_at: None,
+            costs: BTreeMap::new(),
+            budget: BudgetState::Within,
         }
     }
 
@@ -207,12 +477,27 @@ impl Agent {
         self.is_terminal || self.program == "terminal"
     }
 
+    /// Whether `path`, relative to the repository root, lies within this agent's scope. Every
+    /// path does when the agent has no scope.
+    #[must_use]
+    pub fn is_in_scope(&self, path: &Path) -> bool {
+        self.scope
+            .as_deref()
+            .is_none_or(|scope| path.starts_with(scope))
+    }
+
     /// Whether this agent supports Tenex git operations (branch/worktree management).
     #[must_use]
     pub const fn is_git_workspace(&self) -> bool {
         matches!(self.workspace_kind, WorkspaceKind::GitWorktree)
     }
 
+    /// Estimated spend of all of the agent's conversations, in millionths of a US dollar.
+    #[must_use]
+    pub fn cost_micros(&self) -> u64 {
+        self.costs.values().copied().fold(0, u64::saturating_add)
+    }
+
     /// Get a short display ID (first 8 chars of UUID)
     #[must_use]
     pub fn short_id(&self) -> String {
@@ -232,37 +517,95 @@ impl Agent {
         }
     }
 
-    /// Update the agent's status
+    /// Update the agent's status, noting when it stopped
     pub fn set_status(&mut self, status: Status) {
+        let now = Utc::now();
+        self.finished_at = if status.is_stopped() {
+            self.finished_at.or(Some(now))
+        } else {
+            None
+        };
         self.status = status;
-        self.updated_at = Utc::now();
+        self.updated_at = now;
     }
 
     /// Get the age of the agent as a human-readable string
     #[must_use]
     pub fn age_string(&self) -> String {
-        let duration = Utc::now().signed_duration_since(self.created_at);
-
-        if duration.num_days() > 0 {
-            format!("{}d", duration.num_days())
-        } else if duration.num_hours() > 0 {
-            format!("{}h", duration.num_hours())
-        } else if duration.num_minutes() > 0 {
-            format!("{}m", duration.num_minutes())
+        compact_duration(self.elapsed())
+    }
+
+    /// Wall-clock time from the agent's creation until now, or until it stopped
+    ///
+    /// Agents that stopped before `finished_at` was recorded stop at their last update.
+    #[must_use]
+    pub fn elapsed(&self) -> chrono::Duration {
+        let end = self.finished_at.unwrap_or_else(|| {
+            if self.status.is_stopped() {
+                self.updated_at
+            } else {
+                Utc::now()
+            }
+        });
+        end.signed_duration_since(self.created_at)
+    }
+
+    /// Time the agent spent actively producing output
+    #[must_use]
+    pub fn active_duration(&self) -> chrono::Duration {
+        chrono::Duration::milliseconds(i64::try_from(self.active_ms).unwrap_or(i64::MAX))
+    }
+
+    /// Time the agent spent idle (elapsed minus active)
+    #[must_use]
+    pub fn idle_duration(&self) -> chrono::Duration {
+        (self.elapsed() - self.active_duration()).max(chrono::Duration::zero())
+    }
+
+    /// Get the active time as a human-readable string
+    #[must_use]
+    pub fn active_string(&self) -> String {
+        compact_duration(self.active_duration())
+    }
+
+    /// Get the idle time as a human-readable string
+    #[must_use]
+    pub fn idle_string(&self) -> String {
+        compact_duration(self.idle_duration())
+    }
+
+    /// Where the agent writes its handoff notes: `.tenex/handoff.md` in its worktree.
+    ///
+    /// Children share their root's worktree, so each writes its own
+    /// `.tenex/handoff-<short id>.md` instead.
+    #[must_use]
+    pub fn handoff_path(&self) -> PathBuf {
+        let file_name = if self.is_root() {
+            crate::prompts::HANDOFF_FILE_NAME.to_string()
         } else {
-            format!("{}s", duration.num_seconds().max(0))
-        }
+            format!("handoff-{}.md", self.short_id())
+        };
+        self.worktree_path.join(".tenex").join(file_name)
+    }
+
+    /// Title for the agent's mux window and the host terminal: its title and status
+    #[must_use]
+    pub fn window_title(&self) -> String {
+        format!("{} [{}]", self.title, self.status)
     }
 
     /// Get a one-line summary of the agent
     #[must_use]
     pub fn summary(&self) -> String {
         format!(
-            "[{}] {} ({}) - {}",
+            "[{}] {} ({}) - {} - {} elapsed, {} active, {} idle",
             self.short_id(),
             self.title,
             self.program,
-            self.status
+            self.status,
+            self.age_string(),
+            self.active_string(),
+            self.idle_string()
         )
     }
 }
@@ -0,0 +1,88 @@
+//! Recently killed agents whose branches were deleted, kept so their work can be recovered.
+//!
+//! The list lives next to the state file (`killed.json`) rather than in it, since it only
+//! grows on kill and needs none of the state file's merge handling.
+
+use super::Agent;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Most killed agents remembered.
+const MAX_KILLED: usize = 20;
+
+/// Days a killed agent is remembered (git keeps unreachable commits for about two weeks).
+const RETENTION_DAYS: i64 = 14;
+
+/// A root agent that was killed along with its branch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KilledAgent {
+    /// Agent title.
+    pub title: String,
+    /// Deleted branch name.
+    pub branch: String,
+    /// Repository the branch lived in.
+    pub repo_root: PathBuf,
+    /// Branch tip when the agent was killed, if it could be read.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tip: Option<String>,
+    /// When the agent was created.
+    pub created_at: DateTime<Utc>,
+    /// When the agent was killed.
+    pub killed_at: DateTime<Utc>,
+}
+
+impl KilledAgent {
+    /// Record of `agent` being killed now, with its branch at `tip`.
+    #[must_use]
+    pub fn new(agent: &Agent, repo_root: PathBuf, tip: Option<String>) -> Self {
+        Self {
+            title: agent.title.clone(),
+            branch: agent.branch.clone(),
+            repo_root,
+            tip,
+            created_at: agent.created_at,
+            killed_at: Utc::now(),
+        }
+    }
+}
+
+/// Path of the killed-agents list for the state file at `state_path`.
+#[must_use]
+pub fn killed_agents_path(state_path: &Path) -> PathBuf {
+    state_path.with_file_name("killed.json")
+}
+
+/// Recently killed agents, most recent first. A missing or unreadable list is empty.
+#[must_use]
+pub fn load_killed_agents(path: &Path) -> Vec<KilledAgent> {
+    let cutoff = Utc::now() - Duration::days(RETENTION_DAYS);
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str::<Vec<KilledAgent>>(&contents).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|killed| killed.killed_at >= cutoff)
+        .collect()
+}
+
+/// Add `killed` to the list at `path`, dropping entries past the retention limits.
+///
+/// # Errors
+///
+/// Returns an error if the list cannot be written.
+pub fn record_killed_agent(path: &Path, killed: KilledAgent) -> Result<()> {
+    let mut agents = load_killed_agents(path);
+    agents.insert(0, killed);
+    agents.truncate(MAX_KILLED);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let contents =
+        serde_json::to_string_pretty(&agents).context("Failed to serialize killed agents")?;
+    fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
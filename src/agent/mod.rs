@@ -1,9 +1,23 @@
 //! Agent management module
 
+mod dependency;
 mod instance;
+mod killed;
+mod restart;
+mod sealed;
 mod status;
 mod storage;
+mod ticket;
+mod trash;
 
-pub use instance::{Agent, AgentRuntime, ChildConfig, WorkspaceKind};
+pub use dependency::{AgentDependency, DependencyTrigger};
+pub use instance::{
+    Agent, AgentNotifications, AgentRuntime, AgentSandbox, BudgetState, ChildConfig, WorkspaceKind,
+};
+pub use killed::{KilledAgent, killed_agents_path, load_killed_agents, record_killed_agent};
+pub use restart::{RestartMode, RestartPolicy};
+pub use sealed::{Locked, PASSPHRASE_ENV};
 pub use status::Status;
 pub use storage::{Storage, VisibleAgentInfo};
+pub use ticket::{branch_title, is_github_issue, split_ticket};
+pub use trash::{TrashEntry, load_trash, move_to_trash, purge_trash, trash_dir};
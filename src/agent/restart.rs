@@ -0,0 +1,129 @@
+//! Restart policies: whether Tenex starts an agent's program again after it exits
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Longest wait before a restart, however many restarts came before.
+const MAX_BACKOFF: Duration = Duration::from_mins(5);
+
+/// Which exits of an agent's program are followed by a restart.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartMode {
+    /// Leave the agent finished.
+    #[default]
+    Never,
+    /// Restart when the program exits with a non-zero code.
+    OnFailure,
+    /// Restart whatever the exit code.
+    Always,
+}
+
+impl RestartMode {
+    /// Lowercase label shown in the UI.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Never => "never",
+            Self::OnFailure => "on failure",
+            Self::Always => "always",
+        }
+    }
+
+    /// The next mode in cycle order.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Never => Self::OnFailure,
+            Self::OnFailure => Self::Always,
+            Self::Always => Self::Never,
+        }
+    }
+}
+
+/// When and how often an agent whose program exited is started again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RestartPolicy {
+    /// Which exits are followed by a restart.
+    #[serde(default)]
+    pub mode: RestartMode,
+    /// Restarts allowed before the agent is left finished.
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Wait before the first restart; each later restart waits twice as long as the one
+    /// before, up to 5 minutes.
+    #[serde(default = "default_backoff_secs")]
+    pub backoff_secs: u64,
+}
+
+const fn default_max_retries() -> u32 {
+    3
+}
+
+const fn default_backoff_secs() -> u64 {
+    5
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            mode: RestartMode::Never,
+            max_retries: default_max_retries(),
+            backoff_secs: default_backoff_secs(),
+        }
+    }
+}
+
+impl RestartPolicy {
+    /// Policy of root agents when neither the agent nor the settings set one: start the
+    /// program again after any exit, such as a self-update, up to 3 times.
+    #[must_use]
+    pub const fn for_root_agents() -> Self {
+        Self {
+            mode: RestartMode::Always,
+            max_retries: default_max_retries(),
+            backoff_secs: 1,
+        }
+    }
+
+    /// Whether this is the default policy (never restart).
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether a program that exited with `code` after `restarts` earlier restarts is started
+    /// again.
+    #[must_use]
+    pub const fn should_restart(&self, code: u32, restarts: u32) -> bool {
+        if restarts >= self.max_retries {
+            return false;
+        }
+        match self.mode {
+            RestartMode::Never => false,
+            RestartMode::OnFailure => code != 0,
+            RestartMode::Always => true,
+        }
+    }
+
+    /// Wait between the exit and the restart that follows `restarts` earlier restarts.
+    #[must_use]
+    pub fn backoff(&self, restarts: u32) -> Duration {
+        let factor = 1_u64.checked_shl(restarts).unwrap_or(u64::MAX);
+        Duration::from_secs(self.backoff_secs.saturating_mul(factor)).min(MAX_BACKOFF)
+    }
+
+    /// Summary shown in the agent details (`on failure, up to 3 times, 5s apart`).
+    #[must_use]
+    pub fn summary(&self) -> String {
+        if self.mode == RestartMode::Never {
+            return self.mode.label().to_string();
+        }
+        format!(
+            "{}, up to {} times, {}s apart at first",
+            self.mode.label(),
+            self.max_retries,
+            self.backoff_secs
+        )
+    }
+}
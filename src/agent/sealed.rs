@@ -0,0 +1,173 @@
+//! Encryption of sensitive agent fields in the state file.
+//!
+//! When `TENEX_PASSPHRASE` is set, [`Storage`](super::Storage) seals these fields before it
+//! writes the state file and opens them when it reads it, so they are in plain text only in
+//! memory. A sealed value is `sealed:v1:` followed by the base64 of a random nonce and the
+//! ChaCha20-Poly1305 ciphertext, under a key derived from the passphrase with PBKDF2 and the
+//! salt stored in the state file.
+
+use std::num::NonZeroU32;
+use std::sync::Arc;
+
+use anyhow::Result;
+use base64::Engine as _;
+use parking_lot::Mutex;
+use ring::aead::{Aad, CHACHA20_POLY1305, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::rand::{SecureRandom as _, SystemRandom};
+
+use super::Agent;
+
+/// Environment variable holding the passphrase that encrypts the state file.
+pub const PASSPHRASE_ENV: &str = "TENEX_PASSPHRASE";
+
+const PREFIX: &str = "sealed:v1:";
+const SALT_LEN: usize = 16;
+const PBKDF2_ITERATIONS: u32 = 600_000;
+
+/// The key derived last, with the salt it was derived for; deriving it is slow on purpose.
+static KEY: Mutex<Option<(String, Arc<LessSafeKey>)>> = Mutex::new(None);
+
+/// Why sealed fields in the state file cannot be opened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum Locked {
+    /// The state file is encrypted and `TENEX_PASSPHRASE` is not set.
+    #[error("The state file is encrypted; set TENEX_PASSPHRASE to open it")]
+    NoPassphrase,
+    /// `TENEX_PASSPHRASE` is not the passphrase the state file was encrypted with.
+    #[error("TENEX_PASSPHRASE does not open the encrypted state file")]
+    WrongPassphrase,
+}
+
+/// Seals and opens field values under the key of one passphrase and salt.
+#[derive(Debug, Clone)]
+pub(super) struct Sealer {
+    key: Arc<LessSafeKey>,
+    rng: SystemRandom,
+}
+
+impl Sealer {
+    /// The sealer for `salt` and the passphrase in `TENEX_PASSPHRASE`, if it is set.
+    pub(super) fn from_env(salt: &str) -> Result<Option<Self>> {
+        let Some(passphrase) = passphrase() else {
+            return Ok(None);
+        };
+
+        let mut cached = KEY.lock();
+        if let Some((cached_salt, key)) = cached.as_ref()
+            && cached_salt == salt
+        {
+            return Ok(Some(Self::new(Arc::clone(key))));
+        }
+        let salt_bytes = base64::engine::general_purpose::STANDARD.decode(salt)?;
+        let mut key_bytes = [0_u8; 32];
+        ring::pbkdf2::derive(
+            ring::pbkdf2::PBKDF2_HMAC_SHA256,
+            NonZeroU32::new(PBKDF2_ITERATIONS).unwrap_or(NonZeroU32::MIN),
+            &salt_bytes,
+            passphrase.as_bytes(),
+            &mut key_bytes,
+        );
+        let key = UnboundKey::new(&CHACHA20_POLY1305, &key_bytes)
+            .map_err(|_| anyhow::anyhow!("Failed to create the state encryption key"))?;
+        let key = Arc::new(LessSafeKey::new(key));
+        *cached = Some((salt.to_string(), Arc::clone(&key)));
+        drop(cached);
+        Ok(Some(Self::new(key)))
+    }
+
+    fn new(key: Arc<LessSafeKey>) -> Self {
+        Self {
+            key,
+            rng: SystemRandom::new(),
+        }
+    }
+
+    /// A new random salt, as base64.
+    pub(super) fn generate_salt() -> Result<String> {
+        let mut salt = [0_u8; SALT_LEN];
+        SystemRandom::new()
+            .fill(&mut salt)
+            .map_err(|_| anyhow::anyhow!("Failed to generate a salt"))?;
+        Ok(base64::engine::general_purpose::STANDARD.encode(salt))
+    }
+
+    /// `value` sealed, unless it already is.
+    pub(super) fn seal(&self, value: &str) -> Result<String> {
+        if is_sealed(value) {
+            return Ok(value.to_string());
+        }
+        let mut nonce = [0_u8; NONCE_LEN];
+        self.rng
+            .fill(&mut nonce)
+            .map_err(|_| anyhow::anyhow!("Failed to generate a nonce"))?;
+        let mut in_out = value.as_bytes().to_vec();
+        self.key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::empty(),
+                &mut in_out,
+            )
+            .map_err(|_| anyhow::anyhow!("Failed to encrypt a state field"))?;
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&in_out);
+        Ok(format!(
+            "{PREFIX}{}",
+            base64::engine::general_purpose::STANDARD.encode(sealed)
+        ))
+    }
+
+    /// `value` opened, or as it is when it is not sealed.
+    pub(super) fn open(&self, value: &str) -> Result<String> {
+        let Some(encoded) = value.strip_prefix(PREFIX) else {
+            return Ok(value.to_string());
+        };
+        let mut sealed = base64::engine::general_purpose::STANDARD.decode(encoded)?;
+        if sealed.len() < NONCE_LEN {
+            return Err(Locked::WrongPassphrase.into());
+        }
+        let mut in_out = sealed.split_off(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(&sealed)
+            .map_err(|_| anyhow::anyhow!("Malformed sealed state field"))?;
+        let opened = self
+            .key
+            .open_in_place(nonce, Aad::empty(), &mut in_out)
+            .map_err(|_| Locked::WrongPassphrase)?;
+        Ok(String::from_utf8(opened.to_vec())?)
+    }
+}
+
+/// The passphrase in `TENEX_PASSPHRASE`, if it is set.
+pub(super) fn passphrase() -> Option<String> {
+    std::env::var(PASSPHRASE_ENV)
+        .ok()
+        .filter(|passphrase| !passphrase.is_empty())
+}
+
+/// Whether `value` is sealed.
+pub(super) fn is_sealed(value: &str) -> bool {
+    value.starts_with(PREFIX)
+}
+
+/// Replace each sensitive field of `agent` with `f` of it: the conversation id, and the
+/// conversation ids that key its spend.
+pub(super) fn map_sensitive_fields(
+    agent: &mut Agent,
+    mut f: impl FnMut(&str) -> Result<String>,
+) -> Result<()> {
+    if let Some(conversation_id) = &agent.conversation_id {
+        agent.conversation_id = Some(f(conversation_id)?);
+    }
+    if !agent.costs.is_empty() {
+        agent.costs = std::mem::take(&mut agent.costs)
+            .into_iter()
+            .map(|(conversation_id, cost)| Ok((f(&conversation_id)?, cost)))
+            .collect::<Result<_>>()?;
+    }
+    Ok(())
+}
+
+/// Whether any sensitive field of `agent` is sealed.
+pub(super) fn has_sealed_fields(agent: &Agent) -> bool {
+    agent.conversation_id.as_deref().is_some_and(is_sealed)
+        || agent.costs.keys().any(|id| is_sealed(id))
+}
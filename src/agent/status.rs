@@ -12,9 +12,25 @@ pub enum Status {
     Starting,
     /// Agent is actively running
     Running,
+    /// Agent's program exited with this code; its final output stays viewable
+    Finished(u32),
+    /// Agent ran past its maximum runtime and was stopped
+    TimedOut,
 }
 
 impl Status {
+    /// Check if the agent's program has exited
+    #[must_use]
+    pub const fn is_finished(&self) -> bool {
+        matches!(self, Self::Finished(_))
+    }
+
+    /// Check if the agent's program has stopped, on its own or because it timed out
+    #[must_use]
+    pub const fn is_stopped(&self) -> bool {
+        matches!(self, Self::Finished(_) | Self::TimedOut)
+    }
+
     /// Check if the agent is in an active state (can receive input)
     #[must_use]
     pub const fn is_active(&self) -> bool {
@@ -33,6 +49,9 @@ impl Status {
         match self {
             Self::Starting => "...",
             Self::Running => "●",
+            Self::Finished(0) => "■",
+            Self::Finished(_) => "✗",
+            Self::TimedOut => "◷",
         }
     }
 
@@ -42,16 +61,19 @@ impl Status {
         match self {
             Self::Starting => "yellow",
             Self::Running => "green",
+            Self::Finished(0) => "gray",
+            Self::Finished(_) | Self::TimedOut => "red",
         }
     }
 }
 
 impl fmt::Display for Status {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let s = match self {
-            Self::Starting => "Starting",
-            Self::Running => "Running",
-        };
-        write!(f, "{s}")
+        match self {
+            Self::Starting => write!(f, "Starting"),
+            Self::Running => write!(f, "Running"),
+            Self::Finished(code) => write!(f, "Finished ({code})"),
+            Self::TimedOut => write!(f, "Timed out"),
+        }
     }
 }
@@ -1,6 +1,7 @@
 //! Agent persistence layer
 
-use super::{Agent, WorkspaceKind};
+use super::sealed::{self, Sealer};
+use super::{Agent, Locked, WorkspaceKind};
 use crate::config::Config;
 use crate::git;
 use anyhow::{Context, Result};
@@ -161,6 +162,11 @@ pub struct Storage {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub mux_socket: Option<String>,
 
+    /// Salt of the key that seals sensitive agent fields, once `TENEX_PASSPHRASE` has been
+    /// set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encryption_salt: Option<String>,
+
     /// Custom state file path (if set, overrides default location)
     /// When None, uses `Config::state_path()`
     #[serde(skip)]
@@ -215,6 +221,7 @@ impl Storage {
             version: default_version(),
             instance_id: None,
             mux_socket: None,
+            encryption_salt: None,
             state_path: None,
             last_loaded: None,
         }
@@ -228,6 +235,7 @@ impl Storage {
             version: 1, // Can't call default_version() in const context
             instance_id: None,
             mux_socket: None,
+            encryption_salt: None,
             state_path: Some(path),
             last_loaded: None,
         }
@@ -360,6 +368,9 @@ impl Storage {
             .with_context(|| format!("Failed to read state from {}", path.display()))?;
         let mut storage: Self = serde_json::from_str(&contents)
             .with_context(|| format!("Failed to parse state from {}", path.display()))?;
+        storage
+            .open_sealed_fields()
+            .with_context(|| format!("Failed to decrypt state from {}", path.display()))?;
         storage.last_loaded = Some(StorageSnapshot::capture(&storage));
         Ok(storage)
     }
@@ -528,6 +539,8 @@ impl Storage {
         } else if path.exists() {
             match Self::load_from(&path) {
                 Ok(storage) => storage,
+                // Never overwrite fields this process cannot decrypt.
+                Err(err) if err.downcast_ref::<Locked>().is_some() => return Err(err),
                 Err(err) => {
                     let backup_path = backup_state_path(&path);
                     if backup_path.exists() {
@@ -559,8 +572,7 @@ impl Storage {
 
         let mut merged = merge_storage_three_way(&baseline, &disk, self);
         merged.apply_local_agent_fields_from(self);
-        let contents =
-            serde_json::to_string_pretty(&merged).context("Failed to serialize state")?;
+        let contents = merged.serialize_sealed()?;
 
         // Write atomically to avoid corrupting the state file if we're interrupted mid-write.
         write_state_atomically(&path, &contents)?;
@@ -573,6 +585,42 @@ impl Storage {
         Ok(())
     }
 
+    /// Open the sealed fields of agents read from the state file.
+    fn open_sealed_fields(&mut self) -> Result<()> {
+        if !self.agents.iter().any(sealed::has_sealed_fields) {
+            return Ok(());
+        }
+        let salt = self
+            .encryption_salt
+            .as_deref()
+            .context("Encrypted state has no salt")?;
+        let sealer = Sealer::from_env(salt)?.ok_or(Locked::NoPassphrase)?;
+        for agent in &mut self.agents {
+            sealed::map_sensitive_fields(agent, |value| sealer.open(value))?;
+        }
+        Ok(())
+    }
+
+    /// The state file contents, with sensitive agent fields sealed when `TENEX_PASSPHRASE` is
+    /// set.
+    fn serialize_sealed(&mut self) -> Result<String> {
+        if sealed::passphrase().is_none() {
+            return serde_json::to_string_pretty(self).context("Failed to serialize state");
+        }
+        if self.encryption_salt.is_none() {
+            self.encryption_salt = Some(Sealer::generate_salt()?);
+        }
+        let mut on_disk = self.clone();
+        if let Some(salt) = &self.encryption_salt
+            && let Some(sealer) = Sealer::from_env(salt)?
+        {
+            for agent in &mut on_disk.agents {
+                sealed::map_sensitive_fields(agent, |value| sealer.seal(value))?;
+            }
+        }
+        serde_json::to_string_pretty(&on_disk).context("Failed to serialize state")
+    }
+
     pub(crate) fn resolved_state_path(&self) -> std::path::PathBuf {
         let configured = self.state_path.clone().unwrap_or_else(Config::state_path);
         resolve_state_path(&configured)
@@ -704,6 +752,15 @@ impl Storage {
         self.agents.iter().any(|a| a.parent_id == Some(agent_id))
     }
 
+    /// Roots started in other repositories for the swarm rooted at `agent_id`
+    #[must_use]
+    pub fn linked_roots(&self, agent_id: Uuid) -> Vec<&Agent> {
+        self.agents
+            .iter()
+            .filter(|a| a.swarm_root == Some(agent_id))
+            .collect()
+    }
+
     /// Get the count of children for an agent
     #[must_use]
     pub fn child_count(&self, agent_id: Uuid) -> usize {
@@ -733,6 +790,16 @@ impl Storage {
         Some(current)
     }
 
+    /// Mux target of an agent's window: its root's session, plus the window index for children
+    #[must_use]
+    pub fn mux_target(&self, agent: &Agent) -> String {
+        let root_session = &self.root_ancestor(agent.id).unwrap_or(agent).mux_session;
+        agent.window_index.map_or_else(
+            || root_session.clone(),
+            |window_idx| crate::mux::SessionManager::window_target(root_session, window_idx),
+        )
+    }
+
     /// Get all descendants of an agent (children, grandchildren, etc.)
     #[must_use]
     pub fn descendants(&self, agent_id: Uuid) -> Vec<&Agent> {
@@ -927,6 +994,8 @@ impl Storage {
     /// Reserve multiple consecutive window indices for batch spawning.
     /// Returns the starting index; use indices start..start+count.
     /// This is O(n) once instead of O(n*count) for repeated `next_window_index` calls.
+    ///
+    /// These are provisional: the mux daemon assigns the real index when it creates the window.
     #[must_use]
     pub fn reserve_window_indices(&self, root_id: Uuid) -> u32 {
         // Window 1 is the root, children start at 2
@@ -1042,6 +1111,9 @@ fn apply_agent_changes(target: &mut Agent, baseline: &Agent, ours: &Agent) {
     if ours.runtime_scope != baseline.runtime_scope {
         target.runtime_scope.clone_from(&ours.runtime_scope);
     }
+    if ours.sandbox != baseline.sandbox {
+        target.sandbox = ours.sandbox;
+    }
     if ours.mux_session != baseline.mux_session {
         target.mux_session.clone_from(&ours.mux_session);
     }
@@ -1051,6 +1123,9 @@ fn apply_agent_changes(target: &mut Agent, baseline: &Agent, ours: &Agent) {
     if ours.updated_at != baseline.updated_at {
         target.updated_at = ours.updated_at;
     }
+    if ours.finished_at != baseline.finished_at {
+        target.finished_at = ours.finished_at;
+    }
     if ours.parent_id != baseline.parent_id {
         target.parent_id = ours.parent_id;
     }
@@ -1060,4 +1135,59 @@ fn apply_agent_changes(target: &mut Agent, baseline: &Agent, ours: &Agent) {
     if ours.is_terminal != baseline.is_terminal {
         target.is_terminal = ours.is_terminal;
     }
+    if ours.active_ms != baseline.active_ms {
+        target.active_ms = ours.active_ms;
+    }
+    if ours.base_branch != baseline.base_branch {
+        target.base_branch.clone_from(&ours.base_branch);
+    }
+    if ours.prompt != baseline.prompt {
+        target.prompt.clone_from(&ours.prompt);
+    }
+    apply_annotation_changes(target, baseline, ours);
+    if ours.restart_policy != baseline.restart_policy {
+        target.restart_policy = ours.restart_policy;
+    }
+    if ours.restarts != baseline.restarts {
+        target.restarts = ours.restarts;
+    }
+    if ours.max_runtime_mins != baseline.max_runtime_mins {
+        target.max_runtime_mins = ours.max_runtime_mins;
+    }
+    if ours.wrap_up_requested_at != baseline.wrap_up_requested_at {
+        target.wrap_up_requested_at = ours.wrap_up_requested_at;
+    }
+    if ours.costs != baseline.costs {
+        target.costs.clone_from(&ours.costs);
+    }
+    if ours.budget != baseline.budget {
+        target.budget = ours.budget;
+    }
+}
+
+/// Like [`apply_agent_changes`], for what the agent was created from and how Tenex treats it.
+fn apply_annotation_changes(target: &mut Agent, baseline: &Agent, ours: &Agent) {
+    if ours.ticket != baseline.ticket {
+        target.ticket.clone_from(&ours.ticket);
+    }
+    if ours.imported_tmux_session != baseline.imported_tmux_session {
+        target
+            .imported_tmux_session
+            .clone_from(&ours.imported_tmux_session);
+    }
+    if ours.swarm_file != baseline.swarm_file {
+        target.swarm_file.clone_from(&ours.swarm_file);
+    }
+    if ours.blocked_by != baseline.blocked_by {
+        target.blocked_by.clone_from(&ours.blocked_by);
+    }
+    if ours.swarm_root != baseline.swarm_root {
+        target.swarm_root = ours.swarm_root;
+    }
+    if ours.scope != baseline.scope {
+        target.scope.clone_from(&ours.scope);
+    }
+    if ours.notifications != baseline.notifications {
+        target.notifications = ours.notifications;
+    }
 }
@@ -0,0 +1,49 @@
+//! Ticket IDs that link agents to an issue tracker (`PROJ-123`, `#123`).
+
+/// Whether `token` looks like a tracker key (`PROJ-123`) or a GitHub issue (`#123`).
+fn is_ticket(token: &str) -> bool {
+    if let Some(number) = token.strip_prefix('#') {
+        return !number.is_empty() && number.chars().all(|c| c.is_ascii_digit());
+    }
+    let Some((key, number)) = token.rsplit_once('-') else {
+        return false;
+    };
+    key.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+        && key.chars().all(|c| c.is_ascii_alphanumeric())
+        && !number.is_empty()
+        && number.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Split a leading ticket ID (optionally followed by `:`) off an agent title.
+///
+/// Returns the ticket and the rest of the title, or `None` and the whole title when it does
+/// not start with one. A title that is only a ticket keeps it as the title too.
+#[must_use]
+pub fn split_ticket(title: &str) -> (Option<String>, &str) {
+    let title = title.trim();
+    let (token, rest) = title.split_once(char::is_whitespace).unwrap_or((title, ""));
+    let token = token.strip_suffix(':').unwrap_or(token);
+    if !is_ticket(token) {
+        return (None, title);
+    }
+    let rest = rest.trim();
+    (
+        Some(token.to_string()),
+        if rest.is_empty() { title } else { rest },
+    )
+}
+
+/// Whether `ticket` refers to a GitHub issue (`#123`) that a pull request can close.
+#[must_use]
+pub fn is_github_issue(ticket: &str) -> bool {
+    ticket.starts_with('#') && is_ticket(ticket)
+}
+
+/// Text new branch names are generated from: the ticket (if any) followed by the title.
+#[must_use]
+pub fn branch_title(ticket: Option<&str>, title: &str) -> String {
+    match ticket {
+        Some(ticket) if !title.starts_with(ticket) => format!("{ticket} {title}"),
+        _ => title.to_string(),
+    }
+}
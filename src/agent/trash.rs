@@ -0,0 +1,146 @@
+//! Trash for killed agents: worktrees moved aside and branches kept as trash refs until their
+//! retention period ends.
+//!
+//! Each entry is a directory under the trash area (`trash/` next to the state file) holding
+//! `entry.json` and, when the move succeeded, the agent's `worktree/`.
+
+use super::Agent;
+use crate::git;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+/// Metadata file in each trash entry.
+const ENTRY_FILE: &str = "entry.json";
+
+/// Directory holding the trashed worktree in each trash entry.
+const WORKTREE_DIR: &str = "worktree";
+
+/// A killed agent's trashed worktree and branch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrashEntry {
+    /// Agent title.
+    pub title: String,
+    /// The agent's branch.
+    pub branch: String,
+    /// Repository the branch lived in.
+    pub repo_root: PathBuf,
+    /// Ref keeping the deleted branch's commits, if the branch was deleted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trash_ref: Option<String>,
+    /// Whether the worktree was moved into the entry.
+    #[serde(default)]
+    pub has_worktree: bool,
+    /// When the agent was killed.
+    pub trashed_at: DateTime<Utc>,
+}
+
+/// Trash area for the state file at `state_path`.
+#[must_use]
+pub fn trash_dir(state_path: &Path) -> PathBuf {
+    state_path.with_file_name("trash")
+}
+
+/// Move `root`'s worktree into the trash and, when `delete_branch` is set, keep its branch
+/// under a trash ref, so the caller can then remove the worktree and branch as usual.
+///
+/// A worktree that cannot be moved (for example across filesystems) is left for the caller
+/// to delete; the branch is still kept.
+///
+/// # Errors
+///
+/// Returns an error if the trash entry cannot be created or the trash ref cannot be written.
+pub fn move_to_trash(
+    trash_dir: &Path,
+    root: &Agent,
+    repo_root: &Path,
+    delete_branch: bool,
+) -> Result<TrashEntry> {
+    let trashed_at = Utc::now();
+    let stamp = trashed_at.timestamp();
+    let entry_dir = trash_dir.join(format!("{stamp}-{}", root.branch.replace('/', "-")));
+    fs::create_dir_all(&entry_dir)
+        .with_context(|| format!("Failed to create {}", entry_dir.display()))?;
+
+    let trash_ref = if delete_branch {
+        git::trash_branch(repo_root, &root.branch, stamp)?
+    } else {
+        None
+    };
+
+    let has_worktree = root.worktree_path.exists()
+        && fs::rename(&root.worktree_path, entry_dir.join(WORKTREE_DIR))
+            .inspect_err(|err| {
+                warn!(
+                    path = %root.worktree_path.display(),
+                    error = %err,
+                    "Failed to move worktree to trash; deleting it instead"
+                );
+            })
+            .is_ok();
+
+    let entry = TrashEntry {
+        title: root.title.clone(),
+        branch: root.branch.clone(),
+        repo_root: repo_root.to_path_buf(),
+        trash_ref,
+        has_worktree,
+        trashed_at,
+    };
+    let contents =
+        serde_json::to_string_pretty(&entry).context("Failed to serialize trash entry")?;
+    let entry_file = entry_dir.join(ENTRY_FILE);
+    fs::write(&entry_file, contents)
+        .with_context(|| format!("Failed to write {}", entry_file.display()))?;
+    Ok(entry)
+}
+
+/// Trash entries and their directories, oldest first. Unreadable entries are skipped.
+#[must_use]
+pub fn load_trash(trash_dir: &Path) -> Vec<(PathBuf, TrashEntry)> {
+    let Ok(dirs) = fs::read_dir(trash_dir) else {
+        return Vec::new();
+    };
+    let mut entries: Vec<(PathBuf, TrashEntry)> = dirs
+        .flatten()
+        .map(|dir| dir.path())
+        .filter_map(|dir| {
+            let contents = fs::read_to_string(dir.join(ENTRY_FILE)).ok()?;
+            let entry = serde_json::from_str(&contents)
+                .inspect_err(
+                    |err| debug!(path = %dir.display(), error = %err, "Skipping trash entry"),
+                )
+                .ok()?;
+            Some((dir, entry))
+        })
+        .collect();
+    entries.sort_by_key(|(_, entry)| entry.trashed_at);
+    entries
+}
+
+/// Permanently delete trash entries older than `retention` (every entry when `None`), along
+/// with their trash refs. Returns how many entries were purged.
+///
+/// # Errors
+///
+/// Returns an error if an entry's directory cannot be removed.
+pub fn purge_trash(trash_dir: &Path, retention: Option<Duration>) -> Result<usize> {
+    let now = Utc::now();
+    let mut purged = 0;
+    for (dir, entry) in load_trash(trash_dir) {
+        if retention.is_some_and(|retention| now - entry.trashed_at < retention) {
+            continue;
+        }
+        if let Some(trash_ref) = &entry.trash_ref
+            && let Err(err) = git::delete_trash_ref(&entry.repo_root, trash_ref)
+        {
+            warn!(trash_ref, error = %err, "Failed to delete trash ref");
+        }
+        fs::remove_dir_all(&dir).with_context(|| format!("Failed to remove {}", dir.display()))?;
+        purged += 1;
+    }
+    Ok(purged)
+}
@@ -0,0 +1,408 @@
+//! Programmatic interface to Tenex's agent orchestration.
+//!
+//! [`Tenex`] drives the same operations as the TUI (creating, killing, pushing, broadcasting to,
+//! and synthesizing agents) without a terminal, so other tools and tests can script Tenex.
+//! Every call blocks until its work has finished, including git operations the TUI would run in
+//! the background, and saves agent state the way the TUI does.
+//!
+//! Unlike [`crate::cli`], this module is a stable interface: its signatures only change with a
+//! new major version.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, bail};
+use uuid::Uuid;
+
+use crate::agent::{Agent, Storage};
+use crate::app::{Actions, AgentProgram, AppData, Settings, SynthesisOptions};
+use crate::config::Config;
+use crate::error::TenexError;
+use crate::mux::{OutputCapture, SessionManager};
+use crate::state::{AppMode, ConfirmAction, ConfirmingMode};
+
+/// A handle on one Tenex instance's agents.
+#[derive(Debug)]
+pub struct Tenex {
+    data: AppData,
+    actions: Actions,
+    /// The `agent_program` setting, saved while [`Self::set_program`] overrides it.
+    default_program: Option<(AgentProgram, String)>,
+}
+
+impl Tenex {
+    /// Open the current instance (the one `tenex` would open here), loading its config,
+    /// settings, agents, and plugins.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the instance cannot be initialized or its state file cannot be read.
+    pub fn open() -> Result<Self> {
+        // A fresh instance has no state file yet, which is not an error.
+        let state_existed = Config::state_path().exists();
+        let (config, storage, settings, storage_load_error) = crate::cli::load_instance()?;
+        if let Some(message) = storage_load_error
+            && state_existed
+        {
+            bail!(message);
+        }
+        let mut tenex = Self::with_state(config, storage, settings);
+        tenex.data.plugins = crate::plugin::load_plugins(&Config::plugin_dir());
+        Ok(tenex)
+    }
+
+    /// Wrap already-loaded state, for example a [`Storage`] pointed at a scratch state file.
+    #[must_use]
+    pub const fn with_state(config: Config, storage: Storage, settings: Settings) -> Self {
+        Self {
+            data: AppData::new(config, storage, settings, false),
+            actions: Actions::new(),
+            default_program: None,
+        }
+    }
+
+    /// Every stored agent, roots and children alike.
+    pub fn agents(&self) -> impl Iterator<Item = &Agent> {
+        self.data.storage.iter()
+    }
+
+    /// The agent with `id`, if it exists.
+    #[must_use]
+    pub fn agent(&self, id: Uuid) -> Option<&Agent> {
+        self.data.storage.get(id)
+    }
+
+    /// Re-read agent state from disk, picking up agents another Tenex process (such as the TUI)
+    /// created, changed, or killed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the state file cannot be read.
+    pub fn reload(&mut self) -> Result<()> {
+        let path = self.data.storage.resolved_state_path();
+        if !path.exists() {
+            return Ok(());
+        }
+        let mut storage = Storage::load_from(&path)?;
+        storage.state_path.clone_from(&self.data.storage.state_path);
+        storage.apply_local_agent_fields_from(&self.data.storage);
+        self.data.storage = storage;
+        Ok(())
+    }
+
+    /// The last `lines` non-empty lines of agent `id`'s terminal, as plain text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the agent does not exist or its output cannot be captured.
+    pub fn output(&self, id: Uuid, lines: usize) -> Result<Vec<String>> {
+        let agent = self.require(id)?;
+        OutputCapture::new().tail(&mux_target(agent), lines)
+    }
+
+    /// Type `message` into agent `id` and submit it, the way its program expects.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the agent does not exist or the message cannot be sent.
+    pub fn send(&self, id: Uuid, message: &str) -> Result<()> {
+        let agent = self.require(id)?;
+        SessionManager::new().send_keys_and_submit_for_agent(&mux_target(agent), agent, message)
+    }
+
+    /// Create a root agent in `repo` and start it with `prompt`.
+    ///
+    /// In a git repository the agent gets its own worktree and branch; elsewhere it runs in
+    /// `repo` itself. A `ticket:` prefix on `title` sets the agent's ticket, as in the TUI.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the agent cannot be created, including when a worktree for its
+    /// branch already exists.
+    pub fn spawn(&mut self, repo: &Path, title: &str, prompt: Option<&str>) -> Result<Uuid> {
+        let project_root =
+            crate::git::repository_workspace_root(repo).unwrap_or_else(|_| repo.to_path_buf());
+        self.data.cwd_project_root = Some(project_root);
+        self.data.select_cwd_project();
+
+        let before = self.agent_ids();
+        let mode = self.actions.create_agent(&mut self.data, title, prompt)?;
+        mode_result(&mut self.data, mode)?;
+        self.data.spawn.worktree_conflict = None;
+        self.data.wait_for_background_ops()?;
+        self.new_agent_ids(&before)
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("Agent {title} was not created"))
+    }
+
+    /// Add `count` child agents under `parent`, each started with `task`.
+    ///
+    /// Returns the new children's ids.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `parent` does not exist, `count` is zero, or spawning fails.
+    pub fn spawn_children(&mut self, parent: Uuid, count: usize, task: &str) -> Result<Vec<Uuid>> {
+        if count == 0 {
+            bail!("Child count must be at least 1");
+        }
+        self.select(parent)?;
+
+        let before = self.agent_ids();
+        self.data.spawn.start_spawning_under(parent);
+        self.data.spawn.child_count = count;
+        let mode = self.actions.spawn_children(&mut self.data, Some(task))?;
+        mode_result(&mut self.data, mode)?;
+        self.data.wait_for_background_ops()?;
+        Ok(self.new_agent_ids(&before))
+    }
+
+    /// Start agents spawned from now on with `program` (a command such as `codex` or
+    /// `claude --model opus`), or with the `agent_program` setting again when `None`.
+    pub fn set_program(&mut self, program: Option<&str>) {
+        let settings = &mut self.data.settings;
+        let default_program = self.default_program.get_or_insert_with(|| {
+            (
+                settings.agent_program,
+                settings.custom_agent_command.clone(),
+            )
+        });
+        if let Some(program) = program {
+            settings.agent_program = AgentProgram::Custom;
+            settings.custom_agent_command = program.to_string();
+        } else {
+            settings.agent_program = default_program.0;
+            settings.custom_agent_command.clone_from(&default_program.1);
+        }
+    }
+
+    /// Rename agent `id`. A root agent's branch and worktree are renamed with it, as in the TUI.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the agent does not exist or cannot be renamed.
+    pub fn rename(&mut self, id: Uuid, title: &str) -> Result<()> {
+        let agent = self.require(id)?;
+        let is_root = agent.is_root();
+        let current_title = agent.title.clone();
+        self.data.git_op.start_rename(id, current_title, is_root);
+        self.data.git_op.set_branch_name(title.to_string());
+        let mode = Actions::execute_rename(&mut self.data)?;
+        mode_result(&mut self.data, mode)?;
+        self.data.wait_for_background_ops()
+    }
+
+    /// Add `count` reviewers under agent `id` that review its branch against `base_branch`,
+    /// with the focuses of the `review_focuses` setting.
+    ///
+    /// Returns the new reviewers' ids.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the agent does not exist, `count` is zero, or spawning fails.
+    pub fn review(&mut self, id: Uuid, count: usize, base_branch: &str) -> Result<Vec<Uuid>> {
+        if count == 0 {
+            bail!("Reviewer count must be at least 1");
+        }
+        self.select(id)?;
+
+        let before = self.agent_ids();
+        self.data.spawn.start_spawning_under(id);
+        self.data.spawn.child_count = count;
+        self.data.review.base_branch = Some(base_branch.to_string());
+        let focuses = self.data.settings.review_focuses();
+        self.data.review.assign_focuses(focuses, count);
+        let result = self.actions.spawn_review_agents(&mut self.data);
+        self.data.review.clear();
+        result?;
+        self.data.wait_for_background_ops()?;
+        Ok(self.new_agent_ids(&before))
+    }
+
+    /// Kill agent `id` and its descendants.
+    ///
+    /// With `keep_workspace`, a root agent's worktree and branch are left in place; otherwise
+    /// they move to the trash, as in the TUI.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the agent does not exist or cannot be killed.
+    pub fn kill(&mut self, id: Uuid, keep_workspace: bool) -> Result<()> {
+        self.select(id)?;
+        self.actions.kill_agent(&mut self.data, keep_workspace)?;
+        self.data.wait_for_background_ops()
+    }
+
+    /// Push agent `id`'s branch to its remote.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the agent does not exist or the push fails.
+    pub fn push(&mut self, id: Uuid) -> Result<()> {
+        self.select(id)?;
+        Actions::push_branch(&mut self.data)?;
+        let mode = Actions::execute_push(&mut self.data)?;
+        mode_result(&mut self.data, mode)?;
+        self.data.wait_for_background_ops()
+    }
+
+    /// Send `message` to agent `id`, or to its leaf descendants when it has children.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the agent does not exist or no agent received the message.
+    pub fn broadcast(&mut self, id: Uuid, message: &str) -> Result<()> {
+        self.select(id)?;
+        let mode = self.actions.broadcast_to_leaves(&mut self.data, message)?;
+        mode_result(&mut self.data, mode)
+    }
+
+    /// Collect the findings of agent `id`'s descendants and hand them to it, with optional
+    /// extra instructions. Findings are collected with the `synthesis_strategy` setting.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the agent does not exist, has no children to synthesize, or the
+    /// synthesis cannot be delivered.
+    pub fn synthesize(&mut self, id: Uuid, prompt: Option<&str>) -> Result<()> {
+        self.select(id)?;
+        let options = SynthesisOptions {
+            strategy: self.data.settings.synthesis_strategy,
+            keep_children: false,
+        };
+        let mode = self
+            .actions
+            .synthesize_with_prompt(&mut self.data, options, prompt)?;
+        mode_result(&mut self.data, mode)?;
+        self.data.wait_for_background_ops()
+    }
+
+    /// Like [`Self::synthesize`], but collect only the subtrees of `children`, which must be
+    /// descendants of agent `id`. Its other descendants keep running untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the agent does not exist, `children` is empty or names an agent that
+    /// is not one of its non-terminal descendants, or the synthesis cannot be delivered.
+    pub fn synthesize_children(
+        &mut self,
+        id: Uuid,
+        children: &[Uuid],
+        prompt: Option<&str>,
+    ) -> Result<()> {
+        if children.is_empty() {
+            bail!("No children to synthesize");
+        }
+        let descendant_ids = self.data.storage.descendant_ids(id);
+        self.data.clear_synthesis_marks();
+        for child in children {
+            if !descendant_ids.contains(child) || !self.data.mark_for_synthesis(*child) {
+                self.data.clear_synthesis_marks();
+                bail!("Agent {child} is not a descendant of {id} that can be synthesized");
+            }
+        }
+
+        let result = self.synthesize(id, prompt);
+        self.data.clear_synthesis_marks();
+        result
+    }
+
+    /// Remember that root agent `id` was created from the swarm file at `path`, so the TUI can
+    /// re-run or edit it.
+    pub(crate) fn record_swarm_file(&mut self, id: Uuid, path: PathBuf) -> Result<()> {
+        let Some(agent) = self.data.storage.get_mut(id) else {
+            bail!("Agent {id} not found");
+        };
+        agent.swarm_file = Some(path);
+        self.data.storage.save()
+    }
+
+    /// Give agent `id` its own maximum runtime in minutes (`0` for no limit), or have it follow
+    /// the `max_runtime_mins` setting again when `None`.
+    pub(crate) fn set_max_runtime(&mut self, id: Uuid, minutes: Option<u64>) -> Result<()> {
+        let Some(agent) = self.data.storage.get_mut(id) else {
+            bail!("Agent {id} not found");
+        };
+        agent.max_runtime_mins = minutes;
+        self.data.storage.save()
+    }
+
+    /// Remember that root agent `id` was started in another repository for the swarm rooted at
+    /// `swarm_root`, so the swarm root's synthesis collects its work.
+    pub(crate) fn link_to_swarm(&mut self, id: Uuid, swarm_root: Uuid) -> Result<()> {
+        let Some(agent) = self.data.storage.get_mut(id) else {
+            bail!("Agent {id} not found");
+        };
+        agent.swarm_root = Some(swarm_root);
+        self.data.storage.save()
+    }
+
+    fn require(&self, id: Uuid) -> Result<&Agent> {
+        self.data
+            .storage
+            .get(id)
+            .ok_or_else(|| anyhow::anyhow!("Agent {id} not found"))
+    }
+
+    /// Point the handlers (which act on the selected agent) at agent `id`.
+    fn select(&mut self, id: Uuid) -> Result<()> {
+        let Some(agent) = self.data.storage.get(id) else {
+            bail!("Agent {id} not found");
+        };
+
+        // Children of collapsed agents are not in the sidebar, so they cannot be selected.
+        let mut ancestor = agent.parent_id;
+        while let Some(ancestor_id) = ancestor {
+            self.data.storage.set_collapsed(ancestor_id, false);
+            ancestor = self
+                .data
+                .storage
+                .get(ancestor_id)
+                .and_then(|agent| agent.parent_id);
+        }
+        self.data.select_agent_by_id(id);
+        if self.data.selected_agent().map(|agent| agent.id) != Some(id) {
+            bail!("Agent {id} could not be selected");
+        }
+        Ok(())
+    }
+
+    fn agent_ids(&self) -> HashSet<Uuid> {
+        self.data.storage.iter().map(|agent| agent.id).collect()
+    }
+
+    fn new_agent_ids(&self, before: &HashSet<Uuid>) -> Vec<Uuid> {
+        self.data
+            .storage
+            .iter()
+            .map(|agent| agent.id)
+            .filter(|id| !before.contains(id))
+            .collect()
+    }
+}
+
+fn mux_target(agent: &Agent) -> String {
+    agent.window_index.map_or_else(
+        || agent.mux_session.clone(),
+        |window_idx| SessionManager::window_target(&agent.mux_session, window_idx),
+    )
+}
+
+/// Turn the modal a handler asked for into the outcome of an API call.
+fn mode_result(app_data: &mut AppData, mode: AppMode) -> Result<()> {
+    match mode {
+        AppMode::ErrorModal(state) => Err(state.error.into()),
+        AppMode::Confirming(ConfirmingMode {
+            action: ConfirmAction::WorktreeConflict,
+        }) => {
+            let branch = app_data
+                .spawn
+                .take_conflict()
+                .map(|conflict| conflict.branch)
+                .unwrap_or_default();
+            Err(TenexError::WorktreeExists { branch }.into())
+        }
+        _ => Ok(()),
+    }
+}
@@ -1,20 +1,27 @@
 //! Persistent application data that outlives mode transitions.
 
-use super::{AgentProgram, Settings, Tab};
-use crate::agent::{Agent, Status, Storage};
+use super::{
+    AgentProgram, InputHistoryKind, MetricsRecording, Settings, SynthesisStrategy, Tab,
+    TokenEstimate,
+};
+use crate::agent::{Agent, AgentRuntime, Status, Storage};
 use crate::app::AgentRole;
 use crate::app::SidebarItem;
 use crate::app::state::{
-    CommandPaletteState, GitOpState, InputState, ModelSelectorState, ReviewState,
-    SettingsMenuState, SlashCommand, SpawnState, UiState,
+    AutoConnectPickerState, BackgroundOps, CannedResponsesState, CommandPaletteState,
+    ContextPickerState, DependencyPickerState, GitOpState, InboxState, InputState,
+    IssuePickerState, ModelSelectorState, PauseState, RecoverPickerState, RepoPickerState,
+    ReviewState, SendToState, SettingsMenuState, SlashCommand, SpawnState, StartupReport,
+    SynthesisBrowserState, TmuxImportPickerState, UiState,
 };
 use crate::config::Config;
+use crate::error::TenexError;
 use crate::state::{
     AppMode, ChangelogMode, CustomAgentCommandMode, ErrorModalMode, HelpMode, ModelSelectorMode,
     PreparingDockerMode, SettingsMenuMode,
 };
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -25,6 +32,15 @@ pub struct SynthesisTargets {
     pub teardown_agent_ids: Vec<Uuid>,
 }
 
+/// How a synthesis collects its findings and what happens to the synthesized agents.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SynthesisOptions {
+    /// What to collect from each descendant.
+    pub strategy: SynthesisStrategy,
+    /// Leave the synthesized agents running instead of terminating them.
+    pub keep_children: bool,
+}
+
 /// Persistent application data (everything except the current mode).
 #[derive(Debug)]
 pub struct AppData {
@@ -58,6 +74,9 @@ pub struct AppData {
     /// Review state (branch selection).
     pub review: ReviewState,
 
+    /// Push/rebase/merge operations running off the UI thread.
+    pub background_ops: BackgroundOps,
+
     /// Slash command palette state (`/`).
     pub command_palette: CommandPaletteState,
 
@@ -67,18 +86,63 @@ pub struct AppData {
     /// Model selector state (`/agents`).
     pub model_selector: ModelSelectorState,
 
+    /// Send-to picker state (`>`).
+    pub send_to: SendToState,
+
+    /// Dependency picker state (`/depends_on`).
+    pub dependency_picker: DependencyPickerState,
+
+    /// Canned responses picker state (`c`).
+    pub canned_responses: CannedResponsesState,
+
+    /// Repository picker state (`/repos`).
+    pub repo_picker: RepoPickerState,
+
+    /// Questions inbox state (`!`).
+    pub inbox: InboxState,
+
+    /// Context file picker state (`Ctrl+O` in the new-agent prompts).
+    pub context_picker: ContextPickerState,
+
+    /// GitHub issue picker state (`#`).
+    pub issue_picker: IssuePickerState,
+
+    /// Branch recovery picker state (`D`).
+    pub recover_picker: RecoverPickerState,
+
+    /// Startup auto-connect picker state.
+    pub auto_connect_picker: AutoConnectPickerState,
+
+    /// tmux import picker state (`/import_tmux`).
+    pub tmux_import_picker: TmuxImportPickerState,
+
+    /// Synthesis browser state (`/syntheses`).
+    pub synthesis_browser: SynthesisBrowserState,
+
     /// Spawn state (child agent spawning).
     pub spawn: SpawnState,
 
+    /// Whether held messages and automatic restarts are paused (`/pause`, quiet hours).
+    pub pause: PauseState,
+
     /// Transient synthesis marks for visible non-terminal descendants.
     pub(crate) synthesis_marks: Vec<Uuid>,
 
+    /// Options chosen for the synthesis being set up.
+    pub(crate) synthesis_options: SynthesisOptions,
+
     /// User settings (persistent preferences).
     pub settings: Settings,
 
     /// Deferred changelog modal to show once the app returns to normal mode.
     pub pending_changelog: Option<crate::state::ChangelogMode>,
 
+    /// What startup reconciliation changed (shown once after launch).
+    pub startup_report: StartupReport,
+
+    /// Installed plugins (loaded once at startup).
+    pub plugins: Vec<crate::plugin::Plugin>,
+
     /// Whether the terminal supports the keyboard enhancement protocol.
     pub keyboard_enhancement_supported: bool,
 }
@@ -103,13 +167,32 @@ impl AppData {
             ui: UiState::new(),
             git_op: GitOpState::new(),
             review: ReviewState::new(),
+            background_ops: BackgroundOps::new(),
             command_palette: CommandPaletteState::new(),
             settings_menu: SettingsMenuState::new(),
             model_selector: ModelSelectorState::new(),
+            send_to: SendToState::new(),
+            dependency_picker: DependencyPickerState::new(),
+            canned_responses: CannedResponsesState::new(),
+            repo_picker: RepoPickerState::new(),
+            inbox: InboxState::new(),
+            context_picker: ContextPickerState::new(),
+            issue_picker: IssuePickerState::new(),
+            recover_picker: RecoverPickerState::new(),
+            auto_connect_picker: AutoConnectPickerState::new(),
+            tmux_import_picker: TmuxImportPickerState::new(),
+            synthesis_browser: SynthesisBrowserState::new(),
             spawn: SpawnState::new(),
+            pause: PauseState::new(),
             synthesis_marks: Vec::new(),
+            synthesis_options: SynthesisOptions {
+                strategy: settings.synthesis_strategy,
+                keep_children: false,
+            },
             settings,
             pending_changelog: None,
+            startup_report: StartupReport::new(),
+            plugins: Vec::new(),
             keyboard_enhancement_supported,
         }
     }
@@ -117,40 +200,176 @@ impl AppData {
     /// The base command used when spawning new agents (based on user settings).
     #[must_use]
     pub(crate) fn agent_spawn_command(&self) -> String {
-        match self.settings.agent_program {
-            AgentProgram::Codex => "codex".to_string(),
-            AgentProgram::Claude => self.config.default_program.clone(),
-            AgentProgram::Custom => custom_agent_command_or_default(
-                &self.settings.custom_agent_command,
-                &self.config.default_program,
-            ),
-        }
+        self.program_command(
+            self.settings.agent_program,
+            &self.settings.custom_agent_command,
+        )
     }
 
     /// The base command used when spawning planner agents (planning swarms).
     #[must_use]
     pub(crate) fn planner_agent_spawn_command(&self) -> String {
-        match self.settings.planner_agent_program {
-            AgentProgram::Codex => "codex".to_string(),
-            AgentProgram::Claude => self.config.default_program.clone(),
-            AgentProgram::Custom => custom_agent_command_or_default(
-                &self.settings.planner_custom_agent_command,
-                &self.config.default_program,
-            ),
-        }
+        self.program_command(
+            self.settings.planner_agent_program,
+            &self.settings.planner_custom_agent_command,
+        )
     }
 
     /// The base command used when spawning review agents (review swarms).
     #[must_use]
     pub(crate) fn review_agent_spawn_command(&self) -> String {
-        match self.settings.review_agent_program {
+        self.program_command(
+            self.settings.review_agent_program,
+            &self.settings.review_custom_agent_command,
+        )
+    }
+
+    /// The base command the next agent created from the new-agent inputs runs: the one typed
+    /// in their command field (`Ctrl+P`), or else the `agent_program` setting's.
+    #[must_use]
+    pub(crate) fn new_agent_spawn_command(&self) -> String {
+        self.spawn
+            .program
+            .clone()
+            .unwrap_or_else(|| self.agent_spawn_command())
+    }
+
+    /// How the new-agent inputs name the program of [`Self::new_agent_spawn_command`]: a typed
+    /// or custom command by itself, otherwise the program's name.
+    #[must_use]
+    pub(crate) fn new_agent_program_label(&self) -> String {
+        match (&self.spawn.program, self.settings.agent_program) {
+            (Some(command), _) => command.clone(),
+            (None, AgentProgram::Custom) => self.agent_spawn_command(),
+            (None, program) => program.label().to_string(),
+        }
+    }
+
+    /// Whether the new-agent inputs show their command field.
+    #[must_use]
+    pub(crate) const fn editing_new_agent_command(&self) -> bool {
+        self.spawn.editing_program.is_some()
+    }
+
+    /// Open the command field of the new-agent inputs, setting aside the name or prompt typed
+    /// so far.
+    pub(crate) fn edit_new_agent_command(&mut self) {
+        let typed = (std::mem::take(&mut self.input.buffer), self.input.cursor);
+        self.input.set(self.new_agent_spawn_command());
+        self.spawn.editing_program = Some(typed);
+    }
+
+    /// Close the command field of the new-agent inputs and bring back the name or prompt.
+    /// With `keep`, the new agent runs the command typed there; an empty command or the
+    /// setting's own goes back to the `agent_program` setting.
+    pub(crate) fn finish_new_agent_command(&mut self, keep: bool) {
+        let Some((typed, cursor)) = self.spawn.editing_program.take() else {
+            return;
+        };
+        if keep {
+            let command = self.input.buffer.trim();
+            self.spawn.program = (!command.is_empty() && command != self.agent_spawn_command())
+                .then(|| command.to_string());
+        }
+        self.input.set(typed);
+        self.input.cursor = cursor;
+    }
+
+    fn program_command(&self, program: AgentProgram, custom_command: &str) -> String {
+        match program {
             AgentProgram::Codex => "codex".to_string(),
             AgentProgram::Claude => self.config.default_program.clone(),
-            AgentProgram::Custom => custom_agent_command_or_default(
-                &self.settings.review_custom_agent_command,
-                &self.config.default_program,
-            ),
+            AgentProgram::Custom => {
+                custom_agent_command_or_default(custom_command, &self.config.default_program)
+            }
+        }
+    }
+
+    /// The config for agents of the repository `dir` is in; see [`Config::for_repo`].
+    #[must_use]
+    pub(crate) fn repo_config(&self, dir: &Path) -> Config {
+        self.config.for_repo(dir, &self.settings).0
+    }
+
+    /// Whether the branch of root `agent` was created by Tenex, going by the branch prefixes
+    /// of its repository.
+    #[must_use]
+    pub(crate) fn is_managed_branch(&self, agent: &Agent) -> bool {
+        agent.repo_root.as_deref().map_or_else(
+            || self.config.is_managed_branch(&agent.branch),
+            |repo_root| self.repo_config(repo_root).is_managed_branch(&agent.branch),
+        )
+    }
+
+    /// Run `f` with `config` replaced by the config of the repository `dir` is in, so agents
+    /// created there get its branch prefixes, worktree directory and program.
+    ///
+    /// Warns, once per repository, when it sets a `default_program` the user hasn't trusted it
+    /// with.
+    pub(crate) fn with_repo_config<T>(&mut self, dir: &Path, f: impl FnOnce(&mut Self) -> T) -> T {
+        let (repo_config, ignored_program) = self.config.for_repo(dir, &self.settings);
+        if let Some(path) = ignored_program
+            && !self.spawn.ignored_repo_programs.contains(&path)
+        {
+            tracing::warn!(path = %path.display(), "Ignoring default_program of a repository");
+            self.ui.notify_warning(format!(
+                "Ignored default_program in {}; set trust_repo_default_program = true in your \
+                 config.toml to use it",
+                path.display()
+            ));
+            self.spawn.ignored_repo_programs.push(path);
         }
+        let config = std::mem::replace(&mut self.config, repo_config);
+        let result = f(self);
+        self.config = config;
+        result
+    }
+
+    /// The command to run for an agent meant to run `program` in `runtime`: `program` itself
+    /// when it is on `PATH`, or else the first of the `fallback_programs` setting that is.
+    ///
+    /// Docker agents run their program inside the container, so it is not looked up.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TenexError::ProgramNotFound`] if neither `program` nor any fallback is on
+    /// `PATH`.
+    pub(crate) fn resolve_agent_program(
+        &mut self,
+        program: String,
+        runtime: AgentRuntime,
+    ) -> Result<String, TenexError> {
+        if runtime == AgentRuntime::Docker || crate::command::program_exists(&program) {
+            return Ok(program);
+        }
+        let fallbacks = self.settings.fallback_programs.clone();
+        let Some(fallback) = fallbacks
+            .iter()
+            .find(|fallback| crate::command::program_exists(fallback))
+        else {
+            return Err(TenexError::ProgramNotFound { program, fallbacks });
+        };
+        tracing::warn!(%program, %fallback, "Agent program not found; using a fallback");
+        self.ui.notify_warning(format!(
+            "`{program}` is not on PATH; using `{fallback}` instead"
+        ));
+        Ok(fallback.clone())
+    }
+
+    /// Estimated size of the prompt in the input for the program it goes to: the selected
+    /// agent's when `to_selected_agent`, otherwise the one new agents run.
+    #[must_use]
+    pub(crate) fn prompt_token_estimate(&self, to_selected_agent: bool) -> TokenEstimate {
+        let command = if to_selected_agent {
+            self.selected_agent()
+                .map_or_else(|| self.agent_spawn_command(), |agent| agent.program.clone())
+        } else {
+            self.new_agent_spawn_command()
+        };
+        TokenEstimate::new(
+            &self.input.buffer,
+            crate::conversation::detect_agent_cli(&command),
+        )
     }
 
     /// Get the currently selected agent (from visible agents list).
@@ -162,6 +381,14 @@ impl AppData {
         }
     }
 
+    /// Whether killing the selected agent can stop it while keeping its branch and worktree
+    /// (a root agent with its own worktree), rather than only removing it.
+    #[must_use]
+    pub(crate) fn kill_offers_stop(&self) -> bool {
+        self.selected_agent()
+            .is_some_and(|agent| agent.is_root() && agent.is_git_workspace())
+    }
+
     /// Check if there are any running agents.
     #[must_use]
     pub(crate) fn has_running_agents(&self) -> bool {
@@ -173,6 +400,11 @@ impl AppData {
         self.ui.set_status(message);
     }
 
+    /// Show a warning in the status bar.
+    pub(crate) fn notify_warning(&mut self, message: impl Into<String>) {
+        self.ui.notify_warning(message);
+    }
+
     fn synthesis_target_descendants(&self, parent_id: Uuid) -> Vec<&Agent> {
         self.storage
             .descendants(parent_id)
@@ -196,7 +428,17 @@ impl AppData {
                 .filter(|agent| !agent.is_terminal_agent())
                 .map(|agent| agent.id)
                 .collect();
-            self.synthesis_targets_from_roots(parent_id, fallback_root_ids, false)
+            let mut targets =
+                self.synthesis_targets_from_roots(parent_id, fallback_root_ids, false);
+            // Roots the swarm started in other repositories are collected but not torn down.
+            targets.capture_agent_ids.extend(
+                self.storage
+                    .linked_roots(parent_id)
+                    .iter()
+                    .filter(|agent| !agent.is_terminal_agent())
+                    .map(|agent| agent.id),
+            );
+            targets
         } else {
             self.synthesis_targets_from_roots(parent_id, marked_root_ids, true)
         }
@@ -247,6 +489,28 @@ impl AppData {
         self.synthesis_marks.clear();
     }
 
+    /// Highlight the next strategy in the synthesis picker.
+    pub(crate) fn select_next_synthesis_strategy(&mut self) {
+        let strategies = SynthesisStrategy::ALL;
+        let index = strategies
+            .iter()
+            .position(|strategy| *strategy == self.synthesis_options.strategy)
+            .map_or(0, |index| (index + 1) % strategies.len());
+        self.synthesis_options.strategy = strategies[index];
+    }
+
+    /// Highlight the previous strategy in the synthesis picker.
+    pub(crate) fn select_prev_synthesis_strategy(&mut self) {
+        let strategies = SynthesisStrategy::ALL;
+        let index = strategies
+            .iter()
+            .position(|strategy| *strategy == self.synthesis_options.strategy)
+            .map_or(0, |index| {
+                index.checked_sub(1).unwrap_or(strategies.len() - 1)
+            });
+        self.synthesis_options.strategy = strategies[index];
+    }
+
     pub(crate) fn toggle_selected_synthesis_mark(&mut self) -> bool {
         let agent_id = match self.selected_sidebar_item() {
             Some(SidebarItem::Agent(agent)) => agent.info.agent.id,
@@ -255,6 +519,17 @@ impl AppData {
         self.toggle_synthesis_mark(agent_id)
     }
 
+    /// Mark `agent_id`'s subtree for synthesis, returning `false` if it cannot be marked.
+    pub(crate) fn mark_for_synthesis(&mut self, agent_id: Uuid) -> bool {
+        if !self.is_synthesis_mark_eligible(agent_id) {
+            return false;
+        }
+        if !self.is_synthesis_marked(agent_id) {
+            self.synthesis_marks.push(agent_id);
+        }
+        true
+    }
+
     fn toggle_synthesis_mark(&mut self, agent_id: Uuid) -> bool {
         if !self.is_synthesis_mark_eligible(agent_id) {
             return false;
@@ -309,17 +584,21 @@ impl AppData {
     }
 
     pub(crate) fn select_cwd_project(&mut self) {
-        let Some(cwd_root) = self.cwd_project_root.as_deref() else {
-            return;
-        };
+        if let Some(cwd_root) = self.cwd_project_root.clone() {
+            self.select_project(&cwd_root);
+        }
+    }
 
+    /// Select the first agent of the project at `project_root`, or its header when it has no
+    /// visible agents.
+    pub(crate) fn select_project(&mut self, project_root: &Path) {
         let items = self.sidebar_items();
         let mut header_index = 0usize;
         let mut first_agent_index: Option<usize> = None;
 
         for (idx, item) in items.iter().enumerate() {
             match item {
-                SidebarItem::Project(project) if project.root == cwd_root => {
+                SidebarItem::Project(project) if project.root == project_root => {
                     header_index = idx;
                 }
                 SidebarItem::Agent(agent) => {
@@ -329,7 +608,7 @@ impl AppData {
                         .repo_root
                         .as_deref()
                         .unwrap_or(agent.info.agent.worktree_path.as_path());
-                    if agent_root == cwd_root {
+                    if agent_root == project_root {
                         first_agent_index.get_or_insert(idx);
                     }
                 }
@@ -414,7 +693,8 @@ impl AppData {
         self.active_tab = match self.active_tab {
             Tab::Preview => Tab::Diff,
             Tab::Diff => Tab::Commits,
-            Tab::Commits => Tab::Preview,
+            Tab::Commits => Tab::Scratchpad,
+            Tab::Scratchpad => Tab::Preview,
         };
         self.ui.reset_scroll();
     }
@@ -493,10 +773,7 @@ impl AppData {
             return;
         }
 
-        // `preview_dimensions` stores the preview inner height, which is `frame_height - 4`.
-        // The agent list inner height is `frame_height - 3` (one line taller, because it has no tab bar).
-        let preview_inner_height = usize::from(self.ui.preview_dimensions.map_or(20, |(_, h)| h));
-        let viewport_height = preview_inner_height.saturating_add(1);
+        let viewport_height = self.agent_list_viewport_height();
         let max_scroll = visible_count.saturating_sub(viewport_height);
 
         let mut scroll = self.ui.agent_list_scroll.min(max_scroll);
@@ -532,6 +809,7 @@ impl AppData {
             Tab::Preview => self.ui.scroll_preview_up(amount),
             Tab::Diff => self.ui.scroll_diff_up(amount),
             Tab::Commits => self.ui.scroll_commits_up(amount),
+            Tab::Scratchpad => self.ui.scroll_scratchpad_up(amount),
         }
     }
 
@@ -541,6 +819,7 @@ impl AppData {
             Tab::Preview => self.ui.scroll_preview_down(amount),
             Tab::Diff => self.ui.scroll_diff_down(amount),
             Tab::Commits => self.ui.scroll_commits_down(amount),
+            Tab::Scratchpad => self.ui.scroll_scratchpad_down(amount),
         }
     }
 
@@ -550,6 +829,7 @@ impl AppData {
             Tab::Preview => self.ui.preview_to_top(),
             Tab::Diff => self.ui.diff_to_top(),
             Tab::Commits => self.ui.commits_to_top(),
+            Tab::Scratchpad => self.ui.scratchpad_to_top(),
         }
     }
 
@@ -559,6 +839,7 @@ impl AppData {
             Tab::Preview => self.ui.preview_to_bottom(content_lines, visible_lines),
             Tab::Diff => self.ui.diff_to_bottom(content_lines, visible_lines),
             Tab::Commits => self.ui.commits_to_bottom(content_lines, visible_lines),
+            Tab::Scratchpad => self.ui.scratchpad_to_bottom(content_lines, visible_lines),
         }
     }
 
@@ -659,9 +940,9 @@ impl AppData {
                 }
 
                 if let Err(err) = self.settings.save() {
-                    return crate::state::ErrorModalMode {
-                        message: format!("Failed to save settings: {err}"),
-                    }
+                    return crate::state::ErrorModalMode::new(TenexError::SettingsSave {
+                        detail: err.to_string(),
+                    })
                     .into();
                 }
 
@@ -671,9 +952,29 @@ impl AppData {
         }
     }
 
+    /// Built-in slash commands followed by the commands plugins add (built-ins win on a
+    /// name clash).
+    fn slash_commands(&self) -> Vec<SlashCommand<'_>> {
+        let builtins = crate::app::state::SLASH_COMMANDS;
+        let plugin_commands = self
+            .plugins
+            .iter()
+            .flat_map(|plugin| &plugin.manifest.commands)
+            .filter(|command| {
+                !builtins
+                    .iter()
+                    .any(|builtin| builtin.name.eq_ignore_ascii_case(&command.name))
+            })
+            .map(|command| SlashCommand {
+                name: &command.name,
+                description: &command.description,
+            });
+        builtins.iter().copied().chain(plugin_commands).collect()
+    }
+
     /// Return the list of slash commands filtered by the current palette input.
     #[must_use]
-    pub(crate) fn filtered_slash_commands(&self) -> Vec<crate::app::state::SlashCommand> {
+    pub(crate) fn filtered_slash_commands(&self) -> Vec<SlashCommand<'_>> {
         let raw = self.input.buffer.trim();
         let query = raw
             .strip_prefix('/')
@@ -683,9 +984,8 @@ impl AppData {
             .unwrap_or("")
             .to_ascii_lowercase();
 
-        crate::app::state::SLASH_COMMANDS
-            .iter()
-            .copied()
+        self.slash_commands()
+            .into_iter()
             .filter(|cmd| {
                 query.is_empty()
                     || cmd
@@ -698,6 +998,7 @@ impl AppData {
     }
 
     fn run_slash_command_by_name(&mut self, command_name: &str) -> AppMode {
+        crate::metrics::record_action(command_name);
         match command_name {
             "/agents" => {
                 self.input.clear();
@@ -705,6 +1006,24 @@ impl AppData {
                 SettingsMenuMode.into()
             }
             "/toggle_docker" => self.toggle_docker_for_new_roots(),
+            "/sandbox" => self.cycle_sandbox_for_new_agents(),
+            "/profile" => self.cycle_profile(),
+            "/metrics" => self.toggle_metrics(),
+            "/theme" => self.cycle_theme(),
+            "/fetch" => super::Actions::fetch_remotes(self, true),
+            "/refresh" => super::Actions::request_full_sync(self),
+            "/pause" => super::Actions::toggle_pause(self),
+            "/budget" => super::Actions::open_budget(self),
+            "/import_tmux" => super::Actions::start_tmux_import(self),
+            "/syntheses" => super::Actions::start_synthesis_browser(self),
+            "/pr_review" => super::Actions::new().start_pr_review_export(self),
+            "/swarm_rerun" => super::Actions::new().rerun_swarm_file(self),
+            "/swarm_edit" => super::Actions::new().edit_swarm_file(self),
+            "/inbox" => super::Actions::open_inbox(self),
+            "/repos" => super::Actions::open_repo_picker(self),
+            "/scope" => super::Actions::start_scope(self),
+            "/depends_on" => super::Actions::start_dependency_picker(self),
+            "/unblock" => super::Actions::clear_dependency(self),
             "/changelog" => {
                 self.input.clear();
                 match crate::release_notes::current_version()
@@ -714,6 +1033,7 @@ impl AppData {
                         title: "Changelog".to_string(),
                         lines,
                         mark_seen_version: None,
+                        agent_id: None,
                     }
                     .into(),
                     Err(e) => {
@@ -722,18 +1042,28 @@ impl AppData {
                     }
                 }
             }
+            "/notifications" => {
+                self.input.clear();
+                ChangelogMode {
+                    title: "Notifications".to_string(),
+                    lines: self.ui.notifications.history_lines(),
+                    mark_seen_version: None,
+                    agent_id: None,
+                }
+                .into()
+            }
             "/help" => {
                 self.ui.help_scroll = 0;
                 HelpMode.into()
             }
-            other => {
+            other => super::Actions::run_plugin_command(self, other).unwrap_or_else(|| {
                 self.set_status(format!("Unknown command: {other}"));
                 AppMode::normal()
-            }
+            }),
         }
     }
 
-    fn run_typed_slash_command(&mut self, commands: &[SlashCommand]) -> AppMode {
+    fn run_typed_slash_command(&mut self) -> AppMode {
         let typed = self
             .input
             .buffer
@@ -752,17 +1082,16 @@ impl AppData {
             format!("/{typed}").to_ascii_lowercase()
         };
 
+        let commands = self.slash_commands();
         let cmd = if let Some(cmd) = commands
             .iter()
-            .copied()
             .find(|c| c.name.eq_ignore_ascii_case(&normalized))
         {
-            cmd
+            cmd.name.to_string()
         } else {
             let query = normalized.trim_start_matches('/').to_string();
-            let matches: Vec<SlashCommand> = commands
+            let matches: Vec<&SlashCommand<'_>> = commands
                 .iter()
-                .copied()
                 .filter(|c| {
                     c.name
                         .trim_start_matches('/')
@@ -776,7 +1105,7 @@ impl AppData {
                     self.set_status(format!("Unknown command: {typed}"));
                     return AppMode::normal();
                 }
-                [single] => *single,
+                [single] => single.name.to_string(),
                 _ => {
                     self.set_status(format!("Ambiguous command: {typed}"));
                     return AppMode::normal();
@@ -784,7 +1113,7 @@ impl AppData {
             }
         };
 
-        self.run_slash_command_by_name(cmd.name)
+        self.run_slash_command_by_name(&cmd)
     }
 
     /// Select the next slash command in the filtered list.
@@ -850,9 +1179,9 @@ impl AppData {
 
         if let Err(err) = self.settings.save() {
             self.settings.docker_for_new_roots = previous;
-            return ErrorModalMode {
-                message: format!("Failed to save settings: {err}"),
-            }
+            return ErrorModalMode::new(TenexError::SettingsSave {
+                detail: err.to_string(),
+            })
             .into();
         }
 
@@ -861,6 +1190,37 @@ impl AppData {
         AppMode::normal()
     }
 
+    /// Remember `entry` in the input history of `kind`.
+    ///
+    /// A failed save is only logged: the history must not stop what was submitted.
+    pub(crate) fn record_input_history(&mut self, kind: InputHistoryKind, entry: &str) {
+        if self.settings.input_history.record(kind, entry)
+            && let Err(err) = self.settings.save()
+        {
+            tracing::warn!(error = %err, "Failed to save the input history");
+        }
+    }
+
+    /// Check the prompt in the input before it is sent. Returns `true`, after warning about
+    /// what looks wrong, if it should not be sent yet; submitting the same text again sends it.
+    pub(crate) fn hold_prompt_for_review(&mut self, task_required: bool) -> bool {
+        if !self.settings.prompt_checks()
+            || self.input.prompt_warned.as_ref() == Some(&self.input.buffer)
+        {
+            return false;
+        }
+        let problems = super::prompt_lint::check(&self.input.buffer, task_required);
+        if problems.is_empty() {
+            return false;
+        }
+        self.input.prompt_warned = Some(self.input.buffer.clone());
+        self.notify_warning(format!(
+            "Check the prompt: {}. Press Enter again to send it anyway",
+            problems.join("; ")
+        ));
+        true
+    }
+
     pub(crate) fn toggle_docker_for_new_roots(&mut self) -> AppMode {
         let previous = self.settings.docker_for_new_roots;
         if previous {
@@ -883,20 +1243,116 @@ impl AppData {
                 message: "Building the shipped Tenex Docker worker image. This can take a minute the first time, and the image will be reused for future root agents.".to_string(),
             }
             .into(),
-            Err(err) => ErrorModalMode {
-                message: format!("Cannot enable Docker for new root agents: {err}"),
-            }
+            Err(err) => ErrorModalMode::new(TenexError::wrap("Cannot enable Docker for new root agents", &err))
             .into(),
         }
     }
 
+    /// Cycle the sandbox policy applied to newly created default agents and persist it.
+    pub(crate) fn cycle_sandbox_for_new_agents(&mut self) -> AppMode {
+        let previous = self.settings.sandbox;
+        let next = previous.next();
+        self.settings.sandbox = next;
+
+        if let Err(err) = self.settings.save() {
+            self.settings.sandbox = previous;
+            return ErrorModalMode::new(TenexError::SettingsSave {
+                detail: err.to_string(),
+            })
+            .into();
+        }
+
+        self.input.clear();
+        self.set_status(format!("Sandbox for new agents: {}", next.label()));
+        AppMode::normal()
+    }
+
+    /// Switch to the next settings profile by name, or to none after the last one, and save it
+    /// as the profile Tenex starts with (`/profile`).
+    pub(crate) fn cycle_profile(&mut self) -> AppMode {
+        self.input.clear();
+        if self.settings.profiles.is_empty() {
+            self.set_status("No profiles in settings.json");
+            return AppMode::normal();
+        }
+        let previous = self.settings.active_profile().map(str::to_string);
+        let names: Vec<String> = self.settings.profiles.keys().cloned().collect();
+        let next = previous.as_ref().map_or_else(
+            || names.first().cloned(),
+            |current| {
+                names
+                    .iter()
+                    .position(|name| name == current)
+                    .and_then(|index| names.get(index + 1))
+                    .cloned()
+            },
+        );
+
+        self.settings.activate_profile(next.as_deref());
+        let saved = std::mem::replace(&mut self.settings.profile, next.clone());
+        if let Err(err) = self.settings.save() {
+            self.settings.profile = saved;
+            self.settings.activate_profile(previous.as_deref());
+            return ErrorModalMode::new(TenexError::SettingsSave {
+                detail: err.to_string(),
+            })
+            .into();
+        }
+        self.config.apply_settings(&self.settings);
+        self.set_status(format!("Profile: {}", next.as_deref().unwrap_or("none")));
+        AppMode::normal()
+    }
+
+    /// Switch to the next theme and persist it.
+    pub(crate) fn cycle_theme(&mut self) -> AppMode {
+        self.input.clear();
+        let previous = self.settings.theme;
+        self.settings.theme = previous.next();
+        if let Err(err) = self.settings.save() {
+            self.settings.theme = previous;
+            return ErrorModalMode::new(TenexError::SettingsSave {
+                detail: err.to_string(),
+            })
+            .into();
+        }
+        self.set_status(format!("Theme: {}", self.settings.theme.label()));
+        AppMode::normal()
+    }
+
+    /// Turn local usage metrics on or off and persist the choice.
+    pub(crate) fn toggle_metrics(&mut self) -> AppMode {
+        self.input.clear();
+        let previous = self.settings.metrics;
+        let enabled = !previous.is_on();
+        self.settings.metrics = if enabled {
+            MetricsRecording::Local
+        } else {
+            MetricsRecording::Off
+        };
+        if let Err(err) = self.settings.save() {
+            self.settings.metrics = previous;
+            return ErrorModalMode::new(TenexError::SettingsSave {
+                detail: err.to_string(),
+            })
+            .into();
+        }
+        crate::metrics::set_enabled(enabled);
+        self.set_status(if enabled {
+            "Metrics: ON (see `tenex report --metrics`)"
+        } else {
+            "Metrics: OFF"
+        });
+        AppMode::normal()
+    }
+
     pub(crate) fn finish_preparing_docker_for_new_roots(&mut self) -> AppMode {
         let programs = self.docker_runtime_programs();
         let program_refs = programs.iter().map(String::as_str).collect::<Vec<_>>();
         if let Err(err) = crate::runtime::prepare_docker_runtime(&self.settings, &program_refs) {
-            return ErrorModalMode {
-                message: format!("Cannot enable Docker for new root agents: {err}"),
-            }
+            return ErrorModalMode::new(TenexError::wrap(
+                "Cannot enable Docker for new root agents",
+                &err,
+            ))
             .into();
         }
 
@@ -912,17 +1368,17 @@ impl AppData {
         let selected = self
             .filtered_slash_commands()
             .get(self.command_palette.selected)
-            .copied();
-        if let Some(cmd) = selected {
-            return self.run_slash_command_by_name(cmd.name);
+            .map(|cmd| cmd.name.to_string());
+        if let Some(name) = selected {
+            return self.run_slash_command_by_name(&name);
         }
 
-        self.run_typed_slash_command(crate::app::state::SLASH_COMMANDS)
+        self.run_typed_slash_command()
     }
 
     /// Execute the currently-typed slash command (ignores the highlighted selection).
     pub fn submit_slash_command_palette(&mut self) -> AppMode {
-        self.run_typed_slash_command(crate::app::state::SLASH_COMMANDS)
+        self.run_typed_slash_command()
     }
 
     /// Insert a character into the input buffer.
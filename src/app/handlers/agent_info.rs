@@ -0,0 +1,549 @@
+//! Agent details (`i`) and prompt (`I`) modals for the selected agent.
+
+use std::path::Path;
+use std::process::Command;
+use std::sync::atomic::AtomicBool;
+
+use anyhow::Result;
+use chrono::Local;
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::agent::{Agent, AgentNotifications, AgentRuntime, WorkspaceKind};
+use crate::app::{AppData, Settings};
+use crate::conversation::ContextUsage;
+use crate::error::TenexError;
+use crate::git;
+use crate::mux::SessionManager;
+use crate::plugin::{AgentContext, Plugin};
+use crate::prompts;
+use crate::state::{AppMode, ChangelogMode, ErrorModalMode};
+
+use super::Actions;
+
+/// Longest prompt excerpt shown in the details modal, in columns.
+const PROMPT_EXCERPT_WIDTH: usize = 200;
+
+/// Start of the details line that shows the agent's notification preference.
+const NOTIFICATIONS_PREFIX: &str = "Notifications: ";
+
+/// Start of the details line that shows the agent's restart policy.
+const RESTART_PREFIX: &str = "Restart: ";
+
+/// Look up the pull request for `branch` with the `gh` CLI.
+///
+/// Returns `None` when there is no PR or `gh` is unavailable; only cancellation is an error.
+fn find_pr(worktree_path: &Path, branch: &str, cancelled: &AtomicBool) -> Result<Option<String>> {
+    let mut command = Command::new("gh");
+    command
+        .args([
+            "pr",
+            "view",
+            branch,
+            "--json",
+            "url,state",
+            "--jq",
+            r#""\(.url) (\(.state | ascii_downcase))""#,
+        ])
+        .current_dir(worktree_path);
+    let output = match git::output_cancellable(&mut command, cancelled) {
+        Ok(output) => output,
+        Err(err) if err.is::<git::Cancelled>() => return Err(err),
+        Err(err) => {
+            debug!(branch, error = %err, "Skipping PR lookup");
+            return Ok(None);
+        }
+    };
+    let pr = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Ok((output.status.success() && !pr.is_empty()).then_some(pr))
+}
+
+/// `Title (short id)` for each ancestor of `agent`, root first.
+fn parent_chain(app_data: &AppData, agent: &Agent) -> String {
+    let mut chain = Vec::new();
+    let mut parent_id = agent.parent_id;
+    while let Some(parent) = parent_id.and_then(|id| app_data.storage.get(id)) {
+        chain.push(format!("{} ({})", parent.title, parent.short_id()));
+        parent_id = parent.parent_id;
+    }
+    if chain.is_empty() {
+        return "none (root agent)".to_string();
+    }
+    chain.reverse();
+    chain.join(" → ")
+}
+
+fn last_activity(app_data: &AppData, agent: &Agent) -> String {
+    let ui = &app_data.ui;
+    let activity = if ui.agent_has_unseen_waiting_output(agent.id) {
+        "waiting for input (unseen output)"
+    } else if ui.agent_is_waiting_for_input(agent.id) {
+        "waiting for input"
+    } else if ui.agent_has_finished(agent.id) {
+        "finished"
+    } else if ui.pane_digest_by_agent.contains_key(&agent.id) {
+        "producing output"
+    } else {
+        "not observed yet"
+    };
+    format!(
+        "{activity}; {} active, {} idle",
+        agent.active_string(),
+        agent.idle_string()
+    )
+}
+
+/// First line of `prompt`, shortened to [`PROMPT_EXCERPT_WIDTH`].
+fn prompt_excerpt(prompt: &str) -> String {
+    let first_line = prompt.trim().lines().next().unwrap_or_default();
+    let mut excerpt =
+        crate::text_width::truncate_to_width(first_line, PROMPT_EXCERPT_WIDTH).to_string();
+    let line_count = prompt.trim().lines().count();
+    if excerpt.len() < first_line.len() || line_count > 1 {
+        excerpt.push('…');
+    }
+    if line_count > 1 {
+        return format!("{excerpt} ({line_count} lines)");
+    }
+    excerpt
+}
+
+/// `Ticket: ID (link)`, with the link when the repository configures `tenex.ticketUrl`.
+fn ticket_line(agent: &Agent) -> Option<String> {
+    let ticket = agent.ticket.as_deref()?;
+    let url = agent
+        .repo_root
+        .as_deref()
+        .and_then(|repo_root| git::ticket_url(repo_root, ticket));
+    Some(url.map_or_else(
+        || format!("Ticket: {ticket}"),
+        |url| format!("Ticket: {ticket} ({url})"),
+    ))
+}
+
+/// `Scope: dir/`, followed by the flagged changes: files outside the scope and protected
+/// files.
+fn scope_lines(app_data: &AppData, agent: &Agent) -> Vec<String> {
+    let mut lines: Vec<String> = agent
+        .scope
+        .iter()
+        .map(|scope| format!("Scope: {scope}"))
+        .collect();
+    let Some(alerts) = app_data.ui.change_alerts_by_agent.get(&agent.id) else {
+        return lines;
+    };
+    if !alerts.outside_scope.is_empty() {
+        lines.push("Changed outside the scope:".to_string());
+        lines.extend(
+            alerts
+                .outside_scope
+                .iter()
+                .map(|path| format!("- {}", path.display())),
+        );
+    }
+    if !alerts.protected.is_empty() {
+        lines.push("Changed protected paths:".to_string());
+        lines.extend(
+            alerts
+                .protected
+                .iter()
+                .map(|path| format!("- {}", path.display())),
+        );
+    }
+    lines
+}
+
+/// The agent's latest resource and context usage, as far as they are known.
+fn usage_lines(app_data: &AppData, agent: &Agent) -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Some(usage) = app_data.ui.resource_usage_by_agent.get(&agent.id) {
+        lines.push(format!(
+            "Resources: {}% CPU, {} memory, {} process(es)",
+            usage.cpu_percent,
+            usage.memory_label(),
+            usage.processes
+        ));
+    }
+    if let Some(usage) = app_data.ui.context_usage_by_agent.get(&agent.id) {
+        lines.push(context_line(*usage));
+    }
+    lines.extend(spend_line(app_data, agent));
+    lines
+}
+
+fn spend_line(app_data: &AppData, agent: &Agent) -> Option<String> {
+    if agent.costs.is_empty() {
+        return None;
+    }
+    let spent = super::budget::format_usd(agent.cost_micros());
+    let mut line = app_data.settings.agent_budget_micros().map_or_else(
+        || format!("Spend: {spent} (estimated)"),
+        |limit| {
+            format!(
+                "Spend: {spent} of {} (estimated)",
+                super::budget::format_usd(limit)
+            )
+        },
+    );
+    if agent.budget.is_paused() {
+        line.push_str(", paused over budget");
+    }
+    Some(line)
+}
+
+fn context_line(usage: ContextUsage) -> String {
+    match (usage.window, usage.percent()) {
+        (Some(window), Some(percent)) => {
+            format!("Context: {} of {window} tokens ({percent}%)", usage.tokens)
+        }
+        _ => format!("Context: {} tokens", usage.tokens),
+    }
+}
+
+fn notifications_line(notifications: AgentNotifications) -> String {
+    format!(
+        "{NOTIFICATIONS_PREFIX}{} (press n to change)",
+        notifications.label()
+    )
+}
+
+fn restart_line(agent: &Agent, settings: &Settings) -> String {
+    let policy = settings.restart_policy_for(agent);
+    let source = if agent.restart_policy.is_some() {
+        ""
+    } else if settings.restart_policy.is_some() {
+        " from settings"
+    } else {
+        " by default"
+    };
+    let restarts = if agent.restarts > 0 {
+        format!(", restarted {} time(s)", agent.restarts)
+    } else {
+        String::new()
+    };
+    format!(
+        "{RESTART_PREFIX}{}{source}{restarts} (press r to change)",
+        policy.summary()
+    )
+}
+
+fn max_runtime_line(agent: &Agent, default_mins: Option<u64>) -> Option<String> {
+    let minutes = agent
+        .max_runtime_mins
+        .or(default_mins)
+        .filter(|minutes| *minutes > 0 && !agent.is_terminal_agent())?;
+    Some(format!("Max runtime: {minutes} minutes"))
+}
+
+/// The combined status of a swarm that spans repositories: the swarm root of a root started in
+/// another repository, or the roots the agent's swarm started in other repositories.
+fn swarm_link_lines(app_data: &AppData, agent: &Agent) -> Vec<String> {
+    let describe = |linked: &Agent| {
+        let repo = linked
+            .repo_root
+            .as_deref()
+            .unwrap_or(&linked.worktree_path)
+            .display();
+        let children = app_data.storage.child_count(linked.id);
+        format!(
+            "- {} ({}) in {repo}: {}, {children} sub-agent(s), {}",
+            linked.title,
+            linked.short_id(),
+            linked.status,
+            last_activity(app_data, linked)
+        )
+    };
+
+    let mut lines = Vec::new();
+    if let Some(root) = agent.swarm_root.and_then(|id| app_data.storage.get(id)) {
+        lines.push(String::new());
+        lines.push("### Cross-repo swarm".to_string());
+        lines.push("Swarm root:".to_string());
+        lines.push(describe(root));
+    }
+    let linked = app_data.storage.linked_roots(agent.id);
+    if !linked.is_empty() {
+        lines.push(String::new());
+        lines.push("### Cross-repo swarm".to_string());
+        lines.push(format!(
+            "Roots in other repositories ({}), collected by this agent's synthesis:",
+            linked.len()
+        ));
+        lines.extend(linked.into_iter().map(describe));
+    }
+    lines
+}
+
+fn agent_info_lines(app_data: &AppData, agent: &Agent, pr: Option<&str>) -> Vec<String> {
+    let runtime = match agent.runtime {
+        AgentRuntime::Host => "host",
+        AgentRuntime::Docker => "docker",
+    };
+    let target = agent.window_index.map_or_else(
+        || agent.mux_session.clone(),
+        |window_idx| SessionManager::window_target(&agent.mux_session, window_idx),
+    );
+
+    let mut lines = vec![
+        agent.title.clone(),
+        String::new(),
+        "### Agent".to_string(),
+        format!("ID: {}", agent.id),
+        format!("Program: {}", agent.program),
+        format!("Status: {}", agent.status),
+        format!("Runtime: {runtime} (sandbox: {})", agent.sandbox.label()),
+    ];
+    if let Some(conversation_id) = &agent.conversation_id {
+        lines.push(format!("Conversation: {conversation_id}"));
+    }
+    lines.push(format!("Parent chain: {}", parent_chain(app_data, agent)));
+    let children = app_data.storage.child_count(agent.id);
+    if children > 0 {
+        lines.push(format!("Sub-agents: {children}"));
+    }
+    lines.push(format!(
+        "Created: {} ({} ago)",
+        agent
+            .created_at
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M:%S"),
+        agent.age_string()
+    ));
+    lines.push(format!("Last activity: {}", last_activity(app_data, agent)));
+    lines.push(notifications_line(agent.notifications));
+    lines.push(restart_line(agent, &app_data.settings));
+    lines.extend(max_runtime_line(agent, app_data.settings.max_runtime_mins));
+    lines.extend(usage_lines(app_data, agent));
+
+    lines.push(String::new());
+    lines.push("### Workspace".to_string());
+    match agent.workspace_kind {
+        WorkspaceKind::GitWorktree => {
+            lines.push(format!("Branch: {}", agent.branch));
+            let root_id = app_data
+                .storage
+                .root_ancestor(agent.id)
+                .map_or(agent.id, |root| root.id);
+            let base_branch = app_data
+                .storage
+                .get(root_id)
+                .and_then(|root| root.base_branch.clone());
+            match (
+                base_branch,
+                app_data.ui.branch_divergence_by_agent.get(&root_id),
+            ) {
+                (Some(base), Some(divergence)) => lines.push(format!(
+                    "Base branch: {base} (↑{} ↓{} against {})",
+                    divergence.ahead, divergence.behind, divergence.base_ref
+                )),
+                (Some(base), None) => lines.push(format!("Base branch: {base}")),
+                (None, _) => {}
+            }
+            lines.push(format!("Worktree: {}", agent.worktree_path.display()));
+        }
+        WorkspaceKind::PlainDir => {
+            lines.push(format!("Directory: {}", agent.worktree_path.display()));
+        }
+    }
+    if let Some(repo_root) = &agent.repo_root {
+        lines.push(format!("Repository: {}", repo_root.display()));
+    }
+    if agent.is_git_workspace() {
+        lines.push(format!("Pull request: {}", pr.unwrap_or("none found")));
+    }
+    lines.extend(scope_lines(app_data, agent));
+    lines.extend(ticket_line(agent));
+
+    lines.extend(swarm_link_lines(app_data, agent));
+
+    lines.push(String::new());
+    lines.push("### Session".to_string());
+    lines.push(format!("Mux session: {}", agent.mux_session));
+    lines.push(format!("Target: {target}"));
+
+    lines.push(String::new());
+    lines.push("### Prompt".to_string());
+    match agent.prompt.as_deref() {
+        Some(prompt) => {
+            lines.push(prompt_excerpt(prompt));
+            lines.push("Press I to see the full prompt.".to_string());
+        }
+        None => lines.push("(started without a prompt)".to_string()),
+    }
+    lines
+}
+
+fn prompt_lines(title: &str, prompt: &str) -> Vec<String> {
+    let mut lines = vec![title.to_string()];
+    let mut section = |heading: String, text: &str| {
+        lines.push(String::new());
+        lines.push(heading);
+        lines.extend(text.lines().map(str::to_string));
+    };
+    match prompts::split_preamble(prompt) {
+        Some((kind, preamble, task)) => {
+            if !task.is_empty() {
+                section("### Task".to_string(), task);
+            }
+            section(format!("### {kind} preamble"), preamble);
+        }
+        None => section("### Prompt".to_string(), prompt.trim()),
+    }
+    lines
+}
+
+impl Actions {
+    /// Show the selected agent's details in a scrollable modal (`i`).
+    ///
+    /// Git agents first look up their pull request, and plugins with detail panels render
+    /// theirs, in the background, so the modal opens once `gh` and the plugins have answered.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the details cannot be gathered.
+    pub fn show_agent_info(app_data: &mut AppData) -> Result<AppMode> {
+        let Some(agent) = app_data.selected_agent() else {
+            return Ok(ErrorModalMode::new(TenexError::NoAgentSelected {
+                action: "see its details",
+            })
+            .into());
+        };
+        let agent_id = agent.id;
+        let panel_plugins: Vec<Plugin> = app_data
+            .plugins
+            .iter()
+            .filter(|plugin| !plugin.manifest.panels.is_empty())
+            .cloned()
+            .collect();
+        let lookup_pr = agent.is_git_workspace();
+        if !lookup_pr && panel_plugins.is_empty() {
+            return Ok(Self::agent_info_mode(app_data, agent_id, None, Vec::new()));
+        }
+
+        let title = agent.title.clone();
+        let worktree_path = agent.worktree_path.clone();
+        let branch = agent.branch.clone();
+        let context = AgentContext::from(agent);
+        app_data.background_ops.spawn_cancellable(
+            None,
+            format!("Loading details for {title}"),
+            move |cancelled| {
+                let pr = if lookup_pr {
+                    find_pr(&worktree_path, &branch, cancelled)
+                } else {
+                    Ok(None)
+                };
+                let panels = super::plugins::panel_lines(&panel_plugins, &context, cancelled);
+                Box::new(move |app_data: &mut AppData| {
+                    Ok(Self::agent_info_mode(
+                        app_data,
+                        agent_id,
+                        pr?.as_deref(),
+                        panels?,
+                    ))
+                })
+            },
+        );
+        Ok(AppMode::normal())
+    }
+
+    /// Show the prompt the selected agent was started with (`I`).
+    ///
+    /// Planner and reviewer prompts show the task first and the generated preamble after it.
+    pub fn show_agent_prompt(app_data: &mut AppData) -> AppMode {
+        let Some(agent) = app_data.selected_agent() else {
+            return ErrorModalMode::new(TenexError::NoAgentSelected {
+                action: "see its prompt",
+            })
+            .into();
+        };
+        let Some(prompt) = agent.prompt.as_deref() else {
+            let message = format!("{} was started without a prompt", agent.title);
+            app_data.set_status(message);
+            return AppMode::normal();
+        };
+        ChangelogMode {
+            title: format!("Prompt: {}", agent.title),
+            lines: prompt_lines(&agent.title, prompt),
+            mark_seen_version: None,
+            agent_id: None,
+        }
+        .into()
+    }
+
+    fn agent_info_mode(
+        app_data: &AppData,
+        agent_id: Uuid,
+        pr: Option<&str>,
+        panels: Vec<String>,
+    ) -> AppMode {
+        let Some(agent) = app_data.storage.get(agent_id) else {
+            return ErrorModalMode::new(TenexError::AgentMissing).into();
+        };
+        let mut lines = agent_info_lines(app_data, agent, pr);
+        lines.extend(panels);
+        ChangelogMode {
+            title: format!("Agent: {}", agent.title),
+            lines,
+            mark_seen_version: None,
+            agent_id: Some(agent_id),
+        }
+        .into()
+    }
+
+    /// Move the agent shown in the details modal on to its next notification preference (`n`).
+    pub fn cycle_agent_notifications(app_data: &mut AppData, mut mode: ChangelogMode) -> AppMode {
+        let Some(agent) = mode.agent_id.and_then(|id| app_data.storage.get_mut(id)) else {
+            return mode.into();
+        };
+        agent.notifications = agent.notifications.next();
+        let notifications = agent.notifications;
+        let title = agent.title.clone();
+        if let Err(err) = app_data.storage.save() {
+            return ErrorModalMode::new(TenexError::wrap("Failed to save agent state", &err))
+                .into();
+        }
+
+        if let Some(line) = mode
+            .lines
+            .iter_mut()
+            .find(|line| line.starts_with(NOTIFICATIONS_PREFIX))
+        {
+            *line = notifications_line(notifications);
+        }
+        app_data.set_status(format!(
+            "Notifications for {title}: {}",
+            notifications.label()
+        ));
+        mode.into()
+    }
+
+    /// Move the agent shown in the details modal on to its next restart mode (`r`).
+    ///
+    /// The agent keeps its own copy of the policy from then on, starting from the settings'
+    /// retry limit and backoff.
+    pub fn cycle_agent_restart(app_data: &mut AppData, mut mode: ChangelogMode) -> AppMode {
+        let Some(agent) = mode.agent_id.and_then(|id| app_data.storage.get_mut(id)) else {
+            return mode.into();
+        };
+        let mut policy = app_data.settings.restart_policy_for(agent);
+        policy.mode = policy.mode.next();
+        agent.restart_policy = Some(policy);
+        let line = restart_line(agent, &app_data.settings);
+        let title = agent.title.clone();
+        if let Err(err) = app_data.storage.save() {
+            return ErrorModalMode::new(TenexError::wrap("Failed to save agent state", &err))
+                .into();
+        }
+
+        if let Some(existing) = mode
+            .lines
+            .iter_mut()
+            .find(|existing| existing.starts_with(RESTART_PREFIX))
+        {
+            *existing = line;
+        }
+        app_data.set_status(format!("Restart for {title}: {}", policy.mode.label()));
+        mode.into()
+    }
+}
@@ -1,19 +1,21 @@
 //! Agent lifecycle operations: create, kill, reconnect
 
-use crate::agent::{Agent, AgentRuntime, ChildConfig};
+use crate::agent::{Agent, AgentRuntime, AgentSandbox, ChildConfig};
+use crate::error::TenexError;
 use crate::git::{self, WorktreeCreateOptions, WorktreeManager};
 use crate::mux::SessionManager;
 use anyhow::{Context, Result, bail};
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
-use std::time::{Duration, SystemTime};
+use std::time::{Duration, Instant, SystemTime};
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
 use super::Actions;
 use super::swarm::SpawnConfig;
-use crate::app::{AppData, WorktreeConflictInfo};
+use crate::app::state::BackgroundCompletion;
+use crate::app::{AgentRole, AppData, WorktreeConflictInfo};
 use crate::config::Config;
 use crate::state::{AppMode, ConfirmAction, ConfirmingMode, ErrorModalMode};
 
@@ -23,6 +25,17 @@ struct BranchSwitchTarget {
     worktree_path: PathBuf,
 }
 
+/// What happens to a killed root agent's worktree and branch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WorkspaceCleanup {
+    /// Leave the worktree and branch in place.
+    Keep,
+    /// Remove the worktree but keep the branch.
+    RemoveWorktree,
+    /// Remove the worktree and delete the branch.
+    RemoveWorktreeAndBranch,
+}
+
 #[derive(Debug)]
 struct RootLaunchSpec {
     title: String,
@@ -33,6 +46,30 @@ struct RootLaunchSpec {
     worktree_path: PathBuf,
 }
 
+/// Create the worktree and branch of the new root `agent` in the repository at `repo_path`.
+///
+/// Runs on a background thread. Creating a worktree can't be interrupted, so a cancellation
+/// removes the finished worktree and branch again.
+pub(super) fn create_root_worktree(
+    repo_path: &Path,
+    agent: &Agent,
+    options: WorktreeCreateOptions,
+    cancelled: &std::sync::atomic::AtomicBool,
+) -> Result<()> {
+    let repo = git::open_repository(repo_path)?;
+    let worktree_mgr = WorktreeManager::new(&repo);
+    worktree_mgr.create_with_new_branch_with_options(
+        &agent.worktree_path,
+        &agent.branch,
+        options,
+    )?;
+    if cancelled.load(std::sync::atomic::Ordering::Relaxed) {
+        worktree_mgr.remove(&agent.branch)?;
+        return Err(git::Cancelled.into());
+    }
+    Ok(())
+}
+
 fn runtime_for_conflict(
     app_data: &AppData,
     conflict: &WorktreeConflictInfo,
@@ -65,6 +102,7 @@ impl Actions {
         }
 
         if agent.is_root() {
+            agent.sandbox = app_data.settings.sandbox_for(AgentRole::Default);
             let session_prefix = app_data.storage.instance_session_prefix();
             agent.mux_session = format!("{session_prefix}{}", agent.short_id());
             if agent.runtime == AgentRuntime::Docker && agent.runtime_scope.is_empty() {
@@ -73,7 +111,22 @@ impl Actions {
         }
     }
 
-    fn finish_agent_launch(app_data: &AppData, agent: &mut Agent, started_at: SystemTime) {
+    /// Swap `agent`'s program for a fallback when it is not on `PATH`. A conversation of the
+    /// old program cannot be resumed by another one, so it is dropped.
+    fn resolve_program_for_launch(app_data: &mut AppData, agent: &mut Agent) -> Result<()> {
+        let program = app_data.resolve_agent_program(agent.program.clone(), agent.runtime)?;
+        if program != agent.program {
+            agent.program = program;
+            agent.conversation_id = None;
+        }
+        Ok(())
+    }
+
+    pub(super) fn finish_agent_launch(
+        app_data: &AppData,
+        agent: &mut Agent,
+        started_at: SystemTime,
+    ) {
         if crate::conversation::detect_agent_cli(&agent.program)
             == crate::conversation::AgentCli::Codex
         {
@@ -117,23 +170,118 @@ impl Actions {
         }
     }
 
+    /// Warn loudly when a worktree-backed agent is about to run in the primary checkout.
+    ///
+    /// Plain-directory agents intentionally share their directory, so they are exempt.
+    fn warn_if_launching_in_main_checkout(app_data: &mut AppData, agent: &Agent) {
+        if !agent.is_git_workspace() || !git::is_main_checkout(&agent.worktree_path) {
+            return;
+        }
+
+        warn!(
+            title = %agent.title,
+            path = %agent.worktree_path.display(),
+            "Launching agent with the main repository checkout as its working directory"
+        );
+        app_data.notify_warning(format!(
+            "{} is running in the main checkout {}, not a Tenex worktree",
+            agent.title,
+            agent.worktree_path.display()
+        ));
+    }
+
     pub(crate) fn launch_root_agent(
         self,
         app_data: &mut AppData,
         agent: &mut Agent,
         prompt: Option<&str>,
     ) -> Result<()> {
+        self.launch_root_agent_with(
+            app_data,
+            agent,
+            crate::runtime::AgentLaunch::Spawn { prompt },
+        )
+    }
+
+    /// Launch a root agent for an existing worktree, resuming the latest conversation its
+    /// program had there when one can be found, or from the handoff notes left there otherwise.
+    pub(crate) fn resume_root_agent(self, app_data: &mut AppData, agent: &mut Agent) -> Result<()> {
+        // Conversations of Docker agents live inside their containers.
+        if agent.runtime == AgentRuntime::Host {
+            agent.conversation_id =
+                crate::conversation::latest_conversation_id(&agent.program, &agent.worktree_path);
+        }
+        if agent.conversation_id.is_some() {
+            return self.launch_root_agent_with(
+                app_data,
+                agent,
+                crate::runtime::AgentLaunch::Resume,
+            );
+        }
+        // Without a conversation to resume, the notes of the last agent here are all the
+        // context the new one gets.
+        let prompt = Self::take_handoff_prompt(&agent.worktree_path, None);
+        self.launch_root_agent(app_data, agent, prompt.as_deref())
+    }
+
+    /// Give a root agent for an existing worktree a shell session without starting its
+    /// program.
+    pub(crate) fn adopt_root_agent(self, app_data: &mut AppData, agent: &mut Agent) -> Result<()> {
         Self::prepare_agent_for_launch(app_data, agent);
-        crate::runtime::ensure_runtime_ready(agent, &app_data.settings)?;
+        // No conversation was started, so there is nothing to resume on a later respawn.
+        agent.conversation_id = None;
+        let command = crate::runtime::build_terminal_command(agent, None, &app_data.settings);
+        self.session_manager.create(
+            &agent.mux_session,
+            &agent.worktree_path,
+            command.as_deref(),
+        )?;
+        self.resize_target_to_preview(app_data, &agent.mux_session);
+        Ok(())
+    }
+
+    /// Start the session of a root agent imported from tmux, which runs a tmux client
+    /// attached to the imported session.
+    pub(crate) fn launch_tmux_import(
+        self,
+        app_data: &mut AppData,
+        agent: &mut Agent,
+    ) -> Result<()> {
+        Self::prepare_agent_for_launch(app_data, agent);
+        // The client has to reach the tmux server's socket, so it never runs sandboxed.
+        agent.sandbox = AgentSandbox::Off;
         let command = crate::runtime::build_agent_command(
             agent,
-            crate::runtime::AgentLaunch::Spawn { prompt },
+            crate::runtime::AgentLaunch::Spawn { prompt: None },
             &app_data.settings,
-        );
+        )?;
+        self.session_manager
+            .create(&agent.mux_session, &agent.worktree_path, Some(&command))?;
+        self.resize_target_to_preview(app_data, &agent.mux_session);
+        Ok(())
+    }
+
+    fn launch_root_agent_with(
+        self,
+        app_data: &mut AppData,
+        agent: &mut Agent,
+        launch: crate::runtime::AgentLaunch<'_>,
+    ) -> Result<()> {
+        Self::resolve_program_for_launch(app_data, agent)?;
+        Self::prepare_agent_for_launch(app_data, agent);
+        Self::warn_if_launching_in_main_checkout(app_data, agent);
+        crate::runtime::ensure_runtime_ready(agent, &app_data.settings)?;
+        let command = crate::runtime::build_agent_command(agent, launch, &app_data.settings);
         let command = command?;
         let started_at = SystemTime::now();
         self.session_manager
             .create(&agent.mux_session, &agent.worktree_path, Some(&command))?;
+        if let crate::runtime::AgentLaunch::Spawn {
+            prompt: Some(prompt),
+        } = launch
+        {
+            agent.prompt = Some(prompt.to_string());
+        }
         Self::finish_agent_launch(app_data, agent, started_at);
         self.resize_target_to_preview(app_data, &agent.mux_session);
         Ok(())
@@ -146,6 +294,8 @@ impl Actions {
         title: &str,
         prompt: Option<&str>,
     ) -> Result<u32> {
+        Self::resolve_program_for_launch(app_data, agent)?;
+        Self::warn_if_launching_in_main_checkout(app_data, agent);
         crate::runtime::ensure_runtime_ready(agent, &app_data.settings)?;
         let command = crate::runtime::build_agent_command(
             agent,
@@ -161,6 +311,9 @@ impl Actions {
             Some(&command),
         );
         let actual_index = actual_index?;
+        if let Some(prompt) = prompt {
+            agent.prompt = Some(prompt.to_string());
+        }
         Self::finish_agent_launch(app_data, agent, started_at);
         let target = SessionManager::window_target(&agent.mux_session, actual_index);
         self.resize_target_to_preview(app_data, &target);
@@ -169,8 +322,12 @@ impl Actions {
 
     /// Create a new agent
     ///
+    /// A ticket ID at the start of `title` (`PROJ-123 Fix login`) becomes the agent's ticket
+    /// unless one is already pending in `app_data.spawn`, and goes into the branch name.
+    ///
     /// If a worktree with the same name already exists, this will prompt the user
-    /// to either reconnect to the existing worktree or recreate it from scratch.
+    /// to either reconnect to the existing worktree or recreate it from scratch. Otherwise the
+    /// worktree is created in the background and the agent starts once it is ready.
     ///
     /// # Errors
     ///
@@ -180,20 +337,75 @@ impl Actions {
         app_data: &mut AppData,
         title: &str,
         prompt: Option<&str>,
+    ) -> Result<AppMode> {
+        let started = Instant::now();
+        let ops_before = app_data.background_ops.in_flight().len();
+        let result = self.try_create_agent(app_data, title, prompt);
+        // A pending ticket only outlives this call while the user resolves a worktree conflict.
+        let resolving_conflict = matches!(
+            result,
+            Ok(AppMode::Confirming(ConfirmingMode {
+                action: ConfirmAction::WorktreeConflict
+            }))
+        );
+        if !resolving_conflict {
+            app_data.spawn.ticket = None;
+            app_data.spawn.program = None;
+            // Agents whose worktree is still being created are recorded once it is ready.
+            let failed = !matches!(result, Ok(ref mode) if !matches!(mode, AppMode::ErrorModal(_)));
+            let creating_worktree = app_data.background_ops.in_flight().len() > ops_before;
+            if failed || !creating_worktree {
+                crate::metrics::record_spawn(started.elapsed(), !failed);
+            }
+        }
+        result
+    }
+
+    fn try_create_agent(
+        self,
+        app_data: &mut AppData,
+        title: &str,
+        prompt: Option<&str>,
     ) -> Result<AppMode> {
         debug!(title, prompt, "Creating new agent");
 
+        let title = if app_data.spawn.ticket.is_some() {
+            title
+        } else {
+            let (ticket, title) = crate::agent::split_ticket(title);
+            app_data.spawn.ticket = ticket;
+            title
+        };
+        let branch_title = crate::agent::branch_title(app_data.spawn.ticket.as_deref(), title);
+
         let current_dir = std::env::current_dir().ok();
         let repo_path = app_data
             .selected_project_root()
             .or_else(|| app_data.cwd_project_root.clone())
             .or(current_dir)
             .context("Failed to resolve target directory")?;
-        let Ok(repo) = git::open_repository(&repo_path) else {
+        app_data.with_repo_config(&repo_path.clone(), |app_data| {
+            self.create_agent_in(app_data, title, prompt, &branch_title, repo_path)
+        })
+    }
+
+    /// Create the agent in the directory `repo_path`, in a new worktree when it is in a git
+    /// repository.
+    fn create_agent_in(
+        self,
+        app_data: &mut AppData,
+        title: &str,
+        prompt: Option<&str>,
+        branch_title: &str,
+        repo_path: PathBuf,
+    ) -> Result<AppMode> {
+        if git::open_repository(&repo_path).is_err() {
             self.create_agent_in_plain_dir(app_data, title, prompt, &repo_path)?;
             return Ok(AppMode::normal());
-        };
-        let branch = app_data.config.generate_branch_name(title);
+        }
+        let repo_path = Self::worktree_source_repo(app_data, repo_path);
+        let repo = git::open_repository(&repo_path)?;
+        let branch = app_data.config.generate_branch_name(branch_title);
         let worktree_path = app_data
             .config
             .worktree_path_for_repo_root(&repo_path, &branch);
@@ -237,11 +449,15 @@ impl Actions {
             .into());
         }
 
-        let cleaned_stale_target = target_preparation.cleaned_stale_target();
-        self.create_agent_internal(app_data, &repo_path, title, prompt, &branch, &worktree_path)?;
-        if cleaned_stale_target {
-            app_data.set_status(format!("Cleaned stale worktree and created agent: {title}"));
-        }
+        Self::create_agent_internal(
+            app_data,
+            &repo_path,
+            title,
+            prompt,
+            &branch,
+            &worktree_path,
+            target_preparation.cleaned_stale_target(),
+        )?;
         Ok(AppMode::normal())
     }
 
@@ -252,14 +468,23 @@ impl Actions {
         prompt: Option<&str>,
         workdir: &Path,
     ) -> Result<()> {
-        let program = app_data.agent_spawn_command();
-        let branch = app_data.config.generate_branch_name(title);
+        let runtime = crate::runtime::new_root_runtime(&app_data.settings);
+        let program =
+            app_data.resolve_agent_program(app_data.new_agent_spawn_command(), runtime)?;
+        let branch = app_data
+            .config
+            .generate_branch_name(&crate::agent::branch_title(
+                app_data.spawn.ticket.as_deref(),
+                title,
+            ));
 
         let mut agent = Agent::new(title.to_string(), program, branch, workdir.to_path_buf());
         agent.workspace_kind = crate::agent::WorkspaceKind::PlainDir;
         agent.repo_root = Some(workdir.to_path_buf());
-        agent.runtime = crate::runtime::new_root_runtime(&app_data.settings);
-        self.launch_root_agent(app_data, &mut agent, prompt)?;
+        agent.runtime = runtime;
+        agent.ticket = app_data.spawn.ticket.take();
+        let prompt = Self::attach_context_files(app_data, workdir, prompt)?;
+        self.launch_root_agent(app_data, &mut agent, prompt.as_deref())?;
 
         let agent_id = agent.id;
         app_data.storage.add(agent);
@@ -272,18 +497,24 @@ impl Actions {
     }
 
     /// Internal function to actually create the agent after conflict resolution
+    ///
+    /// Checks that can fail quickly run here. The worktree is then created in the background,
+    /// where Esc cancels it, and the agent is launched once it is ready.
     fn create_agent_internal(
-        self,
         app_data: &mut AppData,
         repo_path: &Path,
         title: &str,
         prompt: Option<&str>,
         branch: &str,
         worktree_path: &std::path::Path,
+        cleaned_stale_target: bool,
     ) -> Result<()> {
         let repo = git::open_repository(repo_path)?;
         let worktree_mgr = WorktreeManager::new(&repo);
         let runtime = crate::runtime::new_root_runtime(&app_data.settings);
+        // Before the worktree exists, so a missing program leaves nothing behind.
+        let program =
+            app_data.resolve_agent_program(app_data.new_agent_spawn_command(), runtime)?;
         let target_preparation = worktree_mgr.prepare_worktree_creation_target(
             worktree_path,
             branch,
@@ -296,14 +527,9 @@ impl Actions {
             );
         }
 
-        let created = worktree_mgr.create_with_new_branch_with_options(
-            worktree_path,
-            branch,
-            Self::root_worktree_create_options(runtime),
-        );
-        created?;
+        let cleaned_stale_target =
+            cleaned_stale_target || target_preparation.cleaned_stale_target();
 
-        let program = app_data.agent_spawn_command();
         let mut agent = Agent::new(
             title.to_string(),
             program,
@@ -312,15 +538,62 @@ impl Actions {
         );
         agent.repo_root = Some(repo_path.to_path_buf());
         agent.runtime = runtime;
-        self.launch_root_agent(app_data, &mut agent, prompt)?;
+        agent.base_branch = worktree_mgr.head_branch();
+        agent.ticket = app_data.spawn.ticket.take();
+        let context_files = std::mem::take(&mut app_data.spawn.context_files);
+        let prompt = prompt.map(str::to_string);
+        let options = Self::root_worktree_create_options(runtime);
+        let repo_path = repo_path.to_path_buf();
+        let started = Instant::now();
+
+        app_data.background_ops.spawn_cancellable(
+            Some(agent.id),
+            format!("Creating worktree for {branch}"),
+            move |cancelled| {
+                let created = create_root_worktree(&repo_path, &agent, options, cancelled);
+                Box::new(move |app_data: &mut AppData| {
+                    let result = created.and_then(|()| {
+                        Self::new().finish_root_agent_creation(
+                            app_data,
+                            agent,
+                            &context_files,
+                            prompt.as_deref(),
+                            cleaned_stale_target,
+                        )
+                    });
+                    if !matches!(&result, Err(err) if err.is::<git::Cancelled>()) {
+                        crate::metrics::record_spawn(started.elapsed(), result.is_ok());
+                    }
+                    result.map(|()| AppMode::normal())
+                })
+            },
+        );
+        Ok(())
+    }
+
+    /// Launch a new root agent in the worktree created for it and start tracking it.
+    fn finish_root_agent_creation(
+        self,
+        app_data: &mut AppData,
+        mut agent: Agent,
+        context_files: &[PathBuf],
+        prompt: Option<&str>,
+        cleaned_stale_target: bool,
+    ) -> Result<()> {
+        let prompt = Self::copy_context_files(context_files, &agent.worktree_path, prompt)?;
+        self.launch_root_agent(app_data, &mut agent, prompt.as_deref())?;
 
         let agent_id = agent.id;
+        let title = agent.title.clone();
+        let branch = agent.branch.clone();
+        let worktree_path = agent.worktree_path.clone();
         app_data.storage.add(agent);
         app_data.storage.save()?;
         app_data.select_agent_by_id(agent_id);
+        Self::start_worktree_setup(app_data, agent_id, &worktree_path, &branch);
 
-        info!(title, %branch, "Agent created successfully");
-        if target_preparation.cleaned_stale_target() {
+        info!(%title, %branch, "Agent created successfully");
+        if cleaned_stale_target {
             app_data.set_status(format!("Cleaned stale worktree and created agent: {title}"));
         } else {
             app_data.set_status(format!("Created agent: {title}"));
@@ -342,20 +615,30 @@ impl Actions {
 
         debug!(branch = %conflict.branch, swarm_child_count = ?conflict.swarm_child_count, "Reconnecting to existing worktree");
 
-        let program = app_data.agent_spawn_command();
-        let runtime = runtime_for_conflict(app_data, &conflict)
-            .unwrap_or_else(|| crate::runtime::new_root_runtime(&app_data.settings));
-
-        self.remove_conflicting_agents(app_data, &conflict);
-
-        if let Some(child_count) = conflict.swarm_child_count {
-            self.reconnect_swarm_to_worktree(app_data, &conflict, &program, runtime, child_count)?;
-        } else {
-            self.reconnect_single_to_worktree(app_data, &conflict, &program, runtime)?;
-        }
+        app_data.with_repo_config(&conflict.repo_root.clone(), |app_data| {
+            let runtime = runtime_for_conflict(app_data, &conflict)
+                .unwrap_or_else(|| crate::runtime::new_root_runtime(&app_data.settings));
+            let program =
+                app_data.resolve_agent_program(app_data.new_agent_spawn_command(), runtime)?;
+            app_data.spawn.program = None;
+
+            self.remove_conflicting_agents(app_data, &conflict);
+
+            if let Some(child_count) = conflict.swarm_child_count {
+                self.reconnect_swarm_to_worktree(
+                    app_data,
+                    &conflict,
+                    &program,
+                    runtime,
+                    child_count,
+                )?;
+            } else {
+                self.reconnect_single_to_worktree(app_data, &conflict, &program, runtime)?;
+            }
 
-        app_data.storage.save()?;
-        Ok(AppMode::normal())
+            app_data.storage.save()?;
+            Ok(AppMode::normal())
+        })
     }
 
     fn remove_conflicting_agents(
@@ -427,7 +710,8 @@ impl Actions {
         root_agent.repo_root = Some(conflict.repo_root.clone());
         root_agent.runtime = runtime;
 
-        self.launch_root_agent(app_data, &mut root_agent, None)?;
+        let prompt = Self::take_handoff_prompt(&conflict.worktree_path, None);
+        self.launch_root_agent(app_data, &mut root_agent, prompt.as_deref())?;
 
         let root_session = root_agent.mux_session.clone();
         let root_id = root_agent.id;
@@ -471,7 +755,14 @@ impl Actions {
         );
         agent.repo_root = Some(conflict.repo_root.clone());
         agent.runtime = runtime;
-        self.launch_root_agent(app_data, &mut agent, conflict.prompt.as_deref())?;
+        agent.ticket = app_data.spawn.ticket.take();
+        let prompt = Self::attach_context_files(
+            app_data,
+            &conflict.worktree_path,
+            conflict.prompt.as_deref(),
+        )?;
+        let prompt = Self::take_handoff_prompt(&conflict.worktree_path, prompt.as_deref());
+        self.launch_root_agent(app_data, &mut agent, prompt.as_deref())?;
 
         app_data.storage.add(agent);
 
@@ -515,26 +806,63 @@ impl Actions {
             self.spawn_children(app_data, conflict.prompt.as_deref())
         } else {
             // Single agent creation
-            let created = self.create_agent_internal(
-                app_data,
-                &conflict.repo_root,
-                &conflict.title,
-                conflict.prompt.as_deref(),
-                &conflict.branch,
-                &conflict.worktree_path,
-            );
+            let created = app_data.with_repo_config(&conflict.repo_root, |app_data| {
+                Self::create_agent_internal(
+                    app_data,
+                    &conflict.repo_root,
+                    &conflict.title,
+                    conflict.prompt.as_deref(),
+                    &conflict.branch,
+                    &conflict.worktree_path,
+                    false,
+                )
+            });
+            app_data.spawn.program = None;
             created?;
             Ok(AppMode::normal())
         }
     }
 
-    /// Kill the selected agent (and all its descendants)
-    pub(crate) fn kill_agent(self, app_data: &mut AppData) -> Result<()> {
+    /// Kill the selected agent (and all its descendants).
+    ///
+    /// With `keep_workspace`, a root agent's worktree and branch are left in place so the work
+    /// can be picked up again by creating an agent with the same name. When handoffs are
+    /// enabled, the agents first write their handoff notes and the kill happens in the
+    /// background once they are done.
+    pub(crate) fn kill_agent(self, app_data: &mut AppData, keep_workspace: bool) -> Result<()> {
+        let Some(agent) = app_data.selected_agent() else {
+            return Ok(());
+        };
+        let agent_id = agent.id;
+        let mut handoff_ids: Vec<Uuid> = app_data
+            .storage
+            .descendants(agent_id)
+            .iter()
+            .map(|desc| desc.id)
+            .collect();
+        handoff_ids.push(agent_id);
+
+        let then: BackgroundCompletion = Box::new(move |app_data: &mut AppData| {
+            app_data.select_agent_by_id(agent_id);
+            if app_data.selected_agent().map(|agent| agent.id) == Some(agent_id) {
+                Self::new().kill_agent_now(app_data, keep_workspace)?;
+            }
+            Ok(AppMode::normal())
+        });
+        if self.request_handoffs(app_data, agent_id, &handoff_ids, then) {
+            app_data.set_status("Waiting for handoff notes before killing the agent");
+            return Ok(());
+        }
+        self.kill_agent_now(app_data, keep_workspace)
+    }
+
+    fn kill_agent_now(self, app_data: &mut AppData, keep_workspace: bool) -> Result<()> {
         if let Some(agent) = app_data.selected_agent() {
             let agent_id = agent.id;
             let is_root = agent.is_root();
             let session = agent.mux_session.clone();
             let worktree_name = agent.branch.clone();
+            let managed_branch = app_data.is_managed_branch(agent);
             let window_index = agent.window_index;
             let title = agent.title.clone();
 
@@ -547,18 +875,31 @@ impl Actions {
             );
 
             if is_root {
-                let delete_branch = worktree_name.starts_with(&app_data.config.branch_prefix)
-                    || worktree_name.starts_with("tenex/");
-                self.kill_root_agent_tree(app_data, agent_id, delete_branch)?;
-                app_data.set_status("Agent killed");
+                let cleanup = if keep_workspace {
+                    WorkspaceCleanup::Keep
+                } else if managed_branch {
+                    WorkspaceCleanup::RemoveWorktreeAndBranch
+                } else {
+                    WorkspaceCleanup::RemoveWorktree
+                };
+                let status = if keep_workspace {
+                    format!("Agent stopped; kept branch {worktree_name} and its worktree")
+                } else {
+                    "Agent killed".to_string()
+                };
+                let report: BackgroundCompletion = Box::new(move |app_data: &mut AppData| {
+                    app_data.set_status(status);
+                    Ok(AppMode::normal())
+                });
+                self.kill_root_agent_tree(app_data, agent_id, cleanup, report)?;
                 return Ok(());
             }
 
             // Child agent: kill just this window and its descendants
             // Get the root's session for killing windows
             let root = app_data.storage.root_ancestor(agent_id).unwrap_or(agent);
-            let root_session = root.mux_session.clone();
             let root_id = root.id;
+            let root_session = root.mux_session.clone();
 
             // Collect all window indices being deleted
             let mut deleted_indices: Vec<u32> = Vec::new();
@@ -574,21 +915,16 @@ impl Actions {
                 deleted_indices.push(idx);
             }
 
-            // Sort in descending order and kill windows from highest to lowest
-            // This prevents window renumbering from affecting indices we haven't killed yet
-            deleted_indices.sort_unstable_by(|a, b| b.cmp(a));
-            for idx in &deleted_indices {
-                let _ = self.session_manager.kill_window(&root_session, *idx);
-            }
-
-            // Update window indices for remaining agents under the same root
-            // When the mux renumbers windows, indices shift down
-            super::window::adjust_window_indices_after_deletion(
-                app_data,
-                root_id,
-                agent_id,
-                &deleted_indices,
-            );
+            let mut shutdown_ids: Vec<Uuid> = descendants.iter().map(|desc| desc.id).collect();
+            shutdown_ids.push(agent_id);
+            // Window indices are stable, so the remaining agents' windows keep theirs.
+            let kill_windows: BackgroundCompletion = Box::new(move |_: &mut AppData| {
+                for idx in &deleted_indices {
+                    let _ = self.session_manager.kill_window(&root_session, *idx);
+                }
+                Ok(AppMode::normal())
+            });
+            self.shut_down_agents(app_data, Some(root_id), &shutdown_ids, kill_windows)?;
 
             // Remove agent and all descendants from storage
             app_data.storage.remove_with_descendants(agent_id);
@@ -612,10 +948,7 @@ impl Actions {
     pub fn switch_branch(self, app_data: &mut AppData) -> Result<AppMode> {
         self.try_switch_branch(app_data).or_else(|err| {
             Self::clear_switch_branch_state(app_data);
-            Ok(ErrorModalMode {
-                message: format!("Switch branch failed: {err:#}"),
-            }
-            .into())
+            Ok(ErrorModalMode::new(TenexError::wrap("Switch branch failed", &err)).into())
         })
     }
 
@@ -656,14 +989,15 @@ impl Actions {
             .clone()
             .unwrap_or_else(|| root.worktree_path.clone());
 
-        let Some(target) =
-            Self::prepare_branch_switch_target(&app_data.config, &repo_root, &target_raw, runtime)?
+        let Some(target) = Self::prepare_branch_switch_target(
+            &app_data.repo_config(&repo_root),
+            &repo_root,
+            &target_raw,
+            runtime,
+        )?
         else {
             Self::clear_switch_branch_state(app_data);
-            return Ok(ErrorModalMode {
-                message: format!("Branch not found: {target_raw}"),
-            }
-            .into());
+            return Ok(ErrorModalMode::new(format!("Branch not found: {target_raw}")).into());
         };
 
         let title = target
@@ -675,42 +1009,34 @@ impl Actions {
 
         if !target.worktree_path.exists() {
             Self::clear_switch_branch_state(app_data);
-            return Ok(ErrorModalMode {
-                message: format!(
-                    "Worktree path does not exist: {}",
-                    target.worktree_path.display()
-                ),
-            }
+            return Ok(ErrorModalMode::new(format!(
+                "Worktree path does not exist: {}",
+                target.worktree_path.display()
+            ))
             .into());
         }
 
-        self.kill_root_agent_tree(app_data, root_id, false)?;
-
-        let new_id = self.spawn_root_agent_in_worktree(
-            app_data,
-            RootLaunchSpec {
-                title,
-                program,
-                runtime,
-                repo_root,
-                branch: target.branch.clone(),
-                worktree_path: target.worktree_path,
-            },
-        );
-        let new_id = new_id?;
-
-        app_data.select_agent_by_id(new_id);
-        Self::clear_switch_branch_state(app_data);
-        app_data.set_status(format!("Switched to branch: {}", target.branch));
-        Ok(AppMode::normal())
+        let spec = RootLaunchSpec {
+            title,
+            program,
+            runtime,
+            repo_root,
+            branch: target.branch.clone(),
+            worktree_path: target.worktree_path,
+        };
+        let switch: BackgroundCompletion = Box::new(move |app_data: &mut AppData| {
+            let new_id = self.spawn_root_agent_in_worktree(app_data, spec)?;
+            app_data.select_agent_by_id(new_id);
+            Self::clear_switch_branch_state(app_data);
+            app_data.set_status(format!("Switched to branch: {}", target.branch));
+            Ok(AppMode::normal())
+        });
+        self.kill_root_agent_tree(app_data, root_id, WorkspaceCleanup::RemoveWorktree, switch)
     }
 
     fn switch_branch_user_error(app_data: &mut AppData, message: &str) -> AppMode {
         Self::clear_switch_branch_state(app_data);
-        ErrorModalMode {
-            message: message.to_string(),
-        }
-        .into()
+        ErrorModalMode::new(message.to_string()).into()
     }
 
     fn clear_switch_branch_state(app_data: &mut AppData) {
@@ -807,19 +1133,75 @@ impl Actions {
         let mut agent = Agent::new(spec.title, spec.program, spec.branch, spec.worktree_path);
         agent.repo_root = Some(spec.repo_root);
         agent.runtime = spec.runtime;
-        self.launch_root_agent(app_data, &mut agent, None)?;
+        let prompt = Self::take_handoff_prompt(&agent.worktree_path, None);
+        self.launch_root_agent(app_data, &mut agent, prompt.as_deref())?;
 
         let new_id = agent.id;
+        let worktree_path = agent.worktree_path.clone();
+        let branch = agent.branch.clone();
         app_data.storage.add(agent);
         app_data.storage.save()?;
+        Self::start_worktree_setup(app_data, new_id, &worktree_path, &branch);
         Ok(new_id)
     }
 
+    /// Stop every agent and forget them, keeping their worktrees and branches, then quit (quit
+    /// with "stop all").
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the agent state cannot be saved.
+    pub(crate) fn stop_all_agents(self, app_data: &mut AppData) -> Result<()> {
+        let root_ids: Vec<Uuid> = app_data
+            .storage
+            .root_agents()
+            .into_iter()
+            .map(|root| root.id)
+            .collect();
+        // Shut every agent down at once so the grace period is only waited out once.
+        let agent_ids: Vec<Uuid> = app_data.storage.iter().map(|agent| agent.id).collect();
+        let stop: BackgroundCompletion = Box::new(move |app_data: &mut AppData| {
+            for root_id in root_ids {
+                self.finish_killing_root_tree(app_data, root_id, WorkspaceCleanup::Keep)?;
+            }
+            app_data.should_quit = true;
+            Ok(AppMode::normal())
+        });
+        self.shut_down_agents(app_data, None, &agent_ids, stop)?;
+        app_data.set_status("Stopping agents before quitting");
+        Ok(())
+    }
+
+    /// Let the programs of root `root_id`'s tree exit, then kill its session, clean up its
+    /// workspace as `cleanup` says and forget its agents, before running `then`.
     fn kill_root_agent_tree(
         self,
         app_data: &mut AppData,
         root_id: Uuid,
-        delete_branch: bool,
+        cleanup: WorkspaceCleanup,
+        then: BackgroundCompletion,
+    ) -> Result<AppMode> {
+        let mut shutdown_ids: Vec<Uuid> = app_data
+            .storage
+            .descendants(root_id)
+            .iter()
+            .map(|desc| desc.id)
+            .collect();
+        shutdown_ids.push(root_id);
+        let finish: BackgroundCompletion = Box::new(move |app_data: &mut AppData| {
+            self.finish_killing_root_tree(app_data, root_id, cleanup)?;
+            then(app_data)
+        });
+        self.shut_down_agents(app_data, Some(root_id), &shutdown_ids, finish)
+    }
+
+    /// Kill the session of root `root_id`'s tree, whose programs were asked to quit, clean up
+    /// its workspace as `cleanup` says and forget its agents.
+    fn finish_killing_root_tree(
+        self,
+        app_data: &mut AppData,
+        root_id: Uuid,
+        cleanup: WorkspaceCleanup,
     ) -> Result<()> {
         let Some(root) = app_data.storage.get(root_id) else {
             return Ok(());
@@ -835,19 +1217,24 @@ impl Actions {
             .list_pane_pids(&session)
             .unwrap_or_default();
 
-        // First kill all descendant windows in descending order.
-        let descendants = app_data.storage.descendants(root_id);
-        let mut indices: Vec<u32> = descendants
+        let indices: Vec<u32> = app_data
+            .storage
+            .descendants(root_id)
             .iter()
             .filter_map(|desc| desc.window_index)
             .collect();
-        indices.sort_unstable_by(|a, b| b.cmp(a));
         for idx in indices {
             let _ = self.session_manager.kill_window(&session, idx);
         }
 
         // Kill the session
         let _ = self.session_manager.kill(&session);
+        if let Some(tmux_session) = &runtime_agent.imported_tmux_session
+            && let Err(err) = crate::tmux::kill_session(tmux_session)
+        {
+            warn!(tmux_session, error = %err, "Failed to kill imported tmux session");
+            app_data.set_status(format!("Warning: {err}"));
+        }
 
         // Ensure any remaining pane processes are terminated before removing the worktree.
         for pid in pane_pids {
@@ -866,9 +1253,14 @@ impl Actions {
             warn!(session = %session, error = %err, "Failed to clean up agent runtime");
         }
 
-        if let Some(repo_path) = repo_root.or_else(|| std::env::current_dir().ok())
+        // Imported tmux sessions run in a directory Tenex does not own.
+        if cleanup != WorkspaceCleanup::Keep
+            && runtime_agent.imported_tmux_session.is_none()
+            && let Some(repo_path) = repo_root.or_else(|| std::env::current_dir().ok())
             && let Ok(repo) = git::open_repository(&repo_path)
         {
+            let delete_branch = cleanup == WorkspaceCleanup::RemoveWorktreeAndBranch;
+            Self::set_aside_killed_root(app_data, &runtime_agent, &repo_path, delete_branch);
             let worktree_mgr = WorktreeManager::new(&repo);
             let result = if delete_branch {
                 worktree_mgr.remove(&worktree_name)
@@ -1,5 +1,6 @@
 //! Broadcast operations: send messages to leaf agents
 
+use crate::error::TenexError;
 use crate::mux::SessionManager;
 use anyhow::Result;
 use tracing::{info, warn};
@@ -19,9 +20,9 @@ impl Actions {
     /// Returns an error if broadcasting fails
     pub fn broadcast_to_leaves(self, app_data: &mut AppData, message: &str) -> Result<AppMode> {
         let Some(agent) = app_data.selected_agent() else {
-            return Ok(ErrorModalMode {
-                message: "No agent selected".to_string(),
-            }
+            return Ok(ErrorModalMode::new(TenexError::NoAgentSelected {
+                action: "broadcast",
+            })
             .into());
         };
 
@@ -70,9 +71,6 @@ impl Actions {
             return Ok(AppMode::normal());
         }
         warn!(%agent_id, "No leaf agents found to broadcast to");
-        Ok(ErrorModalMode {
-            message: "No leaf agents found to broadcast to".to_string(),
-        }
-        .into())
+        Ok(ErrorModalMode::new("No leaf agents found to broadcast to").into())
     }
 }
@@ -0,0 +1,251 @@
+//! Budgets: estimating what agents spend from their transcripts, warning as a budget runs out,
+//! and pausing the agents that reach it until you let them go on (`/budget`).
+
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::Actions;
+use crate::agent::{BudgetState, Status};
+use crate::app::AppData;
+use crate::conversation::{self, TokenTally};
+use crate::state::{AppMode, ConfirmAction, ConfirmingMode};
+
+/// `micros` millionths of a dollar as dollars and cents (`$1.25`).
+pub(super) fn format_usd(micros: u64) -> String {
+    let cents = micros.saturating_add(5_000) / 10_000;
+    format!("${}.{:02}", cents / 100, cents % 100)
+}
+
+/// Whether spend went from below `percent` of `limit` to at least that much.
+fn crossed(before: u64, after: u64, limit: u64, percent: u32) -> bool {
+    let threshold = limit.saturating_mul(u64::from(percent)) / 100;
+    before < threshold && after >= threshold
+}
+
+impl Actions {
+    /// Add what each running agent spent since the last check, estimated from the tokens its
+    /// transcript records, then warn about budgets that are nearly spent and pause the agents
+    /// that reached one.
+    ///
+    /// Call after [`Self::sync_context_usage`], which finds the transcripts.
+    pub fn sync_budgets(self, app_data: &mut AppData) {
+        let total_before = total_cost_micros(app_data);
+        let mut tallies = std::mem::take(&mut app_data.ui.token_tally_by_agent);
+        tallies.retain(|agent_id, _| app_data.ui.transcript_by_agent.contains_key(agent_id));
+        let mut spent: Vec<(Uuid, u64, u64)> = Vec::new();
+        for (agent_id, path) in &app_data.ui.transcript_by_agent {
+            let Some(agent) = app_data.storage.get_mut(*agent_id) else {
+                continue;
+            };
+            let Some(conversation_id) = agent.conversation_id.clone() else {
+                continue;
+            };
+            let Some(prices) = app_data.settings.token_prices_for(&agent.program) else {
+                continue;
+            };
+            let (tally_path, tally) = tallies
+                .entry(*agent_id)
+                .or_insert_with(|| (path.clone(), TokenTally::default()));
+            if tally_path != path {
+                tally_path.clone_from(path);
+                *tally = TokenTally::default();
+            }
+            if !tally.read_more(conversation::detect_agent_cli(&agent.program), path) {
+                continue;
+            }
+
+            let cost = prices.cost_micros(tally.usage());
+            if agent.costs.get(&conversation_id) == Some(&cost) {
+                continue;
+            }
+            let before = agent.cost_micros();
+            agent.costs.insert(conversation_id, cost);
+            spent.push((agent.id, before, agent.cost_micros()));
+        }
+        app_data.ui.token_tally_by_agent = tallies;
+        if spent.is_empty() {
+            return;
+        }
+
+        let mut over_budget = Self::check_agent_budgets(app_data, &spent);
+        over_budget.extend(Self::check_total_budget(app_data, total_before));
+        over_budget.sort_unstable();
+        over_budget.dedup();
+        for agent_id in over_budget {
+            self.pause_over_budget(app_data, agent_id);
+        }
+        if let Err(err) = app_data.storage.save() {
+            warn!(error = %err, "Failed to save agent spend");
+        }
+    }
+
+    /// Warn about agents nearly out of their own budget, and return the ones that ran out.
+    fn check_agent_budgets(app_data: &mut AppData, spent: &[(Uuid, u64, u64)]) -> Vec<Uuid> {
+        let Some(limit) = app_data.settings.agent_budget_micros() else {
+            return Vec::new();
+        };
+        let warn_percent = app_data.settings.budget_warning_percent();
+        let mut over_budget = Vec::new();
+        for &(agent_id, before, after) in spent {
+            let Some(agent) = app_data.storage.get(agent_id) else {
+                continue;
+            };
+            if !agent.budget.is_within() {
+                continue;
+            }
+            if after >= limit {
+                over_budget.push(agent_id);
+                app_data.ui.notify_error(format!(
+                    "{} reached its budget of {} and was paused; run /budget to let it go on",
+                    agent.title,
+                    format_usd(limit)
+                ));
+            } else if warn_percent.is_some_and(|percent| crossed(before, after, limit, percent)) {
+                app_data.ui.notify_warning(format!(
+                    "{} has spent {} of its {} budget",
+                    agent.title,
+                    format_usd(after),
+                    format_usd(limit)
+                ));
+            }
+        }
+        over_budget
+    }
+
+    /// Warn when all agents together nearly spent the `budget_usd` setting, and return every
+    /// running agent once they spent it.
+    fn check_total_budget(app_data: &mut AppData, total_before: u64) -> Vec<Uuid> {
+        let Some(limit) = app_data.settings.budget_micros() else {
+            return Vec::new();
+        };
+        if app_data.ui.budget_override {
+            return Vec::new();
+        }
+        let total = total_cost_micros(app_data);
+        if total < limit {
+            if app_data
+                .settings
+                .budget_warning_percent()
+                .is_some_and(|percent| crossed(total_before, total, limit, percent))
+            {
+                app_data.ui.notify_warning(format!(
+                    "Agents have spent {} of the {} budget",
+                    format_usd(total),
+                    format_usd(limit)
+                ));
+            }
+            return Vec::new();
+        }
+
+        let running: Vec<Uuid> = app_data
+            .storage
+            .iter()
+            .filter(|agent| {
+                agent.status == Status::Running
+                    && !agent.is_terminal_agent()
+                    && !agent.budget.is_paused()
+            })
+            .map(|agent| agent.id)
+            .collect();
+        if !running.is_empty() {
+            app_data.ui.notify_error(format!(
+                "Agents have spent {}, reaching the budget of {}; all agents were paused. Run \
+                 /budget to let them go on",
+                format_usd(total),
+                format_usd(limit)
+            ));
+        }
+        running
+    }
+
+    /// Stop the processes of `agent_id` until the pause is lifted with `/budget`.
+    fn pause_over_budget(self, app_data: &mut AppData, agent_id: Uuid) {
+        let Some(agent) = app_data.storage.get(agent_id) else {
+            return;
+        };
+        let target = app_data.storage.mux_target(agent);
+        if let Err(err) = self.session_manager.freeze_processes(&target, true) {
+            warn!(target, error = %err, "Failed to pause agent over budget");
+            return;
+        }
+        if let Some(agent) = app_data.storage.get_mut(agent_id) {
+            info!(title = %agent.title, "Paused agent over budget");
+            agent.budget = BudgetState::Paused;
+        }
+    }
+
+    /// Show the estimated spend (`/budget`), and ask to confirm letting the agents paused over
+    /// budget go on, if there are any.
+    pub fn open_budget(app_data: &mut AppData) -> AppMode {
+        app_data.input.clear();
+        if app_data
+            .storage
+            .iter()
+            .any(|agent| agent.budget.is_paused())
+        {
+            return ConfirmingMode {
+                action: ConfirmAction::ResumeOverBudget,
+            }
+            .into();
+        }
+        let total = format_usd(total_cost_micros(app_data));
+        let status = app_data.settings.budget_micros().map_or_else(
+            || format!("Estimated spend: {total}"),
+            |limit| format!("Estimated spend: {total} of {}", format_usd(limit)),
+        );
+        app_data.set_status(status);
+        AppMode::normal()
+    }
+
+    /// Let every agent paused over budget go on, past the budget it reached.
+    pub(crate) fn resume_over_budget(self, app_data: &mut AppData) {
+        let agent_limit = app_data.settings.agent_budget_micros();
+        if app_data
+            .settings
+            .budget_micros()
+            .is_some_and(|limit| total_cost_micros(app_data) >= limit)
+        {
+            app_data.ui.budget_override = true;
+        }
+
+        let paused: Vec<Uuid> = app_data
+            .storage
+            .iter()
+            .filter(|agent| agent.budget.is_paused())
+            .map(|agent| agent.id)
+            .collect();
+        let mut resumed = 0_usize;
+        for agent_id in paused {
+            let Some(agent) = app_data.storage.get(agent_id) else {
+                continue;
+            };
+            let target = app_data.storage.mux_target(agent);
+            if let Err(err) = self.session_manager.freeze_processes(&target, false) {
+                warn!(target, error = %err, "Failed to resume agent paused over budget");
+            }
+            let Some(agent) = app_data.storage.get_mut(agent_id) else {
+                continue;
+            };
+            agent.budget = if agent_limit.is_some_and(|limit| agent.cost_micros() >= limit) {
+                BudgetState::Overridden
+            } else {
+                BudgetState::Within
+            };
+            resumed += 1;
+        }
+        if let Err(err) = app_data.storage.save() {
+            warn!(error = %err, "Failed to save resumed agents");
+        }
+        info!(resumed, "Resumed agents paused over budget");
+        app_data.set_status(format!("Resumed {resumed} agent(s) past their budget"));
+    }
+}
+
+/// Estimated spend of every agent together, in millionths of a dollar.
+pub(super) fn total_cost_micros(app_data: &AppData) -> u64 {
+    app_data
+        .storage
+        .iter()
+        .map(crate::agent::Agent::cost_micros)
+        .fold(0, u64::saturating_add)
+}
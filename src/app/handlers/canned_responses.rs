@@ -0,0 +1,48 @@
+//! Canned responses: stored messages sent to agents from a picker (`c`).
+
+use anyhow::Result;
+
+use super::Actions;
+use crate::app::AppData;
+use crate::state::{AppMode, CannedResponsesMode, ErrorModalMode};
+
+impl Actions {
+    /// Open the canned responses picker for the selected agent (`c`).
+    ///
+    /// Returns `None` when no agent is selected.
+    pub fn start_canned_responses(app_data: &mut AppData) -> Option<AppMode> {
+        app_data.selected_agent()?;
+        let responses = app_data.settings.canned_responses();
+        if responses.is_empty() {
+            return Some(
+                ErrorModalMode::new(
+                    "No canned responses. Add some to canned_responses in settings.json.",
+                )
+                .into(),
+            );
+        }
+
+        app_data.canned_responses.start(responses);
+        Some(CannedResponsesMode.into())
+    }
+
+    /// Send canned response `index` to the selected agent, or broadcast it to the agent's leaf
+    /// agents when the picker is set to broadcast.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the response cannot be sent.
+    pub fn send_canned_response(self, app_data: &mut AppData, index: usize) -> Result<AppMode> {
+        let Some(response) = app_data.canned_responses.responses.get(index).cloned() else {
+            return Ok(CannedResponsesMode.into());
+        };
+        let broadcast = app_data.canned_responses.broadcast;
+        app_data.canned_responses.clear();
+
+        if broadcast {
+            self.broadcast_to_leaves(app_data, &response.text)
+        } else {
+            self.send_reply(app_data, &response.text)
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! Flagging changes agents should not make: files outside their scope (`/scope`) and files
+//! matching the `protected_paths` setting.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use super::Actions;
+use crate::agent::Status;
+use crate::app::{AppData, ChangeAlerts};
+use crate::git;
+
+impl Actions {
+    /// Scan the worktree of each running agent and flag changed files outside its scope, and,
+    /// for the agent that owns the worktree, changed files in protected paths. Warns in the
+    /// status bar about files flagged for the first time.
+    ///
+    /// Changes count from where the worktree branched off its root's base branch, so committed
+    /// work is flagged as well as uncommitted edits.
+    pub fn sync_change_alerts(app_data: &mut AppData) {
+        let protected = app_data.settings.protected_paths.clone();
+        let mut changed_by_worktree: HashMap<PathBuf, Option<Vec<PathBuf>>> = HashMap::new();
+        let mut alerts_by_agent: BTreeMap<Uuid, ChangeAlerts> = BTreeMap::new();
+
+        for agent in app_data.storage.iter() {
+            if agent.status != Status::Running
+                || !agent.is_git_workspace()
+                || agent.is_terminal_agent()
+            {
+                continue;
+            }
+            let owns_worktree = agent.parent_id.is_none_or(|parent_id| {
+                app_data
+                    .storage
+                    .get(parent_id)
+                    .is_none_or(|parent| parent.worktree_path != agent.worktree_path)
+            });
+            let checks_protected = owns_worktree && !protected.is_empty();
+            if agent.scope.is_none() && !checks_protected {
+                continue;
+            }
+
+            let changed = changed_by_worktree
+                .entry(agent.worktree_path.clone())
+                .or_insert_with(|| {
+                    let base = app_data
+                        .storage
+                        .root_ancestor(agent.id)
+                        .unwrap_or(agent)
+                        .base_branch
+                        .clone();
+                    git::changed_paths(&agent.worktree_path, base.as_deref())
+                        .inspect_err(|err| {
+                            debug!(agent = %agent.id, error = %err, "Failed to list changed files");
+                        })
+                        .ok()
+                });
+            let Some(changed) = changed else {
+                continue;
+            };
+
+            let alerts = ChangeAlerts {
+                outside_scope: changed
+                    .iter()
+                    .filter(|path| !agent.is_in_scope(path))
+                    .cloned()
+                    .collect(),
+                protected: changed
+                    .iter()
+                    .filter(|path| {
+                        checks_protected
+                            && protected
+                                .iter()
+                                .any(|pattern| git::path_matches_glob(pattern, path))
+                    })
+                    .cloned()
+                    .collect(),
+            };
+            if !alerts.is_empty() {
+                alerts_by_agent.insert(agent.id, alerts);
+            }
+        }
+
+        let mut notices: Vec<String> = Vec::new();
+        for (agent_id, alerts) in &alerts_by_agent {
+            let known = app_data.ui.change_alerts_by_agent.get(agent_id);
+            let new = |paths: &[PathBuf]| -> Vec<PathBuf> {
+                paths
+                    .iter()
+                    .filter(|path| known.is_none_or(|known| !known.contains(path)))
+                    .cloned()
+                    .collect()
+            };
+            let Some(agent) = app_data.storage.get(*agent_id) else {
+                continue;
+            };
+            let outside = new(&alerts.outside_scope);
+            if let Some(first) = outside.first() {
+                let scope = agent.scope.as_deref().unwrap_or_default();
+                warn!(agent = %agent_id, scope, files = outside.len(), "Agent changed files outside its scope");
+                notices.push(format!(
+                    "{} changed {}{} outside its scope {scope}",
+                    agent.title,
+                    first.display(),
+                    more(outside.len())
+                ));
+            }
+            let protected = new(&alerts.protected);
+            if let Some(first) = protected.iter().find(|path| !outside.contains(path)) {
+                warn!(agent = %agent_id, files = protected.len(), "Agent changed protected files");
+                notices.push(format!(
+                    "{} changed protected file {}{}",
+                    agent.title,
+                    first.display(),
+                    more(protected.len())
+                ));
+            }
+        }
+
+        app_data.ui.change_alerts_by_agent = alerts_by_agent;
+        for notice in notices {
+            app_data.ui.notify_warning(notice);
+        }
+    }
+
+    /// Flagged changes of every agent working in the worktree of `agent_id`, without
+    /// duplicates, for the warning shown before merging it.
+    #[must_use]
+    pub fn worktree_change_alerts(app_data: &AppData, agent_id: Uuid) -> Vec<PathBuf> {
+        let Some(worktree) = app_data
+            .storage
+            .get(agent_id)
+            .map(|agent| &agent.worktree_path)
+        else {
+            return Vec::new();
+        };
+        let mut paths: Vec<PathBuf> = app_data
+            .storage
+            .iter()
+            .filter(|agent| &agent.worktree_path == worktree)
+            .filter_map(|agent| app_data.ui.change_alerts_by_agent.get(&agent.id))
+            .flat_map(|alerts| alerts.outside_scope.iter().chain(&alerts.protected))
+            .cloned()
+            .collect();
+        paths.sort();
+        paths.dedup();
+        paths
+    }
+}
+
+/// ` and N more` for a notice naming the first of `count` files.
+fn more(count: usize) -> String {
+    match count {
+        0 | 1 => String::new(),
+        count => format!(" and {} more", count - 1),
+    }
+}
@@ -0,0 +1,262 @@
+//! Compacting: restarting an agent with a fresh context, seeded with a summary it wrote of its
+//! own work.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::Actions;
+use crate::agent::Agent;
+use crate::app::AppData;
+use crate::app::state::BackgroundCompletion;
+use crate::conversation::{self, AgentCli};
+use crate::prompts;
+use crate::state::{AppMode, ConfirmAction, ConfirmingMode};
+
+/// How long to wait for the summary when `handoff_timeout_secs` is not set.
+const COMPACT_SUMMARY_TIMEOUT: Duration = Duration::from_mins(3);
+
+/// Where `agent` writes its summary before a compact.
+fn summary_path(agent: &Agent) -> PathBuf {
+    agent
+        .worktree_path
+        .join(".tenex")
+        .join(format!("compact-{}.md", agent.short_id()))
+}
+
+impl Actions {
+    /// Ask to confirm compacting the selected agent (`C`).
+    ///
+    /// Returns `None` when no agent is selected or the selected agent is a terminal.
+    pub fn start_compact(app_data: &mut AppData) -> Option<AppMode> {
+        if app_data.selected_agent()?.is_terminal_agent() {
+            app_data.set_status("Terminals have no conversation to compact");
+            return None;
+        }
+        Some(
+            ConfirmingMode {
+                action: ConfirmAction::Compact,
+            }
+            .into(),
+        )
+    }
+
+    /// Ask the selected agent to summarize its work, then restart its program fresh in the same
+    /// window with the summary as its opening prompt.
+    ///
+    /// The restart happens in the background once the summary is written. If the agent does
+    /// not write it within `handoff_timeout_secs` (three minutes when unset), it is left
+    /// running as it was.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the summary request cannot be sent to the agent.
+    pub(crate) fn compact_agent(self, app_data: &mut AppData) -> Result<()> {
+        let Some(agent) = app_data.selected_agent() else {
+            return Ok(());
+        };
+        if agent.is_terminal_agent() {
+            return Ok(());
+        }
+        let agent_id = agent.id;
+        self.restart_from_summary(app_data, agent_id, None)
+    }
+
+    /// Ask `agent_id` to summarize its work, then restart it in the background from the
+    /// summary, running `program` instead of its own program when given.
+    pub(super) fn restart_from_summary(
+        self,
+        app_data: &mut AppData,
+        agent_id: Uuid,
+        program: Option<String>,
+    ) -> Result<()> {
+        let Some(agent) = app_data.storage.get(agent_id) else {
+            return Ok(());
+        };
+        let title = agent.title.clone();
+
+        let path = summary_path(agent);
+        // A summary left by an earlier compact would count as written right away.
+        let _ = fs::remove_file(&path);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let relative = path.strip_prefix(&agent.worktree_path).unwrap_or(&path);
+        let request = prompts::build_compact_request(&relative.display().to_string());
+        let target = app_data.storage.mux_target(agent);
+        let requested_at = SystemTime::now();
+        self.session_manager
+            .send_keys_and_submit_for_agent(&target, agent, &request)?;
+
+        let timeout = app_data
+            .settings
+            .handoff_timeout()
+            .unwrap_or(COMPACT_SUMMARY_TIMEOUT);
+        info!(%title, ?timeout, "Waiting for the compact summary");
+        app_data.set_status(program.as_deref().map_or_else(
+            || format!("Asked {title} to summarize its work before compacting"),
+            |program| format!("Asked {title} to summarize its work before switching to {program}"),
+        ));
+        app_data.background_ops.spawn_cancellable(
+            Some(agent_id),
+            format!("Waiting for {title} to summarize its work"),
+            move |cancelled| {
+                let outcome = super::handoff::wait_for_handoffs(
+                    vec![path.clone()],
+                    requested_at,
+                    timeout,
+                    cancelled,
+                );
+                Box::new(move |app_data: &mut AppData| {
+                    if outcome? > 0 {
+                        warn!(%title, "Compact summary not written before the timeout");
+                        app_data.ui.notify_warning(format!(
+                            "{title} did not write its summary in time and was left running"
+                        ));
+                        return Ok(AppMode::normal());
+                    }
+                    Self::new().relaunch_from_summary(
+                        app_data,
+                        agent_id,
+                        &path,
+                        program.as_deref(),
+                    )?;
+                    Ok(AppMode::normal())
+                })
+            },
+        );
+        Ok(())
+    }
+
+    /// Start `agent_id` again with a new conversation that opens with the summary at `path`.
+    fn relaunch_from_summary(
+        self,
+        app_data: &mut AppData,
+        agent_id: Uuid,
+        path: &Path,
+        program: Option<&str>,
+    ) -> Result<()> {
+        let summary = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let Some(mut agent) = app_data.storage.get(agent_id).cloned() else {
+            return Ok(());
+        };
+        let previous_program = agent.program.clone();
+        if let Some(program) = program {
+            agent.program = program.to_string();
+        }
+        // A fresh conversation: Claude gets a new session id up front, and Codex's is detected
+        // once it starts.
+        agent.conversation_id = match conversation::detect_agent_cli(&agent.program) {
+            AgentCli::Claude => Some(Uuid::new_v4().to_string()),
+            AgentCli::Codex | AgentCli::Other => None,
+        };
+        let prompt = prompts::build_compact_resume_prompt(&summary);
+        let path = path.to_path_buf();
+        let report = move |app_data: &mut AppData, relaunched: Result<()>| {
+            relaunched?;
+            if let Err(err) = fs::remove_file(&path) {
+                warn!(path = %path.display(), error = %err, "Failed to remove the compact summary");
+            }
+
+            let Some(agent) = app_data.storage.get(agent_id) else {
+                return Ok(());
+            };
+            let title = agent.title.clone();
+            if agent.program == previous_program {
+                info!(%title, "Agent compacted");
+                app_data.set_status(format!("Compacted {title}: restarted it from its summary"));
+            } else {
+                let program = agent.program.clone();
+                info!(%title, from = %previous_program, to = %program, "Agent program switched");
+                app_data.set_status(format!(
+                    "Switched {title} from {previous_program} to {program}, starting from its summary"
+                ));
+            }
+            Ok(())
+        };
+        self.relaunch_agent(
+            app_data,
+            agent,
+            crate::runtime::AgentLaunch::Spawn {
+                prompt: Some(&prompt),
+            },
+            report,
+        )
+    }
+
+    /// Stop the program of `agent` and run it again in the same window with `launch`, then
+    /// store `agent` in place of the old record and run `then` with the outcome.
+    ///
+    /// A running program is given a few seconds to exit in the background first; one that
+    /// already stopped is relaunched right away.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `then` when it runs right away.
+    pub(super) fn relaunch_agent(
+        self,
+        app_data: &mut AppData,
+        agent: Agent,
+        launch: crate::runtime::AgentLaunch<'_>,
+        then: impl FnOnce(&mut AppData, Result<()>) -> Result<()> + Send + 'static,
+    ) -> Result<()> {
+        let agent_id = agent.id;
+        let prompt = match launch {
+            crate::runtime::AgentLaunch::Spawn { prompt } => Some(prompt.map(str::to_string)),
+            crate::runtime::AgentLaunch::Resume => None,
+        };
+        let stopped = agent.status.is_stopped();
+        let relaunch: BackgroundCompletion = Box::new(move |app_data: &mut AppData| {
+            let launch = prompt
+                .as_ref()
+                .map_or(crate::runtime::AgentLaunch::Resume, |prompt| {
+                    crate::runtime::AgentLaunch::Spawn {
+                        prompt: prompt.as_deref(),
+                    }
+                });
+            let result = self.respawn_agent(app_data, agent, launch);
+            then(app_data, result)?;
+            Ok(AppMode::normal())
+        });
+        if stopped {
+            relaunch(app_data)?;
+        } else {
+            self.shut_down_agents(app_data, Some(agent_id), &[agent_id], relaunch)?;
+        }
+        Ok(())
+    }
+
+    /// Run the program of `agent` again in its window with `launch` and store `agent` in place
+    /// of the old record.
+    fn respawn_agent(
+        self,
+        app_data: &mut AppData,
+        mut agent: Agent,
+        launch: crate::runtime::AgentLaunch<'_>,
+    ) -> Result<()> {
+        let agent_id = agent.id;
+        let target = app_data.storage.mux_target(&agent);
+        let command = crate::runtime::build_agent_command(&agent, launch, &app_data.settings)?;
+        let started_at = SystemTime::now();
+        self.session_manager
+            .respawn_window(&target, Some(&command))?;
+        if matches!(launch, crate::runtime::AgentLaunch::Spawn { .. }) {
+            Self::finish_agent_launch(app_data, &mut agent, started_at);
+        }
+
+        if let Some(stored) = app_data.storage.get_mut(agent_id) {
+            stored.program = agent.program;
+            stored.conversation_id = agent.conversation_id;
+        }
+        app_data.storage.save()?;
+        app_data.ui.context_usage_by_agent.remove(&agent_id);
+        app_data.ui.transcript_by_agent.remove(&agent_id);
+        Ok(())
+    }
+}
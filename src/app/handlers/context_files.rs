@@ -0,0 +1,147 @@
+//! Context files: files the user attaches to a new agent's prompt (`Ctrl+O`), copied into
+//! `.tenex/context/` in the agent's worktree.
+
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use tracing::info;
+
+use super::Actions;
+use crate::app::AppData;
+use crate::prompts;
+use crate::state::{AppMode, ChildPromptMode, ContextFilePickerMode, PromptingMode};
+
+/// Directory, relative to the worktree, that attached files are copied into.
+const CONTEXT_DIR: &str = ".tenex/context";
+
+/// A path in `dir` named after `file_name` that isn't taken yet (`notes.md`, `notes-2.md`, ...).
+fn unique_destination(dir: &Path, file_name: &OsStr) -> PathBuf {
+    let candidate = dir.join(file_name);
+    if !candidate.exists() {
+        return candidate;
+    }
+    let name = Path::new(file_name);
+    let stem = name
+        .file_stem()
+        .map_or_else(|| "file".into(), |stem| stem.to_string_lossy());
+    let extension = name
+        .extension()
+        .map(|extension| format!(".{}", extension.to_string_lossy()))
+        .unwrap_or_default();
+    (2..=u32::MAX)
+        .map(|n| dir.join(format!("{stem}-{n}{extension}")))
+        .find(|path| !path.exists())
+        .unwrap_or(candidate)
+}
+
+impl Actions {
+    /// Open the context file picker from the new-agent or child prompt.
+    ///
+    /// Children pick from the root agent's worktree; new agents from the project root.
+    pub fn start_context_file_picker(app_data: &mut AppData, for_children: bool) -> AppMode {
+        let root = if for_children {
+            app_data
+                .spawn
+                .spawning_under
+                .and_then(|id| app_data.storage.root_ancestor(id))
+                .map(|root| root.worktree_path.clone())
+        } else {
+            None
+        };
+        let root = root
+            .or_else(|| app_data.selected_project_root())
+            .or_else(|| app_data.cwd_project_root.clone())
+            .or_else(|| std::env::current_dir().ok())
+            .unwrap_or_default();
+        app_data.context_picker.start(root, for_children);
+        ContextFilePickerMode.into()
+    }
+
+    /// Attach the highlighted file (or detach it if already attached) and return to the prompt.
+    pub fn toggle_context_file(app_data: &mut AppData) -> AppMode {
+        if let Some(path) = app_data.context_picker.selected_path() {
+            let files = &mut app_data.spawn.context_files;
+            if let Some(index) = files.iter().position(|file| *file == path) {
+                files.remove(index);
+            } else {
+                files.push(path);
+            }
+            let count = files.len();
+            app_data.set_status(format!("{count} context file(s) attached"));
+        }
+        Self::close_context_file_picker(app_data)
+    }
+
+    /// Leave the context file picker, returning to the prompt it was opened from.
+    pub fn close_context_file_picker(app_data: &mut AppData) -> AppMode {
+        let for_children = app_data.context_picker.for_children;
+        app_data.context_picker.clear();
+        if for_children {
+            ChildPromptMode.into()
+        } else {
+            PromptingMode.into()
+        }
+    }
+
+    /// Copy the pending context files into `worktree_path` and reference them in `prompt`.
+    ///
+    /// Returns `prompt` unchanged when no files are attached.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file cannot be copied.
+    pub(super) fn attach_context_files(
+        app_data: &mut AppData,
+        worktree_path: &Path,
+        prompt: Option<&str>,
+    ) -> Result<Option<String>> {
+        let files = std::mem::take(&mut app_data.spawn.context_files);
+        Self::copy_context_files(&files, worktree_path, prompt)
+    }
+
+    /// Copy `files` into `worktree_path` and reference them in `prompt`, for files taken from
+    /// the pending ones before the worktree existed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file cannot be copied.
+    pub(super) fn copy_context_files(
+        files: &[PathBuf],
+        worktree_path: &Path,
+        prompt: Option<&str>,
+    ) -> Result<Option<String>> {
+        if files.is_empty() {
+            return Ok(prompt.map(str::to_string));
+        }
+
+        let context_dir = worktree_path.join(CONTEXT_DIR);
+        fs::create_dir_all(&context_dir)
+            .with_context(|| format!("Failed to create {}", context_dir.display()))?;
+        let mut references = Vec::with_capacity(files.len());
+        for source in files {
+            let file_name = source
+                .file_name()
+                .with_context(|| format!("{} is not a file", source.display()))?;
+            let destination = unique_destination(&context_dir, file_name);
+            fs::copy(source, &destination).with_context(|| {
+                format!(
+                    "Failed to copy {} to {}",
+                    source.display(),
+                    destination.display()
+                )
+            })?;
+            let relative = destination
+                .strip_prefix(worktree_path)
+                .unwrap_or(&destination);
+            references.push(relative.display().to_string());
+        }
+
+        info!(count = references.len(), worktree = %worktree_path.display(), "Attached context files");
+        Ok(Some(prompts::build_context_files_prompt(
+            prompt,
+            &references,
+        )))
+    }
+}
@@ -0,0 +1,74 @@
+//! How full each agent's context window is, read from the transcript its CLI writes.
+
+use std::collections::BTreeMap;
+
+use tracing::warn;
+
+use super::Actions;
+use crate::agent::{AgentRuntime, Status};
+use crate::app::AppData;
+use crate::conversation;
+
+impl Actions {
+    /// Read the latest context usage of each running agent from its conversation transcript,
+    /// and warn in the status bar about agents that just reached the `context_warning_percent`
+    /// setting.
+    ///
+    /// Only Claude and Codex agents on the host are tracked; the transcripts of Docker agents
+    /// live inside their containers.
+    pub fn sync_context_usage(app_data: &mut AppData) {
+        let threshold = app_data.settings.context_warning_percent();
+        let mut usage_by_agent = BTreeMap::new();
+        let mut transcripts = BTreeMap::new();
+        let mut notices: Vec<String> = Vec::new();
+
+        for agent in app_data.storage.iter() {
+            if agent.status != Status::Running || agent.runtime != AgentRuntime::Host {
+                continue;
+            }
+            let Some(conversation_id) = agent.conversation_id.as_deref() else {
+                continue;
+            };
+            // A resumed or restarted agent can be in a new conversation.
+            let known = app_data
+                .ui
+                .transcript_by_agent
+                .get(&agent.id)
+                .filter(|path| {
+                    path.file_stem()
+                        .and_then(|stem| stem.to_str())
+                        .is_some_and(|stem| stem.ends_with(conversation_id))
+                })
+                .cloned();
+            let Some(path) = known.or_else(|| {
+                conversation::transcript_path(&agent.program, &agent.worktree_path, conversation_id)
+            }) else {
+                continue;
+            };
+
+            let cli = conversation::detect_agent_cli(&agent.program);
+            if let Some(usage) = conversation::read_context_usage(cli, &path) {
+                let previous = app_data.ui.context_usage_by_agent.get(&agent.id);
+                if let Some(percent) = threshold
+                    && usage.is_near_limit(percent)
+                    && !previous.is_some_and(|previous| previous.is_near_limit(percent))
+                {
+                    warn!(agent = %agent.id, tokens = usage.tokens, "Agent context is nearly full");
+                    notices.push(format!(
+                        "{} has used {}% of its context window; press C to compact it",
+                        agent.title,
+                        usage.percent().unwrap_or_default()
+                    ));
+                }
+                usage_by_agent.insert(agent.id, usage);
+            }
+            transcripts.insert(agent.id, path);
+        }
+
+        app_data.ui.context_usage_by_agent = usage_by_agent;
+        app_data.ui.transcript_by_agent = transcripts;
+        for notice in notices {
+            app_data.ui.notify_warning(notice);
+        }
+    }
+}
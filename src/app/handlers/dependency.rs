@@ -0,0 +1,205 @@
+//! Dependencies between agents: messages held until another agent finishes (`/depends_on`).
+
+use anyhow::Result;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::Actions;
+use crate::agent::{AgentDependency, DependencyTrigger, Status};
+use crate::app::AppData;
+use crate::app::state::DependencyCandidate;
+use crate::error::TenexError;
+use crate::state::{AppMode, DependencyMessageMode, DependencyPickerMode, ErrorModalMode};
+
+impl Actions {
+    /// Open the dependency picker for the selected agent (`/depends_on`).
+    pub fn start_dependency_picker(app_data: &mut AppData) -> AppMode {
+        app_data.input.clear();
+        let Some(agent) = app_data.selected_agent() else {
+            return AppMode::normal();
+        };
+        if agent.is_terminal_agent() {
+            return ErrorModalMode::new("Terminals cannot wait for other agents").into();
+        }
+        let agent_id = agent.id;
+        let candidates: Vec<DependencyCandidate> = app_data
+            .storage
+            .iter()
+            .filter(|other| other.id != agent_id && !other.is_terminal_agent())
+            .filter(|other| !waits_for(app_data, other.id, agent_id))
+            .map(|other| DependencyCandidate {
+                id: other.id,
+                label: format!("{} ({})", other.title, other.short_id()),
+            })
+            .collect();
+        if candidates.is_empty() {
+            return ErrorModalMode::new("No other agents to wait for").into();
+        }
+
+        app_data.dependency_picker.start(agent_id, candidates);
+        DependencyPickerMode.into()
+    }
+
+    /// Move on to the message held for the waiting agent, starting from a notice about the
+    /// agent chosen in the dependency picker.
+    pub fn choose_dependency(app_data: &mut AppData) -> AppMode {
+        let Some(candidate) = app_data.dependency_picker.selected_candidate() else {
+            return DependencyPickerMode.into();
+        };
+        let upstream = candidate.id;
+        let Some(title) = app_data
+            .storage
+            .get(upstream)
+            .map(|agent| agent.title.clone())
+        else {
+            app_data.dependency_picker.clear();
+            return ErrorModalMode::new(TenexError::AgentMissing).into();
+        };
+
+        app_data.dependency_picker.upstream = Some(upstream);
+        app_data.input.set(AgentDependency::default_message(
+            &title,
+            app_data.dependency_picker.trigger,
+        ));
+        DependencyMessageMode.into()
+    }
+
+    /// Hold `message` for the waiting agent until the chosen agent reaches the chosen trigger.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the agent state cannot be saved.
+    pub fn save_dependency(app_data: &mut AppData, message: &str) -> Result<AppMode> {
+        let picker = &app_data.dependency_picker;
+        let (Some(agent_id), Some(upstream)) = (picker.agent_id, picker.upstream) else {
+            app_data.dependency_picker.clear();
+            return Ok(AppMode::normal());
+        };
+        let trigger = picker.trigger;
+        let upstream_title = app_data
+            .storage
+            .get(upstream)
+            .map(|agent| agent.title.clone())
+            .unwrap_or_default();
+        app_data.dependency_picker.clear();
+        app_data.input.clear();
+
+        let Some(agent) = app_data.storage.get_mut(agent_id) else {
+            return Ok(ErrorModalMode::new(TenexError::AgentMissing).into());
+        };
+        agent.blocked_by = Some(AgentDependency {
+            agent_id: upstream,
+            trigger,
+            message: message.to_string(),
+        });
+        let title = agent.title.clone();
+        app_data.storage.save()?;
+
+        info!(agent = %agent_id, upstream = %upstream, ?trigger, "Agent now waits for another agent");
+        app_data.set_status(format!(
+            "{title} waits until {upstream_title} {}",
+            trigger.label()
+        ));
+        Ok(AppMode::normal())
+    }
+
+    /// Drop the selected agent's held message (`/unblock`).
+    pub fn clear_dependency(app_data: &mut AppData) -> AppMode {
+        app_data.input.clear();
+        let Some(agent_id) = app_data.selected_agent().map(|agent| agent.id) else {
+            return AppMode::normal();
+        };
+        let Some(agent) = app_data.storage.get_mut(agent_id) else {
+            return AppMode::normal();
+        };
+        if agent.blocked_by.take().is_none() {
+            let message = format!("{} is not waiting for another agent", agent.title);
+            return ErrorModalMode::new(message).into();
+        }
+        let title = agent.title.clone();
+        if let Err(err) = app_data.storage.save() {
+            return ErrorModalMode::new(TenexError::wrap("Failed to save agent state", &err))
+                .into();
+        }
+        app_data.set_status(format!("{title} no longer waits for another agent"));
+        AppMode::normal()
+    }
+
+    /// Send the held messages of agents whose dependency is met: the agent they wait for has
+    /// reached its trigger or is gone.
+    ///
+    /// Call after pane activity has been synced, which tells when agents wait for input. Nothing
+    /// is sent while Tenex is paused; the messages go out once it resumes.
+    pub fn release_dependencies(self, app_data: &mut AppData) {
+        if app_data.pause.is_paused() {
+            return;
+        }
+        let ready: Vec<(Uuid, AgentDependency)> = app_data
+            .storage
+            .iter()
+            .filter_map(|agent| Some((agent.id, agent.blocked_by.clone()?)))
+            .filter(|(_, dependency)| {
+                app_data
+                    .storage
+                    .get(dependency.agent_id)
+                    .is_none_or(|upstream| {
+                        dependency.trigger == DependencyTrigger::Waiting
+                            && upstream.status == Status::Running
+                            && (app_data.ui.agent_is_waiting_for_input(upstream.id)
+                                || app_data.ui.agent_has_finished(upstream.id))
+                    })
+            })
+            .collect();
+        if ready.is_empty() {
+            return;
+        }
+
+        for (agent_id, dependency) in ready {
+            let Some(agent) = app_data.storage.get(agent_id) else {
+                continue;
+            };
+            if agent.status != Status::Running || agent.budget.is_paused() {
+                continue;
+            }
+            let target = app_data.storage.mux_target(agent);
+            if let Err(err) = self.session_manager.send_keys_and_submit_for_agent(
+                &target,
+                agent,
+                &dependency.message,
+            ) {
+                warn!(agent = %agent_id, error = %err, "Failed to send held message");
+                continue;
+            }
+            let title = agent.title.clone();
+            if let Some(agent) = app_data.storage.get_mut(agent_id) {
+                agent.blocked_by = None;
+            }
+            info!(agent = %agent_id, upstream = %dependency.agent_id, "Released waiting agent");
+            app_data.set_status(format!("Sent {title} its held message"));
+        }
+        if let Err(err) = app_data.storage.save() {
+            warn!(error = %err, "Failed to save agent state after releasing agents");
+        }
+    }
+}
+
+/// Whether agent `id` waits, directly or through other agents, for `upstream`.
+fn waits_for(app_data: &AppData, id: Uuid, upstream: Uuid) -> bool {
+    let mut current = id;
+    // Each agent waits for at most one other, so the chain ends within the agent count.
+    for _ in 0..app_data.storage.len() {
+        let Some(next) = app_data
+            .storage
+            .get(current)
+            .and_then(|agent| agent.blocked_by.as_ref())
+            .map(|dependency| dependency.agent_id)
+        else {
+            return false;
+        };
+        if next == upstream {
+            return true;
+        }
+        current = next;
+    }
+    false
+}
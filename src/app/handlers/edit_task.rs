@@ -0,0 +1,97 @@
+//! Edit task flow: revise an agent's task and send the revision to it.
+
+use anyhow::Result;
+use tracing::info;
+
+use super::Actions;
+use crate::app::AppData;
+use crate::error::TenexError;
+use crate::mux::SessionManager;
+use crate::prompts;
+use crate::state::{AppMode, EditTaskMode, ErrorModalMode};
+
+/// The part of a stored prompt the user edits: the task, without a planning preamble or
+/// scratchpad note.
+fn editable_task(prompt: &str) -> String {
+    let prompt = prompt.trim_end();
+    let prompt = prompt
+        .strip_suffix(prompts::SCRATCHPAD_NOTE)
+        .unwrap_or(prompt);
+    prompt
+        .strip_prefix(prompts::PLAN_PREAMBLE)
+        .unwrap_or(prompt)
+        .trim()
+        .to_string()
+}
+
+/// The prompt to store after revising `previous` to `task`, keeping a planning preamble and
+/// scratchpad note.
+fn revised_prompt(previous: Option<&str>, task: &str) -> String {
+    let prompt = if previous.is_some_and(|prompt| prompt.starts_with(prompts::PLAN_PREAMBLE)) {
+        prompts::build_plan_prompt(task)
+    } else {
+        task.trim().to_string()
+    };
+    if previous.is_some_and(|prompt| prompt.trim_end().ends_with(prompts::SCRATCHPAD_NOTE)) {
+        prompts::build_scratchpad_prompt(&prompt)
+    } else {
+        prompt
+    }
+}
+
+impl Actions {
+    /// Open the task editor for the selected agent (`e`), prefilled with its stored task.
+    ///
+    /// Returns `None` when no agent is selected.
+    pub fn start_edit_task(app_data: &mut AppData) -> Option<AppMode> {
+        let agent = app_data.selected_agent()?;
+        if agent.is_terminal_agent() {
+            return Some(ErrorModalMode::new("Terminals have no task to edit").into());
+        }
+
+        let task = agent
+            .prompt
+            .as_deref()
+            .map(editable_task)
+            .unwrap_or_default();
+        app_data.input.set(task);
+        Some(EditTaskMode.into())
+    }
+
+    /// Send `task` to the selected agent as its revised task and store it as the agent's prompt.
+    ///
+    /// The configured revised-task preamble is sent ahead of the task.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the message cannot be sent or storage cannot be saved.
+    pub fn send_revised_task(self, app_data: &mut AppData, task: &str) -> Result<AppMode> {
+        let Some(agent) = app_data.selected_agent() else {
+            return Ok(ErrorModalMode::new(TenexError::NoAgentSelected {
+                action: "edit its task",
+            })
+            .into());
+        };
+
+        let message =
+            prompts::build_revised_task_prompt(app_data.settings.revised_task_preamble(), task);
+        let target = agent.window_index.map_or_else(
+            || agent.mux_session.clone(),
+            |window_idx| SessionManager::window_target(&agent.mux_session, window_idx),
+        );
+        self.session_manager
+            .send_keys_and_submit_for_agent(&target, agent, &message)?;
+
+        let agent_id = agent.id;
+        let title = agent.title.clone();
+        let prompt = revised_prompt(agent.prompt.as_deref(), task);
+        if let Some(agent) = app_data.storage.get_mut(agent_id) {
+            agent.prompt = Some(prompt);
+        }
+        app_data.storage.save()?;
+
+        info!(%agent_id, task_len = task.len(), "Sent revised task");
+        app_data.set_status(format!("Sent revised task to {title}"));
+        Ok(AppMode::normal())
+    }
+}
@@ -0,0 +1,62 @@
+//! Authentication failures of pushes and fetches: targeted guidance and a retry.
+
+use std::path::Path;
+
+use anyhow::Result;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::app::AppData;
+use crate::git::{self, AuthFailure};
+use crate::state::{AppMode, AuthRetry, GitAuthFailedMode};
+
+use super::super::Actions;
+
+impl Actions {
+    /// Guidance for a push whose `stderr` shows it could not authenticate, keeping what is
+    /// needed to retry it. `None` when the push failed for another reason.
+    pub(super) fn push_auth_failed(
+        app_data: &mut AppData,
+        agent_id: Uuid,
+        worktree_path: &Path,
+        branch_name: &str,
+        base_branch: Option<String>,
+        stderr: &str,
+    ) -> Option<AppMode> {
+        let failure = AuthFailure::detect(stderr)?;
+        let remote = super::push::push_remote(worktree_path, branch_name);
+        let url = git::remote_url(worktree_path, &remote);
+        warn!(branch = %branch_name, ?failure, "Push could not authenticate");
+
+        let retry = if base_branch.is_some() {
+            AuthRetry::PushForPr
+        } else {
+            AuthRetry::Push
+        };
+        app_data.git_op.agent_id = Some(agent_id);
+        branch_name.clone_into(&mut app_data.git_op.branch_name);
+        app_data.git_op.base_branch = base_branch.unwrap_or_default();
+        // The pre-push commands already passed for these commits.
+        app_data.git_op.skip_pre_push = true;
+        Some(
+            GitAuthFailedMode {
+                message: git::auth_guidance("Push", failure, &remote, url.as_deref(), stderr),
+                retry,
+            }
+            .into(),
+        )
+    }
+
+    /// Run the operation that failed to authenticate again (`r` in the guidance modal).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the push cannot be started.
+    pub fn retry_after_auth_failure(app_data: &mut AppData, retry: AuthRetry) -> Result<AppMode> {
+        match retry {
+            AuthRetry::Push => Self::execute_push(app_data),
+            AuthRetry::PushForPr => Self::execute_push_and_open_pr(app_data),
+            AuthRetry::Fetch => Ok(Self::fetch_remotes(app_data, true)),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Messages for commits Tenex makes in an agent's worktree, shaped by the repository's
+//! `tenex.commitTemplate`.
+//!
+//! The template may use `{message}` (Tenex's own summary), `{title}` (the agent title),
+//! `{ticket}` (the agent's ticket ID) and `{co_author}` (a `Co-Authored-By` trailer for the
+//! agent's AI tool). Lines left empty by missing values are dropped.
+
+use std::path::Path;
+
+use crate::agent::Agent;
+use crate::conversation::{AgentCli, detect_agent_cli};
+use crate::git;
+
+/// Git config key holding the commit message template.
+const TEMPLATE_KEY: &str = "tenex.commitTemplate";
+
+/// Git config key naming the co-author (`Name <email>`) for `{co_author}`.
+const CO_AUTHOR_KEY: &str = "tenex.coAuthor";
+
+/// Co-author used for Claude agents when `tenex.coAuthor` is unset.
+const CLAUDE_CO_AUTHOR: &str = "Claude <noreply@anthropic.com>";
+
+const MESSAGE_PLACEHOLDER: &str = "{message}";
+const TITLE_PLACEHOLDER: &str = "{title}";
+const TICKET_PLACEHOLDER: &str = "{ticket}";
+const CO_AUTHOR_PLACEHOLDER: &str = "{co_author}";
+
+/// Agent details available to commit templates, captured on the UI thread.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommitMetadata {
+    /// Agent title.
+    pub title: String,
+    /// Ticket ID, if the agent has one.
+    pub ticket: Option<String>,
+    /// Program the agent runs (used to pick the co-author).
+    pub program: String,
+}
+
+impl CommitMetadata {
+    /// Metadata for commits in `agent`'s worktree (pass the root of its tree).
+    pub fn for_agent(agent: &Agent) -> Self {
+        Self {
+            title: agent.title.clone(),
+            ticket: agent.ticket.clone(),
+            program: agent.program.clone(),
+        }
+    }
+
+    fn co_author_trailer(&self, workdir: &Path) -> String {
+        let co_author = git::config_string(workdir, CO_AUTHOR_KEY).or_else(|| {
+            (detect_agent_cli(&self.program) == AgentCli::Claude)
+                .then(|| CLAUDE_CO_AUTHOR.to_string())
+        });
+        co_author.map_or_else(String::new, |co_author| {
+            format!("Co-Authored-By: {co_author}")
+        })
+    }
+
+    /// Commit message for `summary` in `workdir`: the summary alone when the repository has no
+    /// template, otherwise the filled-in template.
+    pub fn message(&self, workdir: &Path, summary: &str) -> String {
+        let Some(template) = git::config_string(workdir, TEMPLATE_KEY) else {
+            return summary.to_string();
+        };
+        let filled = template
+            .replace(MESSAGE_PLACEHOLDER, summary)
+            .replace(TITLE_PLACEHOLDER, &self.title)
+            .replace(
+                TICKET_PLACEHOLDER,
+                self.ticket.as_deref().unwrap_or_default(),
+            )
+            .replace(CO_AUTHOR_PLACEHOLDER, &self.co_author_trailer(workdir));
+        let message = tidy(&filled);
+        if message.is_empty() {
+            summary.to_string()
+        } else {
+            message
+        }
+    }
+}
+
+/// Trim each line and collapse the blank runs left behind by empty placeholders.
+fn tidy(message: &str) -> String {
+    let mut lines: Vec<&str> = Vec::new();
+    for line in message.lines().map(str::trim) {
+        if line.is_empty() && lines.last().is_none_or(|last| last.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    lines.join("\n").trim().to_string()
+}
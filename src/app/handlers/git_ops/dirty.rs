@@ -0,0 +1,161 @@
+//! Uncommitted changes in a worktree around rebases and merges.
+
+use std::path::Path;
+use std::process::Output;
+
+use anyhow::{Result, bail};
+use tracing::{info, warn};
+
+use crate::app::{AppData, DirtyWorktreeStrategy};
+use crate::git;
+
+use super::super::Actions;
+use super::commit_message::CommitMetadata;
+
+/// What happened to uncommitted changes that were set aside for an operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangesNote {
+    /// Message for the status bar.
+    pub message: String,
+    /// The changes were not put back automatically and the user has to act.
+    pub needs_attention: bool,
+}
+
+impl ChangesNote {
+    /// A note that needs no action from the user.
+    pub fn info(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            needs_attention: false,
+        }
+    }
+
+    /// A note the user has to act on.
+    pub fn attention(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            needs_attention: true,
+        }
+    }
+}
+
+/// Abbreviated commit id for messages.
+pub fn short_id(id: &str) -> &str {
+    id.get(..8).unwrap_or(id)
+}
+
+/// Run `operation` (a rebase or merge named `name`) in `workdir` with uncommitted changes set
+/// aside according to `strategy`.
+///
+/// A work-in-progress commit takes its message from the repository's commit template, filled
+/// in with `metadata`.
+/// Stashed changes are re-applied once the operation has finished or been aborted. If it
+/// stopped on a conflict (`is_conflict` matches git's output) they stay stashed until the user
+/// has resolved it.
+pub fn with_changes_set_aside(
+    workdir: &Path,
+    strategy: DirtyWorktreeStrategy,
+    metadata: &CommitMetadata,
+    name: &str,
+    is_conflict: fn(&str) -> bool,
+    operation: impl FnOnce() -> Result<Output>,
+) -> Result<(Output, Option<ChangesNote>)> {
+    match strategy {
+        DirtyWorktreeStrategy::Refuse => {
+            if git::has_uncommitted_changes(workdir)? {
+                bail!(
+                    "{} has uncommitted changes. Commit or stash them before the {name}, or set \
+                     dirty_worktree_strategy to \"stash\" or \"commit\".",
+                    workdir.display()
+                );
+            }
+            Ok((operation()?, None))
+        }
+        DirtyWorktreeStrategy::Commit => {
+            let message =
+                metadata.message(workdir, &format!("WIP: uncommitted changes before {name}"));
+            let commit = git::commit_wip(workdir, &message)?;
+            let output = operation()?;
+            let note = commit.map(|id| {
+                info!(commit = %id, "Committed uncommitted changes before {name}");
+                ChangesNote::info(format!(
+                    "Uncommitted changes were committed as WIP commit {} before the {name}",
+                    short_id(&id)
+                ))
+            });
+            Ok((output, note))
+        }
+        DirtyWorktreeStrategy::Stash => {
+            let Some(stash_id) = git::stash_changes(
+                workdir,
+                &format!("tenex: uncommitted changes before {name}"),
+            )?
+            else {
+                return Ok((operation()?, None));
+            };
+            info!(stash = %stash_id, "Stashed uncommitted changes before {name}");
+
+            let output = match operation() {
+                Ok(output) => output,
+                Err(err) => {
+                    // The operation was aborted (or never started); put the changes back.
+                    if !git::restore_stash(workdir, &stash_id).unwrap_or(false) {
+                        warn!(stash = %stash_id, "Could not re-apply stashed changes");
+                        return Err(err.context(format!(
+                            "uncommitted changes are kept in stash {}",
+                            short_id(&stash_id)
+                        )));
+                    }
+                    return Err(err);
+                }
+            };
+
+            let combined = format!(
+                "{}{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            );
+            if !output.status.success() && is_conflict(&combined) {
+                let note = ChangesNote::attention(format!(
+                    "Uncommitted changes are saved in stash {}. Run `git stash pop` after resolving the {name} conflict.",
+                    short_id(&stash_id)
+                ));
+                return Ok((output, Some(note)));
+            }
+
+            let note = restore_note(workdir, &stash_id, name)?;
+            Ok((output, Some(note)))
+        }
+    }
+}
+
+/// Re-apply the stash `stash_id` after the operation `name`, describing how it went.
+///
+/// # Errors
+///
+/// Returns an error if git cannot be run.
+pub fn restore_note(workdir: &Path, stash_id: &str, name: &str) -> Result<ChangesNote> {
+    if git::restore_stash(workdir, stash_id)? {
+        return Ok(ChangesNote::info(
+            "Uncommitted changes were stashed and restored",
+        ));
+    }
+    warn!(stash = %stash_id, "Stashed changes conflicted when re-applied");
+    Ok(ChangesNote::attention(format!(
+        "Couldn't cleanly re-apply uncommitted changes after the {name}. They are kept in stash \
+         {id}; resolve any conflict markers, then run `git stash drop` (or `git stash apply {id}` \
+         to retry).",
+        id = short_id(stash_id)
+    )))
+}
+
+impl Actions {
+    /// Tell the user what happened to uncommitted changes around a rebase or merge.
+    pub(super) fn report_changes_note(app_data: &mut AppData, note: Option<ChangesNote>) {
+        match note {
+            Some(note) if note.needs_attention => app_data.notify_warning(note.message),
+            Some(note) => app_data.set_status(note.message),
+            None => {}
+        }
+    }
+}
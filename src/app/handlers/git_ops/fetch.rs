@@ -0,0 +1,244 @@
+//! Fetch flow: `git fetch --prune` plus ahead/behind counts against each root's base branch.
+//!
+//! The counts can also be refreshed without fetching (for example, after a local merge moved
+//! a base branch).
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::app::AppData;
+use crate::git::{self, AuthFailure, BranchDivergence};
+use crate::state::{AppMode, AuthRetry, GitAuthFailedMode};
+
+use super::super::Actions;
+
+/// A root agent whose branch is compared against its base after fetching.
+struct FetchTarget {
+    agent_id: Uuid,
+    worktree_path: PathBuf,
+    branch: String,
+    base_branch: Option<String>,
+}
+
+/// Results gathered on the worker thread.
+struct FetchReport {
+    repo_count: usize,
+    fetch_errors: Vec<String>,
+    /// Guidance for the first fetch that could not authenticate.
+    auth_failure: Option<String>,
+    divergence: Vec<(Uuid, BranchDivergence)>,
+}
+
+fn run_fetch(
+    repos: &BTreeMap<PathBuf, Vec<FetchTarget>>,
+    fetch: bool,
+    cancelled: &AtomicBool,
+) -> anyhow::Result<FetchReport> {
+    let mut report = FetchReport {
+        repo_count: repos.len(),
+        fetch_errors: Vec::new(),
+        auth_failure: None,
+        divergence: Vec::new(),
+    };
+
+    for (repo_path, targets) in repos {
+        if fetch {
+            match git::fetch_prune(repo_path, cancelled) {
+                // New worktrees in a partial clone branch from its HEAD; keep it current.
+                Ok(()) if git::is_partial_clone(repo_path) => {
+                    if let Err(err) = git::fast_forward_head(repo_path) {
+                        warn!(repo = %repo_path.display(), error = %err, "Fast-forward failed");
+                    }
+                }
+                Ok(()) => {}
+                Err(err) if cancelled.load(Ordering::Relaxed) => return Err(err),
+                Err(err) => {
+                    warn!(repo = %repo_path.display(), error = %err, "Fetch failed");
+                    let error = format!("{err:#}");
+                    match AuthFailure::detect(&error) {
+                        Some(failure) if report.auth_failure.is_none() => {
+                            let url = git::remote_url(repo_path, "origin");
+                            report.auth_failure = Some(git::auth_guidance(
+                                "Fetch",
+                                failure,
+                                "origin",
+                                url.as_deref(),
+                                &error,
+                            ));
+                        }
+                        _ => report.fetch_errors.push(error),
+                    }
+                }
+            }
+        }
+
+        for target in targets {
+            let base_branch = target.base_branch.clone().unwrap_or_else(|| {
+                Actions::detect_base_branch(&target.worktree_path, &target.branch)
+            });
+            match git::divergence(&target.worktree_path, &target.branch, &base_branch) {
+                Ok(divergence) => report.divergence.push((target.agent_id, divergence)),
+                Err(err) => debug!(branch = %target.branch, error = %err, "Skipping divergence"),
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+impl Actions {
+    /// Fetch (with prune) every repository that has root agents, then refresh each root's
+    /// ahead/behind counts against its base branch.
+    ///
+    /// Runs in the background. `manual` controls whether "nothing to do" is reported.
+    pub fn fetch_remotes(app_data: &mut AppData, manual: bool) -> AppMode {
+        if app_data.background_ops.has_repo_wide_op() {
+            if manual {
+                app_data.set_status("A fetch is already running");
+            }
+            return AppMode::normal();
+        }
+
+        let repos = Self::fetch_targets(app_data);
+        if repos.is_empty() {
+            if manual {
+                app_data.set_status("No git agents to fetch for");
+            }
+            return AppMode::normal();
+        }
+
+        let label = if repos.len() == 1 {
+            "Fetching remote".to_string()
+        } else {
+            format!("Fetching {} repositories", repos.len())
+        };
+        app_data
+            .background_ops
+            .spawn_cancellable(None, label, move |cancelled| {
+                let report = run_fetch(&repos, true, cancelled);
+                Box::new(move |app_data: &mut AppData| {
+                    Ok(Self::finish_fetch(app_data, report?, manual))
+                })
+            });
+
+        AppMode::normal()
+    }
+
+    /// Recompute ahead/behind counts without fetching, so agents notice a base branch that
+    /// moved locally (for example, after merging another agent into it).
+    pub(super) fn refresh_divergence(app_data: &mut AppData) {
+        if app_data.background_ops.has_repo_wide_op() {
+            return;
+        }
+        let repos = Self::fetch_targets(app_data);
+        if repos.is_empty() {
+            return;
+        }
+        app_data.background_ops.spawn_cancellable(
+            None,
+            "Checking base branches",
+            move |cancelled| {
+                let report = run_fetch(&repos, false, cancelled);
+                Box::new(move |app_data: &mut AppData| {
+                    Ok(Self::finish_fetch(app_data, report?, false))
+                })
+            },
+        );
+    }
+
+    /// Git root agents to compare against their base branches, grouped by repository.
+    fn fetch_targets(app_data: &AppData) -> BTreeMap<PathBuf, Vec<FetchTarget>> {
+        let mut repos: BTreeMap<PathBuf, Vec<FetchTarget>> = BTreeMap::new();
+        for agent in app_data.storage.root_agents() {
+            if !agent.is_git_workspace() || agent.is_terminal_agent() {
+                continue;
+            }
+            let repo_path = agent
+                .repo_root
+                .clone()
+                .unwrap_or_else(|| agent.worktree_path.clone());
+            repos.entry(repo_path).or_default().push(FetchTarget {
+                agent_id: agent.id,
+                worktree_path: agent.worktree_path.clone(),
+                branch: agent.branch.clone(),
+                base_branch: agent.base_branch.clone(),
+            });
+        }
+        repos
+    }
+
+    /// Apply a fetch report. A manual fetch that could not authenticate opens its guidance;
+    /// a scheduled one only warns.
+    fn finish_fetch(app_data: &mut AppData, report: FetchReport, manual: bool) -> AppMode {
+        let stale_after = app_data.settings.stale_base_commits();
+        let mut newly_stale = Vec::new();
+        let mut newly_gone = Vec::new();
+        let mut base_moved = Vec::new();
+
+        for (agent_id, divergence) in report.divergence {
+            let previous = app_data.ui.branch_divergence_by_agent.get(&agent_id);
+            let was_stale = previous.is_some_and(|previous| previous.behind >= stale_after);
+            let was_gone = previous.is_some_and(|previous| previous.upstream_gone);
+            if let Some(agent) = app_data.storage.get(agent_id) {
+                if divergence.behind >= stale_after && !was_stale {
+                    newly_stale.push(format!(
+                        "{} is {} commits behind {} (press U to update)",
+                        agent.title, divergence.behind, divergence.base_ref
+                    ));
+                } else if previous.is_some_and(|previous| divergence.behind > previous.behind) {
+                    base_moved.push(format!(
+                        "{} moved: {} is {} commits behind (press U to update)",
+                        divergence.base_ref, agent.title, divergence.behind
+                    ));
+                }
+                if divergence.upstream_gone && !was_gone {
+                    newly_gone.push(format!("{}'s remote branch was deleted", agent.title));
+                }
+            }
+            app_data
+                .ui
+                .branch_divergence_by_agent
+                .insert(agent_id, divergence);
+        }
+
+        app_data
+            .ui
+            .branch_divergence_by_agent
+            .retain(|agent_id, _| app_data.storage.get(*agent_id).is_some());
+
+        for error in report.fetch_errors {
+            app_data.ui.notify_error(error);
+        }
+        for warning in newly_stale.into_iter().chain(base_moved).chain(newly_gone) {
+            app_data.notify_warning(warning);
+        }
+        if let Some(message) = report.auth_failure {
+            if manual {
+                return GitAuthFailedMode {
+                    message,
+                    retry: AuthRetry::Fetch,
+                }
+                .into();
+            }
+            let summary = message.lines().next().unwrap_or_default();
+            app_data
+                .ui
+                .notify_error(format!("{summary} Run /fetch for help."));
+        } else if manual {
+            let repos = report.repo_count;
+            app_data.set_status(format!(
+                "Fetched {repos} {}",
+                if repos == 1 {
+                    "repository"
+                } else {
+                    "repositories"
+                }
+            ));
+        }
+        AppMode::normal()
+    }
+}
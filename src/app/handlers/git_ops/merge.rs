@@ -1,23 +1,41 @@
 //! Merge flow (branch selector + merge execution).
 
 use crate::agent::{Agent, ChildConfig};
+use crate::error::TenexError;
 use crate::git;
 use crate::mux::SessionManager;
 use anyhow::{Context, Result};
 use tracing::{debug, info};
 
-use crate::app::AppData;
+use crate::app::{AppData, DirtyWorktreeStrategy};
 use crate::state::{AppMode, ErrorModalMode, MergeBranchSelectorMode, SuccessModalMode};
 
 use super::super::Actions;
+use super::commit_message::CommitMetadata;
+use super::dirty::{ChangesNote, restore_note, short_id, with_changes_set_aside};
+use uuid::Uuid;
 
 /// Result of a git merge operation
 enum MergeResult {
     Success,
     Conflict,
+    CheckoutFailed,
     Failed(String),
 }
 
+/// Where a merge ran and how it ended.
+enum MergeOutcome {
+    InWorktree {
+        worktree_path: std::path::PathBuf,
+        result: MergeResult,
+        note: Option<ChangesNote>,
+    },
+    InMainRepo {
+        result: MergeResult,
+        note: Option<ChangesNote>,
+    },
+}
+
 pub(super) fn git_failure_message(stdout: &str, stderr: &str) -> String {
     let stderr = stderr.trim();
     if !stderr.is_empty() {
@@ -46,10 +64,7 @@ impl Actions {
     /// Returns an error if the git repository cannot be opened or branches cannot be listed.
     pub fn merge_branch(app_data: &mut AppData) -> Result<AppMode> {
         let Some(agent) = app_data.selected_agent() else {
-            return Ok(ErrorModalMode {
-                message: "No agent selected. Select an agent first to merge.".to_string(),
-            }
-            .into());
+            return Ok(ErrorModalMode::new(TenexError::NoAgentSelected { action: "merge" }).into());
         };
 
         let agent_id = agent.id;
@@ -74,9 +89,8 @@ impl Actions {
 
     /// Execute the merge operation
     ///
-    /// Merges the agent's branch INTO the target branch (e.g., feature -> master).
-    /// If the target branch has a worktree, merges directly there.
-    /// Otherwise, merges from the main repo.
+    /// Merges the agent's branch INTO the target branch (e.g., feature -> master) in the
+    /// background; its result is reported when it finishes.
     ///
     /// # Errors
     ///
@@ -85,19 +99,17 @@ impl Actions {
         let Some(agent_id) = app_data.git_op.agent_id else {
             app_data.git_op.clear();
             app_data.review.clear();
-            return Ok(ErrorModalMode {
-                message: "No agent ID for merge".to_string(),
-            }
-            .into());
+            return Ok(ErrorModalMode::new(TenexError::AgentMissing).into());
         };
 
+        if let Some(busy) = Self::busy_agent_error(app_data, agent_id) {
+            return Ok(busy);
+        }
+
         let Some(agent) = app_data.storage.get(agent_id) else {
             app_data.git_op.clear();
             app_data.review.clear();
-            return Ok(ErrorModalMode {
-                message: "Agent not found".to_string(),
-            }
-            .into());
+            return Ok(ErrorModalMode::new(TenexError::AgentMissing).into());
         };
         let repo_path = agent
             .repo_root
@@ -105,8 +117,19 @@ impl Actions {
             .or_else(|| git::repository_workspace_root(&agent.worktree_path).ok())
             .unwrap_or_else(|| agent.worktree_path.clone());
 
+        let dirty_strategy = app_data.settings.dirty_worktree_strategy;
         let source_branch = app_data.git_op.branch_name.clone(); // Agent's branch (e.g., tenex/feature)
         let target_branch = app_data.git_op.target_branch.clone(); // Branch to merge into (e.g., master)
+        // A WIP commit on the target belongs to the agent working on it, if any.
+        let metadata = app_data
+            .storage
+            .root_agents()
+            .into_iter()
+            .find(|root| root.branch == target_branch)
+            .map(CommitMetadata::for_agent)
+            .unwrap_or_default();
+        app_data.git_op.clear();
+        app_data.review.clear();
 
         debug!(
             source = %source_branch,
@@ -114,19 +137,120 @@ impl Actions {
             "Executing merge: {source_branch} -> {target_branch}"
         );
 
+        let label = format!("Merging {source_branch} into {target_branch}");
+        app_data
+            .background_ops
+            .spawn(Some(agent_id), label, move || {
+                let outcome = Self::run_merge(
+                    &repo_path,
+                    &source_branch,
+                    &target_branch,
+                    dirty_strategy,
+                    &metadata,
+                );
+                Box::new(move |app_data: &mut AppData| {
+                    Self::finish_merge(app_data, agent_id, &source_branch, &target_branch, outcome?)
+                })
+            });
+
+        Ok(AppMode::normal())
+    }
+
+    /// Run the merge (off the UI thread).
+    ///
+    /// If the target branch has a worktree, merges directly there. Otherwise, merges from the
+    /// main repo.
+    fn run_merge(
+        repo_path: &std::path::Path,
+        source_branch: &str,
+        target_branch: &str,
+        dirty_strategy: DirtyWorktreeStrategy,
+        metadata: &CommitMetadata,
+    ) -> Result<MergeOutcome> {
         // Check if target branch has a worktree
-        if let Some(worktree_path) = Self::find_worktree_for_branch(&repo_path, &target_branch)? {
-            Self::execute_merge_in_worktree(
-                app_data,
-                &source_branch,
-                &target_branch,
+        if let Some(worktree_path) = Self::find_worktree_for_branch(repo_path, target_branch)? {
+            let (result, note) = Self::merge_in_worktree(
+                source_branch,
+                target_branch,
                 &worktree_path,
-            )
+                dirty_strategy,
+                metadata,
+            )?;
+            Ok(MergeOutcome::InWorktree {
+                worktree_path,
+                result,
+                note,
+            })
         } else {
-            Self::execute_merge_in_main_repo(app_data, &repo_path, &source_branch, &target_branch)
+            Self::merge_in_main_repo(repo_path, source_branch, target_branch)
+                .map(|(result, note)| MergeOutcome::InMainRepo { result, note })
         }
     }
 
+    /// Report a finished merge (on the UI thread).
+    fn finish_merge(
+        app_data: &mut AppData,
+        agent_id: Uuid,
+        source_branch: &str,
+        target_branch: &str,
+        mut outcome: MergeOutcome,
+    ) -> Result<AppMode> {
+        let (MergeOutcome::InWorktree { note, .. } | MergeOutcome::InMainRepo { note, .. }) =
+            &mut outcome;
+        Self::report_changes_note(app_data, note.take());
+
+        let result = match outcome {
+            MergeOutcome::InWorktree {
+                worktree_path,
+                result: MergeResult::Conflict,
+                ..
+            } => {
+                info!(source = %source_branch, target = %target_branch, "Merge has conflicts - spawning terminal");
+                return Self::spawn_merge_conflict_terminal_in_worktree(
+                    app_data,
+                    source_branch,
+                    target_branch,
+                    &worktree_path,
+                );
+            }
+            MergeOutcome::InMainRepo {
+                result: MergeResult::Conflict,
+                ..
+            } => {
+                // Stay on target branch, don't restore stash - user needs to resolve.
+                return Self::spawn_conflict_terminal(
+                    app_data,
+                    agent_id,
+                    &format!("Merge Conflict: {source_branch} -> {target_branch}"),
+                    "git status",
+                );
+            }
+            MergeOutcome::InWorktree { result, .. } | MergeOutcome::InMainRepo { result, .. } => {
+                result
+            }
+        };
+
+        Ok(match result {
+            MergeResult::Success | MergeResult::Conflict => {
+                info!(source = %source_branch, target = %target_branch, "Merge successful");
+                // The target is often another agent's base branch.
+                Self::refresh_divergence(app_data);
+                SuccessModalMode {
+                    message: format!("Merged {source_branch} into {target_branch}"),
+                }
+                .into()
+            }
+            MergeResult::CheckoutFailed => {
+                ErrorModalMode::new(format!("Failed to checkout {target_branch}")).into()
+            }
+            MergeResult::Failed(error_msg) => ErrorModalMode::new(TenexError::Git {
+                operation: "Merge",
+                detail: error_msg,
+            })
+            .into(),
+        })
+    }
+
     /// Find the worktree path for a branch, if one exists
     fn find_worktree_for_branch(
         repo_path: &std::path::Path,
@@ -155,65 +279,62 @@ impl Actions {
         Ok(None)
     }
 
-    /// Execute merge directly in a worktree (when target branch is checked out there)
-    fn execute_merge_in_worktree(
-        app_data: &mut AppData,
+    /// Merge directly in a worktree (when target branch is checked out there)
+    ///
+    /// Uncommitted changes in the worktree are set aside first according to `dirty_strategy`.
+    fn merge_in_worktree(
         source_branch: &str,
         target_branch: &str,
         worktree_path: &std::path::Path,
-    ) -> Result<AppMode> {
+        dirty_strategy: DirtyWorktreeStrategy,
+        metadata: &CommitMetadata,
+    ) -> Result<(MergeResult, Option<ChangesNote>)> {
         debug!(source = %source_branch, target = %target_branch, worktree = %worktree_path.display(), "Merging in worktree");
 
         // Merge directly in the worktree
-        let merge_output = crate::git::git_command()
-            .args([
-                "merge",
-                source_branch,
-                "-m",
-                &format!("Merge {source_branch} into {target_branch}"),
-            ])
-            .current_dir(worktree_path)
-            .output()
-            .context("Failed to execute merge")?;
-
-        if !merge_output.status.success() {
-            let stdout = String::from_utf8_lossy(&merge_output.stdout);
-            let stderr = String::from_utf8_lossy(&merge_output.stderr);
-            let combined = format!("{stdout}{stderr}");
+        let (merge_output, note) = with_changes_set_aside(
+            worktree_path,
+            dirty_strategy,
+            metadata,
+            "merge",
+            output_indicates_merge_conflict,
+            || {
+                crate::git::git_command()
+                    .args([
+                        "merge",
+                        source_branch,
+                        "-m",
+                        &format!("Merge {source_branch} into {target_branch}"),
+                    ])
+                    .current_dir(worktree_path)
+                    .output()
+                    .context("Failed to execute merge")
+            },
+        )?;
 
-            // Check if there are merge conflicts (git outputs to stdout)
-            if output_indicates_merge_conflict(&combined) {
-                info!(source = %source_branch, target = %target_branch, "Merge has conflicts - spawning terminal");
+        if merge_output.status.success() {
+            info!(
+                source = %source_branch,
+                target = %target_branch,
+                "Merge successful in worktree"
+            );
+            return Ok((MergeResult::Success, note));
+        }
 
-                return Self::spawn_merge_conflict_terminal_in_worktree(
-                    app_data,
-                    source_branch,
-                    target_branch,
-                    worktree_path,
-                );
-            }
+        let stdout = String::from_utf8_lossy(&merge_output.stdout);
+        let stderr = String::from_utf8_lossy(&merge_output.stderr);
+        let combined = format!("{stdout}{stderr}");
 
-            // Show error with both stdout and stderr for context
-            let error_msg = git_failure_message(&stdout, &stderr);
-            app_data.git_op.clear();
-            app_data.review.clear();
-            return Ok(ErrorModalMode {
-                message: format!("Merge failed: {error_msg}"),
-            }
-            .into());
+        // Check if there are merge conflicts (git outputs to stdout)
+        if output_indicates_merge_conflict(&combined) {
+            return Ok((MergeResult::Conflict, note));
         }
 
-        info!(
-            source = %source_branch,
-            target = %target_branch,
-            "Merge successful in worktree"
-        );
-        app_data.git_op.clear();
-        app_data.review.clear();
-        Ok(SuccessModalMode {
-            message: format!("Merged {source_branch} into {target_branch}"),
-        }
-        .into())
+        // Show error with both stdout and stderr for context
+        Ok((
+            MergeResult::Failed(git_failure_message(&stdout, &stderr)),
+            note,
+        ))
     }
 
     /// Spawn a terminal for merge conflict resolution in a worktree
@@ -323,80 +444,44 @@ impl Actions {
         Ok(AppMode::normal())
     }
 
-    /// Execute merge from main repo (when target branch has no worktree)
-    fn execute_merge_in_main_repo(
-        app_data: &mut AppData,
+    /// Merge from main repo (when target branch has no worktree)
+    fn merge_in_main_repo(
         repo_path: &std::path::Path,
         source_branch: &str,
         target_branch: &str,
-    ) -> Result<AppMode> {
+    ) -> Result<(MergeResult, Option<ChangesNote>)> {
         debug!(source = %source_branch, target = %target_branch, "Merging in main repo");
 
         // Prepare: stash changes and get current branch
-        let did_stash = Self::git_stash_push(repo_path)?;
+        let stash = git::stash_changes(repo_path, "tenex-merge-temp")?;
         let original_branch = Self::git_get_current_branch(repo_path)?;
 
         // Checkout target branch
         if !Self::git_checkout(repo_path, target_branch)? {
-            Self::restore_git_state(repo_path, did_stash);
-            app_data.git_op.clear();
-            app_data.review.clear();
-            return Ok(ErrorModalMode {
-                message: format!("Failed to checkout {target_branch}"),
-            }
-            .into());
+            let note = Self::restore_git_state(repo_path, stash.as_deref())?;
+            return Ok((MergeResult::CheckoutFailed, note));
         }
 
         // Attempt merge
-        let merge_result = Self::git_merge(
-            repo_path,
-            source_branch,
-            target_branch,
-            &original_branch,
-            did_stash,
-        );
-
-        let next = match merge_result {
-            MergeResult::Success => {
-                Self::restore_git_state(repo_path, did_stash);
-                info!(source = %source_branch, target = %target_branch, "Merge successful");
-                SuccessModalMode {
-                    message: format!("Merged {source_branch} into {target_branch}"),
-                }
-                .into()
-            }
-            MergeResult::Conflict => {
-                // Stay on target branch, don't restore stash - user needs to resolve.
-                return Self::spawn_conflict_terminal(
-                    app_data,
-                    &format!("Merge Conflict: {source_branch} -> {target_branch}"),
-                    "git status",
-                );
-            }
-            MergeResult::Failed(error_msg) => {
+        let merge_result = Self::git_merge(repo_path, source_branch, target_branch);
+
+        let note = match &merge_result {
+            // Stay on target branch, don't restore stash - user needs to resolve.
+            MergeResult::Conflict => stash.as_deref().map(|stash_id| {
+                ChangesNote::attention(format!(
+                    "Uncommitted changes in {} are saved in stash {}. After resolving the merge, \
+                     check out {original_branch} and run `git stash pop`.",
+                    repo_path.display(),
+                    short_id(stash_id)
+                ))
+            }),
+            MergeResult::Success | MergeResult::CheckoutFailed | MergeResult::Failed(_) => {
                 Self::git_checkout(repo_path, &original_branch)?;
-                Self::restore_git_state(repo_path, did_stash);
-                ErrorModalMode {
-                    message: format!("Merge failed: {error_msg}"),
-                }
-                .into()
+                Self::restore_git_state(repo_path, stash.as_deref())?
             }
         };
 
-        app_data.git_op.clear();
-        app_data.review.clear();
-        Ok(next)
-    }
-
-    /// Stash any uncommitted changes
-    fn git_stash_push(repo_path: &std::path::Path) -> Result<bool> {
-        let stash_output = crate::git::git_command()
-            .args(["stash", "push", "-m", "tenex-merge-temp"])
-            .current_dir(repo_path)
-            .output()
-            .context("Failed to stash changes")?;
-
-        Ok(String::from_utf8_lossy(&stash_output.stdout).contains("Saved working"))
+        Ok((merge_result, note))
     }
 
     /// Get current branch name
@@ -421,14 +506,14 @@ impl Actions {
         Ok(output.status.success())
     }
 
-    /// Restore git state (checkout original branch and pop stash)
-    fn restore_git_state(repo_path: &std::path::Path, did_stash: bool) {
-        if did_stash {
-            let _ = crate::git::git_command()
-                .args(["stash", "pop"])
-                .current_dir(repo_path)
-                .output();
-        }
+    /// Restore git state (re-apply the stash, once back on the original branch)
+    fn restore_git_state(
+        repo_path: &std::path::Path,
+        stash_id: Option<&str>,
+    ) -> Result<Option<ChangesNote>> {
+        stash_id
+            .map(|stash_id| restore_note(repo_path, stash_id, "merge"))
+            .transpose()
     }
 
     /// Perform git merge and return result
@@ -436,8 +521,6 @@ impl Actions {
         repo_path: &std::path::Path,
         source_branch: &str,
         target_branch: &str,
-        original_branch: &str,
-        did_stash: bool,
     ) -> MergeResult {
         let merge_output = match crate::git::git_command()
             .args([
@@ -454,11 +537,6 @@ impl Actions {
         };
 
         if merge_output.status.success() {
-            // Go back to original branch after successful merge
-            let _ = crate::git::git_command()
-                .args(["checkout", original_branch])
-                .current_dir(repo_path)
-                .output();
             return MergeResult::Success;
         }
 
@@ -469,20 +547,7 @@ impl Actions {
         if output_indicates_merge_conflict(&combined) {
             MergeResult::Conflict
         } else {
-            // Restore state before returning error
-            let _ = crate::git::git_command()
-                .args(["checkout", original_branch])
-                .current_dir(repo_path)
-                .output();
-            if did_stash {
-                let _ = crate::git::git_command()
-                    .args(["stash", "pop"])
-                    .current_dir(repo_path)
-                    .output();
-            }
-
-            let error_msg = git_failure_message(&stdout, &stderr);
-            MergeResult::Failed(error_msg)
+            MergeResult::Failed(git_failure_message(&stdout, &stderr))
         }
     }
 }
@@ -1,33 +1,51 @@
-//! Git operations: Push, Rename Branch, Open PR, Rebase, Merge
+//! Git operations: Push, Rename Branch, Open PR, Rebase, Merge, Fetch, Update from base
 
+mod auth_failure;
+mod commit_message;
+mod dirty;
+mod fetch;
 mod merge;
 mod open_pr;
+mod partial_clone;
+mod pre_push;
 mod push;
 mod rebase;
 mod rename;
+mod secret_scan;
+mod update_base;
+mod worktree_setup;
 
 use crate::agent::{Agent, ChildConfig};
+use crate::error::TenexError;
 use crate::mux::SessionManager;
 use anyhow::Result;
 use tracing::{debug, info};
 
 use crate::app::AppData;
-use crate::state::AppMode;
+use crate::state::{AppMode, ErrorModalMode};
+use uuid::Uuid;
 
 use super::Actions;
 
 impl Actions {
+    /// Refuse to start a git operation while another one is still running for the agent.
+    fn busy_agent_error(app_data: &mut AppData, agent_id: Uuid) -> Option<AppMode> {
+        if !app_data.background_ops.is_busy(agent_id) {
+            return None;
+        }
+
+        app_data.git_op.clear();
+        app_data.review.clear();
+        Some(ErrorModalMode::new(TenexError::GitBusy).into())
+    }
+
     /// Spawn a terminal for resolving conflicts
     fn spawn_conflict_terminal(
         app_data: &mut AppData,
+        agent_id: Uuid,
         title: &str,
         startup_command: &str,
     ) -> Result<AppMode> {
-        let agent_id = app_data
-            .git_op
-            .agent_id
-            .ok_or_else(|| anyhow::anyhow!("No agent ID"))?;
-
         // Get the root ancestor to use its mux session
         let root = app_data
             .storage
@@ -3,7 +3,10 @@
 use anyhow::{Context, Result, bail};
 use tracing::{debug, info, warn};
 
+use crate::agent::Agent;
 use crate::app::AppData;
+use crate::error::TenexError;
+use crate::git;
 use crate::state::{AppMode, ConfirmPushForPRMode, ErrorModalMode};
 
 use super::super::Actions;
@@ -39,6 +42,25 @@ impl RemoteTrackingRef {
     }
 }
 
+/// PR title and body for a root agent with a ticket: `TICKET: title`, and a body that closes a
+/// GitHub issue or links to the ticket.
+fn pr_ticket_fields(root: &Agent) -> Option<(String, String)> {
+    let ticket = root.ticket.as_deref()?;
+    let title = format!("{ticket}: {}", root.title);
+    let body = if crate::agent::is_github_issue(ticket) {
+        format!("Closes {ticket}")
+    } else {
+        root.repo_root
+            .as_deref()
+            .and_then(|repo_root| git::ticket_url(repo_root, ticket))
+            .map_or_else(
+                || format!("Ticket: {ticket}"),
+                |url| format!("Ticket: [{ticket}]({url})"),
+            )
+    };
+    Some((title, body))
+}
+
 impl Actions {
     /// Open a PR for the selected agent's branch (Ctrl+o)
     ///
@@ -79,10 +101,7 @@ impl Actions {
         }
 
         if let Err(err) = Self::open_pr_in_browser(app_data) {
-            return Ok(ErrorModalMode {
-                message: format!("Failed to open PR: {err:#}"),
-            }
-            .into());
+            return Ok(ErrorModalMode::new(TenexError::wrap("Failed to open PR", &err)).into());
         }
 
         Ok(AppMode::normal())
@@ -229,45 +248,94 @@ impl Actions {
     pub fn execute_push_and_open_pr(app_data: &mut AppData) -> Result<AppMode> {
         let Some(agent_id) = app_data.git_op.agent_id else {
             app_data.git_op.clear();
-            return Ok(ErrorModalMode {
-                message: "No agent ID for push".to_string(),
-            }
-            .into());
+            return Ok(ErrorModalMode::new(TenexError::AgentMissing).into());
         };
 
+        if let Some(busy) = Self::busy_agent_error(app_data, agent_id) {
+            return Ok(busy);
+        }
+
         let Some(agent) = app_data.storage.get(agent_id) else {
             app_data.git_op.clear();
-            return Ok(ErrorModalMode {
-                message: "Agent not found".to_string(),
-            }
-            .into());
+            return Ok(ErrorModalMode::new(TenexError::AgentMissing).into());
         };
 
         let worktree_path = agent.worktree_path.clone();
         let branch_name = app_data.git_op.branch_name.clone();
-
-        debug!(branch = %branch_name, "Executing push before opening PR");
-
-        let push_output = super::push::run_push(&worktree_path, &branch_name)?;
-
-        if !push_output.status.success() {
-            let stderr = String::from_utf8_lossy(&push_output.stderr);
-            app_data.git_op.clear();
-            return Ok(ErrorModalMode {
-                message: format!("Push failed: {}", stderr.trim()),
-            }
-            .into());
+        let base_branch = app_data.git_op.base_branch.clone();
+        let pre_push_commands = Self::pre_push_commands(app_data);
+        app_data.git_op.clear();
+        if let Some(blocked) = super::secret_scan::blocked_by_secrets(&worktree_path, &branch_name)
+        {
+            return Ok(blocked);
         }
 
-        info!(branch = %branch_name, "Push successful, opening PR");
+        debug!(branch = %branch_name, "Executing push before opening PR");
 
-        // Now open the PR
-        if let Err(err) = Self::open_pr_in_browser(app_data) {
-            return Ok(ErrorModalMode {
-                message: format!("Failed to open PR: {err:#}"),
-            }
-            .into());
-        }
+        app_data.background_ops.spawn_with_progress(
+            Some(agent_id),
+            format!("Pushing {branch_name}"),
+            move |cancelled, progress| {
+                match super::pre_push::run_pre_push_commands(
+                    &worktree_path,
+                    &pre_push_commands,
+                    cancelled,
+                ) {
+                    Ok(None) => {}
+                    Ok(Some(report)) => {
+                        return Box::new(move |app_data: &mut AppData| {
+                            Ok(Self::pre_push_failed(
+                                app_data,
+                                agent_id,
+                                branch_name,
+                                Some(base_branch),
+                                report,
+                            ))
+                        });
+                    }
+                    Err(err) => return Box::new(move |_: &mut AppData| Err(err)),
+                }
+                let push_output =
+                    super::push::run_push(&worktree_path, &branch_name, cancelled, progress);
+                Box::new(move |app_data: &mut AppData| {
+                    let push_output = push_output?;
+                    if !push_output.status.success() {
+                        let stderr = String::from_utf8_lossy(&push_output.stderr);
+                        if let Some(mode) = Self::push_auth_failed(
+                            app_data,
+                            agent_id,
+                            &worktree_path,
+                            &branch_name,
+                            Some(base_branch),
+                            &stderr,
+                        ) {
+                            return Ok(mode);
+                        }
+                        return Ok(ErrorModalMode::new(TenexError::Git {
+                            operation: "Push",
+                            detail: stderr.trim().to_string(),
+                        })
+                        .into());
+                    }
+
+                    info!(branch = %branch_name, "Push successful, opening PR");
+
+                    // Now open the PR
+                    app_data.git_op.agent_id = Some(agent_id);
+                    app_data.git_op.branch_name = branch_name;
+                    app_data.git_op.base_branch = base_branch;
+                    if let Err(err) = Self::open_pr_in_browser(app_data) {
+                        return Ok(ErrorModalMode::new(TenexError::wrap(
+                            "Failed to open PR",
+                            &err,
+                        ))
+                        .into());
+                    }
+
+                    Ok(AppMode::normal())
+                })
+            },
+        );
 
         Ok(AppMode::normal())
     }
@@ -287,6 +355,10 @@ impl Actions {
         let worktree_path = agent.worktree_path.clone();
         let branch = app_data.git_op.branch_name.clone();
         let base_branch = app_data.git_op.base_branch.clone();
+        let ticket_fields = app_data
+            .storage
+            .root_ancestor(agent_id)
+            .and_then(pr_ticket_fields);
 
         debug!(
             branch = %branch,
@@ -295,10 +367,13 @@ impl Actions {
         );
 
         // Use gh pr create with --web flag to open in browser
-        let output = std::process::Command::new("gh")
-            .args(["pr", "create", "--web", "--base", &base_branch])
-            .current_dir(&worktree_path)
-            .output();
+        let mut command = std::process::Command::new("gh");
+        command.args(["pr", "create", "--web", "--base", &base_branch]);
+        // Name the PR after the agent's ticket and link back to it
+        if let Some((title, body)) = &ticket_fields {
+            command.args(["--title", title, "--body", body]);
+        }
+        let output = command.current_dir(&worktree_path).output();
 
         match output {
             Ok(result) if result.status.success() => {
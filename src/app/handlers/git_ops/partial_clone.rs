@@ -0,0 +1,58 @@
+//! Partial clones as the source repository for new agent worktrees.
+
+use std::path::PathBuf;
+
+use tracing::info;
+
+use crate::app::AppData;
+use crate::git;
+use crate::state::AppMode;
+
+use super::super::Actions;
+
+impl Actions {
+    /// The repository that new worktrees for `repo_path` should be created from.
+    ///
+    /// Repos that set `tenex.partialClone` get their worktrees from a Tenex-maintained
+    /// blobless clone. Until that clone exists, this starts creating it in the background and
+    /// falls back to `repo_path`.
+    pub(crate) fn worktree_source_repo(app_data: &mut AppData, repo_path: PathBuf) -> PathBuf {
+        if !git::partial_clone_enabled(&repo_path) {
+            return repo_path;
+        }
+
+        let clone_path = app_data.config.partial_clone_path_for_repo_root(&repo_path);
+        if git::is_partial_clone(&clone_path) {
+            return clone_path;
+        }
+
+        let label = format!("Creating partial clone at {}", clone_path.display());
+        let already_cloning = app_data
+            .background_ops
+            .in_flight()
+            .iter()
+            .any(|op| op.label == label);
+        if !already_cloning {
+            app_data.set_status(
+                "Creating a partial clone in the background; this agent uses the full repository",
+            );
+            let source = repo_path.clone();
+            app_data
+                .background_ops
+                .spawn_cancellable(None, label, move |cancelled| {
+                    let result = git::create_partial_clone(&source, &clone_path, cancelled);
+                    Box::new(move |app_data: &mut AppData| {
+                        result?;
+                        info!(clone = %clone_path.display(), "Partial clone ready");
+                        app_data.set_status(format!(
+                            "Partial clone ready at {}; new agents will use it",
+                            clone_path.display()
+                        ));
+                        Ok(AppMode::normal())
+                    })
+                });
+        }
+
+        repo_path
+    }
+}
@@ -0,0 +1,105 @@
+//! Pre-push commands: checks the `pre_push_commands` setting runs in the worktree before a push.
+
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+
+use anyhow::{Context, Result};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::app::AppData;
+use crate::state::{AppMode, PrePushFailedMode};
+
+use super::super::Actions;
+
+/// Lines of a failing command's output kept for the modal.
+const FAILURE_OUTPUT_LINES: usize = 12;
+
+/// Run `commands` in `worktree_path` through the shell, in order, stopping at the first that
+/// fails.
+///
+/// Returns a report of the failing command and the end of its output, or `None` when every
+/// command succeeds.
+pub(super) fn run_pre_push_commands(
+    worktree_path: &Path,
+    commands: &[String],
+    cancelled: &AtomicBool,
+) -> Result<Option<String>> {
+    for command_line in commands {
+        debug!(command = %command_line, "Running pre-push command");
+        let mut command = std::process::Command::new("sh");
+        command
+            .args(["-c", command_line])
+            .current_dir(worktree_path);
+        let output = crate::git::output_cancellable(&mut command, cancelled)
+            .with_context(|| format!("Failed to run pre-push command `{command_line}`"))?;
+        if output.status.success() {
+            continue;
+        }
+
+        warn!(command = %command_line, status = %output.status, "Pre-push command failed");
+        let combined = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let lines: Vec<&str> = combined
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .collect();
+        let tail = &lines[lines.len().saturating_sub(FAILURE_OUTPUT_LINES)..];
+        let status = output.status.code().map_or_else(
+            || "was killed".to_string(),
+            |code| format!("exited with {code}"),
+        );
+        let mut report = format!("`{command_line}` {status}");
+        if !tail.is_empty() {
+            report.push_str(":\n\n");
+            report.push_str(&tail.join("\n"));
+        }
+        return Ok(Some(report));
+    }
+    Ok(None)
+}
+
+impl Actions {
+    /// Commands to run before pushing: none when the user chose to push anyway.
+    pub(super) fn pre_push_commands(app_data: &AppData) -> Vec<String> {
+        if app_data.git_op.skip_pre_push {
+            Vec::new()
+        } else {
+            app_data.settings.pre_push_commands.clone()
+        }
+    }
+
+    /// Show the failure of a pre-push command, keeping what is needed to push anyway.
+    pub(super) fn pre_push_failed(
+        app_data: &mut AppData,
+        agent_id: Uuid,
+        branch_name: String,
+        base_branch: Option<String>,
+        report: String,
+    ) -> AppMode {
+        let for_pr = base_branch.is_some();
+        app_data.git_op.agent_id = Some(agent_id);
+        app_data.git_op.branch_name = branch_name;
+        app_data.git_op.base_branch = base_branch.unwrap_or_default();
+        app_data.git_op.pre_push_failure = report;
+        PrePushFailedMode { for_pr }.into()
+    }
+
+    /// Push the branch whose pre-push command failed, skipping the commands this time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the push cannot be started.
+    pub fn push_anyway(app_data: &mut AppData, state: PrePushFailedMode) -> Result<AppMode> {
+        app_data.git_op.pre_push_failure.clear();
+        app_data.git_op.skip_pre_push = true;
+        if state.for_pr {
+            Self::execute_push_and_open_pr(app_data)
+        } else {
+            Self::execute_push(app_data)
+        }
+    }
+}
@@ -2,9 +2,14 @@
 
 use anyhow::{Context, Result, bail};
 use std::process::Output;
-use tracing::{debug, info};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
 
-use crate::app::AppData;
+use crate::app::{AppData, OpProgress, PromptReply};
+use crate::error::TenexError;
+use crate::git::Askpass;
 use crate::state::{AppMode, ConfirmPushMode, ErrorModalMode};
 
 use super::super::Actions;
@@ -63,6 +68,14 @@ fn git_config_get(worktree_path: &std::path::Path, key: &str) -> Result<Option<S
     Ok(Some(value.to_string()))
 }
 
+/// The remote a push of `branch_name` goes to: its upstream's, or `origin`.
+pub(super) fn push_remote(worktree_path: &std::path::Path, branch_name: &str) -> String {
+    configured_upstream(worktree_path, branch_name)
+        .ok()
+        .flatten()
+        .map_or_else(|| "origin".to_string(), |upstream| upstream.remote)
+}
+
 fn command_args(worktree_path: &std::path::Path, branch_name: &str) -> Result<Vec<String>> {
     Ok(match configured_upstream(worktree_path, branch_name)? {
         Some(upstream) => {
@@ -78,13 +91,54 @@ fn command_args(worktree_path: &std::path::Path, branch_name: &str) -> Result<Ve
     })
 }
 
-pub(super) fn run_push(worktree_path: &std::path::Path, branch_name: &str) -> Result<Output> {
+/// Push the branch, killing `git push` if `cancelled` is set.
+///
+/// Transfer progress is reported on `progress`, and credential prompts from git or ssh are
+/// passed to the user through it.
+///
+/// A cancelled push needs no cleanup: the remote only updates its ref once the push completes.
+pub(super) fn run_push(
+    worktree_path: &std::path::Path,
+    branch_name: &str,
+    cancelled: &AtomicBool,
+    progress: &Arc<OpProgress>,
+) -> Result<Output> {
     let args = command_args(worktree_path, branch_name).context("Failed to push to remote")?;
-    crate::git::git_command()
+    let askpass = Askpass::new().context("Failed to prepare for credential prompts")?;
+    let mut command = crate::git::git_command();
+    command
         .args(args.iter().map(String::as_str))
-        .current_dir(worktree_path)
-        .output()
-        .context("Failed to push to remote")
+        .arg("--progress")
+        .current_dir(worktree_path);
+    askpass.configure(&mut command)?;
+    let reporter = Arc::clone(progress);
+    crate::git::output_watched(
+        &mut command,
+        cancelled,
+        move |update| {
+            if update.contains('%') {
+                reporter.set_detail(update.split_whitespace().collect::<Vec<_>>().join(" "));
+            }
+        },
+        || relay_prompt(&askpass, progress),
+    )
+    .context("Failed to push to remote")
+}
+
+/// Pass a prompt the askpass helper is waiting on to the user, and their reply back.
+fn relay_prompt(askpass: &Askpass, progress: &OpProgress) {
+    if let Some(prompt) = askpass.take_prompt() {
+        debug!(prompt = %prompt, "Push is asking for credentials");
+        progress.ask(prompt);
+    }
+    let sent = match progress.take_reply() {
+        Some(PromptReply::Answer(answer)) => askpass.answer(&answer),
+        Some(PromptReply::Declined) => askpass.decline(),
+        None => Ok(()),
+    };
+    if let Err(err) = sent {
+        warn!(error = %err, "Failed to pass credentials to git");
+    }
 }
 
 impl Actions {
@@ -111,46 +165,104 @@ impl Actions {
 
     /// Execute the git push operation (after user confirms)
     ///
+    /// The push runs in the background; its result is reported when it finishes.
+    ///
     /// # Errors
     ///
     /// Returns an error if the push operation fails
     pub fn execute_push(app_data: &mut AppData) -> Result<AppMode> {
         let Some(agent_id) = app_data.git_op.agent_id else {
             app_data.git_op.clear();
-            return Ok(ErrorModalMode {
-                message: "No agent ID for push".to_string(),
-            }
-            .into());
+            return Ok(ErrorModalMode::new(TenexError::AgentMissing).into());
         };
 
+        if let Some(busy) = Self::busy_agent_error(app_data, agent_id) {
+            return Ok(busy);
+        }
+
         let Some(agent) = app_data.storage.get(agent_id) else {
             app_data.git_op.clear();
-            return Ok(ErrorModalMode {
-                message: "Agent not found".to_string(),
-            }
-            .into());
+            return Ok(ErrorModalMode::new(TenexError::AgentMissing).into());
         };
 
         let worktree_path = agent.worktree_path.clone();
         let branch_name = app_data.git_op.branch_name.clone();
+        let pre_push_commands = Self::pre_push_commands(app_data);
+        app_data.git_op.clear();
+        if let Some(blocked) = super::secret_scan::blocked_by_secrets(&worktree_path, &branch_name)
+        {
+            return Ok(blocked);
+        }
 
         debug!(branch = %branch_name, "Executing push");
 
-        let push_output = run_push(&worktree_path, &branch_name)?;
+        app_data.background_ops.spawn_with_progress(
+            Some(agent_id),
+            format!("Pushing {branch_name}"),
+            move |cancelled, progress| {
+                match super::pre_push::run_pre_push_commands(
+                    &worktree_path,
+                    &pre_push_commands,
+                    cancelled,
+                ) {
+                    Ok(None) => {}
+                    Ok(Some(report)) => {
+                        return Box::new(move |app_data: &mut AppData| {
+                            Ok(Self::pre_push_failed(
+                                app_data,
+                                agent_id,
+                                branch_name,
+                                None,
+                                report,
+                            ))
+                        });
+                    }
+                    Err(err) => return Box::new(move |_: &mut AppData| Err(err)),
+                }
+                let push_output = run_push(&worktree_path, &branch_name, cancelled, progress);
+                Box::new(move |app_data: &mut AppData| {
+                    Ok(Self::finish_push(
+                        app_data,
+                        agent_id,
+                        &worktree_path,
+                        &branch_name,
+                        &push_output?,
+                    ))
+                })
+            },
+        );
 
-        if !push_output.status.success() {
-            let stderr = String::from_utf8_lossy(&push_output.stderr);
-            app_data.git_op.clear();
-            return Ok(ErrorModalMode {
-                message: format!("Push failed: {}", stderr.trim()),
+        Ok(AppMode::normal())
+    }
+
+    fn finish_push(
+        app_data: &mut AppData,
+        agent_id: Uuid,
+        worktree_path: &std::path::Path,
+        branch_name: &str,
+        output: &Output,
+    ) -> AppMode {
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if let Some(mode) = Self::push_auth_failed(
+                app_data,
+                agent_id,
+                worktree_path,
+                branch_name,
+                None,
+                &stderr,
+            ) {
+                return mode;
             }
-            .into());
+            return ErrorModalMode::new(TenexError::Git {
+                operation: "Push",
+                detail: stderr.trim().to_string(),
+            })
+            .into();
         }
 
         info!(branch = %branch_name, "Push successful");
         app_data.set_status(format!("Pushed branch: {branch_name}"));
-        app_data.git_op.clear();
-
-        Ok(AppMode::normal())
+        AppMode::normal()
     }
 }
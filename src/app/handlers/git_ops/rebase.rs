@@ -1,18 +1,46 @@
 //! Rebase flow (branch selector + rebase execution).
 
+use crate::error::TenexError;
 use crate::git;
 use anyhow::{Context, Result};
-use tracing::{debug, info};
+use std::path::Path;
+use std::process::Output;
+use std::sync::atomic::AtomicBool;
+use tracing::{debug, info, warn};
 
 use crate::app::AppData;
 use crate::state::{AppMode, ErrorModalMode, RebaseBranchSelectorMode, SuccessModalMode};
 
 use super::super::Actions;
+use super::commit_message::CommitMetadata;
+use uuid::Uuid;
 
 fn output_indicates_rebase_conflict(combined_output: &str) -> bool {
     combined_output.contains("CONFLICT") || combined_output.contains("could not apply")
 }
 
+/// Rebase onto `target_branch`; if cancelled, abort so the branch is left as it was.
+fn run_rebase(worktree_path: &Path, target_branch: &str, cancelled: &AtomicBool) -> Result<Output> {
+    let mut command = crate::git::git_command();
+    command
+        .args(["rebase", target_branch])
+        .current_dir(worktree_path);
+    let output = crate::git::output_cancellable(&mut command, cancelled);
+
+    if output
+        .as_ref()
+        .is_err_and(anyhow::Error::is::<crate::git::Cancelled>)
+    {
+        warn!(target = %target_branch, "Rebase cancelled - aborting");
+        let _ = crate::git::git_command()
+            .args(["rebase", "--abort"])
+            .current_dir(worktree_path)
+            .output();
+    }
+
+    output.context("Failed to execute rebase")
+}
+
 impl Actions {
     /// Start the rebase flow - show branch selector (Ctrl+r)
     ///
@@ -21,10 +49,9 @@ impl Actions {
     /// Returns an error if the git repository cannot be opened or branches cannot be listed.
     pub fn rebase_branch(app_data: &mut AppData) -> Result<AppMode> {
         let Some(agent) = app_data.selected_agent() else {
-            return Ok(ErrorModalMode {
-                message: "No agent selected. Select an agent first to rebase.".to_string(),
-            }
-            .into());
+            return Ok(
+                ErrorModalMode::new(TenexError::NoAgentSelected { action: "rebase" }).into(),
+            );
         };
 
         let agent_id = agent.id;
@@ -49,6 +76,8 @@ impl Actions {
 
     /// Execute the rebase operation
     ///
+    /// The rebase runs in the background; its result is reported when it finishes.
+    ///
     /// # Errors
     ///
     /// Returns an error if the rebase operation fails
@@ -56,24 +85,30 @@ impl Actions {
         let Some(agent_id) = app_data.git_op.agent_id else {
             app_data.git_op.clear();
             app_data.review.clear();
-            return Ok(ErrorModalMode {
-                message: "No agent ID for rebase".to_string(),
-            }
-            .into());
+            return Ok(ErrorModalMode::new(TenexError::AgentMissing).into());
         };
 
+        if let Some(busy) = Self::busy_agent_error(app_data, agent_id) {
+            return Ok(busy);
+        }
+
         let Some(agent) = app_data.storage.get(agent_id) else {
             app_data.git_op.clear();
             app_data.review.clear();
-            return Ok(ErrorModalMode {
-                message: "Agent not found".to_string(),
-            }
-            .into());
+            return Ok(ErrorModalMode::new(TenexError::AgentMissing).into());
         };
 
         let worktree_path = agent.worktree_path.clone();
+        let dirty_strategy = app_data.settings.dirty_worktree_strategy;
+        let metadata = app_data
+            .storage
+            .root_ancestor(agent_id)
+            .map(CommitMetadata::for_agent)
+            .unwrap_or_default();
         let current_branch = app_data.git_op.branch_name.clone();
         let target_branch = app_data.git_op.target_branch.clone();
+        app_data.git_op.clear();
+        app_data.review.clear();
 
         debug!(
             current = %current_branch,
@@ -81,13 +116,41 @@ impl Actions {
             "Executing rebase"
         );
 
-        // Execute git rebase
-        let output = crate::git::git_command()
-            .args(["rebase", &target_branch])
-            .current_dir(&worktree_path)
-            .output()
-            .context("Failed to execute rebase")?;
+        let label = format!("Rebasing {current_branch} onto {target_branch}");
+        app_data
+            .background_ops
+            .spawn_cancellable(Some(agent_id), label, move |cancelled| {
+                let result = super::dirty::with_changes_set_aside(
+                    &worktree_path,
+                    dirty_strategy,
+                    &metadata,
+                    "rebase",
+                    output_indicates_rebase_conflict,
+                    || run_rebase(&worktree_path, &target_branch, cancelled),
+                );
+                Box::new(move |app_data: &mut AppData| {
+                    let (output, note) = result?;
+                    Self::report_changes_note(app_data, note);
+                    Self::finish_rebase(
+                        app_data,
+                        agent_id,
+                        &current_branch,
+                        &target_branch,
+                        &output,
+                    )
+                })
+            });
+
+        Ok(AppMode::normal())
+    }
 
+    fn finish_rebase(
+        app_data: &mut AppData,
+        agent_id: Uuid,
+        current_branch: &str,
+        target_branch: &str,
+        output: &Output,
+    ) -> Result<AppMode> {
         if !output.status.success() {
             let stdout = String::from_utf8_lossy(&output.stdout);
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -101,16 +164,20 @@ impl Actions {
                     "Rebase has conflicts - spawning terminal"
                 );
                 // Spawn terminal for conflict resolution
-                return Self::spawn_conflict_terminal(app_data, "Rebase Conflict", "git status");
+                return Self::spawn_conflict_terminal(
+                    app_data,
+                    agent_id,
+                    "Rebase Conflict",
+                    "git status",
+                );
             }
 
             // Show error with both stdout and stderr for context
             let error_msg = super::merge::git_failure_message(stdout.as_ref(), stderr.as_ref());
-            app_data.git_op.clear();
-            app_data.review.clear();
-            return Ok(ErrorModalMode {
-                message: format!("Rebase failed: {error_msg}"),
-            }
+            return Ok(ErrorModalMode::new(TenexError::Git {
+                operation: "Rebase",
+                detail: error_msg,
+            })
             .into());
         }
 
@@ -119,8 +186,6 @@ impl Actions {
             target = %target_branch,
             "Rebase successful"
         );
-        app_data.git_op.clear();
-        app_data.review.clear();
         Ok(SuccessModalMode {
             message: format!("Rebased {current_branch} onto {target_branch}"),
         }
@@ -1,6 +1,7 @@
 //! Git rename flow (agents/branches/worktrees/mux sessions).
 
 use crate::agent::AgentRuntime;
+use crate::error::TenexError;
 use crate::mux::SessionManager;
 use anyhow::{Context, Result, bail};
 use tracing::{debug, info, warn};
@@ -41,8 +42,7 @@ impl Actions {
         app_data
             .git_op
             .start_rename(agent_id, current_name.clone(), is_root);
-        app_data.input.buffer = current_name;
-        app_data.input.cursor = app_data.input.buffer.len();
+        app_data.input.set(current_name);
         Ok(RenameBranchMode.into())
     }
 
@@ -57,19 +57,13 @@ impl Actions {
     pub fn execute_rename(app_data: &mut AppData) -> Result<AppMode> {
         let Some(agent_id) = app_data.git_op.agent_id else {
             app_data.git_op.clear();
-            return Ok(ErrorModalMode {
-                message: "No agent ID for rename".to_string(),
-            }
-            .into());
+            return Ok(ErrorModalMode::new(TenexError::AgentMissing).into());
         };
 
         // Verify agent exists
         if app_data.storage.get(agent_id).is_none() {
             app_data.git_op.agent_id = None;
-            return Ok(ErrorModalMode {
-                message: "Agent not found".to_string(),
-            }
-            .into());
+            return Ok(ErrorModalMode::new(TenexError::AgentMissing).into());
         }
 
         let old_name = app_data.git_op.original_branch.clone();
@@ -99,9 +93,10 @@ impl Actions {
 
         if let Err(err) = result {
             app_data.git_op.clear();
-            return Ok(ErrorModalMode {
-                message: format!("Rename failed: {err:#}"),
-            }
+            return Ok(ErrorModalMode::new(TenexError::Git {
+                operation: "Rename",
+                detail: format!("{err:#}"),
+            })
             .into());
         }
 
@@ -130,11 +125,11 @@ impl Actions {
         let old_branch = agent.branch.clone();
         let mux_session = agent.mux_session.clone();
 
-        // Generate new branch name from new title
-        let new_branch = app_data.config.generate_branch_name(new_name);
-        let new_worktree_path = app_data
-            .config
-            .worktree_path_for_repo_root(&repo_root, &new_branch);
+        // Generate new branch name from new title, keeping the branch's kind prefix
+        let config = app_data.repo_config(&repo_root);
+        let kind = config.branch_kind_of(&old_branch).unwrap_or_default();
+        let new_branch = config.generate_branch_name_for(kind, new_name);
+        let new_worktree_path = config.worktree_path_for_repo_root(&repo_root, &new_branch);
 
         // Rename local branch
         let rename_output = crate::git::git_command()
@@ -0,0 +1,63 @@
+//! Secret scanning before a push: refuse to push commits that look like they add credentials.
+
+use std::path::Path;
+
+use tracing::{debug, warn};
+
+use crate::git;
+use crate::state::{AppMode, ErrorModalMode};
+
+use super::super::Actions;
+
+/// Findings listed in the modal; the rest are counted.
+const MAX_LISTED_FINDINGS: usize = 8;
+
+/// Scan the commits a push of `branch_name` would send for credentials, and return a modal
+/// listing the findings when there are any.
+///
+/// A scan that cannot run does not block the push.
+pub(super) fn blocked_by_secrets(worktree_path: &Path, branch_name: &str) -> Option<AppMode> {
+    let base = Actions::detect_base_branch(worktree_path, branch_name);
+    let patch = match git::outgoing_patch(worktree_path, &base) {
+        Ok(patch) => patch,
+        Err(err) => {
+            debug!(branch = %branch_name, error = %err, "Skipped secret scan");
+            return None;
+        }
+    };
+    let findings = git::scan_patch(&patch);
+    if findings.is_empty() {
+        return None;
+    }
+
+    warn!(branch = %branch_name, findings = findings.len(), "Push blocked by secret scan");
+    let mut lines = vec![
+        format!(
+            "Push blocked: {} line(s) in {branch_name} look like credentials:",
+            findings.len()
+        ),
+        String::new(),
+    ];
+    lines.extend(findings.iter().take(MAX_LISTED_FINDINGS).map(|finding| {
+        format!(
+            "{}:{}  {} ({})",
+            finding.path.display(),
+            finding.line,
+            finding.rule,
+            finding.excerpt
+        )
+    }));
+    if findings.len() > MAX_LISTED_FINDINGS {
+        lines.push(format!(
+            "...and {} more",
+            findings.len() - MAX_LISTED_FINDINGS
+        ));
+    }
+    lines.push(String::new());
+    lines.push(format!(
+        "Remove them from the branch, or add `{}` to a line that holds no secret.",
+        git::ALLOW_SECRET_MARKER
+    ));
+    let message = lines.join("\n");
+    Some(ErrorModalMode::new(message).into())
+}
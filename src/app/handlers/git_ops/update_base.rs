@@ -0,0 +1,289 @@
+//! Update-from-base flow: rebase onto (or merge in) a root agent's base branch.
+
+use std::path::Path;
+use std::process::Output;
+use std::sync::atomic::AtomicBool;
+
+use anyhow::{Context, Result};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::app::{AppData, BaseUpdateStrategy, DirtyWorktreeStrategy};
+use crate::error::TenexError;
+use crate::git::{self, BranchDivergence};
+use crate::mux::SessionManager;
+use crate::state::{AppMode, ErrorModalMode, SuccessModalMode};
+
+use super::super::Actions;
+use super::commit_message::CommitMetadata;
+use super::dirty::{ChangesNote, with_changes_set_aside};
+
+/// Result of the update, gathered on the worker thread.
+enum UpdateOutcome {
+    /// The branch already contains every commit on the base.
+    UpToDate { base_ref: String },
+    /// Git ran; `divergence` is re-read afterwards when the update succeeded.
+    Ran {
+        base_ref: String,
+        behind: u32,
+        output: Output,
+        divergence: Option<BranchDivergence>,
+        note: Option<ChangesNote>,
+    },
+}
+
+/// Git settings for the update, captured on the UI thread.
+#[derive(Clone, Copy)]
+struct UpdateStrategies {
+    update: BaseUpdateStrategy,
+    dirty: DirtyWorktreeStrategy,
+}
+
+/// Rebase onto or merge in `base_ref`; if cancelled, abort so the branch is left as it was.
+fn run_git_update(
+    worktree_path: &Path,
+    base_ref: &str,
+    strategy: BaseUpdateStrategy,
+    cancelled: &AtomicBool,
+) -> Result<Output> {
+    let (args, abort): (&[&str], [&str; 2]) = match strategy {
+        BaseUpdateStrategy::Rebase => (&["rebase"], ["rebase", "--abort"]),
+        BaseUpdateStrategy::Merge => (&["merge", "--no-edit"], ["merge", "--abort"]),
+    };
+    let mut command = git::git_command();
+    command.args(args).arg(base_ref).current_dir(worktree_path);
+    let output = git::output_cancellable(&mut command, cancelled);
+
+    if output
+        .as_ref()
+        .is_err_and(anyhow::Error::is::<git::Cancelled>)
+    {
+        warn!(base = %base_ref, "Update from base cancelled - aborting");
+        let _ = git::git_command()
+            .args(abort)
+            .current_dir(worktree_path)
+            .output();
+    }
+
+    output.with_context(|| format!("Failed to {} {base_ref}", strategy.label()))
+}
+
+fn run_update(
+    worktree_path: &Path,
+    branch: &str,
+    base_branch: &str,
+    strategies: UpdateStrategies,
+    metadata: &CommitMetadata,
+    cancelled: &AtomicBool,
+) -> Result<UpdateOutcome> {
+    let before = git::divergence(worktree_path, branch, base_branch)?;
+    if before.behind == 0 {
+        return Ok(UpdateOutcome::UpToDate {
+            base_ref: before.base_ref,
+        });
+    }
+
+    let (output, note) = with_changes_set_aside(
+        worktree_path,
+        strategies.dirty,
+        metadata,
+        strategies.update.label(),
+        output_indicates_conflict,
+        || {
+            run_git_update(
+                worktree_path,
+                &before.base_ref,
+                strategies.update,
+                cancelled,
+            )
+        },
+    )?;
+    let divergence = if output.status.success() {
+        git::divergence(worktree_path, branch, base_branch)
+            .inspect_err(|err| debug!(branch, error = %err, "Skipping divergence"))
+            .ok()
+    } else {
+        None
+    };
+
+    Ok(UpdateOutcome::Ran {
+        base_ref: before.base_ref,
+        behind: before.behind,
+        output,
+        divergence,
+        note,
+    })
+}
+
+fn output_indicates_conflict(combined_output: &str) -> bool {
+    combined_output.contains("CONFLICT")
+        || combined_output.contains("could not apply")
+        || combined_output.contains("Automatic merge failed")
+}
+
+impl Actions {
+    /// Bring the selected agent's branch up to date with its base branch (`U`).
+    ///
+    /// Works on the root agent that owns the worktree, using the configured
+    /// [`BaseUpdateStrategy`]. Runs in the background; on success every agent working in the
+    /// worktree is told that its branch changed underneath it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the selected agent's root cannot be resolved.
+    pub fn update_from_base(app_data: &mut AppData) -> Result<AppMode> {
+        let Some(agent) = app_data.selected_agent() else {
+            return Ok(ErrorModalMode::new(TenexError::NoAgentSelected {
+                action: "update from base",
+            })
+            .into());
+        };
+        if !agent.is_git_workspace() {
+            return Ok(ErrorModalMode::new(TenexError::NotGitRepository {
+                action: "Update from base",
+            })
+            .into());
+        }
+
+        let root_id = app_data
+            .storage
+            .root_ancestor(agent.id)
+            .map(|root| root.id)
+            .context("Agent not found")?;
+        if let Some(busy) = Self::busy_agent_error(app_data, root_id) {
+            return Ok(busy);
+        }
+
+        let Some(root) = app_data.storage.get(root_id) else {
+            return Ok(ErrorModalMode::new(TenexError::AgentMissing).into());
+        };
+        let worktree_path = root.worktree_path.clone();
+        let branch = root.branch.clone();
+        let base_branch = root
+            .base_branch
+            .clone()
+            .unwrap_or_else(|| Self::detect_base_branch(&worktree_path, &branch));
+        let strategy = app_data.settings.base_update_strategy;
+        let strategies = UpdateStrategies {
+            update: strategy,
+            dirty: app_data.settings.dirty_worktree_strategy,
+        };
+        let metadata = CommitMetadata::for_agent(root);
+
+        debug!(branch = %branch, base = %base_branch, strategy = strategy.label(), "Updating from base");
+
+        let label = match strategy {
+            BaseUpdateStrategy::Rebase => format!("Rebasing {branch} onto {base_branch}"),
+            BaseUpdateStrategy::Merge => format!("Merging {base_branch} into {branch}"),
+        };
+        app_data
+            .background_ops
+            .spawn_cancellable(Some(root_id), label, move |cancelled| {
+                let outcome = run_update(
+                    &worktree_path,
+                    &branch,
+                    &base_branch,
+                    strategies,
+                    &metadata,
+                    cancelled,
+                );
+                Box::new(move |app_data: &mut AppData| {
+                    Self::finish_update_from_base(app_data, root_id, &branch, strategy, outcome?)
+                })
+            });
+
+        Ok(AppMode::normal())
+    }
+
+    fn finish_update_from_base(
+        app_data: &mut AppData,
+        root_id: Uuid,
+        branch: &str,
+        strategy: BaseUpdateStrategy,
+        outcome: UpdateOutcome,
+    ) -> Result<AppMode> {
+        let (base_ref, behind, output, divergence) = match outcome {
+            UpdateOutcome::UpToDate { base_ref } => {
+                app_data.set_status(format!("{branch} is already up to date with {base_ref}"));
+                return Ok(AppMode::normal());
+            }
+            UpdateOutcome::Ran {
+                base_ref,
+                behind,
+                output,
+                divergence,
+                note,
+            } => {
+                Self::report_changes_note(app_data, note);
+                (base_ref, behind, output, divergence)
+            }
+        };
+
+        if !output.status.success() {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if output_indicates_conflict(&format!("{stdout}{stderr}")) {
+                info!(branch, base = %base_ref, "Update from base has conflicts - spawning terminal");
+                return Self::spawn_conflict_terminal(
+                    app_data,
+                    root_id,
+                    &format!("Update Conflict: {base_ref} -> {branch}"),
+                    "git status",
+                );
+            }
+
+            let error_msg = super::merge::git_failure_message(stdout.as_ref(), stderr.as_ref());
+            return Ok(
+                ErrorModalMode::new(format!("Update from {base_ref} failed: {error_msg}")).into(),
+            );
+        }
+
+        if let Some(divergence) = divergence {
+            app_data
+                .ui
+                .branch_divergence_by_agent
+                .insert(root_id, divergence);
+        }
+
+        let verb = match strategy {
+            BaseUpdateStrategy::Rebase => "rebased onto",
+            BaseUpdateStrategy::Merge => "merged with",
+        };
+        let message = format!(
+            "Note: branch {branch} was just {verb} {base_ref} ({behind} new commit(s) from the base). \
+             Files in this worktree may have changed; re-read anything you are editing before continuing."
+        );
+        let notified = Self::notify_worktree_agents(app_data, root_id, &message);
+
+        info!(branch, base = %base_ref, notified, "Updated from base");
+        Ok(SuccessModalMode {
+            message: format!(
+                "Updated {branch} from {base_ref} ({}, {behind} commit(s)); notified {notified} agent(s)",
+                strategy.label()
+            ),
+        }
+        .into())
+    }
+
+    /// Send `message` to the root agent and every non-terminal descendant sharing its worktree.
+    fn notify_worktree_agents(app_data: &AppData, root_id: Uuid, message: &str) -> usize {
+        let session_manager = SessionManager::new();
+        let mut targets = Vec::new();
+        targets.extend(app_data.storage.get(root_id));
+        targets.extend(app_data.storage.descendants(root_id));
+
+        targets
+            .into_iter()
+            .filter(|agent| !agent.is_terminal_agent())
+            .filter(|agent| {
+                let target = agent.window_index.map_or_else(
+                    || agent.mux_session.clone(),
+                    |window_idx| SessionManager::window_target(&agent.mux_session, window_idx),
+                );
+                session_manager
+                    .send_keys_and_submit_for_agent(&target, agent, message)
+                    .is_ok()
+            })
+            .count()
+    }
+}
@@ -0,0 +1,45 @@
+//! Post-creation worktree setup (submodules, Git LFS) run in the background.
+
+use std::path::Path;
+
+use tracing::info;
+use uuid::Uuid;
+
+use crate::app::AppData;
+use crate::git;
+use crate::state::AppMode;
+
+use super::super::Actions;
+
+impl Actions {
+    /// Initialize submodules and pull LFS content for a newly created worktree.
+    ///
+    /// Does nothing when the repository uses neither (or both are turned off in its git
+    /// config); otherwise runs in the background with progress in the status bar.
+    pub(crate) fn start_worktree_setup(
+        app_data: &mut AppData,
+        agent_id: Uuid,
+        worktree_path: &Path,
+        branch: &str,
+    ) {
+        let setup = git::detect_worktree_setup(worktree_path);
+        if !setup.is_needed() {
+            return;
+        }
+
+        let worktree_path = worktree_path.to_path_buf();
+        let branch = branch.to_string();
+        let label = format!("Setting up {} for {branch}", setup.label());
+        app_data
+            .background_ops
+            .spawn_cancellable(Some(agent_id), label, move |cancelled| {
+                let result = git::run_worktree_setup(&worktree_path, setup, cancelled);
+                Box::new(move |app_data: &mut AppData| {
+                    result?;
+                    info!(%branch, steps = setup.label(), "Worktree setup finished");
+                    app_data.set_status(format!("Set up {} for {branch}", setup.label()));
+                    Ok(AppMode::normal())
+                })
+            });
+    }
+}
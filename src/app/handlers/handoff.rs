@@ -0,0 +1,154 @@
+//! Handoffs: asking agents to write down their progress before they are killed or synthesized,
+//! and handing those notes to the agents that replace them.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime};
+
+use anyhow::Result;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use super::Actions;
+use crate::app::AppData;
+use crate::app::state::BackgroundCompletion;
+use crate::prompts;
+
+/// How often to look for the handoff files.
+const HANDOFF_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How long a handoff file must go unchanged before it counts as written.
+const HANDOFF_SETTLE_TIME: Duration = Duration::from_secs(1);
+
+impl Actions {
+    /// Ask `agent_ids` to write their handoff notes, then run `then` once every file is written
+    /// or the configured timeout passes.
+    ///
+    /// Returns `false` without doing anything when handoffs are disabled or none of the agents
+    /// could be asked; the caller should then go ahead right away.
+    pub(super) fn request_handoffs(
+        self,
+        app_data: &mut AppData,
+        owner_id: Uuid,
+        agent_ids: &[Uuid],
+        then: BackgroundCompletion,
+    ) -> bool {
+        let Some(timeout) = app_data.settings.handoff_timeout() else {
+            return false;
+        };
+
+        let requested_at = SystemTime::now();
+        let mut paths: Vec<PathBuf> = Vec::new();
+        for agent_id in agent_ids {
+            let Some(agent) = app_data.storage.get(*agent_id) else {
+                continue;
+            };
+            if agent.is_terminal_agent() {
+                continue;
+            }
+
+            let path = agent.handoff_path();
+            if let Some(dir) = path.parent()
+                && let Err(err) = fs::create_dir_all(dir)
+            {
+                warn!(dir = %dir.display(), error = %err, "Failed to create handoff directory");
+                continue;
+            }
+            let relative = path.strip_prefix(&agent.worktree_path).unwrap_or(&path);
+            let request = prompts::build_handoff_request(&relative.display().to_string());
+            let target = app_data.storage.mux_target(agent);
+            if let Err(err) = self
+                .session_manager
+                .send_keys_and_submit_for_agent(&target, agent, &request)
+            {
+                debug!(target, error = %err, "Failed to request handoff");
+                continue;
+            }
+            paths.push(path);
+        }
+
+        if paths.is_empty() {
+            return false;
+        }
+
+        info!(count = paths.len(), ?timeout, "Waiting for handoff notes");
+        let count = paths.len();
+        app_data.background_ops.spawn_cancellable(
+            Some(owner_id),
+            format!("Waiting for handoff notes ({count})"),
+            move |cancelled| {
+                let outcome = wait_for_handoffs(paths, requested_at, timeout, cancelled);
+                Box::new(move |app_data: &mut AppData| {
+                    let missing = outcome?;
+                    if missing > 0 {
+                        warn!(missing, "Handoff notes not written before the timeout");
+                        app_data.set_status(format!(
+                            "{missing} of {count} agents did not write handoff notes in time"
+                        ));
+                    }
+                    then(app_data)
+                })
+            },
+        );
+        true
+    }
+
+    /// Prepend the handoff notes an earlier agent left in `worktree_path` to `prompt`.
+    ///
+    /// The notes file is moved aside once read, so each file is handed to exactly one
+    /// replacement. Returns `prompt` unchanged when there are no notes.
+    pub(super) fn take_handoff_prompt(
+        worktree_path: &Path,
+        prompt: Option<&str>,
+    ) -> Option<String> {
+        let dir = worktree_path.join(".tenex");
+        let path = dir.join(prompts::HANDOFF_FILE_NAME);
+        let notes = fs::read_to_string(&path).unwrap_or_default();
+        if notes.trim().is_empty() {
+            return prompt.map(str::to_string);
+        }
+
+        if let Err(err) = fs::rename(&path, dir.join(prompts::PREVIOUS_HANDOFF_FILE_NAME)) {
+            warn!(path = %path.display(), error = %err, "Failed to move consumed handoff notes");
+        }
+        info!(worktree = %worktree_path.display(), "Resuming from handoff notes");
+        Some(prompts::build_handoff_resume_prompt(&notes, prompt))
+    }
+}
+
+/// Wait until every file in `paths` is written, returning how many were still missing when
+/// `timeout` passed.
+pub(super) fn wait_for_handoffs(
+    mut paths: Vec<PathBuf>,
+    requested_at: SystemTime,
+    timeout: Duration,
+    cancelled: &AtomicBool,
+) -> Result<usize> {
+    let deadline = std::time::Instant::now() + timeout;
+    loop {
+        paths.retain(|path| !handoff_written(path, requested_at));
+        if paths.is_empty() || std::time::Instant::now() >= deadline {
+            return Ok(paths.len());
+        }
+        if cancelled.load(Ordering::Relaxed) {
+            return Err(crate::git::Cancelled.into());
+        }
+        std::thread::sleep(HANDOFF_POLL_INTERVAL);
+    }
+}
+
+/// Whether `path` was written after `requested_at` and has stopped changing.
+fn handoff_written(path: &Path, requested_at: SystemTime) -> bool {
+    let Ok(metadata) = fs::metadata(path) else {
+        return false;
+    };
+    let Ok(modified) = metadata.modified() else {
+        return false;
+    };
+    metadata.len() > 0
+        && modified >= requested_at
+        && modified
+            .elapsed()
+            .is_ok_and(|elapsed| elapsed >= HANDOFF_SETTLE_TIME)
+}
@@ -0,0 +1,202 @@
+//! Questions inbox: agents waiting on an answer from the user (`!`), and quick replies (`w`).
+
+use anyhow::Result;
+use tracing::{debug, info};
+
+use super::Actions;
+use crate::agent::{AgentNotifications, Status};
+use crate::app::state::{QUESTION_SCAN_LINES, find_question};
+use crate::app::{AppData, InboxScope};
+use crate::error::TenexError;
+use crate::state::{AppMode, ErrorModalMode, InboxMode, ReplyMode};
+use crate::text_width;
+
+/// Longest question shown in the status bar notice, in columns.
+const QUESTION_NOTICE_WIDTH: usize = 80;
+
+impl Actions {
+    /// Collect the questions of agents waiting for input, and drop those of agents that are
+    /// working again.
+    ///
+    /// Each agent's notification preference decides whether its questions, or the end of its
+    /// turn, raise a notice in the status bar.
+    ///
+    /// Call after pane activity has been synced, which tells when agents wait for input.
+    pub fn sync_questions(self, app_data: &mut AppData) {
+        let patterns = app_data.settings.question_patterns();
+        let all_waiting = app_data.settings.inbox_scope == InboxScope::AllWaiting;
+        let mut notices: Vec<String> = Vec::new();
+
+        for agent in app_data.storage.iter() {
+            if agent.status != Status::Running || agent.is_terminal_agent() {
+                continue;
+            }
+            let Some(digest) = app_data.ui.pane_digest_by_agent.get(&agent.id) else {
+                continue;
+            };
+            if !app_data.ui.agent_is_waiting_for_input(agent.id) {
+                app_data.inbox.forget(agent.id);
+                continue;
+            }
+            let started_waiting = !app_data.inbox.is_tracked(agent.id);
+            if app_data.inbox.is_checked(agent.id, digest.hash) {
+                continue;
+            }
+
+            let target = app_data.storage.mux_target(agent);
+            let lines = match self.output_capture.tail(&target, QUESTION_SCAN_LINES) {
+                Ok(lines) => plain_lines(&lines),
+                Err(err) => {
+                    debug!(agent = %agent.id, error = %err, "Failed to read output for the inbox");
+                    continue;
+                }
+            };
+            let any_question =
+                all_waiting || agent.notifications == AgentNotifications::AnyQuestion;
+            let question = find_question(&lines, &patterns).or_else(|| {
+                any_question
+                    .then(|| lines.iter().rev().find(|line| !line.trim().is_empty()))
+                    .flatten()
+                    .map(|line| line.trim().to_string())
+            });
+            let is_new = app_data
+                .inbox
+                .record(agent.id, digest.hash, question.clone());
+            match (agent.notifications, question) {
+                (AgentNotifications::Default | AgentNotifications::AnyQuestion, Some(question))
+                    if is_new =>
+                {
+                    info!(title = agent.title, question, "Agent asked a question");
+                    let question = text_width::truncate_to_width(&question, QUESTION_NOTICE_WIDTH);
+                    notices.push(format!("{} asks: {question} (! for inbox)", agent.title));
+                }
+                (AgentNotifications::FinishOnly, _) if started_waiting => {
+                    notices.push(format!("{} finished its turn", agent.title));
+                }
+                _ => {}
+            }
+        }
+
+        let storage = &app_data.storage;
+        app_data.inbox.retain_agents(|id| {
+            storage
+                .get(id)
+                .is_some_and(|agent| agent.status == Status::Running)
+        });
+
+        for notice in notices {
+            app_data.set_status(notice);
+        }
+    }
+
+    /// Open the questions inbox (`!`).
+    pub fn open_inbox(app_data: &mut AppData) -> AppMode {
+        app_data.input.clear();
+        app_data.inbox.selected = 0;
+        InboxMode.into()
+    }
+
+    /// Select the agent that asked the selected question and close the inbox.
+    pub fn jump_to_question(app_data: &mut AppData) -> AppMode {
+        let Some(agent_id) = app_data.inbox.selected_entry().map(|entry| entry.agent_id) else {
+            return AppMode::normal();
+        };
+        reveal_agent(app_data, agent_id);
+        AppMode::normal()
+    }
+
+    /// Select the agent that asked the selected question and start typing a reply to it.
+    pub fn reply_to_question(app_data: &mut AppData) -> AppMode {
+        let Some(agent_id) = app_data.inbox.selected_entry().map(|entry| entry.agent_id) else {
+            return InboxMode.into();
+        };
+        reveal_agent(app_data, agent_id);
+        app_data.input.clear();
+        ReplyMode.into()
+    }
+
+    /// Drop the selected question from the inbox without answering it.
+    pub fn dismiss_question(app_data: &mut AppData) -> AppMode {
+        if let Some(agent_id) = app_data.inbox.selected_entry().map(|entry| entry.agent_id) {
+            app_data.inbox.remove(agent_id);
+        }
+        InboxMode.into()
+    }
+
+    /// Open the reply input for the selected agent (`w`).
+    ///
+    /// Returns `None` when no agent is selected.
+    pub fn start_reply(app_data: &mut AppData) -> Option<AppMode> {
+        let agent = app_data.selected_agent()?;
+        if agent.status != Status::Running {
+            return Some(
+                ErrorModalMode::new(TenexError::AgentNotRunning {
+                    title: agent.title.clone(),
+                })
+                .into(),
+            );
+        }
+        app_data.input.clear();
+        Some(ReplyMode.into())
+    }
+
+    /// Send `reply` to the selected agent and drop its question from the inbox.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reply cannot be sent.
+    pub fn send_reply(self, app_data: &mut AppData, reply: &str) -> Result<AppMode> {
+        app_data.input.clear();
+        let Some(agent) = app_data.selected_agent() else {
+            return Ok(AppMode::normal());
+        };
+        if agent.status != Status::Running {
+            return Ok(ErrorModalMode::new(TenexError::AgentNotRunning {
+                title: agent.title.clone(),
+            })
+            .into());
+        }
+        let agent_id = agent.id;
+        let title = agent.title.clone();
+        let target = app_data.storage.mux_target(agent);
+        self.session_manager
+            .send_keys_and_submit_for_agent(&target, agent, reply)?;
+
+        info!(agent = %agent_id, "Sent reply");
+        app_data.inbox.remove(agent_id);
+        app_data.set_status(format!("Replied to {title}"));
+        Ok(AppMode::normal())
+    }
+}
+
+/// Select `agent_id` in the sidebar, expanding its collapsed ancestors first.
+fn reveal_agent(app_data: &mut AppData, agent_id: uuid::Uuid) {
+    let mut parent = app_data
+        .storage
+        .get(agent_id)
+        .and_then(|agent| agent.parent_id);
+    while let Some(id) = parent {
+        let _ = app_data.storage.set_collapsed(id, false);
+        parent = app_data.storage.get(id).and_then(|agent| agent.parent_id);
+    }
+    app_data.select_agent_by_id(agent_id);
+}
+
+/// `lines` with ANSI escape sequences removed.
+pub(super) fn plain_lines(lines: &[String]) -> Vec<String> {
+    let joined = lines.join("\n");
+    ansi_to_tui::IntoText::into_text(&joined.as_bytes()).map_or_else(
+        |_| lines.to_vec(),
+        |text| {
+            text.lines
+                .iter()
+                .map(|line| {
+                    line.spans
+                        .iter()
+                        .map(|span| span.content.as_ref())
+                        .collect()
+                })
+                .collect()
+        },
+    )
+}
@@ -0,0 +1,145 @@
+//! GitHub issue flow: create an agent for an issue (`#` and `tenex spawn --issue`).
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::AtomicBool;
+
+use anyhow::{Context, Result, bail};
+use tracing::{debug, info};
+
+use super::Actions;
+use crate::app::{AppData, GithubIssue};
+use crate::git;
+use crate::prompts;
+use crate::state::{AppMode, ErrorModalMode, IssuePickerMode};
+
+/// Most open issues listed in the picker.
+const ISSUE_LIMIT: &str = "200";
+
+/// Fields requested from `gh` for each issue.
+const ISSUE_FIELDS: &str = "number,title,body,url";
+
+/// Run `gh` with `args` in `repo_path` and return its stdout.
+fn run_gh(repo_path: &Path, args: &[&str], cancelled: &AtomicBool) -> Result<String> {
+    let mut command = Command::new("gh");
+    command.args(args).current_dir(repo_path);
+    let output = match git::output_cancellable(&mut command, cancelled) {
+        Ok(output) => output,
+        Err(err) if err.is::<git::Cancelled>() => return Err(err),
+        Err(err) => {
+            debug!(error = %err, "gh CLI not available");
+            bail!("gh CLI not found. Install it with: brew install gh");
+        }
+    };
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("gh {} failed: {}", args.join(" "), stderr.trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// List the open issues of the repository at `repo_path`.
+fn list_issues(repo_path: &Path, cancelled: &AtomicBool) -> Result<Vec<GithubIssue>> {
+    let stdout = run_gh(
+        repo_path,
+        &[
+            "issue",
+            "list",
+            "--state",
+            "open",
+            "--limit",
+            ISSUE_LIMIT,
+            "--json",
+            ISSUE_FIELDS,
+        ],
+        cancelled,
+    )?;
+    serde_json::from_str(&stdout).context("Failed to parse gh issue list output")
+}
+
+/// Look up issue `number` in the repository at `repo_path`.
+fn view_issue(repo_path: &Path, number: u64) -> Result<GithubIssue> {
+    let number = number.to_string();
+    let stdout = run_gh(
+        repo_path,
+        &["issue", "view", &number, "--json", ISSUE_FIELDS],
+        &AtomicBool::new(false),
+    )?;
+    serde_json::from_str(&stdout).context("Failed to parse gh issue view output")
+}
+
+impl Actions {
+    /// Repository new root agents are created in: the selected project, else the directory
+    /// Tenex was started in.
+    fn issue_repo_path(app_data: &AppData) -> Option<PathBuf> {
+        app_data
+            .selected_project_root()
+            .or_else(|| app_data.cwd_project_root.clone())
+            .or_else(|| std::env::current_dir().ok())
+            .filter(|path| git::is_git_repository(path))
+    }
+
+    /// Load the target repository's open issues in the background, then open the issue
+    /// picker (`#`).
+    pub fn start_issue_picker(app_data: &mut AppData) -> AppMode {
+        let Some(repo_path) = Self::issue_repo_path(app_data) else {
+            return ErrorModalMode::new(
+                "Creating an agent from an issue requires a git repository with a GitHub remote.",
+            )
+            .into();
+        };
+
+        let label = format!("Loading GitHub issues for {}", repo_path.display());
+        app_data
+            .background_ops
+            .spawn_cancellable(None, label, move |cancelled| {
+                let issues = list_issues(&repo_path, cancelled);
+                Box::new(move |app_data: &mut AppData| {
+                    let issues = issues?;
+                    if issues.is_empty() {
+                        return Ok(ErrorModalMode::new(format!(
+                            "No open issues in {}",
+                            repo_path.display()
+                        ))
+                        .into());
+                    }
+                    app_data.issue_picker.start(issues);
+                    Ok(IssuePickerMode.into())
+                })
+            });
+        AppMode::normal()
+    }
+
+    /// Create an agent for GitHub issue `number` (`tenex spawn --issue`).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the issue cannot be looked up or the agent cannot be created.
+    pub fn spawn_issue_agent(self, app_data: &mut AppData, number: u64) -> Result<AppMode> {
+        let repo_path = Self::issue_repo_path(app_data)
+            .context("Creating an agent from an issue requires a git repository")?;
+        let issue = view_issue(&repo_path, number)?;
+        self.create_issue_agent(app_data, &issue)
+    }
+
+    /// Create an agent for `issue`, named after the issue and prompted with its description.
+    ///
+    /// The issue becomes the agent's ticket (`#123`), so the branch name includes its number
+    /// and the agent's pull request can close it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the agent cannot be created.
+    pub fn create_issue_agent(
+        self,
+        app_data: &mut AppData,
+        issue: &GithubIssue,
+    ) -> Result<AppMode> {
+        let prompt =
+            prompts::build_issue_prompt(issue.number, &issue.title, &issue.url, &issue.body);
+
+        info!(number = issue.number, "Creating agent for GitHub issue");
+        app_data.spawn.ticket = Some(format!("#{}", issue.number));
+        self.create_agent(app_data, issue.title.trim(), Some(&prompt))
+    }
+}
@@ -3,12 +3,39 @@
 //! This module contains the `Actions` struct and all action handlers,
 //! organized into focused submodules by domain.
 
+mod agent_info;
 mod agent_lifecycle;
 mod broadcast;
+mod budget;
+mod canned_responses;
+mod change_alerts;
+mod compact;
+mod context_files;
+mod context_usage;
+mod dependency;
+mod edit_task;
 mod git_ops;
+mod handoff;
+mod inbox;
+mod issues;
+mod pause;
+mod plugins;
+mod pr_review;
 mod preview;
+mod recover;
+mod repos;
+mod restart;
+mod scope;
+mod scratchpad;
+mod send_to;
+mod shutdown;
 mod swarm;
+mod swarm_file;
+mod switch_model;
 mod sync;
+mod synthesis;
+mod timeouts;
+mod tmux_import;
 mod window;
 
 use crate::config::Action;
@@ -136,8 +163,8 @@ impl Actions {
             };
 
             let worktree_mgr = WorktreeManager::new(&repo);
-            let delete_branch = agent.branch.starts_with(&app_data.config.branch_prefix)
-                || agent.branch.starts_with("tenex/");
+            let delete_branch = app_data.is_managed_branch(&agent);
+            Self::set_aside_killed_root(app_data, &agent, &repo_path, delete_branch);
             let _ = if delete_branch {
                 worktree_mgr.remove(&agent.branch)
             } else {
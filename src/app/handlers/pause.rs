@@ -0,0 +1,48 @@
+//! Global pause: holding back held messages and automatic restarts on demand (`/pause`) or
+//! during the quiet hours.
+
+use chrono::Local;
+use tracing::info;
+
+use super::Actions;
+use crate::app::AppData;
+use crate::state::AppMode;
+
+impl Actions {
+    /// Pause held messages and automatic restarts, or resume them when they are paused
+    /// (`/pause`).
+    ///
+    /// Resuming during the quiet hours keeps things running until the quiet hours end.
+    pub fn toggle_pause(app_data: &mut AppData) -> AppMode {
+        app_data.input.clear();
+        let paused = app_data.pause.toggle();
+        info!(paused, "Toggled the global pause");
+        app_data.set_status(if paused {
+            "Paused: held messages and restarts wait until you run /pause again"
+        } else {
+            "Resumed held messages and restarts"
+        });
+        AppMode::normal()
+    }
+
+    /// Check whether the quiet hours started or ended, and say so in the status bar.
+    ///
+    /// Call before anything that respects the pause, so it sees the current state.
+    pub fn update_pause(app_data: &mut AppData) {
+        let in_quiet_hours = app_data
+            .settings
+            .quiet_hours
+            .is_some_and(|hours| hours.contains(Local::now().time()));
+        match app_data.pause.update(in_quiet_hours) {
+            Some(true) => {
+                info!("Quiet hours started");
+                app_data.set_status("Quiet hours: held messages and restarts are paused");
+            }
+            Some(false) => {
+                info!("Quiet hours ended");
+                app_data.set_status("Quiet hours ended: resumed held messages and restarts");
+            }
+            None => {}
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//! Plugin requests: palette commands, sidebar columns, and agent detail panels.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::AtomicBool;
+
+use tracing::warn;
+use uuid::Uuid;
+
+use super::Actions;
+use crate::app::AppData;
+use crate::plugin::{self, AgentContext, Plugin, PluginRequest};
+use crate::state::{AppMode, ChangelogMode};
+
+/// Label of the background operation that refreshes plugin columns.
+const COLUMN_REFRESH_LABEL: &str = "Refreshing plugin columns";
+
+/// Ask each plugin with detail panels for its panels about `agent`, as `### title` sections.
+///
+/// A panel that fails shows its error instead of failing the details modal.
+pub(super) fn panel_lines(
+    plugins: &[Plugin],
+    agent: &AgentContext,
+    cancelled: &AtomicBool,
+) -> anyhow::Result<Vec<String>> {
+    let mut lines = Vec::new();
+    for plugin in plugins {
+        for panel in &plugin.manifest.panels {
+            let request = PluginRequest::Panel {
+                name: panel.clone(),
+                agent: agent.clone(),
+            };
+            let body = match plugin::invoke(plugin, &request, cancelled) {
+                Ok(response) => response.lines,
+                Err(err) if err.is::<crate::git::Cancelled>() => return Err(err),
+                Err(err) => vec![format!("(failed: {err:#})")],
+            };
+            lines.push(String::new());
+            lines.push(format!("### {panel}"));
+            lines.extend(body);
+        }
+    }
+    Ok(lines)
+}
+
+impl Actions {
+    /// Run the plugin palette command `name` (including the leading `/`) in the background.
+    ///
+    /// Returns `None` when no plugin provides the command. The plugin's `lines` open in a
+    /// scrollable modal; otherwise its `status` is shown in the status bar.
+    pub fn run_plugin_command(app_data: &mut AppData, name: &str) -> Option<AppMode> {
+        let (plugin, command) = app_data.plugins.iter().find_map(|plugin| {
+            plugin
+                .manifest
+                .commands
+                .iter()
+                .find(|command| command.name.eq_ignore_ascii_case(name))
+                .map(|command| (plugin.clone(), command.name.clone()))
+        })?;
+        app_data.input.clear();
+
+        let request = PluginRequest::Command {
+            name: command.trim_start_matches('/').to_string(),
+            agent: app_data.selected_agent().map(AgentContext::from),
+        };
+        app_data.background_ops.spawn_cancellable(
+            None,
+            format!("Running {command}"),
+            move |cancelled| {
+                let response = plugin::invoke(&plugin, &request, cancelled);
+                Box::new(move |app_data: &mut AppData| {
+                    let response = response?;
+                    if response.lines.is_empty() {
+                        let status = response
+                            .status
+                            .unwrap_or_else(|| format!("{command} finished"));
+                        app_data.set_status(status);
+                        return Ok(AppMode::normal());
+                    }
+                    if let Some(status) = response.status {
+                        app_data.set_status(status);
+                    }
+                    Ok(ChangelogMode {
+                        title: format!("{}: {command}", plugin.manifest.name),
+                        lines: response.lines,
+                        mark_seen_version: None,
+                        agent_id: None,
+                    }
+                    .into())
+                })
+            },
+        );
+        Some(AppMode::normal())
+    }
+
+    /// Ask the plugins that add a sidebar column for fresh values, in the background.
+    ///
+    /// Does nothing when no plugin adds a column or a refresh is still running.
+    pub fn refresh_plugin_columns(app_data: &mut AppData) {
+        let plugins: Vec<Plugin> = app_data
+            .plugins
+            .iter()
+            .filter(|plugin| plugin.manifest.column)
+            .cloned()
+            .collect();
+        if plugins.is_empty()
+            || app_data
+                .background_ops
+                .in_flight()
+                .iter()
+                .any(|op| op.label == COLUMN_REFRESH_LABEL)
+        {
+            return;
+        }
+
+        let agents: Vec<AgentContext> = app_data.storage.iter().map(AgentContext::from).collect();
+        app_data
+            .background_ops
+            .spawn_cancellable(None, COLUMN_REFRESH_LABEL, move |cancelled| {
+                let mut columns: BTreeMap<Uuid, Vec<String>> = BTreeMap::new();
+                for plugin in &plugins {
+                    let request = PluginRequest::Column {
+                        agents: agents.clone(),
+                    };
+                    let values = match plugin::invoke(plugin, &request, cancelled) {
+                        Ok(response) => response.values,
+                        Err(err) => {
+                            warn!(plugin = %plugin.manifest.name, error = %err, "Plugin column failed");
+                            continue;
+                        }
+                    };
+                    for agent in &agents {
+                        if let Some(value) = values.get(&agent.id.to_string())
+                            && !value.is_empty()
+                        {
+                            columns.entry(agent.id).or_default().push(value.clone());
+                        }
+                    }
+                }
+                Box::new(move |app_data: &mut AppData| {
+                    app_data.ui.plugin_columns_by_agent = columns;
+                    Ok(AppMode::normal())
+                })
+            });
+    }
+}
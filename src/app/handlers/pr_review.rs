@@ -0,0 +1,308 @@
+//! Reviewer findings as a draft GitHub pull request review (`/pr_review`).
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::sync::atomic::AtomicBool;
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use serde_json::json;
+use tracing::{debug, info};
+use uuid::Uuid;
+
+use super::Actions;
+use crate::app::AppData;
+use crate::git;
+use crate::rpc::file_mentions;
+use crate::state::{AppMode, ErrorModalMode, SuccessModalMode};
+
+/// Scrollback lines read from each reviewer.
+const REVIEWER_OUTPUT_LINES: u32 = 5000;
+
+/// Title prefix of the agents a review swarm spawns.
+const REVIEWER_TITLE_PREFIX: &str = "Reviewer ";
+
+/// A finding anchored to a line of the pull request's diff.
+#[derive(Debug)]
+struct InlineComment {
+    path: String,
+    line: u32,
+    body: String,
+}
+
+/// The pull request for a branch, as reported by `gh pr view`.
+#[derive(Debug, Deserialize)]
+struct PullRequest {
+    number: u64,
+    url: String,
+}
+
+/// The review `gh api` created.
+#[derive(Debug, Deserialize)]
+struct CreatedReview {
+    html_url: String,
+}
+
+impl Actions {
+    /// Post the findings of the selected agent's reviewers (or of the selected reviewer) to its
+    /// branch's pull request as a draft review, in the background.
+    ///
+    /// Findings that name a `file:line` in the pull request's diff become inline comments; the
+    /// rest go into the review body.
+    pub fn start_pr_review_export(self, app_data: &mut AppData) -> AppMode {
+        app_data.input.clear();
+        let Some(agent) = app_data.selected_agent() else {
+            return ErrorModalMode::new("Select a reviewer or the agent it reviews.").into();
+        };
+        let reviewers: Vec<_> = if agent.title.starts_with(REVIEWER_TITLE_PREFIX) {
+            vec![agent]
+        } else {
+            app_data
+                .storage
+                .descendants(agent.id)
+                .into_iter()
+                .filter(|child| child.title.starts_with(REVIEWER_TITLE_PREFIX))
+                .filter(|child| !child.is_terminal_agent())
+                .collect()
+        };
+        if reviewers.is_empty() {
+            return ErrorModalMode::new(format!(
+                "{} has no reviewers. Start a review swarm with R first.",
+                agent.title
+            ))
+            .into();
+        }
+        let Some(root) = app_data.storage.root_ancestor(agent.id) else {
+            return AppMode::normal();
+        };
+
+        let outputs: Vec<(String, String)> = reviewers
+            .iter()
+            .map(|reviewer| {
+                let target = app_data.storage.mux_target(reviewer);
+                let output = self
+                    .output_capture
+                    .capture_pane_with_history(&target, REVIEWER_OUTPUT_LINES)
+                    .unwrap_or_default();
+                (reviewer.title.clone(), output)
+            })
+            .collect();
+        let worktree = root.worktree_path.clone();
+        let branch = root.branch.clone();
+        let owner_id = root.id;
+
+        info!(
+            branch,
+            reviewers = outputs.len(),
+            "Exporting review to pull request"
+        );
+        app_data.background_ops.spawn_cancellable(
+            Some(owner_id),
+            format!("Posting review findings to the PR for {branch}"),
+            move |cancelled| {
+                let posted = post_review(&worktree, &branch, &outputs, cancelled);
+                Box::new(move |_app_data: &mut AppData| {
+                    let (url, inline, general) = posted?;
+                    Ok(SuccessModalMode {
+                        message: format!(
+                            "Drafted a pending review with {inline} inline comment(s) and {general} general finding(s). Check and submit it at {url}"
+                        ),
+                    }
+                    .into())
+                })
+            },
+        );
+        AppMode::normal()
+    }
+}
+
+/// Post `outputs` (reviewer title and output) to `branch`'s pull request as a pending review,
+/// returning the review's URL and how many findings were inline and general.
+fn post_review(
+    worktree: &Path,
+    branch: &str,
+    outputs: &[(String, String)],
+    cancelled: &AtomicBool,
+) -> Result<(String, usize, usize)> {
+    let pr: PullRequest = serde_json::from_str(&run_gh(
+        worktree,
+        &["pr", "view", branch, "--json", "number,url"],
+        cancelled,
+    )?)
+    .context("Failed to parse gh pr view output")?;
+    let diff = run_gh(worktree, &["pr", "diff", &pr.number.to_string()], cancelled)?;
+    let commentable = commentable_lines(&diff);
+
+    let mut inline = Vec::new();
+    let mut general = Vec::new();
+    for (title, output) in outputs {
+        collect_findings(
+            title,
+            output,
+            worktree,
+            &commentable,
+            &mut inline,
+            &mut general,
+        );
+    }
+    if inline.is_empty() && general.is_empty() {
+        bail!("No file:line references found in the reviewers' output");
+    }
+
+    let mut body = format!("Findings from {} Tenex reviewer(s).", outputs.len());
+    if !general.is_empty() {
+        body.push_str("\n\nFindings outside the diff:\n\n");
+        body.push_str(&general.join("\n"));
+    }
+    let request = json!({
+        "body": body,
+        "comments": inline
+            .iter()
+            .map(|comment| json!({
+                "path": comment.path,
+                "line": comment.line,
+                "side": "RIGHT",
+                "body": comment.body,
+            }))
+            .collect::<Vec<_>>(),
+    });
+
+    // No `event`, so the review stays pending until someone submits it on GitHub.
+    let dir = worktree.join(".tenex");
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let request_path = dir.join(format!("pr-review-{}.json", Uuid::new_v4()));
+    fs::write(&request_path, request.to_string())
+        .with_context(|| format!("Failed to write {}", request_path.display()))?;
+    let endpoint = format!("repos/{{owner}}/{{repo}}/pulls/{}/reviews", pr.number);
+    let request_arg = request_path.display().to_string();
+    let created = run_gh(
+        worktree,
+        &[
+            "api",
+            "--method",
+            "POST",
+            &endpoint,
+            "--input",
+            &request_arg,
+        ],
+        cancelled,
+    );
+    let _ = fs::remove_file(&request_path);
+    let created: CreatedReview =
+        serde_json::from_str(&created?).context("Failed to parse gh api output")?;
+
+    info!(pr = %pr.url, inline = inline.len(), general = general.len(), "Posted draft review");
+    Ok((created.html_url, inline.len(), general.len()))
+}
+
+/// Run `gh` with `args` in `worktree` and return its stdout.
+fn run_gh(worktree: &Path, args: &[&str], cancelled: &AtomicBool) -> Result<String> {
+    let mut command = Command::new("gh");
+    command.args(args).current_dir(worktree);
+    let output = match git::output_cancellable(&mut command, cancelled) {
+        Ok(output) => output,
+        Err(err) if err.is::<git::Cancelled>() => return Err(err),
+        Err(err) => {
+            debug!(error = %err, "gh CLI not available");
+            bail!("gh CLI not found. Install it with: brew install gh");
+        }
+    };
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("gh {} failed: {}", args.join(" "), stderr.trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// New-side line ranges of each file's hunks in `diff`, which are the lines GitHub accepts
+/// review comments on.
+fn commentable_lines(diff: &str) -> HashMap<String, Vec<(u32, u32)>> {
+    let mut ranges: HashMap<String, Vec<(u32, u32)>> = HashMap::new();
+    let mut path: Option<String> = None;
+    for line in diff.lines() {
+        if let Some(new_path) = line.strip_prefix("+++ ") {
+            path = new_path.strip_prefix("b/").map(str::to_string);
+            continue;
+        }
+        let Some(hunk) = line.strip_prefix("@@ ") else {
+            continue;
+        };
+        let Some(path) = &path else {
+            continue;
+        };
+        // `@@ -old,count +new,count @@`
+        let Some(new_range) = hunk
+            .split_whitespace()
+            .find_map(|part| part.strip_prefix('+'))
+        else {
+            continue;
+        };
+        let (start, count) = new_range.split_once(',').unwrap_or((new_range, "1"));
+        if let (Ok(start), Ok(count)) = (start.parse::<u32>(), count.parse::<u32>())
+            && count > 0
+        {
+            ranges
+                .entry(path.clone())
+                .or_default()
+                .push((start, start + count - 1));
+        }
+    }
+    ranges
+}
+
+/// Turn each paragraph of `output` that names a `file:line` into a finding: an inline comment
+/// when the line is in the diff, otherwise a line of the review body.
+fn collect_findings(
+    title: &str,
+    output: &str,
+    worktree: &Path,
+    commentable: &HashMap<String, Vec<(u32, u32)>>,
+    inline: &mut Vec<InlineComment>,
+    general: &mut Vec<String>,
+) {
+    let lines: Vec<String> = output
+        .lines()
+        .map(|line| line.trim_end().to_string())
+        .collect();
+    for paragraph in lines.split(|line| line.trim().is_empty()) {
+        let Some((path, line)) = paragraph.iter().find_map(|text| {
+            file_mentions(std::slice::from_ref(text), worktree)
+                .into_iter()
+                .rev()
+                .find_map(|mention| Some((mention.path, mention.line?)))
+        }) else {
+            continue;
+        };
+        let Some(path) = relative_path(&path, worktree) else {
+            continue;
+        };
+
+        let text = paragraph.join("\n");
+        let in_diff = commentable.get(&path).is_some_and(|ranges| {
+            ranges
+                .iter()
+                .any(|(start, end)| (*start..=*end).contains(&line))
+        });
+        if in_diff {
+            inline.push(InlineComment {
+                path,
+                line,
+                body: format!("**{title}:**\n\n{text}"),
+            });
+        } else {
+            general.push(format!("- **{title}** (`{path}:{line}`): {}", text.trim()));
+        }
+    }
+}
+
+/// `path` relative to `worktree`, with `/` separators as GitHub expects.
+fn relative_path(path: &Path, worktree: &Path) -> Option<String> {
+    let relative = path.strip_prefix(worktree).ok()?;
+    let parts: Vec<_> = relative
+        .components()
+        .map(|part| part.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    Some(parts.join("/"))
+}
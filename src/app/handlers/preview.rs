@@ -125,7 +125,7 @@ impl Actions {
                 .max(usize::from(rows))
         };
 
-        let (content, cursor_position) = {
+        let (content, cursor_position, pane_title) = {
             let vt = app
                 .data
                 .ui
@@ -172,12 +172,15 @@ impl Actions {
             let (cursor_row, cursor_col) = vt.parser.screen().cursor_position();
             let cursor_hidden = vt.parser.screen().hide_cursor();
             let cursor_position = Some((cursor_col, cursor_row, cursor_hidden));
-            (content, cursor_position)
+            let pane_title =
+                Some(vt.parser.screen().title().to_string()).filter(|title| !title.is_empty());
+            (content, cursor_position, pane_title)
         };
 
         app.data.ui.set_preview_content(content);
         app.data.ui.preview_cursor_position = cursor_position;
         app.data.ui.preview_pane_size = Some((cols, rows));
+        app.data.ui.preview_pane_title = pane_title;
         true
     }
 
@@ -191,20 +194,17 @@ impl Actions {
     ) {
         app.data.ui.preview_vt_by_target.remove(target);
 
-        let history_lines = history_lines_following.max(u32::from(rows));
-        let content = if wants_full_history {
-            self.output_capture
-                .capture_full_history(target)
-                .unwrap_or_default()
-        } else {
-            self.output_capture
-                .capture_pane_with_history(target, history_lines)
-                .unwrap_or_default()
-        };
-
-        app.data.ui.set_preview_content(content);
-        app.data.ui.preview_cursor_position = self.output_capture.cursor_position(target).ok();
-        app.data.ui.preview_pane_size = self.output_capture.pane_size(target).ok();
+        let history_lines =
+            (!wants_full_history).then(|| history_lines_following.max(u32::from(rows)));
+        let snapshot = self
+            .output_capture
+            .capture_snapshot(target, history_lines)
+            .unwrap_or_default();
+
+        app.data.ui.set_preview_content(snapshot.content);
+        app.data.ui.preview_cursor_position = snapshot.cursor_position;
+        app.data.ui.preview_pane_size = snapshot.size;
+        app.data.ui.preview_pane_title = snapshot.title.filter(|title| !title.is_empty());
     }
 
     /// Update diff content for the selected agent
@@ -220,7 +220,7 @@ impl Actions {
                     let diff_gen = DiffGenerator::new(&repo);
                     app.data.ui.diff_force_refresh = false;
 
-                    let model = match diff_gen.uncommitted_model() {
+                    let mut model = match diff_gen.uncommitted_model() {
                         Ok(model) => model,
                         Err(err) => {
                             app.data.ui.diff_model = None;
@@ -233,6 +233,9 @@ impl Actions {
                         }
                     };
                     let marker_hash = diff_gen.uncommitted_change_marker().unwrap_or(u64::MAX);
+                    if let Some(agent) = app.data.storage.get(agent_id) {
+                        model.retain_files(|path| agent.is_in_scope(path));
+                    }
 
                     app.data.ui.diff_hash = marker_hash;
                     app.data.ui.diff_model = Some(model.clone());
@@ -471,6 +474,7 @@ fn set_preview_message(app: &mut App, message: &str) {
     app.data.ui.set_preview_content(message.to_string());
     app.data.ui.preview_cursor_position = None;
     app.data.ui.preview_pane_size = None;
+    app.data.ui.preview_pane_title = None;
 }
 
 #[derive(Debug)]
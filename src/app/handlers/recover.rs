@@ -0,0 +1,165 @@
+//! Killed agents' work: setting it aside on kill (trash, recovery record) and recreating a
+//! killed agent's branch from its lost commits (`D`).
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+
+use anyhow::Result;
+use tracing::{debug, info, warn};
+
+use super::Actions;
+use crate::agent::{
+    Agent, KilledAgent, killed_agents_path, load_killed_agents, move_to_trash, purge_trash,
+    record_killed_agent, trash_dir,
+};
+use crate::app::{AppData, RecoveryCandidate};
+use crate::git;
+use crate::state::{AppMode, ErrorModalMode, RecoverPickerMode, SuccessModalMode};
+
+/// Seconds around an agent's lifetime in which dangling commits are attributed to it.
+const DANGLING_SLACK_SECS: i64 = 60;
+
+/// Lost commits for each of `killed`, scanning each repository for dangling commits once.
+fn find_candidates(
+    killed: &[KilledAgent],
+    cancelled: &AtomicBool,
+) -> Result<Vec<RecoveryCandidate>> {
+    let mut dangling_by_repo: HashMap<PathBuf, Vec<String>> = HashMap::new();
+    let mut candidates = Vec::new();
+    for agent in killed {
+        let dangling = match dangling_by_repo.entry(agent.repo_root.clone()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let dangling = match git::dangling_commits(&agent.repo_root, cancelled) {
+                    Ok(dangling) => dangling,
+                    Err(err) if err.is::<git::Cancelled>() => return Err(err),
+                    Err(err) => {
+                        debug!(repo = %agent.repo_root.display(), error = %err, "Skipping dangling commits");
+                        Vec::new()
+                    }
+                };
+                entry.insert(dangling)
+            }
+        };
+
+        let window = (
+            agent.created_at.timestamp() - DANGLING_SLACK_SECS,
+            agent.killed_at.timestamp() + DANGLING_SLACK_SECS,
+        );
+        match git::find_lost_commits(
+            &agent.repo_root,
+            &agent.branch,
+            agent.tip.as_deref(),
+            dangling,
+            window,
+        ) {
+            Ok(commits) => {
+                candidates.extend(commits.into_iter().map(|commit| RecoveryCandidate {
+                    title: agent.title.clone(),
+                    branch: agent.branch.clone(),
+                    repo_root: agent.repo_root.clone(),
+                    commit,
+                }));
+            }
+            Err(err) => debug!(branch = %agent.branch, error = %err, "Skipping killed agent"),
+        }
+    }
+    Ok(candidates)
+}
+
+impl Actions {
+    /// Set aside a killed root agent's work before its worktree (and, with `delete_branch`,
+    /// its branch) is removed.
+    ///
+    /// The branch tip is remembered for recovery (`D`), and while the trash is enabled the
+    /// worktree moves to the trash and the branch is kept as a trash ref. Expired trash is
+    /// purged on the way.
+    pub(super) fn set_aside_killed_root(
+        app_data: &AppData,
+        root: &Agent,
+        repo_path: &Path,
+        delete_branch: bool,
+    ) {
+        if !root.is_git_workspace() {
+            return;
+        }
+        let state_path = app_data.storage.resolved_state_path();
+
+        if delete_branch {
+            let tip = git::branch_tip(repo_path, &root.branch);
+            let killed = KilledAgent::new(root, repo_path.to_path_buf(), tip);
+            if let Err(err) = record_killed_agent(&killed_agents_path(&state_path), killed) {
+                warn!(branch = %root.branch, error = %err, "Failed to record killed agent");
+            }
+        }
+
+        let Some(retention) = app_data.settings.trash_retention() else {
+            return;
+        };
+        let trash = trash_dir(&state_path);
+        match move_to_trash(&trash, root, repo_path, delete_branch) {
+            Ok(entry) => {
+                info!(branch = %root.branch, trash_ref = ?entry.trash_ref, "Moved agent to trash");
+            }
+            Err(err) => warn!(branch = %root.branch, error = %err, "Failed to move agent to trash"),
+        }
+        match purge_trash(&trash, Some(retention)) {
+            Ok(0) => {}
+            Ok(purged) => info!(purged, "Purged expired trash"),
+            Err(err) => warn!(error = %err, "Failed to purge expired trash"),
+        }
+    }
+
+    /// Search recently killed agents' repositories for their lost branch tips in the
+    /// background, then open the recovery picker (`D`).
+    pub fn start_recovery(app_data: &mut AppData) -> AppMode {
+        let path = killed_agents_path(&app_data.storage.resolved_state_path());
+        let killed = load_killed_agents(&path);
+        if killed.is_empty() {
+            return ErrorModalMode::new("No recently killed agents to recover. Only agents whose branch was deleted are remembered.")
+            .into();
+        }
+
+        let label = format!("Searching for commits of {} killed agent(s)", killed.len());
+        app_data
+            .background_ops
+            .spawn_cancellable(None, label, move |cancelled| {
+                let candidates = find_candidates(&killed, cancelled);
+                Box::new(move |app_data: &mut AppData| {
+                    let candidates = candidates?;
+                    if candidates.is_empty() {
+                        return Ok(ErrorModalMode::new("No commits found for recently killed agents. They may have been garbage collected.")
+                        .into());
+                    }
+                    app_data.recover_picker.start(candidates);
+                    Ok(RecoverPickerMode.into())
+                })
+            });
+        AppMode::normal()
+    }
+
+    /// Recreate `candidate`'s branch at its commit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the branch cannot be created.
+    pub fn recover_branch(candidate: &RecoveryCandidate) -> Result<AppMode> {
+        let name = git::recreate_branch(
+            &candidate.repo_root,
+            &candidate.branch,
+            &candidate.commit.commit,
+        )?;
+        info!(branch = %name, commit = %candidate.commit.commit, "Recovered branch");
+        Ok(SuccessModalMode {
+            message: format!(
+                "Recreated branch {name} at {} ({}) in {}. Switch an agent to it with Ctrl+s to keep working.",
+                candidate.commit.short_commit(),
+                candidate.commit.summary,
+                candidate.repo_root.display()
+            ),
+        }
+        .into())
+    }
+}
@@ -0,0 +1,105 @@
+//! Workspace repositories: switching between repositories and adding more (`/repos`).
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, bail};
+use tracing::info;
+
+use super::Actions;
+use crate::app::state::RepoEntry;
+use crate::app::{AppData, SidebarItem};
+use crate::error::TenexError;
+use crate::state::{AddRepoMode, AppMode, ErrorModalMode, RepoPickerMode};
+
+impl Actions {
+    /// Open the repository picker (`/repos`), listing the repositories in the sidebar.
+    pub fn open_repo_picker(app_data: &mut AppData) -> AppMode {
+        app_data.input.clear();
+        let repos: Vec<RepoEntry> = app_data
+            .sidebar_items()
+            .into_iter()
+            .filter_map(|item| match item {
+                SidebarItem::Project(project) => Some(RepoEntry {
+                    registered: app_data.settings.repos.contains(&project.root),
+                    root: project.root,
+                    label: project.label,
+                    agent_count: project.agent_count,
+                }),
+                SidebarItem::Agent(_) => None,
+            })
+            .collect();
+        let current = app_data.selected_project_root();
+        app_data.repo_picker.start(repos, current.as_ref());
+        RepoPickerMode.into()
+    }
+
+    /// Switch to the repository selected in the picker. Selecting it in the sidebar makes new
+    /// agents start in it.
+    pub fn switch_repo(app_data: &mut AppData) -> AppMode {
+        let Some(repo) = app_data.repo_picker.selected_repo().cloned() else {
+            return RepoPickerMode.into();
+        };
+        app_data.repo_picker.clear();
+        app_data.select_project(&repo.root);
+        app_data.set_status(format!("Switched to {}", repo.label));
+        AppMode::normal()
+    }
+
+    /// Start typing the path of a repository to add to the workspace.
+    pub fn start_add_repo(app_data: &mut AppData) -> AppMode {
+        app_data.input.clear();
+        AddRepoMode.into()
+    }
+
+    /// Register the repository containing `path` in the workspace and switch to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` is not a directory or the settings cannot be saved.
+    pub fn add_repo(app_data: &mut AppData, path: &str) -> Result<AppMode> {
+        let path = path
+            .strip_prefix("~/")
+            .and_then(|rest| crate::paths::home_dir().map(|home| home.join(rest)))
+            .unwrap_or_else(|| PathBuf::from(path));
+        let path = std::fs::canonicalize(&path)
+            .with_context(|| format!("Failed to find {}", path.display()))?;
+        if !path.is_dir() {
+            bail!("{} is not a directory", path.display());
+        }
+        let root = crate::git::repository_workspace_root(&path).unwrap_or(path);
+
+        app_data.input.clear();
+        app_data.repo_picker.clear();
+        let added = app_data.settings.register_repo(root.clone())?;
+        app_data.select_project(&root);
+        if added {
+            info!(root = %root.display(), "Added repository to the workspace");
+            app_data.set_status(format!("Added {} to the workspace", root.display()));
+        } else {
+            app_data.set_status(format!("{} is already in the workspace", root.display()));
+        }
+        Ok(AppMode::normal())
+    }
+
+    /// Remove the repository selected in the picker from the workspace. Its agents stay.
+    pub fn remove_repo(app_data: &mut AppData) -> AppMode {
+        let Some(repo) = app_data.repo_picker.selected_repo().cloned() else {
+            return RepoPickerMode.into();
+        };
+        if !repo.registered {
+            return ErrorModalMode::new(format!(
+                    "{} was not added with /repos; it is listed because agents work in it or Tenex started in it",
+                    repo.label
+                ))
+            .into();
+        }
+        if let Err(err) = app_data.settings.unregister_repo(&repo.root) {
+            return ErrorModalMode::new(TenexError::SettingsSave {
+                detail: err.to_string(),
+            })
+            .into();
+        }
+        app_data.set_status(format!("Removed {} from the workspace", repo.label));
+        Self::open_repo_picker(app_data)
+    }
+}
@@ -0,0 +1,103 @@
+//! Restarting agents whose program exited, following their restart policy.
+
+use anyhow::Result;
+use chrono::Utc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::Actions;
+use crate::agent::Status;
+use crate::app::AppData;
+
+impl Actions {
+    /// Start the program of each finished agent again when its restart policy asks for it and
+    /// its backoff has passed.
+    ///
+    /// A restart resumes the agent's conversation when it has one. Otherwise the program starts
+    /// over from the handoff notes left in the worktree, or from the agent's first prompt.
+    /// Nothing is restarted while Tenex is paused. Returns whether any agent was restarted or
+    /// failed to restart.
+    pub(super) fn restart_exited_agents(self, app_data: &mut AppData) -> bool {
+        if app_data.pause.is_paused() {
+            return false;
+        }
+        let now = Utc::now();
+        let due: Vec<Uuid> = app_data
+            .storage
+            .iter()
+            .filter(|agent| {
+                let Status::Finished(code) = agent.status else {
+                    return false;
+                };
+                let policy = app_data.settings.restart_policy_for(agent);
+                !agent.is_terminal_agent()
+                    && policy.should_restart(code, agent.restarts)
+                    && (now - agent.updated_at)
+                        .to_std()
+                        .is_ok_and(|waited| waited >= policy.backoff(agent.restarts))
+            })
+            .map(|agent| agent.id)
+            .collect();
+
+        for agent_id in &due {
+            self.restart_agent(app_data, *agent_id);
+        }
+        !due.is_empty()
+    }
+
+    fn restart_agent(self, app_data: &mut AppData, agent_id: Uuid) {
+        let Some(agent) = app_data.storage.get(agent_id).cloned() else {
+            return;
+        };
+        let Status::Finished(code) = agent.status else {
+            return;
+        };
+        let title = agent.title.clone();
+        let policy = app_data.settings.restart_policy_for(&agent);
+        let attempt = agent.restarts.saturating_add(1);
+
+        let prompt = if agent.conversation_id.is_some() {
+            None
+        } else {
+            Self::take_handoff_prompt(&agent.worktree_path, agent.prompt.as_deref())
+        };
+        let launch = if agent.conversation_id.is_some() {
+            crate::runtime::AgentLaunch::Resume
+        } else {
+            crate::runtime::AgentLaunch::Spawn {
+                prompt: prompt.as_deref(),
+            }
+        };
+        let report = move |app_data: &mut AppData, relaunched: Result<()>| {
+            let Some(stored) = app_data.storage.get_mut(agent_id) else {
+                return Ok(());
+            };
+            stored.restarts = attempt;
+            match relaunched {
+                Ok(()) => {
+                    stored.set_status(Status::Running);
+                    info!(%title, code, attempt, "Restarted agent after its program exited");
+                    app_data.ui.notify_warning(format!(
+                        "Restarted {title} after exit code {code} ({attempt} of {})",
+                        policy.max_retries
+                    ));
+                }
+                Err(err) => {
+                    // Count the failed attempt and wait out the next backoff from now.
+                    stored.set_status(Status::Finished(code));
+                    warn!(%title, error = %err, "Failed to restart agent");
+                    app_data
+                        .ui
+                        .notify_error(format!("Failed to restart {title}: {err:#}"));
+                }
+            }
+            Ok(())
+        };
+        // The program has already exited, so the agent is relaunched right away, and `report`
+        // handles a failure.
+        let _ = self.relaunch_agent(app_data, agent, launch, report);
+        if let Err(err) = app_data.storage.save() {
+            warn!(error = %err, "Failed to save agent restart");
+        }
+    }
+}
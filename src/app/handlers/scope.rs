@@ -0,0 +1,84 @@
+//! Path scopes: the directory of the repository an agent keeps its changes to (`/scope`).
+
+use std::path::{Component, Path};
+
+use anyhow::{Result, bail};
+use tracing::info;
+
+use super::Actions;
+use crate::agent::Status;
+use crate::app::AppData;
+use crate::prompts;
+use crate::state::{AppMode, ErrorModalMode, ScopeMode};
+
+impl Actions {
+    /// Start typing the selected agent's scope (`/scope`), starting from its current one.
+    pub fn start_scope(app_data: &mut AppData) -> AppMode {
+        app_data.input.clear();
+        let Some(agent) = app_data.selected_agent() else {
+            return AppMode::normal();
+        };
+        if agent.is_terminal_agent() {
+            return ErrorModalMode::new("Terminals cannot have a scope").into();
+        }
+        app_data.input.set(agent.scope.clone().unwrap_or_default());
+        ScopeMode.into()
+    }
+
+    /// Set the selected agent's scope to `input`, or clear it when `input` is empty, and tell
+    /// the agent about it when it is running.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `input` is not a directory inside the repository, the agent state
+    /// cannot be saved, or the running agent cannot be told.
+    pub fn set_scope(self, app_data: &mut AppData, input: &str) -> Result<AppMode> {
+        let scope = normalize_scope(input)?;
+        app_data.input.clear();
+        let Some(agent_id) = app_data.selected_agent().map(|agent| agent.id) else {
+            return Ok(AppMode::normal());
+        };
+        let Some(agent) = app_data.storage.get_mut(agent_id) else {
+            return Ok(AppMode::normal());
+        };
+        agent.scope.clone_from(&scope);
+        let title = agent.title.clone();
+        app_data.storage.save()?;
+        app_data.ui.diff_force_refresh = true;
+
+        if let Some(agent) = app_data.storage.get(agent_id)
+            && agent.status == Status::Running
+        {
+            let note = scope.as_deref().map_or_else(
+                || prompts::SCOPE_CLEARED_NOTE.to_string(),
+                prompts::build_scope_note,
+            );
+            let target = app_data.storage.mux_target(agent);
+            self.session_manager
+                .send_keys_and_submit_for_agent(&target, agent, &note)?;
+        }
+
+        info!(agent = %agent_id, ?scope, "Set agent scope");
+        app_data.set_status(scope.map_or_else(
+            || format!("{title} may change any file again"),
+            |scope| format!("{title} now keeps its changes to {scope}"),
+        ));
+        Ok(AppMode::normal())
+    }
+}
+
+/// `input` as a scope: a directory relative to the repository root, ending in `/`. `None` when
+/// `input` is empty.
+fn normalize_scope(input: &str) -> Result<Option<String>> {
+    let trimmed = input.trim().trim_start_matches("./").trim_end_matches('/');
+    if trimmed.is_empty() || trimmed == "." {
+        return Ok(None);
+    }
+    if !Path::new(trimmed)
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+    {
+        bail!("The scope must be a directory inside the repository, such as services/api/");
+    }
+    Ok(Some(format!("{trimmed}/")))
+}
@@ -0,0 +1,99 @@
+//! Shared scratchpad: `.tenex/scratchpad.md` in a root worktree, read and written by every
+//! agent in the swarm and by the user through the Scratchpad tab.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use tracing::info;
+
+use super::Actions;
+use crate::app::{App, AppData};
+use crate::error::TenexError;
+use crate::prompts;
+use crate::state::{AppMode, EditScratchpadMode, ErrorModalMode};
+
+/// Contents of a newly created scratchpad.
+const SCRATCHPAD_TEMPLATE: &str = "# Scratchpad\n\nShared notes for the agents in this swarm and the user. Append your notes; don't rewrite other people's.\n";
+
+fn scratchpad_path(worktree_path: &Path) -> PathBuf {
+    worktree_path.join(prompts::SCRATCHPAD_PATH)
+}
+
+/// Create the scratchpad in `worktree_path` if it doesn't exist yet.
+///
+/// # Errors
+///
+/// Returns an error if the `.tenex` directory or the file cannot be created.
+pub(super) fn ensure_scratchpad(worktree_path: &Path) -> Result<()> {
+    let path = scratchpad_path(worktree_path);
+    if path.exists() {
+        return Ok(());
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    fs::write(&path, SCRATCHPAD_TEMPLATE)
+        .with_context(|| format!("Failed to create {}", path.display()))
+}
+
+/// Root worktree of the selected agent, whose scratchpad its swarm shares.
+fn selected_root_worktree(app_data: &AppData) -> Option<PathBuf> {
+    let agent = app_data.selected_agent()?;
+    let root = app_data.storage.root_ancestor(agent.id).unwrap_or(agent);
+    Some(root.worktree_path.clone())
+}
+
+impl Actions {
+    /// Reload the Scratchpad tab from the selected agent's root worktree.
+    pub fn update_scratchpad(app: &mut App) {
+        let Some(worktree_path) = selected_root_worktree(&app.data) else {
+            app.data.ui.set_scratchpad_content("(No agent selected)");
+            return;
+        };
+        let content = fs::read_to_string(scratchpad_path(&worktree_path)).unwrap_or_else(|_| {
+            format!(
+                "(No scratchpad yet)\n\nSpawned children are told about {} in their worktree.\nPress Enter to start one.",
+                prompts::SCRATCHPAD_PATH
+            )
+        });
+        app.data.ui.set_scratchpad_content(content);
+    }
+
+    /// Open the scratchpad editor for the selected agent's swarm (Enter on the Scratchpad tab).
+    ///
+    /// Returns `None` when no agent is selected.
+    pub fn start_edit_scratchpad(app_data: &mut AppData) -> Option<AppMode> {
+        let worktree_path = selected_root_worktree(app_data)?;
+        let content = fs::read_to_string(scratchpad_path(&worktree_path))
+            .unwrap_or_else(|_| SCRATCHPAD_TEMPLATE.to_string());
+        app_data.input.set(content);
+        Some(EditScratchpadMode.into())
+    }
+
+    /// Write `content` to the selected agent's scratchpad.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the scratchpad cannot be written.
+    pub fn save_scratchpad(app_data: &mut AppData, content: &str) -> Result<AppMode> {
+        let Some(worktree_path) = selected_root_worktree(app_data) else {
+            return Ok(ErrorModalMode::new(TenexError::NoAgentSelected {
+                action: "open its scratchpad",
+            })
+            .into());
+        };
+
+        ensure_scratchpad(&worktree_path)?;
+        let path = scratchpad_path(&worktree_path);
+        let mut content = content.trim_end().to_string();
+        content.push('\n');
+        fs::write(&path, &content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        app_data.ui.set_scratchpad_content(content);
+
+        info!(path = %path.display(), "Saved scratchpad");
+        app_data.set_status("Saved scratchpad");
+        Ok(AppMode::normal())
+    }
+}
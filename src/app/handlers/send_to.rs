@@ -0,0 +1,126 @@
+//! Send-to flow: hand one agent's output to another agent (`>`).
+
+use anyhow::Result;
+use tracing::info;
+
+use super::Actions;
+use crate::agent::Agent;
+use crate::app::{AppData, SendToTarget};
+use crate::error::TenexError;
+use crate::mux::SessionManager;
+use crate::state::{AppMode, ErrorModalMode, SendToMode};
+
+/// How many lines of recent output are sent when nothing is selected.
+const SEND_TO_TAIL_LINES: usize = 200;
+
+fn mux_target(agent: &Agent) -> String {
+    agent.window_index.map_or_else(
+        || agent.mux_session.clone(),
+        |window_idx| SessionManager::window_target(&agent.mux_session, window_idx),
+    )
+}
+
+/// Contents of the `.tenex/<id>.md` file handed to the receiving agent.
+fn shared_output_contents(source: &Agent, output: &str) -> String {
+    format!(
+        "# Output from {} ({})\n\n```\n{}\n```\n",
+        source.title,
+        source.short_id(),
+        output.trim_end()
+    )
+}
+
+impl Actions {
+    /// Open the send-to picker for the selected agent (`>`).
+    ///
+    /// Returns `None` when no agent is selected.
+    pub fn start_send_to(app_data: &mut AppData) -> Option<AppMode> {
+        let source = app_data.selected_agent()?;
+        let source_id = source.id;
+        let targets: Vec<SendToTarget> = app_data
+            .storage
+            .iter()
+            .filter(|agent| agent.id != source_id && !agent.is_terminal_agent())
+            .map(|agent| SendToTarget {
+                id: agent.id,
+                label: format!("{} ({})", agent.title, agent.short_id()),
+            })
+            .collect();
+        if targets.is_empty() {
+            return Some(ErrorModalMode::new("No other agents to send output to").into());
+        }
+
+        app_data.send_to.start(source_id, targets);
+        Some(SendToMode.into())
+    }
+
+    /// Send the source agent's output to the target chosen in the send-to picker.
+    ///
+    /// Sends the text last selected with the mouse in the source's preview when there is one,
+    /// otherwise the source's recent output. The output is written to `.tenex/<id>.md` in the
+    /// target's worktree and the target is told to read it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the output cannot be captured, written, or sent.
+    pub fn send_output_to_target(self, app_data: &mut AppData) -> Result<AppMode> {
+        let source = app_data
+            .send_to
+            .source
+            .and_then(|id| app_data.storage.get(id));
+        let target = app_data
+            .send_to
+            .selected_target()
+            .and_then(|target| app_data.storage.get(target.id));
+        let (Some(source), Some(target)) = (source, target) else {
+            app_data.send_to.clear();
+            return Ok(ErrorModalMode::new(TenexError::AgentMissing).into());
+        };
+
+        let selection = app_data
+            .ui
+            .last_preview_selection
+            .as_ref()
+            .filter(|(agent_id, _)| *agent_id == source.id)
+            .map(|(_, text)| text.clone());
+        let (kind, output) = match selection {
+            Some(text) => ("selected output", text),
+            None => (
+                "recent output",
+                self.output_capture
+                    .tail(&mux_target(source), SEND_TO_TAIL_LINES)?
+                    .join("\n"),
+            ),
+        };
+        if output.trim().is_empty() {
+            let message = format!("{} has no output to send", source.title);
+            app_data.send_to.clear();
+            return Ok(ErrorModalMode::new(message).into());
+        }
+
+        let message_id = uuid::Uuid::new_v4();
+        Self::write_synthesis_file(
+            &target.worktree_path,
+            message_id,
+            &shared_output_contents(source, &output),
+        )?;
+        let message = format!(
+            "Read .tenex/{message_id}.md - it contains {kind} from the agent \"{}\", shared with you by the user.",
+            source.title
+        );
+        self.session_manager.send_keys_and_submit_for_agent(
+            &mux_target(target),
+            target,
+            &message,
+        )?;
+
+        info!(source = %source.id, target = %target.id, kind, "Sent agent output");
+        let status = format!("Sent {kind} from {} to {}", source.title, target.title);
+        if kind == "selected output" {
+            app_data.ui.last_preview_selection = None;
+        }
+        app_data.send_to.clear();
+        app_data.set_status(status);
+        Ok(AppMode::normal())
+    }
+}
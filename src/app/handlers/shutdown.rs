@@ -0,0 +1,107 @@
+//! Graceful shutdown: ask agent programs to quit before their windows are killed.
+
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use tracing::{debug, info};
+use uuid::Uuid;
+
+use super::Actions;
+use crate::agent::Agent;
+use crate::app::AppData;
+use crate::app::state::BackgroundCompletion;
+use crate::conversation::detect_agent_cli;
+use crate::mux::SessionManager;
+use crate::state::AppMode;
+
+/// How long agent programs get to exit on their own before their windows are killed.
+const SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(3);
+
+/// How often to check whether the programs have exited.
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+impl Actions {
+    /// Send each of `agent_ids` its program's quit sequence, then run `then` once the programs
+    /// have exited or a few seconds have passed.
+    ///
+    /// Agent CLIs that Tenex knows get their exit command, so they can save their transcripts
+    /// and tool state; other programs get Ctrl+C and Ctrl+D. Windows that are already gone are
+    /// skipped, and `then` kills whatever is still running. The wait runs in the background,
+    /// shown on `owner_id`; when no program was asked to quit, `then` runs right away and its
+    /// mode is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `then` when it runs right away.
+    pub(super) fn shut_down_agents(
+        self,
+        app_data: &mut AppData,
+        owner_id: Option<Uuid>,
+        agent_ids: &[Uuid],
+        then: BackgroundCompletion,
+    ) -> Result<AppMode> {
+        let running = self.send_quit_sequences(app_data, agent_ids);
+        if running.is_empty() {
+            return then(app_data);
+        }
+        info!(count = running.len(), "Waiting for agents to exit");
+
+        let session_manager = self.session_manager;
+        let label = format!("Waiting for agents to exit ({})", running.len());
+        app_data.background_ops.spawn(owner_id, label, move || {
+            let running = wait_for_exit(session_manager, running);
+            if !running.is_empty() {
+                debug!(?running, "Agents did not exit in time; killing them");
+            }
+            then
+        });
+        Ok(AppMode::normal())
+    }
+
+    /// Send each of `agent_ids` its program's quit sequence without waiting for it to exit,
+    /// returning the mux targets it was sent to.
+    pub(super) fn send_quit_sequences(self, app_data: &AppData, agent_ids: &[Uuid]) -> Vec<String> {
+        let mut running: Vec<String> = Vec::new();
+        for agent_id in agent_ids {
+            let Some(agent) = app_data.storage.get(*agent_id) else {
+                continue;
+            };
+            let target = app_data.storage.mux_target(agent);
+            if !self.session_manager.window_alive(&target).unwrap_or(false) {
+                continue;
+            }
+
+            if let Err(err) = self.send_quit_sequence(&target, agent) {
+                debug!(target, error = %err, "Failed to send quit sequence");
+                continue;
+            }
+            running.push(target);
+        }
+        running
+    }
+
+    fn send_quit_sequence(self, target: &str, agent: &Agent) -> Result<()> {
+        let quit_command = detect_agent_cli(&agent.program).quit_command();
+        match quit_command {
+            Some(command) if !agent.is_terminal_agent() => self
+                .session_manager
+                .send_keys_and_submit_for_agent(target, agent, command),
+            _ => self
+                .session_manager
+                .send_keys_batch(target, &[String::from("\u{3}"), String::from("\u{4}")]),
+        }
+    }
+}
+
+/// Wait until the windows of `running` are gone or the grace period passes, returning the ones
+/// still open.
+fn wait_for_exit(session_manager: SessionManager, mut running: Vec<String>) -> Vec<String> {
+    let deadline = Instant::now() + SHUTDOWN_GRACE_PERIOD;
+    loop {
+        running.retain(|target| session_manager.window_alive(target).unwrap_or(false));
+        if running.is_empty() || Instant::now() >= deadline {
+            return running;
+        }
+        std::thread::sleep(SHUTDOWN_POLL_INTERVAL);
+    }
+}
@@ -1,6 +1,8 @@
 //! Swarm operations: spawn children, spawn review agents, synthesize
 
 use crate::agent::{Agent, AgentRuntime, ChildConfig, Storage, WorkspaceKind};
+use crate::config::BranchKind;
+use crate::error::TenexError;
 use crate::git::{self, WorktreeManager};
 use crate::mux::SessionManager;
 use crate::prompts;
@@ -12,7 +14,10 @@ use std::time::{Duration, Instant};
 use tracing::{debug, info, warn};
 
 use super::Actions;
-use crate::app::{AppData, WorktreeConflictInfo};
+use super::synthesis::can_summarize;
+use crate::app::data::SynthesisTargets;
+use crate::app::state::BackgroundCompletion;
+use crate::app::{AgentRole, AppData, SynthesisOptions, SynthesisStrategy, WorktreeConflictInfo};
 use crate::state::{AppMode, ConfirmAction, ConfirmingMode, ErrorModalMode};
 
 /// Configuration for spawning child agents
@@ -25,9 +30,32 @@ pub struct SpawnConfig {
     pub parent_agent_id: uuid::Uuid,
 }
 
-struct NewRootSpawnConfig {
-    config: SpawnConfig,
-    cleaned_stale_worktree: bool,
+/// Child agents requested from a prompt, with the options pending when it was submitted.
+struct ChildRequest {
+    count: usize,
+    task: Option<String>,
+    use_plan_prompt: bool,
+    context_files: Vec<PathBuf>,
+}
+
+impl ChildRequest {
+    /// Take the pending options for `count` children started with `task`.
+    fn take(app_data: &mut AppData, count: usize, task: Option<&str>) -> Self {
+        Self {
+            count,
+            task: task.map(str::to_string),
+            use_plan_prompt: app_data.spawn.use_plan_prompt,
+            context_files: std::mem::take(&mut app_data.spawn.context_files),
+        }
+    }
+
+    const fn role(&self) -> AgentRole {
+        if self.use_plan_prompt {
+            AgentRole::Planner
+        } else {
+            AgentRole::Default
+        }
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -41,6 +69,7 @@ struct ReviewChildAgentConfig<'a> {
     program: &'a str,
     review_prompt: &'a str,
     reviewer_number: usize,
+    focus: Option<&'a str>,
     reserved_window_index: u32,
 }
 
@@ -87,7 +116,10 @@ impl Actions {
             .unwrap_or(current_dir)
     }
 
-    fn build_synthesis_read_command(synthesis_id: uuid::Uuid, prompt: Option<&str>) -> String {
+    pub(super) fn build_synthesis_read_command(
+        synthesis_id: uuid::Uuid,
+        prompt: Option<&str>,
+    ) -> String {
         let mut read_command = format!(
             "Read .tenex/{synthesis_id}.md - it contains the collected descendant work. Use it to guide your next steps."
         );
@@ -102,7 +134,7 @@ impl Actions {
         read_command
     }
 
-    fn write_synthesis_file(
+    pub(super) fn write_synthesis_file(
         worktree_path: &Path,
         synthesis_id: uuid::Uuid,
         synthesis_content: &str,
@@ -116,10 +148,29 @@ impl Actions {
             .context(format!("Failed to create {}", synthesis_file.display()))?;
         write_synthesis_contents(&mut file, synthesis_content)
             .context(format!("Failed to write to {}", synthesis_file.display()))?;
+        super::synthesis::compress_closed_synthesis_files(&tenex_dir, synthesis_id);
 
         Ok(synthesis_file)
     }
 
+    /// Write the patch of `worktree_path` against `base_branch` to
+    /// `.tenex/review/<id>/diff.patch`, so reviewers start from the change set.
+    fn write_review_packet(worktree_path: &Path, base_branch: &str) -> Result<PathBuf> {
+        let patch = git::patch_against_base(worktree_path, base_branch)?;
+        let packet_dir = worktree_path
+            .join(".tenex")
+            .join("review")
+            .join(uuid::Uuid::new_v4().to_string());
+        fs::create_dir_all(&packet_dir)
+            .context(format!("Failed to create {}", packet_dir.display()))?;
+
+        let patch_path = packet_dir.join("diff.patch");
+        fs::write(&patch_path, patch)
+            .context(format!("Failed to write {}", patch_path.display()))?;
+        debug!(?patch_path, "Wrote review packet");
+        Ok(patch_path)
+    }
+
     fn start_codex_review_flow(self, target: &str, base_branch: &str) -> Result<CodexReviewStart> {
         let base_branch = base_branch.trim();
         if base_branch.is_empty() {
@@ -281,7 +332,10 @@ impl Actions {
         app_data: &mut AppData,
         config: ReviewChildAgentConfig<'_>,
     ) -> Result<Agent> {
-        let child_title = format!("Reviewer {}", config.reviewer_number);
+        let child_title = config.focus.map_or_else(
+            || format!("Reviewer {}", config.reviewer_number),
+            |focus| format!("Reviewer {} ({focus})", config.reviewer_number),
+        );
         let mut child = Agent::new_child(
             child_title.clone(),
             config.program.to_string(),
@@ -299,6 +353,7 @@ impl Actions {
         );
         child.workspace_kind = config.workspace_kind;
         child.runtime = config.runtime;
+        child.sandbox = app_data.settings.sandbox_for(AgentRole::Review);
         child.runtime_scope = app_data
             .storage
             .root_ancestor(config.parent_id)
@@ -321,6 +376,9 @@ impl Actions {
 
     /// Spawn child agents under a parent (or create new root with children)
     ///
+    /// A new root's worktree is created in the background; the root and its children start
+    /// once it is ready.
+    ///
     /// # Errors
     ///
     /// Returns an error if spawning fails
@@ -335,29 +393,54 @@ impl Actions {
             "Spawning child agents"
         );
 
-        let (spawn_config, cleaned_stale_worktree) = if let Some(pid) = parent_id {
-            (Self::get_existing_parent_config(app_data, pid)?, false)
-        } else {
-            match self.create_new_root_for_swarm(app_data, task, count)? {
-                Some(new_root) => (new_root.config, new_root.cleaned_stale_worktree),
-                None => {
-                    return Ok(ConfirmingMode {
-                        action: ConfirmAction::WorktreeConflict,
-                    }
-                    .into());
-                }
-            }
+        let Some(pid) = parent_id else {
+            let cwd_fallback = PathBuf::from(".");
+            let repo_path = Self::resolve_swarm_repo_path(
+                app_data,
+                std::env::current_dir().unwrap_or(cwd_fallback),
+            );
+            return app_data.with_repo_config(&repo_path.clone(), |app_data| {
+                self.spawn_children_under_new_root(app_data, task, count, repo_path)
+            });
         };
+        let spawn_config = Self::get_existing_parent_config(app_data, pid)?;
+        let request = ChildRequest::take(app_data, count, task);
+        let repo_root = Self::swarm_repo_root(app_data, pid);
+        app_data.with_repo_config(&repo_root, |app_data| {
+            self.finish_spawning_children(app_data, &spawn_config, &request, false)
+        })
+    }
 
-        self.spawn_child_agents(app_data, &spawn_config, count, task)?;
+    /// The repository the swarm of agent `id` works in, whose config its new agents get.
+    fn swarm_repo_root(app_data: &AppData, id: uuid::Uuid) -> PathBuf {
+        app_data.storage.root_ancestor(id).map_or_else(
+            || PathBuf::from("."),
+            |root| {
+                root.repo_root
+                    .clone()
+                    .unwrap_or_else(|| root.worktree_path.clone())
+            },
+        )
+    }
+
+    /// Start `request`'s children under `config`'s parent, then save and report them.
+    fn finish_spawning_children(
+        self,
+        app_data: &mut AppData,
+        config: &SpawnConfig,
+        request: &ChildRequest,
+        cleaned_stale_worktree: bool,
+    ) -> Result<AppMode> {
+        self.spawn_child_agents(app_data, config, request)?;
 
         // Expand the parent to show children
         app_data
             .storage
-            .set_collapsed(spawn_config.parent_agent_id, false);
+            .set_collapsed(config.parent_agent_id, false);
 
         app_data.storage.save()?;
-        info!(count, parent_id = %spawn_config.parent_agent_id, "Child agents spawned successfully");
+        let count = request.count;
+        info!(count, parent_id = %config.parent_agent_id, "Child agents spawned successfully");
         if cleaned_stale_worktree {
             app_data.set_status(format!(
                 "Cleaned stale worktree and spawned {count} child agents"
@@ -389,27 +472,26 @@ impl Actions {
         })
     }
 
-    /// Create a new root agent for a swarm, returns None if worktree conflict
-    fn create_new_root_for_swarm(
+    /// Create a new root agent for a swarm and spawn `count` children under it, or ask what to
+    /// do when its worktree already exists.
+    fn spawn_children_under_new_root(
         self,
         app_data: &mut AppData,
         task: Option<&str>,
         count: usize,
-    ) -> Result<Option<NewRootSpawnConfig>> {
+        repo_path: PathBuf,
+    ) -> Result<AppMode> {
         let root_title = Self::generate_root_title(task);
-        let cwd_fallback = PathBuf::from(".");
-        let repo_path = Self::resolve_swarm_repo_path(
-            app_data,
-            std::env::current_dir().unwrap_or(cwd_fallback),
-        );
-        let Ok(repo) = git::open_repository(&repo_path) else {
+        if git::open_repository(&repo_path).is_err() {
             let config = self.create_plain_dir_root_for_swarm(app_data, root_title, repo_path)?;
-            return Ok(Some(NewRootSpawnConfig {
-                config,
-                cleaned_stale_worktree: false,
-            }));
-        };
-        let branch = app_data.config.generate_branch_name(&root_title);
+            let request = ChildRequest::take(app_data, count, task);
+            return self.finish_spawning_children(app_data, &config, &request, false);
+        }
+        let repo_path = Self::worktree_source_repo(app_data, repo_path);
+        let repo = git::open_repository(&repo_path)?;
+        let branch = app_data
+            .config
+            .generate_branch_name_for(BranchKind::Swarm, &root_title);
 
         let worktree_mgr = WorktreeManager::new(&repo);
 
@@ -433,39 +515,76 @@ impl Actions {
                 count,
                 repo_path,
             );
-            return Ok(None);
+            return Ok(ConfirmingMode {
+                action: ConfirmAction::WorktreeConflict,
+            }
+            .into());
         }
 
         let runtime = crate::runtime::new_root_runtime(&app_data.settings);
-        let worktree_options = Self::root_worktree_create_options(runtime);
-        worktree_mgr.create_with_new_branch_with_options(
-            &worktree_path,
-            &branch,
-            worktree_options,
-        )?;
-
-        let program = app_data.agent_spawn_command();
-        let mut root_agent = Agent::new(root_title, program, branch.clone(), worktree_path.clone());
-        root_agent.repo_root = Some(repo_path);
+        // Before the worktree exists, so a missing program leaves nothing behind.
+        let program = app_data.resolve_agent_program(app_data.agent_spawn_command(), runtime)?;
+        let options = Self::root_worktree_create_options(runtime);
+        let mut root_agent = Agent::new(root_title, program, branch.clone(), worktree_path);
+        root_agent.repo_root = Some(repo_path.clone());
         root_agent.runtime = runtime;
+        root_agent.base_branch = worktree_mgr.head_branch();
+        let request = ChildRequest::take(app_data, count, task);
+        let cleaned_stale_worktree = target_preparation.cleaned_stale_target();
+        let config_dir = repo_path.clone();
+
+        app_data.background_ops.spawn_cancellable(
+            Some(root_agent.id),
+            format!("Creating worktree for {branch}"),
+            move |cancelled| {
+                let created = super::agent_lifecycle::create_root_worktree(
+                    &repo_path,
+                    &root_agent,
+                    options,
+                    cancelled,
+                );
+                Box::new(move |app_data: &mut AppData| {
+                    created?;
+                    app_data.with_repo_config(&config_dir, |app_data| {
+                        let actions = Self::new();
+                        let config = actions.launch_swarm_root(app_data, root_agent)?;
+                        actions.finish_spawning_children(
+                            app_data,
+                            &config,
+                            &request,
+                            cleaned_stale_worktree,
+                        )
+                    })
+                })
+            },
+        );
+        Ok(AppMode::normal())
+    }
 
+    /// Launch a new swarm root in the worktree created for it and start tracking it.
+    fn launch_swarm_root(
+        self,
+        app_data: &mut AppData,
+        mut root_agent: Agent,
+    ) -> Result<SpawnConfig> {
         self.launch_root_agent(app_data, &mut root_agent, None)?;
 
-        let root_session = root_agent.mux_session.clone();
-        let root_id = root_agent.id;
-
+        let config = SpawnConfig {
+            root_session: root_agent.mux_session.clone(),
+            worktree_path: root_agent.worktree_path.clone(),
+            branch: root_agent.branch.clone(),
+            workspace_kind: WorkspaceKind::GitWorktree,
+            runtime: root_agent.runtime,
+            parent_agent_id: root_agent.id,
+        };
         app_data.storage.add(root_agent);
-        Ok(Some(NewRootSpawnConfig {
-            config: SpawnConfig {
-                root_session,
-                worktree_path,
-                branch,
-                workspace_kind: WorkspaceKind::GitWorktree,
-                runtime,
-                parent_agent_id: root_id,
-            },
-            cleaned_stale_worktree: target_preparation.cleaned_stale_target(),
-        }))
+        Self::start_worktree_setup(
+            app_data,
+            config.parent_agent_id,
+            &config.worktree_path,
+            &config.branch,
+        );
+        Ok(config)
     }
 
     fn create_plain_dir_root_for_swarm(
@@ -475,7 +594,9 @@ impl Actions {
         workdir: PathBuf,
     ) -> Result<SpawnConfig> {
         let program = app_data.agent_spawn_command();
-        let branch = app_data.config.generate_branch_name(&root_title);
+        let branch = app_data
+            .config
+            .generate_branch_name_for(BranchKind::Swarm, &root_title);
         let runtime = crate::runtime::new_root_runtime(&app_data.settings);
         let mut root_agent = Agent::new(root_title, program, branch.clone(), workdir.clone());
         root_agent.workspace_kind = WorkspaceKind::PlainDir;
@@ -554,20 +675,33 @@ impl Actions {
         self,
         app_data: &mut AppData,
         config: &SpawnConfig,
-        count: usize,
-        task: Option<&str>,
+        request: &ChildRequest,
     ) -> Result<()> {
         let start_window_index = app_data
             .storage
             .reserve_window_indices(config.parent_agent_id);
-        let program = if app_data.spawn.use_plan_prompt {
-            app_data.planner_agent_spawn_command()
-        } else {
-            app_data.agent_spawn_command()
+        if let Err(err) = super::scratchpad::ensure_scratchpad(&config.worktree_path) {
+            warn!(error = %err, "Failed to create shared scratchpad");
+        }
+        let child_prompt = request
+            .task
+            .as_deref()
+            .map(|t| Self::build_child_prompt(t, request.use_plan_prompt));
+        let child_prompt = Self::copy_context_files(
+            &request.context_files,
+            &config.worktree_path,
+            child_prompt.as_deref(),
+        )?
+        .map(|prompt| prompts::build_scratchpad_prompt(&prompt));
+        let scope = app_data
+            .storage
+            .get(config.parent_agent_id)
+            .and_then(|parent| parent.scope.clone());
+        let child_prompt = match (child_prompt, &scope) {
+            (Some(prompt), Some(scope)) => Some(prompts::build_scope_prompt(&prompt, scope)),
+            (prompt, _) => prompt,
         };
-        let child_prompt =
-            task.map(|t| Self::build_child_prompt(t, app_data.spawn.use_plan_prompt));
-        let child_title_prefix = if app_data.spawn.use_plan_prompt && child_prompt.is_some() {
+        let child_title_prefix = if request.use_plan_prompt && child_prompt.is_some() {
             "Planner"
         } else {
             "Agent"
@@ -578,7 +712,7 @@ impl Actions {
             child_title_prefix,
         );
 
-        for i in 0..count {
+        for i in 0..request.count {
             let window_index = start_window_index + u32::try_from(i).unwrap_or(0);
             let child_number = start_child_number.saturating_add(i);
             let child_title = format!("{child_title_prefix} {child_number}");
@@ -586,7 +720,7 @@ impl Actions {
                 app_data,
                 config,
                 window_index,
-                &program,
+                request.role(),
                 child_prompt.as_deref(),
                 &child_title,
             )?;
@@ -610,13 +744,18 @@ impl Actions {
         app_data: &mut AppData,
         config: &SpawnConfig,
         window_index: u32,
-        program: &str,
+        role: AgentRole,
         child_prompt: Option<&str>,
         child_title: &str,
     ) -> Result<()> {
+        let program = if role == AgentRole::Planner {
+            app_data.planner_agent_spawn_command()
+        } else {
+            app_data.agent_spawn_command()
+        };
         let mut child = Agent::new_child(
             child_title.to_string(),
-            program.to_string(),
+            program,
             config.branch.clone(),
             config.worktree_path.clone(),
             ChildConfig {
@@ -631,6 +770,11 @@ impl Actions {
         );
         child.workspace_kind = config.workspace_kind;
         child.runtime = config.runtime;
+        child.scope = app_data
+            .storage
+            .get(config.parent_agent_id)
+            .and_then(|parent| parent.scope.clone());
+        child.sandbox = app_data.settings.sandbox_for(role);
         child.runtime_scope = app_data
             .storage
             .root_ancestor(config.parent_agent_id)
@@ -657,7 +801,8 @@ impl Actions {
         count: usize,
         task: &str,
     ) -> Result<()> {
-        self.spawn_child_agents(app_data, config, count, Some(task))?;
+        let request = ChildRequest::take(app_data, count, Some(task));
+        self.spawn_child_agents(app_data, config, &request)?;
 
         // Expand the parent to show children
         app_data
@@ -706,11 +851,28 @@ impl Actions {
         }
 
         // Build the review prompt
-        let review_prompt = prompts::build_review_prompt(&base_branch);
+        if let Err(err) = super::scratchpad::ensure_scratchpad(&worktree_path) {
+            warn!(error = %err, "Failed to create shared scratchpad");
+        }
+        let mut review_prompt = prompts::build_review_prompt(&base_branch);
+        match Self::write_review_packet(&worktree_path, &base_branch) {
+            Ok(patch_path) => {
+                let relative = patch_path
+                    .strip_prefix(&worktree_path)
+                    .unwrap_or(&patch_path);
+                review_prompt = prompts::build_review_packet_prompt(
+                    &review_prompt,
+                    &relative.display().to_string(),
+                );
+            }
+            Err(err) => warn!(base_branch, error = %err, "Failed to write review packet"),
+        }
 
         // Reserve window indices
         let start_window_index = app_data.storage.reserve_window_indices(parent_id);
-        let program = app_data.review_agent_spawn_command();
+        let repo_root = Self::swarm_repo_root(app_data, parent_id);
+        let program =
+            app_data.with_repo_config(&repo_root, |app_data| app_data.review_agent_spawn_command());
         let mut codex_review_flows: Vec<(String, String)> = Vec::new();
         let start_reviewer_number = next_child_number(&app_data.storage, parent_id, "Reviewer");
 
@@ -719,6 +881,17 @@ impl Actions {
             let offset = u32::try_from(i).map_or(u32::MAX, |value| value);
             let window_index = start_window_index.saturating_add(offset);
             let reviewer_number = start_reviewer_number.saturating_add(i);
+            let focus = app_data.review.focus_for(i).cloned();
+            let reviewer_prompt = prompts::build_scratchpad_prompt(&focus.as_ref().map_or_else(
+                || review_prompt.clone(),
+                |focus| {
+                    prompts::build_review_focus_prompt(
+                        &review_prompt,
+                        &focus.name,
+                        &focus.instructions,
+                    )
+                },
+            ));
             let config = ReviewChildAgentConfig {
                 root_session: root_session.as_str(),
                 worktree_path: worktree_path.as_path(),
@@ -727,12 +900,17 @@ impl Actions {
                 runtime: root_runtime,
                 parent_id,
                 program: program.as_str(),
-                review_prompt: review_prompt.as_str(),
+                review_prompt: reviewer_prompt.as_str(),
                 reviewer_number,
+                focus: focus.as_ref().map(|focus| focus.name.as_str()),
                 reserved_window_index: window_index,
             };
-            let child = self.spawn_review_child_agent(app_data, config)?;
-            codex_review_flows.extend(Self::codex_review_flow_for_child(&child, &base_branch));
+            let mut child = self.spawn_review_child_agent(app_data, config)?;
+            if let Some(flow) = Self::codex_review_flow_for_child(&child, &base_branch) {
+                // Codex reviewers run the built-in `/review` flow instead of the review prompt.
+                child.prompt = Some(format!("/review against {base_branch}"));
+                codex_review_flows.push(flow);
+            }
             app_data.storage.add(child);
         }
 
@@ -760,7 +938,7 @@ impl Actions {
         Ok(())
     }
 
-    /// Synthesize children into the parent agent
+    /// Synthesize children into the parent agent, using the default synthesis strategy
     ///
     /// Writes synthesis content to `.tenex/<id>.md` and tells the parent to read it.
     ///
@@ -768,12 +946,20 @@ impl Actions {
     ///
     /// Returns an error if synthesis fails
     pub fn synthesize(self, app_data: &mut AppData) -> Result<AppMode> {
-        self.synthesize_with_prompt(app_data, None)
+        let options = SynthesisOptions {
+            strategy: app_data.settings.synthesis_strategy,
+            keep_children: false,
+        };
+        self.synthesize_with_prompt(app_data, options, None)
     }
 
-    /// Synthesize children into the parent agent with optional extra instructions.
+    /// Synthesize children into the parent agent with `options` and optional extra
+    /// instructions.
     ///
-    /// Writes synthesis content to `.tenex/<id>.md` and tells the parent to read it.
+    /// Writes synthesis content to `.tenex/<id>.md` and tells the parent to read it. The
+    /// synthesized agents are terminated unless `options` keeps them. When
+    /// handoffs are enabled, the children first write their handoff notes (which become part
+    /// of the synthesis) and the synthesis happens in the background once they are done.
     ///
     /// # Errors
     ///
@@ -781,154 +967,174 @@ impl Actions {
     pub fn synthesize_with_prompt(
         self,
         app_data: &mut AppData,
+        options: SynthesisOptions,
         prompt: Option<&str>,
     ) -> Result<AppMode> {
-        let Some(agent) = app_data.selected_agent() else {
-            return Ok(ErrorModalMode {
-                message: "No agent selected".to_string(),
+        if let Some(parent) = app_data.selected_agent()
+            && !parent.is_terminal_agent()
+        {
+            let parent_id = parent.id;
+            let targets = app_data.synthesis_targets_for(parent_id);
+            let prompt = prompt.map(str::to_string);
+            let then: BackgroundCompletion = Box::new(move |app_data: &mut AppData| {
+                app_data.select_agent_by_id(parent_id);
+                if app_data.selected_agent().map(|agent| agent.id) != Some(parent_id) {
+                    bail!("The agent to synthesize into no longer exists");
+                }
+                Self::new().synthesize_now(app_data, options, prompt.as_deref())
+            });
+            if self.request_handoffs(app_data, parent_id, &targets.capture_agent_ids, then) {
+                app_data.set_status("Waiting for handoff notes before synthesizing");
+                return Ok(AppMode::normal());
             }
+        }
+        self.synthesize_now(app_data, options, prompt)
+    }
+
+    fn synthesize_now(
+        self,
+        app_data: &mut AppData,
+        options: SynthesisOptions,
+        prompt: Option<&str>,
+    ) -> Result<AppMode> {
+        let strategy = options.strategy;
+        let Some(agent) = app_data.selected_agent() else {
+            return Ok(ErrorModalMode::new(TenexError::NoAgentSelected {
+                action: "synthesize its children",
+            })
             .into());
         };
 
         if agent.is_terminal_agent() {
-            return Ok(ErrorModalMode {
-                message: "Cannot synthesize into a terminal agent".to_string(),
-            }
-            .into());
+            return Ok(ErrorModalMode::new("Cannot synthesize into a terminal agent").into());
         }
 
-        if !app_data.storage.has_children(agent.id) {
+        if !app_data.storage.has_children(agent.id)
+            && app_data.storage.linked_roots(agent.id).is_empty()
+        {
             warn!(agent_id = %agent.id, title = %agent.title, "No children to synthesize");
-            return Ok(ErrorModalMode {
-                message: "Selected agent has no children to synthesize".to_string(),
-            }
-            .into());
+            return Ok(ErrorModalMode::new("Selected agent has no children to synthesize").into());
+        }
+
+        if strategy == SynthesisStrategy::Summaries && !can_summarize(&agent.program) {
+            return Ok(ErrorModalMode::new("Summaries need a Claude or Codex parent agent").into());
         }
 
         let parent_id = agent.id;
-        let parent_agent = agent.clone();
-        let parent_session = agent.mux_session.clone();
         let parent_title = agent.title.clone();
-        let worktree_path = agent.worktree_path.clone();
-        // Determine the correct target for the parent
-        // If the parent has a window_index, it's a child agent running in a window
-        let parent_target = agent.window_index.map_or_else(
-            || parent_session.clone(),
-            |window_idx| SessionManager::window_target(&parent_session, window_idx),
-        );
 
-        info!(%parent_id, %parent_title, "Synthesizing descendants into parent");
+        info!(%parent_id, %parent_title, strategy = strategy.label(), "Synthesizing descendants into parent");
 
         let targets = app_data.synthesis_targets_for(parent_id);
 
         if targets.capture_agent_ids.is_empty() {
             warn!(agent_id = %parent_id, title = %parent_title, "No non-terminal children to synthesize");
-            return Ok(ErrorModalMode {
-                message: "Selected agent has no non-terminal children to synthesize".to_string(),
-            }
+            return Ok(ErrorModalMode::new(
+                "Selected agent has no non-terminal children to synthesize",
+            )
             .into());
         }
 
         let findings =
-            self.capture_synthesis_findings(app_data, &parent_session, &targets.capture_agent_ids);
+            self.capture_synthesis_findings(app_data, &targets.capture_agent_ids, strategy);
+
+        if strategy == SynthesisStrategy::Summaries {
+            Self::summarize_then_deliver(app_data, parent_id, findings, targets, options, prompt);
+            app_data.set_status("Summarizing agents before synthesizing");
+            return Ok(AppMode::normal());
+        }
+
+        self.deliver_synthesis(app_data, parent_id, &findings, &targets, options, prompt)
+    }
+
+    /// Write `findings` to the parent's synthesis file, tear down the synthesized agents unless
+    /// `options` keeps them, and tell the parent to read the file.
+    pub(super) fn deliver_synthesis(
+        self,
+        app_data: &mut AppData,
+        parent_id: uuid::Uuid,
+        findings: &[(String, String)],
+        targets: &SynthesisTargets,
+        options: SynthesisOptions,
+        prompt: Option<&str>,
+    ) -> Result<AppMode> {
+        let Some(parent_agent) = app_data.storage.get(parent_id).cloned() else {
+            bail!("The agent to synthesize into no longer exists");
+        };
+        let parent_session = parent_agent.mux_session.clone();
+        let parent_target = app_data.storage.mux_target(&parent_agent);
 
         // Build synthesis content
-        let synthesis_content = prompts::build_synthesis_prompt(&findings);
+        let synthesis_content = prompts::build_synthesis_prompt(findings);
 
         let synthesis_id = uuid::Uuid::new_v4();
-        let synthesis_file =
-            Self::write_synthesis_file(&worktree_path, synthesis_id, &synthesis_content)?;
+        let synthesis_file = Self::write_synthesis_file(
+            &parent_agent.worktree_path,
+            synthesis_id,
+            &synthesis_content,
+        )?;
 
         debug!(?synthesis_file, "Wrote synthesis file");
 
-        let root_id = app_data
-            .storage
-            .root_ancestor(parent_id)
-            .map_or(parent_id, |root| root.id);
         let descendants_count = targets.capture_agent_ids.len();
-        self.remove_synthesis_targets(
-            app_data,
-            root_id,
-            &parent_session,
-            &targets.teardown_root_ids,
-            &targets.teardown_agent_ids,
-        );
+        if !options.keep_children {
+            self.remove_synthesis_targets(
+                app_data,
+                parent_id,
+                &parent_session,
+                &targets.teardown_root_ids,
+                &targets.teardown_agent_ids,
+            )?;
+        }
 
         // Now tell the parent to read the file
         let read_command = Self::build_synthesis_read_command(synthesis_id, prompt);
-        let session_manager = &self.session_manager;
-        let target = &parent_target;
-        let agent = &parent_agent;
-        let command = &read_command;
-        session_manager.send_keys_and_submit_for_agent(target, agent, command)?;
+        self.session_manager.send_keys_and_submit_for_agent(
+            &parent_target,
+            &parent_agent,
+            &read_command,
+        )?;
 
         app_data.validate_selection();
         app_data.storage.save()?;
         app_data.clear_synthesis_marks();
-        info!(%parent_title, descendants_count, "Synthesis complete");
+        info!(parent_title = %parent_agent.title, descendants_count, "Synthesis complete");
         app_data.set_status("Synthesized findings into parent agent");
         Ok(AppMode::normal())
     }
 
-    fn capture_synthesis_findings(
-        self,
-        app_data: &AppData,
-        parent_session: &str,
-        capture_agent_ids: &[uuid::Uuid],
-    ) -> Vec<(String, String)> {
-        let mut findings = Vec::new();
-
-        for agent_id in capture_agent_ids {
-            let Some(descendant) = app_data.storage.get(*agent_id) else {
-                continue;
-            };
-            let target = descendant.window_index.map_or_else(
-                || descendant.mux_session.clone(),
-                |window_idx| SessionManager::window_target(parent_session, window_idx),
-            );
-
-            let output = self
-                .output_capture
-                .capture_pane_with_history(&target, 5000)
-                .unwrap_or_else(|_| "(Could not capture output)".to_string());
-
-            findings.push((descendant.title.clone(), output));
-        }
-
-        findings
-    }
-
+    /// Ask the synthesized agents to quit and forget them; their windows are killed once they
+    /// have exited.
     fn remove_synthesis_targets(
         self,
         app_data: &mut AppData,
-        root_id: uuid::Uuid,
+        parent_id: uuid::Uuid,
         parent_session: &str,
         teardown_root_ids: &[uuid::Uuid],
         teardown_agent_ids: &[uuid::Uuid],
-    ) {
+    ) -> Result<()> {
         let mut deleted_indices: Vec<u32> = teardown_agent_ids
             .iter()
             .filter_map(|agent_id| app_data.storage.get(*agent_id)?.window_index)
             .collect();
-        deleted_indices.sort_unstable_by(|a, b| b.cmp(a));
+        deleted_indices.sort_unstable();
         deleted_indices.dedup();
 
-        for idx in &deleted_indices {
-            if let Err(e) = self.session_manager.kill_window(parent_session, *idx) {
-                warn!(session = %parent_session, window_index = *idx, error = %e, "{}", SYNTHESIS_KILL_WINDOW_WARN);
+        let parent_session = parent_session.to_string();
+        let kill_windows: BackgroundCompletion = Box::new(move |_: &mut AppData| {
+            for idx in &deleted_indices {
+                if let Err(e) = self.session_manager.kill_window(&parent_session, *idx) {
+                    warn!(session = %parent_session, window_index = *idx, error = %e, "{}", SYNTHESIS_KILL_WINDOW_WARN);
+                }
             }
-        }
-
-        super::window::adjust_window_indices_after_deletions(
-            app_data,
-            root_id,
-            teardown_root_ids,
-            &deleted_indices,
-        );
+            Ok(AppMode::normal())
+        });
+        self.shut_down_agents(app_data, Some(parent_id), teardown_agent_ids, kill_windows)?;
 
         for descendant_id in teardown_root_ids {
             app_data.storage.remove_with_descendants(*descendant_id);
         }
+        Ok(())
     }
 }
 
@@ -0,0 +1,74 @@
+//! Re-running and editing the swarm file an agent tree was created from (`tenex apply`).
+
+use std::path::PathBuf;
+
+use super::Actions;
+use crate::app::AppData;
+use crate::error::TenexError;
+use crate::state::{AppMode, ErrorModalMode};
+
+impl Actions {
+    /// Apply the selected agent tree's swarm file again, from the tree's repository, in a new
+    /// terminal under its root.
+    pub fn rerun_swarm_file(self, app_data: &mut AppData) -> AppMode {
+        let path = match selected_swarm_file(app_data) {
+            Ok(path) => path,
+            Err(mode) => return mode,
+        };
+        let repo_root = app_data
+            .selected_agent()
+            .and_then(|agent| app_data.storage.root_ancestor(agent.id))
+            .and_then(|root| root.repo_root.clone());
+        let exe = std::env::current_exe().map_or_else(
+            |_| env!("CARGO_PKG_NAME").to_string(),
+            |exe| exe.display().to_string(),
+        );
+        let path = path.display().to_string();
+        let mut command = shell_words::join([exe.as_str(), "apply", path.as_str()]);
+        if let Some(repo_root) = repo_root {
+            command = format!(
+                "cd {} && {command}",
+                shell_words::quote(&repo_root.display().to_string())
+            );
+        }
+        self.open_swarm_terminal(app_data, &command)
+    }
+
+    /// Open the selected agent tree's swarm file in `$VISUAL` or `$EDITOR`, in a new terminal
+    /// under its root.
+    pub fn edit_swarm_file(self, app_data: &mut AppData) -> AppMode {
+        let path = match selected_swarm_file(app_data) {
+            Ok(path) => path,
+            Err(mode) => return mode,
+        };
+        let editor = ["VISUAL", "EDITOR"]
+            .into_iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|editor| !editor.trim().is_empty())
+            .unwrap_or_else(|| "vi".to_string());
+        let command = format!(
+            "{editor} {}",
+            shell_words::quote(&path.display().to_string())
+        );
+        self.open_swarm_terminal(app_data, &command)
+    }
+
+    fn open_swarm_terminal(self, app_data: &mut AppData, command: &str) -> AppMode {
+        self.spawn_terminal(app_data, Some(command))
+            .unwrap_or_else(|err| {
+                ErrorModalMode::new(TenexError::wrap("Failed to open terminal", &err)).into()
+            })
+    }
+}
+
+/// The swarm file of the selected agent's root, or the error modal to show instead.
+fn selected_swarm_file(app_data: &mut AppData) -> Result<PathBuf, AppMode> {
+    app_data.input.clear();
+    let swarm_file = app_data
+        .selected_agent()
+        .and_then(|agent| app_data.storage.root_ancestor(agent.id))
+        .and_then(|root| root.swarm_file.clone());
+    swarm_file.ok_or_else(|| {
+        ErrorModalMode::new("The selected agent was not created by tenex apply.").into()
+    })
+}
@@ -0,0 +1,83 @@
+//! Switching the model or program a running agent uses (`M`).
+
+use anyhow::{Result, bail};
+use tracing::info;
+
+use super::Actions;
+use crate::agent::AgentRuntime;
+use crate::app::AppData;
+use crate::conversation::{self, AgentCli};
+use crate::error::TenexError;
+use crate::state::{AppMode, SwitchModelMode};
+
+impl Actions {
+    /// Start typing the program to relaunch the selected agent with, starting from its current
+    /// one.
+    ///
+    /// Returns `None` when no agent is selected or the selected agent is a terminal.
+    pub fn start_switch_model(app_data: &mut AppData) -> Option<AppMode> {
+        let agent = app_data.selected_agent()?;
+        if agent.is_terminal_agent() {
+            app_data.set_status("Terminals have no model to switch");
+            return None;
+        }
+        let program = agent.program.clone();
+        app_data.input.set(program);
+        Some(SwitchModelMode.into())
+    }
+
+    /// Relaunch the selected agent in the same window running `input` instead of its program.
+    ///
+    /// When the new program is the same CLI and the agent has a conversation, the new program
+    /// resumes it. Otherwise the agent first summarizes its work and the new program starts
+    /// from the summary, as with a compact.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `input` is empty or cannot be parsed, or the agent cannot be
+    /// relaunched.
+    pub fn switch_model(self, app_data: &mut AppData, input: &str) -> Result<AppMode> {
+        let program = input.trim().to_string();
+        if program.is_empty() {
+            bail!("Enter the program to relaunch the agent with");
+        }
+        conversation::build_spawn_argv(&program, None, None)?;
+        let Some(mut agent) = app_data.selected_agent().cloned() else {
+            return Ok(AppMode::normal());
+        };
+        if agent.runtime == AgentRuntime::Host && !crate::command::program_exists(&program) {
+            return Err(TenexError::ProgramNotFound {
+                program,
+                fallbacks: Vec::new(),
+            }
+            .into());
+        }
+        app_data.input.clear();
+        if agent.program == program {
+            app_data.set_status(format!("{} already runs {program}", agent.title));
+            return Ok(AppMode::normal());
+        }
+
+        let cli = conversation::detect_agent_cli(&program);
+        let resumable = cli != AgentCli::Other
+            && cli == conversation::detect_agent_cli(&agent.program)
+            && agent.conversation_id.is_some();
+        if !resumable {
+            self.restart_from_summary(app_data, agent.id, Some(program))?;
+            return Ok(AppMode::normal());
+        }
+
+        let previous_program = std::mem::replace(&mut agent.program, program.clone());
+        let title = agent.title.clone();
+        let report = move |app_data: &mut AppData, relaunched: Result<()>| {
+            relaunched?;
+            info!(%title, from = %previous_program, to = %program, "Agent program switched");
+            app_data.set_status(format!(
+                "Switched {title} from {previous_program} to {program}, resuming its conversation"
+            ));
+            Ok(())
+        };
+        self.relaunch_agent(app_data, agent, crate::runtime::AgentLaunch::Resume, report)?;
+        Ok(AppMode::normal())
+    }
+}
@@ -1,6 +1,7 @@
 //! Sync operations: agent status synchronization and auto-connect
 
 use crate::agent::{Agent, Status};
+use crate::config::Config;
 use crate::git::{self, WorktreeManager};
 use anyhow::{Context, Result};
 use std::collections::HashMap;
@@ -11,7 +12,11 @@ use std::path::Path;
 use tracing::{debug, info, warn};
 
 use super::Actions;
-use crate::app::{App, AppData, PaneActivityDigestMode};
+use crate::app::{
+    App, AppData, AutoConnectCandidate, AutoConnectPolicy, PaneActivity, PaneActivityDigestMode,
+    StatusRules,
+};
+use crate::state::{AppMode, AutoConnectPickerMode};
 
 impl Actions {
     /// Check and update agent statuses based on mux sessions
@@ -34,6 +39,65 @@ impl Actions {
         self.sync_agent_status_with_sessions(app, sessions)
     }
 
+    /// Sync every agent on the next tick instead of waiting for the background interval.
+    pub fn request_full_sync(app_data: &mut AppData) -> AppMode {
+        app_data.ui.full_sync_requested = true;
+        app_data.set_status("Refreshing every agent");
+        AppMode::normal()
+    }
+
+    /// Check the sessions of foreground agents only: those on screen, selected, starting, or
+    /// recently active.
+    ///
+    /// Each session is checked on its own instead of listing every session. If one is gone,
+    /// this falls back to [`Self::sync_agent_status`] so the agent is pruned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if saving updated state fails.
+    pub fn sync_foreground_agent_status(self, app: &mut App) -> Result<()> {
+        if !crate::mux::is_server_running() {
+            debug!("Mux daemon not running; skipping agent sync");
+            return Ok(());
+        }
+
+        let foreground = app.data.foreground_agent_ids();
+        let sessions: HashSet<String> = foreground
+            .iter()
+            .filter_map(|id| app.data.storage.root_ancestor(*id))
+            .map(|root| root.mux_session.clone())
+            .collect();
+
+        let mut existing = HashSet::new();
+        for session in sessions {
+            match self.session_manager.try_exists(&session) {
+                Ok(true) => {
+                    existing.insert(session);
+                }
+                Ok(false) => return self.sync_agent_status(app),
+                Err(err) => {
+                    debug!(session, error = %err, "Failed to check mux session; skipping it");
+                }
+            }
+        }
+
+        let mut changed = false;
+        for agent in app.data.storage.iter_mut() {
+            if foreground.contains(&agent.id)
+                && agent.status == Status::Starting
+                && existing.contains(&agent.mux_session)
+            {
+                debug!(title = %agent.title, "Agent status: Starting -> Running");
+                agent.set_status(Status::Running);
+                changed = true;
+            }
+        }
+        if changed {
+            app.data.storage.save()?;
+        }
+        Ok(())
+    }
+
     fn sync_agent_status_with_sessions(
         self,
         app: &mut App,
@@ -60,6 +124,18 @@ impl Actions {
             return Ok(());
         }
 
+        let root_exit_codes: HashMap<String, u32> = sessions
+            .iter()
+            .filter_map(|session| Some((session.name.clone(), session.exit_code?)))
+            .collect();
+        let state_path = crate::config::Config::state_path();
+        let own_exited_sessions: Vec<String> = sessions
+            .iter()
+            .filter(|session| {
+                session.exit_code.is_some() && session.owner.as_ref() == Some(&state_path)
+            })
+            .map(|session| session.name.clone())
+            .collect();
         let active_sessions: std::collections::HashSet<String> =
             sessions.into_iter().map(|s| s.name).collect();
 
@@ -127,6 +203,11 @@ impl Actions {
             }
         }
 
+        self.reap_exited_sessions(&app.data, &own_exited_sessions);
+        changed |= self.mark_finished_agents(&mut app.data, &root_exit_codes);
+        changed |= self.enforce_run_timeouts(&mut app.data);
+        changed |= self.restart_exited_agents(&mut app.data);
+
         if changed {
             app.data.storage.save()?;
             app.validate_selection();
@@ -135,6 +216,89 @@ impl Actions {
         Ok(())
     }
 
+    /// Kill sessions this instance created whose root program has exited and that no agent
+    /// owns any more.
+    ///
+    /// The daemon keeps exited sessions so finished agents keep their output; once the agent
+    /// is gone, nothing will read it again. Other instances sharing the daemon reap their own.
+    fn reap_exited_sessions(self, app_data: &AppData, exited_sessions: &[String]) {
+        for session in exited_sessions {
+            if app_data
+                .storage
+                .iter()
+                .any(|agent| &agent.mux_session == session)
+            {
+                continue;
+            }
+            debug!(session, "Killing exited mux session without an agent");
+            if let Err(err) = self.session_manager.kill(session) {
+                debug!(session, error = %err, "Failed to kill exited mux session");
+            }
+        }
+    }
+
+    /// Mark running agents whose program has exited as finished. The mux daemon keeps their
+    /// windows, so their final output stays in the preview until they are killed.
+    ///
+    /// Root agents finish when the root window of their session exits (`root_exit_codes`);
+    /// child agents when their own window does.
+    fn mark_finished_agents(
+        self,
+        app_data: &mut AppData,
+        root_exit_codes: &HashMap<String, u32>,
+    ) -> bool {
+        let mut window_exit_codes: HashMap<String, HashMap<u32, u32>> = HashMap::new();
+        let mut finished: Vec<(uuid::Uuid, u32)> = Vec::new();
+
+        for agent in app_data.storage.iter() {
+            if agent.status != Status::Running {
+                continue;
+            }
+            let code = match agent.window_index {
+                None => root_exit_codes.get(&agent.mux_session).copied(),
+                Some(index) => {
+                    let root = app_data.storage.root_ancestor(agent.id).unwrap_or(agent);
+                    window_exit_codes
+                        .entry(root.mux_session.clone())
+                        .or_insert_with(|| {
+                            self.session_manager
+                                .list_windows(&root.mux_session)
+                                .map(|windows| {
+                                    windows
+                                        .into_iter()
+                                        .filter_map(|window| {
+                                            Some((window.index, window.exit_code?))
+                                        })
+                                        .collect()
+                                })
+                                .unwrap_or_default()
+                        })
+                        .get(&index)
+                        .copied()
+                }
+            };
+            if let Some(code) = code {
+                finished.push((agent.id, code));
+            }
+        }
+
+        for (agent_id, code) in &finished {
+            let Some(agent) = app_data.storage.get_mut(*agent_id) else {
+                continue;
+            };
+            info!(title = %agent.title, code, "Agent program exited");
+            agent.set_status(Status::Finished(*code));
+            let message = format!("{} finished with exit code {code}", agent.title);
+            if *code == 0 {
+                app_data.set_status(message);
+            } else {
+                app_data.ui.notify_warning(message);
+            }
+        }
+
+        !finished.is_empty()
+    }
+
     /// Update per-agent activity indicators from raw output sequence changes once per interval.
     ///
     /// If an agent's mux output sequence has not changed since the previous observation,
@@ -142,17 +306,39 @@ impl Actions {
     /// - `●` in green while output is changing (working)
     /// - `◐` in yellow when waiting and unseen (needs attention)
     /// - `○` in red when waiting and already seen
+    /// - `✓` in red when the output shows the program has exited
+    ///
+    /// Agents whose program has `status_rules` are instead judged by their last output lines
+    /// when one of them matches a rule.
     ///
     /// # Errors
     ///
     /// Returns an error only if internal state mutation fails (captures are best-effort).
     pub fn sync_agent_pane_activity(self, app: &mut App) -> Result<()> {
+        self.sync_pane_activity_of(app, None);
+        Ok(())
+    }
+
+    /// Like [`Self::sync_agent_pane_activity`], but only for foreground agents: those on
+    /// screen, selected, starting, or recently active.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if internal state mutation fails (captures are best-effort).
+    pub fn sync_foreground_pane_activity(self, app: &mut App) -> Result<()> {
+        let foreground = app.data.foreground_agent_ids();
+        self.sync_pane_activity_of(app, Some(&foreground));
+        Ok(())
+    }
+
+    /// Observe the panes of running agents, or only those in `only` if given.
+    fn sync_pane_activity_of(self, app: &mut App, only: Option<&HashSet<uuid::Uuid>>) {
         // Pane capture depends on the mux daemon; avoid stale "waiting" indicators when it's down.
         if !crate::mux::is_server_running() {
             app.data.ui.pane_digest_by_agent.clear();
             app.data.ui.pane_last_seen_hash_by_agent.clear();
             app.data.ui.pane_activity_digest_mode = PaneActivityDigestMode::Cursor;
-            return Ok(());
+            return;
         }
 
         let selected_agent_id = app.selected_agent().map(|agent| agent.id);
@@ -164,24 +350,34 @@ impl Actions {
             keep_ids.insert(agent.id);
 
             // Only track activity once the session exists and the agent is running.
-            if agent.status != Status::Running {
+            if agent.status != Status::Running || only.is_some_and(|ids| !ids.contains(&agent.id)) {
                 continue;
             }
 
             let target = mux_target_for_agent(app, agent);
+            let matched = app
+                .data
+                .settings
+                .status_rules_for(&agent.program)
+                .and_then(|rules| self.match_status_rules(rules, &target));
             let mut cursor_fn = || self.output_stream.cursor(&target);
             let mut capture_fn = || self.output_capture.capture_pane(&target);
             let _ = observe_agent_pane_activity(
                 &mut app.data.ui,
                 agent.id,
-                selected_agent_id,
                 &target,
+                matched,
                 &mut digest_mode,
                 &mut cursor_fn,
                 &mut capture_fn,
             );
+            if selected_agent_id == Some(agent.id) {
+                app.data.ui.mark_agent_pane_seen(agent.id);
+            }
         }
 
+        credit_active_time(app);
+
         app.data
             .ui
             .retain_agent_pane_digests(|id| keep_ids.contains(id));
@@ -189,21 +385,202 @@ impl Actions {
             .ui
             .retain_agent_pane_last_seen_hashes(|id| keep_ids.contains(id));
         app.data.ui.pane_activity_digest_mode = digest_mode;
+    }
 
-        Ok(())
+    /// The activity the last output lines of `target` show under `rules`, if any.
+    fn match_status_rules(self, rules: &StatusRules, target: &str) -> Option<PaneActivity> {
+        match self.output_capture.tail(target, StatusRules::SCAN_LINES) {
+            Ok(lines) => rules.classify(&super::inbox::plain_lines(&lines)),
+            Err(err) => {
+                debug!(target, error = %err, "Failed to read output for status rules");
+                None
+            }
+        }
+    }
+
+    /// Start sampling CPU and memory usage of every running agent's process tree, unless the
+    /// previous sample has not arrived yet.
+    ///
+    /// Usage is read through the mux daemon, which owns the agent PTYs and reads the process
+    /// table once for all agents. The request runs on a worker thread;
+    /// [`Self::apply_agent_resource_usage`] picks the result up. Platforms without `ps` are not
+    /// sampled.
+    pub fn sample_agent_resource_usage(self, app: &mut App) {
+        if !crate::mux::is_server_running() || !app.data.ui.resource_sampler.is_supported() {
+            app.data.ui.resource_usage_by_agent.clear();
+            return;
+        }
+
+        let targets: Vec<(uuid::Uuid, String)> = app
+            .data
+            .storage
+            .iter()
+            .filter(|agent| agent.status == Status::Running)
+            .map(|agent| (agent.id, mux_target_for_agent(app, agent)))
+            .collect();
+        if targets.is_empty() {
+            app.data.ui.resource_usage_by_agent.clear();
+            return;
+        }
+        app.data
+            .ui
+            .resource_sampler
+            .start(targets, self.session_manager);
+    }
+
+    /// Record the sample started by [`Self::sample_agent_resource_usage`] once it arrives.
+    ///
+    /// When an agent first crosses the configured alert thresholds, a warning is shown in the
+    /// status bar.
+    pub fn apply_agent_resource_usage(app: &mut App) {
+        let Some(sample) = app.data.ui.resource_sampler.take_finished() else {
+            return;
+        };
+        let usages = match sample.usages {
+            Ok(usages) => usages,
+            Err(err) => {
+                debug!(error = %err, "Failed to sample agent resource usage");
+                return;
+            }
+        };
+
+        let alert = app.data.settings.resource_alert();
+        let mut keep_ids: HashSet<uuid::Uuid> = HashSet::new();
+        let mut newly_runaway: Vec<(String, crate::app::AgentResourceUsage)> = Vec::new();
+        for (agent_id, usage) in sample.agent_ids.into_iter().zip(usages) {
+            let Some(sample_usage) = usage else {
+                continue;
+            };
+            let Some(title) = app
+                .data
+                .storage
+                .get(agent_id)
+                .filter(|agent| agent.status == Status::Running)
+                .map(|agent| agent.title.clone())
+            else {
+                continue;
+            };
+            keep_ids.insert(agent_id);
+
+            let was_runaway = app
+                .data
+                .ui
+                .resource_usage_by_agent
+                .get(&agent_id)
+                .is_some_and(|usage| usage.runaway);
+            let usage = app.data.ui.observe_agent_resource_usage(
+                agent_id,
+                sample_usage,
+                sample.sampled_at,
+                alert,
+            );
+            if usage.runaway && !was_runaway {
+                newly_runaway.push((title, usage));
+            }
+        }
+
+        app.data
+            .ui
+            .resource_usage_by_agent
+            .retain(|id, _| keep_ids.contains(id));
+
+        if let Some((title, usage)) = newly_runaway.first() {
+            warn!(
+                title,
+                cpu_percent = usage.cpu_percent,
+                rss_bytes = usage.rss_bytes,
+                processes = usage.processes,
+                "Agent processes exceeded resource alert thresholds"
+            );
+            app.notify_warning(format!(
+                "Runaway agent: {title} is using {}% CPU and {} ({} processes)",
+                usage.cpu_percent,
+                usage.memory_label(),
+                usage.processes
+            ));
+        }
+    }
+
+    /// Send each agent's title and status to the mux daemon as its window title.
+    ///
+    /// Titles are only sent when they change, so an external client attached to the mux sees
+    /// which agent a window belongs to and what it is doing.
+    pub fn sync_window_titles(self, app: &mut App) {
+        if !crate::mux::is_server_running() {
+            app.data.ui.mux_title_by_agent.clear();
+            return;
+        }
+
+        let mut keep_ids: HashSet<uuid::Uuid> = HashSet::new();
+        for agent in app.data.storage.iter() {
+            keep_ids.insert(agent.id);
+            let title = agent.window_title();
+            if app.data.ui.mux_title_by_agent.get(&agent.id) == Some(&title) {
+                continue;
+            }
+
+            let target = mux_target_for_agent(app, agent);
+            match self.session_manager.set_title(&target, &title) {
+                Ok(()) => {
+                    app.data.ui.mux_title_by_agent.insert(agent.id, title);
+                }
+                Err(err) => debug!(target, error = %err, "Failed to set mux window title"),
+            }
+        }
+
+        app.data
+            .ui
+            .mux_title_by_agent
+            .retain(|id, _| keep_ids.contains(id));
     }
 
     /// Auto-connect to existing worktrees on startup
     ///
     /// This function scans for worktrees that match the configured branch prefix
     /// and creates agents for them if they don't already exist in storage.
-    /// The agent title will be the branch name.
+    /// The agent title will be the branch name. The `auto_connect` setting decides whether
+    /// this happens at all, whether the agent program is started, and whether the user picks
+    /// the worktrees first.
     ///
     /// # Errors
     ///
     /// Returns an error if worktrees cannot be listed or agent creation fails
     pub fn auto_connect_worktrees(self, app: &mut App) -> Result<()> {
-        let repo_path = match app.data.cwd_project_root.clone() {
+        let policy = app.data.settings.auto_connect;
+        if policy == AutoConnectPolicy::Off {
+            debug!("Auto-connect is off");
+            return Ok(());
+        }
+
+        let candidates = Self::auto_connect_candidates(&app.data)?;
+        if candidates.is_empty() {
+            return Ok(());
+        }
+        debug!(
+            count = candidates.len(),
+            policy = policy.label(),
+            "Auto-connect candidates"
+        );
+
+        if policy == AutoConnectPolicy::Ask {
+            if matches!(app.mode, AppMode::Normal(_)) {
+                app.data.auto_connect_picker.start(candidates);
+                app.apply_mode(AutoConnectPickerMode.into());
+            } else {
+                app.data.set_status(format!(
+                    "Skipped {} worktree(s) without an agent; restart Tenex to choose which to reconnect",
+                    candidates.len()
+                ));
+            }
+            return Ok(());
+        }
+
+        self.connect_worktrees(&mut app.data, candidates, policy)
+    }
+
+    /// Tenex worktrees of the current repository that no stored agent uses.
+    fn auto_connect_candidates(app_data: &AppData) -> Result<Vec<AutoConnectCandidate>> {
+        let repo_path = match app_data.cwd_project_root.clone() {
             Some(root) => root,
             None => {
                 std::env::current_dir().context("Failed to resolve current project directory")?
@@ -212,22 +589,25 @@ impl Actions {
         let Ok(repo) = git::open_repository(&repo_path) else {
             // Not in a git repository, nothing to auto-connect
             debug!("Not in a git repository, skipping auto-connect");
-            return Ok(());
+            return Ok(Vec::new());
         };
 
         let worktree_mgr = WorktreeManager::new(&repo);
         let worktrees = worktree_mgr.list()?;
-        let program = app.agent_spawn_command();
-        let instance_worktree_dir_fallback = app.data.config.worktree_dir.clone();
-        let instance_worktree_dir = app
-            .data
-            .config
-            .worktree_dir
+        let config = app_data.repo_config(&repo_path);
+        // Repos with their own `tenex.worktreeDir` keep worktrees outside the instance directory.
+        let instance_worktree_dir_fallback = if Config::has_repo_worktree_dir(&repo_path) {
+            config.worktree_dir_for_repo_root(&repo_path)
+        } else {
+            config.worktree_dir.clone()
+        };
+        let instance_worktree_dir = instance_worktree_dir_fallback
             .canonicalize()
             .unwrap_or(instance_worktree_dir_fallback);
 
         debug!(count = worktrees.len(), "Found worktrees for auto-connect");
 
+        let mut candidates = Vec::new();
         for wt in worktrees {
             let worktree_path_fallback = wt.path.clone();
             let worktree_path = wt.path.canonicalize().unwrap_or(worktree_path_fallback);
@@ -261,45 +641,100 @@ impl Actions {
                 }
             };
 
-            if !is_tenex_managed_branch(&branch_name, &app.data.config.branch_prefix) {
+            if !config.is_managed_branch(&branch_name) {
                 debug!(
                     branch = %branch_name,
-                    prefix = %app.data.config.branch_prefix,
                     "Skipping worktree with different prefix"
                 );
                 continue;
             }
 
             // Check if there's already an agent for this branch
-            let agent_exists = app.data.storage.iter().any(|a| a.branch == branch_name);
+            let agent_exists = app_data.storage.iter().any(|a| a.branch == branch_name);
             if agent_exists {
                 debug!(branch = %branch_name, "Agent already exists for worktree");
                 continue;
             }
 
+            candidates.push(AutoConnectCandidate {
+                title: title_for_branch(&branch_name, &config),
+                branch: branch_name,
+                worktree_path,
+                repo_root: repo_path.clone(),
+            });
+        }
+        Ok(candidates)
+    }
+
+    /// Create agents for `candidates` as `policy` says.
+    ///
+    /// A worktree whose program is still running in an unowned mux session of this instance
+    /// is reattached to that session instead of starting the program again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an agent cannot be launched or the agents cannot be saved.
+    pub(crate) fn connect_worktrees(
+        self,
+        app_data: &mut AppData,
+        candidates: Vec<AutoConnectCandidate>,
+        policy: AutoConnectPolicy,
+    ) -> Result<()> {
+        let mut live_sessions = self.unowned_sessions(app_data);
+
+        for candidate in candidates {
             info!(
-                branch = %branch_name,
-                path = ?worktree_path,
+                branch = %candidate.branch,
+                path = ?candidate.worktree_path,
                 "Auto-connecting to existing worktree"
             );
 
             // Create an agent for this worktree
+            let program = app_data.with_repo_config(&candidate.repo_root, |app_data| {
+                app_data.agent_spawn_command()
+            });
             let mut agent = Agent::new(
-                title_for_branch(&branch_name, &app.data.config.branch_prefix),
-                program.clone(),
-                branch_name.clone(),
-                worktree_path.clone(),
+                candidate.title,
+                program,
+                candidate.branch,
+                candidate.worktree_path,
             );
-            agent.repo_root = Some(repo_path.clone());
-            agent.runtime = crate::runtime::new_root_runtime(&app.data.settings);
-            self.launch_root_agent(&mut app.data, &mut agent, None)?;
+            agent.repo_root = Some(candidate.repo_root);
+            if let Some(session) = take_session_in(&mut live_sessions, &agent.worktree_path) {
+                // The runtime it was started with is unknown; keep the host default so that
+                // killing the agent never tears down a container.
+                info!(branch = %agent.branch, session = %session.name, "Reattaching to running session");
+                app_data.startup_report.adopted.push(format!(
+                    "{} (reattached to running session {})",
+                    agent.title, session.name
+                ));
+                agent.mux_session = session.name;
+                agent.set_status(Status::Running);
+            } else if policy == AutoConnectPolicy::AdoptOnly {
+                self.adopt_root_agent(app_data, &mut agent)?;
+                app_data
+                    .startup_report
+                    .adopted
+                    .push(format!("{} (shell only; program not started)", agent.title));
+            } else {
+                agent.runtime = crate::runtime::new_root_runtime(&app_data.settings);
+                self.resume_root_agent(app_data, &mut agent)?;
+                let adopted = if agent.conversation_id.is_some() {
+                    format!("{} (resumed its latest conversation)", agent.title)
+                } else {
+                    agent.title.clone()
+                };
+                app_data.startup_report.adopted.push(adopted);
+            }
 
-            app.data.storage.add(agent);
-            info!(branch = %branch_name, "Auto-connected to existing worktree");
+            info!(branch = %agent.branch, "Auto-connected to existing worktree");
+            app_data.startup_report.adopted_ids.insert(agent.id);
+            app_data.storage.add(agent);
         }
 
         // Save storage if we added any agents
-        app.data.storage.save()?;
+        app_data.storage.save()?;
+        app_data.validate_selection();
         Ok(())
     }
 
@@ -311,19 +746,62 @@ impl Actions {
     /// (including terminals) using each agent's stored program.
     ///
     /// This function is intended to run on startup and is best-effort: it will continue
-    /// attempting to restore other agents if one fails to spawn.
+    /// attempting to restore other agents if one fails to spawn. What it did, along with any
+    /// orphaned mux sessions, is recorded in the startup report.
     ///
     /// # Errors
     ///
     /// Returns an error if saving updated state fails.
     pub fn respawn_missing_agents(self, app: &mut App) -> Result<()> {
-        self.respawn_missing_agents_in_data(&mut app.data)
+        let summary = self.respawn_missing_agents_in_data(&mut app.data)?;
+        let orphaned_sessions = self.orphaned_sessions(&mut app.data);
+
+        let report = &mut app.data.startup_report;
+        report.reattached = summary
+            .reattached
+            .into_iter()
+            .filter(|(id, _)| !report.adopted_ids.contains(id))
+            .map(|(_, title)| title)
+            .collect();
+        report.respawned = summary.respawned;
+        report.disconnected = summary.disconnected;
+        report.orphaned_sessions = orphaned_sessions;
+        Ok(())
+    }
+
+    /// Names of mux sessions of this instance that no stored agent owns.
+    fn orphaned_sessions(self, app_data: &mut AppData) -> Vec<String> {
+        self.unowned_sessions(app_data)
+            .into_iter()
+            .map(|session| session.name)
+            .collect()
+    }
+
+    /// Live mux sessions named for this instance that no stored agent owns.
+    fn unowned_sessions(self, app_data: &mut AppData) -> Vec<crate::mux::Session> {
+        if !crate::mux::is_server_running() {
+            return Vec::new();
+        }
+        let Ok(sessions) = self.session_manager.list() else {
+            return Vec::new();
+        };
+        let prefix = app_data.storage.instance_session_prefix();
+        let owned: HashSet<&str> = app_data
+            .storage
+            .iter()
+            .map(|agent| agent.mux_session.as_str())
+            .collect();
+        sessions
+            .into_iter()
+            .filter(|session| session.name.starts_with(&prefix))
+            .filter(|session| !owned.contains(session.name.as_str()))
+            .collect()
     }
 
-    fn respawn_missing_agents_in_data(self, app_data: &mut AppData) -> Result<()> {
+    fn respawn_missing_agents_in_data(self, app_data: &mut AppData) -> Result<RespawnSummary> {
         let roots = stored_root_agents(app_data);
         if roots.is_empty() {
-            return Ok(());
+            return Ok(RespawnSummary::default());
         }
 
         let session_manager = self.session_manager;
@@ -332,7 +810,8 @@ impl Actions {
             respawn_root_agent(session_manager, app_data, root, &mut summary);
         }
 
-        finish_respawn_summary(app_data, &summary)
+        finish_respawn_summary(app_data, &summary)?;
+        Ok(summary)
     }
 
     pub(crate) fn restart_mux_daemon(self, app_data: &mut AppData) -> Result<()> {
@@ -353,8 +832,8 @@ impl Actions {
 fn observe_agent_pane_activity(
     ui: &mut crate::app::state::UiState,
     agent_id: uuid::Uuid,
-    selected_agent_id: Option<uuid::Uuid>,
     target: &str,
+    matched: Option<PaneActivity>,
     digest_mode: &mut PaneActivityDigestMode,
     cursor_fn: &mut dyn FnMut() -> Result<crate::mux::OutputCursor>,
     capture_fn: &mut dyn FnMut() -> Result<String>,
@@ -370,13 +849,50 @@ fn observe_agent_pane_activity(
         );
         *digest_mode = next_mode;
     }
-    ui.observe_agent_pane_digest(agent_id, digest);
+    ui.observe_agent_pane_digest(agent_id, digest, matched);
+    Ok(())
+}
 
-    if selected_agent_id == Some(agent_id) {
-        ui.pane_last_seen_hash_by_agent.insert(agent_id, digest);
+/// Longest gap credited as active time in one step (guards against sleep/suspend gaps).
+const MAX_ACTIVE_TIME_STEP: std::time::Duration = std::time::Duration::from_secs(5);
+/// How often accumulated active time is written back to the state file.
+const ACTIVE_TIME_SAVE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Add the time since the previous observation to every agent whose pane is active.
+fn credit_active_time(app: &mut App) {
+    let now = std::time::Instant::now();
+    let step = app
+        .data
+        .ui
+        .active_time_accounted_at
+        .replace(now)
+        .map(|last| now.duration_since(last).min(MAX_ACTIVE_TIME_STEP));
+    let Some(step) = step else {
+        return;
+    };
+    let step_ms = u64::try_from(step.as_millis()).unwrap_or(u64::MAX);
+
+    let active_ids = app.data.ui.active_agent_ids();
+    if active_ids.is_empty() {
+        return;
+    }
+    for id in active_ids {
+        if let Some(agent) = app.data.storage.get_mut(id) {
+            agent.active_ms = agent.active_ms.saturating_add(step_ms);
+        }
     }
 
-    Ok(())
+    let save_due = app
+        .data
+        .ui
+        .active_time_saved_at
+        .is_none_or(|saved| now.duration_since(saved) >= ACTIVE_TIME_SAVE_INTERVAL);
+    if save_due {
+        app.data.ui.active_time_saved_at = Some(now);
+        if let Err(err) = app.data.storage.save() {
+            warn!(error = %err, "Failed to persist agent active time");
+        }
+    }
 }
 
 fn mux_target_for_agent(app: &App, agent: &Agent) -> String {
@@ -428,7 +944,12 @@ fn pane_activity_digest_with_fallback(
 #[derive(Default)]
 struct RespawnSummary {
     changed: bool,
-    respawned_sessions: usize,
+    /// Root agents (ID and title) whose session was still running.
+    reattached: Vec<(uuid::Uuid, String)>,
+    /// Root agents whose session was recreated.
+    respawned: Vec<String>,
+    /// Root agents whose session could not be recreated.
+    disconnected: Vec<String>,
 }
 
 fn finish_respawn_summary(app_data: &mut AppData, summary: &RespawnSummary) -> Result<()> {
@@ -437,16 +958,30 @@ fn finish_respawn_summary(app_data: &mut AppData, summary: &RespawnSummary) -> R
         app_data.validate_selection();
     }
 
-    if summary.respawned_sessions > 0 {
+    if !summary.respawned.is_empty() {
         app_data.set_status(format!(
             "Respawned {} agent session(s)",
-            summary.respawned_sessions
+            summary.respawned.len()
         ));
     }
 
     Ok(())
 }
 
+/// Remove and return the first of `sessions` running in `worktree_path`.
+fn take_session_in(
+    sessions: &mut Vec<crate::mux::Session>,
+    worktree_path: &std::path::Path,
+) -> Option<crate::mux::Session> {
+    let index = sessions.iter().position(|session| {
+        session
+            .working_dir
+            .as_ref()
+            .is_some_and(|dir| dir.canonicalize().unwrap_or_else(|_| dir.clone()) == worktree_path)
+    })?;
+    Some(sessions.remove(index))
+}
+
 fn stored_root_agents(app_data: &AppData) -> Vec<Agent> {
     app_data
         .storage
@@ -464,6 +999,7 @@ fn respawn_root_agent(
 ) {
     if session_manager.exists(&root.mux_session) {
         summary.changed |= normalize_tree_running(app_data, root.id, &root.mux_session, true);
+        summary.reattached.push((root.id, root.title.clone()));
         return;
     }
 
@@ -477,6 +1013,19 @@ fn respawn_root_agent(
             worktree = %root.worktree_path.display(),
             "Worktree missing; cannot respawn agent session"
         );
+        summary.disconnected.push(root.title.clone());
+        return;
+    }
+
+    if let Some(tmux_session) = &root.imported_tmux_session
+        && !crate::tmux::session_exists(tmux_session)
+    {
+        warn!(
+            title = %root.title,
+            tmux_session,
+            "Imported tmux session is gone; cannot respawn agent session"
+        );
+        summary.disconnected.push(root.title.clone());
         return;
     }
 
@@ -487,6 +1036,7 @@ fn respawn_root_agent(
             error = %err,
             "Failed to prepare runtime for root agent; skipping respawn"
         );
+        summary.disconnected.push(root.title.clone());
         return;
     }
 
@@ -499,6 +1049,7 @@ fn respawn_root_agent(
                 error = %err,
                 "Failed to build command for root agent; skipping respawn"
             );
+            summary.disconnected.push(root.title.clone());
             return;
         }
     };
@@ -514,10 +1065,11 @@ fn respawn_root_agent(
             error = %err,
             "Failed to recreate mux session for agent"
         );
+        summary.disconnected.push(root.title.clone());
         return;
     }
 
-    summary.respawned_sessions = summary.respawned_sessions.saturating_add(1);
+    summary.respawned.push(root.title.clone());
     summary.changed = true;
 
     let descendants = sorted_descendants(app_data, root.id);
@@ -664,15 +1216,8 @@ fn has_isolated_state_marker(worktree_path: &Path, stop_at: &Path) -> bool {
     }
 }
 
-fn is_tenex_managed_branch(branch: &str, branch_prefix: &str) -> bool {
-    (!branch_prefix.is_empty() && branch.starts_with(branch_prefix)) || branch.starts_with("tenex/")
-}
-
-fn title_for_branch(branch: &str, branch_prefix: &str) -> String {
-    let stripped = branch
-        .strip_prefix(branch_prefix)
-        .or_else(|| branch.strip_prefix("tenex/"))
-        .unwrap_or(branch);
+fn title_for_branch(branch: &str, config: &Config) -> String {
+    let stripped = config.strip_managed_prefix(branch);
     if stripped.is_empty() {
         branch.to_string()
     } else {
@@ -699,7 +1244,8 @@ fn normalize_tree_running(
     ids.extend(descendant_ids);
 
     for agent in app_data.storage.iter_mut() {
-        if !ids.contains(&agent.id) {
+        // A stopped agent's session is kept by the daemon with its final output.
+        if !ids.contains(&agent.id) || (running && agent.status.is_stopped()) {
             continue;
         }
 
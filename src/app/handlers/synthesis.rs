@@ -0,0 +1,403 @@
+//! Synthesis strategies (what a synthesis collects from each descendant for its parent) and
+//! the browser for past synthesis files (`/syntheses`).
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::AtomicBool;
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow, bail};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::Actions;
+use crate::agent::Agent;
+use crate::app::data::SynthesisTargets;
+use crate::app::{AppData, SynthesisFile, SynthesisOptions, SynthesisStrategy};
+use crate::conversation::build_one_shot_argv;
+use crate::git;
+use crate::prompts;
+use crate::state::{AppMode, ErrorModalMode, SynthesisBrowserMode};
+
+/// Scrollback lines captured for the transcript and summary strategies.
+const TRANSCRIPT_LINES: u32 = 5000;
+
+/// Terminal lines kept by the final-message strategy.
+const FINAL_MESSAGE_LINES: usize = 80;
+
+/// Lines of each synthesis file shown in the browser's preview.
+const PREVIEW_LINES: usize = 12;
+
+/// Synthesis files untouched for this long are compressed when a new one is written.
+const COMPRESS_SYNTHESIS_AFTER: Duration = Duration::from_hours(1);
+
+impl Actions {
+    /// Collect what `strategy` asks for from each of `capture_agent_ids`, as `(title, content)`
+    /// pairs.
+    ///
+    /// For [`SynthesisStrategy::Summaries`] this is the transcripts; they are summarized by
+    /// [`Self::summarize_then_deliver`].
+    pub(super) fn capture_synthesis_findings(
+        self,
+        app_data: &AppData,
+        capture_agent_ids: &[Uuid],
+        strategy: SynthesisStrategy,
+    ) -> Vec<(String, String)> {
+        let mut diffed_worktrees: HashMap<PathBuf, String> = HashMap::new();
+        let mut findings = Vec::new();
+
+        for agent_id in capture_agent_ids {
+            let Some(descendant) = app_data.storage.get(*agent_id) else {
+                continue;
+            };
+            let target = app_data.storage.mux_target(descendant);
+
+            let finding = match strategy {
+                SynthesisStrategy::Transcripts | SynthesisStrategy::Summaries => {
+                    let output = self
+                        .output_capture
+                        .capture_pane_with_history(&target, TRANSCRIPT_LINES)
+                        .unwrap_or_else(|_| "(Could not capture output)".to_string());
+                    match read_handoff_notes(descendant) {
+                        Some(notes) => {
+                            format!("### Handoff notes\n{notes}\n\n### Terminal output\n{output}")
+                        }
+                        None => output,
+                    }
+                }
+                SynthesisStrategy::FinalMessages => self
+                    .output_capture
+                    .tail(&target, FINAL_MESSAGE_LINES)
+                    .map_or_else(
+                        |_| "(Could not capture output)".to_string(),
+                        |lines| lines.join("\n"),
+                    ),
+                SynthesisStrategy::Diffs => {
+                    Self::diff_finding(app_data, descendant, &mut diffed_worktrees)
+                }
+                SynthesisStrategy::Handoffs => read_handoff_notes(descendant)
+                    .unwrap_or_else(|| "(No handoff notes)".to_string()),
+            };
+            findings.push((descendant.title.clone(), finding));
+        }
+
+        findings
+    }
+
+    /// The diff of `agent`'s worktree against its base branch, or a pointer to the agent whose
+    /// finding already has it.
+    fn diff_finding(
+        app_data: &AppData,
+        agent: &Agent,
+        diffed_worktrees: &mut HashMap<PathBuf, String>,
+    ) -> String {
+        let worktree = &agent.worktree_path;
+        if let Some(owner) = diffed_worktrees.get(worktree) {
+            return format!("(Works in the same worktree as {owner}; see its diff.)");
+        }
+        diffed_worktrees.insert(worktree.clone(), agent.title.clone());
+
+        let base = app_data
+            .storage
+            .root_ancestor(agent.id)
+            .and_then(|root| root.base_branch.clone())
+            .unwrap_or_else(|| Self::detect_base_branch(worktree, &agent.branch));
+        worktree_diff(worktree, &base)
+            .unwrap_or_else(|err| format!("(Could not diff against {base}: {err:#})"))
+    }
+
+    /// Summarize each transcript in `findings` with the parent's program in the background, then
+    /// deliver the synthesis to `parent_id`.
+    pub(super) fn summarize_then_deliver(
+        app_data: &mut AppData,
+        parent_id: Uuid,
+        findings: Vec<(String, String)>,
+        targets: SynthesisTargets,
+        options: SynthesisOptions,
+        prompt: Option<&str>,
+    ) {
+        let Some(parent) = app_data.storage.get(parent_id) else {
+            return;
+        };
+        let program = parent.program.clone();
+        let workdir = parent.worktree_path.clone();
+        let prompt = prompt.map(str::to_string);
+        let count = findings.len();
+        info!(count, %program, "Summarizing transcripts for synthesis");
+        app_data.background_ops.spawn_cancellable(
+            Some(parent_id),
+            format!("Summarizing {count} agents for synthesis"),
+            move |cancelled| {
+                let summaries = summarize_findings(&program, &workdir, findings, cancelled);
+                Box::new(move |app_data: &mut AppData| {
+                    let findings = summaries?;
+                    Self::new().deliver_synthesis(
+                        app_data,
+                        parent_id,
+                        &findings,
+                        &targets,
+                        options,
+                        prompt.as_deref(),
+                    )
+                })
+            },
+        );
+    }
+
+    /// Open the synthesis browser on the selected agent's past synthesis files (`/syntheses`).
+    pub fn start_synthesis_browser(app_data: &mut AppData) -> AppMode {
+        app_data.input.clear();
+        let Some(agent) = app_data.selected_agent() else {
+            return ErrorModalMode::new("Select an agent to browse its synthesis files.").into();
+        };
+
+        let files = list_synthesis_files(&agent.worktree_path);
+        if files.is_empty() {
+            return ErrorModalMode::new(format!(
+                "No synthesis files in {}'s worktree.",
+                agent.title
+            ))
+            .into();
+        }
+
+        let agent_id = agent.id;
+        app_data.synthesis_browser.start(agent_id, files);
+        SynthesisBrowserMode.into()
+    }
+
+    /// Tell the browsed agent to read `file` again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the agent no longer exists or cannot be sent the read command.
+    pub fn resend_synthesis(self, app_data: &mut AppData, file: &SynthesisFile) -> Result<()> {
+        // The agent reads the plain file.
+        crate::compression::decompress_file(&file.path)?;
+        let Some(agent) = app_data
+            .synthesis_browser
+            .agent_id
+            .and_then(|agent_id| app_data.storage.get(agent_id))
+        else {
+            bail!("The agent for this synthesis no longer exists");
+        };
+
+        let target = app_data.storage.mux_target(agent);
+        let read_command = Self::build_synthesis_read_command(file.id, None);
+        self.session_manager
+            .send_keys_and_submit_for_agent(&target, agent, &read_command)?;
+        info!(agent = %agent.title, synthesis_id = %file.id, "Re-sent synthesis");
+        let title = agent.title.clone();
+        app_data.set_status(format!("Sent synthesis {} to {title}", file.short_id()));
+        Ok(())
+    }
+
+    /// Copy `file` into the instance's `exports` directory, returning the copy's path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created or the file cannot be copied.
+    pub fn export_synthesis(app_data: &AppData, file: &SynthesisFile) -> Result<PathBuf> {
+        let dir = app_data
+            .storage
+            .resolved_state_path()
+            .with_file_name("exports");
+        fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+
+        let path = dir.join(format!("synthesis-{}.md", file.id));
+        let mut reader = crate::compression::open(&file.path)?;
+        let mut out = fs::File::create(&path)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+        std::io::copy(&mut reader, &mut out)
+            .with_context(|| format!("Failed to copy {}", file.path.display()))?;
+        info!(path = %path.display(), "Exported synthesis");
+        Ok(path)
+    }
+
+    /// Delete `file` from the agent's worktree.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be removed.
+    pub fn delete_synthesis(file: &SynthesisFile) -> Result<()> {
+        fs::remove_file(&file.path)
+            .with_context(|| format!("Failed to delete {}", file.path.display()))?;
+        info!(path = %file.path.display(), "Deleted synthesis");
+        Ok(())
+    }
+}
+
+/// Compress the synthesis files in `tenex_dir` that have not changed for
+/// [`COMPRESS_SYNTHESIS_AFTER`], other than `keep`. Their agents have read them by then; the
+/// browser still lists them and restores one when it is sent again.
+pub(super) fn compress_closed_synthesis_files(tenex_dir: &Path, keep: Uuid) {
+    let Ok(entries) = fs::read_dir(tenex_dir) else {
+        return;
+    };
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+        let closed = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age >= COMPRESS_SYNTHESIS_AFTER);
+        if !closed
+            || crate::compression::is_compressed(&path)
+            || synthesis_file_id(&path).is_none_or(|id| id == keep)
+        {
+            continue;
+        }
+        if let Err(err) = crate::compression::compress_file(&path) {
+            warn!(path = %path.display(), error = %err, "Failed to compress synthesis file");
+        }
+    }
+}
+
+/// The id of the synthesis file at `path` (`<uuid>.md` or `<uuid>.md.zst`).
+fn synthesis_file_id(path: &Path) -> Option<Uuid> {
+    let name = path.file_name()?.to_str()?;
+    let name = name
+        .strip_suffix(".zst")
+        .unwrap_or(name)
+        .strip_suffix(".md")?;
+    Uuid::parse_str(name).ok()
+}
+
+/// The synthesis files (`.tenex/<uuid>.md`, or `.md.zst` once compressed) in `worktree`, newest
+/// first.
+fn list_synthesis_files(worktree: &Path) -> Vec<SynthesisFile> {
+    let Ok(entries) = fs::read_dir(worktree.join(".tenex")) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<SynthesisFile> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let path = entry.path();
+            let id = synthesis_file_id(&path)?;
+            let metadata = entry.metadata().ok()?;
+            Some(SynthesisFile {
+                id,
+                preview: read_preview(&path),
+                path,
+                modified: metadata.modified().ok()?,
+                size: metadata.len(),
+            })
+        })
+        .collect();
+    files.sort_by_key(|file| std::cmp::Reverse(file.modified));
+    files
+}
+
+fn read_preview(path: &Path) -> Vec<String> {
+    let Ok(reader) = crate::compression::open(path) else {
+        return Vec::new();
+    };
+    reader
+        .lines()
+        .map_while(Result::ok)
+        .take(PREVIEW_LINES)
+        .collect()
+}
+/// Whether `program` can summarize transcripts for [`SynthesisStrategy::Summaries`].
+pub(super) fn can_summarize(program: &str) -> bool {
+    build_one_shot_argv(program, "").is_ok_and(|argv| argv.is_some())
+}
+
+fn read_handoff_notes(agent: &Agent) -> Option<String> {
+    let notes = fs::read_to_string(agent.handoff_path()).ok()?;
+    let notes = notes.trim_end();
+    (!notes.trim().is_empty()).then(|| notes.to_string())
+}
+
+/// Diff of `worktree`, including uncommitted changes, against where it branched off `base`.
+fn worktree_diff(worktree: &Path, base: &str) -> Result<String> {
+    let diff = git::patch_against_base(worktree, base)?;
+    let diff = diff.trim_end();
+    if diff.is_empty() {
+        return Ok("(No changes)".to_string());
+    }
+    Ok(format!("```diff\n{diff}\n```"))
+}
+
+/// Replace each transcript in `findings` with `program`'s summary of it, keeping the transcript
+/// when it cannot be summarized.
+fn summarize_findings(
+    program: &str,
+    workdir: &Path,
+    findings: Vec<(String, String)>,
+    cancelled: &AtomicBool,
+) -> Result<Vec<(String, String)>> {
+    let dir = workdir.join(".tenex");
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+
+    std::thread::scope(|scope| {
+        // Start every summarizer before waiting on any, so they run in parallel.
+        let mut handles = Vec::with_capacity(findings.len());
+        for (title, transcript) in findings {
+            let dir = &dir;
+            handles.push(scope.spawn(move || {
+                match summarize_transcript(program, workdir, dir, &title, &transcript, cancelled) {
+                    Ok(summary) => Ok((title, summary)),
+                    Err(err) if err.is::<git::Cancelled>() => Err(err),
+                    Err(err) => {
+                        warn!(%title, error = %err, "Failed to summarize transcript");
+                        let finding = format!("(Could not summarize: {err:#})\n\n{transcript}");
+                        Ok((title, finding))
+                    }
+                }
+            }));
+        }
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .map_err(|_| anyhow!("Transcript summarizer stopped unexpectedly"))?
+            })
+            .collect()
+    })
+}
+
+fn summarize_transcript(
+    program: &str,
+    workdir: &Path,
+    dir: &Path,
+    title: &str,
+    transcript: &str,
+    cancelled: &AtomicBool,
+) -> Result<String> {
+    let path = dir.join(format!("transcript-{}.md", Uuid::new_v4()));
+    fs::write(&path, transcript).with_context(|| format!("Failed to write {}", path.display()))?;
+    let relative = path.strip_prefix(workdir).unwrap_or(&path);
+    let request = prompts::build_synthesis_summary_request(&relative.display().to_string(), title);
+
+    let output = build_one_shot_argv(program, &request)
+        .and_then(|argv| argv.context("The parent's program cannot summarize transcripts"))
+        .and_then(|argv| {
+            let (exe, args) = argv
+                .split_first()
+                .context("The parent's program is empty")?;
+            let mut command = Command::new(exe);
+            command.args(args).current_dir(workdir);
+            git::output_cancellable(&mut command, cancelled)
+        });
+    let _ = fs::remove_file(&path);
+    let output = output?;
+
+    if !output.status.success() {
+        bail!(
+            "{program} exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let summary = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if summary.is_empty() {
+        bail!("{program} printed no summary");
+    }
+    Ok(summary)
+}
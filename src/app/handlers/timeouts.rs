@@ -0,0 +1,96 @@
+//! Run timeouts: asking agents that ran past their maximum runtime to wrap up, then stopping
+//! them.
+
+use chrono::{TimeDelta, Utc};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::Actions;
+use crate::agent::{Agent, Status};
+use crate::app::AppData;
+use crate::prompts;
+
+/// How long an agent that was asked to wrap up keeps running before it is stopped.
+const WRAP_UP_GRACE: TimeDelta = TimeDelta::minutes(2);
+
+impl Actions {
+    /// Ask running agents that reached their maximum runtime to wrap up, and stop the ones
+    /// whose wrap-up time has passed, marking them timed out.
+    ///
+    /// Returns whether any agent changed.
+    pub(super) fn enforce_run_timeouts(self, app_data: &mut AppData) -> bool {
+        let now = Utc::now();
+        let default_mins = app_data.settings.max_runtime_mins;
+        let mut wrap_up: Vec<(Uuid, u64)> = Vec::new();
+        let mut stop: Vec<Uuid> = Vec::new();
+        for agent in app_data.storage.iter() {
+            let Some(minutes) = max_runtime_mins(agent, default_mins) else {
+                continue;
+            };
+            match agent.wrap_up_requested_at {
+                None if now - agent.created_at >= minutes_delta(minutes) => {
+                    wrap_up.push((agent.id, minutes));
+                }
+                Some(requested_at) if now - requested_at >= WRAP_UP_GRACE => stop.push(agent.id),
+                _ => {}
+            }
+        }
+
+        for (agent_id, minutes) in &wrap_up {
+            self.request_wrap_up(app_data, *agent_id, *minutes);
+        }
+        // Nothing is killed afterwards, so there is no need to wait for the programs to exit.
+        self.send_quit_sequences(app_data, &stop);
+        for agent_id in &stop {
+            let Some(agent) = app_data.storage.get_mut(*agent_id) else {
+                continue;
+            };
+            info!(title = %agent.title, "Stopped agent after its maximum runtime");
+            agent.set_status(Status::TimedOut);
+            let message = format!("{} timed out and was stopped", agent.title);
+            app_data.ui.notify_warning(message);
+        }
+        !wrap_up.is_empty() || !stop.is_empty()
+    }
+
+    fn request_wrap_up(self, app_data: &mut AppData, agent_id: Uuid, minutes: u64) {
+        let Some(agent) = app_data.storage.get(agent_id) else {
+            return;
+        };
+        let target = app_data.storage.mux_target(agent);
+        let title = agent.title.clone();
+        if let Err(err) = self.session_manager.send_keys_and_submit_for_agent(
+            &target,
+            agent,
+            &prompts::build_wrap_up_request(minutes),
+        ) {
+            warn!(%title, error = %err, "Failed to ask agent to wrap up");
+        }
+        // Stop the agent after the grace period even if the request could not be sent.
+        if let Some(agent) = app_data.storage.get_mut(agent_id) {
+            agent.wrap_up_requested_at = Some(Utc::now());
+        }
+        info!(%title, minutes, "Asked agent to wrap up after its maximum runtime");
+        app_data.ui.notify_warning(format!(
+            "{title} reached its {minutes} minute limit and was asked to wrap up"
+        ));
+    }
+}
+
+/// The runtime limit that applies to `agent`, in minutes, if it is a running agent with one.
+fn max_runtime_mins(agent: &Agent, default_mins: Option<u64>) -> Option<u64> {
+    if agent.status != Status::Running || agent.is_terminal_agent() || agent.budget.is_paused() {
+        return None;
+    }
+    agent
+        .max_runtime_mins
+        .or(default_mins)
+        .filter(|minutes| *minutes > 0)
+}
+
+fn minutes_delta(minutes: u64) -> TimeDelta {
+    i64::try_from(minutes)
+        .ok()
+        .and_then(TimeDelta::try_minutes)
+        .unwrap_or(TimeDelta::MAX)
+}
@@ -0,0 +1,87 @@
+//! Importing tmux sessions started outside Tenex as root agents (`/import_tmux`).
+
+use anyhow::Result;
+use tracing::info;
+
+use super::Actions;
+use crate::agent::{Agent, WorkspaceKind};
+use crate::app::AppData;
+use crate::error::TenexError;
+use crate::state::{AppMode, ErrorModalMode, TmuxImportPickerMode};
+use crate::tmux::{self, TmuxSession};
+
+impl Actions {
+    /// Open the tmux import picker with the tmux sessions that match `tmux_import_pattern`
+    /// and have not been imported yet.
+    pub fn start_tmux_import(app_data: &mut AppData) -> AppMode {
+        app_data.input.clear();
+        let sessions = match tmux::list_sessions() {
+            Ok(sessions) => sessions,
+            Err(err) => {
+                return ErrorModalMode::new(TenexError::wrap("Failed to list tmux sessions", &err))
+                    .into();
+            }
+        };
+
+        let pattern = app_data.settings.tmux_import_pattern().to_string();
+        let sessions: Vec<TmuxSession> = sessions
+            .into_iter()
+            .filter(|session| tmux::matches_pattern(&pattern, &session.name))
+            .filter(|session| {
+                !app_data.storage.iter().any(|agent| {
+                    agent.imported_tmux_session.as_deref() == Some(session.name.as_str())
+                })
+            })
+            .collect();
+        if sessions.is_empty() {
+            return ErrorModalMode::new(format!(
+                "No tmux sessions matching \"{pattern}\" to import."
+            ))
+            .into();
+        }
+
+        app_data.tmux_import_picker.start(sessions);
+        TmuxImportPickerMode.into()
+    }
+
+    /// Register `sessions` as root agents that drive the tmux sessions through an attached
+    /// tmux client, so they can be previewed, messaged, and killed like native agents.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a session cannot be started or the agents cannot be saved.
+    pub(crate) fn import_tmux_sessions(
+        self,
+        app_data: &mut AppData,
+        sessions: Vec<TmuxSession>,
+    ) -> Result<()> {
+        let mut last_id = None;
+        for session in sessions {
+            let mut agent = Agent::new(
+                session.name.clone(),
+                tmux::attach_program(&session.name),
+                session.name.clone(),
+                session.path.clone(),
+            );
+            // There is no worktree: the agent works wherever the tmux session does.
+            agent.workspace_kind = WorkspaceKind::PlainDir;
+            agent.repo_root = Some(session.path);
+            agent.imported_tmux_session = Some(session.name);
+            self.launch_tmux_import(app_data, &mut agent)?;
+
+            info!(
+                title = %agent.title,
+                command = %session.command,
+                "Imported tmux session"
+            );
+            last_id = Some(agent.id);
+            app_data.storage.add(agent);
+        }
+
+        app_data.storage.save()?;
+        if let Some(id) = last_id {
+            app_data.select_agent_by_id(id);
+        }
+        Ok(())
+    }
+}
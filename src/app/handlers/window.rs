@@ -1,11 +1,11 @@
-//! Window operations: resize and adjust window indices
+//! Window operations: resize
 
 use std::collections::HashSet;
 
-use tracing::{debug, warn};
+use tracing::warn;
 
 use super::Actions;
-use crate::app::{App, AppData};
+use crate::app::App;
 
 impl Actions {
     /// Resize all agent mux windows to match the preview pane dimensions
@@ -34,19 +34,47 @@ impl Actions {
         }
 
         let targets = self.resize_targets(app);
+        if targets.is_empty() {
+            return true;
+        }
+        // One round trip for every window instead of one per agent.
+        let results = match self.session_manager.resize_windows(&targets, width, height) {
+            Ok(results) => results,
+            Err(err) => {
+                warn!(width, height, error = %err, "Failed to resize agent previews");
+                app.set_status(format!("Failed to resize agent preview: {err}"));
+                return false;
+            }
+        };
+
         let mut resized_all = true;
-        for target in targets {
-            resized_all &= self.resize_agent_window_target(app, &target, width, height);
+        for (target, result) in targets.iter().zip(results) {
+            if let Err(err) = result {
+                warn!(
+                    target,
+                    width,
+                    height,
+                    error = %err,
+                    "Failed to resize agent preview"
+                );
+                app.set_status(format!("Failed to resize agent preview: {err}"));
+                resized_all = false;
+            }
         }
         resized_all
     }
 
     fn resize_targets(self, app: &App) -> Vec<String> {
+        let Ok(sessions) = self.session_manager.list() else {
+            return Vec::new();
+        };
+        let sessions: HashSet<String> = sessions.into_iter().map(|session| session.name).collect();
+
         let mut targets = Vec::new();
         for agent in app.data.storage.iter() {
             if agent.is_root() {
                 // Root agent: resize the session
-                if self.session_manager.exists(&agent.mux_session) {
+                if sessions.contains(&agent.mux_session) {
                     targets.push(agent.mux_session.clone());
                 }
             } else if let Some(window_idx) = agent.window_index {
@@ -58,7 +86,7 @@ impl Actions {
                     };
                     root_agent = parent;
                 }
-                if self.session_manager.exists(&root_agent.mux_session) {
+                if sessions.contains(&root_agent.mux_session) {
                     let window_target = crate::mux::SessionManager::window_target(
                         &root_agent.mux_session,
                         window_idx,
@@ -69,105 +97,4 @@ impl Actions {
         }
         targets
     }
-
-    fn resize_agent_window_target(
-        self,
-        app: &mut App,
-        target: &str,
-        width: u16,
-        height: u16,
-    ) -> bool {
-        match self.session_manager.resize_window(target, width, height) {
-            Ok(()) => true,
-            Err(err) => {
-                warn!(
-                    target,
-                    width,
-                    height,
-                    error = %err,
-                    "Failed to resize agent preview"
-                );
-                app.set_status(format!("Failed to resize agent preview: {err}"));
-                false
-            }
-        }
-    }
-}
-
-/// Adjust window indices for all agents under a root after windows are deleted
-///
-/// This handles the case where the mux renumbers windows and
-/// window indices shift after windows are deleted. We compute the new
-/// indices mathematically rather than relying on window names.
-pub fn adjust_window_indices_after_deletion(
-    app_data: &mut AppData,
-    root_id: uuid::Uuid,
-    deleted_agent_id: uuid::Uuid,
-    deleted_indices: &[u32],
-) {
-    adjust_window_indices_after_deletions(app_data, root_id, &[deleted_agent_id], deleted_indices);
-}
-
-pub fn adjust_window_indices_after_deletions(
-    app_data: &mut AppData,
-    root_id: uuid::Uuid,
-    deleted_agent_ids: &[uuid::Uuid],
-    deleted_indices: &[u32],
-) {
-    if deleted_indices.is_empty() {
-        return;
-    }
-
-    let mut sorted_deleted: Vec<u32> = deleted_indices.to_vec();
-    sorted_deleted.sort_unstable();
-
-    let mut deleted_ids = HashSet::new();
-    for deleted_agent_id in deleted_agent_ids {
-        deleted_ids.insert(*deleted_agent_id);
-        deleted_ids.extend(app_data.storage.descendant_ids(*deleted_agent_id));
-    }
-
-    let descendants_to_update: Vec<uuid::Uuid> = app_data
-        .storage
-        .descendants(root_id)
-        .iter()
-        .filter(|a| !deleted_ids.contains(&a.id))
-        .map(|a| a.id)
-        .collect();
-
-    adjust_window_indices_for_agent_ids(app_data, &descendants_to_update, &sorted_deleted);
-}
-
-fn adjust_window_indices_for_agent_ids(
-    app_data: &mut AppData,
-    agent_ids: &[uuid::Uuid],
-    deleted_indices_sorted: &[u32],
-) {
-    // Update each remaining agent's window index
-    for agent_id in agent_ids {
-        let Some(agent) = app_data.storage.get_mut(*agent_id) else {
-            continue;
-        };
-        if let Some(current_idx) = agent.window_index {
-            // Count how many deleted indices are less than current index
-            let decrement = u32::try_from(
-                deleted_indices_sorted
-                    .iter()
-                    .filter(|&&d| d < current_idx)
-                    .count(),
-            )
-            .unwrap_or(0);
-            if decrement > 0 {
-                let new_idx = current_idx - decrement;
-                debug!(
-                    agent_id = %agent.short_id(),
-                    agent_title = %agent.title,
-                    %current_idx,
-                    %new_idx,
-                    "Adjusting window index after deletion"
-                );
-                agent.window_index = Some(new_idx);
-            }
-        }
-    }
 }
@@ -3,17 +3,29 @@
 mod data;
 mod event;
 mod handlers;
+mod prompt_lint;
 mod settings;
 pub(crate) mod sidebar;
 mod state;
+mod swarm_file;
 
 pub use crate::state::ConfirmAction;
-pub use data::AppData;
+pub use data::{AppData, SynthesisOptions};
 pub use event::{Event, Handler};
 pub use handlers::Actions;
-pub use settings::{AgentProgram, AgentRole, Settings};
+pub use prompt_lint::TokenEstimate;
+pub use settings::{
+    AgentProgram, AgentRole, AutoConnectPolicy, BaseUpdateStrategy, CannedResponse,
+    DirtyWorktreeStrategy, InboxScope, InputHistory, InputHistoryKind, LogRotation,
+    MetricsRecording, Motion, Profile, QuietHours, ReviewFocus, Settings, StatusRules,
+    SynthesisStrategy, Theme, TokenPrices,
+};
 pub(crate) use sidebar::{SidebarAgentInfo, SidebarItem, SidebarProject};
 pub use state::{
-    App, BranchInfo, DiffEdit, DiffLineMeta, InputMode, MuxdVersionMismatchInfo,
-    PaneActivityDigestMode, PreviewSelectionPoint, Tab, WorktreeConflictInfo,
+    AgentResourceUsage, App, AutoConnectCandidate, BranchInfo, ChangeAlerts, DiffEdit,
+    DiffLineMeta, GithubIssue, InputMode, LineEdit, MuxdVersionMismatchInfo, Notification,
+    NotificationSeverity, OpProgress, PaneActivity, PaneActivityDigestMode, PreviewSelectionPoint,
+    PromptReply, RecoveryCandidate, ResourceAlert, SendToTarget, SynthesisFile, Tab,
+    WorktreeConflictInfo,
 };
+pub use swarm_file::{SwarmAgent, SwarmFile, SwarmReview};
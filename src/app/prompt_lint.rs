@@ -0,0 +1,166 @@
+//! Checks of outgoing prompts for mistakes that would waste an agent run.
+//!
+//! These are cheap text checks, not a spell checker: an empty task, template placeholders
+//! left in (`{task}`, `{{name}}`), and a code fence that is never closed. Text inside code is
+//! not checked for placeholders, since braces there are usually code. [`TokenEstimate`]
+//! approximates how much of the model's context a prompt takes.
+
+use crate::conversation::AgentCli;
+
+/// Share of the context window at which a prompt is flagged as close to the limit, in percent.
+const NEAR_LIMIT_PERCENT: usize = 80;
+
+/// Approximate size of a prompt in tokens for the CLI it is sent to.
+///
+/// Tokenizers are not run; the count is derived from the character count using a typical
+/// ratio for each CLI's tokenizer, which is close enough to warn about oversized prompts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenEstimate {
+    /// Estimated tokens in the prompt.
+    pub tokens: usize,
+    /// CLI the prompt is sent to.
+    pub cli: AgentCli,
+}
+
+impl TokenEstimate {
+    /// Estimate the tokens `prompt` takes for `cli`.
+    #[must_use]
+    pub fn new(prompt: &str, cli: AgentCli) -> Self {
+        let chars = prompt.chars().count();
+        let tokens = match cli {
+            // About 3.5 characters per token for English text and code.
+            AgentCli::Claude => chars.saturating_mul(2).div_ceil(7),
+            AgentCli::Codex | AgentCli::Other => chars.div_ceil(4),
+        };
+        Self { tokens, cli }
+    }
+
+    /// Whether the prompt takes most of the CLI's context window.
+    #[must_use]
+    pub fn near_limit(self) -> bool {
+        self.cli.context_window().is_some_and(|limit| {
+            self.tokens.saturating_mul(100) >= limit.saturating_mul(NEAR_LIMIT_PERCENT)
+        })
+    }
+
+    /// Footer text, such as `~1.2k tokens (claude, 200k context)`.
+    #[must_use]
+    pub fn label(self) -> String {
+        let tokens = format_count(self.tokens);
+        match self.cli.context_window() {
+            Some(limit) if self.near_limit() => format!(
+                "~{tokens} tokens, near {}'s {} context limit",
+                self.cli.label(),
+                format_count(limit)
+            ),
+            Some(limit) => format!(
+                "~{tokens} tokens ({}, {} context)",
+                self.cli.label(),
+                format_count(limit)
+            ),
+            None => format!("~{tokens} tokens"),
+        }
+    }
+}
+
+/// `count` shortened to thousands above 1000, such as `1.2k` or `200k`.
+fn format_count(count: usize) -> String {
+    if count < 1000 {
+        count.to_string()
+    } else if count < 10_000 {
+        format!("{}.{}k", count / 1000, count % 1000 / 100)
+    } else {
+        format!("{}k", count / 1000)
+    }
+}
+
+/// Problems found in `prompt`, as short phrases. `task_required` reports an empty prompt.
+pub fn check(prompt: &str, task_required: bool) -> Vec<String> {
+    let mut problems = Vec::new();
+    if prompt.trim().is_empty() {
+        if task_required {
+            problems.push("the task is empty".to_string());
+        }
+        return problems;
+    }
+
+    let mut fences = 0_usize;
+    let mut placeholders: Vec<&str> = Vec::new();
+    for line in prompt.lines() {
+        if is_fence(line) {
+            fences += 1;
+            continue;
+        }
+        if fences % 2 == 1 {
+            continue;
+        }
+        for placeholder in line_placeholders(line) {
+            if !placeholders.contains(&placeholder) {
+                placeholders.push(placeholder);
+            }
+        }
+    }
+
+    if !placeholders.is_empty() {
+        problems.push(format!(
+            "unfilled placeholder{} {}",
+            if placeholders.len() == 1 { "" } else { "s" },
+            placeholders.join(", ")
+        ));
+    }
+    if fences % 2 == 1 {
+        problems.push("a ``` code fence is not closed".to_string());
+    }
+    problems
+}
+
+/// Whether `line` opens or closes a fenced code block.
+fn is_fence(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("```") || line.starts_with("~~~")
+}
+
+/// `{name}` and `{{ name }}` placeholders in `line`, skipping inline code.
+fn line_placeholders(line: &str) -> Vec<&str> {
+    let mut found = Vec::new();
+    let mut in_code = false;
+    let mut rest = line;
+    while let Some(index) = rest.find(['`', '{']) {
+        let from = &rest[index..];
+        if let Some(after) = from.strip_prefix('`') {
+            in_code = !in_code;
+            rest = after;
+            continue;
+        }
+        let len = if in_code { 0 } else { placeholder_len(from) };
+        if len > 0 {
+            found.push(&from[..len]);
+            rest = &from[len..];
+        } else {
+            rest = &from[1..];
+        }
+    }
+    found
+}
+
+/// Length of the placeholder `text` starts with, or `0` if it does not start with one.
+fn placeholder_len(text: &str) -> usize {
+    let (open, close) = if text.starts_with("{{") {
+        ("{{", "}}")
+    } else {
+        ("{", "}")
+    };
+    let Some(end) = text[open.len()..].find(close) else {
+        return 0;
+    };
+    let name = text[open.len()..open.len() + end].trim();
+    let is_name = name.chars().next().is_some_and(char::is_alphabetic)
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-' || c == '.');
+    if is_name {
+        open.len() + end + close.len()
+    } else {
+        0
+    }
+}
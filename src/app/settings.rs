@@ -3,11 +3,16 @@
 //! Stores user preferences that persist across sessions, such as
 //! keyboard remapping choices.
 
+use crate::agent::{Agent, AgentSandbox, RestartPolicy};
+use crate::app::{PaneActivity, ResourceAlert};
 use crate::config::Config;
+use chrono::NaiveTime;
 use semver::Version;
 use serde::{Deserialize, Serialize};
 
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 use tracing::{debug, warn};
 
@@ -39,6 +44,203 @@ impl AgentProgram {
     }
 }
 
+/// How "update from base" brings an agent branch up to date with its base branch.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BaseUpdateStrategy {
+    /// Rebase the agent branch onto the base (linear history).
+    #[default]
+    Rebase,
+    /// Merge the base into the agent branch (no rewritten commits).
+    Merge,
+}
+
+impl BaseUpdateStrategy {
+    /// Lowercase label shown in the UI.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Rebase => "rebase",
+            Self::Merge => "merge",
+        }
+    }
+}
+
+/// What to do with uncommitted changes in a worktree before rebasing or merging into it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DirtyWorktreeStrategy {
+    /// Stash the changes and re-apply them afterwards.
+    #[default]
+    Stash,
+    /// Commit the changes as a work-in-progress commit first.
+    Commit,
+    /// Refuse to start until the worktree is clean.
+    Refuse,
+}
+
+impl DirtyWorktreeStrategy {
+    /// Lowercase label shown in the UI.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Stash => "stash",
+            Self::Commit => "commit",
+            Self::Refuse => "refuse",
+        }
+    }
+}
+
+/// Whether Tenex records usage metrics. Recorded metrics never leave the machine.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricsRecording {
+    /// Record nothing.
+    #[default]
+    Off,
+    /// Record to `metrics.json` in the instance directory.
+    Local,
+}
+
+impl MetricsRecording {
+    /// Whether metrics are recorded.
+    #[must_use]
+    pub const fn is_on(self) -> bool {
+        matches!(self, Self::Local)
+    }
+}
+
+/// Colors the TUI draws with.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Theme {
+    /// The standard dark palette.
+    #[default]
+    Default,
+    /// Bright text and borders on black, in the terminal's basic colors.
+    HighContrast,
+}
+
+impl Theme {
+    /// The theme `/theme` switches to.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Default => Self::HighContrast,
+            Self::HighContrast => Self::Default,
+        }
+    }
+
+    /// Lowercase label shown in the UI.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::HighContrast => "high contrast",
+        }
+    }
+}
+
+/// Whether the TUI animates indicators such as the progress spinner.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Motion {
+    /// Animate indicators.
+    #[default]
+    Full,
+    /// Keep every indicator still.
+    Reduced,
+}
+
+impl Motion {
+    /// Whether indicators may animate.
+    #[must_use]
+    pub const fn animates(self) -> bool {
+        matches!(self, Self::Full)
+    }
+}
+
+/// What a synthesis collects from each descendant for its parent.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SynthesisStrategy {
+    /// Each descendant's full terminal scrollback, after its handoff notes.
+    #[default]
+    Transcripts,
+    /// A summary of each descendant's scrollback, written by the parent's program.
+    Summaries,
+    /// Only the end of each descendant's terminal, where its final message is.
+    FinalMessages,
+    /// The diff of each descendant's worktree against its base branch.
+    Diffs,
+    /// Only the descendants' handoff notes.
+    Handoffs,
+}
+
+impl SynthesisStrategy {
+    /// All strategies, in display order.
+    pub const ALL: &'static [Self] = &[
+        Self::Transcripts,
+        Self::Summaries,
+        Self::FinalMessages,
+        Self::Diffs,
+        Self::Handoffs,
+    ];
+
+    /// Label shown in the UI.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Transcripts => "Transcripts",
+            Self::Summaries => "Summaries",
+            Self::FinalMessages => "Final messages",
+            Self::Diffs => "Diffs",
+            Self::Handoffs => "Handoff notes",
+        }
+    }
+
+    /// One-line description shown in the synthesis picker.
+    #[must_use]
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::Transcripts => "Full scrollback of each agent",
+            Self::Summaries => "Each scrollback summarized by the parent's program first",
+            Self::FinalMessages => "Only the end of each agent's terminal",
+            Self::Diffs => "Each worktree's diff against its base branch",
+            Self::Handoffs => "Only the agents' .tenex/handoff notes",
+        }
+    }
+}
+
+/// What Tenex does on startup with Tenex worktrees that have no agent.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AutoConnectPolicy {
+    /// Leave such worktrees alone.
+    Off,
+    /// Create agents for them with a shell session, without starting the agent program.
+    AdoptOnly,
+    /// Create agents for them and start the agent program, resuming its latest conversation
+    /// in the worktree when one can be found.
+    #[default]
+    Reconnect,
+    /// List them on startup and reconnect the ones the user checks.
+    Ask,
+}
+
+impl AutoConnectPolicy {
+    /// Label shown in the UI.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::AdoptOnly => "adopt-only",
+            Self::Reconnect => "reconnect",
+            Self::Ask => "ask",
+        }
+    }
+}
+
 /// Which kind of agent should be configured in settings.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum AgentRole {
@@ -76,6 +278,381 @@ impl AgentRole {
     }
 }
 
+/// Which agents waiting for input go in the questions inbox.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InboxScope {
+    /// Agents whose last output lines ask a question.
+    #[default]
+    Questions,
+    /// Every agent that stops to wait for input, with its last output line.
+    AllWaiting,
+}
+
+/// A focus that reviewers in a review swarm can be given.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewFocus {
+    /// Short name shown in the focus picker and the reviewer's title (for example `Security`).
+    pub name: String,
+    /// Instructions added to the review prompt of reviewers with this focus.
+    pub instructions: String,
+}
+
+/// A named message that can be sent to agents from the canned responses picker.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CannedResponse {
+    /// Short name shown in the picker (for example `approve`).
+    pub name: String,
+    /// Message sent to the agent.
+    pub text: String,
+}
+
+/// Output text that tells what a program is doing, for agent CLIs whose activity can't be
+/// told from whether their output changes.
+///
+/// Patterns are matched case-insensitively against the last non-empty output lines. The
+/// matching line nearest the bottom decides; when one line matches several kinds, finished
+/// wins over waiting, and waiting over working.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusRules {
+    /// Text of lines shown while the program works (spinners, `esc to interrupt`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub working: Vec<String>,
+    /// Text of lines shown while the program waits for input (its prompt).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub waiting: Vec<String>,
+    /// Text of lines shown once the program is done (exit banners).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub finished: Vec<String>,
+}
+
+impl StatusRules {
+    /// Non-empty output lines, counted from the bottom, matched against the patterns.
+    pub const SCAN_LINES: usize = 8;
+
+    /// The activity `lines` (oldest first) show, if one of the last of them matches a pattern.
+    #[must_use]
+    pub fn classify(&self, lines: &[String]) -> Option<PaneActivity> {
+        let matches = |patterns: &[String], line: &str| {
+            patterns
+                .iter()
+                .filter(|pattern| !pattern.is_empty())
+                .any(|pattern| line.contains(&pattern.to_lowercase()))
+        };
+        lines
+            .iter()
+            .rev()
+            .filter(|line| !line.trim().is_empty())
+            .take(Self::SCAN_LINES)
+            .find_map(|line| {
+                let line = line.to_lowercase();
+                if matches(&self.finished, &line) {
+                    Some(PaneActivity::Finished)
+                } else if matches(&self.waiting, &line) {
+                    Some(PaneActivity::Waiting)
+                } else if matches(&self.working, &line) {
+                    Some(PaneActivity::Active)
+                } else {
+                    None
+                }
+            })
+    }
+}
+
+/// What a program's tokens cost, in US dollars per million tokens.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TokenPrices {
+    /// Input tokens read without the prompt cache.
+    #[serde(default)]
+    pub input: f64,
+    /// Input tokens written to the prompt cache.
+    #[serde(default)]
+    pub cache_write: f64,
+    /// Input tokens read from the prompt cache.
+    #[serde(default)]
+    pub cache_read: f64,
+    /// Output tokens.
+    #[serde(default)]
+    pub output: f64,
+}
+
+impl TokenPrices {
+    /// Built-in prices for Claude Code, at Sonnet's list prices.
+    const CLAUDE: Self = Self {
+        input: 3.0,
+        cache_write: 3.75,
+        cache_read: 0.3,
+        output: 15.0,
+    };
+
+    /// Built-in prices for Codex, at GPT-5's list prices.
+    const CODEX: Self = Self {
+        input: 1.25,
+        cache_write: 1.25,
+        cache_read: 0.125,
+        output: 10.0,
+    };
+
+    /// Estimated cost of `usage`, in millionths of a US dollar.
+    #[must_use]
+    #[expect(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        reason = "token counts and costs are estimates far below the limits of f64 and u64"
+    )]
+    pub fn cost_micros(&self, usage: crate::conversation::TokenUsage) -> u64 {
+        let cost = self.output.mul_add(
+            usage.output as f64,
+            self.cache_read.mul_add(
+                usage.cache_read as f64,
+                self.cache_write
+                    .mul_add(usage.cache_write as f64, self.input * usage.input as f64),
+            ),
+        );
+        cost.max(0.0).round() as u64
+    }
+}
+
+/// Daily window, in local time, during which Tenex sends nothing to agents on its own.
+///
+/// A window whose end comes before its start runs past midnight (`22:00` to `07:00`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuietHours {
+    /// Start of the window, as `HH:MM`.
+    #[serde(with = "clock_time")]
+    pub start: NaiveTime,
+    /// End of the window, as `HH:MM`.
+    #[serde(with = "clock_time")]
+    pub end: NaiveTime,
+}
+
+impl QuietHours {
+    /// Whether `time` falls inside the window.
+    #[must_use]
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+/// Serde helpers for times of day written as `HH:MM`.
+mod clock_time {
+    use chrono::NaiveTime;
+    use serde::{Deserialize, Deserializer, Serializer, de::Error as _};
+
+    #[expect(
+        clippy::trivially_copy_pass_by_ref,
+        reason = "serde's serialize_with passes fields by reference"
+    )]
+    pub fn serialize<S: Serializer>(time: &NaiveTime, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&time.format("%H:%M"))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveTime, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        NaiveTime::parse_from_str(text.trim(), "%H:%M").map_err(|_| {
+            D::Error::custom(format!("invalid time {text:?}; use HH:MM, such as 22:00"))
+        })
+    }
+}
+
+/// `usd` in millionths of a dollar; `None` for amounts that are not positive.
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    reason = "budgets are small positive amounts"
+)]
+fn usd_to_micros(usd: f64) -> Option<u64> {
+    (usd > 0.0).then(|| (usd * 1_000_000.0).round() as u64)
+}
+
+/// When `tenex.log` and the mux daemon's log are rotated, and how many old logs are kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogRotation {
+    /// Size in bytes at which a log is rotated while running (`None` rotates only on startup).
+    pub max_bytes: Option<u64>,
+    /// Rotated logs kept next to each log; `<log>.1` is the newest.
+    pub keep: u32,
+}
+
+/// Text inputs that remember what was submitted in them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputHistoryKind {
+    /// Prompts of new agents.
+    Prompt,
+    /// Messages broadcast to leaf agents.
+    Broadcast,
+    /// Startup commands of terminals.
+    TerminalCommand,
+}
+
+/// Submitted text of the inputs that keep a history, oldest first.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputHistory {
+    /// Prompts of new agents.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub prompts: Vec<String>,
+    /// Messages broadcast to leaf agents.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub broadcasts: Vec<String>,
+    /// Startup commands of terminals.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub terminal_commands: Vec<String>,
+}
+
+impl InputHistory {
+    /// Entries kept per input; older ones are dropped.
+    pub const MAX_ENTRIES: usize = 100;
+
+    /// Whether no input has a history.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.prompts.is_empty() && self.broadcasts.is_empty() && self.terminal_commands.is_empty()
+    }
+
+    /// The history of `kind`, oldest first.
+    #[must_use]
+    pub fn entries(&self, kind: InputHistoryKind) -> &[String] {
+        match kind {
+            InputHistoryKind::Prompt => &self.prompts,
+            InputHistoryKind::Broadcast => &self.broadcasts,
+            InputHistoryKind::TerminalCommand => &self.terminal_commands,
+        }
+    }
+
+    /// Add `entry` as the newest entry of `kind`, moving it there if it is already in the
+    /// history. Blank entries are skipped. Returns whether the history changed.
+    pub fn record(&mut self, kind: InputHistoryKind, entry: &str) -> bool {
+        let entries = match kind {
+            InputHistoryKind::Prompt => &mut self.prompts,
+            InputHistoryKind::Broadcast => &mut self.broadcasts,
+            InputHistoryKind::TerminalCommand => &mut self.terminal_commands,
+        };
+        if entry.trim().is_empty() || entries.last().is_some_and(|last| last == entry) {
+            return false;
+        }
+        entries.retain(|existing| existing != entry);
+        entries.push(entry.to_string());
+        let excess = entries.len().saturating_sub(Self::MAX_ENTRIES);
+        entries.drain(..excess);
+        true
+    }
+}
+
+/// Profile chosen with `--profile` for this process, in place of the saved one.
+static PROFILE_OVERRIDE: OnceLock<String> = OnceLock::new();
+
+/// Named defaults (`work`, `personal`) that replace the matching top-level settings while the
+/// profile is active. Unset fields keep the top-level value.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    /// Program for new default agents.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_program: Option<AgentProgram>,
+    /// Command for new default agents when `agent_program` is `custom`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_agent_command: Option<String>,
+    /// Budget for all agents together, in US dollars.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget_usd: Option<f64>,
+    /// Budget for each agent, in US dollars.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_budget_usd: Option<f64>,
+    /// Directory for the worktrees of new agents.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worktree_dir: Option<PathBuf>,
+    /// Which waiting agents go in the questions inbox.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inbox_scope: Option<InboxScope>,
+    /// Daily window during which held messages and restarts are paused.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quiet_hours: Option<QuietHours>,
+}
+
+impl Profile {
+    /// Replace the settings this profile sets.
+    fn apply_to(&self, settings: &mut Settings) {
+        if let Some(program) = self.agent_program {
+            settings.agent_program = program;
+        }
+        if let Some(command) = &self.custom_agent_command {
+            settings.custom_agent_command.clone_from(command);
+        }
+        if self.budget_usd.is_some() {
+            settings.budget_usd = self.budget_usd;
+        }
+        if self.agent_budget_usd.is_some() {
+            settings.agent_budget_usd = self.agent_budget_usd;
+        }
+        if self.worktree_dir.is_some() {
+            settings.worktree_dir.clone_from(&self.worktree_dir);
+        }
+        if let Some(scope) = self.inbox_scope {
+            settings.inbox_scope = scope;
+        }
+        if self.quiet_hours.is_some() {
+            settings.quiet_hours = self.quiet_hours;
+        }
+    }
+
+    /// Take the values this profile sets back from `settings`, keeping changes made while it
+    /// was active.
+    fn update_from(&mut self, settings: &Settings) {
+        if self.agent_program.is_some() {
+            self.agent_program = Some(settings.agent_program);
+        }
+        if self.custom_agent_command.is_some() {
+            self.custom_agent_command = Some(settings.custom_agent_command.clone());
+        }
+        if self.budget_usd.is_some() {
+            self.budget_usd = settings.budget_usd;
+        }
+        if self.agent_budget_usd.is_some() {
+            self.agent_budget_usd = settings.agent_budget_usd;
+        }
+        if self.worktree_dir.is_some() {
+            self.worktree_dir.clone_from(&settings.worktree_dir);
+        }
+        if self.inbox_scope.is_some() {
+            self.inbox_scope = Some(settings.inbox_scope);
+        }
+        if self.quiet_hours.is_some() {
+            self.quiet_hours = settings.quiet_hours;
+        }
+    }
+
+    /// Put back the values of `unprofiled` that this profile replaced.
+    fn restore(&self, settings: &mut Settings, unprofiled: &Settings) {
+        if self.agent_program.is_some() {
+            settings.agent_program = unprofiled.agent_program;
+        }
+        if self.custom_agent_command.is_some() {
+            settings
+                .custom_agent_command
+                .clone_from(&unprofiled.custom_agent_command);
+        }
+        if self.budget_usd.is_some() {
+            settings.budget_usd = unprofiled.budget_usd;
+        }
+        if self.agent_budget_usd.is_some() {
+            settings.agent_budget_usd = unprofiled.agent_budget_usd;
+        }
+        if self.worktree_dir.is_some() {
+            settings.worktree_dir.clone_from(&unprofiled.worktree_dir);
+        }
+        if self.inbox_scope.is_some() {
+            settings.inbox_scope = unprofiled.inbox_scope;
+        }
+        if self.quiet_hours.is_some() {
+            settings.quiet_hours = unprofiled.quiet_hours;
+        }
+    }
+}
+
 /// Persistent user settings
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Settings {
@@ -111,16 +688,459 @@ pub struct Settings {
     #[serde(default)]
     pub review_custom_agent_command: String,
 
+    /// Commands tried in order when an agent's program is not on `PATH` (`["codex"]`). Empty
+    /// fails with an error instead.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_programs: Vec<String>,
+
+    /// Whether agents are restarted after their program exits, unless an agent has its own
+    /// policy. When unset, root agents restart after any exit and other agents never restart.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restart_policy: Option<RestartPolicy>,
+
+    /// Minutes an agent may run before it is asked to wrap up and then stopped, unless it has
+    /// its own limit. Unset or `0` lets agents run without a limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_runtime_mins: Option<u64>,
+
+    /// Daily window during which held messages are not delivered and exited agents are not
+    /// restarted. Unset has no quiet hours.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quiet_hours: Option<QuietHours>,
+
+    /// Estimated spend, in US dollars, at which every agent is paused until you confirm that
+    /// they may go on. Unset has no limit for all agents together.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget_usd: Option<f64>,
+
+    /// Estimated spend, in US dollars, at which an agent is paused until you confirm that it
+    /// may go on. Unset has no limit per agent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_budget_usd: Option<f64>,
+
+    /// Percent of a budget at which Tenex warns that it is nearly spent. `0` disables the
+    /// warning; unset uses the built-in default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget_warning_percent: Option<u32>,
+
+    /// Token prices, in US dollars per million tokens, keyed by program name. Claude and
+    /// Codex have built-in prices.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub token_prices: BTreeMap<String, TokenPrices>,
+
+    /// Sandbox policy for new default agents
+    #[serde(default)]
+    pub sandbox: AgentSandbox,
+
+    /// Sandbox policy for new planner agents
+    #[serde(default)]
+    pub planner_sandbox: AgentSandbox,
+
+    /// Sandbox policy for new review agents
+    #[serde(default)]
+    pub review_sandbox: AgentSandbox,
+
     /// Whether newly created root agents should start in Docker.
     #[serde(default)]
     pub docker_for_new_roots: bool,
 
+    /// Whether to record usage metrics locally.
+    #[serde(default)]
+    pub metrics: MetricsRecording,
+
+    /// Colors the TUI draws with.
+    #[serde(default)]
+    pub theme: Theme,
+
+    /// Whether the TUI animates indicators.
+    #[serde(default)]
+    pub motion: Motion,
+
+    /// Docker image to use instead of the shipped worker image (a repo devcontainer wins).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docker_image: Option<String>,
+
+    /// Branch prefix override for regular agents (e.g. `agent/`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch_prefix: Option<String>,
+
+    /// Branch prefix override for swarm roots
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub swarm_branch_prefix: Option<String>,
+
+    /// CPU percent (of one core) above which an agent's process tree is flagged as runaway.
+    /// `0` disables the alert; unset uses the built-in default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_alert_cpu_percent: Option<u32>,
+
+    /// Resident memory in MiB above which an agent's process tree is flagged as runaway.
+    /// `0` disables the alert; unset uses the built-in default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_alert_memory_mb: Option<u64>,
+
+    /// Percent of its context window an agent's conversation may fill before it is flagged.
+    /// `0` disables the warning; unset uses the built-in default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_warning_percent: Option<u32>,
+
+    /// Seconds between automatic `git fetch --prune` runs. Unset or `0` disables auto-fetch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_fetch_interval_secs: Option<u64>,
+
+    /// Seconds between status and activity checks of agents that are off screen, not
+    /// selected, and idle. `0` checks every agent every tick; unset uses the built-in default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background_sync_secs: Option<u64>,
+
+    /// Size in MiB at which `tenex.log` and the mux daemon's log are rotated. `0` rotates them
+    /// only on startup; unset uses the built-in default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_max_size_mb: Option<u64>,
+
+    /// Rotated logs kept next to each log. `0` keeps none; unset uses the built-in default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_retention: Option<u32>,
+
+    /// Commits a root agent may fall behind its base branch before it is flagged as stale.
+    /// Unset uses the built-in default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stale_base_commits: Option<u32>,
+
+    /// Whether "update from base" rebases onto or merges in the base branch.
+    #[serde(default)]
+    pub base_update_strategy: BaseUpdateStrategy,
+
+    /// How rebases and merges handle uncommitted changes in the worktree they run in.
+    #[serde(default)]
+    pub dirty_worktree_strategy: DirtyWorktreeStrategy,
+
+    /// What startup does with Tenex worktrees that have no agent.
+    #[serde(default)]
+    pub auto_connect: AutoConnectPolicy,
+
+    /// Session name pattern (`*` and `?` wildcards) for tmux sessions offered by
+    /// `/import_tmux`. Unset offers every session.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tmux_import_pattern: Option<String>,
+
+    /// Days a killed agent's worktree and branch stay in the trash before they are purged.
+    /// `0` deletes them immediately; unset uses the built-in default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trash_retention_days: Option<u32>,
+
+    /// Text sent ahead of a task revised with `e`. Empty sends the revised task alone; unset
+    /// uses the built-in note.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revised_task_preamble: Option<String>,
+
+    /// Seconds to wait for an agent's handoff notes before it is killed or synthesized.
+    /// Unset or `0` kills and synthesizes without asking for handoff notes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub handoff_timeout_secs: Option<u64>,
+
+    /// Strategy the synthesis picker starts on.
+    #[serde(default)]
+    pub synthesis_strategy: SynthesisStrategy,
+
+    /// Focuses offered to reviewers in a review swarm. Empty skips the focus picker; unset
+    /// uses the built-in focuses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub review_focuses: Option<Vec<ReviewFocus>>,
+
+    /// Messages offered by the canned responses picker. Empty disables the picker; unset uses
+    /// the built-in responses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canned_responses: Option<Vec<CannedResponse>>,
+
+    /// Text that marks a waiting agent's output line as a question for the inbox
+    /// (case-insensitive), besides lines ending in `?`. Unset uses the built-in patterns.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub question_patterns: Option<Vec<String>>,
+
+    /// Output patterns that tell when agents are working, waiting for input, or finished, keyed
+    /// by program name (`{"my-agent": {"waiting": ["ready>"]}}`). Agents whose program has no
+    /// rules are judged by whether their output changes.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub status_rules: BTreeMap<String, StatusRules>,
+
+    /// Which waiting agents go in the questions inbox.
+    #[serde(default)]
+    pub inbox_scope: InboxScope,
+
+    /// Repositories listed in the sidebar even when no agent works in them (`/repos`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub repos: Vec<PathBuf>,
+
+    /// Globs of files agents should not change (`**/migrations/**`). Agents that change them
+    /// are flagged in the sidebar.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub protected_paths: Vec<String>,
+
+    /// Shell commands run in the worktree before Tenex pushes (format, lint, generate). A
+    /// failing command stops the push until the user chooses to push anyway.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pre_push_commands: Vec<String>,
+
+    /// Whether prompts are checked for an empty task, leftover template placeholders, and
+    /// unclosed code fences before they are sent. Unset checks them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_checks: Option<bool>,
+
+    /// What was submitted in the new agent prompt, broadcast, and terminal command inputs,
+    /// recalled with `Up` and `Down`.
+    #[serde(default, skip_serializing_if = "InputHistory::is_empty")]
+    pub input_history: InputHistory,
+
     /// The most recent Tenex version for which the user has seen "What's New".
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub last_seen_version: Option<String>,
+
+    /// Directory for the worktrees of new agents. Unset uses `worktrees/` in the instance
+    /// directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worktree_dir: Option<PathBuf>,
+
+    /// Named profiles, each replacing some of these settings while it is active.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
+
+    /// Profile active when Tenex starts without `--profile`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+
+    /// Profile whose values replace the matching settings now.
+    #[serde(skip)]
+    active_profile: Option<String>,
+
+    /// These settings as they were before the active profile replaced some of them.
+    #[serde(skip)]
+    unprofiled: Option<Box<Self>>,
 }
 
 impl Settings {
+    const DEFAULT_RESOURCE_ALERT_CPU_PERCENT: u32 = 400;
+    const DEFAULT_RESOURCE_ALERT_MEMORY_MB: u64 = 8192;
+    const DEFAULT_STALE_BASE_COMMITS: u32 = 20;
+    const DEFAULT_TRASH_RETENTION_DAYS: u32 = 7;
+    const DEFAULT_BACKGROUND_SYNC_SECS: u64 = 5;
+    const DEFAULT_LOG_MAX_SIZE_MB: u64 = 10;
+    const DEFAULT_CONTEXT_WARNING_PERCENT: u32 = 80;
+    const DEFAULT_BUDGET_WARNING_PERCENT: u32 = 80;
+    const DEFAULT_LOG_RETENTION: u32 = 3;
+
+    /// Text sent ahead of a revised task (may be empty).
+    #[must_use]
+    pub fn revised_task_preamble(&self) -> &str {
+        self.revised_task_preamble
+            .as_deref()
+            .unwrap_or(crate::prompts::REVISED_TASK_PREAMBLE)
+    }
+
+    /// Focuses offered to reviewers in a review swarm.
+    #[must_use]
+    pub fn review_focuses(&self) -> Vec<ReviewFocus> {
+        self.review_focuses.clone().unwrap_or_else(|| {
+            crate::prompts::DEFAULT_REVIEW_FOCUSES
+                .iter()
+                .map(|(name, instructions)| ReviewFocus {
+                    name: (*name).to_string(),
+                    instructions: (*instructions).to_string(),
+                })
+                .collect()
+        })
+    }
+
+    /// Messages offered by the canned responses picker.
+    #[must_use]
+    pub fn canned_responses(&self) -> Vec<CannedResponse> {
+        self.canned_responses.clone().unwrap_or_else(|| {
+            crate::prompts::DEFAULT_CANNED_RESPONSES
+                .iter()
+                .map(|(name, text)| CannedResponse {
+                    name: (*name).to_string(),
+                    text: (*text).to_string(),
+                })
+                .collect()
+        })
+    }
+
+    /// Text that marks a waiting agent's output line as a question.
+    #[must_use]
+    pub fn question_patterns(&self) -> Vec<String> {
+        self.question_patterns.clone().unwrap_or_else(|| {
+            crate::app::state::DEFAULT_QUESTION_PATTERNS
+                .iter()
+                .map(|pattern| (*pattern).to_string())
+                .collect()
+        })
+    }
+
+    /// Status rules for agents running the command line `program`, looked up by the name of its
+    /// executable.
+    #[must_use]
+    pub fn status_rules_for(&self, program: &str) -> Option<&StatusRules> {
+        if self.status_rules.is_empty() {
+            return None;
+        }
+        self.status_rules
+            .get(&crate::command::program_name(program)?)
+    }
+
+    /// Token prices for agents running the command line `program`: the `token_prices` entry
+    /// for its executable, or the built-in prices of Claude and Codex.
+    #[must_use]
+    pub fn token_prices_for(&self, program: &str) -> Option<TokenPrices> {
+        let custom = crate::command::program_name(program)
+            .and_then(|name| self.token_prices.get(&name).copied());
+        custom.or_else(|| match crate::conversation::detect_agent_cli(program) {
+            crate::conversation::AgentCli::Claude => Some(TokenPrices::CLAUDE),
+            crate::conversation::AgentCli::Codex => Some(TokenPrices::CODEX),
+            crate::conversation::AgentCli::Other => None,
+        })
+    }
+
+    /// Hard limit on the estimated spend of all agents together, in millionths of a dollar.
+    #[must_use]
+    pub fn budget_micros(&self) -> Option<u64> {
+        self.budget_usd.and_then(usd_to_micros)
+    }
+
+    /// Hard limit on the estimated spend of each agent, in millionths of a dollar.
+    #[must_use]
+    pub fn agent_budget_micros(&self) -> Option<u64> {
+        self.agent_budget_usd.and_then(usd_to_micros)
+    }
+
+    /// Percent of a budget at which Tenex warns, unless disabled.
+    #[must_use]
+    pub fn budget_warning_percent(&self) -> Option<u32> {
+        Some(
+            self.budget_warning_percent
+                .unwrap_or(Self::DEFAULT_BUDGET_WARNING_PERCENT),
+        )
+        .filter(|percent| *percent > 0)
+    }
+
+    /// Pattern tmux session names must match to be offered for import.
+    #[must_use]
+    pub fn tmux_import_pattern(&self) -> &str {
+        self.tmux_import_pattern
+            .as_deref()
+            .unwrap_or(crate::tmux::DEFAULT_IMPORT_PATTERN)
+    }
+
+    /// Interval between automatic fetches, if auto-fetch is enabled.
+    #[must_use]
+    pub fn auto_fetch_interval(&self) -> Option<std::time::Duration> {
+        self.auto_fetch_interval_secs
+            .filter(|secs| *secs > 0)
+            .map(std::time::Duration::from_secs)
+    }
+
+    /// Interval between checks of background agents (off screen, not selected, and idle).
+    #[must_use]
+    pub fn background_sync_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(
+            self.background_sync_secs
+                .unwrap_or(Self::DEFAULT_BACKGROUND_SYNC_SECS),
+        )
+    }
+
+    /// Whether outgoing prompts are checked before they are sent.
+    #[must_use]
+    pub fn prompt_checks(&self) -> bool {
+        self.prompt_checks.unwrap_or(true)
+    }
+
+    /// When logs are rotated and how many rotated logs are kept.
+    #[must_use]
+    pub fn log_rotation(&self) -> LogRotation {
+        let max_size_mb = self
+            .log_max_size_mb
+            .unwrap_or(Self::DEFAULT_LOG_MAX_SIZE_MB);
+        LogRotation {
+            max_bytes: (max_size_mb > 0).then(|| max_size_mb.saturating_mul(1024 * 1024)),
+            keep: self.log_retention.unwrap_or(Self::DEFAULT_LOG_RETENTION),
+        }
+    }
+
+    /// How long to wait for handoff notes, if agents are asked for them before being killed or
+    /// synthesized.
+    #[must_use]
+    pub fn handoff_timeout(&self) -> Option<std::time::Duration> {
+        self.handoff_timeout_secs
+            .filter(|secs| *secs > 0)
+            .map(std::time::Duration::from_secs)
+    }
+
+    /// How many commits behind its base an agent can be before it is flagged as stale.
+    #[must_use]
+    pub fn stale_base_commits(&self) -> u32 {
+        self.stale_base_commits
+            .unwrap_or(Self::DEFAULT_STALE_BASE_COMMITS)
+            .max(1)
+    }
+
+    /// How long killed agents stay in the trash, if the trash is enabled.
+    #[must_use]
+    pub fn trash_retention(&self) -> Option<chrono::Duration> {
+        let days = self
+            .trash_retention_days
+            .unwrap_or(Self::DEFAULT_TRASH_RETENTION_DAYS);
+        (days > 0).then(|| chrono::Duration::days(i64::from(days)))
+    }
+
+    /// Percent of the context window at which an agent is flagged, unless disabled.
+    #[must_use]
+    pub fn context_warning_percent(&self) -> Option<u32> {
+        Some(
+            self.context_warning_percent
+                .unwrap_or(Self::DEFAULT_CONTEXT_WARNING_PERCENT),
+        )
+        .filter(|percent| *percent > 0)
+    }
+
+    /// Thresholds used to flag runaway agent process trees.
+    #[must_use]
+    pub fn resource_alert(&self) -> ResourceAlert {
+        let cpu_percent = self
+            .resource_alert_cpu_percent
+            .unwrap_or(Self::DEFAULT_RESOURCE_ALERT_CPU_PERCENT);
+        let memory_mb = self
+            .resource_alert_memory_mb
+            .unwrap_or(Self::DEFAULT_RESOURCE_ALERT_MEMORY_MB);
+        ResourceAlert {
+            cpu_percent: (cpu_percent > 0).then_some(cpu_percent),
+            rss_bytes: (memory_mb > 0).then(|| memory_mb.saturating_mul(1024 * 1024)),
+        }
+    }
+
+    /// Restart policy of `agent`: its own, else the `restart_policy` setting, else restarting
+    /// root agents after any exit.
+    #[must_use]
+    pub fn restart_policy_for(&self, agent: &Agent) -> RestartPolicy {
+        agent
+            .restart_policy
+            .or(self.restart_policy)
+            .unwrap_or_else(|| {
+                if agent.is_root() {
+                    RestartPolicy::for_root_agents()
+                } else {
+                    RestartPolicy::default()
+                }
+            })
+    }
+
+    /// Sandbox policy configured for agents of the given role.
+    #[must_use]
+    pub const fn sandbox_for(&self, role: AgentRole) -> AgentSandbox {
+        match role {
+            AgentRole::Default => self.sandbox,
+            AgentRole::Planner => self.planner_sandbox,
+            AgentRole::Review => self.review_sandbox,
+        }
+    }
+
     fn deserialize_with_upgrade_defaults(content: &str) -> Result<Self, serde_json::Error> {
         let mut value: serde_json::Value = serde_json::from_str(content)?;
 
@@ -157,15 +1177,77 @@ impl Settings {
         serde_json::from_value(value)
     }
 
+    /// Use the profile `name` in place of the saved one for the rest of this process.
+    pub fn use_profile_for_process(name: &str) {
+        let _ = PROFILE_OVERRIDE.set(name.to_string());
+    }
+
+    /// Name of the active profile.
+    #[must_use]
+    pub fn active_profile(&self) -> Option<&str> {
+        self.active_profile.as_deref()
+    }
+
+    /// Make `name` the active profile, or use none. Returns `false`, changing nothing, when
+    /// there is no profile named `name`.
+    pub fn activate_profile(&mut self, name: Option<&str>) -> bool {
+        let profile = match name {
+            Some(name) => match self.profiles.get(name) {
+                Some(profile) => Some(profile.clone()),
+                None => return false,
+            },
+            None => None,
+        };
+        self.deactivate_profile();
+        if let (Some(name), Some(profile)) = (name, profile) {
+            self.unprofiled = Some(Box::new(self.clone()));
+            profile.apply_to(self);
+            self.active_profile = Some(name.to_string());
+        }
+        true
+    }
+
+    /// Put back the settings the active profile replaced, keeping any changes made since in
+    /// the profile.
+    fn deactivate_profile(&mut self) {
+        let Some(unprofiled) = self.unprofiled.take() else {
+            return;
+        };
+        let Some(name) = self.active_profile.take() else {
+            return;
+        };
+        let Some(mut profile) = self.profiles.get(&name).cloned() else {
+            return;
+        };
+        profile.update_from(self);
+        profile.restore(self, &unprofiled);
+        self.profiles.insert(name, profile);
+    }
+
     /// Get the settings file path
     #[must_use]
     pub fn path() -> PathBuf {
         Config::settings_path()
     }
 
-    /// Load settings from disk, returning defaults if file doesn't exist
+    /// Load settings from disk, returning defaults if file doesn't exist, and activate the
+    /// profile chosen with `--profile` or else the saved one.
     #[must_use]
     pub fn load() -> Self {
+        let mut settings = Self::load_unprofiled();
+        let profile = PROFILE_OVERRIDE
+            .get()
+            .cloned()
+            .or_else(|| settings.profile.clone());
+        if let Some(name) = profile
+            && !settings.activate_profile(Some(&name))
+        {
+            warn!("Unknown profile {name} in settings");
+        }
+        settings
+    }
+
+    fn load_unprofiled() -> Self {
         let path = Self::path();
         if !path.exists() {
             debug!("Settings file not found, using defaults");
@@ -204,7 +1286,9 @@ impl Settings {
             .ok_or_else(|| std::io::Error::other("Settings path has no parent directory"))?;
         std::fs::create_dir_all(parent)?;
 
-        let content = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        let mut on_disk = self.clone();
+        on_disk.deactivate_profile();
+        let content = serde_json::to_string_pretty(&on_disk).map_err(std::io::Error::other)?;
 
         std::fs::write(&path, content)?;
         debug!("Saved settings to {:?}", path);
@@ -221,6 +1305,35 @@ impl Settings {
         self.save()
     }
 
+    /// Register `root` in the workspace and save. Returns whether it was not registered yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the settings file cannot be written.
+    pub fn register_repo(&mut self, root: PathBuf) -> std::io::Result<bool> {
+        if self.repos.contains(&root) {
+            return Ok(false);
+        }
+        self.repos.push(root);
+        self.save()?;
+        Ok(true)
+    }
+
+    /// Remove `root` from the workspace and save. Returns whether it was registered.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the settings file cannot be written.
+    pub fn unregister_repo(&mut self, root: &Path) -> std::io::Result<bool> {
+        let count = self.repos.len();
+        self.repos.retain(|repo| repo != root);
+        if self.repos.len() == count {
+            return Ok(false);
+        }
+        self.save()?;
+        Ok(true)
+    }
+
     /// Enable the merge key remap and save
     ///
     /// # Errors
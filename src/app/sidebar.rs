@@ -1,6 +1,6 @@
-use crate::agent::{Agent, VisibleAgentInfo};
+use crate::agent::{Agent, Status, VisibleAgentInfo};
 use crate::app::AppData;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
@@ -93,11 +93,17 @@ impl AppData {
             roots_by_project.entry(project_root).or_default().push(root);
         }
 
-        if let Some(cwd_root) = self.cwd_project_root.clone() {
-            if !project_order.contains(&cwd_root) {
-                project_order.push(cwd_root.clone());
+        // The current directory and registered repositories are listed even without agents.
+        for project_root in self
+            .cwd_project_root
+            .iter()
+            .chain(&self.settings.repos)
+            .cloned()
+        {
+            if !project_order.contains(&project_root) {
+                project_order.push(project_root.clone());
             }
-            agent_counts_by_project.entry(cwd_root).or_insert(0);
+            agent_counts_by_project.entry(project_root).or_insert(0);
         }
 
         let mut name_counts: HashMap<String, usize> = HashMap::new();
@@ -165,6 +171,37 @@ impl AppData {
         self.sidebar_items().len()
     }
 
+    /// Rows of the agent list that fit on screen.
+    pub(crate) fn agent_list_viewport_height(&self) -> usize {
+        // `preview_dimensions` stores the preview inner height, which is `frame_height - 4`.
+        // The agent list inner height is `frame_height - 3` (one line taller, because it has no tab bar).
+        usize::from(self.ui.preview_dimensions.map_or(20, |(_, h)| h)).saturating_add(1)
+    }
+
+    /// Agents whose status and activity are checked every tick: those on screen in the agent
+    /// list, the selected one, and those starting up or producing output.
+    pub(crate) fn foreground_agent_ids(&self) -> HashSet<Uuid> {
+        let mut ids: HashSet<Uuid> = self
+            .sidebar_items()
+            .into_iter()
+            .skip(self.ui.agent_list_scroll)
+            .take(self.agent_list_viewport_height())
+            .filter_map(|item| match item {
+                SidebarItem::Agent(agent) => Some(agent.info.agent.id),
+                SidebarItem::Project(_) => None,
+            })
+            .collect();
+        ids.extend(self.selected_agent().map(|agent| agent.id));
+        ids.extend(self.ui.active_agent_ids());
+        ids.extend(
+            self.storage
+                .iter()
+                .filter(|agent| agent.status == Status::Starting)
+                .map(|agent| agent.id),
+        );
+        ids
+    }
+
     pub(crate) fn selected_sidebar_item(&self) -> Option<SidebarItem<'_>> {
         self.sidebar_items().get(self.selected).cloned()
     }
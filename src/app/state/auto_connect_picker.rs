@@ -0,0 +1,96 @@
+//! Auto-connect picker state: choosing which agent-less worktrees to reconnect on startup
+
+use std::path::PathBuf;
+
+/// A Tenex worktree without an agent that auto-connect can create one for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutoConnectCandidate {
+    /// Title for the new agent.
+    pub title: String,
+    /// The worktree's branch.
+    pub branch: String,
+    /// Canonical worktree path.
+    pub worktree_path: PathBuf,
+    /// Repository the worktree belongs to.
+    pub repo_root: PathBuf,
+}
+
+/// State for the startup auto-connect picker (`auto_connect: "ask"`)
+#[derive(Debug, Default)]
+pub struct AutoConnectPickerState {
+    /// Worktrees that have no agent.
+    pub candidates: Vec<AutoConnectCandidate>,
+
+    /// Whether each candidate is checked for reconnecting
+    pub checked: Vec<bool>,
+
+    /// Currently selected index
+    pub selected: usize,
+}
+
+impl AutoConnectPickerState {
+    /// Create a new auto-connect picker state
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            candidates: Vec::new(),
+            checked: Vec::new(),
+            selected: 0,
+        }
+    }
+
+    /// Start the picker with the given candidates, all checked
+    pub fn start(&mut self, candidates: Vec<AutoConnectCandidate>) {
+        self.checked = vec![true; candidates.len()];
+        self.candidates = candidates;
+        self.selected = 0;
+    }
+
+    /// Select next candidate
+    pub const fn select_next(&mut self) {
+        let count = self.candidates.len();
+        if count > 0 {
+            self.selected = (self.selected + 1) % count;
+        }
+    }
+
+    /// Select previous candidate
+    pub fn select_prev(&mut self) {
+        let count = self.candidates.len();
+        if count > 0 {
+            self.selected = self.selected.checked_sub(1).unwrap_or(count - 1);
+        }
+    }
+
+    /// Check or uncheck the selected candidate
+    pub fn toggle_selected(&mut self) {
+        if let Some(checked) = self.checked.get_mut(self.selected) {
+            *checked = !*checked;
+        }
+    }
+
+    /// Check every candidate, or uncheck them all when all are already checked
+    pub fn toggle_all(&mut self) {
+        let check = !self.checked.iter().all(|checked| *checked);
+        self.checked.fill(check);
+    }
+
+    /// Take the checked candidates, clearing the picker
+    pub fn take_checked(&mut self) -> Vec<AutoConnectCandidate> {
+        let checked = std::mem::take(&mut self.checked);
+        let candidates = std::mem::take(&mut self.candidates);
+        self.selected = 0;
+        candidates
+            .into_iter()
+            .zip(checked)
+            .filter_map(|(candidate, checked)| checked.then_some(candidate))
+            .collect()
+    }
+
+    /// Clear all auto-connect picker state
+    pub fn clear(&mut self) {
+        self.candidates.clear();
+        self.checked.clear();
+        self.selected = 0;
+    }
+}
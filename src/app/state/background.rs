@@ -0,0 +1,441 @@
+//! Background operations: long-running git work that runs off the UI thread.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Instant;
+
+use anyhow::{Context, Result};
+use uuid::Uuid;
+
+use crate::app::AppData;
+use crate::state::{AppMode, AuthPromptMode};
+
+use super::App;
+
+/// Frames for the status bar spinner shown next to in-flight operations.
+const SPINNER_FRAMES: &[char] = &['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+const SPINNER_FRAME_MS: u128 = 100;
+
+/// Applied on the UI thread once a background operation's work has finished.
+pub type BackgroundCompletion = Box<dyn FnOnce(&mut AppData) -> Result<AppMode> + Send>;
+
+/// What a running operation shares with the UI while it works: a progress detail, such as
+/// git's transfer progress, and an authentication prompt it is waiting on.
+#[derive(Debug, Default)]
+pub struct OpProgress {
+    state: Mutex<ProgressState>,
+}
+
+#[derive(Debug, Default)]
+struct ProgressState {
+    detail: String,
+    prompt: Option<String>,
+    reply: Option<PromptReply>,
+    declines_prompts: bool,
+}
+
+/// The user's reply to an authentication prompt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PromptReply {
+    /// Text to send, such as a password.
+    Answer(String),
+    /// The user declined the prompt, failing the operation.
+    Declined,
+}
+
+impl OpProgress {
+    fn with<T>(&self, f: impl FnOnce(&mut ProgressState) -> T) -> T {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        f(&mut state)
+    }
+
+    /// Replace the progress detail shown after the operation's label.
+    pub fn set_detail(&self, detail: impl Into<String>) {
+        let detail = detail.into();
+        self.with(|state| state.detail = detail);
+    }
+
+    /// The progress detail shown after the operation's label (empty when there is none).
+    #[must_use]
+    pub fn detail(&self) -> String {
+        self.with(|state| state.detail.clone())
+    }
+
+    /// Ask the user `prompt`; the reply comes from [`Self::take_reply`]. The prompt is
+    /// declined right away when nobody can answer it.
+    pub fn ask(&self, prompt: String) {
+        self.with(|state| {
+            if state.declines_prompts {
+                state.reply = Some(PromptReply::Declined);
+            } else {
+                state.prompt = Some(prompt);
+            }
+        });
+    }
+
+    /// The reply to the last prompt, once the user gave one.
+    #[must_use]
+    pub fn take_reply(&self) -> Option<PromptReply> {
+        self.with(|state| state.reply.take())
+    }
+
+    /// The prompt the operation is waiting on.
+    #[must_use]
+    pub fn prompt(&self) -> Option<String> {
+        self.with(|state| state.prompt.clone())
+    }
+
+    /// Reply to the waiting prompt.
+    pub fn reply(&self, reply: PromptReply) {
+        self.with(|state| {
+            state.prompt = None;
+            state.reply = Some(reply);
+        });
+    }
+
+    /// Decline the waiting prompt and every later one (nobody is there to answer them).
+    pub fn decline_prompts(&self) {
+        self.with(|state| {
+            state.declines_prompts = true;
+            if state.prompt.take().is_some() {
+                state.reply = Some(PromptReply::Declined);
+            }
+        });
+    }
+}
+
+/// A single in-flight background operation.
+#[derive(Debug)]
+pub struct BackgroundOp {
+    /// Agent the operation was started for (`None` for repository-wide work like fetch).
+    pub agent_id: Option<Uuid>,
+    /// Human-readable progress label (for example, `Pushing feature/x`).
+    pub label: String,
+    /// When the operation started.
+    pub started_at: Instant,
+    /// Set to ask the worker to stop (only for operations that can be cancelled safely).
+    cancel: Option<Arc<AtomicBool>>,
+    progress: Arc<OpProgress>,
+    receiver: Receiver<BackgroundCompletion>,
+}
+
+impl BackgroundOp {
+    /// Progress line for the status bar (spinner, label and elapsed seconds).
+    ///
+    /// Without `animate`, a still `…` stands in for the spinner.
+    #[must_use]
+    pub fn progress_line(&self, now: Instant, animate: bool) -> String {
+        let elapsed = now.saturating_duration_since(self.started_at);
+        let frame_index = elapsed.as_millis() / SPINNER_FRAME_MS;
+        let frame = usize::try_from(frame_index)
+            .ok()
+            .filter(|_| animate)
+            .and_then(|index| SPINNER_FRAMES.get(index % SPINNER_FRAMES.len()))
+            .copied()
+            .unwrap_or(if animate { '⠋' } else { '…' });
+        let seconds = elapsed.as_secs();
+        let label = if self.progress.prompt().is_some() {
+            format!("{}: waiting for authentication", self.label)
+        } else {
+            let detail = self.progress.detail();
+            if detail.is_empty() {
+                self.label.clone()
+            } else {
+                format!("{}: {detail}", self.label)
+            }
+        };
+        if self.is_cancelling() {
+            format!("{frame} Cancelling: {label} ({seconds}s)")
+        } else if self.is_cancellable() {
+            format!("{frame} {label} ({seconds}s, Esc cancels)")
+        } else {
+            format!("{frame} {label} ({seconds}s)")
+        }
+    }
+
+    /// What the operation reports while it works.
+    #[must_use]
+    pub fn progress(&self) -> &OpProgress {
+        &self.progress
+    }
+
+    /// Whether the operation can be cancelled.
+    #[must_use]
+    pub const fn is_cancellable(&self) -> bool {
+        self.cancel.is_some()
+    }
+
+    /// Whether cancellation was requested.
+    #[must_use]
+    pub fn is_cancelling(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+    }
+}
+
+/// Tracks background operations until their results are applied.
+#[derive(Debug, Default)]
+pub struct BackgroundOps {
+    ops: Vec<BackgroundOp>,
+    /// Modes finished operations asked for while the user was busy in a modal, oldest first.
+    deferred_modes: Vec<AppMode>,
+}
+
+impl BackgroundOps {
+    /// Create an empty tracker.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            ops: Vec::new(),
+            deferred_modes: Vec::new(),
+        }
+    }
+
+    /// Run `work` on a worker thread and track it until it completes.
+    ///
+    /// `work` performs the slow part (git subprocesses) and returns a completion that is
+    /// applied to the app on the UI thread.
+    pub fn spawn<F>(&mut self, agent_id: Option<Uuid>, label: impl Into<String>, work: F)
+    where
+        F: FnOnce() -> BackgroundCompletion + Send + 'static,
+    {
+        self.start(agent_id, label.into(), None, move |_, _| work());
+    }
+
+    /// Like [`Self::spawn`], but the operation can be cancelled with [`Self::cancel_latest`].
+    ///
+    /// `work` receives the cancellation flag and must stop (restoring any partial state) once
+    /// it is set; see [`crate::git::output_cancellable`].
+    pub fn spawn_cancellable<F>(
+        &mut self,
+        agent_id: Option<Uuid>,
+        label: impl Into<String>,
+        work: F,
+    ) where
+        F: FnOnce(&AtomicBool) -> BackgroundCompletion + Send + 'static,
+    {
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.start(agent_id, label.into(), Some(cancel), move |cancelled, _| {
+            work(cancelled)
+        });
+    }
+
+    /// Like [`Self::spawn_cancellable`], but `work` also receives an [`OpProgress`] to report
+    /// progress on and to ask the user for credentials through.
+    pub fn spawn_with_progress<F>(
+        &mut self,
+        agent_id: Option<Uuid>,
+        label: impl Into<String>,
+        work: F,
+    ) where
+        F: FnOnce(&AtomicBool, &Arc<OpProgress>) -> BackgroundCompletion + Send + 'static,
+    {
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.start(agent_id, label.into(), Some(cancel), work);
+    }
+
+    fn start<F>(
+        &mut self,
+        agent_id: Option<Uuid>,
+        label: String,
+        cancel: Option<Arc<AtomicBool>>,
+        work: F,
+    ) where
+        F: FnOnce(&AtomicBool, &Arc<OpProgress>) -> BackgroundCompletion + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+        let worker_cancel = cancel
+            .clone()
+            .unwrap_or_else(|| Arc::new(AtomicBool::new(false)));
+        let progress = Arc::new(OpProgress::default());
+        let worker_progress = Arc::clone(&progress);
+        std::thread::spawn(move || {
+            let _ = sender.send(work(&worker_cancel, &worker_progress));
+        });
+        self.ops.push(BackgroundOp {
+            agent_id,
+            label,
+            started_at: Instant::now(),
+            cancel,
+            progress,
+            receiver,
+        });
+    }
+
+    /// Ask the most recently started cancellable operation to stop.
+    ///
+    /// Returns its label, or `None` if nothing running can be cancelled.
+    pub fn cancel_latest(&self) -> Option<&str> {
+        let op = self
+            .ops
+            .iter()
+            .rev()
+            .find(|op| op.is_cancellable() && !op.is_cancelling())?;
+        if let Some(cancel) = &op.cancel {
+            cancel.store(true, Ordering::Relaxed);
+        }
+        Some(&op.label)
+    }
+
+    /// The first authentication prompt an operation is waiting on.
+    #[must_use]
+    pub fn pending_prompt(&self) -> Option<String> {
+        self.ops.iter().find_map(|op| op.progress.prompt())
+    }
+
+    /// Reply to the prompt returned by [`Self::pending_prompt`].
+    pub fn reply_to_prompt(&self, reply: PromptReply) {
+        if let Some(op) = self.ops.iter().find(|op| op.progress.prompt().is_some()) {
+            op.progress.reply(reply);
+        }
+    }
+
+    /// Whether an operation is already running for `agent_id`.
+    #[must_use]
+    pub fn is_busy(&self, agent_id: Uuid) -> bool {
+        self.ops.iter().any(|op| op.agent_id == Some(agent_id))
+    }
+
+    /// Whether repository-wide work (like a fetch) is running.
+    #[must_use]
+    pub fn has_repo_wide_op(&self) -> bool {
+        self.ops.iter().any(|op| op.agent_id.is_none())
+    }
+
+    /// In-flight operations, oldest first.
+    #[must_use]
+    pub fn in_flight(&self) -> &[BackgroundOp] {
+        &self.ops
+    }
+
+    /// Remove finished operations and return their completions (with their labels).
+    fn take_finished(&mut self) -> Vec<(String, Result<BackgroundCompletion>)> {
+        let mut finished = Vec::new();
+        self.ops.retain(|op| match op.receiver.try_recv() {
+            Ok(completion) => {
+                finished.push((op.label.clone(), Ok(completion)));
+                false
+            }
+            Err(TryRecvError::Empty) => true,
+            Err(TryRecvError::Disconnected) => {
+                finished.push((
+                    op.label.clone(),
+                    Err(anyhow::anyhow!("worker thread stopped unexpectedly")),
+                ));
+                false
+            }
+        });
+        finished
+    }
+
+    /// Wait for every in-flight operation and return its completion (with its label).
+    fn wait_all(&mut self) -> Vec<(String, Result<BackgroundCompletion>)> {
+        self.ops
+            .drain(..)
+            .map(|op| {
+                let completion = op
+                    .receiver
+                    .recv()
+                    .map_err(|_| anyhow::anyhow!("worker thread stopped unexpectedly"));
+                (op.label, completion)
+            })
+            .collect()
+    }
+}
+
+impl AppData {
+    /// Block until every background operation has finished, applying its results.
+    ///
+    /// For callers without a UI loop to pick results up, such as [`crate::api`]. Modes the
+    /// completions return are dropped, except error modals, which count as failures.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first failure once every operation has finished.
+    pub(crate) fn wait_for_background_ops(&mut self) -> Result<()> {
+        let mut first_error = None;
+        while !self.background_ops.ops.is_empty() {
+            for op in &self.background_ops.ops {
+                op.progress.decline_prompts();
+            }
+            for (label, completion) in self.background_ops.wait_all() {
+                let result = completion
+                    .and_then(|completion| completion(self))
+                    .and_then(|mode| match mode {
+                        AppMode::ErrorModal(state) => Err(state.error.into()),
+                        _ => Ok(()),
+                    })
+                    .with_context(|| format!("{label} failed"));
+                if let Err(err) = result {
+                    first_error.get_or_insert(err);
+                }
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+}
+
+impl App {
+    /// Apply the results of background operations that have finished.
+    ///
+    /// Results never interrupt a modal the user is working in: while one is open, failures and
+    /// successes are reported in the status bar instead, and any other mode a result asks for
+    /// opens once the user is back in normal mode.
+    pub fn finish_background_ops(&mut self) {
+        for (label, completion) in self.data.background_ops.take_finished() {
+            let next = completion.and_then(|completion| completion(&mut self.data));
+            let next = match next {
+                Ok(next) => next,
+                Err(err) if err.is::<crate::git::Cancelled>() => {
+                    self.set_status(format!("Cancelled: {label}"));
+                    continue;
+                }
+                Err(err) => {
+                    self.notify_error(format!("{label} failed: {err:#}"));
+                    continue;
+                }
+            };
+
+            let user_is_idle = matches!(self.mode, AppMode::Normal(_));
+            match next {
+                AppMode::Normal(_) => {}
+                AppMode::ErrorModal(state) if !user_is_idle => {
+                    self.notify_error(state.error.to_string());
+                }
+                AppMode::SuccessModal(state) if !user_is_idle => self.set_status(state.message),
+                other if user_is_idle => self.apply_mode(other),
+                other => {
+                    self.set_status(format!(
+                        "{label} finished; its next step opens when you close this dialog"
+                    ));
+                    self.data.background_ops.deferred_modes.push(other);
+                }
+            }
+        }
+        if matches!(self.mode, AppMode::Normal(_))
+            && !self.data.background_ops.deferred_modes.is_empty()
+        {
+            let mode = self.data.background_ops.deferred_modes.remove(0);
+            self.apply_mode(mode);
+        }
+    }
+
+    /// Ask for the credentials a background operation is waiting on, once the user is not
+    /// busy in another modal.
+    pub fn show_auth_prompt(&mut self) {
+        match (&self.mode, self.data.background_ops.pending_prompt()) {
+            (AppMode::Normal(_), Some(prompt)) => {
+                self.data.input.clear();
+                self.apply_mode(AuthPromptMode { prompt }.into());
+            }
+            // The operation finished or was cancelled while its prompt was open.
+            (AppMode::AuthPrompt(_), None) => {
+                self.data.input.clear();
+                self.apply_mode(AppMode::normal());
+            }
+            _ => {}
+        }
+    }
+}
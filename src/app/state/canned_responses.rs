@@ -0,0 +1,66 @@
+//! Canned responses picker state: choosing a stored message to send to agents
+
+use crate::app::CannedResponse;
+
+/// State for the canned responses picker (`c`)
+#[derive(Debug, Default)]
+pub struct CannedResponsesState {
+    /// Responses offered, from the `canned_responses` setting.
+    pub responses: Vec<CannedResponse>,
+
+    /// Currently selected index
+    pub selected: usize,
+
+    /// Whether the response goes to the selected agent's leaf agents instead of the agent.
+    pub broadcast: bool,
+}
+
+impl CannedResponsesState {
+    /// Create a new canned responses state
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            responses: Vec::new(),
+            selected: 0,
+            broadcast: false,
+        }
+    }
+
+    /// Start the picker with the given responses
+    pub fn start(&mut self, responses: Vec<CannedResponse>) {
+        self.responses = responses;
+        self.selected = 0;
+        self.broadcast = false;
+    }
+
+    /// Select next response
+    pub const fn select_next(&mut self) {
+        let count = self.responses.len();
+        if count > 0 {
+            self.selected = (self.selected + 1) % count;
+        }
+    }
+
+    /// Select previous response
+    pub const fn select_prev(&mut self) {
+        let count = self.responses.len();
+        if count > 0 {
+            self.selected = match self.selected.checked_sub(1) {
+                Some(prev) => prev,
+                None => count - 1,
+            };
+        }
+    }
+
+    /// Switch between sending to the selected agent and broadcasting to its leaf agents
+    pub const fn toggle_broadcast(&mut self) {
+        self.broadcast = !self.broadcast;
+    }
+
+    /// Clear all canned responses state
+    pub fn clear(&mut self) {
+        self.responses.clear();
+        self.selected = 0;
+        self.broadcast = false;
+    }
+}
@@ -32,7 +32,7 @@ impl App {
 
     /// Return the list of slash commands filtered by the current palette input.
     #[must_use]
-    pub fn filtered_slash_commands(&self) -> Vec<SlashCommand> {
+    pub fn filtered_slash_commands(&self) -> Vec<SlashCommand<'_>> {
         self.data.filtered_slash_commands()
     }
 
@@ -43,7 +43,8 @@ impl App {
     }
 
     /// Execute a resolved slash command.
-    pub fn run_slash_command(&mut self, cmd: SlashCommand) {
+    pub fn run_slash_command(&mut self, cmd: SlashCommand<'_>) {
+        crate::metrics::record_action(cmd.name);
         let next = match cmd.name {
             "/agents" => {
                 self.data.input.clear();
@@ -51,6 +52,24 @@ impl App {
                 SettingsMenuMode.into()
             }
             "/toggle_docker" => self.data.toggle_docker_for_new_roots(),
+            "/sandbox" => self.data.cycle_sandbox_for_new_agents(),
+            "/profile" => self.data.cycle_profile(),
+            "/metrics" => self.data.toggle_metrics(),
+            "/theme" => self.data.cycle_theme(),
+            "/fetch" => crate::app::Actions::fetch_remotes(&mut self.data, true),
+            "/refresh" => crate::app::Actions::request_full_sync(&mut self.data),
+            "/pause" => crate::app::Actions::toggle_pause(&mut self.data),
+            "/budget" => crate::app::Actions::open_budget(&mut self.data),
+            "/import_tmux" => crate::app::Actions::start_tmux_import(&mut self.data),
+            "/syntheses" => crate::app::Actions::start_synthesis_browser(&mut self.data),
+            "/pr_review" => self.actions.start_pr_review_export(&mut self.data),
+            "/swarm_rerun" => self.actions.rerun_swarm_file(&mut self.data),
+            "/swarm_edit" => self.actions.edit_swarm_file(&mut self.data),
+            "/inbox" => crate::app::Actions::open_inbox(&mut self.data),
+            "/repos" => crate::app::Actions::open_repo_picker(&mut self.data),
+            "/scope" => crate::app::Actions::start_scope(&mut self.data),
+            "/depends_on" => crate::app::Actions::start_dependency_picker(&mut self.data),
+            "/unblock" => crate::app::Actions::clear_dependency(&mut self.data),
             "/changelog" => match crate::release_notes::current_version()
                 .and_then(|version| crate::release_notes::changelog_lines_for_version(&version))
             {
@@ -58,6 +77,7 @@ impl App {
                     title: "Changelog".to_string(),
                     lines,
                     mark_seen_version: None,
+                    agent_id: None,
                 }
                 .into(),
                 Err(e) => {
@@ -65,14 +85,23 @@ impl App {
                     AppMode::normal()
                 }
             },
+            "/notifications" => ChangelogMode {
+                title: "Notifications".to_string(),
+                lines: self.data.ui.notifications.history_lines(),
+                mark_seen_version: None,
+                agent_id: None,
+            }
+            .into(),
             "/help" => {
                 self.data.ui.help_scroll = 0;
                 HelpMode.into()
             }
-            other => {
-                self.set_status(format!("Unknown command: {other}"));
-                AppMode::normal()
-            }
+            other => crate::app::Actions::run_plugin_command(&mut self.data, other).unwrap_or_else(
+                || {
+                    self.set_status(format!("Unknown command: {other}"));
+                    AppMode::normal()
+                },
+            ),
         };
         self.apply_mode(next);
     }
@@ -94,7 +123,7 @@ impl App {
 
     /// Get the currently selected slash command (based on filter + selection index).
     #[must_use]
-    pub fn selected_slash_command(&self) -> Option<SlashCommand> {
+    pub fn selected_slash_command(&self) -> Option<SlashCommand<'_>> {
         self.filtered_slash_commands()
             .get(self.data.command_palette.selected)
             .copied()
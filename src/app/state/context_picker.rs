@@ -0,0 +1,167 @@
+//! Context file picker state: attaching files to a new agent's prompt
+
+use std::path::{Path, PathBuf};
+
+/// Most files listed from the project in the context file picker.
+const MAX_CANDIDATES: usize = 5000;
+
+/// Expand a leading `~/` to the home directory.
+fn expand_home(path: &str) -> PathBuf {
+    path.strip_prefix("~/")
+        .and_then(|rest| crate::paths::home_dir().map(|home| home.join(rest)))
+        .unwrap_or_else(|| PathBuf::from(path))
+}
+
+/// Files under `root`, relative to it: tracked files in a git repo, else top-level files.
+fn list_candidates(root: &Path) -> Vec<String> {
+    let tracked = crate::git::git_command()
+        .args(["ls-files", "--cached", "--others", "--exclude-standard"])
+        .current_dir(root)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .take(MAX_CANDIDATES)
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        });
+    if let Some(tracked) = tracked {
+        return tracked;
+    }
+
+    let mut files: Vec<String> = std::fs::read_dir(root)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_file()))
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .take(MAX_CANDIDATES)
+        .collect();
+    files.sort();
+    files
+}
+
+/// State for the context file picker modal (`Ctrl+O` in the new-agent prompts)
+#[derive(Debug, Default)]
+pub struct ContextPickerState {
+    /// Directory that relative candidates are resolved against
+    pub root: PathBuf,
+
+    /// Files offered by the picker, relative to `root`
+    pub candidates: Vec<String>,
+
+    /// Current filter text (a substring, or a path to any file)
+    pub filter: String,
+
+    /// Currently selected index in filtered list
+    pub selected: usize,
+
+    /// Whether the picker was opened from the child prompt (vs. the new-agent prompt)
+    pub for_children: bool,
+}
+
+impl ContextPickerState {
+    /// Create a new context picker state
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            root: PathBuf::new(),
+            candidates: Vec::new(),
+            filter: String::new(),
+            selected: 0,
+            for_children: false,
+        }
+    }
+
+    /// Start the picker with the files under `root`
+    pub fn start(&mut self, root: PathBuf, for_children: bool) {
+        self.candidates = list_candidates(&root);
+        self.root = root;
+        self.filter.clear();
+        self.selected = 0;
+        self.for_children = for_children;
+    }
+
+    /// Whether the filter is a path rather than a search term
+    fn filter_is_path(&self) -> bool {
+        ["/", "~/", "./", "../"]
+            .iter()
+            .any(|prefix| self.filter.starts_with(prefix))
+    }
+
+    /// Get filtered candidates based on current filter
+    ///
+    /// A filter that is a path to an existing file is offered on its own.
+    #[must_use]
+    pub fn filtered_candidates(&self) -> Vec<&str> {
+        if self.filter_is_path() {
+            return if self.resolve(&self.filter).is_file() {
+                vec![self.filter.as_str()]
+            } else {
+                Vec::new()
+            };
+        }
+        let filter_lower = self.filter.to_lowercase();
+        self.candidates
+            .iter()
+            .map(String::as_str)
+            .filter(|c| filter_lower.is_empty() || c.to_lowercase().contains(&filter_lower))
+            .collect()
+    }
+
+    /// Resolve a candidate to a path on disk
+    #[must_use]
+    pub fn resolve(&self, candidate: &str) -> PathBuf {
+        let path = expand_home(candidate);
+        if path.is_absolute() {
+            path
+        } else {
+            self.root.join(path)
+        }
+    }
+
+    /// Select next item in filtered list
+    pub fn select_next(&mut self) {
+        let count = self.filtered_candidates().len();
+        if count > 0 {
+            self.selected = (self.selected + 1) % count;
+        }
+    }
+
+    /// Select previous item in filtered list
+    pub fn select_prev(&mut self) {
+        let count = self.filtered_candidates().len();
+        if count > 0 {
+            self.selected = self.selected.checked_sub(1).unwrap_or(count - 1);
+        }
+    }
+
+    /// Get the currently selected file, resolved to a path on disk
+    #[must_use]
+    pub fn selected_path(&self) -> Option<PathBuf> {
+        self.filtered_candidates()
+            .get(self.selected)
+            .map(|candidate| self.resolve(candidate))
+    }
+
+    /// Handle character input in filter
+    pub fn handle_filter_char(&mut self, c: char) {
+        self.filter.push(c);
+        self.selected = 0;
+    }
+
+    /// Handle backspace in filter
+    pub fn handle_filter_backspace(&mut self) {
+        self.filter.pop();
+        self.selected = 0;
+    }
+
+    /// Clear all context picker state
+    pub fn clear(&mut self) {
+        self.candidates.clear();
+        self.filter.clear();
+        self.selected = 0;
+    }
+}
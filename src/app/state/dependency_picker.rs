@@ -0,0 +1,120 @@
+//! Dependency picker state: choosing the agent another agent waits for
+
+use uuid::Uuid;
+
+use crate::agent::DependencyTrigger;
+
+/// An agent that can be waited for in the dependency picker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyCandidate {
+    /// Agent ID.
+    pub id: Uuid,
+    /// Label shown in the picker (`Title (short id)`).
+    pub label: String,
+}
+
+/// State for the dependency picker (`/depends_on`)
+#[derive(Debug, Default)]
+pub struct DependencyPickerState {
+    /// Agent that will wait.
+    pub agent_id: Option<Uuid>,
+
+    /// Agents it can wait for.
+    pub candidates: Vec<DependencyCandidate>,
+
+    /// Current filter text for candidate search
+    pub filter: String,
+
+    /// Currently selected index in filtered list
+    pub selected: usize,
+
+    /// When the held message is sent.
+    pub trigger: DependencyTrigger,
+
+    /// Agent chosen to wait for, while the held message is being written.
+    pub upstream: Option<Uuid>,
+}
+
+impl DependencyPickerState {
+    /// Create a new dependency picker state
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            agent_id: None,
+            candidates: Vec::new(),
+            filter: String::new(),
+            selected: 0,
+            trigger: DependencyTrigger::Waiting,
+            upstream: None,
+        }
+    }
+
+    /// Start the picker for `agent_id` with the given candidates
+    pub fn start(&mut self, agent_id: Uuid, candidates: Vec<DependencyCandidate>) {
+        self.agent_id = Some(agent_id);
+        self.candidates = candidates;
+        self.filter.clear();
+        self.selected = 0;
+        self.trigger = DependencyTrigger::Waiting;
+        self.upstream = None;
+    }
+
+    /// Get filtered candidates based on current filter
+    #[must_use]
+    pub fn filtered_candidates(&self) -> Vec<&DependencyCandidate> {
+        let filter_lower = self.filter.to_lowercase();
+        self.candidates
+            .iter()
+            .filter(|c| filter_lower.is_empty() || c.label.to_lowercase().contains(&filter_lower))
+            .collect()
+    }
+
+    /// Select next item in filtered list
+    pub fn select_next(&mut self) {
+        let count = self.filtered_candidates().len();
+        if count > 0 {
+            self.selected = (self.selected + 1) % count;
+        }
+    }
+
+    /// Select previous item in filtered list
+    pub fn select_prev(&mut self) {
+        let count = self.filtered_candidates().len();
+        if count > 0 {
+            self.selected = self.selected.checked_sub(1).unwrap_or(count - 1);
+        }
+    }
+
+    /// Get the currently selected candidate
+    #[must_use]
+    pub fn selected_candidate(&self) -> Option<&DependencyCandidate> {
+        self.filtered_candidates().get(self.selected).copied()
+    }
+
+    /// Switch between the triggers
+    pub const fn toggle_trigger(&mut self) {
+        self.trigger = self.trigger.toggled();
+    }
+
+    /// Handle character input in filter
+    pub fn handle_filter_char(&mut self, c: char) {
+        self.filter.push(c);
+        self.selected = 0;
+    }
+
+    /// Handle backspace in filter
+    pub fn handle_filter_backspace(&mut self) {
+        self.filter.pop();
+        self.selected = 0;
+    }
+
+    /// Clear all dependency picker state
+    pub fn clear(&mut self) {
+        self.agent_id = None;
+        self.candidates.clear();
+        self.filter.clear();
+        self.selected = 0;
+        self.trigger = DependencyTrigger::Waiting;
+        self.upstream = None;
+    }
+}
@@ -37,6 +37,12 @@ pub struct GitOpState {
 
     /// Type of git operation (rebase or merge)
     pub operation_type: Option<GitOperationType>,
+
+    /// Whether the next push skips the pre-push commands (the user chose to push anyway)
+    pub skip_pre_push: bool,
+
+    /// Report of the pre-push command that failed, shown until the user decides
+    pub pre_push_failure: String,
 }
 
 impl GitOpState {
@@ -52,6 +58,8 @@ impl GitOpState {
             is_root_rename: false,
             target_branch: String::new(),
             operation_type: None,
+            skip_pre_push: false,
+            pre_push_failure: String::new(),
         }
     }
 
@@ -101,6 +109,8 @@ impl GitOpState {
         self.is_root_rename = false;
         self.target_branch.clear();
         self.operation_type = None;
+        self.skip_pre_push = false;
+        self.pre_push_failure.clear();
     }
 
     /// Start the rebase flow
@@ -151,8 +161,7 @@ impl App {
         self.data
             .git_op
             .start_rename(agent_id, current_name.clone(), is_root);
-        self.data.input.buffer = current_name;
-        self.data.input.cursor = self.data.input.buffer.len(); // Cursor at end
+        self.data.input.set(current_name);
         self.apply_mode(RenameBranchMode.into());
     }
 
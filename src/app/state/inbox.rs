@@ -0,0 +1,171 @@
+//! Questions inbox state: agents waiting on an answer from the user
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Local};
+use uuid::Uuid;
+
+/// Non-empty output lines, counted from the bottom, searched for a question.
+pub const QUESTION_SCAN_LINES: usize = 8;
+
+/// Text that marks a line as a question when the `question_patterns` setting is unset.
+pub const DEFAULT_QUESTION_PATTERNS: &[&str] = &[
+    "(y/n)",
+    "[y/n]",
+    "do you want",
+    "would you like",
+    "should i ",
+    "press enter",
+    "waiting for your",
+];
+
+/// A question an agent asked and has not had answered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InboxEntry {
+    /// Agent that asked.
+    pub agent_id: Uuid,
+    /// The output line that holds the question.
+    pub question: String,
+    /// When the question was noticed.
+    pub asked_at: DateTime<Local>,
+}
+
+/// State for the questions inbox (`!`)
+#[derive(Debug, Default)]
+pub struct InboxState {
+    /// Unanswered questions, oldest first.
+    pub entries: Vec<InboxEntry>,
+
+    /// Currently selected index in the overlay
+    pub selected: usize,
+
+    /// Pane hash last searched for a question, per agent, so unchanged panes are not searched
+    /// again.
+    checked: BTreeMap<Uuid, u64>,
+}
+
+impl InboxState {
+    /// Create an empty inbox
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            selected: 0,
+            checked: BTreeMap::new(),
+        }
+    }
+
+    /// Whether the pane of `agent_id` has already been searched at `hash`.
+    #[must_use]
+    pub fn is_checked(&self, agent_id: Uuid, hash: u64) -> bool {
+        self.checked.get(&agent_id) == Some(&hash)
+    }
+
+    /// Whether the pane of `agent_id` has been searched since it started waiting for input.
+    #[must_use]
+    pub fn is_tracked(&self, agent_id: Uuid) -> bool {
+        self.checked.contains_key(&agent_id)
+    }
+
+    /// Remember that the pane of `agent_id` was searched at `hash`, and add its question if it
+    /// asked one. Returns whether the question is new.
+    pub fn record(&mut self, agent_id: Uuid, hash: u64, question: Option<String>) -> bool {
+        self.checked.insert(agent_id, hash);
+        let Some(question) = question else {
+            return false;
+        };
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.agent_id == agent_id) {
+            let is_new = entry.question != question;
+            if is_new {
+                entry.question = question;
+                entry.asked_at = Local::now();
+            }
+            return is_new;
+        }
+        self.entries.push(InboxEntry {
+            agent_id,
+            question,
+            asked_at: Local::now(),
+        });
+        true
+    }
+
+    /// Drop the question of `agent_id`, leaving its pane marked as searched so the same
+    /// question is not picked up again.
+    pub fn remove(&mut self, agent_id: Uuid) {
+        self.entries.retain(|entry| entry.agent_id != agent_id);
+        self.clamp_selection();
+    }
+
+    /// Drop the question of `agent_id` and forget its pane, once it is working again.
+    pub fn forget(&mut self, agent_id: Uuid) {
+        self.checked.remove(&agent_id);
+        self.remove(agent_id);
+    }
+
+    /// Forget agents that are no longer working or waiting.
+    pub fn retain_agents(&mut self, mut keep: impl FnMut(Uuid) -> bool) {
+        self.entries.retain(|entry| keep(entry.agent_id));
+        self.checked.retain(|id, _| keep(*id));
+        self.clamp_selection();
+    }
+
+    /// Select next entry
+    pub const fn select_next(&mut self) {
+        let count = self.entries.len();
+        if count > 0 {
+            self.selected = (self.selected + 1) % count;
+        }
+    }
+
+    /// Select previous entry
+    pub const fn select_prev(&mut self) {
+        let count = self.entries.len();
+        if count > 0 {
+            self.selected = match self.selected.checked_sub(1) {
+                Some(prev) => prev,
+                None => count - 1,
+            };
+        }
+    }
+
+    /// Get the currently selected entry
+    #[must_use]
+    pub fn selected_entry(&self) -> Option<&InboxEntry> {
+        self.entries.get(self.selected)
+    }
+
+    const fn clamp_selection(&mut self) {
+        self.selected = if self.entries.is_empty() {
+            0
+        } else if self.selected >= self.entries.len() {
+            self.entries.len() - 1
+        } else {
+            self.selected
+        };
+    }
+}
+
+/// Find a question in the last lines of an agent's output (plain text, oldest line first).
+///
+/// A line counts as a question when it ends in `?` or contains one of `patterns`
+/// (case-insensitive). The bottom-most such line wins.
+#[must_use]
+pub fn find_question(lines: &[String], patterns: &[String]) -> Option<String> {
+    let patterns: Vec<String> = patterns
+        .iter()
+        .filter(|pattern| !pattern.is_empty())
+        .map(|pattern| pattern.to_lowercase())
+        .collect();
+    lines
+        .iter()
+        .rev()
+        .map(|line| line.trim_matches(|c: char| c.is_whitespace() || matches!(c, '│' | '┃' | '║')))
+        .filter(|line| !line.is_empty())
+        .take(QUESTION_SCAN_LINES)
+        .find(|line| {
+            let lower = line.to_lowercase();
+            line.ends_with('?') || patterns.iter().any(|pattern| lower.contains(pattern))
+        })
+        .map(str::to_string)
+}
@@ -1,4 +1,29 @@
 //! Input state: text input buffer and cursor management
+//!
+//! `cursor` is always a byte offset on a character boundary; moving between lines keeps the
+//! display column, so wide characters line up as they are drawn.
+
+use crate::text_width;
+
+/// Readline-style edits in text inputs, beyond inserting and deleting characters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEdit {
+    /// Move to the start of the word before the cursor (`Alt+B`)
+    WordLeft,
+    /// Move to the end of the word after the cursor (`Alt+F`)
+    WordRight,
+    /// Kill from the cursor to the end of the line, or the line break at its end (`Ctrl+K`)
+    KillToEnd,
+    /// Insert the last killed text at the cursor (`Ctrl+Y`)
+    Yank,
+    /// Restore the text before the last edit (`Ctrl+Z`)
+    Undo,
+    /// Restore the text the last undo removed (`Ctrl+Shift+Z`)
+    Redo,
+}
+
+/// Undo steps kept per input; older ones are dropped.
+const MAX_UNDO_STEPS: usize = 100;
 
 /// State for text input in various modes
 #[derive(Debug, Default)]
@@ -11,6 +36,28 @@ pub struct InputState {
 
     /// Scroll position in input modal (for multiline text)
     pub scroll: u16,
+
+    /// Text removed by the last kill (`Ctrl+W`, `Ctrl+U`, `Ctrl+K`), kept across inputs so it
+    /// can be yanked into another prompt
+    pub kill_buffer: String,
+
+    /// History entry shown in the buffer, if `Up` recalled one
+    pub history_index: Option<usize>,
+
+    /// Text typed before `Up` recalled a history entry, restored by `Down` past the newest
+    pub history_draft: String,
+
+    /// Earlier `(buffer, cursor)` states, most recent last, restored by `Ctrl+Z`
+    pub undo_stack: Vec<(String, usize)>,
+
+    /// States removed by `Ctrl+Z`, most recent last, restored by `Ctrl+Shift+Z`
+    pub redo_stack: Vec<(String, usize)>,
+
+    /// Text the prompt checks last warned about; submitting it unchanged sends it anyway
+    pub prompt_warned: Option<String>,
+
+    /// Whether the last edit typed part of a word, so the next character joins its undo step
+    typing: bool,
 }
 
 impl InputState {
@@ -21,6 +68,13 @@ impl InputState {
             buffer: String::new(),
             cursor: 0,
             scroll: 0,
+            kill_buffer: String::new(),
+            history_index: None,
+            history_draft: String::new(),
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            prompt_warned: None,
+            typing: false,
         }
     }
 
@@ -29,130 +83,259 @@ impl InputState {
         self.buffer.clear();
         self.cursor = 0;
         self.scroll = 0;
+        self.history_index = None;
+        self.history_draft.clear();
+        self.prompt_warned = None;
+        self.reset_undo();
+    }
+
+    /// Forget the undo history, for a new input
+    fn reset_undo(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.typing = false;
     }
 
-    /// Clear the current input buffer.
+    /// Save the current state as an undo step before an edit. Characters typed in a row into
+    /// one word share a step, so undo removes a word at a time rather than a letter.
+    fn checkpoint(&mut self, typing: bool) {
+        if !(typing && self.typing) {
+            self.undo_stack.push((self.buffer.clone(), self.cursor));
+            let excess = self.undo_stack.len().saturating_sub(MAX_UNDO_STEPS);
+            self.undo_stack.drain(..excess);
+        }
+        self.redo_stack.clear();
+        self.typing = typing;
+    }
+
+    /// Replace the buffer and cursor with `state`, returning the replaced ones
+    fn swap_state(&mut self, (buffer, cursor): (String, usize)) -> (String, usize) {
+        let previous = (std::mem::replace(&mut self.buffer, buffer), self.cursor);
+        self.cursor = cursor;
+        self.typing = false;
+        previous
+    }
+
+    /// Recall the entry of `entries` (oldest first) before the one shown, for `Up` on the first
+    /// line.
     ///
-    /// This matches typical line-editor behavior for "clear line" in Tenex's
-    /// text input modals.
+    /// Returns `false` without changing anything if the cursor is below the first line or
+    /// there is no older entry, so the caller can move the cursor instead.
+    pub fn history_previous(&mut self, entries: &[String]) -> bool {
+        if self.buffer[..self.cursor].contains('\n') {
+            return false;
+        }
+        let index = self.history_index.map_or_else(
+            || entries.len().checked_sub(1),
+            |index| index.checked_sub(1),
+        );
+        let Some(entry) = index.and_then(|index| entries.get(index)) else {
+            return false;
+        };
+        self.checkpoint(false);
+        if self.history_index.is_none() {
+            self.history_draft = std::mem::take(&mut self.buffer);
+        }
+        self.history_index = index;
+        self.buffer.clone_from(entry);
+        self.cursor = self.buffer.len();
+        true
+    }
+
+    /// Recall the entry after the one shown, or the text typed before browsing past the
+    /// newest, for `Down` on the last line.
+    ///
+    /// Returns `false` without changing anything if the cursor is above the last line or no
+    /// entry is shown.
+    pub fn history_next(&mut self, entries: &[String]) -> bool {
+        if self.buffer[self.cursor..].contains('\n') {
+            return false;
+        }
+        let Some(index) = self.history_index else {
+            return false;
+        };
+        self.checkpoint(false);
+        if let Some(entry) = entries.get(index.saturating_add(1)) {
+            self.history_index = Some(index.saturating_add(1));
+            self.buffer.clone_from(entry);
+        } else {
+            self.history_index = None;
+            self.buffer = std::mem::take(&mut self.history_draft);
+        }
+        self.cursor = self.buffer.len();
+        true
+    }
+
+    /// Kill from the start of the current line to the cursor (`Ctrl+U`).
+    ///
+    /// In a single-line input this clears everything before the cursor, like a shell.
     pub fn clear_line(&mut self) {
-        self.clear();
+        let start = self.buffer[..self.cursor].rfind('\n').map_or(0, |i| i + 1);
+        self.kill(start, self.cursor);
     }
 
-    /// Delete the previous word (like many shell/readline editors).
+    /// Kill the previous word (like many shell/readline editors).
     ///
     /// This removes any whitespace immediately before the cursor, then removes
     /// the contiguous non-whitespace "word" segment.
     pub fn delete_word(&mut self) {
-        if self.cursor == 0 {
-            return;
-        }
-
-        let mut start = self.cursor;
-        let mut found_non_whitespace = false;
+        self.kill(self.previous_word_start(), self.cursor);
+    }
 
-        for (index, ch) in self.buffer[..self.cursor].char_indices().rev() {
-            if !found_non_whitespace {
-                if ch.is_whitespace() {
-                    start = index;
-                    continue;
+    /// Apply a readline-style edit
+    pub fn apply(&mut self, edit: LineEdit) {
+        match edit {
+            LineEdit::WordLeft => {
+                self.cursor = self.previous_word_start();
+                self.typing = false;
+            }
+            LineEdit::WordRight => {
+                self.cursor = self.next_word_end();
+                self.typing = false;
+            }
+            LineEdit::KillToEnd => {
+                let rest = &self.buffer[self.cursor..];
+                let end = match rest.find('\n') {
+                    Some(0) => self.cursor + 1,
+                    Some(i) => self.cursor + i,
+                    None => self.buffer.len(),
+                };
+                self.kill(self.cursor, end);
+            }
+            LineEdit::Yank => {
+                if self.kill_buffer.is_empty() {
+                    return;
                 }
-                found_non_whitespace = true;
-                start = index;
-                continue;
+                self.checkpoint(false);
+                self.buffer.insert_str(self.cursor, &self.kill_buffer);
+                self.cursor += self.kill_buffer.len();
             }
-
-            if ch.is_whitespace() {
-                start = index.saturating_add(ch.len_utf8());
-                break;
+            LineEdit::Undo => {
+                if let Some(state) = self.undo_stack.pop() {
+                    let current = self.swap_state(state);
+                    self.redo_stack.push(current);
+                }
+            }
+            LineEdit::Redo => {
+                if let Some(state) = self.redo_stack.pop() {
+                    let current = self.swap_state(state);
+                    self.undo_stack.push(current);
+                }
             }
-
-            start = index;
         }
+    }
 
-        self.buffer.drain(start..self.cursor);
+    /// Remove `start..end` into the kill buffer and leave the cursor at `start`. An empty range
+    /// keeps the previous kill.
+    fn kill(&mut self, start: usize, end: usize) {
+        if start >= end {
+            return;
+        }
+        self.checkpoint(false);
+        self.kill_buffer = self.buffer.drain(start..end).collect();
         self.cursor = start;
     }
 
+    /// Byte offset of the start of the word before the cursor, skipping whitespace
+    fn previous_word_start(&self) -> usize {
+        let before = &self.buffer[..self.cursor];
+        let word_end = before.trim_end().len();
+        before[..word_end]
+            .rfind(char::is_whitespace)
+            .map_or(0, |i| {
+                i + before[i..].chars().next().map_or(1, char::len_utf8)
+            })
+    }
+
+    /// Byte offset of the end of the word after the cursor, skipping whitespace
+    fn next_word_end(&self) -> usize {
+        let after = &self.buffer[self.cursor..];
+        let word_start = after.len() - after.trim_start().len();
+        let word_len = after[word_start..]
+            .find(char::is_whitespace)
+            .unwrap_or(after.len() - word_start);
+        self.cursor + word_start + word_len
+    }
+
     /// Set the input buffer content and move cursor to end
     pub fn set(&mut self, content: String) {
         self.cursor = content.len();
         self.buffer = content;
+        self.history_index = None;
+        self.history_draft.clear();
+        self.prompt_warned = None;
+        self.reset_undo();
     }
 
     /// Insert a character at the cursor position
     pub fn insert_char(&mut self, c: char) {
+        self.checkpoint(!c.is_whitespace());
         self.buffer.insert(self.cursor, c);
         self.cursor += c.len_utf8();
     }
 
-    /// Delete the character before the cursor (backspace)
+    /// Delete the character before the cursor (backspace), with its combining marks
     pub fn backspace(&mut self) {
         if self.cursor > 0 {
-            // Find the previous character boundary
-            let prev_char_boundary = self.buffer[..self.cursor]
-                .char_indices()
-                .next_back()
-                .map_or(0, |(i, _)| i);
-            self.buffer.remove(prev_char_boundary);
-            self.cursor = prev_char_boundary;
+            self.checkpoint(false);
+            let start = text_width::previous_boundary(&self.buffer, self.cursor);
+            self.buffer.replace_range(start..self.cursor, "");
+            self.cursor = start;
         }
     }
 
-    /// Delete the character at the cursor (delete key)
+    /// Delete the character at the cursor (delete key), with its combining marks
     pub fn delete(&mut self) {
         if self.cursor < self.buffer.len() {
-            self.buffer.remove(self.cursor);
+            self.checkpoint(false);
+            let end = text_width::next_boundary(&self.buffer, self.cursor);
+            self.buffer.replace_range(self.cursor..end, "");
         }
     }
 
-    /// Move cursor left by one character
+    /// Move cursor left by one character, past its combining marks
     pub fn cursor_left(&mut self) {
-        if self.cursor > 0 {
-            // Find previous character boundary
-            self.cursor = self.buffer[..self.cursor]
-                .char_indices()
-                .next_back()
-                .map_or(0, |(i, _)| i);
-        }
+        self.typing = false;
+        self.cursor = text_width::previous_boundary(&self.buffer, self.cursor);
     }
 
-    /// Move cursor right by one character
+    /// Move cursor right by one character, past its combining marks
     pub fn cursor_right(&mut self) {
-        if self.cursor < self.buffer.len() {
-            // Find next character boundary
-            self.cursor = self.buffer[self.cursor..]
-                .char_indices()
-                .nth(1)
-                .map_or(self.buffer.len(), |(i, _)| self.cursor + i);
-        }
+        self.typing = false;
+        self.cursor = text_width::next_boundary(&self.buffer, self.cursor);
+    }
+
+    /// Display column of the cursor within its line
+    fn cursor_column(&self) -> usize {
+        let text = &self.buffer[..self.cursor];
+        let current_line_start = text.rfind('\n').map_or(0, |i| i + 1);
+        text_width::width(&text[current_line_start..])
     }
 
     /// Move cursor up one line (for multiline input)
     pub fn cursor_up(&mut self) {
+        self.typing = false;
         let text = &self.buffer[..self.cursor];
         // Find current line start and column
         let current_line_start = text.rfind('\n').map_or(0, |i| i + 1);
-        let column = self.cursor - current_line_start;
+        let column = self.cursor_column();
 
         if current_line_start > 0 {
             // Find previous line
             let prev_text = &self.buffer[..current_line_start - 1];
             let prev_line_start = prev_text.rfind('\n').map_or(0, |i| i + 1);
-            let prev_line_len = current_line_start - 1 - prev_line_start;
+            let prev_line = &prev_text[prev_line_start..];
 
             // Move to same column or end of previous line
-            self.cursor = prev_line_start + column.min(prev_line_len);
+            self.cursor = prev_line_start + text_width::byte_index_for_column(prev_line, column);
         }
     }
 
     /// Move cursor down one line (for multiline input)
     pub fn cursor_down(&mut self) {
+        self.typing = false;
         let text = &self.buffer;
-        // Find current line start and column
-        let before_cursor = &text[..self.cursor];
-        let current_line_start = before_cursor.rfind('\n').map_or(0, |i| i + 1);
-        let column = self.cursor - current_line_start;
+        let column = self.cursor_column();
 
         // Find next line
         if let Some(next_newline) = text[self.cursor..].find('\n') {
@@ -160,21 +343,23 @@ impl InputState {
             let next_line_end = text[next_line_start..]
                 .find('\n')
                 .map_or(text.len(), |i| next_line_start + i);
-            let next_line_len = next_line_end - next_line_start;
+            let next_line = &text[next_line_start..next_line_end];
 
             // Move to same column or end of next line
-            self.cursor = next_line_start + column.min(next_line_len);
+            self.cursor = next_line_start + text_width::byte_index_for_column(next_line, column);
         }
     }
 
     /// Move cursor to start of current line
     pub fn cursor_home(&mut self) {
+        self.typing = false;
         let text = &self.buffer[..self.cursor];
         self.cursor = text.rfind('\n').map_or(0, |i| i + 1);
     }
 
     /// Move cursor to end of current line
     pub fn cursor_end(&mut self) {
+        self.typing = false;
         let text = &self.buffer[self.cursor..];
         self.cursor += text.find('\n').unwrap_or(text.len());
     }
@@ -191,3 +376,102 @@ impl InputState {
         self.buffer.trim().is_empty()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(text: &str, cursor: usize) -> InputState {
+        let mut input = InputState::new();
+        input.set(text.to_string());
+        input.cursor = cursor;
+        input
+    }
+
+    #[test]
+    fn cursor_moves_over_whole_wide_characters() {
+        let mut input = input("日本語", 0);
+        input.cursor_right();
+        assert_eq!(input.cursor, "日".len());
+        assert_eq!(input.cursor_column(), 2);
+        input.cursor_end();
+        input.cursor_left();
+        assert_eq!(input.cursor, "日本".len());
+        assert_eq!(input.cursor_column(), 4);
+    }
+
+    #[test]
+    fn cursor_keeps_combining_marks_with_their_character() {
+        let text = "e\u{301}x";
+        let mut input = input(text, 0);
+        input.cursor_right();
+        assert_eq!(input.cursor, "e\u{301}".len());
+        assert_eq!(input.cursor_column(), 1);
+        input.cursor_left();
+        assert_eq!(input.cursor, 0);
+    }
+
+    #[test]
+    fn cursor_moves_over_joined_emoji_as_one() {
+        let family = "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}";
+        let thumbs = "\u{1f44d}\u{1f3fd}";
+        let text = format!("a{family}{thumbs}b");
+        let mut input = input(&text, 1);
+        input.cursor_right();
+        assert_eq!(input.cursor, 1 + family.len());
+        input.cursor_right();
+        assert_eq!(input.cursor, 1 + family.len() + thumbs.len());
+        input.cursor_left();
+        input.cursor_left();
+        assert_eq!(input.cursor, 1);
+    }
+
+    #[test]
+    fn vertical_movement_keeps_the_display_column() {
+        let mut input = input("日本語\nabcdef\n😀😀😀", "日本".len());
+        input.cursor_down();
+        assert_eq!(input.cursor, "日本語\nabcd".len());
+        input.cursor_down();
+        assert_eq!(input.cursor, "日本語\nabcdef\n😀😀".len());
+        input.cursor_up();
+        input.cursor_up();
+        assert_eq!(input.cursor, "日本".len());
+    }
+
+    #[test]
+    fn vertical_movement_never_lands_inside_a_wide_character() {
+        let mut input = input("abc\n日本", 1);
+        input.cursor_down();
+        assert_eq!(input.cursor, "abc\n".len());
+        assert_eq!(input.cursor_column(), 0);
+        input.cursor_right();
+        input.cursor_right();
+        input.cursor_up();
+        assert_eq!(input.cursor, "abc".len());
+    }
+
+    #[test]
+    fn backspace_and_delete_remove_whole_characters() {
+        let mut input = input("日e\u{301}😀\u{fe0f}z", "日e\u{301}😀\u{fe0f}".len());
+        input.backspace();
+        assert_eq!(input.buffer, "日e\u{301}z");
+        input.backspace();
+        assert_eq!(input.buffer, "日z");
+        assert_eq!(input.cursor, "日".len());
+        input.cursor_home();
+        input.delete();
+        assert_eq!(input.buffer, "z");
+        assert_eq!(input.cursor, 0);
+    }
+
+    #[test]
+    fn word_edits_handle_multi_byte_words() {
+        let mut input = input("修正 the ログイン", "修正 the ログイン".len());
+        input.delete_word();
+        assert_eq!(input.buffer, "修正 the ");
+        input.apply(LineEdit::WordLeft);
+        assert_eq!(input.cursor, "修正 ".len());
+        input.apply(LineEdit::Yank);
+        assert_eq!(input.buffer, "修正 ログインthe ");
+    }
+}
@@ -0,0 +1,111 @@
+//! Issue picker state: choosing a GitHub issue to create an agent for
+
+use serde::Deserialize;
+
+/// An open GitHub issue, as listed by `gh issue list`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct GithubIssue {
+    /// Issue number.
+    pub number: u64,
+    /// Issue title.
+    pub title: String,
+    /// Issue description (Markdown).
+    #[serde(default)]
+    pub body: String,
+    /// Web URL of the issue.
+    #[serde(default)]
+    pub url: String,
+}
+
+impl GithubIssue {
+    /// Label shown in the picker (`#123 Title`).
+    #[must_use]
+    pub fn label(&self) -> String {
+        format!("#{} {}", self.number, self.title)
+    }
+}
+
+/// State for the issue picker modal (`#`)
+#[derive(Debug, Default)]
+pub struct IssuePickerState {
+    /// Open issues in the target repository.
+    pub issues: Vec<GithubIssue>,
+
+    /// Current filter text for issue search
+    pub filter: String,
+
+    /// Currently selected index in filtered list
+    pub selected: usize,
+}
+
+impl IssuePickerState {
+    /// Create a new issue picker state
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            issues: Vec::new(),
+            filter: String::new(),
+            selected: 0,
+        }
+    }
+
+    /// Start the picker with the given issues
+    pub fn start(&mut self, issues: Vec<GithubIssue>) {
+        self.issues = issues;
+        self.filter.clear();
+        self.selected = 0;
+    }
+
+    /// Get filtered issues based on current filter (matches the number or title)
+    #[must_use]
+    pub fn filtered_issues(&self) -> Vec<&GithubIssue> {
+        let filter_lower = self.filter.to_lowercase();
+        self.issues
+            .iter()
+            .filter(|issue| {
+                filter_lower.is_empty() || issue.label().to_lowercase().contains(&filter_lower)
+            })
+            .collect()
+    }
+
+    /// Select next item in filtered list
+    pub fn select_next(&mut self) {
+        let count = self.filtered_issues().len();
+        if count > 0 {
+            self.selected = (self.selected + 1) % count;
+        }
+    }
+
+    /// Select previous item in filtered list
+    pub fn select_prev(&mut self) {
+        let count = self.filtered_issues().len();
+        if count > 0 {
+            self.selected = self.selected.checked_sub(1).unwrap_or(count - 1);
+        }
+    }
+
+    /// Get the currently selected issue
+    #[must_use]
+    pub fn selected_issue(&self) -> Option<&GithubIssue> {
+        self.filtered_issues().get(self.selected).copied()
+    }
+
+    /// Handle character input in filter
+    pub fn handle_filter_char(&mut self, c: char) {
+        self.filter.push(c);
+        self.selected = 0;
+    }
+
+    /// Handle backspace in filter
+    pub fn handle_filter_backspace(&mut self) {
+        self.filter.pop();
+        self.selected = 0;
+    }
+
+    /// Clear all issue picker state
+    pub fn clear(&mut self) {
+        self.issues.clear();
+        self.filter.clear();
+        self.selected = 0;
+    }
+}
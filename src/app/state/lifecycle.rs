@@ -4,6 +4,7 @@ use tracing::{debug, warn};
 
 use super::App;
 use crate::app::AgentRole;
+use crate::error::TenexError;
 use crate::state::{
     AppMode, CommandPaletteMode, ErrorModalMode, KeyboardRemapPromptMode, ModelSelectorMode,
     SettingsMenuMode, SuccessModalMode,
@@ -32,8 +33,7 @@ impl App {
             }
             AppMode::CommandPalette(_) => {
                 self.data.command_palette.reset();
-                self.data.input.buffer = "/".to_string();
-                self.data.input.cursor = 1;
+                self.data.input.set("/".to_string());
                 self.data.input.scroll = 0;
                 self.mode = CommandPaletteMode.into();
             }
@@ -54,10 +54,15 @@ impl App {
             }
             AppMode::Creating(state) => {
                 self.data.input.clear();
+                self.data.spawn.start_new_agent();
                 self.mode = AppMode::Creating(state);
             }
             AppMode::Prompting(state) => {
-                self.data.input.clear();
+                // Coming back from the context file picker keeps the prompt typed so far.
+                if !matches!(self.mode, AppMode::ContextFilePicker(_)) {
+                    self.data.input.clear();
+                    self.data.spawn.start_new_agent();
+                }
                 self.mode = AppMode::Prompting(state);
             }
             AppMode::Confirming(state) => {
@@ -65,7 +70,10 @@ impl App {
                 self.mode = AppMode::Confirming(state);
             }
             AppMode::ChildPrompt(state) => {
-                self.data.input.clear();
+                if !matches!(self.mode, AppMode::ContextFilePicker(_)) {
+                    self.data.input.clear();
+                    self.data.spawn.context_files.clear();
+                }
                 self.mode = AppMode::ChildPrompt(state);
             }
             AppMode::Broadcasting(state) => {
@@ -92,7 +100,8 @@ impl App {
                 self.mode = AppMode::SynthesisPrompt(state);
             }
             AppMode::ErrorModal(state) => {
-                self.data.ui.set_error(state.message.clone());
+                crate::metrics::record_error();
+                self.data.ui.set_error(state.error.to_string());
                 self.mode = AppMode::ErrorModal(state);
             }
             AppMode::SuccessModal(state) => {
@@ -123,14 +132,9 @@ impl App {
         self.apply_mode(AppMode::normal());
     }
 
-    /// Set an error message and show the error modal.
-    pub fn set_error(&mut self, message: impl Into<String>) {
-        self.apply_mode(
-            ErrorModalMode {
-                message: message.into(),
-            }
-            .into(),
-        );
+    /// Show `error` in the error modal; plain messages become [`TenexError::Other`].
+    pub fn set_error(&mut self, error: impl Into<TenexError>) {
+        self.apply_mode(ErrorModalMode::new(error).into());
     }
 
     /// Clear the current error message.
@@ -149,6 +153,16 @@ impl App {
         self.data.ui.set_status(message);
     }
 
+    /// Show a warning in the status bar.
+    pub fn notify_warning(&mut self, message: impl Into<String>) {
+        self.data.ui.notify_warning(message);
+    }
+
+    /// Show a non-blocking error toast in the status bar.
+    pub fn notify_error(&mut self, message: impl Into<String>) {
+        self.data.ui.notify_error(message);
+    }
+
     /// Clear the current status message.
     pub fn clear_status(&mut self) {
         self.data.ui.clear_status();
@@ -3,30 +3,63 @@
 //! This module contains the main `App` struct and its sub-states,
 //! organized into focused modules by domain.
 
+mod auto_connect_picker;
+mod background;
+mod canned_responses;
 mod command_palette;
+mod context_picker;
+mod dependency_picker;
 mod git_op;
+mod inbox;
 mod input;
+mod issue_picker;
 mod lifecycle;
 mod models;
 mod navigation;
+mod notifications;
+mod pause;
+mod recover_picker;
+mod repo_picker;
+mod resource_sampler;
 mod review;
 mod scroll;
+mod send_to;
 mod settings_menu;
 mod spawn;
+mod startup_report;
+mod synthesis_browser;
 mod text_input;
+mod tmux_import_picker;
 mod ui;
 
+pub use auto_connect_picker::{AutoConnectCandidate, AutoConnectPickerState};
+pub use background::{BackgroundCompletion, BackgroundOps, OpProgress, PromptReply};
+pub use canned_responses::CannedResponsesState;
 pub use command_palette::CommandPaletteState;
+pub use context_picker::ContextPickerState;
+pub use dependency_picker::{DependencyCandidate, DependencyPickerState};
 pub use git_op::GitOpState;
-pub use input::InputState;
+pub use inbox::{DEFAULT_QUESTION_PATTERNS, InboxState, QUESTION_SCAN_LINES, find_question};
+pub use input::{InputState, LineEdit};
+pub use issue_picker::{GithubIssue, IssuePickerState};
 pub use models::ModelSelectorState;
+pub use notifications::{Notification, NotificationSeverity};
+pub use pause::PauseState;
+pub use recover_picker::{RecoverPickerState, RecoveryCandidate};
+pub use repo_picker::{RepoEntry, RepoPickerState};
+pub use resource_sampler::ResourceSampler;
 pub use review::ReviewState;
+pub use send_to::{SendToState, SendToTarget};
 pub use settings_menu::SettingsMenuState;
 pub use spawn::SpawnState;
 pub use spawn::WorktreeConflictInfo;
+pub use startup_report::StartupReport;
+pub use synthesis_browser::{SynthesisBrowserState, SynthesisFile};
+pub use tmux_import_picker::TmuxImportPickerState;
 pub use ui::{
-    DiffEdit, DiffLineMeta, MuxdVersionMismatchInfo, PaneActivityDigestMode, PreviewSelectionPoint,
-    PreviewVtState, UiState,
+    AgentResourceUsage, ChangeAlerts, DiffEdit, DiffLineMeta, MuxdVersionMismatchInfo,
+    PaneActivity, PaneActivityDigestMode, PreviewSelectionPoint, PreviewVtState, ResourceAlert,
+    UiState,
 };
 
 use crate::agent::Storage;
@@ -41,15 +74,15 @@ pub use crate::git::BranchInfo;
 
 /// Slash command definition (for the `/` command palette)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct SlashCommand {
+pub struct SlashCommand<'a> {
     /// Command name, including the leading `/` (for example, `/help`).
-    pub name: &'static str,
+    pub name: &'a str,
     /// Human-readable description shown in the palette.
-    pub description: &'static str,
+    pub description: &'a str,
 }
 
-/// All available slash commands (shown in the command palette)
-pub const SLASH_COMMANDS: &[SlashCommand] = &[
+/// Built-in slash commands (shown in the command palette ahead of plugin commands)
+pub const SLASH_COMMANDS: &[SlashCommand<'static>] = &[
     SlashCommand {
         name: "/agents",
         description: "Configure agent programs (default/planner/review)",
@@ -58,10 +91,86 @@ pub const SLASH_COMMANDS: &[SlashCommand] = &[
         name: "/toggle_docker",
         description: "Toggle Docker for newly created root agents",
     },
+    SlashCommand {
+        name: "/sandbox",
+        description: "Cycle the sandbox policy for new agents (off/worktree/offline)",
+    },
+    SlashCommand {
+        name: "/profile",
+        description: "Switch to the next settings profile",
+    },
+    SlashCommand {
+        name: "/metrics",
+        description: "Toggle recording local usage metrics",
+    },
+    SlashCommand {
+        name: "/theme",
+        description: "Switch between the default and high-contrast themes",
+    },
+    SlashCommand {
+        name: "/fetch",
+        description: "Fetch and prune remotes, then compare agent branches with their base",
+    },
+    SlashCommand {
+        name: "/refresh",
+        description: "Check the status and activity of every agent now",
+    },
+    SlashCommand {
+        name: "/pause",
+        description: "Pause or resume held messages and automatic restarts",
+    },
+    SlashCommand {
+        name: "/budget",
+        description: "Show estimated spend, and resume agents paused over budget",
+    },
+    SlashCommand {
+        name: "/import_tmux",
+        description: "Import tmux sessions started outside Tenex as agents",
+    },
+    SlashCommand {
+        name: "/syntheses",
+        description: "Browse the selected agent's past synthesis files",
+    },
+    SlashCommand {
+        name: "/pr_review",
+        description: "Post the selected agent's reviewer findings as a draft PR review",
+    },
+    SlashCommand {
+        name: "/swarm_rerun",
+        description: "Apply the selected agent's swarm file again",
+    },
+    SlashCommand {
+        name: "/swarm_edit",
+        description: "Edit the selected agent's swarm file",
+    },
+    SlashCommand {
+        name: "/inbox",
+        description: "Show the questions agents are waiting on",
+    },
+    SlashCommand {
+        name: "/repos",
+        description: "Switch between the workspace's repositories, or add one",
+    },
+    SlashCommand {
+        name: "/scope",
+        description: "Keep the selected agent's changes to a directory of the repository",
+    },
+    SlashCommand {
+        name: "/depends_on",
+        description: "Hold a message for the selected agent until another agent finishes",
+    },
+    SlashCommand {
+        name: "/unblock",
+        description: "Drop the message the selected agent is waiting to receive",
+    },
     SlashCommand {
         name: "/changelog",
         description: "Show what's new / changelog",
     },
+    SlashCommand {
+        name: "/notifications",
+        description: "Show recent status bar notifications",
+    },
     SlashCommand {
         name: "/help",
         description: "Show help",
@@ -129,6 +238,8 @@ pub enum Tab {
     Diff,
     /// Current branch commit list
     Commits,
+    /// Shared scratchpad for the selected agent's swarm
+    Scratchpad,
 }
 
 impl std::fmt::Display for Tab {
@@ -137,6 +248,7 @@ impl std::fmt::Display for Tab {
             Self::Preview => write!(f, "Preview"),
             Self::Diff => write!(f, "Diff"),
             Self::Commits => write!(f, "Commits"),
+            Self::Scratchpad => write!(f, "Scratchpad"),
         }
     }
 }
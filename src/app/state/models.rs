@@ -91,6 +91,7 @@ impl ModelSelectorState {
 }
 
 use super::App;
+use crate::error::TenexError;
 use crate::state::{CustomAgentCommandMode, ModelSelectorMode};
 
 impl App {
@@ -151,7 +152,9 @@ impl App {
     pub fn set_agent_program_and_save(&mut self, program: AgentProgram) {
         self.data.settings.agent_program = program;
         if let Err(e) = self.data.settings.save() {
-            self.set_error(format!("Failed to save settings: {e}"));
+            self.set_error(TenexError::SettingsSave {
+                detail: e.to_string(),
+            });
             return;
         }
 
@@ -163,7 +166,9 @@ impl App {
         self.data.settings.custom_agent_command = command;
         self.data.settings.agent_program = AgentProgram::Custom;
         if let Err(e) = self.data.settings.save() {
-            self.set_error(format!("Failed to save settings: {e}"));
+            self.set_error(TenexError::SettingsSave {
+                detail: e.to_string(),
+            });
             return;
         }
 
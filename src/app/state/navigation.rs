@@ -44,7 +44,8 @@ impl App {
         self.data.active_tab = match self.data.active_tab {
             Tab::Preview => Tab::Diff,
             Tab::Diff => Tab::Commits,
-            Tab::Commits => Tab::Preview,
+            Tab::Commits => Tab::Scratchpad,
+            Tab::Scratchpad => Tab::Preview,
         };
         self.reset_scroll();
     }
@@ -68,11 +69,7 @@ impl App {
             return;
         }
 
-        // `preview_dimensions` stores the preview inner height, which is `frame_height - 4`.
-        // The agent list inner height is `frame_height - 3` (one line taller, because it has no tab bar).
-        let preview_inner_height =
-            usize::from(self.data.ui.preview_dimensions.map_or(20, |(_, h)| h));
-        let viewport_height = preview_inner_height.saturating_add(1);
+        let viewport_height = self.data.agent_list_viewport_height();
         let max_scroll = visible_count.saturating_sub(viewport_height);
 
         let mut scroll = self.data.ui.agent_list_scroll.min(max_scroll);
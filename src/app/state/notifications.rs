@@ -0,0 +1,203 @@
+//! Status bar notification queue with timed display and history.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+
+/// Maximum number of notifications kept for the history overlay.
+const HISTORY_LIMIT: usize = 200;
+
+/// Maximum number of notifications waiting to be displayed; the oldest are dropped first.
+const PENDING_LIMIT: usize = 8;
+
+/// Identical warnings/errors raised again within this window are not shown again.
+const REPEAT_SUPPRESSION: chrono::TimeDelta = chrono::TimeDelta::seconds(60);
+
+/// How long the current notification stays up when others are waiting behind it.
+const QUEUED_DISPLAY_DURATION: Duration = Duration::from_millis(1500);
+
+/// How important a notification is (controls styling and display time).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NotificationSeverity {
+    /// Routine progress/confirmation messages.
+    Info,
+    /// Something the user should notice but that did not fail.
+    Warning,
+    /// A background operation failed.
+    Error,
+}
+
+impl NotificationSeverity {
+    /// Short label used in the history overlay.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warn",
+            Self::Error => "error",
+        }
+    }
+
+    /// How long a notification of this severity stays in the status bar.
+    #[must_use]
+    pub const fn display_duration(self) -> Duration {
+        match self {
+            Self::Info => Duration::from_secs(4),
+            Self::Warning => Duration::from_secs(6),
+            Self::Error => Duration::from_secs(8),
+        }
+    }
+}
+
+/// A single status bar notification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    /// Message text.
+    pub message: String,
+    /// Severity level.
+    pub severity: NotificationSeverity,
+    /// When the notification was raised.
+    pub created_at: DateTime<Local>,
+}
+
+/// Queue of notifications shown one at a time in the status bar.
+#[derive(Debug, Default)]
+pub struct NotificationQueue {
+    current: Option<Notification>,
+    current_shown_at: Option<Instant>,
+    pending: VecDeque<Notification>,
+    history: VecDeque<Notification>,
+}
+
+impl NotificationQueue {
+    /// Create an empty queue.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            current: None,
+            current_shown_at: None,
+            pending: VecDeque::new(),
+            history: VecDeque::new(),
+        }
+    }
+
+    /// Queue a notification for display.
+    ///
+    /// A message identical to the one on screen (or the last one queued) is dropped, and
+    /// repeated warnings/errors are suppressed for a while so recurring background failures
+    /// don't flood the status bar.
+    pub fn push(&mut self, message: impl Into<String>, severity: NotificationSeverity) {
+        let message = message.into();
+        let now = Local::now();
+        let is_visible_duplicate = self
+            .pending
+            .back()
+            .or(self.current.as_ref())
+            .is_some_and(|last| last.message == message && last.severity == severity);
+        let is_recent_repeat = severity != NotificationSeverity::Info
+            && self.history.iter().rev().any(|previous| {
+                previous.message == message
+                    && previous.severity == severity
+                    && now.signed_duration_since(previous.created_at) < REPEAT_SUPPRESSION
+            });
+        if is_visible_duplicate || is_recent_repeat {
+            return;
+        }
+
+        let notification = Notification {
+            message,
+            severity,
+            created_at: now,
+        };
+
+        if self.history.len() >= HISTORY_LIMIT {
+            self.history.pop_front();
+        }
+        self.history.push_back(notification.clone());
+
+        if self.current.is_none() {
+            self.current = Some(notification);
+            self.current_shown_at = None;
+            return;
+        }
+
+        if self.pending.len() >= PENDING_LIMIT {
+            self.pending.pop_front();
+        }
+        self.pending.push_back(notification);
+    }
+
+    /// Advance the queue: expire the current notification and show the next one.
+    ///
+    /// Returns `true` when the visible notification changed.
+    pub fn tick(&mut self, now: Instant) -> bool {
+        let Some(current) = &self.current else {
+            return false;
+        };
+
+        let Some(shown_at) = self.current_shown_at else {
+            self.current_shown_at = Some(now);
+            return false;
+        };
+
+        let display_duration = if self.pending.is_empty() {
+            current.severity.display_duration()
+        } else {
+            current
+                .severity
+                .display_duration()
+                .min(QUEUED_DISPLAY_DURATION)
+        };
+        if now.saturating_duration_since(shown_at) < display_duration {
+            return false;
+        }
+
+        self.current = self.pending.pop_front();
+        self.current_shown_at = self.current.as_ref().map(|_| now);
+        true
+    }
+
+    /// The notification currently shown in the status bar.
+    #[must_use]
+    pub const fn current(&self) -> Option<&Notification> {
+        self.current.as_ref()
+    }
+
+    /// Dismiss the visible notification and anything queued behind it.
+    ///
+    /// History is kept.
+    pub fn clear(&mut self) {
+        self.current = None;
+        self.current_shown_at = None;
+        self.pending.clear();
+    }
+
+    /// All recorded notifications, oldest first.
+    pub fn history(&self) -> impl DoubleEndedIterator<Item = &Notification> {
+        self.history.iter()
+    }
+
+    /// Lines for the notification history overlay (newest first).
+    #[must_use]
+    pub fn history_lines(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "{} notification(s), newest first",
+            self.history.len()
+        )];
+        lines.push(String::new());
+        if self.history.is_empty() {
+            lines.push("No notifications yet.".to_string());
+            return lines;
+        }
+        lines.extend(self.history().rev().map(|notification| {
+            format!(
+                "{} [{}] {}",
+                notification.created_at.format("%H:%M:%S"),
+                notification.severity.label(),
+                notification.message
+            )
+        }));
+        lines
+    }
+}
@@ -0,0 +1,83 @@
+//! Pause state: whether Tenex holds back what it would send to agents on its own
+
+/// Where the current time stands against the `quiet_hours` setting.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum QuietHoursState {
+    /// Outside the quiet hours (or none are set).
+    #[default]
+    Outside,
+    /// Inside the quiet hours.
+    Inside,
+    /// Inside the quiet hours, but resumed with `/pause` until they end.
+    Overridden,
+}
+
+/// Whether held messages and automatic restarts are paused, on demand (`/pause`) or by the
+/// `quiet_hours` setting.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PauseState {
+    /// Paused on demand with `/pause`.
+    on_demand: bool,
+
+    /// Where the current time stood against the quiet hours at the last update.
+    quiet_hours: QuietHoursState,
+
+    /// Whether everything was paused at the last update, to tell when that changes.
+    was_paused: bool,
+}
+
+impl PauseState {
+    /// Create a state that is not paused
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            on_demand: false,
+            quiet_hours: QuietHoursState::Outside,
+            was_paused: false,
+        }
+    }
+
+    /// Whether held messages and automatic restarts are paused
+    #[must_use]
+    pub const fn is_paused(self) -> bool {
+        self.on_demand || matches!(self.quiet_hours, QuietHoursState::Inside)
+    }
+
+    /// Whether the pause comes from the quiet hours rather than `/pause`
+    #[must_use]
+    pub const fn is_quiet_hours(self) -> bool {
+        !self.on_demand && self.is_paused()
+    }
+
+    /// Record whether the current time is inside the quiet hours.
+    ///
+    /// Returns whether the state is now paused when that changed since the last update.
+    pub const fn update(&mut self, in_quiet_hours: bool) -> Option<bool> {
+        self.quiet_hours = match (in_quiet_hours, self.quiet_hours) {
+            (false, _) => QuietHoursState::Outside,
+            (true, QuietHoursState::Overridden) => QuietHoursState::Overridden,
+            (true, _) => QuietHoursState::Inside,
+        };
+        let paused = self.is_paused();
+        if paused == self.was_paused {
+            return None;
+        }
+        self.was_paused = paused;
+        Some(paused)
+    }
+
+    /// Pause when running, or resume when paused (until the current quiet hours end, if they
+    /// caused the pause). Returns whether the state is now paused.
+    pub const fn toggle(&mut self) -> bool {
+        if self.is_paused() {
+            self.on_demand = false;
+            if matches!(self.quiet_hours, QuietHoursState::Inside) {
+                self.quiet_hours = QuietHoursState::Overridden;
+            }
+        } else {
+            self.on_demand = true;
+        }
+        self.was_paused = self.is_paused();
+        self.was_paused
+    }
+}
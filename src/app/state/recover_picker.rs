@@ -0,0 +1,116 @@
+//! Recovery picker state: choosing a lost commit to recreate a killed agent's branch at
+
+use crate::git::LostCommit;
+use std::path::PathBuf;
+
+/// A commit that may hold a killed agent's work.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecoveryCandidate {
+    /// Title of the killed agent.
+    pub title: String,
+    /// The agent's deleted branch.
+    pub branch: String,
+    /// Repository the branch lived in.
+    pub repo_root: PathBuf,
+    /// The lost commit.
+    pub commit: LostCommit,
+}
+
+impl RecoveryCandidate {
+    /// Label used for filtering (`title branch commit summary`).
+    #[must_use]
+    pub fn label(&self) -> String {
+        format!(
+            "{} {} {} {}",
+            self.title,
+            self.branch,
+            self.commit.short_commit(),
+            self.commit.summary
+        )
+    }
+}
+
+/// State for the recovery picker modal (`D`)
+#[derive(Debug, Default)]
+pub struct RecoverPickerState {
+    /// Lost commits found for recently killed agents.
+    pub candidates: Vec<RecoveryCandidate>,
+
+    /// Current filter text for candidate search
+    pub filter: String,
+
+    /// Currently selected index in filtered list
+    pub selected: usize,
+}
+
+impl RecoverPickerState {
+    /// Create a new recovery picker state
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            candidates: Vec::new(),
+            filter: String::new(),
+            selected: 0,
+        }
+    }
+
+    /// Start the picker with the given candidates
+    pub fn start(&mut self, candidates: Vec<RecoveryCandidate>) {
+        self.candidates = candidates;
+        self.filter.clear();
+        self.selected = 0;
+    }
+
+    /// Get filtered candidates based on current filter
+    #[must_use]
+    pub fn filtered_candidates(&self) -> Vec<&RecoveryCandidate> {
+        let filter_lower = self.filter.to_lowercase();
+        self.candidates
+            .iter()
+            .filter(|candidate| {
+                filter_lower.is_empty() || candidate.label().to_lowercase().contains(&filter_lower)
+            })
+            .collect()
+    }
+
+    /// Select next item in filtered list
+    pub fn select_next(&mut self) {
+        let count = self.filtered_candidates().len();
+        if count > 0 {
+            self.selected = (self.selected + 1) % count;
+        }
+    }
+
+    /// Select previous item in filtered list
+    pub fn select_prev(&mut self) {
+        let count = self.filtered_candidates().len();
+        if count > 0 {
+            self.selected = self.selected.checked_sub(1).unwrap_or(count - 1);
+        }
+    }
+
+    /// Get the currently selected candidate
+    #[must_use]
+    pub fn selected_candidate(&self) -> Option<&RecoveryCandidate> {
+        self.filtered_candidates().get(self.selected).copied()
+    }
+
+    /// Handle character input in filter
+    pub fn handle_filter_char(&mut self, c: char) {
+        self.filter.push(c);
+        self.selected = 0;
+    }
+
+    /// Handle backspace in filter
+    pub fn handle_filter_backspace(&mut self) {
+        self.filter.pop();
+        self.selected = 0;
+    }
+
+    /// Clear all recovery picker state
+    pub fn clear(&mut self) {
+        self.candidates.clear();
+        self.filter.clear();
+        self.selected = 0;
+    }
+}
@@ -0,0 +1,76 @@
+//! Repository picker state: switching between the repositories of the workspace
+
+use std::path::PathBuf;
+
+/// A repository offered by the repository picker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoEntry {
+    /// Repository root.
+    pub root: PathBuf,
+    /// Name shown in the sidebar.
+    pub label: String,
+    /// Number of agents working in the repository.
+    pub agent_count: usize,
+    /// Whether the repository is registered in the `repos` setting.
+    pub registered: bool,
+}
+
+/// State for the repository picker (`/repos`)
+#[derive(Debug, Default)]
+pub struct RepoPickerState {
+    /// Repositories offered, in sidebar order.
+    pub repos: Vec<RepoEntry>,
+
+    /// Currently selected index
+    pub selected: usize,
+}
+
+impl RepoPickerState {
+    /// Create a new repository picker state
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            repos: Vec::new(),
+            selected: 0,
+        }
+    }
+
+    /// Start the picker with the given repositories, selecting `current` when it is listed
+    pub fn start(&mut self, repos: Vec<RepoEntry>, current: Option<&PathBuf>) {
+        self.selected = current
+            .and_then(|current| repos.iter().position(|repo| &repo.root == current))
+            .unwrap_or(0);
+        self.repos = repos;
+    }
+
+    /// Select next repository
+    pub const fn select_next(&mut self) {
+        let count = self.repos.len();
+        if count > 0 {
+            self.selected = (self.selected + 1) % count;
+        }
+    }
+
+    /// Select previous repository
+    pub const fn select_prev(&mut self) {
+        let count = self.repos.len();
+        if count > 0 {
+            self.selected = match self.selected.checked_sub(1) {
+                Some(prev) => prev,
+                None => count - 1,
+            };
+        }
+    }
+
+    /// Get the currently selected repository
+    #[must_use]
+    pub fn selected_repo(&self) -> Option<&RepoEntry> {
+        self.repos.get(self.selected)
+    }
+
+    /// Clear all repository picker state
+    pub fn clear(&mut self) {
+        self.repos.clear();
+        self.selected = 0;
+    }
+}
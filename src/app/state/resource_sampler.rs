@@ -0,0 +1,102 @@
+//! Resource sampling: every running agent's process tree, sampled together on a worker thread.
+
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::time::Instant;
+
+use anyhow::Result;
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::mux::{ProcessUsage, SessionManager};
+
+/// Usage of the agents sampled together, in the order they were requested.
+#[derive(Debug)]
+pub struct ResourceSample {
+    /// Agents sampled, in request order.
+    pub agent_ids: Vec<Uuid>,
+    /// Usage of each agent in `agent_ids`, `None` for one without a running process.
+    pub usages: Usages,
+    /// When the sample was taken.
+    pub sampled_at: Instant,
+}
+
+/// Usage of each requested target, as the mux daemon answers it.
+type Usages = Result<Vec<Option<ProcessUsage>>>;
+
+/// A sample being taken on a worker thread.
+#[derive(Debug)]
+struct InFlight {
+    /// Agents sampled, in request order.
+    agent_ids: Vec<Uuid>,
+    /// Where the usages and the time they were sampled arrive.
+    receiver: Receiver<(Usages, Instant)>,
+}
+
+/// Takes one sample at a time off the UI thread; the mux daemon reads the process table once
+/// per sample.
+#[derive(Debug, Default)]
+pub struct ResourceSampler {
+    /// Whether `ps` is available to read the process table, checked once.
+    supported: Option<bool>,
+    /// The sample being taken.
+    in_flight: Option<InFlight>,
+}
+
+impl ResourceSampler {
+    /// A sampler that has not taken a sample yet.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            supported: None,
+            in_flight: None,
+        }
+    }
+
+    /// Whether this platform can sample process usage. Without `ps` sampling is skipped.
+    pub fn is_supported(&mut self) -> bool {
+        *self.supported.get_or_insert_with(|| {
+            let supported = crate::command::program_exists("ps");
+            if !supported {
+                debug!("ps is not available; agent resource usage is not sampled");
+            }
+            supported
+        })
+    }
+
+    /// Start sampling the process trees of `targets` (agent and mux target), unless a sample
+    /// is still being taken.
+    pub fn start(&mut self, targets: Vec<(Uuid, String)>, session_manager: SessionManager) {
+        if self.in_flight.is_some() || targets.is_empty() {
+            return;
+        }
+        let (agent_ids, targets): (Vec<Uuid>, Vec<String>) = targets.into_iter().unzip();
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let usages = session_manager.process_usages(&targets);
+            let _ = sender.send((usages, Instant::now()));
+        });
+        self.in_flight = Some(InFlight {
+            agent_ids,
+            receiver,
+        });
+    }
+
+    /// The sample started by [`Self::start`], once it has arrived.
+    pub fn take_finished(&mut self) -> Option<ResourceSample> {
+        let in_flight = self.in_flight.as_ref()?;
+        let (usages, sampled_at) = match in_flight.receiver.try_recv() {
+            Ok(sample) => sample,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => (
+                Err(anyhow::anyhow!("worker thread stopped unexpectedly")),
+                Instant::now(),
+            ),
+        };
+        let agent_ids = self.in_flight.take()?.agent_ids;
+        Some(ResourceSample {
+            agent_ids,
+            usages,
+            sampled_at,
+        })
+    }
+}
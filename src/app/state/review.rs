@@ -1,5 +1,6 @@
-//! Review state: branch selection for review agents
+//! Review state: focus assignment and branch selection for review agents
 
+use crate::app::ReviewFocus;
 use crate::git::BranchInfo;
 
 /// State for the review swarm feature
@@ -16,6 +17,15 @@ pub struct ReviewState {
 
     /// Selected base branch for review (after confirmation)
     pub base_branch: Option<String>,
+
+    /// Focuses that can be assigned to reviewers
+    pub focuses: Vec<ReviewFocus>,
+
+    /// Index into `focuses` for each reviewer (`None` for a general review)
+    pub assignments: Vec<Option<usize>>,
+
+    /// Currently selected reviewer in the focus picker
+    pub selected_reviewer: usize,
 }
 
 impl ReviewState {
@@ -27,6 +37,9 @@ impl ReviewState {
             filter: String::new(),
             selected: 0,
             base_branch: None,
+            focuses: Vec::new(),
+            assignments: Vec::new(),
+            selected_reviewer: 0,
         }
     }
 
@@ -94,12 +107,64 @@ impl ReviewState {
         }
     }
 
+    /// Give `count` reviewers different focuses from `focuses`, in order, wrapping around
+    /// when there are more reviewers than focuses
+    pub fn assign_focuses(&mut self, focuses: Vec<ReviewFocus>, count: usize) {
+        self.assignments = (0..count)
+            .map(|reviewer| (!focuses.is_empty()).then(|| reviewer % focuses.len()))
+            .collect();
+        self.focuses = focuses;
+        self.selected_reviewer = 0;
+    }
+
+    /// Select next reviewer in the focus picker
+    pub const fn select_next_reviewer(&mut self) {
+        let count = self.assignments.len();
+        if count > 0 {
+            self.selected_reviewer = (self.selected_reviewer + 1) % count;
+        }
+    }
+
+    /// Select previous reviewer in the focus picker
+    pub fn select_prev_reviewer(&mut self) {
+        let count = self.assignments.len();
+        if count > 0 {
+            self.selected_reviewer = self.selected_reviewer.checked_sub(1).unwrap_or(count - 1);
+        }
+    }
+
+    /// Move the selected reviewer to the next (or previous) focus, passing through a general
+    /// review between the last focus and the first
+    pub fn cycle_selected_focus(&mut self, forward: bool) {
+        let option_count = self.focuses.len() + 1;
+        let Some(assignment) = self.assignments.get_mut(self.selected_reviewer) else {
+            return;
+        };
+        let current = assignment.map_or(0, |index| index + 1);
+        let next = if forward {
+            (current + 1) % option_count
+        } else {
+            current.checked_sub(1).unwrap_or(option_count - 1)
+        };
+        *assignment = next.checked_sub(1);
+    }
+
+    /// Focus assigned to the `reviewer`th reviewer, if any
+    #[must_use]
+    pub fn focus_for(&self, reviewer: usize) -> Option<&ReviewFocus> {
+        let index = (*self.assignments.get(reviewer)?)?;
+        self.focuses.get(index)
+    }
+
     /// Clear all review-related state
     pub fn clear(&mut self) {
         self.branches.clear();
         self.filter.clear();
         self.selected = 0;
         self.base_branch = None;
+        self.focuses.clear();
+        self.assignments.clear();
+        self.selected_reviewer = 0;
     }
 }
 
@@ -15,6 +15,7 @@ impl App {
             Tab::Preview => self.data.ui.scroll_preview_up(amount),
             Tab::Diff => self.data.ui.scroll_diff_up(amount),
             Tab::Commits => self.data.ui.scroll_commits_up(amount),
+            Tab::Scratchpad => self.data.ui.scroll_scratchpad_up(amount),
         }
     }
 
@@ -24,6 +25,7 @@ impl App {
             Tab::Preview => self.data.ui.scroll_preview_down(amount),
             Tab::Diff => self.data.ui.scroll_diff_down(amount),
             Tab::Commits => self.data.ui.scroll_commits_down(amount),
+            Tab::Scratchpad => self.data.ui.scroll_scratchpad_down(amount),
         }
     }
 
@@ -33,6 +35,7 @@ impl App {
             Tab::Preview => self.data.ui.preview_to_top(),
             Tab::Diff => self.data.ui.diff_to_top(),
             Tab::Commits => self.data.ui.commits_to_top(),
+            Tab::Scratchpad => self.data.ui.scratchpad_to_top(),
         }
     }
 
@@ -42,6 +45,10 @@ impl App {
             Tab::Preview => self.data.ui.preview_to_bottom(content_lines, visible_lines),
             Tab::Diff => self.data.ui.diff_to_bottom(content_lines, visible_lines),
             Tab::Commits => self.data.ui.commits_to_bottom(content_lines, visible_lines),
+            Tab::Scratchpad => self
+                .data
+                .ui
+                .scratchpad_to_bottom(content_lines, visible_lines),
         }
     }
 
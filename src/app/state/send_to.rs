@@ -0,0 +1,101 @@
+//! Send-to state: choosing which agent receives another agent's output
+
+use uuid::Uuid;
+
+/// An agent that can receive output in the send-to picker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SendToTarget {
+    /// Agent ID.
+    pub id: Uuid,
+    /// Label shown in the picker (`Title (short id)`).
+    pub label: String,
+}
+
+/// State for the send-to picker modal (`>`)
+#[derive(Debug, Default)]
+pub struct SendToState {
+    /// Agent whose output is being sent.
+    pub source: Option<Uuid>,
+
+    /// Agents that can receive the output.
+    pub targets: Vec<SendToTarget>,
+
+    /// Current filter text for target search
+    pub filter: String,
+
+    /// Currently selected index in filtered list
+    pub selected: usize,
+}
+
+impl SendToState {
+    /// Create a new send-to state
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            source: None,
+            targets: Vec::new(),
+            filter: String::new(),
+            selected: 0,
+        }
+    }
+
+    /// Start the picker for `source` with the given candidate targets
+    pub fn start(&mut self, source: Uuid, targets: Vec<SendToTarget>) {
+        self.source = Some(source);
+        self.targets = targets;
+        self.filter.clear();
+        self.selected = 0;
+    }
+
+    /// Get filtered targets based on current filter
+    #[must_use]
+    pub fn filtered_targets(&self) -> Vec<&SendToTarget> {
+        let filter_lower = self.filter.to_lowercase();
+        self.targets
+            .iter()
+            .filter(|t| filter_lower.is_empty() || t.label.to_lowercase().contains(&filter_lower))
+            .collect()
+    }
+
+    /// Select next item in filtered list
+    pub fn select_next(&mut self) {
+        let count = self.filtered_targets().len();
+        if count > 0 {
+            self.selected = (self.selected + 1) % count;
+        }
+    }
+
+    /// Select previous item in filtered list
+    pub fn select_prev(&mut self) {
+        let count = self.filtered_targets().len();
+        if count > 0 {
+            self.selected = self.selected.checked_sub(1).unwrap_or(count - 1);
+        }
+    }
+
+    /// Get the currently selected target
+    #[must_use]
+    pub fn selected_target(&self) -> Option<&SendToTarget> {
+        self.filtered_targets().get(self.selected).copied()
+    }
+
+    /// Handle character input in filter
+    pub fn handle_filter_char(&mut self, c: char) {
+        self.filter.push(c);
+        self.selected = 0;
+    }
+
+    /// Handle backspace in filter
+    pub fn handle_filter_backspace(&mut self) {
+        self.filter.pop();
+        self.selected = 0;
+    }
+
+    /// Clear all send-to state
+    pub fn clear(&mut self) {
+        self.source = None;
+        self.targets.clear();
+        self.filter.clear();
+        self.selected = 0;
+    }
+}
@@ -45,6 +45,23 @@ pub struct SpawnState {
 
     /// Repository/workspace root to use when spawning a new root swarm.
     pub root_repo_path: Option<std::path::PathBuf>,
+
+    /// Files to copy into the next new agent's worktree and reference in its prompt.
+    pub context_files: Vec<std::path::PathBuf>,
+
+    /// Ticket the next new root agent is created for.
+    pub ticket: Option<String>,
+
+    /// Command typed for the agent being created, instead of the `agent_program` setting.
+    pub program: Option<String>,
+
+    /// While the command field of the new-agent input is open, the name or prompt typed so far
+    /// and its cursor. The input holds the command meanwhile.
+    pub editing_program: Option<(String, usize)>,
+
+    /// Repository config files already warned about for setting an untrusted
+    /// `default_program`.
+    pub ignored_repo_programs: Vec<std::path::PathBuf>,
 }
 
 impl SpawnState {
@@ -58,9 +75,21 @@ impl SpawnState {
             terminal_counter: 0,
             worktree_conflict: None,
             root_repo_path: None,
+            context_files: Vec::new(),
+            ticket: None,
+            program: None,
+            editing_program: None,
+            ignored_repo_programs: Vec::new(),
         }
     }
 
+    /// Forget the files attached and the command typed for the previous new agent.
+    pub fn start_new_agent(&mut self) {
+        self.context_files.clear();
+        self.program = None;
+        self.editing_program = None;
+    }
+
     /// Increment child count
     pub const fn increment_child_count(&mut self) {
         self.child_count = self.child_count.saturating_add(1);
@@ -0,0 +1,85 @@
+//! What Tenex changed on startup while reconnecting to agents, shown once as a summary.
+
+use std::collections::BTreeSet;
+
+use uuid::Uuid;
+
+/// Hint shown under orphaned mux sessions.
+const ORPHAN_HINT: &str = "Run `tenex reset` to remove them.";
+
+/// Outcome of startup reconciliation (auto-connect and respawn).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StartupReport {
+    /// Agents created by auto-connect, so they are not also counted as reattached.
+    pub adopted_ids: BTreeSet<Uuid>,
+    /// Root agents whose mux session was still running.
+    pub reattached: Vec<String>,
+    /// Root agents whose mux session was recreated.
+    pub respawned: Vec<String>,
+    /// Agents created for existing worktrees that had no stored agent.
+    pub adopted: Vec<String>,
+    /// Root agents whose session is gone and could not be recreated.
+    pub disconnected: Vec<String>,
+    /// Mux sessions of this instance that no stored agent owns.
+    pub orphaned_sessions: Vec<String>,
+}
+
+impl StartupReport {
+    /// Create an empty report.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            adopted_ids: BTreeSet::new(),
+            reattached: Vec::new(),
+            respawned: Vec::new(),
+            adopted: Vec::new(),
+            disconnected: Vec::new(),
+            orphaned_sessions: Vec::new(),
+        }
+    }
+
+    /// Whether startup changed anything worth reporting.
+    ///
+    /// Reattaching to running sessions alone is the normal case and is not reported.
+    #[must_use]
+    pub const fn is_noteworthy(&self) -> bool {
+        !self.respawned.is_empty()
+            || !self.adopted.is_empty()
+            || !self.disconnected.is_empty()
+            || !self.orphaned_sessions.is_empty()
+    }
+
+    /// Report lines for the summary modal.
+    #[must_use]
+    pub fn lines(&self) -> Vec<String> {
+        let sections = [
+            ("Sessions reattached", &self.reattached, None),
+            ("Sessions respawned", &self.respawned, None),
+            ("Worktrees adopted", &self.adopted, None),
+            (
+                "Agents disconnected (session gone, could not respawn)",
+                &self.disconnected,
+                None,
+            ),
+            (
+                "Orphaned mux sessions",
+                &self.orphaned_sessions,
+                Some(ORPHAN_HINT),
+            ),
+        ];
+
+        let mut lines = vec!["What Tenex did while reconnecting to your agents:".to_string()];
+        for (heading, items, hint) in sections {
+            if items.is_empty() {
+                continue;
+            }
+            lines.push(String::new());
+            lines.push(format!("### {heading} ({})", items.len()));
+            lines.extend(items.iter().map(|item| format!("- {item}")));
+            if let Some(hint) = hint {
+                lines.push(hint.to_string());
+            }
+        }
+        lines
+    }
+}
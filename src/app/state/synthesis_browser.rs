@@ -0,0 +1,98 @@
+//! Synthesis browser state: past synthesis files in an agent's worktree
+
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use uuid::Uuid;
+
+/// A synthesis file written for an agent (`.tenex/<uuid>.md`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SynthesisFile {
+    /// The synthesis id, which is also the file name.
+    pub id: Uuid,
+    /// Absolute path of the file.
+    pub path: PathBuf,
+    /// When the file was last written.
+    pub modified: SystemTime,
+    /// Size of the file in bytes.
+    pub size: u64,
+    /// The first lines of the file.
+    pub preview: Vec<String>,
+}
+
+impl SynthesisFile {
+    /// The first eight characters of the synthesis id.
+    #[must_use]
+    pub fn short_id(&self) -> String {
+        self.id.to_string().chars().take(8).collect()
+    }
+}
+
+/// State for the synthesis browser (`/syntheses`)
+#[derive(Debug, Default)]
+pub struct SynthesisBrowserState {
+    /// Agent whose synthesis files are listed.
+    pub agent_id: Option<Uuid>,
+
+    /// Synthesis files, newest first.
+    pub files: Vec<SynthesisFile>,
+
+    /// Currently selected index
+    pub selected: usize,
+}
+
+impl SynthesisBrowserState {
+    /// Create a new synthesis browser state
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            agent_id: None,
+            files: Vec::new(),
+            selected: 0,
+        }
+    }
+
+    /// Start the browser on `agent_id`'s files
+    pub fn start(&mut self, agent_id: Uuid, files: Vec<SynthesisFile>) {
+        self.agent_id = Some(agent_id);
+        self.files = files;
+        self.selected = 0;
+    }
+
+    /// Select next file
+    pub const fn select_next(&mut self) {
+        let count = self.files.len();
+        if count > 0 {
+            self.selected = (self.selected + 1) % count;
+        }
+    }
+
+    /// Select previous file
+    pub fn select_prev(&mut self) {
+        let count = self.files.len();
+        if count > 0 {
+            self.selected = self.selected.checked_sub(1).unwrap_or(count - 1);
+        }
+    }
+
+    /// Get the currently selected file
+    #[must_use]
+    pub fn selected_file(&self) -> Option<&SynthesisFile> {
+        self.files.get(self.selected)
+    }
+
+    /// Drop the selected file from the list, keeping the selection in range
+    pub fn remove_selected(&mut self) {
+        if self.selected < self.files.len() {
+            self.files.remove(self.selected);
+        }
+        self.selected = self.selected.min(self.files.len().saturating_sub(1));
+    }
+
+    /// Clear all synthesis browser state
+    pub fn clear(&mut self) {
+        self.agent_id = None;
+        self.files.clear();
+        self.selected = 0;
+    }
+}
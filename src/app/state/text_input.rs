@@ -22,6 +22,13 @@ impl App {
                 | AppMode::TerminalPrompt(_)
                 | AppMode::CustomAgentCommand(_)
                 | AppMode::SynthesisPrompt(_)
+                | AppMode::EditTask(_)
+                | AppMode::EditScratchpad(_)
+                | AppMode::DependencyMessage(_)
+                | AppMode::Reply(_)
+                | AppMode::AddRepo(_)
+                | AppMode::Scope(_)
+                | AppMode::SwitchModel(_)
         ) || matches!(self.mode, AppMode::Confirming(_))
     }
 
@@ -0,0 +1,88 @@
+//! tmux import picker state: choosing which outside tmux sessions to register as agents
+
+use crate::tmux::TmuxSession;
+
+/// State for the tmux import picker (`/import_tmux`)
+#[derive(Debug, Default)]
+pub struct TmuxImportPickerState {
+    /// tmux sessions that can be imported.
+    pub sessions: Vec<TmuxSession>,
+
+    /// Whether each session is checked for import
+    pub checked: Vec<bool>,
+
+    /// Currently selected index
+    pub selected: usize,
+}
+
+impl TmuxImportPickerState {
+    /// Create a new tmux import picker state
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            sessions: Vec::new(),
+            checked: Vec::new(),
+            selected: 0,
+        }
+    }
+
+    /// Start the picker with the given sessions, none checked
+    pub fn start(&mut self, sessions: Vec<TmuxSession>) {
+        self.checked = vec![false; sessions.len()];
+        self.sessions = sessions;
+        self.selected = 0;
+    }
+
+    /// Select next session
+    pub const fn select_next(&mut self) {
+        let count = self.sessions.len();
+        if count > 0 {
+            self.selected = (self.selected + 1) % count;
+        }
+    }
+
+    /// Select previous session
+    pub fn select_prev(&mut self) {
+        let count = self.sessions.len();
+        if count > 0 {
+            self.selected = self.selected.checked_sub(1).unwrap_or(count - 1);
+        }
+    }
+
+    /// Check or uncheck the selected session
+    pub fn toggle_selected(&mut self) {
+        if let Some(checked) = self.checked.get_mut(self.selected) {
+            *checked = !*checked;
+        }
+    }
+
+    /// Check every session, or uncheck them all when all are already checked
+    pub fn toggle_all(&mut self) {
+        let check = !self.checked.iter().all(|checked| *checked);
+        self.checked.fill(check);
+    }
+
+    /// Take the checked sessions, or the selected one when none is checked, clearing the picker
+    pub fn take_checked(&mut self) -> Vec<TmuxSession> {
+        let mut checked = std::mem::take(&mut self.checked);
+        let sessions = std::mem::take(&mut self.sessions);
+        if !checked.iter().any(|checked| *checked)
+            && let Some(selected) = checked.get_mut(self.selected)
+        {
+            *selected = true;
+        }
+        self.selected = 0;
+        sessions
+            .into_iter()
+            .zip(checked)
+            .filter_map(|(session, checked)| checked.then_some(session))
+            .collect()
+    }
+
+    /// Clear all tmux import picker state
+    pub fn clear(&mut self) {
+        self.sessions.clear();
+        self.checked.clear();
+        self.selected = 0;
+    }
+}
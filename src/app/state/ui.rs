@@ -4,10 +4,13 @@ use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 use uuid::Uuid;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use ratatui::{style::Style, text::Text};
 
+use super::notifications::{NotificationQueue, NotificationSeverity};
+use crate::conversation::{ContextUsage, TokenTally};
+
 const PREVIEW_VT_SCROLLBACK: usize = 10_000;
 
 /// A point in the preview pane selection (absolute line index + 0-based column).
@@ -22,10 +25,14 @@ pub struct PreviewSelectionPoint {
 /// Whether an agent's pane output is changing or stalled.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PaneActivity {
-    /// Output changed since the last observation.
+    /// Output changed since the last observation, or shows a working line of the program's
+    /// status rules.
     Active,
-    /// Output is identical to the last observation.
+    /// Output is identical to the last observation, or shows a waiting line of the program's
+    /// status rules.
     Waiting,
+    /// Output shows an exit banner of the program's status rules.
+    Finished,
 }
 
 /// Cached pane output digest used to detect activity.
@@ -37,6 +44,91 @@ pub struct PaneDigest {
     pub activity: PaneActivity,
 }
 
+/// Latest sampled resource usage for an agent's process tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AgentResourceUsage {
+    /// CPU usage since the previous sample, in percent of one core.
+    pub cpu_percent: u32,
+    /// Resident memory in bytes.
+    pub rss_bytes: u64,
+    /// Number of processes in the tree.
+    pub processes: u32,
+    /// Cumulative CPU time at the last sample (used to compute `cpu_percent`).
+    pub cpu_time_ms: u64,
+    /// When the last sample was taken.
+    pub sampled_at: std::time::Instant,
+    /// Whether the usage exceeded the configured alert thresholds.
+    pub runaway: bool,
+}
+
+impl AgentResourceUsage {
+    /// Compact memory label for the sidebar and status bar (e.g. `512M`, `1.5G`).
+    #[must_use]
+    pub fn memory_label(&self) -> String {
+        const MIB: u64 = 1024 * 1024;
+        const GIB: u64 = 1024 * MIB;
+        if self.rss_bytes >= GIB {
+            let tenths = self.rss_bytes * 10 / GIB;
+            format!("{}.{}G", tenths / 10, tenths % 10)
+        } else {
+            format!("{}M", self.rss_bytes / MIB)
+        }
+    }
+}
+
+/// Changed files an agent should not have touched, as of the last scan.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChangeAlerts {
+    /// Changed files outside the agent's scope.
+    pub outside_scope: Vec<PathBuf>,
+    /// Changed files matching the `protected_paths` setting.
+    pub protected: Vec<PathBuf>,
+}
+
+impl ChangeAlerts {
+    /// Number of flagged files.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.outside_scope.len()
+            + self
+                .protected
+                .iter()
+                .filter(|path| !self.outside_scope.contains(path))
+                .count()
+    }
+
+    /// Whether no file is flagged.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.outside_scope.is_empty() && self.protected.is_empty()
+    }
+
+    /// Whether `path` is already flagged.
+    #[must_use]
+    pub fn contains(&self, path: &Path) -> bool {
+        self.outside_scope.iter().any(|flagged| flagged == path)
+            || self.protected.iter().any(|flagged| flagged == path)
+    }
+}
+
+/// Thresholds above which an agent's process tree counts as runaway.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResourceAlert {
+    /// CPU percent threshold (`None` disables the CPU alert).
+    pub cpu_percent: Option<u32>,
+    /// Resident memory threshold in bytes (`None` disables the memory alert).
+    pub rss_bytes: Option<u64>,
+}
+
+impl ResourceAlert {
+    /// Whether the given usage crosses any enabled threshold.
+    #[must_use]
+    pub fn exceeded_by(self, cpu_percent: u32, rss_bytes: u64) -> bool {
+        self.cpu_percent.is_some_and(|limit| cpu_percent > limit)
+            || self.rss_bytes.is_some_and(|limit| rss_bytes > limit)
+    }
+}
+
 /// How Tenex currently computes sidebar activity digests.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum PaneActivityDigestMode {
@@ -113,6 +205,9 @@ pub struct UiState {
     /// Scroll position in commits pane
     pub commits_scroll: usize,
 
+    /// Scroll position in scratchpad pane
+    pub scratchpad_scroll: usize,
+
     /// Cursor position (selected line index) in diff pane
     pub diff_cursor: usize,
 
@@ -157,12 +252,18 @@ pub struct UiState {
     /// Whether the user is currently dragging to select preview lines.
     pub preview_selection_dragging: bool,
 
+    /// Agent and text of the last completed preview selection (sent by `>` instead of the tail).
+    pub last_preview_selection: Option<(Uuid, String)>,
+
     /// Cached cursor position in the selected pane (x, y), 0-based, and whether it is hidden.
     pub preview_cursor_position: Option<(u16, u16, bool)>,
 
     /// Cached pane size for the selected pane (cols, rows).
     pub preview_pane_size: Option<(u16, u16)>,
 
+    /// Title the selected pane's program last set with OSC 0/2, if any.
+    pub preview_pane_title: Option<String>,
+
     /// Per-target VT100 parsing state for the preview pane.
     pub preview_vt_by_target: BTreeMap<String, PreviewVtState>,
 
@@ -178,6 +279,12 @@ pub struct UiState {
     /// Cached byte ranges for each commit line (matches `commits_content.lines()`)
     pub commits_line_ranges: Vec<(usize, usize)>,
 
+    /// Cached scratchpad content for the selected agent's root worktree
+    pub scratchpad_content: String,
+
+    /// Cached byte ranges for each scratchpad line (matches `scratchpad_content.lines()`)
+    pub scratchpad_line_ranges: Vec<(usize, usize)>,
+
     /// Cached metadata for each diff line (matches `diff_content.lines()`)
     pub diff_line_meta: Vec<DiffLineMeta>,
 
@@ -205,6 +312,21 @@ pub struct UiState {
     /// Whether the diff has unseen changes since last view
     pub diff_has_unseen_changes: bool,
 
+    /// Changed files outside the agent's scope or in protected paths, per agent
+    pub change_alerts_by_agent: BTreeMap<Uuid, ChangeAlerts>,
+
+    /// How full each agent's context window is, as of the last transcript read
+    pub context_usage_by_agent: BTreeMap<Uuid, ContextUsage>,
+
+    /// Conversation transcript found for each agent, so it is not searched for again
+    pub transcript_by_agent: BTreeMap<Uuid, PathBuf>,
+
+    /// Tokens counted so far in the transcript each agent's spend is read from
+    pub token_tally_by_agent: BTreeMap<Uuid, (PathBuf, TokenTally)>,
+
+    /// Whether agents may go on past the `budget_usd` setting, as confirmed after it was reached
+    pub budget_override: bool,
+
     /// Current commits hash (0 when no commits)
     pub commits_hash: u64,
 
@@ -217,6 +339,9 @@ pub struct UiState {
     /// Request an immediate diff refresh after an edit action
     pub diff_force_refresh: bool,
 
+    /// Request a status and activity sync of every agent, not just foreground ones
+    pub full_sync_requested: bool,
+
     /// Cached terminal viewport dimensions (width, height).
     pub terminal_dimensions: Option<(u16, u16)>,
 
@@ -229,8 +354,17 @@ pub struct UiState {
     /// Last error message (if any)
     pub last_error: Option<String>,
 
-    /// Status message to display
-    pub status_message: Option<String>,
+    /// Whether the error modal shows the full message instead of its summary.
+    pub error_details_expanded: bool,
+
+    /// Scroll offset (in wrapped lines) of the full message in the error modal.
+    pub error_details_scroll: usize,
+
+    /// Line of the log file the last error was written to (`None` when logging is off).
+    pub error_log_line: Option<usize>,
+
+    /// Queued status bar notifications (and their history).
+    pub notifications: NotificationQueue,
 
     /// Cached pane output digests per agent (used for the `●`/`◐`/`○` activity indicator).
     pub pane_digest_by_agent: BTreeMap<Uuid, PaneDigest>,
@@ -243,6 +377,30 @@ pub struct UiState {
 
     /// Collapsed project sections in the sidebar (keyed by repository/workspace root path).
     pub collapsed_projects: BTreeSet<std::path::PathBuf>,
+
+    /// Latest sampled CPU/memory usage per agent.
+    pub resource_usage_by_agent: BTreeMap<Uuid, AgentResourceUsage>,
+
+    /// Samples `resource_usage_by_agent` off the UI thread.
+    pub resource_sampler: super::ResourceSampler,
+
+    /// Window titles last sent to the mux daemon, per agent.
+    pub mux_title_by_agent: BTreeMap<Uuid, String>,
+
+    /// Title last written to the host terminal (`None` until the first write).
+    pub terminal_title: Option<String>,
+
+    /// Ahead/behind counts against the base branch per root agent (refreshed by fetch).
+    pub branch_divergence_by_agent: BTreeMap<Uuid, crate::git::BranchDivergence>,
+
+    /// Plugin sidebar column values per agent, in plugin order (refreshed periodically).
+    pub plugin_columns_by_agent: BTreeMap<Uuid, Vec<String>>,
+
+    /// When pane activity was last credited to agents' active time.
+    pub active_time_accounted_at: Option<std::time::Instant>,
+
+    /// When accumulated active time was last persisted.
+    pub active_time_saved_at: Option<std::time::Instant>,
 }
 
 impl UiState {
@@ -254,6 +412,7 @@ impl UiState {
             preview_scroll: 0,
             diff_scroll: 0,
             commits_scroll: 0,
+            scratchpad_scroll: 0,
             diff_cursor: 0,
             diff_visual_anchor: None,
             help_scroll: 0,
@@ -270,13 +429,17 @@ impl UiState {
             preview_selection_anchor: None,
             preview_selection_cursor: PreviewSelectionPoint { line: 0, column: 0 },
             preview_selection_dragging: false,
+            last_preview_selection: None,
             preview_cursor_position: None,
             preview_pane_size: None,
+            preview_pane_title: None,
             preview_vt_by_target: BTreeMap::new(),
             diff_content: String::new(),
             diff_line_ranges: Vec::new(),
             commits_content: String::new(),
             commits_line_ranges: Vec::new(),
+            scratchpad_content: String::new(),
+            scratchpad_line_ranges: Vec::new(),
             diff_line_meta: Vec::new(),
             diff_model: None,
             diff_folded_files: Vec::new(),
@@ -286,19 +449,36 @@ impl UiState {
             diff_hash: 0,
             diff_last_seen_hash_by_agent: Vec::new(),
             diff_has_unseen_changes: false,
+            change_alerts_by_agent: BTreeMap::new(),
+            context_usage_by_agent: BTreeMap::new(),
+            transcript_by_agent: BTreeMap::new(),
+            token_tally_by_agent: BTreeMap::new(),
+            budget_override: false,
             commits_hash: 0,
             commits_last_seen_hash_by_agent: Vec::new(),
             commits_has_unseen_changes: false,
             diff_force_refresh: false,
+            full_sync_requested: false,
             terminal_dimensions: None,
             preview_dimensions: None,
             muxd_version_mismatch: None,
             last_error: None,
-            status_message: None,
+            error_details_expanded: false,
+            error_details_scroll: 0,
+            error_log_line: None,
+            notifications: NotificationQueue::new(),
             pane_digest_by_agent: BTreeMap::new(),
             pane_last_seen_hash_by_agent: BTreeMap::new(),
             pane_activity_digest_mode: PaneActivityDigestMode::Cursor,
             collapsed_projects: BTreeSet::new(),
+            resource_usage_by_agent: BTreeMap::new(),
+            resource_sampler: super::ResourceSampler::new(),
+            mux_title_by_agent: BTreeMap::new(),
+            terminal_title: None,
+            branch_divergence_by_agent: BTreeMap::new(),
+            plugin_columns_by_agent: BTreeMap::new(),
+            active_time_accounted_at: None,
+            active_time_saved_at: None,
         }
     }
 
@@ -323,6 +503,24 @@ impl UiState {
         )
     }
 
+    /// Whether the agent's output shows that its program has exited.
+    #[must_use]
+    pub fn agent_has_finished(&self, agent_id: Uuid) -> bool {
+        self.pane_digest_by_agent
+            .get(&agent_id)
+            .is_some_and(|digest| digest.activity == PaneActivity::Finished)
+    }
+
+    /// Agents whose pane output changed at the last observation.
+    #[must_use]
+    pub fn active_agent_ids(&self) -> Vec<Uuid> {
+        self.pane_digest_by_agent
+            .iter()
+            .filter(|(_, digest)| digest.activity == PaneActivity::Active)
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
     #[must_use]
     pub fn agent_has_unseen_waiting_output(&self, agent_id: Uuid) -> bool {
         let Some(digest) = self.pane_digest_by_agent.get(&agent_id) else {
@@ -345,16 +543,57 @@ impl UiState {
         }
     }
 
-    pub fn observe_agent_pane_digest(&mut self, agent_id: Uuid, hash: u64) {
-        let activity = match self.pane_digest_by_agent.get(&agent_id) {
+    /// Record the output digest of an agent. `matched` is the activity its status rules
+    /// matched in the output, if any; otherwise unchanged output counts as waiting.
+    pub fn observe_agent_pane_digest(
+        &mut self,
+        agent_id: Uuid,
+        hash: u64,
+        matched: Option<PaneActivity>,
+    ) {
+        let activity = matched.unwrap_or_else(|| match self.pane_digest_by_agent.get(&agent_id) {
             Some(previous) if previous.hash == hash => PaneActivity::Waiting,
             _ => PaneActivity::Active,
-        };
+        });
 
         self.pane_digest_by_agent
             .insert(agent_id, PaneDigest { hash, activity });
     }
 
+    /// Record a resource sample for an agent and return the updated usage.
+    ///
+    /// CPU percent is derived from the CPU time consumed since the previous sample.
+    pub fn observe_agent_resource_usage(
+        &mut self,
+        agent_id: Uuid,
+        sample: crate::mux::ProcessUsage,
+        sampled_at: std::time::Instant,
+        alert: ResourceAlert,
+    ) -> AgentResourceUsage {
+        let cpu_percent = self
+            .resource_usage_by_agent
+            .get(&agent_id)
+            .and_then(|previous| {
+                let elapsed_ms = sampled_at
+                    .checked_duration_since(previous.sampled_at)?
+                    .as_millis();
+                let used_ms = u128::from(sample.cpu_time_ms.saturating_sub(previous.cpu_time_ms));
+                (elapsed_ms > 0).then(|| used_ms * 100 / elapsed_ms)
+            })
+            .map_or(0, |percent| u32::try_from(percent).unwrap_or(u32::MAX));
+
+        let usage = AgentResourceUsage {
+            cpu_percent,
+            rss_bytes: sample.rss_bytes,
+            processes: sample.processes,
+            cpu_time_ms: sample.cpu_time_ms,
+            sampled_at,
+            runaway: alert.exceeded_by(cpu_percent, sample.rss_bytes),
+        };
+        self.resource_usage_by_agent.insert(agent_id, usage);
+        usage
+    }
+
     pub fn retain_agent_pane_digests<F>(&mut self, mut keep: F)
     where
         F: FnMut(&Uuid) -> bool,
@@ -395,6 +634,14 @@ impl UiState {
         self.normalize_commits_scroll();
     }
 
+    /// Set scratchpad content and refresh cached line ranges.
+    pub fn set_scratchpad_content(&mut self, content: impl Into<String>) {
+        let content = content.into();
+        self.scratchpad_line_ranges = compute_line_ranges(&content);
+        self.scratchpad_content = content;
+        self.normalize_scratchpad_scroll();
+    }
+
     /// Set diff content, line metadata, and refresh cached line ranges.
     pub fn set_diff_view(&mut self, content: impl Into<String>, meta: Vec<DiffLineMeta>) {
         let content = content.into();
@@ -429,6 +676,8 @@ impl UiState {
         // Commits: set to 0 to show from top (most recent first)
         self.commits_scroll = 0;
         self.normalize_commits_scroll();
+        // Scratchpad: set to 0 to show from top
+        self.scratchpad_scroll = 0;
     }
 
     /// Reset interactive diff state when switching agents/worktrees.
@@ -537,6 +786,20 @@ impl UiState {
         self.normalize_commits_scroll();
     }
 
+    /// Scroll up in the scratchpad pane by the given amount.
+    pub fn scroll_scratchpad_up(&mut self, amount: usize) {
+        self.normalize_scratchpad_scroll();
+        self.scratchpad_scroll = self.scratchpad_scroll.saturating_sub(amount);
+        self.normalize_scratchpad_scroll();
+    }
+
+    /// Scroll down in the scratchpad pane by the given amount.
+    pub fn scroll_scratchpad_down(&mut self, amount: usize) {
+        self.normalize_scratchpad_scroll();
+        self.scratchpad_scroll = self.scratchpad_scroll.saturating_add(amount);
+        self.normalize_scratchpad_scroll();
+    }
+
     /// Check if preview scroll is at bottom and re-enable follow mode if so
     fn check_preview_follow(&mut self) {
         let preview_lines = self.preview_text.lines.len();
@@ -580,6 +843,16 @@ impl UiState {
         }
     }
 
+    fn normalize_scratchpad_scroll(&mut self) {
+        let scratchpad_lines = self.scratchpad_line_ranges.len();
+        let visible_height = self.preview_dimensions.map_or(20, |(_, h)| usize::from(h));
+        let scratchpad_max = scratchpad_lines.saturating_sub(visible_height);
+
+        if self.scratchpad_scroll > scratchpad_max {
+            self.scratchpad_scroll = scratchpad_max;
+        }
+    }
+
     fn normalize_diff_visual_anchor(&mut self) {
         let Some(anchor) = self.diff_visual_anchor else {
             return;
@@ -665,6 +938,11 @@ impl UiState {
         self.commits_scroll = 0;
     }
 
+    /// Scroll scratchpad to the top
+    pub const fn scratchpad_to_top(&mut self) {
+        self.scratchpad_scroll = 0;
+    }
+
     /// Scroll preview to the bottom
     pub const fn preview_to_bottom(&mut self, content_lines: usize, visible_lines: usize) {
         self.preview_scroll = content_lines.saturating_sub(visible_lines);
@@ -682,6 +960,11 @@ impl UiState {
         self.commits_scroll = content_lines.saturating_sub(visible_lines);
     }
 
+    /// Scroll scratchpad to the bottom
+    pub const fn scratchpad_to_bottom(&mut self, content_lines: usize, visible_lines: usize) {
+        self.scratchpad_scroll = content_lines.saturating_sub(visible_lines);
+    }
+
     /// Move the diff cursor up by the given amount.
     pub fn diff_cursor_up(&mut self, amount: usize) {
         self.diff_cursor = self.diff_cursor.saturating_sub(amount);
@@ -843,21 +1126,56 @@ impl UiState {
         let msg = message.into();
         tracing::warn!(error = %msg, "Application error");
         self.last_error = Some(msg);
+        self.error_details_expanded = false;
+        self.error_details_scroll = 0;
+        // The log is written synchronously, so the error just logged is its last line.
+        self.error_log_line = std::fs::read_to_string(crate::paths::log_path())
+            .ok()
+            .map(|log| log.lines().count())
+            .filter(|&lines| lines > 0);
     }
 
     /// Clear the current error message
     pub fn clear_error(&mut self) {
         self.last_error = None;
+        self.error_details_expanded = false;
+        self.error_details_scroll = 0;
+        self.error_log_line = None;
+    }
+
+    /// Text copied from the error modal: the full message and where it was logged.
+    #[must_use]
+    pub fn error_report(&self, message: &str) -> String {
+        let log_path = crate::paths::log_path();
+        self.error_log_line.map_or_else(
+            || message.to_string(),
+            |line| format!("{message}\n\nLog: {}:{line}", log_path.display()),
+        )
     }
 
     /// Set a status message to display
     pub fn set_status(&mut self, message: impl Into<String>) {
-        self.status_message = Some(message.into());
+        self.notifications.push(message, NotificationSeverity::Info);
+    }
+
+    /// Show a warning in the status bar.
+    pub fn notify_warning(&mut self, message: impl Into<String>) {
+        self.notifications
+            .push(message, NotificationSeverity::Warning);
+    }
+
+    /// Show a non-blocking error toast in the status bar (for background operations).
+    pub fn notify_error(&mut self, message: impl Into<String>) {
+        let message = message.into();
+        tracing::warn!(error = %message, "Background error");
+        crate::metrics::record_error();
+        self.notifications
+            .push(message, NotificationSeverity::Error);
     }
 
     /// Clear the current status message
     pub fn clear_status(&mut self) {
-        self.status_message = None;
+        self.notifications.clear();
     }
 }
 
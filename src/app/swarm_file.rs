@@ -0,0 +1,224 @@
+//! Declarative swarm definitions (`tenex apply swarm.yaml`)
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+/// A tree of agents to create: a root agent, its children, and the stages that follow them.
+///
+/// Swarm files are YAML (`.yaml`, `.yml`), TOML (`.toml`), or JSON (`.json`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SwarmFile {
+    /// Title of the root agent, which also names its branch.
+    pub title: String,
+
+    /// Prompt the root agent starts with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+
+    /// Program the root agent runs; the `agent_program` setting when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub program: Option<String>,
+
+    /// Minutes the root agent may run before it is asked to wrap up and stopped; the
+    /// `max_runtime_mins` setting when unset, and no limit when `0`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_runtime_mins: Option<u64>,
+
+    /// Repository to work in, relative to the swarm file; the current directory when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo: Option<PathBuf>,
+
+    /// Agents under the root.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub agents: Vec<SwarmAgent>,
+
+    /// Whether the root synthesizes its children's work once they have all finished.
+    #[serde(default)]
+    pub synthesize: bool,
+
+    /// Review swarm started under the root once every other agent has finished.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub review: Option<SwarmReview>,
+}
+
+/// One entry of a swarm file's agent tree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SwarmAgent {
+    /// Name of the agent, unique within the file. With a `count` above 1, the agents are
+    /// numbered (`name 1`, `name 2`, ...).
+    pub name: String,
+
+    /// Prompt the agent starts with.
+    pub prompt: String,
+
+    /// How many agents to start with this prompt.
+    #[serde(default = "default_count")]
+    pub count: usize,
+
+    /// Program the agent runs; the `agent_program` setting when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub program: Option<String>,
+
+    /// Minutes each of these agents may run before it is asked to wrap up and stopped; the
+    /// `max_runtime_mins` setting when unset, and no limit when `0`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_runtime_mins: Option<u64>,
+
+    /// Repository to start the agent in as a root of its own, relative to the swarm file (top
+    /// level only). It gets the swarm root's ticket, and the root's synthesis collects its work.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo: Option<PathBuf>,
+
+    /// Names of agents that must finish before this one starts.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+
+    /// Agents under this one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub agents: Vec<Self>,
+}
+
+/// The review stage of a swarm file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SwarmReview {
+    /// Number of reviewers.
+    #[serde(default = "default_reviewer_count")]
+    pub count: usize,
+
+    /// Branch to review against; the root's base branch when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+}
+
+const fn default_count() -> usize {
+    1
+}
+
+const fn default_reviewer_count() -> usize {
+    3
+}
+
+impl SwarmFile {
+    /// Read and validate the swarm file at `path`, picking the format from its extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed, or describes an invalid swarm.
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let swarm: Self = match extension.as_str() {
+            "yaml" | "yml" => serde_norway::from_str(&contents)
+                .with_context(|| format!("Failed to parse {}", path.display()))?,
+            "toml" => toml::from_str(&contents)
+                .with_context(|| format!("Failed to parse {}", path.display()))?,
+            "json" => serde_json::from_str(&contents)
+                .with_context(|| format!("Failed to parse {}", path.display()))?,
+            _ => bail!(
+                "Unknown swarm file format for {}: use .yaml, .yml, .toml, or .json",
+                path.display()
+            ),
+        };
+        swarm.validate()?;
+        Ok(swarm)
+    }
+
+    /// Every agent entry in the tree, depth first, with the name of its parent entry (`None`
+    /// for children of the root).
+    #[must_use]
+    pub fn entries(&self) -> Vec<(Option<&str>, &SwarmAgent)> {
+        let mut entries = Vec::new();
+        let mut stack: Vec<(Option<&str>, &SwarmAgent)> = self
+            .agents
+            .iter()
+            .rev()
+            .map(|agent| (None, agent))
+            .collect();
+        while let Some((parent, agent)) = stack.pop() {
+            entries.push((parent, agent));
+            stack.extend(
+                agent
+                    .agents
+                    .iter()
+                    .rev()
+                    .map(|child| (Some(agent.name.as_str()), child)),
+            );
+        }
+        entries
+    }
+
+    /// Check names, counts, and dependencies.
+    fn validate(&self) -> Result<()> {
+        if self.title.trim().is_empty() {
+            bail!("The swarm needs a title");
+        }
+        let entries = self.entries();
+        let mut names = HashSet::new();
+        for (parent, agent) in &entries {
+            if agent.name.trim().is_empty() {
+                bail!("Every agent needs a name");
+            }
+            if !names.insert(agent.name.as_str()) {
+                bail!("Agent name {:?} is used more than once", agent.name);
+            }
+            if agent.count == 0 {
+                bail!("Agent {:?} has a count of 0", agent.name);
+            }
+            if agent.repo.is_some() && parent.is_some() {
+                bail!(
+                    "Agent {:?} sets a repo, which only top-level agents can",
+                    agent.name
+                );
+            }
+            if agent.repo.is_some() && agent.count != 1 {
+                bail!("Agent {:?} sets a repo, so its count must be 1", agent.name);
+            }
+        }
+        for (_, agent) in &entries {
+            for dependency in &agent.depends_on {
+                if !names.contains(dependency.as_str()) {
+                    bail!(
+                        "Agent {:?} depends on {dependency:?}, which is not in the file",
+                        agent.name
+                    );
+                }
+            }
+        }
+
+        // An agent starts after its parent entry and its dependencies, so those must not loop.
+        let mut started: HashSet<&str> = HashSet::new();
+        while started.len() < entries.len() {
+            let ready: Vec<&str> = entries
+                .iter()
+                .filter(|(parent, agent)| {
+                    !started.contains(agent.name.as_str())
+                        && parent.is_none_or(|parent| started.contains(parent))
+                        && agent
+                            .depends_on
+                            .iter()
+                            .all(|dependency| started.contains(dependency.as_str()))
+                })
+                .map(|(_, agent)| agent.name.as_str())
+                .collect();
+            if ready.is_empty() {
+                bail!("The agents' dependencies form a cycle");
+            }
+            started.extend(ready);
+        }
+        if self.review.as_ref().is_some_and(|review| review.count == 0) {
+            bail!("The review stage has a count of 0");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,297 @@
+//! Creating the agents of a swarm file for `tenex apply`.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+use serde_json::json;
+use uuid::Uuid;
+
+use super::run::{IdleWatch, POLL_INTERVAL, Progress, wait_until_idle};
+use crate::agent::branch_title;
+use crate::api::Tenex;
+use crate::app::{SwarmAgent, SwarmFile};
+
+/// Options for `tenex apply`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApplyOptions {
+    /// Swarm file to apply.
+    pub path: PathBuf,
+    /// Give up when the swarm takes longer than this.
+    pub timeout: Option<Duration>,
+    /// How long an agent's output must stay unchanged before it counts as finished.
+    pub idle: Duration,
+    /// Print progress as JSON lines instead of text.
+    pub json: bool,
+}
+
+/// Create the agents the swarm file at `options.path` describes.
+///
+/// Agents start once their parent entry has started and every agent they depend on has
+/// finished. The command returns when every agent has started, or, when the file has a
+/// synthesis or review stage, once those stages have started too.
+///
+/// # Errors
+///
+/// Returns an error if the file is invalid, an agent cannot be created or stops responding, or
+/// the swarm exceeds its timeout.
+pub fn cmd_apply(options: &ApplyOptions) -> Result<()> {
+    let progress = Progress {
+        json: options.json,
+        started: Instant::now(),
+    };
+    let result = apply_swarm(options, &progress);
+    if let Err(err) = &result {
+        progress.emit(
+            "failed",
+            &format!("Apply failed: {err:#}"),
+            json!({ "error": format!("{err:#}") }),
+        );
+    }
+    result
+}
+
+fn apply_swarm(options: &ApplyOptions, progress: &Progress) -> Result<()> {
+    let path = std::fs::canonicalize(&options.path)
+        .with_context(|| format!("Failed to find {}", options.path.display()))?;
+    let swarm = SwarmFile::load(&path)?;
+    let deadline = options.timeout.map(|timeout| progress.started + timeout);
+    let swarm_dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let repo = match &swarm.repo {
+        Some(repo) => swarm_dir.join(repo),
+        None => std::env::current_dir()?,
+    };
+
+    let mut tenex = Tenex::open()?;
+    tenex.set_program(swarm.program.as_deref());
+    let root = tenex.spawn(&repo, &swarm.title, swarm.prompt.as_deref())?;
+    tenex.set_program(None);
+    tenex.record_swarm_file(root, path.clone())?;
+    if swarm.max_runtime_mins.is_some() {
+        tenex.set_max_runtime(root, swarm.max_runtime_mins)?;
+    }
+    progress.emit(
+        "started",
+        &format!("Started {} ({root})", swarm.title),
+        json!({ "name": swarm.title, "agents": [root] }),
+    );
+
+    let stage = Stage {
+        swarm_dir,
+        idle: options.idle,
+        deadline,
+        progress,
+    };
+    start_entries(&mut tenex, &swarm, root, &stage)?;
+
+    if swarm.synthesize {
+        synthesize_linked_roots(&mut tenex, root, &stage)?;
+        tenex.synthesize(root, None)?;
+        progress.emit(
+            "synthesize",
+            &format!("Handed the children's work to {}", swarm.title),
+            json!({ "agents": [root] }),
+        );
+        if swarm.review.is_some() {
+            wait_until_idle(&tenex, &[root], options.idle, deadline, progress)?;
+        }
+    }
+
+    if let Some(review) = &swarm.review {
+        let base = review
+            .base
+            .clone()
+            .or_else(|| {
+                tenex
+                    .agent(root)
+                    .and_then(|agent| agent.base_branch.clone())
+            })
+            .ok_or_else(|| anyhow::anyhow!("Set review.base: the root's base branch is unknown"))?;
+        let reviewers = tenex.review(root, review.count, &base)?;
+        progress.emit(
+            "review",
+            &format!("Started {} reviewer(s) against {base}", reviewers.len()),
+            json!({ "base": base, "agents": reviewers }),
+        );
+    }
+
+    progress.emit(
+        "done",
+        &format!("Applied {}", path.display()),
+        json!({ "root": root }),
+    );
+    Ok(())
+}
+
+/// Where and how long the agents of a swarm file run.
+struct Stage<'a> {
+    /// Directory of the swarm file, which `repo` paths are relative to.
+    swarm_dir: &'a Path,
+    /// How long an agent's output must stay unchanged before it counts as finished.
+    idle: Duration,
+    /// When to give up.
+    deadline: Option<Instant>,
+    /// Where progress is reported.
+    progress: &'a Progress,
+}
+
+/// Start every agent entry of `swarm` under `root` in dependency order, waiting for agents
+/// other entries depend on to finish. With a synthesis or review stage, wait for every agent
+/// to finish.
+///
+/// Top-level entries with a `repo` start as roots of their own in that repository.
+fn start_entries(
+    tenex: &mut Tenex,
+    swarm: &SwarmFile,
+    root: Uuid,
+    stage: &Stage<'_>,
+) -> Result<()> {
+    let Stage {
+        swarm_dir,
+        idle,
+        deadline,
+        progress,
+    } = *stage;
+    let entries = swarm.entries();
+    let wait_for_all = swarm.synthesize || swarm.review.is_some();
+    let awaited: HashSet<&str> = entries
+        .iter()
+        .flat_map(|(_, agent)| agent.depends_on.iter().map(String::as_str))
+        .collect();
+    let mut started: HashMap<&str, Vec<Uuid>> = HashMap::new();
+    let mut finished: HashSet<&str> = HashSet::new();
+    let mut watch = IdleWatch::new(idle);
+
+    loop {
+        for (parent, agent) in &entries {
+            if started.contains_key(agent.name.as_str())
+                || !agent
+                    .depends_on
+                    .iter()
+                    .all(|dependency| finished.contains(dependency.as_str()))
+            {
+                continue;
+            }
+            let ids = match (parent, &agent.repo) {
+                (None, Some(repo)) => vec![start_linked_root(
+                    tenex,
+                    root,
+                    &swarm_dir.join(repo),
+                    agent,
+                )?],
+                (None, None) => start_entry(tenex, &[root], agent)?,
+                (Some(parent), _) => match started.get(parent) {
+                    Some(parents) => start_entry(tenex, parents, agent)?,
+                    None => continue,
+                },
+            };
+            progress.emit(
+                "started",
+                &format!("Started {} ({} agent(s))", agent.name, ids.len()),
+                json!({ "name": agent.name, "agents": ids }),
+            );
+            started.insert(agent.name.as_str(), ids);
+        }
+
+        let mut waiting = 0;
+        for (name, ids) in &started {
+            if finished.contains(name) || !(wait_for_all || awaited.contains(name)) {
+                continue;
+            }
+            let mut idle = true;
+            for &id in ids {
+                idle &= watch.is_idle(tenex, id)?;
+            }
+            if idle {
+                finished.insert(name);
+                progress.emit(
+                    "finished",
+                    &format!("{name} finished"),
+                    json!({ "name": name, "agents": ids }),
+                );
+            } else {
+                waiting += 1;
+            }
+        }
+
+        let unstarted = entries.len() - started.len();
+        if unstarted == 0 && waiting == 0 {
+            return Ok(());
+        }
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            bail!("Timed out with {unstarted} agent(s) not started and {waiting} still working");
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Start a top-level entry that sets `repo` as a root of its own in `repo`, with the ticket of
+/// the swarm's `root`, and link it to `root`.
+fn start_linked_root(
+    tenex: &mut Tenex,
+    root: Uuid,
+    repo: &Path,
+    agent: &SwarmAgent,
+) -> Result<Uuid> {
+    let ticket = tenex.agent(root).and_then(|root| root.ticket.clone());
+    let title = branch_title(ticket.as_deref(), &agent.name);
+    tenex.set_program(agent.program.as_deref());
+    let spawned = tenex.spawn(repo, &title, Some(&agent.prompt));
+    tenex.set_program(None);
+    let id = spawned?;
+    tenex.link_to_swarm(id, root)?;
+    if agent.max_runtime_mins.is_some() {
+        tenex.set_max_runtime(id, agent.max_runtime_mins)?;
+    }
+    Ok(id)
+}
+
+/// Have each root the swarm started in another repository collect its own children's work,
+/// and wait for them to finish, so the swarm root's synthesis sees the combined result.
+fn synthesize_linked_roots(tenex: &mut Tenex, root: Uuid, stage: &Stage<'_>) -> Result<()> {
+    let linked: Vec<Uuid> = tenex
+        .agents()
+        .filter(|agent| agent.swarm_root == Some(root))
+        .map(|agent| agent.id)
+        .filter(|id| tenex.agents().any(|agent| agent.parent_id == Some(*id)))
+        .collect();
+    if linked.is_empty() {
+        return Ok(());
+    }
+    for &id in &linked {
+        tenex.synthesize(id, None)?;
+    }
+    stage.progress.emit(
+        "synthesize",
+        &format!(
+            "Handed the children's work to {} linked root(s)",
+            linked.len()
+        ),
+        json!({ "agents": linked }),
+    );
+    wait_until_idle(tenex, &linked, stage.idle, stage.deadline, stage.progress)
+}
+
+/// Start `agent.count` agents for an entry under each of `parents`, named after the entry.
+fn start_entry(tenex: &mut Tenex, parents: &[Uuid], agent: &SwarmAgent) -> Result<Vec<Uuid>> {
+    tenex.set_program(agent.program.as_deref());
+    let mut ids = Vec::new();
+    for &parent in parents {
+        let children = tenex.spawn_children(parent, agent.count, &agent.prompt)?;
+        for (number, &child) in children.iter().enumerate() {
+            let title = if agent.count == 1 {
+                agent.name.clone()
+            } else {
+                format!("{} {}", agent.name, number + 1)
+            };
+            tenex.rename(child, &title)?;
+            if agent.max_runtime_mins.is_some() {
+                tenex.set_max_runtime(child, agent.max_runtime_mins)?;
+            }
+        }
+        ids.extend(children);
+    }
+    tenex.set_program(None);
+    Ok(ids)
+}
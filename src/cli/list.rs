@@ -0,0 +1,176 @@
+//! Plain-text agent list and announcements of agent state changes for `tenex list`.
+//!
+//! Each line is a sentence a screen reader can read on its own, unlike the TUI's grid, so
+//! lines carry no timestamps or symbols.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::run::IdleWatch;
+use crate::agent::{Agent, Status};
+use crate::api::Tenex;
+
+/// How often agents are checked for changes while following.
+const FOLLOW_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Options for `tenex list`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListOptions {
+    /// Keep running and announce each change.
+    pub follow: bool,
+    /// How long a running agent's output must stay unchanged to count as waiting for input.
+    pub idle: Duration,
+}
+
+/// What was last announced about one agent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Seen {
+    title: String,
+    state: String,
+    waiting: Option<bool>,
+}
+
+/// Print one sentence per agent and, with `follow`, announce changes until interrupted.
+///
+/// # Errors
+///
+/// Returns an error if the instance cannot be opened or its state file cannot be read.
+pub fn cmd_list(options: ListOptions) -> Result<()> {
+    let mut tenex = Tenex::open()?;
+    let agents: Vec<&Agent> = tenex.agents().collect();
+    if agents.is_empty() {
+        println!("No agents.");
+    }
+    for agent in &agents {
+        println!("{}", describe(&tenex, agent));
+    }
+    if options.follow {
+        follow(&mut tenex, options.idle)?;
+    }
+    Ok(())
+}
+
+/// A sentence naming the agent, its parent, its state, its branch, its age, and how long it
+/// ran once it stopped.
+fn describe(tenex: &Tenex, agent: &Agent) -> String {
+    let parent = agent
+        .parent_id
+        .and_then(|id| tenex.agent(id))
+        .map(|parent| format!(", child of {}", parent.title))
+        .unwrap_or_default();
+    let ran = if agent.status.is_stopped() {
+        format!(", ran for {}", duration_in_words(agent.elapsed()))
+    } else {
+        String::new()
+    };
+    format!(
+        "{}{parent}: {}. Branch {}, started {} ago{ran}.",
+        agent.title,
+        state(agent),
+        agent.branch,
+        duration_in_words(Utc::now().signed_duration_since(agent.created_at))
+    )
+}
+
+/// `elapsed` spelled out, such as `2 hours 5 minutes`, since `2h5m` reads badly aloud.
+fn duration_in_words(elapsed: chrono::Duration) -> String {
+    let unit = |count: i64, name: &str| {
+        if count == 1 {
+            format!("1 {name}")
+        } else {
+            format!("{count} {name}s")
+        }
+    };
+    let minutes = elapsed.num_minutes();
+    match (elapsed.num_days(), elapsed.num_hours() % 24, minutes % 60) {
+        _ if minutes < 1 => "less than a minute".to_string(),
+        (0, 0, minutes) => unit(minutes, "minute"),
+        (0, hours, 0) => unit(hours, "hour"),
+        (0, hours, minutes) => format!("{} {}", unit(hours, "hour"), unit(minutes, "minute")),
+        (days, 0, _) => unit(days, "day"),
+        (days, hours, _) => format!("{} {}", unit(days, "day"), unit(hours, "hour")),
+    }
+}
+
+/// The agent's state in words.
+fn state(agent: &Agent) -> String {
+    if agent.budget.is_paused() {
+        return "paused over budget".to_string();
+    }
+    match agent.status {
+        Status::Starting => "starting".to_string(),
+        Status::Running => "running".to_string(),
+        Status::Finished(0) => "exited".to_string(),
+        Status::Finished(code) => format!("exited with code {code}"),
+        Status::TimedOut => "timed out".to_string(),
+    }
+}
+
+/// Announce agents that appear, disappear, are renamed, change state, or start or stop
+/// waiting for input.
+fn follow(tenex: &mut Tenex, idle: Duration) -> Result<()> {
+    println!("Following agents. Press Ctrl+C to stop.");
+    let mut watch = IdleWatch::new(idle);
+    let mut seen: HashMap<Uuid, Seen> = tenex
+        .agents()
+        .map(|agent| {
+            let seen = Seen {
+                title: agent.title.clone(),
+                state: state(agent),
+                waiting: None,
+            };
+            (agent.id, seen)
+        })
+        .collect();
+
+    loop {
+        std::thread::sleep(FOLLOW_INTERVAL);
+        tenex.reload()?;
+        let mut current = HashMap::new();
+        for agent in tenex.agents() {
+            let previous = seen.remove(&agent.id);
+            let waiting = (agent.status == Status::Running && !agent.budget.is_paused())
+                .then(|| watch.is_idle(tenex, agent.id).ok())
+                .flatten();
+            let now = Seen {
+                title: agent.title.clone(),
+                state: state(agent),
+                waiting,
+            };
+            for message in changes(previous.as_ref(), &now, tenex, agent) {
+                println!("{message}");
+            }
+            current.insert(agent.id, now);
+        }
+        for gone in seen.values() {
+            println!("{} was removed.", gone.title);
+        }
+        seen = current;
+    }
+}
+
+/// Sentences describing how an agent changed since it was `previous`ly seen.
+fn changes(previous: Option<&Seen>, now: &Seen, tenex: &Tenex, agent: &Agent) -> Vec<String> {
+    let Some(previous) = previous else {
+        return vec![format!("Added {}", describe(tenex, agent))];
+    };
+    let mut messages = Vec::new();
+    if previous.title != now.title {
+        messages.push(format!("{} was renamed to {}.", previous.title, now.title));
+    }
+    if previous.state != now.state {
+        messages.push(format!("{} is now {}.", now.title, now.state));
+    }
+    if now.waiting.is_some() && previous.waiting.is_some() && previous.waiting != now.waiting {
+        messages.push(if now.waiting == Some(true) {
+            format!("{} is waiting for input.", now.title)
+        } else {
+            format!("{} is working.", now.title)
+        });
+    }
+    messages
+}
@@ -0,0 +1,103 @@
+//! Size-capped log files for `tenex.log` and the mux daemon's log.
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::app::LogRotation;
+
+/// A log file that moves to `<path>.1` once it reaches its size cap, shifting older logs to
+/// `<path>.2` and so on.
+#[derive(Debug)]
+pub(super) struct RotatingLog {
+    path: PathBuf,
+    rotation: LogRotation,
+    file: Option<File>,
+    written: u64,
+}
+
+impl RotatingLog {
+    /// Start a new log at `path`, rotating the previous session's log out of the way.
+    pub(super) fn start(path: &Path, rotation: LogRotation) -> Self {
+        rotate(path, rotation.keep);
+        Self {
+            path: path.to_path_buf(),
+            rotation,
+            file: create(path),
+            written: 0,
+        }
+    }
+}
+
+impl Write for RotatingLog {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = u64::try_from(buf.len()).unwrap_or(u64::MAX);
+        let full = self
+            .rotation
+            .max_bytes
+            .is_some_and(|max| self.written > 0 && self.written.saturating_add(len) > max);
+        if full {
+            self.file = None;
+            rotate(&self.path, self.rotation.keep);
+            self.file = create(&self.path);
+            self.written = 0;
+        }
+
+        // Logging is best-effort: a log that cannot be written must not fail the caller.
+        let Some(file) = self.file.as_mut() else {
+            return Ok(buf.len());
+        };
+        let written = file.write(buf)?;
+        self.written = self
+            .written
+            .saturating_add(u64::try_from(written).unwrap_or(u64::MAX));
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.as_mut().map_or(Ok(()), Write::flush)
+    }
+}
+
+fn create(path: &Path) -> Option<File> {
+    match File::create(path) {
+        Ok(file) => Some(file),
+        Err(err) => {
+            eprintln!(
+                "Warning: Failed to create log file {}: {err}",
+                path.display()
+            );
+            None
+        }
+    }
+}
+
+/// `<path>.<index>`.
+fn numbered(path: &Path, index: u32) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(format!(".{index}"));
+    PathBuf::from(name)
+}
+
+/// Move `path` to `<path>.1`, shifting older logs up and removing those past `keep`. With
+/// `keep` at `0`, `path` is removed. Empty and missing logs are left alone.
+fn rotate(path: &Path, keep: u32) {
+    if fs::metadata(path).map_or(true, |metadata| metadata.len() == 0) {
+        return;
+    }
+
+    // Logs past `keep`, including those left by a larger retention earlier.
+    let mut stale = keep.max(1);
+    while fs::remove_file(numbered(path, stale)).is_ok() {
+        stale = stale.saturating_add(1);
+    }
+    if keep == 0 {
+        let _ = fs::remove_file(path);
+        return;
+    }
+
+    for index in (1..keep).rev() {
+        let _ = fs::rename(numbered(path, index), numbered(path, index + 1));
+    }
+    let _ = fs::rename(path, numbered(path, 1));
+}
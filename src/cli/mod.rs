@@ -6,26 +6,46 @@
 use crate::App;
 use crate::AppMode;
 use crate::agent::Storage;
-use crate::app::{MuxdVersionMismatchInfo, Settings};
+use crate::app::{MuxdVersionMismatchInfo, Settings, SidebarItem};
 use crate::config::Config;
+use crate::error::TenexError;
 use crate::mux::SessionManager;
 use crate::state::{ChangelogMode, ConfirmAction, ConfirmingMode, UpdatePromptMode};
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use clap::{CommandFactory, Parser, Subcommand};
 use semver::Version;
 
+mod apply;
+mod list;
+mod log_file;
+mod replay;
+mod run;
+
+pub use apply::{ApplyOptions, cmd_apply};
+pub use list::{ListOptions, cmd_list};
+pub use replay::cmd_replay;
+pub use run::{RunOptions, cmd_run, parse_duration};
+
 /// Terminal multiplexer for AI coding agents
-#[derive(Debug, Clone, Copy, Parser)]
+#[derive(Debug, Clone, Parser)]
 #[command(name = "tenex")]
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
     /// Optional CLI subcommand selected by the user.
     #[command(subcommand)]
     pub command: Option<Commands>,
+
+    /// Record the session's input to FILE for `tenex replay`
+    #[arg(long, value_name = "FILE")]
+    pub record: Option<std::path::PathBuf>,
+
+    /// Use the settings profile NAME instead of the saved one
+    #[arg(long, value_name = "NAME", global = true)]
+    pub profile: Option<String>,
 }
 
 /// Top-level subcommands accepted by the `tenex` binary.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Subcommand)]
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
 pub enum Commands {
     /// Kill all agents and clear state
     Reset {
@@ -33,6 +53,81 @@ pub enum Commands {
         #[arg(long)]
         force: bool,
     },
+    /// Start Tenex with a new agent for a GitHub issue
+    Spawn {
+        /// Issue number; the agent's branch and prompt come from the issue
+        #[arg(long)]
+        issue: u64,
+    },
+    /// Permanently delete trashed worktrees and branches of killed agents
+    PurgeTrash {
+        /// Purge everything, not just entries past the retention period
+        #[arg(long)]
+        all: bool,
+    },
+    /// Serve a JSON-RPC API on stdin/stdout for editor extensions
+    Rpc,
+    /// Plan, implement, and synthesize a task without the TUI, exiting non-zero on failure
+    Run {
+        /// Task for the agents
+        #[arg(long)]
+        task: String,
+        /// Title of the root agent (default: the task's first line)
+        #[arg(long)]
+        title: Option<String>,
+        /// Child agents that implement the task
+        #[arg(long, default_value_t = 3)]
+        children: usize,
+        /// Have the root agent synthesize the children's work at the end
+        #[arg(long)]
+        synthesize: bool,
+        /// Fail when the run takes longer than this (for example 90m or 2h)
+        #[arg(long, value_parser = parse_duration)]
+        timeout: Option<std::time::Duration>,
+        /// How long an agent's output must stay unchanged to count as finished
+        #[arg(long, value_parser = parse_duration, default_value = "60s")]
+        idle: std::time::Duration,
+        /// Print progress as JSON lines
+        #[arg(long)]
+        json: bool,
+    },
+    /// Create the agents a swarm file (YAML, TOML, or JSON) describes
+    Apply {
+        /// Swarm file to apply
+        file: std::path::PathBuf,
+        /// Fail when the swarm takes longer than this (for example 90m or 2h)
+        #[arg(long, value_parser = parse_duration)]
+        timeout: Option<std::time::Duration>,
+        /// How long an agent's output must stay unchanged to count as finished
+        #[arg(long, value_parser = parse_duration, default_value = "60s")]
+        idle: std::time::Duration,
+        /// Print progress as JSON lines
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print one plain-text line per agent, for screen readers and scripts
+    List {
+        /// Keep running and announce each change to an agent
+        #[arg(long)]
+        follow: bool,
+        /// How long a running agent's output must stay unchanged to count as waiting for input
+        #[arg(long, value_parser = parse_duration, default_value = "10s")]
+        idle: std::time::Duration,
+    },
+    /// Play a recording made with --record back without a terminal, in a scratch instance
+    Replay {
+        /// Recording to play back
+        file: std::path::PathBuf,
+        /// Run in the current instance; set by the scratch process (internal)
+        #[arg(long, hide = true)]
+        scratch: bool,
+    },
+    /// Summarize what Tenex recorded locally
+    Report {
+        /// Show usage metrics (recorded while the `metrics` setting is on)
+        #[arg(long)]
+        metrics: bool,
+    },
     /// Run the mux daemon (internal).
     #[command(hide = true)]
     Muxd,
@@ -54,20 +149,89 @@ pub enum ResetScope {
 /// Returns any error raised while initializing state, executing a command, or
 /// running the interactive TUI.
 pub fn run() -> Result<()> {
-    init_logging();
-
+    // Git and ssh start this binary to ask for credentials during a push.
+    if let Some(code) = crate::git::run_askpass_helper() {
+        std::process::exit(code);
+    }
     let cli = parse_cli();
+    if let Some(name) = &cli.profile {
+        if !Settings::load().profiles.contains_key(name) {
+            anyhow::bail!("No profile named {name} in {}", Settings::path().display());
+        }
+        Settings::use_profile_for_process(name);
+    }
+    init_logging(matches!(cli.command, Some(Commands::Muxd)));
+    if let Some(path) = &cli.record {
+        crate::tui::recording::record_to(path)?;
+    }
     match &cli.command {
         Some(Commands::Reset { force }) => {
             crate::migration::migrate_default_state_dir()
                 .unwrap_or_else(|err| warn_migration_failure(&err));
             cmd_reset(*force)
         }
+        Some(Commands::Spawn { issue }) => {
+            crate::migration::migrate_default_state_dir()
+                .unwrap_or_else(|err| warn_migration_failure(&err));
+            cmd_default(Some(*issue))
+        }
+        Some(Commands::PurgeTrash { all }) => cmd_purge_trash(*all),
+        Some(Commands::Rpc) => {
+            crate::migration::migrate_default_state_dir()
+                .unwrap_or_else(|err| warn_migration_failure(&err));
+            crate::rpc::serve()
+        }
+        Some(Commands::Run {
+            task,
+            title,
+            children,
+            synthesize,
+            timeout,
+            idle,
+            json,
+        }) => {
+            crate::migration::migrate_default_state_dir()
+                .unwrap_or_else(|err| warn_migration_failure(&err));
+            cmd_run(&RunOptions {
+                task: task.clone(),
+                title: title.clone(),
+                children: *children,
+                synthesize: *synthesize,
+                timeout: *timeout,
+                idle: *idle,
+                json: *json,
+            })
+        }
+        Some(Commands::Apply {
+            file,
+            timeout,
+            idle,
+            json,
+        }) => {
+            crate::migration::migrate_default_state_dir()
+                .unwrap_or_else(|err| warn_migration_failure(&err));
+            cmd_apply(&ApplyOptions {
+                path: file.clone(),
+                timeout: *timeout,
+                idle: *idle,
+                json: *json,
+            })
+        }
+        Some(Commands::List { follow, idle }) => {
+            crate::migration::migrate_default_state_dir()
+                .unwrap_or_else(|err| warn_migration_failure(&err));
+            cmd_list(ListOptions {
+                follow: *follow,
+                idle: *idle,
+            })
+        }
+        Some(Commands::Replay { file, scratch }) => cmd_replay(file, *scratch),
+        Some(Commands::Report { metrics }) => cmd_report(*metrics),
         Some(Commands::Muxd) => crate::mux::run_mux_daemon(),
         None => {
             crate::migration::migrate_default_state_dir()
                 .unwrap_or_else(|err| warn_migration_failure(&err));
-            cmd_default()
+            cmd_default(None)
         }
     }
 }
@@ -76,17 +240,32 @@ fn warn_migration_failure(err: &anyhow::Error) {
     eprintln!("Warning: Failed to migrate Tenex state directory: {err}");
 }
 
-/// Runs the default interactive CLI path.
+/// Runs the default interactive CLI path, first creating an agent for `issue` if given.
 ///
 /// # Errors
 ///
 /// Returns an error if state initialization, state persistence, update
 /// installation, process restart, or the TUI runner fails.
-fn cmd_default() -> Result<()> {
-    let config = Config::default();
+fn cmd_default(issue: Option<u64>) -> Result<()> {
+    let (config, storage, settings, storage_load_error) = load_instance()?;
+    run_interactive(config, storage, settings, storage_load_error, issue)
+}
+
+/// Loads the config, settings, and agent state of the current instance, initializing the
+/// instance and backfilling older state as needed.
+///
+/// The returned message describes a state file that could not be read (the storage is then
+/// empty).
+///
+/// # Errors
+///
+/// Returns an error if the instance cannot be initialized or backfilled state cannot be saved.
+pub(crate) fn load_instance() -> Result<(Config, Storage, Settings, Option<String>)> {
+    let mut config = Config::load();
     let state_path = Config::state_path();
     let settings = Settings::load();
-    let (mut storage, storage_load_error) = load_storage(&state_path);
+    config.apply_settings(&settings);
+    let (mut storage, storage_load_error) = load_storage(&state_path)?;
     let env_mux_socket = env_mux_socket();
     ensure_instance_initialized(
         &config,
@@ -103,11 +282,16 @@ fn cmd_default() -> Result<()> {
         storage.save_to(&state_path)?;
     }
 
-    run_interactive(config, storage, settings, storage_load_error)
+    Ok((config, storage, settings, storage_load_error))
 }
 
-fn init_logging() {
-    let log_path = crate::paths::log_path();
+/// Start `tenex.log`, or the mux daemon's log for `daemon`, rotating the previous one.
+fn init_logging(daemon: bool) {
+    let log_path = if daemon {
+        crate::paths::daemon_log_path()
+    } else {
+        crate::paths::log_path()
+    };
     let debug = std::env::var("DEBUG").ok();
     if let Some(parent) = log_path.parent()
         && let Err(e) = std::fs::create_dir_all(parent)
@@ -118,15 +302,10 @@ fn init_logging() {
         );
     }
 
-    // Clear the log file on startup
-    if let Err(e) = std::fs::write(&log_path, "") {
-        eprintln!(
-            "Warning: Failed to clear log file {}: {e}",
-            log_path.display()
-        );
-    }
+    // Start an empty log even with logging off, so error modals never point into an old one
+    let log = log_file::RotatingLog::start(&log_path, Settings::load().log_rotation());
 
-    // Log to tenex.log in the OS temp directory
+    // Log to the OS temp directory
     // Set DEBUG=0-3 to control verbosity (0=off, 1=warn, 2=info, 3=debug)
     let debug_level = debug.and_then(|v| v.parse::<u8>().ok()).unwrap_or(0);
 
@@ -140,13 +319,8 @@ fn init_logging() {
         _ => tracing::Level::DEBUG,
     };
 
-    let log_dir = log_path
-        .parent()
-        .unwrap_or_else(|| std::path::Path::new("."));
-
-    let file_appender = tracing_appender::rolling::never(log_dir, "tenex.log");
     let _ = tracing_subscriber::fmt()
-        .with_writer(file_appender)
+        .with_writer(std::sync::Mutex::new(log))
         .with_max_level(level)
         .with_ansi(false)
         .try_init();
@@ -172,10 +346,15 @@ fn parse_cli() -> Cli {
 }
 
 /// Converts a storage load result into usable storage plus an optional error.
-#[must_use]
-fn load_storage(state_path: &std::path::Path) -> (Storage, Option<String>) {
+///
+/// # Errors
+///
+/// Returns an error, leaving the state file as it is, if it is encrypted and
+/// `TENEX_PASSPHRASE` does not open it.
+fn load_storage(state_path: &std::path::Path) -> Result<(Storage, Option<String>)> {
     match Storage::load_from(state_path) {
-        Ok(storage) => (storage, None),
+        Ok(storage) => Ok((storage, None)),
+        Err(err) if err.downcast_ref::<crate::agent::Locked>().is_some() => Err(err),
         Err(err) => {
             let mut message = format!("Failed to load state file {}: {err}", state_path.display());
 
@@ -200,7 +379,7 @@ fn load_storage(state_path: &std::path::Path) -> (Storage, Option<String>) {
                 let _ = preserve_corrupt_state_file(&backup_path);
             }
 
-            (Storage::new(), Some(message))
+            Ok((Storage::new(), Some(message)))
         }
     }
 }
@@ -292,6 +471,7 @@ fn run_interactive(
     storage: Storage,
     settings: Settings,
     storage_load_error: Option<String>,
+    issue: Option<u64>,
 ) -> Result<()> {
     let cwd = std::env::current_dir().ok();
 
@@ -313,6 +493,7 @@ fn run_interactive(
         app.set_error(message);
     }
     app.set_cwd_project_root(cwd_project_root);
+    app.data.plugins = crate::plugin::load_plugins(&Config::plugin_dir());
 
     maybe_queue_whats_new(&mut app);
 
@@ -343,7 +524,16 @@ fn run_interactive(
         eprintln!("Warning: Failed to respawn agents: {e}");
     }
 
-    if let Some(info) = crate::tui::run(app)? {
+    if let Some(number) = issue {
+        spawn_issue_agent(&mut app, number);
+    }
+    queue_startup_report(&mut app);
+
+    let state_path = app.data.storage.resolved_state_path();
+    let update = crate::tui::run(app)?;
+    print_session_summary(&state_path);
+
+    if let Some(info) = update {
         println!(
             "Updating Tenex from {} to {}...",
             info.current_version, info.latest_version
@@ -355,6 +545,67 @@ fn run_interactive(
     Ok(())
 }
 
+/// Shows what startup reconciliation changed, once, before the agent list is used.
+///
+/// The report opens right away when no other modal is up. Otherwise it waits for normal mode,
+/// unless a "What's New" modal is already waiting.
+fn queue_startup_report(app: &mut App) {
+    let report = &app.data.startup_report;
+    if !report.is_noteworthy() {
+        return;
+    }
+    let modal = ChangelogMode {
+        title: "Startup Report".to_string(),
+        lines: report.lines(),
+        mark_seen_version: None,
+        agent_id: None,
+    };
+    if matches!(&app.mode, AppMode::Normal(_)) {
+        app.apply_mode(modal.into());
+    } else if app.data.pending_changelog.is_none() {
+        app.data.pending_changelog = Some(modal);
+    }
+}
+
+/// Creates the agent for `tenex spawn --issue`, reporting failures in the TUI.
+fn spawn_issue_agent(app: &mut App, number: u64) {
+    // Create the agent in the repository Tenex was started in, not the first stored project.
+    if let Some(root) = app.data.cwd_project_root.clone()
+        && let Some(index) =
+            app.data.sidebar_items().iter().position(
+                |item| matches!(item, SidebarItem::Project(project) if project.root == root),
+            )
+    {
+        app.data.selected = index;
+    }
+    match crate::app::Actions::new().spawn_issue_agent(&mut app.data, number) {
+        Ok(mode) => app.apply_mode(mode),
+        Err(err) => app.set_error(TenexError::wrap(
+            format!("Failed to create agent for issue #{number}"),
+            &err,
+        )),
+    }
+}
+
+/// Prints elapsed, active, and idle time for each agent still tracked after the TUI exits.
+fn print_session_summary(state_path: &std::path::Path) {
+    let Ok(storage) = Storage::load_from(state_path) else {
+        return;
+    };
+    let agents: Vec<_> = storage
+        .iter()
+        .filter(|agent| !agent.is_terminal_agent())
+        .collect();
+    if agents.is_empty() {
+        return;
+    }
+
+    println!("Tenex session summary:");
+    for agent in agents {
+        println!("  {}", agent.summary());
+    }
+}
+
 /// Queues "What's New" release notes when settings show they have not been seen.
 pub fn maybe_queue_whats_new(app: &mut App) {
     let Ok(current_version) = crate::release_notes::current_version() else {
@@ -384,6 +635,7 @@ pub fn maybe_queue_whats_new(app: &mut App) {
                         title: "What's New".to_string(),
                         lines,
                         mark_seen_version: Some(current_version),
+                        agent_id: None,
                     });
                 }
                 Err(e) => {
@@ -411,6 +663,7 @@ pub fn maybe_queue_whats_new(app: &mut App) {
                         title: "What's New".to_string(),
                         lines,
                         mark_seen_version: Some(current_version),
+                        agent_id: None,
                     });
                 }
                 Err(e) => {
@@ -532,6 +785,39 @@ pub fn restart_current_process() -> Result<()> {
     std::process::exit(0);
 }
 
+fn cmd_purge_trash(all: bool) -> Result<()> {
+    let trash = crate::agent::trash_dir(&Storage::new().resolved_state_path());
+    let retention = if all {
+        None
+    } else {
+        Settings::load().trash_retention()
+    };
+    let purged = crate::agent::purge_trash(&trash, retention)?;
+    let remaining = crate::agent::load_trash(&trash).len();
+    println!(
+        "Purged {purged} trash entries; {remaining} remain in {}.",
+        trash.display()
+    );
+    Ok(())
+}
+
+fn cmd_report(metrics: bool) -> Result<()> {
+    if !metrics {
+        bail!("Nothing to report; pass --metrics for usage metrics");
+    }
+    let path = crate::metrics::metrics_path();
+    let recorded = crate::metrics::load(&path)?;
+    if recorded == crate::metrics::Metrics::default() {
+        println!(
+            "No metrics recorded in {}. Run /metrics in Tenex to start recording.",
+            path.display()
+        );
+        return Ok(());
+    }
+    print!("{}", recorded.report());
+    Ok(())
+}
+
 fn cmd_reset(force: bool) -> Result<()> {
     use crate::git::WorktreeManager;
     use std::collections::HashSet;
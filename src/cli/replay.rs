@@ -0,0 +1,78 @@
+//! `tenex replay`: playing a recording back without a terminal.
+
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+
+use crate::tui::recording::{self, RecordedEvent};
+use crate::tui::replay;
+
+/// Play the recording at `path` back and print the last frame.
+///
+/// The replay saves state, settings, and worktrees like the TUI would, so unless `scratch` is
+/// set it runs in a new process with its own temporary instance and mux daemon, which are
+/// removed afterwards.
+///
+/// # Errors
+///
+/// Returns an error if the recording cannot be read or played back, or if the replay's modes
+/// diverge from the recorded ones.
+pub fn cmd_replay(path: &Path, scratch: bool) -> Result<()> {
+    if scratch {
+        return replay_here(path);
+    }
+
+    let dir = std::env::temp_dir().join(format!("tenex-replay-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let socket = dir.join("mux.sock");
+    let status = std::env::current_exe()
+        .context("Failed to resolve current executable")
+        .and_then(|exe| {
+            Command::new(exe)
+                .arg("replay")
+                .arg("--scratch")
+                .arg(path)
+                .env("TENEX_STATE_PATH", dir.join("state.json"))
+                .env("TENEX_MUX_SOCKET", &socket)
+                .status()
+                .context("Failed to start the replay")
+        });
+    let _ = crate::mux::terminate_mux_daemon_for_socket(&socket.display().to_string());
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let status = status?;
+    if !status.success() {
+        std::process::exit(status.code().unwrap_or(1));
+    }
+    Ok(())
+}
+
+fn replay_here(path: &Path) -> Result<()> {
+    let events = recording::load(path)?;
+    let Some(RecordedEvent::Start(start)) = events.first() else {
+        bail!("{} is not a Tenex recording", path.display());
+    };
+    if start.version != env!("CARGO_PKG_VERSION") {
+        eprintln!(
+            "Warning: recorded with Tenex {}, replaying with {}",
+            start.version,
+            env!("CARGO_PKG_VERSION")
+        );
+    }
+
+    let mut app = replay::app(start);
+    let result = replay::replay(&mut app, &events)?;
+    println!("{}", result.frame.text);
+    println!("\nReplayed {} of {} events", result.events, events.len());
+    if result.divergences.is_empty() {
+        return Ok(());
+    }
+    for divergence in &result.divergences {
+        eprintln!("{divergence}");
+    }
+    bail!(
+        "{} recorded modes differ from the replay",
+        result.divergences.len()
+    )
+}
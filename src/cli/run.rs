@@ -0,0 +1,257 @@
+//! Headless plan, implement, and synthesize cycle for `tenex run`.
+
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::time::{Duration, Instant};
+
+use anyhow::{Result, bail};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::api::Tenex;
+
+/// Lines of each agent's output compared between polls.
+const OUTPUT_LINES: usize = 200;
+
+/// How often agents' output is checked while waiting.
+pub(super) const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Longest root agent title derived from the task, in columns.
+const MAX_TITLE_WIDTH: usize = 50;
+
+/// Options for `tenex run`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunOptions {
+    /// Task the agents work on.
+    pub task: String,
+    /// Title of the root agent; derived from the task when unset.
+    pub title: Option<String>,
+    /// Child agents that implement the task.
+    pub children: usize,
+    /// Whether the root agent synthesizes the children's work at the end.
+    pub synthesize: bool,
+    /// Give up when the run takes longer than this.
+    pub timeout: Option<Duration>,
+    /// How long an agent's output must stay unchanged before it counts as finished.
+    pub idle: Duration,
+    /// Print progress as JSON lines instead of text.
+    pub json: bool,
+}
+
+/// Progress output of a run, as text or JSON lines on stdout.
+#[derive(Debug)]
+pub(super) struct Progress {
+    pub(super) json: bool,
+    pub(super) started: Instant,
+}
+
+impl Progress {
+    /// Print one event: `message` as a timestamped line, or `fields` merged into a JSON object.
+    pub(super) fn emit(&self, event: &str, message: &str, fields: serde_json::Value) {
+        let elapsed = self.started.elapsed().as_secs();
+        if self.json {
+            let mut line = json!({ "event": event, "elapsed_secs": elapsed, "message": message });
+            if let (Some(line), serde_json::Value::Object(fields)) = (line.as_object_mut(), fields)
+            {
+                line.extend(fields);
+            }
+            println!("{line}");
+        } else {
+            println!(
+                "[{:02}:{:02}:{:02}] {message}",
+                elapsed / 3600,
+                elapsed / 60 % 60,
+                elapsed % 60
+            );
+        }
+    }
+}
+
+/// Run a full cycle in the current repository: the root agent plans the task, `children`
+/// child agents implement it, and the root synthesizes their work when asked to.
+///
+/// Agents are left running when the run ends, so their work can be checked in the TUI.
+///
+/// # Errors
+///
+/// Returns an error if an agent cannot be created, stops responding, or the run exceeds its
+/// timeout.
+pub fn cmd_run(options: &RunOptions) -> Result<()> {
+    let progress = Progress {
+        json: options.json,
+        started: Instant::now(),
+    };
+    let result = run_cycle(options, &progress);
+    if let Err(err) = &result {
+        progress.emit(
+            "failed",
+            &format!("Run failed: {err:#}"),
+            json!({ "error": format!("{err:#}") }),
+        );
+    }
+    result
+}
+
+fn run_cycle(options: &RunOptions, progress: &Progress) -> Result<()> {
+    if options.task.trim().is_empty() {
+        bail!("The task is empty");
+    }
+    let deadline = options.timeout.map(|timeout| progress.started + timeout);
+    let mut tenex = Tenex::open()?;
+    let repo = std::env::current_dir()?;
+    let title = options
+        .title
+        .clone()
+        .unwrap_or_else(|| title_from_task(&options.task));
+
+    let plan_prompt = crate::prompts::build_plan_prompt(&options.task);
+    let root = tenex.spawn(&repo, &title, Some(&plan_prompt))?;
+    progress.emit(
+        "phase",
+        &format!("Planning: started {title} ({root})"),
+        json!({ "phase": "plan", "agents": [root] }),
+    );
+    wait_until_idle(&tenex, &[root], options.idle, deadline, progress)?;
+
+    let children = tenex.spawn_children(root, options.children, &options.task)?;
+    progress.emit(
+        "phase",
+        &format!("Implementing: started {} child agent(s)", children.len()),
+        json!({ "phase": "implement", "agents": children }),
+    );
+    wait_until_idle(&tenex, &children, options.idle, deadline, progress)?;
+
+    if options.synthesize {
+        tenex.synthesize(root, None)?;
+        progress.emit(
+            "phase",
+            &format!("Synthesizing: handed the children's work to {title}"),
+            json!({ "phase": "synthesize", "agents": [root] }),
+        );
+        wait_until_idle(&tenex, &[root], options.idle, deadline, progress)?;
+    }
+
+    let branch = tenex
+        .agent(root)
+        .map(|agent| agent.branch.clone())
+        .unwrap_or_default();
+    progress.emit(
+        "done",
+        &format!("Done: {title} finished on branch {branch}"),
+        json!({ "root": root, "children": children, "branch": branch }),
+    );
+    Ok(())
+}
+
+/// Tracks when each agent's output last changed, to tell when agents have finished.
+#[derive(Debug)]
+pub(super) struct IdleWatch {
+    idle: Duration,
+    last_change: HashMap<Uuid, (u64, Instant)>,
+}
+
+impl IdleWatch {
+    /// Watch for output that stays unchanged for `idle`.
+    pub(super) fn new(idle: Duration) -> Self {
+        Self {
+            idle,
+            last_change: HashMap::new(),
+        }
+    }
+
+    /// Check agent `id`'s output and report whether it has stayed unchanged for the idle
+    /// period.
+    pub(super) fn is_idle(&mut self, tenex: &Tenex, id: Uuid) -> Result<bool> {
+        let now = Instant::now();
+        let mut hasher = DefaultHasher::new();
+        tenex.output(id, OUTPUT_LINES)?.hash(&mut hasher);
+        let hash = hasher.finish();
+        let entry = self.last_change.entry(id).or_insert((hash, now));
+        if entry.0 != hash {
+            *entry = (hash, now);
+        }
+        Ok(now.duration_since(entry.1) >= self.idle)
+    }
+}
+
+/// Wait until the output of every agent in `ids` has stopped changing for `idle`, reporting
+/// whenever the number of busy agents changes.
+pub(super) fn wait_until_idle(
+    tenex: &Tenex,
+    ids: &[Uuid],
+    idle: Duration,
+    deadline: Option<Instant>,
+    progress: &Progress,
+) -> Result<()> {
+    let mut watch = IdleWatch::new(idle);
+    let mut reported_busy = None;
+    loop {
+        let mut busy = 0;
+        for &id in ids {
+            if !watch.is_idle(tenex, id)? {
+                busy += 1;
+            }
+        }
+        if busy == 0 {
+            return Ok(());
+        }
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            bail!("Timed out with {busy} agent(s) still working");
+        }
+        if reported_busy.replace(busy) != Some(busy) {
+            progress.emit(
+                "waiting",
+                &format!("Waiting for {busy} of {} agent(s)", ids.len()),
+                json!({ "busy": busy, "agents": ids.len() }),
+            );
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// The first line of `task`, shortened to a usable agent title.
+fn title_from_task(task: &str) -> String {
+    let first_line = task.trim().lines().next().unwrap_or_default().trim();
+    crate::text_width::truncate_to_width(first_line, MAX_TITLE_WIDTH)
+        .trim_end()
+        .to_string()
+}
+
+/// Parse a duration such as `90s`, `45m`, `2h`, or `1h30m`. A bare number is seconds.
+///
+/// # Errors
+///
+/// Returns an error naming the expected format when `text` is not a duration.
+pub fn parse_duration(text: &str) -> Result<Duration, String> {
+    let invalid = || format!("invalid duration {text:?}; use a form like 90s, 45m, 2h, or 1h30m");
+    let text = text.trim();
+    if let Ok(secs) = text.parse::<u64>() {
+        return Ok(Duration::from_secs(secs));
+    }
+
+    let mut total: u64 = 0;
+    let mut digits = String::new();
+    for ch in text.chars() {
+        if ch.is_ascii_digit() {
+            digits.push(ch);
+            continue;
+        }
+        let unit = match ch {
+            's' => 1,
+            'm' => 60,
+            'h' => 3600,
+            'd' => 86_400,
+            _ => return Err(invalid()),
+        };
+        let value: u64 = digits.parse().map_err(|_| invalid())?;
+        total = value
+            .checked_mul(unit)
+            .and_then(|secs| total.checked_add(secs))
+            .ok_or_else(invalid)?;
+        digits.clear();
+    }
+    if !digits.is_empty() || total == 0 {
+        return Err(invalid());
+    }
+    Ok(Duration::from_secs(total))
+}
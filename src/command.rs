@@ -1,5 +1,7 @@
 //! Command line parsing helpers.
 
+use std::path::Path;
+
 use anyhow::{Context, Result, bail};
 
 /// Split a command line into an argv vector.
@@ -19,3 +21,58 @@ pub fn parse_command_line(command_line: &str) -> Result<Vec<String>> {
 
     Ok(argv)
 }
+
+/// File name of the executable of `command_line` (`claude` for `/usr/bin/claude --resume`).
+#[must_use]
+pub fn program_name(command_line: &str) -> Option<String> {
+    let exe = parse_command_line(command_line).ok()?.into_iter().next()?;
+    Path::new(&exe)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map(str::to_string)
+}
+
+/// Whether the executable of `command_line` can be run: a path to an executable file, or the
+/// name of one in a `PATH` directory.
+#[must_use]
+pub fn program_exists(command_line: &str) -> bool {
+    let Some(program) = parse_command_line(command_line)
+        .ok()
+        .and_then(|argv| argv.into_iter().next())
+    else {
+        return false;
+    };
+    if program.contains(std::path::is_separator) {
+        return is_executable(Path::new(&program));
+    }
+    std::env::var_os("PATH").is_some_and(|path| {
+        std::env::split_paths(&path).any(|dir| is_executable(&dir.join(&program)))
+    })
+}
+
+fn is_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt as _;
+        path.metadata()
+            .is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+    }
+
+    #[cfg(not(unix))]
+    {
+        // Windows finds `claude` as `claude.exe` or `claude.cmd`, using the extensions in
+        // `PATHEXT`.
+        if path.extension().is_some() && path.is_file() {
+            return true;
+        }
+        let extensions = std::env::var("PATHEXT").unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".into());
+        extensions
+            .split(';')
+            .filter(|extension| !extension.is_empty())
+            .any(|extension| {
+                let mut candidate = path.as_os_str().to_os_string();
+                candidate.push(extension);
+                Path::new(&candidate).is_file()
+            })
+    }
+}
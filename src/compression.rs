@@ -0,0 +1,130 @@
+//! zstd compression of files Tenex keeps after they stop changing.
+//!
+//! Finished input recordings and older synthesis files are compressed in place to
+//! `<name>.zst`. Readers go through [`open`] or [`read_to_string`], which accept either form,
+//! so callers never need to know whether a file was compressed.
+
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read as _};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+/// Extension added to compressed files.
+pub const EXTENSION: &str = "zst";
+
+/// zstd level: fast, and most of the ratio for text.
+const LEVEL: i32 = 3;
+
+/// `path` with `.zst` appended.
+#[must_use]
+pub fn compressed_path(path: &Path) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(".");
+    name.push(EXTENSION);
+    PathBuf::from(name)
+}
+
+/// Whether `path` names a compressed file.
+#[must_use]
+pub fn is_compressed(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == EXTENSION)
+}
+
+/// `path` without its `.zst` extension, or `path` itself if it has none.
+#[must_use]
+pub fn uncompressed_path(path: &Path) -> PathBuf {
+    if is_compressed(path) {
+        path.with_extension("")
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// Compress `path` to `<path>.zst` and remove the original, returning the new path.
+///
+/// The compressed file is written next to the original and renamed into place, so a failure
+/// leaves the original untouched.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, compressed, or replaced.
+pub fn compress_file(path: &Path) -> Result<PathBuf> {
+    let target = compressed_path(path);
+    let partial = compressed_path(&target.with_extension("partial"));
+    let result = (|| {
+        let mut input =
+            File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        let output = File::create(&partial)
+            .with_context(|| format!("Failed to create {}", partial.display()))?;
+        let mut encoder = zstd::Encoder::new(output, LEVEL)?;
+        io::copy(&mut input, &mut encoder)
+            .with_context(|| format!("Failed to compress {}", path.display()))?;
+        encoder.finish()?.sync_all()?;
+        fs::rename(&partial, &target)
+            .with_context(|| format!("Failed to write {}", target.display()))
+    })();
+    if let Err(err) = result {
+        let _ = fs::remove_file(&partial);
+        return Err(err);
+    }
+    fs::remove_file(path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    Ok(target)
+}
+
+/// Decompress `path` (a `.zst` file) back to its original name and remove it, returning the
+/// restored path. A path that is not compressed is returned as is.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, decompressed, or written.
+pub fn decompress_file(path: &Path) -> Result<PathBuf> {
+    if !is_compressed(path) {
+        return Ok(path.to_path_buf());
+    }
+    let target = uncompressed_path(path);
+    let mut reader = open(path)?;
+    let mut output =
+        File::create(&target).with_context(|| format!("Failed to create {}", target.display()))?;
+    io::copy(&mut reader, &mut output)
+        .with_context(|| format!("Failed to decompress {}", path.display()))?;
+    fs::remove_file(path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    Ok(target)
+}
+
+/// Open `path` for reading, decompressing it if it is a `.zst` file.
+///
+/// If `path` does not exist but `<path>.zst` does, that is opened instead.
+///
+/// # Errors
+///
+/// Returns an error if neither file can be opened.
+pub fn open(path: &Path) -> Result<Box<dyn BufRead>> {
+    let path = if !path.exists() && compressed_path(path).exists() {
+        compressed_path(path)
+    } else {
+        path.to_path_buf()
+    };
+    let file = File::open(&path).with_context(|| format!("Failed to open {}", path.display()))?;
+    if is_compressed(&path) {
+        let decoder = zstd::Decoder::new(file)
+            .with_context(|| format!("Failed to decompress {}", path.display()))?;
+        Ok(Box::new(BufReader::new(decoder)))
+    } else {
+        Ok(Box::new(BufReader::new(file)))
+    }
+}
+
+/// Read `path` as text, decompressing it if needed (see [`open`]).
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or is not UTF-8.
+pub fn read_to_string(path: &Path) -> Result<String> {
+    let mut text = String::new();
+    open(path)?
+        .read_to_string(&mut text)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(text)
+}
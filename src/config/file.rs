@@ -0,0 +1,123 @@
+//! `config.toml` files: the user's in the config directory and a repository's in `.tenex/`
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use tracing::{debug, warn};
+
+use super::Config;
+use crate::paths;
+
+/// Values a `config.toml` may set; every one is optional.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct ConfigFile {
+    default_program: Option<String>,
+    branch_prefix: Option<String>,
+    swarm_branch_prefix: Option<String>,
+    poll_interval_ms: Option<u64>,
+    worktree_dir: Option<PathBuf>,
+    clone_dir: Option<PathBuf>,
+    trust_repo_default_program: Option<bool>,
+}
+
+impl ConfigFile {
+    /// Read `path`; a missing file sets nothing.
+    fn read(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Read `path`, logging why it was skipped if it can't be used.
+    pub(super) fn load(path: &Path) -> Self {
+        match Self::read(path) {
+            Ok(file) => {
+                debug!(path = %path.display(), "Loaded config file");
+                file
+            }
+            Err(err) => {
+                warn!("{err:#}");
+                Self::default()
+            }
+        }
+    }
+
+    /// Override `config` with the values this file sets.
+    ///
+    /// A leading `~` in a directory expands to the home directory, and other relative
+    /// directories start from `base`.
+    pub(super) fn apply_to(self, config: &mut Config, base: &Path) {
+        let strings = [
+            (&mut config.default_program, self.default_program),
+            (&mut config.branch_prefix, self.branch_prefix),
+            (&mut config.swarm_branch_prefix, self.swarm_branch_prefix),
+        ];
+        for (target, value) in strings {
+            if let Some(value) = value {
+                *target = value;
+            }
+        }
+        if let Some(poll_interval_ms) = self.poll_interval_ms {
+            config.poll_interval_ms = poll_interval_ms;
+        }
+        if let Some(dir) = self.worktree_dir {
+            config.worktree_dir = resolve_dir(&dir, base);
+            config
+                .configured_worktree_dir
+                .clone_from(&config.worktree_dir);
+        }
+        if let Some(dir) = self.clone_dir {
+            config.clone_dir = resolve_dir(&dir, base);
+        }
+        if let Some(trust) = self.trust_repo_default_program {
+            config.trust_repo_default_program = trust;
+        }
+    }
+
+    /// Override `config` with the values a repository's file may set: the branch prefixes,
+    /// the worktree directory, and `default_program` if `config` trusts repositories with it.
+    ///
+    /// Returns whether the file set a `default_program` that was ignored.
+    pub(super) fn apply_repo_to(mut self, config: &mut Config, repo_root: &Path) -> bool {
+        let ignored_program =
+            !config.trust_repo_default_program && self.default_program.take().is_some();
+        let instance_wide = self.poll_interval_ms.take().is_some()
+            | self.clone_dir.take().is_some()
+            | self.trust_repo_default_program.take().is_some();
+        if instance_wide {
+            debug!(
+                repo = %repo_root.display(),
+                "Ignoring settings only the user's config.toml may set"
+            );
+        }
+        self.apply_to(config, repo_root);
+        ignored_program
+    }
+}
+
+fn resolve_dir(dir: &Path, base: &Path) -> PathBuf {
+    let dir = Config::expand_home(dir);
+    if dir.is_absolute() {
+        dir
+    } else {
+        Config::normalize_lexically(&base.join(dir))
+    }
+}
+
+/// The user's `config.toml`: `$XDG_CONFIG_HOME/tenex/config.toml`, or
+/// `~/.config/tenex/config.toml`.
+#[must_use]
+pub fn user_config_path() -> Option<PathBuf> {
+    paths::config_dir().map(|dir| dir.join("tenex").join("config.toml"))
+}
+
+/// The `config.toml` of the repository at `repo_root`.
+#[must_use]
+pub fn repo_config_path(repo_root: &Path) -> PathBuf {
+    repo_root.join(".tenex").join("config.toml")
+}
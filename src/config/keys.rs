@@ -9,12 +9,34 @@ pub enum Action {
     NewAgent,
     /// Create a new agent with a prompt
     NewAgentWithPrompt,
+    /// Create a new agent for a GitHub issue
+    NewAgentFromIssue,
     /// Focus the active detail pane (Preview attaches terminal, Diff enters diff focus)
     FocusPreview,
     /// Detach from the agent terminal (return to Tenex controls)
     UnfocusPreview,
     /// Kill selected agent
     Kill,
+    /// Recreate a recently killed agent's deleted branch
+    RecoverBranch,
+    /// Show the selected agent's details
+    AgentInfo,
+    /// Show the prompt the selected agent was started with
+    ShowPrompt,
+    /// Edit the selected agent's task and send the revision
+    EditTask,
+    /// Send the selected agent's output to another agent
+    SendTo,
+    /// Show the questions agents are waiting to have answered
+    Inbox,
+    /// Type a short reply and send it to the selected agent
+    Reply,
+    /// Pick a canned response and send it to the selected agent or its leaf agents
+    CannedResponses,
+    /// Restart the selected agent from a summary of its conversation
+    Compact,
+    /// Relaunch the selected agent with a different model or program
+    SwitchModel,
     /// Push branch to remote
     Push,
     /// Rename branch (local; pushes new remote branch, keeps old remote branch)
@@ -85,6 +107,8 @@ pub enum Action {
     Merge,
     /// Switch the current agent's branch
     SwitchBranch,
+    /// Rebase onto (or merge in) the agent's base branch
+    UpdateFromBase,
     /// Open slash command palette
     CommandPalette,
 }
@@ -147,6 +171,16 @@ const BINDINGS: &[Binding] = &[
         modifiers: KeyModifiers::SHIFT,
         action: Action::NewAgentWithPrompt,
     },
+    Binding {
+        code: KeyCode::Char('#'),
+        modifiers: KeyModifiers::NONE,
+        action: Action::NewAgentFromIssue,
+    },
+    Binding {
+        code: KeyCode::Char('#'),
+        modifiers: KeyModifiers::SHIFT,
+        action: Action::NewAgentFromIssue,
+    },
     Binding {
         code: KeyCode::Enter,
         modifiers: KeyModifiers::NONE,
@@ -162,6 +196,86 @@ const BINDINGS: &[Binding] = &[
         modifiers: KeyModifiers::NONE,
         action: Action::Kill,
     },
+    Binding {
+        code: KeyCode::Char('D'),
+        modifiers: KeyModifiers::NONE,
+        action: Action::RecoverBranch,
+    },
+    Binding {
+        code: KeyCode::Char('D'),
+        modifiers: KeyModifiers::SHIFT,
+        action: Action::RecoverBranch,
+    },
+    Binding {
+        code: KeyCode::Char('i'),
+        modifiers: KeyModifiers::NONE,
+        action: Action::AgentInfo,
+    },
+    Binding {
+        code: KeyCode::Char('I'),
+        modifiers: KeyModifiers::NONE,
+        action: Action::ShowPrompt,
+    },
+    Binding {
+        code: KeyCode::Char('I'),
+        modifiers: KeyModifiers::SHIFT,
+        action: Action::ShowPrompt,
+    },
+    Binding {
+        code: KeyCode::Char('e'),
+        modifiers: KeyModifiers::NONE,
+        action: Action::EditTask,
+    },
+    Binding {
+        code: KeyCode::Char('>'),
+        modifiers: KeyModifiers::NONE,
+        action: Action::SendTo,
+    },
+    Binding {
+        code: KeyCode::Char('>'),
+        modifiers: KeyModifiers::SHIFT,
+        action: Action::SendTo,
+    },
+    Binding {
+        code: KeyCode::Char('!'),
+        modifiers: KeyModifiers::NONE,
+        action: Action::Inbox,
+    },
+    Binding {
+        code: KeyCode::Char('w'),
+        modifiers: KeyModifiers::NONE,
+        action: Action::Reply,
+    },
+    Binding {
+        code: KeyCode::Char('c'),
+        modifiers: KeyModifiers::NONE,
+        action: Action::CannedResponses,
+    },
+    Binding {
+        code: KeyCode::Char('C'),
+        modifiers: KeyModifiers::NONE,
+        action: Action::Compact,
+    },
+    Binding {
+        code: KeyCode::Char('C'),
+        modifiers: KeyModifiers::SHIFT,
+        action: Action::Compact,
+    },
+    Binding {
+        code: KeyCode::Char('M'),
+        modifiers: KeyModifiers::NONE,
+        action: Action::SwitchModel,
+    },
+    Binding {
+        code: KeyCode::Char('M'),
+        modifiers: KeyModifiers::SHIFT,
+        action: Action::SwitchModel,
+    },
+    Binding {
+        code: KeyCode::Char('!'),
+        modifiers: KeyModifiers::SHIFT,
+        action: Action::Inbox,
+    },
     // Hierarchy
     Binding {
         code: KeyCode::Char('S'),
@@ -374,6 +488,16 @@ const BINDINGS: &[Binding] = &[
         modifiers: KeyModifiers::CONTROL,
         action: Action::SwitchBranch,
     },
+    Binding {
+        code: KeyCode::Char('U'),
+        modifiers: KeyModifiers::NONE,
+        action: Action::UpdateFromBase,
+    },
+    Binding {
+        code: KeyCode::Char('U'),
+        modifiers: KeyModifiers::SHIFT,
+        action: Action::UpdateFromBase,
+    },
     // Hidden (not shown in help but still functional)
     Binding {
         code: KeyCode::Esc,
@@ -394,9 +518,22 @@ impl Action {
         match self {
             Self::NewAgent => "[a]dd agent",
             Self::NewAgentWithPrompt => "[A]dd agent with prompt",
-            Self::FocusPreview => "[Enter] focus preview (Preview tab) / diff (Diff tab)",
+            Self::NewAgentFromIssue => "[#] add agent for a GitHub issue",
+            Self::FocusPreview => {
+                "[Enter] focus preview (Preview tab) / diff (Diff tab) / edit scratchpad"
+            }
             Self::UnfocusPreview => "[Ctrl+q] detach terminal / quit app",
-            Self::Kill => "[d]elete agent and sub-agents",
+            Self::Kill => "[d]elete or stop agent and sub-agents",
+            Self::RecoverBranch => "[D] recover a killed agent's branch",
+            Self::AgentInfo => "[i]nfo for selected agent",
+            Self::ShowPrompt => "[I]nitial prompt of selected agent",
+            Self::EditTask => "[e]dit and re-send task",
+            Self::SendTo => "[>] send output to another agent",
+            Self::Inbox => "[!] questions agents are waiting on",
+            Self::Reply => "[w]rite a quick reply to selected agent",
+            Self::CannedResponses => "[c]anned responses for selected agent",
+            Self::Compact => "[C]ompact: restart agent from a summary of its work",
+            Self::SwitchModel => "[M]odel: relaunch agent with another model or program",
             Self::Push => "[Ctrl+p]ush branch to remote",
             Self::RenameBranch => "[r]ename branch",
             Self::OpenPR => "[Ctrl+o]pen pull request",
@@ -432,6 +569,7 @@ impl Action {
             Self::Rebase => "[Ctrl+r]ebase onto branch",
             Self::Merge => "[Ctrl+m]erge branch",
             Self::SwitchBranch => "[Ctrl+s]witch branch",
+            Self::UpdateFromBase => "[U]pdate from base branch",
             Self::CommandPalette => "[/] commands",
         }
     }
@@ -442,8 +580,19 @@ impl Action {
         match self {
             Self::NewAgent => "a",
             Self::NewAgentWithPrompt => "A",
+            Self::NewAgentFromIssue => "#",
             Self::FocusPreview => "Enter",
             Self::Kill => "d",
+            Self::RecoverBranch => "D",
+            Self::AgentInfo => "i",
+            Self::ShowPrompt => "I",
+            Self::EditTask => "e",
+            Self::SendTo => ">",
+            Self::Inbox => "!",
+            Self::Reply => "w",
+            Self::CannedResponses => "c",
+            Self::Compact => "C",
+            Self::SwitchModel => "M",
             Self::SwitchTab => "Tab",
             Self::DiffCursorUp | Self::PrevAgent => "↑",
             Self::DiffCursorDown | Self::NextAgent => "↓",
@@ -478,6 +627,7 @@ impl Action {
             Self::Rebase => "Ctrl+r",
             Self::Merge => "Ctrl+m",
             Self::SwitchBranch => "Ctrl+s",
+            Self::UpdateFromBase => "U",
             Self::CommandPalette => "/",
         }
     }
@@ -488,7 +638,18 @@ impl Action {
         match self {
             Self::NewAgent
             | Self::NewAgentWithPrompt
+            | Self::NewAgentFromIssue
             | Self::Kill
+            | Self::RecoverBranch
+            | Self::AgentInfo
+            | Self::ShowPrompt
+            | Self::EditTask
+            | Self::SendTo
+            | Self::Inbox
+            | Self::Reply
+            | Self::CannedResponses
+            | Self::Compact
+            | Self::SwitchModel
             | Self::SpawnChildren
             | Self::PlanSwarm
             | Self::AddChildren
@@ -502,7 +663,8 @@ impl Action {
             | Self::OpenPR
             | Self::Rebase
             | Self::Merge
-            | Self::SwitchBranch => ActionGroup::GitOps,
+            | Self::SwitchBranch
+            | Self::UpdateFromBase => ActionGroup::GitOps,
             Self::FocusPreview
             | Self::UnfocusPreview
             | Self::ToggleCollapse
@@ -539,7 +701,18 @@ impl Action {
         // Agents
         Self::NewAgent,
         Self::NewAgentWithPrompt,
+        Self::NewAgentFromIssue,
         Self::Kill,
+        Self::RecoverBranch,
+        Self::AgentInfo,
+        Self::ShowPrompt,
+        Self::EditTask,
+        Self::SendTo,
+        Self::Inbox,
+        Self::Reply,
+        Self::CannedResponses,
+        Self::Compact,
+        Self::SwitchModel,
         Self::SpawnChildren,
         Self::PlanSwarm,
         Self::ReviewSwarm,
@@ -557,6 +730,7 @@ impl Action {
         Self::Rebase,
         Self::Merge,
         Self::SwitchBranch,
+        Self::UpdateFromBase,
         // Other
         Self::Help,
         Self::CommandPalette,
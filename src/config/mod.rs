@@ -1,24 +1,49 @@
 //! Configuration management for Tenex
 
+mod file;
 mod keys;
 
+pub use file::{repo_config_path, user_config_path};
 pub use keys::{
     Action, ActionGroup, get_action, get_display_description, get_display_keys, status_hints,
 };
 
+use crate::app::Settings;
 use crate::paths;
 use std::path::Path;
 use std::path::PathBuf;
+use tracing::warn;
 
-/// Application configuration (uses hardcoded defaults)
+/// Which kind of workspace a generated branch belongs to.
+///
+/// Each kind has its own configurable prefix so remote branch listings stay
+/// organized and CI rules can target specific kinds of branches.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BranchKind {
+    /// A regular agent created from the new-agent prompt.
+    #[default]
+    Agent,
+    /// The root agent of a planning or spawned swarm.
+    Swarm,
+}
+
+impl BranchKind {
+    /// All branch kinds.
+    pub const ALL: &'static [Self] = &[Self::Swarm, Self::Agent];
+}
+
+/// Application configuration: defaults, overridden by `config.toml` files and then by settings
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Config {
     /// Default program to run for agents (e.g., "claude", "aider")
     pub default_program: String,
 
-    /// Prefix for branch names created by tenex
+    /// Prefix for branch names created by tenex for regular agents
     pub branch_prefix: String,
 
+    /// Prefix for branch names created for swarm roots
+    pub swarm_branch_prefix: String,
+
     /// Auto-accept prompts (experimental)
     pub auto_yes: bool,
 
@@ -27,6 +52,15 @@ pub struct Config {
 
     /// Directory for worktrees
     pub worktree_dir: PathBuf,
+
+    /// Directory for Tenex-maintained partial clones
+    pub clone_dir: PathBuf,
+
+    /// Whether a repository's `.tenex/config.toml` may set `default_program`
+    pub trust_repo_default_program: bool,
+
+    /// Directory for worktrees before settings, used when settings don't set one
+    configured_worktree_dir: PathBuf,
 }
 
 impl Default for Config {
@@ -34,14 +68,52 @@ impl Default for Config {
         Self {
             default_program: "claude --allow-dangerously-skip-permissions".to_string(),
             branch_prefix: "agent/".to_string(),
+            swarm_branch_prefix: "agent/".to_string(),
             auto_yes: false,
             poll_interval_ms: 100,
             worktree_dir: Self::default_worktree_dir(),
+            clone_dir: Self::default_clone_dir(),
+            trust_repo_default_program: false,
+            configured_worktree_dir: Self::default_worktree_dir(),
         }
     }
 }
 
 impl Config {
+    /// Defaults overridden by the user's `config.toml`.
+    ///
+    /// A repository's `.tenex/config.toml` applies only to its own agents; see
+    /// [`Self::for_repo`]. A file that can't be read or parsed is skipped with a warning in the
+    /// log.
+    #[must_use]
+    pub fn load() -> Self {
+        let mut config = Self::default();
+        if let Some(path) = user_config_path() {
+            let base = path
+                .parent()
+                .map_or_else(|| PathBuf::from("."), Path::to_path_buf);
+            file::ConfigFile::load(&path).apply_to(&mut config, &base);
+        }
+        config
+    }
+
+    /// The config for agents of the repository `dir` is in: this one overridden by the
+    /// repository's `.tenex/config.toml`, then by `settings`.
+    ///
+    /// Also returns the repository's file when it sets a `default_program` that was ignored
+    /// because `trust_repo_default_program` is off.
+    #[must_use]
+    pub fn for_repo(&self, dir: &Path, settings: &Settings) -> (Self, Option<PathBuf>) {
+        let mut config = self.clone();
+        let Ok(repo_root) = crate::git::repository_root(dir) else {
+            return (config, None);
+        };
+        let path = repo_config_path(&repo_root);
+        let ignored_program = file::ConfigFile::load(&path).apply_repo_to(&mut config, &repo_root);
+        config.apply_settings(settings);
+        (config, ignored_program.then_some(path))
+    }
+
     fn default_instance_root_from(home_dir: Option<PathBuf>) -> PathBuf {
         let home_dir = home_dir.unwrap_or_else(|| PathBuf::from("."));
         home_dir.join(".tenex")
@@ -128,6 +200,24 @@ impl Config {
         Self::instance_root().join("worktrees")
     }
 
+    /// Default directory for partial clones used as worktree sources.
+    ///
+    /// - Default: `~/.tenex/clones/`
+    /// - With `TENEX_STATE_PATH`: `clones/` under the instance root
+    #[must_use]
+    pub fn default_clone_dir() -> PathBuf {
+        Self::instance_root().join("clones")
+    }
+
+    /// Directory holding installed plugins.
+    ///
+    /// - Default: `~/.tenex/plugins/`
+    /// - With `TENEX_STATE_PATH`: `plugins/` under the instance root
+    #[must_use]
+    pub fn plugin_dir() -> PathBuf {
+        Self::instance_root().join("plugins")
+    }
+
     fn instance_root_from_state_path(state_path: &Path) -> PathBuf {
         state_path
             .parent()
@@ -142,30 +232,188 @@ impl Config {
             .map_or_else(|| "project".to_string(), str::to_string)
     }
 
-    fn worktree_leaf_dir_name(branch: &str, branch_prefix: &str) -> String {
-        let leaf = branch
-            .strip_prefix(branch_prefix)
+    fn worktree_leaf_dir_name(&self, branch: &str) -> String {
+        self.strip_managed_prefix(branch).replace('/', "-")
+    }
+
+    /// Returns the configured branch prefix for a kind of workspace.
+    #[must_use]
+    pub fn branch_prefix_for(&self, kind: BranchKind) -> &str {
+        match kind {
+            BranchKind::Agent => &self.branch_prefix,
+            BranchKind::Swarm => &self.swarm_branch_prefix,
+        }
+    }
+
+    /// Returns the kind whose prefix matches `branch`, preferring the longest prefix.
+    #[must_use]
+    pub fn branch_kind_of(&self, branch: &str) -> Option<BranchKind> {
+        BranchKind::ALL
+            .iter()
+            .copied()
+            .filter(|kind| {
+                let prefix = self.branch_prefix_for(*kind);
+                !prefix.is_empty() && branch.starts_with(prefix)
+            })
+            .max_by_key(|kind| self.branch_prefix_for(*kind).len())
+    }
+
+    /// Whether `branch` was created by Tenex (and may be deleted along with its agent).
+    #[must_use]
+    pub fn is_managed_branch(&self, branch: &str) -> bool {
+        self.branch_kind_of(branch).is_some() || branch.starts_with("tenex/")
+    }
+
+    /// Strips the Tenex-managed prefix from `branch`, if any.
+    #[must_use]
+    pub fn strip_managed_prefix<'a>(&self, branch: &'a str) -> &'a str {
+        self.branch_kind_of(branch)
+            .and_then(|kind| branch.strip_prefix(self.branch_prefix_for(kind)))
             .or_else(|| branch.strip_prefix("tenex/"))
-            .unwrap_or(branch);
-        leaf.replace('/', "-")
+            .unwrap_or(branch)
+    }
+
+    /// Applies user overrides from settings (e.g. custom branch prefixes).
+    pub fn apply_settings(&mut self, settings: &Settings) {
+        self.worktree_dir = settings
+            .worktree_dir
+            .as_deref()
+            .map_or_else(|| self.configured_worktree_dir.clone(), Self::expand_home);
+        let overrides = [
+            (&mut self.branch_prefix, &settings.branch_prefix),
+            (&mut self.swarm_branch_prefix, &settings.swarm_branch_prefix),
+        ];
+        for (target, value) in overrides {
+            if let Some(value) = value {
+                value.clone_into(target);
+            }
+        }
     }
 
     /// Returns the directory Tenex should store worktrees for a given repo root under.
+    ///
+    /// A repository can choose its own location with `git config tenex.worktreeDir <path>`;
+    /// otherwise worktrees go in a per-project directory under `worktree_dir`.
     #[must_use]
     pub fn worktree_dir_for_repo_root(&self, repo_root: &Path) -> PathBuf {
-        self.worktree_dir.join(Self::project_dir_name(repo_root))
+        Self::repo_worktree_dir_override(repo_root)
+            .unwrap_or_else(|| self.worktree_dir.join(Self::project_dir_name(repo_root)))
+    }
+
+    /// Whether `repo_root` overrides where its worktrees go (`tenex.worktreeDir`).
+    #[must_use]
+    pub fn has_repo_worktree_dir(repo_root: &Path) -> bool {
+        Self::repo_worktree_dir_override(repo_root).is_some()
+    }
+
+    fn repo_worktree_dir_override(repo_root: &Path) -> Option<PathBuf> {
+        let repo = crate::git::open_repository(repo_root).ok()?;
+        let raw = repo.config().ok()?.get_string("tenex.worktreeDir").ok()?;
+        Self::resolve_repo_worktree_dir(repo_root, &raw, paths::home_dir())
+    }
+
+    /// `path` with a leading `~` replaced by the home directory.
+    fn expand_home(path: &Path) -> PathBuf {
+        match (path.strip_prefix("~"), paths::home_dir()) {
+            (Ok(rest), Some(home)) => home.join(rest),
+            _ => path.to_path_buf(),
+        }
+    }
+
+    /// Resolve a `tenex.worktreeDir` value for `repo_root`.
+    ///
+    /// `{repo}` expands to the repository directory name and a leading `~` to the home
+    /// directory. Relative paths are resolved against the repository root, so
+    /// `../{repo}-worktrees` puts worktrees next to the repository. `.` and `..` are resolved
+    /// lexically so paths compare equal regardless of how they were written (Windows verbatim
+    /// paths don't allow `..`).
+    ///
+    /// A directory inside the repository is ignored with a warning, since agents must not
+    /// write to the main checkout.
+    fn resolve_repo_worktree_dir(
+        repo_root: &Path,
+        raw: &str,
+        home_dir: Option<PathBuf>,
+    ) -> Option<PathBuf> {
+        let raw = raw.trim();
+        if raw.is_empty() {
+            return None;
+        }
+
+        let expanded = raw.replace("{repo}", &Self::project_dir_name(repo_root));
+        let path = if expanded == "~" {
+            home_dir?
+        } else if let Some(rest) = expanded
+            .strip_prefix("~/")
+            .or_else(|| expanded.strip_prefix("~\\"))
+        {
+            home_dir?.join(rest)
+        } else {
+            PathBuf::from(expanded)
+        };
+
+        let path = if path.is_absolute() {
+            path
+        } else {
+            repo_root.join(path)
+        };
+        let path = Self::normalize_lexically(&path);
+        if path.starts_with(Self::normalize_lexically(repo_root)) {
+            warn!(
+                worktree_dir = %path.display(),
+                repo = %repo_root.display(),
+                "Ignoring tenex.worktreeDir inside the repository; agents must not write to the main checkout"
+            );
+            return None;
+        }
+        Some(path)
+    }
+
+    fn normalize_lexically(path: &Path) -> PathBuf {
+        use std::path::Component;
+
+        let mut normalized = PathBuf::new();
+        for component in path.components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if !normalized.pop() {
+                        normalized.push(component);
+                    }
+                }
+                Component::Prefix(_) | Component::RootDir | Component::Normal(_) => {
+                    normalized.push(component);
+                }
+            }
+        }
+        normalized
+    }
+
+    /// Returns where Tenex keeps the partial clone of a given repo root.
+    ///
+    /// The `-partial` suffix keeps its worktrees apart from the repo's own.
+    #[must_use]
+    pub fn partial_clone_path_for_repo_root(&self, repo_root: &Path) -> PathBuf {
+        self.clone_dir
+            .join(format!("{}-partial", Self::project_dir_name(repo_root)))
     }
 
     /// Returns the worktree path for a given repo root and branch name.
     #[must_use]
     pub fn worktree_path_for_repo_root(&self, repo_root: &Path, branch: &str) -> PathBuf {
         self.worktree_dir_for_repo_root(repo_root)
-            .join(Self::worktree_leaf_dir_name(branch, &self.branch_prefix))
+            .join(self.worktree_leaf_dir_name(branch))
     }
 
     /// Generate a branch name for a new agent
     #[must_use]
     pub fn generate_branch_name(&self, title: &str) -> String {
+        self.generate_branch_name_for(BranchKind::Agent, title)
+    }
+
+    /// Generate a branch name for a new workspace of the given kind
+    #[must_use]
+    pub fn generate_branch_name_for(&self, kind: BranchKind, title: &str) -> String {
         let sanitized: String = title
             .chars()
             .map(|c| {
@@ -182,6 +430,10 @@ impl Config {
         } else {
             &sanitized
         };
-        format!("{}{}", self.branch_prefix, truncated.trim_matches('-'))
+        format!(
+            "{}{}",
+            self.branch_prefix_for(kind),
+            truncated.trim_matches('-')
+        )
     }
 }
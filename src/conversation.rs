@@ -7,7 +7,7 @@ use crate::command;
 use chrono::{Datelike as _, Duration as ChronoDuration, Local, NaiveDate, Utc};
 use serde::Deserialize;
 use std::collections::HashSet;
-use std::io::{BufRead as _, BufReader};
+use std::io::{BufRead as _, BufReader, Read as _, Seek as _, SeekFrom};
 use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime};
 
@@ -24,6 +24,40 @@ pub enum AgentCli {
     Other,
 }
 
+impl AgentCli {
+    /// Command that asks the CLI to exit cleanly, typed at its prompt and submitted.
+    ///
+    /// `None` for programs Tenex knows nothing about; those get Ctrl+C and Ctrl+D instead.
+    #[must_use]
+    pub const fn quit_command(self) -> Option<&'static str> {
+        match self {
+            Self::Claude => Some("/exit"),
+            Self::Codex => Some("/quit"),
+            Self::Other => None,
+        }
+    }
+
+    /// Lowercase name shown in the UI.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Claude => "claude",
+            Self::Codex => "codex",
+            Self::Other => "custom",
+        }
+    }
+
+    /// Tokens of context the CLI's default model accepts, if known.
+    #[must_use]
+    pub const fn context_window(self) -> Option<usize> {
+        match self {
+            Self::Claude => Some(200_000),
+            Self::Codex => Some(272_000),
+            Self::Other => None,
+        }
+    }
+}
+
 /// Detect the agent CLI from a configured program string.
 pub fn detect_agent_cli(program: &str) -> AgentCli {
     let Ok(argv) = command::parse_command_line(program) else {
@@ -77,6 +111,26 @@ pub fn build_spawn_argv(
     Ok(argv)
 }
 
+/// Build argv that runs `program` non-interactively on `prompt` and prints its reply.
+///
+/// Returns `None` for programs Tenex doesn't know how to run that way.
+///
+/// # Errors
+///
+/// Returns an error when `program` cannot be parsed into an argv vector.
+pub fn build_one_shot_argv(program: &str, prompt: &str) -> Result<Option<Vec<String>>> {
+    let mut argv = command::parse_command_line(program)?;
+
+    match detect_agent_cli(program) {
+        AgentCli::Claude => argv.push("-p".to_string()),
+        AgentCli::Codex => argv.push("exec".to_string()),
+        AgentCli::Other => return Ok(None),
+    }
+    argv.push(prompt.to_string());
+
+    Ok(Some(argv))
+}
+
 /// Build argv for resuming an agent conversation by id.
 ///
 /// # Errors
@@ -100,6 +154,392 @@ pub fn build_resume_argv(program: &str, conversation_id: &str) -> Result<Vec<Str
     Ok(argv)
 }
 
+/// Best-effort lookup of the most recent conversation `program` had in `workdir`, so it can be
+/// resumed. Codex sessions are only searched from the last day or so.
+#[must_use]
+pub fn latest_conversation_id(program: &str, workdir: &Path) -> Option<String> {
+    match detect_agent_cli(program) {
+        AgentCli::Claude => latest_claude_session_id(workdir),
+        AgentCli::Codex => detect_codex_session_id_once_in_root(
+            &codex_sessions_root()?,
+            workdir,
+            SystemTime::UNIX_EPOCH,
+            &HashSet::new(),
+        ),
+        AgentCli::Other => None,
+    }
+}
+
+/// Claude keeps each project's conversations as `<session id>.jsonl` files in a directory
+/// named after the project path with every non-alphanumeric character replaced by `-`.
+fn claude_project_dir(workdir: &Path) -> Option<PathBuf> {
+    let project_dir: String = normalize_path(workdir)
+        .to_string_lossy()
+        .chars()
+        .map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '-' })
+        .collect();
+    Some(claude_projects_root()?.join(project_dir))
+}
+
+fn latest_claude_session_id(workdir: &Path) -> Option<String> {
+    let entries = std::fs::read_dir(claude_project_dir(workdir)?).ok()?;
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("jsonl"))
+        .filter_map(|path| {
+            let modified = std::fs::metadata(&path).ok()?.modified().ok()?;
+            Some((modified, path))
+        })
+        .max_by_key(|(modified, _)| *modified)
+        .and_then(|(_, path)| Some(path.file_stem()?.to_str()?.to_string()))
+}
+
+/// How much of its context window a conversation takes, as of its latest model turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContextUsage {
+    /// Tokens in the context: the prompt of the latest turn, with everything before it, and
+    /// the reply.
+    pub tokens: usize,
+    /// Tokens the model accepts, if known.
+    pub window: Option<usize>,
+}
+
+impl ContextUsage {
+    /// Share of the context window in use, in percent, if the window is known.
+    #[must_use]
+    pub fn percent(self) -> Option<usize> {
+        self.window
+            .filter(|window| *window > 0)
+            .map(|window| self.tokens.saturating_mul(100) / window)
+    }
+
+    /// Whether at least `percent` of the context window is in use.
+    #[must_use]
+    pub fn is_near_limit(self, percent: u32) -> bool {
+        self.percent()
+            .is_some_and(|used| used >= usize::try_from(percent).unwrap_or(usize::MAX))
+    }
+}
+
+/// Bytes read from the end of a transcript when looking for its latest usage. Transcripts grow
+/// to many megabytes, and the latest turn is near the end.
+const TRANSCRIPT_TAIL_BYTES: u64 = 512 * 1024;
+
+/// Transcript file of conversation `conversation_id`, which `program` had in `workdir`.
+#[must_use]
+pub fn transcript_path(program: &str, workdir: &Path, conversation_id: &str) -> Option<PathBuf> {
+    match detect_agent_cli(program) {
+        AgentCli::Claude => {
+            let path = claude_project_dir(workdir)?.join(format!("{conversation_id}.jsonl"));
+            path.is_file().then_some(path)
+        }
+        AgentCli::Codex => find_codex_transcript(&codex_sessions_root()?, conversation_id),
+        AgentCli::Other => None,
+    }
+}
+
+/// Codex names transcripts `rollout-<time>-<session id>.jsonl` in `YYYY/MM/DD` directories.
+/// The newest days are searched first, since running sessions are usually recent.
+fn find_codex_transcript(sessions_root: &Path, conversation_id: &str) -> Option<PathBuf> {
+    let suffix = format!("{conversation_id}.jsonl");
+    let newest_first = |dir: &Path| -> Vec<PathBuf> {
+        let mut entries: Vec<PathBuf> = std::fs::read_dir(dir)
+            .map(|entries| entries.flatten().map(|entry| entry.path()).collect())
+            .unwrap_or_default();
+        entries.sort_unstable_by(|a, b| b.cmp(a));
+        entries
+    };
+    for year in newest_first(sessions_root) {
+        for month in newest_first(&year) {
+            for day in newest_first(&month) {
+                let found = newest_first(&day).into_iter().find(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.ends_with(&suffix))
+                });
+                if found.is_some() {
+                    return found;
+                }
+            }
+        }
+    }
+    None
+}
+
+/// The latest context usage recorded in the transcript at `path`, written by `cli`.
+#[must_use]
+pub fn read_context_usage(cli: AgentCli, path: &Path) -> Option<ContextUsage> {
+    let mut file = std::fs::File::open(path).ok()?;
+    let len = file.metadata().ok()?.len();
+    let start = len.saturating_sub(TRANSCRIPT_TAIL_BYTES);
+    file.seek(SeekFrom::Start(start)).ok()?;
+    let mut tail = Vec::new();
+    file.read_to_end(&mut tail).ok()?;
+    let tail = String::from_utf8_lossy(&tail);
+
+    // A tail that starts mid-file starts mid-line.
+    let lines = tail.lines().skip(usize::from(start > 0));
+    let mut lines: Vec<&str> = lines.collect();
+    lines.reverse();
+    match cli {
+        AgentCli::Claude => lines
+            .into_iter()
+            .filter(|line| line.contains("\"usage\""))
+            .find_map(claude_context_usage),
+        AgentCli::Codex => lines
+            .into_iter()
+            .filter(|line| line.contains("\"token_count\""))
+            .find_map(codex_context_usage),
+        AgentCli::Other => None,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ClaudeTranscriptLine {
+    #[serde(default, rename = "isSidechain")]
+    is_sidechain: bool,
+    message: ClaudeTranscriptMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClaudeTranscriptMessage {
+    usage: ClaudeUsage,
+}
+
+#[derive(Debug, Deserialize)]
+#[expect(
+    clippy::struct_field_names,
+    reason = "Field names match Claude's transcript format"
+)]
+struct ClaudeUsage {
+    #[serde(default)]
+    input_tokens: usize,
+    #[serde(default)]
+    cache_creation_input_tokens: usize,
+    #[serde(default)]
+    cache_read_input_tokens: usize,
+    #[serde(default)]
+    output_tokens: usize,
+}
+
+/// Claude records the usage of each reply; its input counts split by cache status add up to
+/// the whole context the reply saw. Replies of subagents (`isSidechain`) have their own
+/// context and are skipped.
+fn claude_context_usage(line: &str) -> Option<ContextUsage> {
+    let parsed: ClaudeTranscriptLine = serde_json::from_str(line).ok()?;
+    if parsed.is_sidechain {
+        return None;
+    }
+    let usage = parsed.message.usage;
+    Some(ContextUsage {
+        tokens: usage
+            .input_tokens
+            .saturating_add(usage.cache_creation_input_tokens)
+            .saturating_add(usage.cache_read_input_tokens)
+            .saturating_add(usage.output_tokens),
+        window: AgentCli::Claude.context_window(),
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct CodexTokenCountLine {
+    payload: CodexTokenCountPayload,
+}
+
+#[derive(Debug, Deserialize)]
+struct CodexTokenCountPayload {
+    #[serde(rename = "type")]
+    kind: String,
+    info: CodexTokenInfo,
+}
+
+#[derive(Debug, Deserialize)]
+struct CodexTokenInfo {
+    last_token_usage: CodexTokenUsage,
+    model_context_window: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CodexTokenUsage {
+    total_tokens: usize,
+}
+
+/// Codex records a `token_count` event after each turn, with the usage of that turn and the
+/// model's context window.
+fn codex_context_usage(line: &str) -> Option<ContextUsage> {
+    let parsed: CodexTokenCountLine = serde_json::from_str(line).ok()?;
+    if parsed.payload.kind != "token_count" {
+        return None;
+    }
+    let info = parsed.payload.info;
+    Some(ContextUsage {
+        tokens: info.last_token_usage.total_tokens,
+        window: info
+            .model_context_window
+            .or_else(|| AgentCli::Codex.context_window()),
+    })
+}
+
+/// Tokens a conversation has used across all of its model turns, by how they are billed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TokenUsage {
+    /// Input tokens read without the prompt cache.
+    pub input: u64,
+    /// Input tokens written to the prompt cache.
+    pub cache_write: u64,
+    /// Input tokens read from the prompt cache.
+    pub cache_read: u64,
+    /// Output tokens, reasoning included.
+    pub output: u64,
+}
+
+/// Running token totals of a transcript, read a little more each time it grows.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TokenTally {
+    /// Bytes of the transcript counted so far; always the end of a complete line.
+    offset: u64,
+    /// Tokens used by the turns counted so far.
+    usage: TokenUsage,
+    /// Claude writes one line per content block of a reply, each with the reply's usage; the
+    /// reply counted last, so its other lines are skipped.
+    last_message_id: Option<String>,
+}
+
+impl TokenTally {
+    /// Tokens used by the turns counted so far.
+    #[must_use]
+    pub const fn usage(&self) -> TokenUsage {
+        self.usage
+    }
+
+    /// Count the turns `cli` wrote to the transcript at `path` since the last read. Returns
+    /// whether the totals changed.
+    pub fn read_more(&mut self, cli: AgentCli, path: &Path) -> bool {
+        let Ok(mut file) = std::fs::File::open(path) else {
+            return false;
+        };
+        let len = file.metadata().map_or(0, |metadata| metadata.len());
+        if len < self.offset {
+            // The transcript was replaced; count it from the start.
+            *self = Self::default();
+        }
+        if len == self.offset || file.seek(SeekFrom::Start(self.offset)).is_err() {
+            return false;
+        }
+        let mut chunk = Vec::new();
+        if file.read_to_end(&mut chunk).is_err() {
+            return false;
+        }
+        // Leave a line that is still being written for the next read.
+        let Some(complete) = chunk.iter().rposition(|byte| *byte == b'\n') else {
+            return false;
+        };
+        self.offset += u64::try_from(complete + 1).unwrap_or(u64::MAX);
+
+        let before = self.usage;
+        for line in String::from_utf8_lossy(&chunk[..complete]).lines() {
+            match cli {
+                AgentCli::Claude if line.contains("\"usage\"") => self.count_claude_line(line),
+                AgentCli::Codex if line.contains("\"token_count\"") => {
+                    if let Some(total) = codex_total_usage(line) {
+                        self.usage = total;
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.usage != before
+    }
+
+    fn count_claude_line(&mut self, line: &str) {
+        let Ok(parsed) = serde_json::from_str::<ClaudeBilledLine>(line) else {
+            return;
+        };
+        if parsed.message.id.is_some() && parsed.message.id == self.last_message_id {
+            return;
+        }
+        self.last_message_id = parsed.message.id;
+        let usage = parsed.message.usage;
+        self.usage.input = self.usage.input.saturating_add(usage.input_tokens as u64);
+        self.usage.cache_write = self
+            .usage
+            .cache_write
+            .saturating_add(usage.cache_creation_input_tokens as u64);
+        self.usage.cache_read = self
+            .usage
+            .cache_read
+            .saturating_add(usage.cache_read_input_tokens as u64);
+        self.usage.output = self.usage.output.saturating_add(usage.output_tokens as u64);
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ClaudeBilledLine {
+    message: ClaudeBilledMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClaudeBilledMessage {
+    #[serde(default)]
+    id: Option<String>,
+    usage: ClaudeUsage,
+}
+
+#[derive(Debug, Deserialize)]
+struct CodexTotalLine {
+    payload: CodexTotalPayload,
+}
+
+#[derive(Debug, Deserialize)]
+struct CodexTotalPayload {
+    #[serde(rename = "type")]
+    kind: String,
+    info: CodexTotalInfo,
+}
+
+#[derive(Debug, Deserialize)]
+struct CodexTotalInfo {
+    total_token_usage: CodexTotalUsage,
+}
+
+#[derive(Debug, Deserialize)]
+#[expect(
+    clippy::struct_field_names,
+    reason = "Field names match Codex's transcript format"
+)]
+struct CodexTotalUsage {
+    #[serde(default)]
+    input_tokens: u64,
+    #[serde(default)]
+    cached_input_tokens: u64,
+    #[serde(default)]
+    output_tokens: u64,
+}
+
+/// Codex's `token_count` events carry the conversation's running totals; its input count
+/// includes the cached input.
+fn codex_total_usage(line: &str) -> Option<TokenUsage> {
+    let parsed: CodexTotalLine = serde_json::from_str(line).ok()?;
+    if parsed.payload.kind != "token_count" {
+        return None;
+    }
+    let total = parsed.payload.info.total_token_usage;
+    Some(TokenUsage {
+        input: total.input_tokens.saturating_sub(total.cached_input_tokens),
+        cache_write: 0,
+        cache_read: total.cached_input_tokens,
+        output: total.output_tokens,
+    })
+}
+
+fn claude_projects_root() -> Option<PathBuf> {
+    let config_dir_from_env = std::env::var_os("CLAUDE_CONFIG_DIR").map(PathBuf::from);
+    let config_dir_from_home = crate::paths::home_dir().map(|home| home.join(".claude"));
+    let config_dir = config_dir_from_env.or(config_dir_from_home)?;
+    Some(config_dir.join("projects"))
+}
+
 /// Best-effort detection of the Codex session id created after spawning a `codex` process.
 #[must_use]
 pub fn try_detect_codex_session_id<S: std::hash::BuildHasher>(
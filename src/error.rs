@@ -0,0 +1,162 @@
+//! Errors shown to the user, by category.
+//!
+//! Each category carries the help text the error modal shows under the message, so the
+//! same failure explains itself the same way wherever it is raised. Handlers return these in
+//! [`ErrorModalMode`](crate::state::ErrorModalMode), and code that only has an
+//! [`anyhow::Error`] can recover the category with [`TenexError::wrap`].
+
+use std::fmt::Display;
+
+use crate::git::AuthFailure;
+
+/// An error the UI shows, with category-specific help.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TenexError {
+    /// An action that works on the selected agent ran with none selected.
+    #[error("No agent selected")]
+    NoAgentSelected {
+        /// What the user tried to do, completing "Select an agent first to ...".
+        action: &'static str,
+    },
+    /// The agent an action was started for no longer exists.
+    #[error("Agent not found")]
+    AgentMissing,
+    /// An action that talks to an agent found its session stopped.
+    #[error("{title} is not running")]
+    AgentNotRunning {
+        /// Title of the agent.
+        title: String,
+    },
+    /// A git action ran on an agent outside a git repository.
+    #[error("{action} requires a git repository")]
+    NotGitRepository {
+        /// The action, capitalized (`Push`, `Rebase`, ...).
+        action: &'static str,
+    },
+    /// A git operation was started while another one runs for the same agent.
+    #[error("A git operation is already running for this agent")]
+    GitBusy,
+    /// A git operation could not reach or authenticate with the remote.
+    #[error("{operation} failed: {}", .failure.summary())]
+    GitAuth {
+        /// The operation, capitalized (`Push`, `Fetch`).
+        operation: &'static str,
+        /// Why git could not authenticate.
+        failure: AuthFailure,
+    },
+    /// A git operation failed for another reason.
+    #[error("{operation} failed: {detail}")]
+    Git {
+        /// The operation, capitalized (`Push`, `Rebase`, ...).
+        operation: &'static str,
+        /// What git reported.
+        detail: String,
+    },
+    /// A worktree for the branch is already registered.
+    #[error("A worktree for branch '{branch}' already exists")]
+    WorktreeExists {
+        /// The branch of the worktree.
+        branch: String,
+    },
+    /// The mux daemon that hosts the agent sessions cannot be reached.
+    #[error("Failed to connect to mux daemon at {endpoint}")]
+    MuxDead {
+        /// The socket the daemon should listen on.
+        endpoint: String,
+    },
+    /// An agent's program is not on `PATH`, and neither is any fallback program.
+    #[error("`{program}` is not installed or not on PATH")]
+    ProgramNotFound {
+        /// The command the agent was to run.
+        program: String,
+        /// The fallback programs that were tried after it.
+        fallbacks: Vec<String>,
+    },
+    /// `settings.json` could not be written.
+    #[error("Failed to save settings: {detail}")]
+    SettingsSave {
+        /// The I/O error.
+        detail: String,
+    },
+    /// Any other error, described by its message alone.
+    #[error("{0}")]
+    Other(String),
+}
+
+impl TenexError {
+    /// What usually fixes the error, shown under its message.
+    #[must_use]
+    pub fn help(&self) -> Option<String> {
+        match self {
+            Self::NoAgentSelected { action } => Some(format!(
+                "Select an agent in the sidebar first to {action}."
+            )),
+            Self::AgentMissing => Some(
+                "The agent was removed before the action finished. Select another agent and try again."
+                    .to_string(),
+            ),
+            Self::AgentNotRunning { .. } => Some(
+                "Its session has exited, so it cannot take input. Start a new agent to continue the task."
+                    .to_string(),
+            ),
+            Self::NotGitRepository { .. } => {
+                Some("Start Tenex in a git repo to use worktrees.".to_string())
+            }
+            Self::GitBusy => Some("Wait for it to finish, or press Esc to cancel it.".to_string()),
+            Self::GitAuth { .. } => Some(
+                "Fix the credentials, then try again. Run /fetch to see the steps that usually fix it."
+                    .to_string(),
+            ),
+            Self::WorktreeExists { .. } => Some(
+                "Reconnect to the existing worktree, or find it with git worktree list and remove it."
+                    .to_string(),
+            ),
+            Self::MuxDead { .. } => Some(format!(
+                "The daemon hosts every agent session. Check {} for why it stopped, then restart Tenex to start a new one.",
+                crate::paths::log_path().display()
+            )),
+            Self::ProgramNotFound { fallbacks, .. } if fallbacks.is_empty() => Some(format!(
+                "Install it, pick another program with /agents, or list commands to try instead in fallback_programs in {}.",
+                crate::app::Settings::path().display()
+            )),
+            Self::ProgramNotFound { fallbacks, .. } => Some(format!(
+                "None of fallback_programs ({}) is on PATH either. Install one of them, or pick another program with /agents.",
+                fallbacks.join(", ")
+            )),
+            Self::SettingsSave { .. } => Some(format!(
+                "Check that {} and its directory are writable.",
+                crate::app::Settings::path().display()
+            )),
+            Self::Git { .. } | Self::Other(_) => None,
+        }
+    }
+
+    /// The message followed by its help, as copied from the error modal.
+    #[must_use]
+    pub fn describe(&self) -> String {
+        self.help()
+            .map_or_else(|| self.to_string(), |help| format!("{self}\n\n{help}"))
+    }
+
+    /// The error to show for `err` raised while doing `context`: the category of `err` when it
+    /// carries one, otherwise its message after `context`.
+    #[must_use]
+    pub fn wrap(context: impl Display, err: &anyhow::Error) -> Self {
+        err.chain()
+            .find_map(|cause| cause.downcast_ref::<Self>())
+            .cloned()
+            .unwrap_or_else(|| Self::Other(format!("{context}: {err:#}")))
+    }
+}
+
+impl From<String> for TenexError {
+    fn from(message: String) -> Self {
+        Self::Other(message)
+    }
+}
+
+impl From<&str> for TenexError {
+    fn from(message: &str) -> Self {
+        Self::Other(message.to_string())
+    }
+}
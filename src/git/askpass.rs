@@ -0,0 +1,153 @@
+//! Authentication prompts from git and ssh, relayed to the Tenex UI.
+//!
+//! Tenex points `GIT_ASKPASS` and `SSH_ASKPASS` at its own binary. When git or ssh needs a
+//! username, password, or passphrase, that helper process writes the prompt into a private
+//! directory and waits there for the answer the user types into Tenex.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use uuid::Uuid;
+
+/// Names the exchange directory of a running session; the helper process is started with it.
+const ASKPASS_DIR_VAR: &str = "TENEX_ASKPASS_DIR";
+const PROMPT_FILE: &str = "prompt";
+const ANSWER_FILE: &str = "answer";
+const DECLINED_FILE: &str = "declined";
+
+/// How long the helper waits for an answer before failing the prompt.
+const ANSWER_TIMEOUT: Duration = Duration::from_mins(5);
+const HELPER_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A private directory through which the helper and Tenex exchange one prompt at a time.
+///
+/// The directory is removed on drop, which also releases a helper still waiting in it.
+#[derive(Debug)]
+pub struct Askpass {
+    dir: PathBuf,
+}
+
+impl Askpass {
+    /// Create the exchange directory, readable only by the current user.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created.
+    pub fn new() -> Result<Self> {
+        let dir = std::env::temp_dir().join(format!("tenex-askpass-{}", Uuid::new_v4()));
+        let mut builder = fs::DirBuilder::new();
+        #[cfg(unix)]
+        std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+        builder
+            .create(&dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        Ok(Self { dir })
+    }
+
+    /// Send the authentication prompts of `command` through this session.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path of the Tenex binary cannot be found.
+    pub fn configure(&self, command: &mut Command) -> Result<()> {
+        let helper = std::env::current_exe().context("Failed to locate the Tenex binary")?;
+        command
+            .env("GIT_ASKPASS", &helper)
+            .env("SSH_ASKPASS", &helper)
+            .env("SSH_ASKPASS_REQUIRE", "force")
+            .env(ASKPASS_DIR_VAR, &self.dir);
+        Ok(())
+    }
+
+    /// The prompt a helper is waiting on. Each prompt is returned once.
+    #[must_use]
+    pub fn take_prompt(&self) -> Option<String> {
+        let path = self.dir.join(PROMPT_FILE);
+        let prompt = fs::read_to_string(&path).ok()?;
+        let _ = fs::remove_file(&path);
+        Some(prompt.trim().to_string())
+    }
+
+    /// Send `answer` to the waiting helper.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the answer cannot be written.
+    pub fn answer(&self, answer: &str) -> Result<()> {
+        write_exchange_file(&self.dir, ANSWER_FILE, answer)
+    }
+
+    /// Decline the prompt the helper is waiting on, failing it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reply cannot be written.
+    pub fn decline(&self) -> Result<()> {
+        write_exchange_file(&self.dir, DECLINED_FILE, "")
+    }
+}
+
+impl Drop for Askpass {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Write `name` in `dir` so the other side never reads it half-written.
+fn write_exchange_file(dir: &Path, name: &str, contents: &str) -> Result<()> {
+    let partial = dir.join(format!("{name}.partial"));
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options
+        .open(&partial)
+        .with_context(|| format!("Failed to write {}", partial.display()))?;
+    file.write_all(contents.as_bytes())
+        .with_context(|| format!("Failed to write {}", partial.display()))?;
+    fs::rename(&partial, dir.join(name))
+        .with_context(|| format!("Failed to write {name} in {}", dir.display()))
+}
+
+/// Act as the askpass helper when this process was started as one: hand the prompt in the
+/// first argument to Tenex, print the answer for git or ssh, and return the exit code.
+///
+/// Returns `None` when the process is not a helper.
+#[must_use]
+pub fn run_askpass_helper() -> Option<i32> {
+    let dir = PathBuf::from(std::env::var_os(ASKPASS_DIR_VAR)?);
+    let prompt = std::env::args().nth(1).unwrap_or_default();
+    let answer = ask(&dir, &prompt).ok().flatten();
+    Some(answer.map_or(1, |answer| {
+        let mut stdout = std::io::stdout();
+        match writeln!(stdout, "{answer}") {
+            Ok(()) => 0,
+            Err(_) => 1,
+        }
+    }))
+}
+
+/// Post `prompt` in `dir` and wait for the answer, `None` if it is declined or never comes.
+fn ask(dir: &Path, prompt: &str) -> Result<Option<String>> {
+    write_exchange_file(dir, PROMPT_FILE, prompt)?;
+    let answer_path = dir.join(ANSWER_FILE);
+    let declined_path = dir.join(DECLINED_FILE);
+    let started = Instant::now();
+    while started.elapsed() < ANSWER_TIMEOUT && dir.exists() {
+        if let Ok(answer) = fs::read_to_string(&answer_path) {
+            let _ = fs::remove_file(&answer_path);
+            return Ok(Some(answer));
+        }
+        if declined_path.exists() {
+            let _ = fs::remove_file(&declined_path);
+            return Ok(None);
+        }
+        std::thread::sleep(HELPER_POLL_INTERVAL);
+    }
+    let _ = fs::remove_file(dir.join(PROMPT_FILE));
+    Ok(None)
+}
@@ -0,0 +1,196 @@
+//! Recognizing authentication failures in git's output, with guidance on fixing them.
+
+use std::path::Path;
+
+use crate::error::TenexError;
+
+/// Why git could not reach or authenticate with a remote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthFailure {
+    /// The SSH host key is unknown or changed.
+    SshHostKey,
+    /// The SSH server rejected every key offered: none is loaded in ssh-agent, or the host
+    /// does not know it.
+    SshKey,
+    /// The HTTPS server rejected the credentials: an expired or revoked token, or a password
+    /// where a token is required.
+    HttpsCredentials,
+    /// The remote URL leads nowhere: a wrong URL, a renamed repository, or one the account
+    /// cannot see.
+    RemoteNotFound,
+}
+
+/// Lowercase fragments of git and ssh errors, checked in order, and the failure they mean.
+const PATTERNS: &[(&str, AuthFailure)] = &[
+    ("host key verification failed", AuthFailure::SshHostKey),
+    (
+        "remote host identification has changed",
+        AuthFailure::SshHostKey,
+    ),
+    ("permission denied (publickey", AuthFailure::SshKey),
+    ("sign_and_send_pubkey", AuthFailure::SshKey),
+    ("agent refused operation", AuthFailure::SshKey),
+    ("authentication failed for", AuthFailure::HttpsCredentials),
+    (
+        "invalid username or password",
+        AuthFailure::HttpsCredentials,
+    ),
+    (
+        "password authentication was removed",
+        AuthFailure::HttpsCredentials,
+    ),
+    ("could not read username", AuthFailure::HttpsCredentials),
+    ("could not read password", AuthFailure::HttpsCredentials),
+    ("http basic: access denied", AuthFailure::HttpsCredentials),
+    (
+        "requested url returned error: 401",
+        AuthFailure::HttpsCredentials,
+    ),
+    (
+        "requested url returned error: 403",
+        AuthFailure::HttpsCredentials,
+    ),
+    ("repository not found", AuthFailure::RemoteNotFound),
+    (
+        "does not appear to be a git repository",
+        AuthFailure::RemoteNotFound,
+    ),
+    (
+        "requested url returned error: 404",
+        AuthFailure::RemoteNotFound,
+    ),
+    ("could not resolve host", AuthFailure::RemoteNotFound),
+];
+
+impl AuthFailure {
+    /// The failure `stderr` of a push or fetch reports, if it is one of these.
+    #[must_use]
+    pub fn detect(stderr: &str) -> Option<Self> {
+        let stderr = stderr.to_lowercase();
+        PATTERNS
+            .iter()
+            .find(|(fragment, _)| stderr.contains(fragment))
+            .map(|(_, failure)| *failure)
+    }
+
+    /// One-line description of the failure.
+    #[must_use]
+    pub const fn summary(self) -> &'static str {
+        match self {
+            Self::SshHostKey => "the SSH host key of the remote is unknown or changed",
+            Self::SshKey => "the SSH server rejected your key",
+            Self::HttpsCredentials => "the server rejected your HTTPS credentials",
+            Self::RemoteNotFound => "the remote repository was not found",
+        }
+    }
+
+    /// Steps that usually fix the failure, for `remote` at `url`.
+    #[must_use]
+    pub fn steps(self, remote: &str, url: Option<&str>) -> Vec<String> {
+        let ssh_target = url.and_then(ssh_target);
+        let host = url.and_then(host).unwrap_or("<host>");
+        match self {
+            Self::SshHostKey => vec![
+                format!(
+                    "Connect once to check and accept the key: ssh -T {}",
+                    ssh_target.as_deref().unwrap_or("git@<host>")
+                ),
+                format!(
+                    "If the host changed its key on purpose, forget the old one: ssh-keygen -R {host}"
+                ),
+            ],
+            Self::SshKey => vec![
+                "Check that ssh-agent is running and holds your key: ssh-add -l".to_string(),
+                "Load your key: ssh-add ~/.ssh/id_ed25519".to_string(),
+                format!(
+                    "Check that the host accepts it: ssh -T {}",
+                    ssh_target.as_deref().unwrap_or("git@<host>")
+                ),
+                "Make sure the public key is added to your account on the host".to_string(),
+            ],
+            Self::HttpsCredentials => vec![
+                "Your token may have expired, been revoked, or lack write access".to_string(),
+                "Create a new token and sign in again (for GitHub: gh auth login)".to_string(),
+                format!(
+                    "Forget the stored credential so git asks again: printf 'protocol=https\\nhost={host}\\n\\n' | git credential reject"
+                ),
+            ],
+            Self::RemoteNotFound => vec![
+                format!("Check the remote URL: git remote get-url {remote}"),
+                format!("Fix it: git remote set-url {remote} <url>"),
+                "Private repositories also report this when your account has no access".to_string(),
+            ],
+        }
+    }
+}
+
+/// Guidance for an `operation` (such as `Push`) that failed with `failure` against `remote`:
+/// the failure, the remote URL, what git said, and the steps to fix it.
+#[must_use]
+pub fn auth_guidance(
+    operation: &'static str,
+    failure: AuthFailure,
+    remote: &str,
+    url: Option<&str>,
+    stderr: &str,
+) -> String {
+    let mut lines = vec![
+        format!("{}.", TenexError::GitAuth { operation, failure }),
+        String::new(),
+    ];
+    lines.push(format!("Remote {remote}: {}", url.unwrap_or("(no URL)")));
+    if let Some(said) = stderr
+        .lines()
+        .map(str::trim)
+        .find(|line| AuthFailure::detect(line).is_some())
+    {
+        lines.push(format!("Git said: {said}"));
+    }
+    lines.push(String::new());
+    lines.extend(
+        failure
+            .steps(remote, url)
+            .into_iter()
+            .map(|step| format!("• {step}")),
+    );
+    lines.join("\n")
+}
+
+/// The URL of `remote` in the repository at `repo_path`.
+#[must_use]
+pub fn remote_url(repo_path: &Path, remote: &str) -> Option<String> {
+    let output = super::git_command()
+        .args(["remote", "get-url", remote])
+        .current_dir(repo_path)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|url| !url.is_empty())
+}
+
+/// `user@host` to test an SSH remote with `ssh -T`, if `url` is an SSH remote.
+fn ssh_target(url: &str) -> Option<String> {
+    if let Some(rest) = url.strip_prefix("ssh://") {
+        let authority = rest.split('/').next()?;
+        return Some(authority.split(':').next()?.to_string());
+    }
+    if url.contains("://") {
+        return None;
+    }
+    // scp-like syntax: git@github.com:owner/repo.git
+    let (target, _) = url.split_once(':')?;
+    Some(target.to_string())
+}
+
+/// The host name in `url`.
+fn host(url: &str) -> Option<&str> {
+    let authority = url.split_once("://").map_or_else(
+        || url.split_once(':').map(|(target, _)| target),
+        |(_, rest)| rest.split('/').next(),
+    )?;
+    let host = authority.rsplit('@').next()?;
+    host.split(':').next().filter(|host| !host.is_empty())
+}
@@ -0,0 +1,132 @@
+//! Cancellable subprocesses (git, and agent programs run non-interactively).
+
+use anyhow::{Context, Result};
+use std::io::Read;
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Error returned when a git command was cancelled before it finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl std::fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+fn drain<R: Read + Send + 'static>(mut pipe: R) -> JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.read_to_end(&mut buf);
+        buf
+    })
+}
+
+/// Like [`drain`], but passes each complete update in the output (text ending in a carriage
+/// return or newline, as git writes its progress) to `on_update`.
+fn drain_watched<R, F>(mut pipe: R, mut on_update: F) -> JoinHandle<Vec<u8>>
+where
+    R: Read + Send + 'static,
+    F: FnMut(&str) + Send + 'static,
+{
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let mut chunk = [0_u8; 4096];
+        let mut reported = 0;
+        loop {
+            let read = match pipe.read(&mut chunk) {
+                Ok(0) => break,
+                Ok(read) => read,
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(_) => break,
+            };
+            buf.extend_from_slice(&chunk[..read]);
+            let Some(end) = buf.iter().rposition(|byte| matches!(byte, b'\r' | b'\n')) else {
+                continue;
+            };
+            if end < reported {
+                continue;
+            }
+            for update in String::from_utf8_lossy(&buf[reported..end]).split(['\r', '\n']) {
+                let update = update.trim();
+                if !update.is_empty() {
+                    on_update(update);
+                }
+            }
+            reported = end + 1;
+        }
+        buf
+    })
+}
+
+fn collect(reader: Option<JoinHandle<Vec<u8>>>) -> Vec<u8> {
+    reader
+        .and_then(|reader| reader.join().ok())
+        .unwrap_or_default()
+}
+
+/// Run `command` to completion like [`Command::output`], killing it once `cancelled` is set.
+///
+/// # Errors
+///
+/// Returns [`Cancelled`] if the command was killed, or an error if it could not be run.
+pub fn output_cancellable(command: &mut Command, cancelled: &AtomicBool) -> Result<Output> {
+    output_watched(command, cancelled, |_| {}, || {})
+}
+
+/// Like [`output_cancellable`], but passes each update `command` writes to stderr, such as a
+/// line of git's progress, to `on_progress`, and calls `on_poll` each time it checks whether
+/// the command was cancelled.
+///
+/// # Errors
+///
+/// Returns [`Cancelled`] if the command was killed, or an error if it could not be run.
+pub fn output_watched<P, W>(
+    command: &mut Command,
+    cancelled: &AtomicBool,
+    on_progress: P,
+    mut on_poll: W,
+) -> Result<Output>
+where
+    P: FnMut(&str) + Send + 'static,
+    W: FnMut(),
+{
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run {}", command.get_program().to_string_lossy()))?;
+    let stdout = child.stdout.take().map(drain);
+    let stderr = child
+        .stderr
+        .take()
+        .map(|pipe| drain_watched(pipe, on_progress));
+
+    let status = loop {
+        if let Some(status) = child.try_wait().context("Failed to wait for git")? {
+            break status;
+        }
+        if cancelled.load(Ordering::Relaxed) {
+            let _ = child.kill();
+            let _ = child.wait();
+            // Helpers like ssh may still hold the pipes open; don't wait for the readers.
+            return Err(Cancelled.into());
+        }
+        on_poll();
+        std::thread::sleep(CANCEL_POLL_INTERVAL);
+    };
+
+    Ok(Output {
+        status,
+        stdout: collect(stdout),
+        stderr: collect(stderr),
+    })
+}
@@ -0,0 +1,162 @@
+//! Tenex-maintained partial clones used as the source repository for agent worktrees.
+//!
+//! A repository opts in with `git config tenex.partialClone true`. Tenex then clones its
+//! `origin` with `--filter=blob:none --no-checkout`, so file contents are only downloaded for
+//! the commits agents actually check out, and creates worktrees from that clone.
+
+use anyhow::{Context, Result, bail};
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+
+/// Git config key recording which repository a Tenex partial clone mirrors.
+const CLONE_OF_KEY: &str = "tenex.partialCloneOf";
+
+fn git_stdout(workdir: &Path, args: &[&str]) -> Option<String> {
+    let output = super::git_command()
+        .args(args)
+        .current_dir(workdir)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|stdout| !stdout.is_empty())
+}
+
+/// Whether `repo_root` asks for agent worktrees to come from a partial clone.
+#[must_use]
+pub fn partial_clone_enabled(repo_root: &Path) -> bool {
+    git_stdout(
+        repo_root,
+        &["config", "--bool", "--get", "tenex.partialClone"],
+    )
+    .is_some_and(|value| value == "true")
+}
+
+/// Whether `repo_path` is a partial clone created by [`create_partial_clone`].
+#[must_use]
+pub fn is_partial_clone(repo_path: &Path) -> bool {
+    git_stdout(repo_path, &["config", "--get", CLONE_OF_KEY]).is_some()
+}
+
+/// Clone `source_repo`'s `origin` into `dest` as a blobless, no-checkout partial clone.
+///
+/// The clone is made next to `dest` and renamed into place once complete, so an interrupted
+/// clone never looks usable.
+///
+/// # Errors
+///
+/// Returns an error if `source_repo` has no `origin` remote, or the clone fails or is
+/// cancelled.
+pub fn create_partial_clone(source_repo: &Path, dest: &Path, cancelled: &AtomicBool) -> Result<()> {
+    let url = git_stdout(source_repo, &["remote", "get-url", "origin"])
+        .with_context(|| format!("{} has no origin remote to clone", source_repo.display()))?;
+    let parent = dest
+        .parent()
+        .context("Partial clone destination has no parent directory")?;
+    fs::create_dir_all(parent).with_context(|| format!("Failed to create {}", parent.display()))?;
+
+    // `<dest>.cloning`: appended rather than set as the extension, so destinations that differ
+    // only in their extension don't share a temporary directory.
+    let mut name = dest
+        .file_name()
+        .context("Partial clone destination has no directory name")?
+        .to_os_string();
+    name.push(".cloning");
+    let partial = parent.join(name);
+    if partial.exists() {
+        fs::remove_dir_all(&partial)
+            .with_context(|| format!("Failed to remove {}", partial.display()))?;
+    }
+
+    let mut command = super::git_command();
+    command
+        .args(["clone", "--filter=blob:none", "--no-checkout", &url])
+        .arg(&partial)
+        .current_dir(parent);
+    let output = super::output_cancellable(&mut command, cancelled);
+    let output = match output {
+        Ok(output) => output,
+        Err(err) => {
+            let _ = fs::remove_dir_all(&partial);
+            return Err(err);
+        }
+    };
+    if !output.status.success() {
+        let _ = fs::remove_dir_all(&partial);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("git clone --filter=blob:none failed: {}", stderr.trim());
+    }
+
+    let marked = super::git_command()
+        .args(["config", CLONE_OF_KEY])
+        .arg(source_repo)
+        .current_dir(&partial)
+        .output()
+        .is_ok_and(|output| output.status.success());
+    if !marked {
+        let _ = fs::remove_dir_all(&partial);
+        bail!(
+            "Failed to mark {} as a Tenex partial clone",
+            partial.display()
+        );
+    }
+
+    fs::rename(&partial, dest).with_context(|| {
+        format!(
+            "Failed to move partial clone into place at {}",
+            dest.display()
+        )
+    })
+}
+
+/// Fast-forward the partial clone's `HEAD` branch to its upstream (after a fetch).
+///
+/// New worktrees branch from `HEAD`, and the clone has no checkout to update, so this keeps
+/// new agents starting from the latest remote commit. Returns whether the branch moved.
+///
+/// # Errors
+///
+/// Returns an error if the ref update fails.
+pub fn fast_forward_head(repo_path: &Path) -> Result<bool> {
+    let Some(branch) = git_stdout(repo_path, &["symbolic-ref", "--short", "HEAD"]) else {
+        return Ok(false);
+    };
+    let upstream = format!("{branch}@{{upstream}}");
+    let (Some(local), Some(remote)) = (
+        git_stdout(repo_path, &["rev-parse", "--verify", &branch]),
+        git_stdout(repo_path, &["rev-parse", "--verify", &upstream]),
+    ) else {
+        return Ok(false);
+    };
+    if local == remote {
+        return Ok(false);
+    }
+
+    let is_fast_forward = super::git_command()
+        .args(["merge-base", "--is-ancestor", &local, &remote])
+        .current_dir(repo_path)
+        .output()
+        .is_ok_and(|output| output.status.success());
+    if !is_fast_forward {
+        return Ok(false);
+    }
+
+    let output = super::git_command()
+        .args([
+            "update-ref",
+            &format!("refs/heads/{branch}"),
+            &remote,
+            &local,
+        ])
+        .current_dir(repo_path)
+        .output()
+        .context("Failed to run git update-ref")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("Failed to fast-forward {branch}: {}", stderr.trim());
+    }
+    Ok(true)
+}
@@ -0,0 +1,34 @@
+//! Per-repository Tenex settings stored in git config (`tenex.*` keys).
+
+use std::path::Path;
+
+/// Git config key holding the link pattern for agent tickets, with `{ticket}` standing for the
+/// ticket ID (for example `https://acme.atlassian.net/browse/{ticket}`).
+const TICKET_URL_KEY: &str = "tenex.ticketUrl";
+
+/// Placeholder for the ticket ID in [`TICKET_URL_KEY`].
+const TICKET_PLACEHOLDER: &str = "{ticket}";
+
+/// Read git config `key` in `workdir`; `None` when unset or empty.
+#[must_use]
+pub fn config_string(workdir: &Path, key: &str) -> Option<String> {
+    let output = super::git_command()
+        .args(["config", "--get", key])
+        .current_dir(workdir)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Link to `ticket` from the repository's `tenex.ticketUrl` pattern.
+///
+/// A GitHub-style `#123` fills the pattern with just the number.
+#[must_use]
+pub fn ticket_url(repo_path: &Path, ticket: &str) -> Option<String> {
+    let pattern = config_string(repo_path, TICKET_URL_KEY)?;
+    Some(pattern.replace(TICKET_PLACEHOLDER, ticket.trim_start_matches('#')))
+}
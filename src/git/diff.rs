@@ -257,6 +257,91 @@ fn push_model_hunk_line(
     });
 }
 
+/// Patch of `worktree`, including uncommitted changes to tracked files, against the commit
+/// where it branched off `base`.
+///
+/// # Errors
+///
+/// Returns an error if git cannot be run or cannot find the merge base.
+pub fn patch_against_base(worktree: &Path, base: &str) -> Result<String> {
+    let output = super::git_command()
+        .args(["diff", "--merge-base", base])
+        .current_dir(worktree)
+        .output()
+        .context("Failed to run git diff")?;
+    if !output.status.success() {
+        anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Files of `worktree` changed since the commit where it branched off `base`, or since `HEAD`
+/// when `base` is `None`, including untracked files. Paths are relative to the worktree root.
+///
+/// # Errors
+///
+/// Returns an error if git cannot be run or cannot find the merge base.
+pub fn changed_paths(worktree: &Path, base: Option<&str>) -> Result<Vec<PathBuf>> {
+    let mut diff = super::git_command();
+    diff.args(["diff", "--name-only", "-z"]);
+    match base {
+        Some(base) => diff.args(["--merge-base", base]),
+        None => diff.arg("HEAD"),
+    };
+    let mut untracked = super::git_command();
+    untracked.args(["ls-files", "--others", "--exclude-standard", "-z"]);
+
+    let mut paths = path_list(diff, worktree)?;
+    paths.extend(path_list(untracked, worktree)?);
+    paths.sort();
+    paths.dedup();
+    Ok(paths)
+}
+
+/// NUL-separated paths printed by a git command run in `worktree`.
+fn path_list(mut command: std::process::Command, worktree: &Path) -> Result<Vec<PathBuf>> {
+    let output = command
+        .current_dir(worktree)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .collect())
+}
+
+/// Whether `path`, relative to the repository root, matches glob `pattern`.
+///
+/// `**` matches any number of directories, and `*` and `?` match within one path component,
+/// so `**/migrations/**` matches every file under any `migrations` directory.
+#[must_use]
+pub fn path_matches_glob(pattern: &str, path: &Path) -> bool {
+    let pattern: Vec<&str> = pattern
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    let components: Vec<String> = path
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    let components: Vec<&str> = components.iter().map(String::as_str).collect();
+    segments_match(&pattern, &components)
+}
+
+fn segments_match(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| segments_match(rest, &path[skip..])),
+        Some((segment, rest)) => path.split_first().is_some_and(|(name, path)| {
+            crate::tmux::matches_pattern(segment, name) && segments_match(rest, path)
+        }),
+    }
+}
+
 /// Generator for git diffs
 pub struct Generator<'a> {
     repo: &'a Repository,
@@ -714,6 +799,18 @@ pub struct DiffModel {
     pub hash: u64,
 }
 
+impl DiffModel {
+    /// Keep only the files whose path satisfies `keep`, updating the summary.
+    pub fn retain_files(&mut self, keep: impl Fn(&Path) -> bool) {
+        self.files.retain(|file| keep(&file.path));
+        self.summary = Summary {
+            files_changed: self.files.len(),
+            additions: self.files.iter().map(|file| file.additions).sum(),
+            deletions: self.files.iter().map(|file| file.deletions).sum(),
+        };
+    }
+}
+
 /// A single file in a structured diff.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DiffFile {
@@ -1,14 +1,40 @@
 //! Git operations module
 
+mod askpass;
+mod auth;
 mod branch;
+mod cancel;
+mod clone;
+mod config;
 mod diff;
+mod recover;
+mod remote;
+mod secrets;
+mod setup;
+mod stash;
+mod trash;
 mod worktree;
 
+pub use askpass::{Askpass, run_askpass_helper};
+pub use auth::{AuthFailure, auth_guidance, remote_url};
 pub use branch::{BranchInfo, Manager as BranchManager};
+pub use cancel::Cancelled;
+pub(crate) use cancel::{output_cancellable, output_watched};
+pub use clone::{create_partial_clone, fast_forward_head, is_partial_clone, partial_clone_enabled};
+pub use config::{config_string, ticket_url};
 pub use diff::{
     DiffDigest, DiffFile, DiffHunk, DiffHunkLine, DiffModel, FileChange, FileStatus,
-    Generator as DiffGenerator, LineChange, Summary as DiffSummary,
+    Generator as DiffGenerator, LineChange, Summary as DiffSummary, changed_paths,
+    patch_against_base, path_matches_glob,
 };
+pub use recover::{
+    LostCommit, LostCommitSource, branch_tip, dangling_commits, find_lost_commits, recreate_branch,
+};
+pub use remote::{BranchDivergence, base_ref, divergence, fetch_prune};
+pub use secrets::{ALLOW_SECRET_MARKER, SecretFinding, outgoing_patch, scan_patch};
+pub use setup::{WorktreeSetup, detect_worktree_setup, run_worktree_setup};
+pub use stash::{commit_wip, has_uncommitted_changes, restore_stash, stash_changes};
+pub use trash::{TRASH_REF_PREFIX, delete_trash_ref, trash_branch};
 pub use worktree::{
     CreateOptions as WorktreeCreateOptions, Info as WorktreeInfo, Manager as WorktreeManager,
     TargetPreparation as WorktreeTargetPreparation,
@@ -125,6 +151,62 @@ pub fn repository_workspace_root(path: &Path) -> Result<PathBuf> {
         .context("Repository has no working directory")
 }
 
+/// Resolve `path` through its deepest existing ancestor so not-yet-created paths compare
+/// correctly against canonical paths (symlinked temp dirs, `..` components, etc.).
+fn resolve_path_for_comparison(path: &Path) -> PathBuf {
+    let mut existing = path;
+    let mut missing = Vec::new();
+    loop {
+        if let Ok(canonical) = existing.canonicalize() {
+            return missing
+                .iter()
+                .rev()
+                .fold(canonical, |acc: PathBuf, part| acc.join(part));
+        }
+        let (Some(parent), Some(name)) = (existing.parent(), existing.file_name()) else {
+            return path.to_path_buf();
+        };
+        missing.push(name.to_os_string());
+        existing = parent;
+    }
+}
+
+/// Check whether `path` resolves inside (or to) the main checkout of the repository
+/// that owns `repo_path`.
+///
+/// Linked worktrees never count as the main checkout, even when `repo_path` points at one.
+#[must_use]
+pub fn is_inside_main_checkout(path: &Path, repo_path: &Path) -> bool {
+    let Ok(main_root) = repository_workspace_root(repo_path) else {
+        return false;
+    };
+    let main_root = resolve_path_for_comparison(&main_root);
+    resolve_path_for_comparison(path).starts_with(main_root)
+}
+
+/// Check whether `path` is inside a repository's primary working tree rather than a
+/// linked worktree.
+#[must_use]
+pub fn is_main_checkout(path: &Path) -> bool {
+    Repository::discover(path).is_ok_and(|repo| !repo.is_worktree() && repo.workdir().is_some())
+}
+
+/// Refuse worktree targets that resolve inside the user's main checkout.
+///
+/// # Errors
+///
+/// Returns an error if `path` resolves inside the main checkout of `repo_path`.
+pub fn ensure_outside_main_checkout(path: &Path, repo_path: &Path) -> Result<()> {
+    if is_inside_main_checkout(path, repo_path) {
+        anyhow::bail!(
+            "Refusing to create a Tenex worktree at {} because it is inside the main checkout of {}; agents must not write to the primary repository",
+            path.display(),
+            repo_path.display()
+        );
+    }
+    Ok(())
+}
+
 /// Ensure `.tenex/` is in `.git/info/exclude`
 ///
 /// This prevents synthesis files from being tracked by git.
@@ -0,0 +1,235 @@
+//! Finding lost branch tips after a branch was deleted.
+//!
+//! Deleting a branch also deletes its reflog, and removing a worktree deletes that worktree's
+//! `HEAD` log, but the commits themselves stay in the object database until `git gc` prunes
+//! them. They can still be found through the recorded tip, other reflogs that mention the
+//! branch (for example `checkout: moving from <branch> to main`), and dangling commits.
+
+use anyhow::{Context, Result, bail};
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+
+/// Where a lost commit was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LostCommitSource {
+    /// The branch tip Tenex recorded when the agent was killed.
+    Recorded,
+    /// A trash ref kept for the branch (`refs/tenex/trash/`).
+    Trash,
+    /// A reflog entry mentioning the branch.
+    Reflog,
+    /// A dangling commit made while the agent was running.
+    Dangling,
+}
+
+impl LostCommitSource {
+    /// Short label for display.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Recorded => "recorded tip",
+            Self::Trash => "trash",
+            Self::Reflog => "reflog",
+            Self::Dangling => "dangling",
+        }
+    }
+}
+
+/// A commit that may be the tip of a deleted branch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LostCommit {
+    /// Full commit hash.
+    pub commit: String,
+    /// Commit time (seconds since the Unix epoch).
+    pub time: i64,
+    /// First line of the commit message.
+    pub summary: String,
+    /// Where the commit was found.
+    pub source: LostCommitSource,
+}
+
+impl LostCommit {
+    /// Abbreviated commit hash.
+    #[must_use]
+    pub fn short_commit(&self) -> &str {
+        self.commit.get(..7).unwrap_or(&self.commit)
+    }
+}
+
+/// Current commit of local branch `branch`, if it exists.
+#[must_use]
+pub fn branch_tip(repo_path: &Path, branch: &str) -> Option<String> {
+    let output = super::git_command()
+        .args(["rev-parse", "--verify", "--quiet"])
+        .arg(format!("refs/heads/{branch}^{{commit}}"))
+        .current_dir(repo_path)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|commit| !commit.is_empty())
+}
+
+/// Look up `commit` in `repo`, if the object still exists.
+fn describe(repo: &git2::Repository, commit: &str, source: LostCommitSource) -> Option<LostCommit> {
+    let oid = git2::Oid::from_str(commit).ok()?;
+    let found = repo.find_commit(oid).ok()?;
+    Some(LostCommit {
+        commit: oid.to_string(),
+        time: found.time().seconds(),
+        summary: found.summary().unwrap_or_default().to_string(),
+        source,
+    })
+}
+
+/// Commits kept for `branch` under trash refs.
+fn trash_tips(repo: &git2::Repository, branch: &str) -> Vec<String> {
+    let Ok(references) = repo.references_glob(&format!("{}{branch}/*", super::TRASH_REF_PREFIX))
+    else {
+        return Vec::new();
+    };
+    references
+        .flatten()
+        .filter_map(|reference| reference.target())
+        .map(|oid| oid.to_string())
+        .collect()
+}
+
+/// Commits that reflog entries record as `branch`'s tip.
+///
+/// `checkout: moving from <branch> to ...` records the branch tip as the old value; any other
+/// entry naming the branch (a checkout to it, a merge of it) records it as the new value.
+fn reflog_tips(repo: &git2::Repository, branch: &str) -> Vec<String> {
+    let mut names = vec!["HEAD".to_string()];
+    if let Ok(references) = repo.references() {
+        names.extend(
+            references
+                .flatten()
+                .filter_map(|reference| reference.name().map(str::to_string)),
+        );
+    }
+
+    let moved_from = format!("from {branch} ");
+    let mut tips = Vec::new();
+    for name in names {
+        let Ok(reflog) = repo.reflog(&name) else {
+            continue;
+        };
+        for entry in reflog.iter() {
+            let Some(message) = entry.message() else {
+                continue;
+            };
+            if message.contains(&moved_from) {
+                tips.push(entry.id_old().to_string());
+            } else if message
+                .split_whitespace()
+                .any(|word| word.trim_end_matches(':') == branch)
+            {
+                tips.push(entry.id_new().to_string());
+            }
+        }
+    }
+    tips
+}
+
+/// Dangling commits in the repository at `repo_path`, ignoring reflogs so commits only kept
+/// alive by a reflog count too.
+///
+/// # Errors
+///
+/// Returns an error if `git fsck` cannot run or is cancelled.
+pub fn dangling_commits(repo_path: &Path, cancelled: &AtomicBool) -> Result<Vec<String>> {
+    let mut command = super::git_command();
+    command
+        .args(["fsck", "--no-reflogs", "--no-progress"])
+        .current_dir(repo_path);
+    let output =
+        super::output_cancellable(&mut command, cancelled).context("Failed to run git fsck")?;
+    // fsck exits non-zero when it finds problems unrelated to us, so only stdout matters.
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.strip_prefix("dangling commit "))
+        .map(|commit| commit.trim().to_string())
+        .collect())
+}
+
+/// Candidate tips for deleted branch `branch`, best first.
+///
+/// `recorded` is the tip Tenex saw when it deleted the branch; trash refs and reflogs naming
+/// the branch come next. `dangling` are the repository's dangling commits (see
+/// [`dangling_commits`]); those made between `since` and `until` (Unix seconds) are offered
+/// last, newest first.
+///
+/// # Errors
+///
+/// Returns an error if the repository cannot be opened.
+pub fn find_lost_commits(
+    repo_path: &Path,
+    branch: &str,
+    recorded: Option<&str>,
+    dangling: &[String],
+    (since, until): (i64, i64),
+) -> Result<Vec<LostCommit>> {
+    /// Most dangling commits offered per branch.
+    const MAX_DANGLING: usize = 5;
+
+    let repo = super::open_repository(repo_path)?;
+    let mut found: Vec<LostCommit> = recorded
+        .and_then(|commit| describe(&repo, commit, LostCommitSource::Recorded))
+        .into_iter()
+        .collect();
+
+    let mut from_trash: Vec<LostCommit> = trash_tips(&repo, branch)
+        .iter()
+        .filter_map(|commit| describe(&repo, commit, LostCommitSource::Trash))
+        .collect();
+    from_trash.sort_by_key(|commit| std::cmp::Reverse(commit.time));
+    found.extend(from_trash);
+
+    let mut from_reflog: Vec<LostCommit> = reflog_tips(&repo, branch)
+        .iter()
+        .filter_map(|commit| describe(&repo, commit, LostCommitSource::Reflog))
+        .collect();
+    from_reflog.sort_by_key(|commit| std::cmp::Reverse(commit.time));
+    found.extend(from_reflog);
+
+    let mut from_dangling: Vec<LostCommit> = dangling
+        .iter()
+        .filter_map(|commit| describe(&repo, commit, LostCommitSource::Dangling))
+        .filter(|commit| (since..=until).contains(&commit.time))
+        .collect();
+    from_dangling.sort_by_key(|commit| std::cmp::Reverse(commit.time));
+    found.extend(from_dangling.into_iter().take(MAX_DANGLING));
+
+    let mut seen = HashSet::new();
+    found.retain(|commit| seen.insert(commit.commit.clone()));
+    Ok(found)
+}
+
+/// Create local branch `branch` at `commit`, or `<branch>-recovered` (then `-recovered-2`, ...)
+/// when `branch` already exists. Returns the name of the created branch.
+///
+/// # Errors
+///
+/// Returns an error if the branch cannot be created.
+pub fn recreate_branch(repo_path: &Path, branch: &str, commit: &str) -> Result<String> {
+    let name = std::iter::once(branch.to_string())
+        .chain(std::iter::once(format!("{branch}-recovered")))
+        .chain((2..=u32::MAX).map(|n| format!("{branch}-recovered-{n}")))
+        .find(|name| branch_tip(repo_path, name).is_none())
+        .context("No free branch name to recover into")?;
+
+    let output = super::git_command()
+        .args(["branch", &name, commit])
+        .current_dir(repo_path)
+        .output()
+        .context("Failed to run git branch")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("Failed to create branch {name}: {}", stderr.trim());
+    }
+    Ok(name)
+}
@@ -0,0 +1,121 @@
+//! Remote fetch and base-branch divergence helpers.
+
+use anyhow::{Context, Result, bail};
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+
+/// How an agent branch compares to its base branch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchDivergence {
+    /// Ref the branch was compared against (the base's upstream when it has one).
+    pub base_ref: String,
+    /// Commits on the agent branch that are not on the base.
+    pub ahead: u32,
+    /// Commits on the base that the agent branch does not have.
+    pub behind: u32,
+    /// The branch tracks a remote branch that no longer exists (pruned by fetch).
+    pub upstream_gone: bool,
+}
+
+fn git_succeeds(workdir: &Path, args: &[&str]) -> bool {
+    super::git_command()
+        .args(args)
+        .current_dir(workdir)
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+fn git_stdout(workdir: &Path, args: &[&str]) -> Option<String> {
+    let output = super::git_command()
+        .args(args)
+        .current_dir(workdir)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|stdout| !stdout.is_empty())
+}
+
+/// Run `git fetch --prune` in `repo_path`, stopping early if `cancelled` is set.
+///
+/// # Errors
+///
+/// Returns an error if the fetch fails or is cancelled.
+pub fn fetch_prune(repo_path: &Path, cancelled: &AtomicBool) -> Result<()> {
+    let mut command = super::git_command();
+    command.args(["fetch", "--prune"]).current_dir(repo_path);
+    let output = super::output_cancellable(&mut command, cancelled)
+        .with_context(|| format!("Failed to fetch in {}", repo_path.display()))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("git fetch --prune failed: {}", stderr.trim());
+    }
+    Ok(())
+}
+
+/// The ref to compare against for `base_branch`: its upstream if it has one, else the branch.
+#[must_use]
+pub fn base_ref(workdir: &Path, base_branch: &str) -> String {
+    git_stdout(
+        workdir,
+        &[
+            "rev-parse",
+            "--abbrev-ref",
+            "--symbolic-full-name",
+            &format!("{base_branch}@{{upstream}}"),
+        ],
+    )
+    .unwrap_or_else(|| base_branch.to_string())
+}
+
+/// Compare the branch checked out in `worktree_path` with `base_branch`.
+///
+/// # Errors
+///
+/// Returns an error if the commits cannot be counted (for example, the base doesn't exist).
+pub fn divergence(
+    worktree_path: &Path,
+    branch: &str,
+    base_branch: &str,
+) -> Result<BranchDivergence> {
+    let base_ref = base_ref(worktree_path, base_branch);
+    let counts = git_stdout(
+        worktree_path,
+        &[
+            "rev-list",
+            "--left-right",
+            "--count",
+            &format!("HEAD...{base_ref}"),
+        ],
+    )
+    .with_context(|| format!("Failed to compare HEAD with {base_ref}"))?;
+
+    let mut counts = counts.split_whitespace().map(str::parse::<u32>);
+    let (Some(Ok(ahead)), Some(Ok(behind))) = (counts.next(), counts.next()) else {
+        bail!("Unexpected rev-list output while comparing HEAD with {base_ref}");
+    };
+
+    let has_upstream_config = git_succeeds(
+        worktree_path,
+        &["config", "--get", &format!("branch.{branch}.merge")],
+    );
+    let upstream_gone = has_upstream_config
+        && !git_succeeds(
+            worktree_path,
+            &[
+                "rev-parse",
+                "--verify",
+                "--quiet",
+                &format!("{branch}@{{upstream}}"),
+            ],
+        );
+
+    Ok(BranchDivergence {
+        base_ref,
+        ahead,
+        behind,
+        upstream_gone,
+    })
+}
@@ -0,0 +1,159 @@
+//! Lightweight secret scanning of the changes Tenex is about to push.
+
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use anyhow::{Context, Result};
+use regex_lite::Regex;
+
+/// Text that marks an added line as a known false positive.
+pub const ALLOW_SECRET_MARKER: &str = "tenex:allow-secret";
+
+/// Shannon entropy, in bits per character, above which an assigned value counts as a secret.
+const ENTROPY_THRESHOLD: f64 = 3.5;
+
+/// Well-known credential formats: rule name and pattern.
+const RULES: &[(&str, &str)] = &[
+    ("AWS access key", r"\b(AKIA|ASIA)[0-9A-Z]{16}\b"),
+    (
+        "GitHub token",
+        r"\b(gh[pousr]_[A-Za-z0-9]{36,}|github_pat_[A-Za-z0-9_]{60,})",
+    ),
+    ("Slack token", r"\bxox[abposr]-[A-Za-z0-9-]{10,}"),
+    ("Anthropic API key", r"\bsk-ant-[A-Za-z0-9_-]{20,}"),
+    ("OpenAI API key", r"\bsk-(proj-)?[A-Za-z0-9_-]{32,}"),
+    ("Google API key", r"\bAIza[0-9A-Za-z_-]{35}"),
+    ("Stripe live key", r"\b[rs]k_live_[0-9A-Za-z]{20,}"),
+    ("Private key", r"-----BEGIN ([A-Z]+ )?PRIVATE KEY-----"),
+];
+
+static COMPILED_RULES: LazyLock<Vec<(&'static str, Regex)>> = LazyLock::new(|| {
+    RULES
+        .iter()
+        .filter_map(|(name, pattern)| Regex::new(pattern).ok().map(|regex| (*name, regex)))
+        .collect()
+});
+
+/// A value assigned to a name that suggests a credential (`api_key = "..."`).
+static ASSIGNMENT: LazyLock<Option<Regex>> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?i)(secret|token|passw(or)?d|api_?key|access_?key|private_?key|credential)[A-Za-z0-9_]*["']?\s*[:=]\s*["']?([A-Za-z0-9+/=_\-.]{16,})"#,
+    )
+    .ok()
+});
+
+/// An added line that looks like it holds a credential.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretFinding {
+    /// File the line was added to, relative to the repository root.
+    pub path: PathBuf,
+    /// Line number in the new version of the file.
+    pub line: u32,
+    /// Name of the rule that matched.
+    pub rule: &'static str,
+    /// The matched text with most of it masked.
+    pub excerpt: String,
+}
+
+/// Patch of the commits `git push` would send from `worktree`: those since its upstream, or,
+/// without one, since it branched off `base`.
+///
+/// # Errors
+///
+/// Returns an error if git cannot be run or knows neither the upstream nor `base`.
+pub fn outgoing_patch(worktree: &std::path::Path, base: &str) -> Result<String> {
+    for range in ["@{upstream}...HEAD".to_string(), format!("{base}...HEAD")] {
+        let output = super::git_command()
+            .args(["diff", "--no-color", "--no-ext-diff", &range])
+            .current_dir(worktree)
+            .output()
+            .context("Failed to run git diff")?;
+        if output.status.success() {
+            return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
+        }
+    }
+    anyhow::bail!("Could not find the commits to push against the upstream or {base}")
+}
+
+/// Find lines that `patch` adds and that match a credential rule or assign a high-entropy
+/// value to a secret-sounding name. Lines with [`ALLOW_SECRET_MARKER`] are skipped.
+#[must_use]
+pub fn scan_patch(patch: &str) -> Vec<SecretFinding> {
+    let mut findings = Vec::new();
+    let mut file = PathBuf::new();
+    let mut line_number: u32 = 0;
+
+    for line in patch.lines() {
+        if let Some(new_path) = line.strip_prefix("+++ ") {
+            file = PathBuf::from(new_path.strip_prefix("b/").unwrap_or(new_path));
+            continue;
+        }
+        if let Some(header) = line.strip_prefix("@@ ") {
+            line_number = hunk_new_start(header).unwrap_or(0);
+            continue;
+        }
+        if let Some(added) = line.strip_prefix('+') {
+            if !added.contains(ALLOW_SECRET_MARKER)
+                && let Some((rule, matched)) = match_line(added)
+            {
+                findings.push(SecretFinding {
+                    path: file.clone(),
+                    line: line_number,
+                    rule,
+                    excerpt: mask(matched),
+                });
+            }
+            line_number = line_number.saturating_add(1);
+        } else if line.starts_with(' ') {
+            line_number = line_number.saturating_add(1);
+        }
+    }
+    findings
+}
+
+/// First line of the new file a hunk covers, from the header after `@@ `.
+fn hunk_new_start(header: &str) -> Option<u32> {
+    let new_range = header
+        .split_whitespace()
+        .find(|part| part.starts_with('+'))?;
+    new_range
+        .trim_start_matches('+')
+        .split(',')
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// The rule `line` matches and the matching text.
+fn match_line(line: &str) -> Option<(&'static str, &str)> {
+    for (rule, regex) in COMPILED_RULES.iter() {
+        if let Some(found) = regex.find(line) {
+            return Some((rule, found.as_str()));
+        }
+    }
+    let captures = ASSIGNMENT.as_ref()?.captures(line)?;
+    let value = captures.get(3)?.as_str();
+    (shannon_entropy(value) >= ENTROPY_THRESHOLD).then_some(("High-entropy secret", value))
+}
+
+/// Shannon entropy of `value`, in bits per character.
+fn shannon_entropy(value: &str) -> f64 {
+    let mut counts = std::collections::HashMap::new();
+    for c in value.chars() {
+        *counts.entry(c).or_insert(0_u32) += 1;
+    }
+    let total = f64::from(u32::try_from(value.chars().count()).unwrap_or(u32::MAX));
+    counts
+        .values()
+        .map(|&count| {
+            let p = f64::from(count) / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// `text` with everything but its first four characters masked.
+fn mask(text: &str) -> String {
+    let shown: String = text.chars().take(4).collect();
+    format!("{shown}…")
+}
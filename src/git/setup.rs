@@ -0,0 +1,109 @@
+//! Post-creation setup for new worktrees: submodules and Git LFS content.
+//!
+//! Worktrees are created through libgit2, which neither initializes submodules nor runs the
+//! LFS smudge filter, so repos using either come up with empty submodule directories and LFS
+//! pointer files. Each step can be turned off per repository with git config:
+//! `tenex.worktreeSubmodules` and `tenex.worktreeLfs` (both default to `true`).
+
+use anyhow::{Context, Result, bail};
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+
+/// Setup steps a new worktree needs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WorktreeSetup {
+    /// Run `git submodule update --init --recursive`.
+    pub submodules: bool,
+    /// Run `git lfs pull`.
+    pub lfs: bool,
+}
+
+impl WorktreeSetup {
+    /// Whether there is anything to do.
+    #[must_use]
+    pub const fn is_needed(self) -> bool {
+        self.submodules || self.lfs
+    }
+
+    /// Short description of the steps (for progress labels).
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match (self.submodules, self.lfs) {
+            (true, true) => "submodules and LFS",
+            (true, false) => "submodules",
+            (false, true) => "LFS",
+            (false, false) => "nothing",
+        }
+    }
+}
+
+/// Read a boolean git config `key` in `workdir`, defaulting to `true` when unset.
+fn config_enabled(workdir: &Path, key: &str) -> bool {
+    super::git_command()
+        .args(["config", "--bool", "--get", key])
+        .current_dir(workdir)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .is_none_or(|output| String::from_utf8_lossy(&output.stdout).trim() != "false")
+}
+
+fn uses_lfs(worktree_path: &Path) -> bool {
+    fs::read_to_string(worktree_path.join(".gitattributes"))
+        .is_ok_and(|attributes| attributes.contains("filter=lfs"))
+}
+
+fn lfs_installed(worktree_path: &Path) -> bool {
+    super::git_command()
+        .args(["lfs", "version"])
+        .current_dir(worktree_path)
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// Detect which setup steps the worktree at `worktree_path` needs.
+#[must_use]
+pub fn detect_worktree_setup(worktree_path: &Path) -> WorktreeSetup {
+    let submodules = worktree_path.join(".gitmodules").is_file()
+        && config_enabled(worktree_path, "tenex.worktreeSubmodules");
+    let lfs = uses_lfs(worktree_path)
+        && config_enabled(worktree_path, "tenex.worktreeLfs")
+        && lfs_installed(worktree_path);
+    WorktreeSetup { submodules, lfs }
+}
+
+fn run_step(worktree_path: &Path, args: &[&str], cancelled: &AtomicBool) -> Result<()> {
+    let mut command = super::git_command();
+    command.args(args).current_dir(worktree_path);
+    let output = super::output_cancellable(&mut command, cancelled)
+        .with_context(|| format!("Failed to run git {}", args.join(" ")))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("git {} failed: {}", args.join(" "), stderr.trim());
+    }
+    Ok(())
+}
+
+/// Run the `setup` steps in `worktree_path`, stopping early if `cancelled` is set.
+///
+/// # Errors
+///
+/// Returns an error if a step fails or is cancelled.
+pub fn run_worktree_setup(
+    worktree_path: &Path,
+    setup: WorktreeSetup,
+    cancelled: &AtomicBool,
+) -> Result<()> {
+    if setup.submodules {
+        run_step(
+            worktree_path,
+            &["submodule", "update", "--init", "--recursive"],
+            cancelled,
+        )?;
+    }
+    if setup.lfs {
+        run_step(worktree_path, &["lfs", "pull"], cancelled)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,97 @@
+//! Setting uncommitted changes aside around rebases and merges.
+
+use anyhow::{Context, Result, bail};
+use std::path::Path;
+
+fn run_git(workdir: &Path, args: &[&str]) -> Result<std::process::Output> {
+    super::git_command()
+        .args(args)
+        .current_dir(workdir)
+        .output()
+        .with_context(|| format!("Failed to run git {}", args.join(" ")))
+}
+
+fn rev_parse(workdir: &Path, rev: &str) -> Result<String> {
+    let output = run_git(workdir, &["rev-parse", "--verify", rev])?;
+    if !output.status.success() {
+        bail!("Failed to resolve {rev}");
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Whether `workdir` has staged or unstaged changes to tracked files.
+///
+/// Untracked files are ignored: they don't stop a rebase or merge unless git would overwrite
+/// them, and git reports that case itself.
+///
+/// # Errors
+///
+/// Returns an error if `git status` cannot be run.
+pub fn has_uncommitted_changes(workdir: &Path) -> Result<bool> {
+    let output = run_git(workdir, &["status", "--porcelain", "--untracked-files=no"])?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("git status failed: {}", stderr.trim());
+    }
+    Ok(!output.stdout.iter().all(u8::is_ascii_whitespace))
+}
+
+/// Stash uncommitted changes to tracked files, returning the stash commit id.
+///
+/// Returns `None` when there was nothing to stash.
+///
+/// # Errors
+///
+/// Returns an error if the stash fails.
+pub fn stash_changes(workdir: &Path, message: &str) -> Result<Option<String>> {
+    if !has_uncommitted_changes(workdir)? {
+        return Ok(None);
+    }
+    let output = run_git(workdir, &["stash", "push", "-m", message])?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("git stash failed: {}", stderr.trim());
+    }
+    rev_parse(workdir, "refs/stash").map(Some)
+}
+
+/// Re-apply the stash `stash_id` and drop it from the stash list.
+///
+/// Returns `false` (keeping the stash entry) if applying it failed, for example because it
+/// conflicted with the new commits; conflict markers are then left in the worktree.
+///
+/// # Errors
+///
+/// Returns an error if git cannot be run.
+pub fn restore_stash(workdir: &Path, stash_id: &str) -> Result<bool> {
+    let output = run_git(workdir, &["stash", "apply", stash_id])?;
+    if !output.status.success() {
+        return Ok(false);
+    }
+
+    let list = run_git(workdir, &["stash", "list", "--format=%H"])?;
+    let stdout = String::from_utf8_lossy(&list.stdout);
+    if let Some(index) = stdout.lines().position(|line| line.trim() == stash_id) {
+        let _ = run_git(workdir, &["stash", "drop", &format!("stash@{{{index}}}")]);
+    }
+    Ok(true)
+}
+
+/// Commit uncommitted changes to tracked files as a work-in-progress commit.
+///
+/// Returns the new commit id, or `None` when there was nothing to commit.
+///
+/// # Errors
+///
+/// Returns an error if the commit fails.
+pub fn commit_wip(workdir: &Path, message: &str) -> Result<Option<String>> {
+    if !has_uncommitted_changes(workdir)? {
+        return Ok(None);
+    }
+    let output = run_git(workdir, &["commit", "--all", "--no-verify", "-m", message])?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("git commit failed: {}", stderr.trim());
+    }
+    rev_parse(workdir, "HEAD").map(Some)
+}
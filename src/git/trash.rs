@@ -0,0 +1,60 @@
+//! Trash refs: deleted agent branches kept under `refs/tenex/trash/` until purged.
+
+use anyhow::{Context, Result, bail};
+use std::path::Path;
+
+/// Namespace for trashed branch refs.
+pub const TRASH_REF_PREFIX: &str = "refs/tenex/trash/";
+
+fn update_ref(repo_path: &Path, args: &[&str]) -> Result<()> {
+    let output = super::git_command()
+        .arg("update-ref")
+        .args(args)
+        .current_dir(repo_path)
+        .output()
+        .context("Failed to run git update-ref")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!(
+            "git update-ref {} failed: {}",
+            args.join(" "),
+            stderr.trim()
+        );
+    }
+    Ok(())
+}
+
+/// Keep local branch `branch` reachable as `refs/tenex/trash/<branch>/<stamp>` before it is
+/// deleted. Returns the trash ref, or `None` when the branch does not exist.
+///
+/// # Errors
+///
+/// Returns an error if the ref cannot be written.
+pub fn trash_branch(repo_path: &Path, branch: &str, stamp: i64) -> Result<Option<String>> {
+    let Some(tip) = super::branch_tip(repo_path, branch) else {
+        return Ok(None);
+    };
+    let trash_ref = format!("{TRASH_REF_PREFIX}{branch}/{stamp}");
+    update_ref(repo_path, &[&trash_ref, &tip])?;
+    Ok(Some(trash_ref))
+}
+
+/// Delete trash ref `trash_ref` (a no-op when it is already gone).
+///
+/// # Errors
+///
+/// Returns an error if the ref exists but cannot be deleted.
+pub fn delete_trash_ref(repo_path: &Path, trash_ref: &str) -> Result<()> {
+    if !trash_ref.starts_with(TRASH_REF_PREFIX) {
+        bail!("{trash_ref} is not a Tenex trash ref");
+    }
+    let exists = super::git_command()
+        .args(["rev-parse", "--verify", "--quiet", trash_ref])
+        .current_dir(repo_path)
+        .output()
+        .is_ok_and(|output| output.status.success());
+    if !exists {
+        return Ok(());
+    }
+    update_ref(repo_path, &["-d", trash_ref])
+}
@@ -9,6 +9,30 @@ use tracing::{debug, info, warn};
 
 const LOCAL_INSTRUCTION_FILE_NAMES: &[&str] = &["AGENTS.md", "CLAUDE.md"];
 
+fn run_git<I, S>(workdir: &Path, args: I) -> Result<()>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<std::ffi::OsStr>,
+{
+    let args: Vec<S> = args.into_iter().collect();
+    let output = super::git_command()
+        .args(&args)
+        .current_dir(workdir)
+        .stdin(Stdio::null())
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        let command = args
+            .iter()
+            .map(|arg| arg.as_ref().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("git {command} failed: {}", stderr.trim());
+    }
+    Ok(())
+}
+
 fn remove_dir_all_with_retries(path: &Path) -> Result<()> {
     if !path.exists() {
         return Ok(());
@@ -335,14 +359,19 @@ impl<'a> Manager<'a> {
     ///
     /// # Errors
     ///
-    /// Returns an error when the path already exists but is not a safe Tenex-owned
-    /// stale worktree target, or when stale cleanup fails.
+    /// Returns an error when the path resolves inside the main checkout, when it
+    /// already exists but is not a safe Tenex-owned stale worktree target, or when
+    /// stale cleanup fails.
     pub fn prepare_worktree_creation_target(
         &self,
         path: &Path,
         branch: &str,
         worktree_root: &Path,
     ) -> Result<TargetPreparation> {
+        if let Some(workdir) = self.repo.workdir() {
+            super::ensure_outside_main_checkout(path, workdir)?;
+        }
+
         if let Some(registered_path) = self.worktree_path(branch) {
             return Ok(TargetPreparation::registered(registered_path));
         }
@@ -554,6 +583,13 @@ impl<'a> Manager<'a> {
         // Worktree name cannot contain slashes (it becomes a directory name in .git/worktrees/)
         let worktree_name = branch.replace('/', "-");
 
+        let sparse_paths = self.sparse_checkout_paths();
+        if !sparse_paths.is_empty() {
+            self.add_sparse_worktree(&worktree_name, path, branch, &sparse_paths)?;
+            self.finish_worktree_create(path, options);
+            return Ok(());
+        }
+
         let mut add_opts = git2::WorktreeAddOptions::new();
         add_opts.reference(Some(&reference));
 
@@ -611,6 +647,98 @@ impl<'a> Manager<'a> {
         Ok(())
     }
 
+    /// Directories to materialize in new worktrees, from the repository's
+    /// `tenex.sparseCheckout` git config (one value per directory).
+    ///
+    /// Empty when sparse checkout is not configured, in which case worktrees are complete.
+    #[must_use]
+    pub fn sparse_checkout_paths(&self) -> Vec<String> {
+        let mut paths = Vec::new();
+        let Ok(config) = self.repo.config() else {
+            return paths;
+        };
+        let Ok(entries) = config.multivar("tenex.sparseCheckout", None) else {
+            return paths;
+        };
+        let _ = entries.for_each(|entry| {
+            if let Some(value) = entry.value().map(str::trim)
+                && !value.is_empty()
+            {
+                paths.push(value.to_string());
+            }
+        });
+        paths
+    }
+
+    /// Add a cone-mode sparse worktree for `branch` at `path` with the git CLI.
+    ///
+    /// libgit2 cannot skip the initial checkout, so the worktree is added without one, the
+    /// sparse patterns are set, and only then are files written. It is added in a staging
+    /// directory named `worktree_name` and moved into place so its admin directory name matches
+    /// the one libgit2 would have used (removal looks worktrees up by that name).
+    fn add_sparse_worktree(
+        &self,
+        worktree_name: &str,
+        path: &Path,
+        branch: &str,
+        sparse_paths: &[String],
+    ) -> Result<()> {
+        let repo_root = self
+            .repo
+            .workdir()
+            .context("Repository has no working directory")?;
+        let staging_dir = path
+            .parent()
+            .unwrap_or(repo_root)
+            .join(format!(".{worktree_name}.sparse-staging"));
+        let staged_path = staging_dir.join(worktree_name);
+
+        debug!(branch, ?path, ?sparse_paths, "Creating sparse worktree");
+
+        let result = (|| {
+            let _ = remove_dir_all_with_retries(&staging_dir);
+            fs::create_dir_all(&staging_dir).with_context(|| {
+                format!(
+                    "Failed to create staging directory {}",
+                    staging_dir.display()
+                )
+            })?;
+
+            run_git(
+                repo_root,
+                [
+                    "worktree".as_ref(),
+                    "add".as_ref(),
+                    "--no-checkout".as_ref(),
+                    staged_path.as_os_str(),
+                    branch.as_ref(),
+                ],
+            )?;
+            if staged_path != path {
+                run_git(
+                    repo_root,
+                    [
+                        "worktree".as_ref(),
+                        "move".as_ref(),
+                        staged_path.as_os_str(),
+                        path.as_os_str(),
+                    ],
+                )?;
+            }
+
+            run_git(
+                path,
+                ["sparse-checkout", "set", "--cone"]
+                    .into_iter()
+                    .chain(sparse_paths.iter().map(String::as_str)),
+            )?;
+            run_git(path, ["read-tree", "-mu", "HEAD"])
+        })();
+
+        let _ = fs::remove_dir_all(&staging_dir);
+        result.with_context(|| format!("Failed to create sparse worktree at {}", path.display()))
+    }
+
     /// Create a worktree with a new branch from HEAD
     ///
     /// If the branch already exists (e.g., from a previous run), it will be deleted
@@ -678,13 +806,18 @@ impl<'a> Manager<'a> {
 
         let reference = branch_ref.into_reference();
 
-        self.repo
-            .worktree(
-                &worktree_name,
-                path,
-                Some(git2::WorktreeAddOptions::new().reference(Some(&reference))),
-            )
-            .with_context(|| format!("Failed to create worktree at {}", path.display()))?;
+        let sparse_paths = self.sparse_checkout_paths();
+        if sparse_paths.is_empty() {
+            self.repo
+                .worktree(
+                    &worktree_name,
+                    path,
+                    Some(git2::WorktreeAddOptions::new().reference(Some(&reference))),
+                )
+                .with_context(|| format!("Failed to create worktree at {}", path.display()))?;
+        } else {
+            self.add_sparse_worktree(&worktree_name, path, branch, &sparse_paths)?;
+        }
 
         self.finish_worktree_create(path, options);
 
@@ -1099,6 +1232,18 @@ impl<'a> Manager<'a> {
         Ok((branch_name, short_hash))
     }
 
+    /// Name of the branch checked out in the main repository, if HEAD is on a branch.
+    ///
+    /// New worktree branches start from this commit, so it is their base branch.
+    #[must_use]
+    pub fn head_branch(&self) -> Option<String> {
+        let head = self.repo.head().ok()?;
+        if !head.is_branch() {
+            return None;
+        }
+        head.shorthand().map(str::to_string)
+    }
+
     /// Get the HEAD commit information for an existing worktree
     ///
     /// Returns (`branch_name`, `short_commit_hash`) if the worktree exists and has a valid HEAD
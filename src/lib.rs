@@ -11,17 +11,25 @@ pub mod conversation;
 
 pub mod action;
 pub mod agent;
+pub mod api;
 pub mod app;
 pub mod cli;
+pub mod compression;
 pub mod config;
+pub mod error;
 pub mod git;
+pub mod metrics;
 pub mod migration;
 pub mod mux;
 pub mod paths;
+pub mod plugin;
 pub mod prompts;
 pub mod release_notes;
+pub mod rpc;
 pub(crate) mod runtime;
 pub mod state;
+pub mod text_width;
+pub mod tmux;
 
 pub mod tui;
 pub mod update;
@@ -29,6 +37,7 @@ pub mod update;
 pub use agent::{Agent, Status};
 pub use app::{App, Tab};
 pub use config::Config;
+pub use error::TenexError;
 pub use state::AppMode;
 
 /// Best-effort cleanup for runtime resources owned by this agent.
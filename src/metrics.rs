@@ -0,0 +1,237 @@
+//! Local usage metrics: counts of actions, how long agents take to spawn, and how often things
+//! fail.
+//!
+//! Metrics are off unless the `metrics` setting is on. They are kept in `metrics.json` in the
+//! instance directory and never leave the machine; `tenex report --metrics` summarizes them.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::config::Config;
+
+/// How often recorded metrics are written to the file while Tenex runs.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The metrics being recorded, while recording is on.
+static RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+
+#[derive(Debug)]
+struct Recorder {
+    path: PathBuf,
+    metrics: Metrics,
+    dirty: bool,
+    last_flush: Instant,
+}
+
+/// Everything recorded since the metrics file was started.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Metrics {
+    /// When recording started.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<DateTime<Utc>>,
+    /// Times each action or slash command was run, by name.
+    #[serde(default)]
+    pub actions: BTreeMap<String, u64>,
+    /// Errors shown to the user.
+    #[serde(default)]
+    pub errors: u64,
+    /// Agents created from the new agent prompt.
+    #[serde(default)]
+    pub spawns: SpawnMetrics,
+}
+
+/// How long creating an agent took, and how often it failed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpawnMetrics {
+    /// Agents created.
+    #[serde(default)]
+    pub count: u64,
+    /// Attempts that failed.
+    #[serde(default)]
+    pub failures: u64,
+    /// Time spent creating the agents that were created, in milliseconds.
+    #[serde(default)]
+    pub total_ms: u64,
+    /// Longest time creating one agent took, in milliseconds.
+    #[serde(default)]
+    pub max_ms: u64,
+}
+
+impl Metrics {
+    /// Actions run, in total.
+    #[must_use]
+    pub fn action_count(&self) -> u64 {
+        self.actions
+            .values()
+            .fold(0, |total, count| total.saturating_add(*count))
+    }
+
+    /// A summary for `tenex report --metrics`, most-run actions first.
+    #[must_use]
+    pub fn report(&self) -> String {
+        let mut report = String::new();
+        if let Some(since) = self.since {
+            let _ = writeln!(report, "Since {}", since.format("%Y-%m-%d %H:%M UTC"));
+        }
+
+        let actions = self.action_count();
+        let _ = writeln!(report, "\nActions: {actions}");
+        let mut by_count: Vec<(&String, &u64)> = self.actions.iter().collect();
+        by_count.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        for (name, count) in by_count {
+            let _ = writeln!(report, "  {count:>6}  {name}");
+        }
+        let _ = writeln!(
+            report,
+            "\nErrors: {} ({} of actions)",
+            self.errors,
+            percent(self.errors, actions)
+        );
+
+        let spawns = self.spawns;
+        let attempts = spawns.count.saturating_add(spawns.failures);
+        let _ = writeln!(
+            report,
+            "\nSpawns: {} created, {} failed ({} of attempts)",
+            spawns.count,
+            spawns.failures,
+            percent(spawns.failures, attempts)
+        );
+        if let Some(average_ms) = spawns.total_ms.checked_div(spawns.count) {
+            let _ = writeln!(
+                report,
+                "  average {}, longest {}",
+                seconds(average_ms),
+                seconds(spawns.max_ms)
+            );
+        }
+        report
+    }
+}
+
+fn percent(part: u64, whole: u64) -> String {
+    if whole == 0 {
+        return "0%".to_string();
+    }
+    format!("{}%", part.saturating_mul(100) / whole)
+}
+
+fn seconds(ms: u64) -> String {
+    format!("{}.{}s", ms / 1000, (ms % 1000) / 100)
+}
+
+/// Location of the metrics file of the current instance.
+#[must_use]
+pub fn metrics_path() -> PathBuf {
+    Config::instance_root().join("metrics.json")
+}
+
+/// Read the metrics file at `path`; a missing file has no metrics.
+///
+/// # Errors
+///
+/// Returns an error if the file exists but cannot be read or parsed.
+pub fn load(path: &Path) -> Result<Metrics> {
+    if !path.exists() {
+        return Ok(Metrics::default());
+    }
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&contents).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// Start recording to the metrics file, adding to what it holds, or stop and write what was
+/// recorded.
+pub fn set_enabled(enabled: bool) {
+    let mut recorder = RECORDER.lock();
+    if !enabled {
+        if let Some(mut recorder) = recorder.take() {
+            recorder.flush();
+        }
+        return;
+    }
+    if recorder.is_some() {
+        return;
+    }
+    let path = metrics_path();
+    let mut metrics = load(&path).unwrap_or_else(|err| {
+        warn!(error = %err, "Starting a new metrics file");
+        Metrics::default()
+    });
+    metrics.since.get_or_insert_with(Utc::now);
+    *recorder = Some(Recorder {
+        path,
+        metrics,
+        dirty: false,
+        last_flush: Instant::now(),
+    });
+}
+
+/// Write what was recorded since the last write.
+pub fn flush() {
+    if let Some(recorder) = RECORDER.lock().as_mut() {
+        recorder.flush();
+    }
+}
+
+/// Count a run of the action or slash command `name`.
+pub fn record_action(name: &str) {
+    update(|metrics| {
+        let count = metrics.actions.entry(name.to_string()).or_default();
+        *count = count.saturating_add(1);
+    });
+}
+
+/// Count an error shown to the user.
+pub fn record_error() {
+    update(|metrics| metrics.errors = metrics.errors.saturating_add(1));
+}
+
+/// Record an attempt to create an agent that took `elapsed` and succeeded or not.
+pub fn record_spawn(elapsed: Duration, succeeded: bool) {
+    update(|metrics| {
+        let spawns = &mut metrics.spawns;
+        if !succeeded {
+            spawns.failures = spawns.failures.saturating_add(1);
+            return;
+        }
+        let ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
+        spawns.count = spawns.count.saturating_add(1);
+        spawns.total_ms = spawns.total_ms.saturating_add(ms);
+        spawns.max_ms = spawns.max_ms.max(ms);
+    });
+}
+
+fn update(change: impl FnOnce(&mut Metrics)) {
+    if let Some(recorder) = RECORDER.lock().as_mut() {
+        change(&mut recorder.metrics);
+        recorder.dirty = true;
+        if recorder.last_flush.elapsed() >= FLUSH_INTERVAL {
+            recorder.flush();
+        }
+    }
+}
+
+impl Recorder {
+    fn flush(&mut self) {
+        self.last_flush = Instant::now();
+        if !self.dirty {
+            return;
+        }
+        let result = serde_json::to_string_pretty(&self.metrics)
+            .map_err(anyhow::Error::from)
+            .and_then(|contents| Ok(std::fs::write(&self.path, contents)?));
+        match result {
+            Ok(()) => self.dirty = false,
+            Err(err) => warn!(path = %self.path.display(), error = %err, "Failed to write metrics"),
+        }
+    }
+}
@@ -11,6 +11,9 @@ use std::sync::{Arc, OnceLock};
 use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::{debug, warn};
 
+use super::protocol::MuxEvent;
+use super::server::events;
+
 /// Default PTY rows when no UI size is known.
 pub const DEFAULT_ROWS: u16 = 24;
 /// Default PTY columns when no UI size is known.
@@ -42,20 +45,46 @@ pub struct MuxSession {
     pub name: String,
     /// Unix timestamp when the session was created.
     pub created: i64,
-    /// Number of times the root window has been restarted after exiting.
-    pub root_restart_attempts: u32,
-    /// Unix timestamp of the most recent root window restart.
-    pub last_root_restart: i64,
-    /// Windows in index order (index 0 is the root window).
+    /// State file of the Tenex instance that created the session.
+    pub owner: Option<String>,
+    /// Windows in creation order (the first is the root window, index 0).
     pub windows: Vec<Arc<Mutex<MuxWindow>>>,
+    /// Index given to the next window created in this session.
+    ///
+    /// Indices are never reused or renumbered, so a window keeps its index (and clients' targets
+    /// stay valid) when other windows are killed.
+    pub next_window_index: u32,
+}
+
+impl MuxSession {
+    /// Position in `windows` of the window with `index`.
+    #[must_use]
+    pub fn window_position(&self, index: u32) -> Option<usize> {
+        self.windows
+            .iter()
+            .position(|window| window.lock().index == index)
+    }
+
+    /// The window with `index`.
+    #[must_use]
+    pub fn window(&self, index: u32) -> Option<Arc<Mutex<MuxWindow>>> {
+        self.window_position(index)
+            .and_then(|position| self.windows.get(position).cloned())
+    }
 }
 
 /// A window with its PTY and terminal state.
 pub struct MuxWindow {
+    /// Name of the session the window belongs to.
+    pub session: String,
     /// Window index within the session.
     pub index: u32,
     /// Window name.
     pub name: String,
+    /// Descriptive title set by Tenex (empty when unset).
+    pub title: String,
+    /// Title the program last set with OSC 0/2 (empty when unset).
+    pub pane_title: String,
     /// Working directory for the spawned process.
     pub working_dir: PathBuf,
     /// Command argv used to spawn the process.
@@ -72,16 +101,37 @@ pub struct MuxWindow {
     pub output_history: OutputHistory,
     /// Current PTY size.
     pub size: PtySize,
+    /// Exit code of the process, once it has exited.
+    pub exit_code: Option<u32>,
+}
+
+impl MuxWindow {
+    /// Exit code of the window's process, or `None` while it runs.
+    ///
+    /// The window and its terminal state are kept after the process exits, so its final
+    /// output stays readable until the window is killed.
+    pub fn exit_code(&mut self) -> Option<u32> {
+        if self.exit_code.is_none()
+            && let Ok(Some(status)) = self.child.try_wait()
+        {
+            self.exit_code = Some(status.exit_code());
+        }
+        self.exit_code
+    }
 }
 
 impl std::fmt::Debug for MuxWindow {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("MuxWindow")
+            .field("session", &self.session)
             .field("index", &self.index)
             .field("name", &self.name)
+            .field("title", &self.title)
+            .field("pane_title", &self.pane_title)
             .field("working_dir", &self.working_dir)
             .field("command", &self.command)
             .field("size", &self.size)
+            .field("exit_code", &self.exit_code)
             .finish_non_exhaustive()
     }
 }
@@ -332,15 +382,10 @@ pub fn resolve_window(target: &str) -> Result<Arc<Mutex<MuxWindow>>> {
             .ok_or_else(|| anyhow::anyhow!("Session '{}' not found", parsed.session))?
     };
 
-    let window = {
-        let session_guard = session.lock();
-        let idx = parsed.window_index as usize;
-        session_guard
-            .windows
-            .get(idx)
-            .cloned()
-            .ok_or_else(|| anyhow::anyhow!("Window '{}' not found", parsed.window_index))?
-    };
+    let window = session
+        .lock()
+        .window(parsed.window_index)
+        .ok_or_else(|| anyhow::anyhow!("Window '{}' not found", parsed.window_index))?;
 
     Ok(window)
 }
@@ -351,6 +396,7 @@ pub fn resolve_window(target: &str) -> Result<Arc<Mutex<MuxWindow>>> {
 ///
 /// Returns an error if the PTY or child process cannot be created.
 pub fn spawn_window(
+    session: &str,
     index: u32,
     window_name: &str,
     working_dir: &Path,
@@ -382,8 +428,11 @@ pub fn spawn_window(
     let input = WindowInput::new(window_input_label(window_name, index), writer);
 
     let window = Arc::new(Mutex::new(MuxWindow {
+        session: session.to_string(),
         index,
         name: window_name.to_string(),
+        title: String::new(),
+        pane_title: String::new(),
         working_dir: working_dir.to_path_buf(),
         command: command_vec,
         master: pair.master,
@@ -392,6 +441,7 @@ pub fn spawn_window(
         parser,
         output_history: OutputHistory::default(),
         size,
+        exit_code: None,
     }));
 
     spawn_reader_thread(window.clone(), reader);
@@ -439,26 +489,14 @@ fn spawn_reader_thread_inner(
 
                 if cpr_queries == 0 && da_queries == 0 && osc10_queries == 0 && osc11_queries == 0 {
                     let mut guard = window.lock();
-                    guard.parser.process(chunk);
-                    let checkpoint_bytes = if guard.output_history.should_checkpoint(chunk.len()) {
-                        Some(guard.parser.screen().state_formatted())
-                    } else {
-                        None
-                    };
-                    guard.output_history.record(chunk, checkpoint_bytes);
+                    process_output(&mut guard, chunk);
                     drop(guard);
                     continue;
                 }
 
                 {
                     let mut guard = window.lock();
-                    guard.parser.process(chunk);
-                    let checkpoint_bytes = if guard.output_history.should_checkpoint(chunk.len()) {
-                        Some(guard.parser.screen().state_formatted())
-                    } else {
-                        None
-                    };
-                    guard.output_history.record(chunk, checkpoint_bytes);
+                    process_output(&mut guard, chunk);
                     respond_to_terminal_queries(
                         &guard,
                         cpr_queries,
@@ -469,10 +507,65 @@ fn spawn_reader_thread_inner(
                     drop(guard);
                 }
             }
+
+            let code = wait_for_exit_code(&window);
+            let (session, window) = {
+                let guard = window.lock();
+                (guard.session.clone(), guard.index)
+            };
+            events::publish(|| MuxEvent::Exited {
+                session,
+                window,
+                code,
+            });
         });
     })
 }
 
+/// Exit code of the window's process after its PTY closed.
+///
+/// The PTY can close shortly before the process can be reaped, so this polls for a moment
+/// before giving up.
+fn wait_for_exit_code(window: &Mutex<MuxWindow>) -> Option<u32> {
+    const ATTEMPTS: u32 = 50;
+    const INTERVAL: std::time::Duration = std::time::Duration::from_millis(20);
+
+    for _ in 0..ATTEMPTS {
+        let code = window.lock().exit_code();
+        if code.is_some() {
+            return code;
+        }
+        std::thread::sleep(INTERVAL);
+    }
+    None
+}
+
+/// Feed `chunk` through the window's terminal parser and output history, and tell subscribers
+/// about the new output and any title the program set.
+fn process_output(window: &mut MuxWindow, chunk: &[u8]) {
+    window.parser.process(chunk);
+    let checkpoint_bytes = if window.output_history.should_checkpoint(chunk.len()) {
+        Some(window.parser.screen().state_formatted())
+    } else {
+        None
+    };
+    window.output_history.record(chunk, checkpoint_bytes);
+    events::publish(|| MuxEvent::Output {
+        session: window.session.clone(),
+        window: window.index,
+        end: window.output_history.seq_end,
+    });
+
+    if window.parser.screen().title() != window.pane_title {
+        window.pane_title = window.parser.screen().title().to_string();
+        events::publish(|| MuxEvent::TitleChanged {
+            session: window.session.clone(),
+            window: window.index,
+            title: window.pane_title.clone(),
+        });
+    }
+}
+
 fn scan_terminal_queries(
     scan_buf: &mut Vec<u8>,
     tail: &[u8],
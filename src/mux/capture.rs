@@ -1,12 +1,25 @@
 //! Mux output capture (client-side).
 
 use super::protocol::{CaptureKind, MuxRequest, MuxResponse};
-use anyhow::{Result, bail};
+use anyhow::{Context, Result, bail};
 
 /// Capture output from mux sessions.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct Capture;
 
+/// Everything the preview shows for a pane, fetched in one round trip.
+#[derive(Debug, Clone, Default)]
+pub struct PaneSnapshot {
+    /// Captured content with ANSI color codes.
+    pub content: String,
+    /// Cursor column, row, and whether it is hidden.
+    pub cursor_position: Option<(u16, u16, bool)>,
+    /// Pane columns and rows.
+    pub size: Option<(u16, u16)>,
+    /// Title the pane's program last set with OSC 0/2 (empty when unset).
+    pub title: Option<String>,
+}
+
 impl Capture {
     /// Create a new output capture instance.
     #[must_use]
@@ -41,6 +54,64 @@ impl Capture {
         self.capture(target, CaptureKind::FullHistory)
     }
 
+    /// Capture the pane's content, cursor, size, and title in one round trip.
+    ///
+    /// `history_lines` of scrollback are captured, or all of it when `None`. Parts that cannot
+    /// be retrieved are left empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mux daemon cannot be reached.
+    pub fn capture_snapshot(
+        &self,
+        target: &str,
+        history_lines: Option<u32>,
+    ) -> Result<PaneSnapshot> {
+        let _ = self;
+        let kind = history_lines.map_or(CaptureKind::FullHistory, |lines| CaptureKind::History {
+            lines,
+        });
+        let target = target.to_string();
+        let responses = super::client::request_batch(&[
+            MuxRequest::Capture {
+                target: target.clone(),
+                kind,
+                raw: true,
+            },
+            MuxRequest::CursorPosition {
+                target: target.clone(),
+            },
+            MuxRequest::PaneSize {
+                target: target.clone(),
+            },
+            MuxRequest::PaneTitle { target },
+        ])?;
+
+        let mut responses = responses.into_iter();
+        let content = responses
+            .next()
+            .and_then(|response| captured_text(response).ok())
+            .unwrap_or_default();
+        let cursor_position = match responses.next() {
+            Some(MuxResponse::Position { x, y, hidden }) => Some((x, y, hidden)),
+            _ => None,
+        };
+        let size = match responses.next() {
+            Some(MuxResponse::Size { cols, rows }) => Some((cols, rows)),
+            _ => None,
+        };
+        let title = match responses.next() {
+            Some(MuxResponse::Text { text }) => Some(text),
+            _ => None,
+        };
+        Ok(PaneSnapshot {
+            content,
+            cursor_position,
+            size,
+            title,
+        })
+    }
+
     /// Get the current pane size.
     ///
     /// # Errors
@@ -86,6 +157,21 @@ impl Capture {
         }
     }
 
+    /// Return the title the pane's program last set with OSC 0/2 (empty when unset).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the title cannot be retrieved.
+    pub fn pane_title(&self, target: &str) -> Result<String> {
+        match super::client::request(&MuxRequest::PaneTitle {
+            target: target.to_string(),
+        })? {
+            MuxResponse::Text { text } => Ok(text),
+            MuxResponse::Err { message } => bail!("{message}"),
+            other => bail!("Unexpected response: {other:?}"),
+        }
+    }
+
     /// Get the last N non-empty lines from the pane.
     ///
     /// # Errors
@@ -106,13 +192,22 @@ impl Capture {
 
     fn capture(self, target: &str, kind: CaptureKind) -> Result<String> {
         let _ = self;
-        match super::client::request(&MuxRequest::Capture {
+        captured_text(super::client::request(&MuxRequest::Capture {
             target: target.to_string(),
             kind,
-        })? {
-            MuxResponse::Text { text } => Ok(text),
-            MuxResponse::Err { message } => bail!("{message}"),
-            other => bail!("Unexpected response: {other:?}"),
+            raw: true,
+        })?)
+    }
+}
+
+/// The text of a capture response, whether it came as JSON or in a binary frame.
+fn captured_text(response: MuxResponse) -> Result<String> {
+    match response {
+        MuxResponse::Text { text } => Ok(text),
+        MuxResponse::RawText { text } => {
+            String::from_utf8(text).context("Mux capture is not valid UTF-8")
         }
+        MuxResponse::Err { message } => bail!("{message}"),
+        other => bail!("Unexpected response: {other:?}"),
     }
 }
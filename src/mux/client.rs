@@ -2,19 +2,25 @@
 
 use super::endpoint::{SocketEndpoint, socket_endpoint};
 use super::ipc;
-use super::protocol::{MuxRequest, MuxResponse};
-use anyhow::{Context, Result};
+use super::protocol::{MuxEvent, MuxRequest, MuxResponse};
+use crate::error::TenexError;
+use anyhow::{Context, Result, bail};
 use interprocess::local_socket::Stream;
 use interprocess::local_socket::traits::Stream as StreamTrait;
 use parking_lot::Mutex;
+use tracing::debug;
 
 use std::path::PathBuf;
 use std::process::Command;
 use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
 static CLIENT: OnceLock<Mutex<MuxClient>> = OnceLock::new();
 
+/// Set once the daemon failed a batch, so later batches go one request at a time.
+static BATCH_UNSUPPORTED: AtomicBool = AtomicBool::new(false);
+
 static ENDPOINT: OnceLock<SocketEndpoint> = OnceLock::new();
 
 const DAEMON_CONNECT_RETRY_INTERVAL: Duration = Duration::from_millis(25);
@@ -29,6 +35,11 @@ const DAEMON_CONNECT_RETRY_ATTEMPTS: usize = 200;
 ///
 /// Returns an error if the daemon cannot be reached or the request fails.
 pub fn request(req: &MuxRequest) -> Result<MuxResponse> {
+    #[cfg(feature = "mock-mux")]
+    if let Some(response) = super::mock::handle(req) {
+        return Ok(response);
+    }
+
     let endpoint = endpoint()?;
 
     {
@@ -38,6 +49,112 @@ pub fn request(req: &MuxRequest) -> Result<MuxResponse> {
     }
 }
 
+/// Send several requests to the mux daemon in one round trip.
+///
+/// Responses come back in request order; a request that fails gets a [`MuxResponse::Err`] in
+/// its place. A daemon that predates batching gets the requests one at a time.
+///
+/// # Errors
+///
+/// Returns an error if the daemon cannot be reached.
+pub fn request_batch(requests: &[MuxRequest]) -> Result<Vec<MuxResponse>> {
+    let batched = requests.len() > 1;
+    let mut batch_failed = false;
+    if batched && !BATCH_UNSUPPORTED.load(Ordering::Relaxed) {
+        let expected = requests.len();
+        match request(&MuxRequest::Batch {
+            requests: requests.to_vec(),
+        }) {
+            Ok(MuxResponse::Batch { responses }) if responses.len() == expected => {
+                return Ok(responses);
+            }
+            Ok(other) => {
+                debug!(?other, "Mux daemon does not support batches");
+                BATCH_UNSUPPORTED.store(true, Ordering::Relaxed);
+            }
+            // An older daemon drops the connection on a request it cannot decode; only treat
+            // that as missing support if the requests then go through one at a time.
+            Err(err) => {
+                debug!(error = %err, "Mux batch failed; retrying requests one at a time");
+                batch_failed = true;
+            }
+        }
+    }
+
+    let mut reached = false;
+    let mut last_err = None;
+    let mut responses = Vec::with_capacity(requests.len());
+    for req in requests {
+        match request(req) {
+            Ok(response) => {
+                reached = true;
+                responses.push(response);
+            }
+            Err(err) => {
+                responses.push(MuxResponse::Err {
+                    message: format!("{err:#}"),
+                });
+                last_err = Some(err);
+            }
+        }
+    }
+    if !reached && let Some(err) = last_err {
+        return Err(err);
+    }
+    if batch_failed {
+        BATCH_UNSUPPORTED.store(true, Ordering::Relaxed);
+    }
+    Ok(responses)
+}
+
+/// Subscribe to session events from the running mux daemon.
+///
+/// Unlike [`request`], this does not start the daemon: there is nothing to observe without one.
+///
+/// # Errors
+///
+/// Returns an error if no daemon is listening or it rejects the subscription.
+pub fn subscribe() -> Result<EventStream> {
+    let endpoint = endpoint()?;
+    let mut stream = Stream::connect(endpoint.name.clone())
+        .with_context(|| format!("No mux daemon is listening at {}", endpoint.display))?;
+    match send_request(&mut stream, &MuxRequest::Subscribe)? {
+        MuxResponse::Ok => Ok(EventStream {
+            stream,
+            done: false,
+        }),
+        MuxResponse::Err { message } => bail!(message),
+        other => bail!("Unexpected mux response: {other:?}"),
+    }
+}
+
+/// Events streamed by the mux daemon after [`subscribe`].
+///
+/// Iteration blocks until the next event and ends after the first error (usually the daemon
+/// exiting).
+#[derive(Debug)]
+pub struct EventStream {
+    stream: Stream,
+    done: bool,
+}
+
+impl Iterator for EventStream {
+    type Item = Result<MuxEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let event = match ipc::read_json::<MuxResponse>(&mut self.stream) {
+            Ok(MuxResponse::Event { event }) => Ok(event),
+            Ok(other) => Err(anyhow::anyhow!("Unexpected mux response: {other:?}")),
+            Err(err) => Err(err),
+        };
+        self.done = event.is_err();
+        Some(event)
+    }
+}
+
 pub(super) fn endpoint() -> Result<SocketEndpoint> {
     {
         if let Some(endpoint) = ENDPOINT.get() {
@@ -103,10 +220,10 @@ impl MuxClient {
                 }
 
                 if self.stream.is_none() {
-                    return Err(anyhow::anyhow!(
-                        "Failed to connect to mux daemon at {}",
-                        self.endpoint.display
-                    ));
+                    return Err(TenexError::MuxDead {
+                        endpoint: self.endpoint.display.clone(),
+                    }
+                    .into());
                 }
             }
         }
@@ -119,7 +236,7 @@ impl MuxClient {
 
 fn send_request(stream: &mut Stream, req: &MuxRequest) -> Result<MuxResponse> {
     ipc::write_json(stream, req)?;
-    ipc::read_json(stream)
+    ipc::read_response(stream)
 }
 
 fn start_daemon(endpoint: &SocketEndpoint) -> Result<()> {
@@ -2,7 +2,7 @@
 
 use super::endpoint::SocketEndpoint;
 use super::ipc;
-use super::protocol::{CaptureKind, MuxRequest, MuxResponse, SessionInfo, WindowInfo};
+use super::protocol::{CaptureKind, MuxEvent, MuxRequest, MuxResponse, SessionInfo, WindowInfo};
 use anyhow::{Context, Result, bail};
 use base64::Engine as _;
 use interprocess::local_socket::traits::{ListenerExt, Stream as StreamTrait};
@@ -115,6 +115,10 @@ fn handle_connection(stream: &mut dyn ReadWrite) -> Result<()> {
             }
         };
 
+        if matches!(request, MuxRequest::Subscribe) {
+            return stream_events(stream);
+        }
+
         let response = match dispatch_request(request) {
             Ok(response) => response,
             Err(err) => MuxResponse::Err {
@@ -122,7 +126,7 @@ fn handle_connection(stream: &mut dyn ReadWrite) -> Result<()> {
             },
         };
 
-        ipc::write_json(stream, &response)?;
+        ipc::write_response(stream, &response)?;
     }
 }
 
@@ -135,8 +139,9 @@ fn dispatch_request(request: MuxRequest) -> Result<MuxResponse> {
             name,
             working_dir,
             command,
+            owner,
             ..
-        } => handle_create_session(&name, &working_dir, &command),
+        } => handle_create_session(&name, &working_dir, &command, owner),
         MuxRequest::KillSession { name } => handle_kill_session(&name),
         MuxRequest::RenameSession { old_name, new_name } => {
             handle_rename_session(&old_name, &new_name)
@@ -153,6 +158,7 @@ fn dispatch_request(request: MuxRequest) -> Result<MuxResponse> {
             session,
             window_index,
         } => handle_kill_window(&session, window_index),
+        MuxRequest::RespawnWindow { target, command } => handle_respawn_window(&target, &command),
         MuxRequest::RenameWindow {
             session,
             window_index,
@@ -160,13 +166,22 @@ fn dispatch_request(request: MuxRequest) -> Result<MuxResponse> {
         } => handle_rename_window(&session, window_index, &new_name),
         MuxRequest::Resize { target, cols, rows } => handle_resize(&target, cols, rows),
         MuxRequest::SendInput { target, data } => handle_send_input(&target, &data),
-        MuxRequest::Capture { target, kind } => {
+        MuxRequest::Capture { target, kind, raw } => {
             let content = handle_capture(&target, kind)?;
-            Ok(MuxResponse::Text { text: content })
+            if raw {
+                Ok(MuxResponse::RawText {
+                    text: content.into_bytes(),
+                })
+            } else {
+                Ok(MuxResponse::Text { text: content })
+            }
         }
         MuxRequest::PaneSize { target } => handle_pane_size(&target),
         MuxRequest::CursorPosition { target } => handle_cursor_position(&target),
         MuxRequest::PaneCurrentCommand { target } => handle_pane_current_command(&target),
+        MuxRequest::WindowAlive { target } => handle_window_alive(&target),
+        MuxRequest::PaneTitle { target } => handle_pane_title(&target),
+        MuxRequest::SetTitle { target, title } => handle_set_title(&target, &title),
         MuxRequest::Tail { target, lines } => {
             let content = handle_tail(&target, lines)?;
             Ok(MuxResponse::Text { text: content })
@@ -175,10 +190,73 @@ fn dispatch_request(request: MuxRequest) -> Result<MuxResponse> {
             target,
             after,
             max_bytes,
-        } => handle_read_output(&target, after, max_bytes),
+            raw,
+        } => handle_read_output(&target, after, max_bytes, raw),
         MuxRequest::OutputCursor { target } => handle_output_cursor(&target),
         MuxRequest::ListPanePids { session } => handle_list_pids(&session),
+        MuxRequest::ProcessUsages { targets } => handle_process_usages(&targets),
+        MuxRequest::FreezeProcesses { target, frozen } => handle_freeze_processes(&target, frozen),
+        MuxRequest::Subscribe => bail!("Subscribe must be handled by the connection loop"),
+        MuxRequest::Batch { requests } => Ok(handle_batch(requests)),
+    }
+}
+
+fn handle_batch(requests: Vec<MuxRequest>) -> MuxResponse {
+    let responses = requests
+        .into_iter()
+        .map(|request| {
+            let result = match request {
+                MuxRequest::Batch { .. } => Err(anyhow::anyhow!("Batches cannot be nested")),
+                MuxRequest::Subscribe => Err(anyhow::anyhow!("Subscribe cannot be batched")),
+                request => dispatch_request(request),
+            };
+            result.unwrap_or_else(|err| MuxResponse::Err {
+                message: err.to_string(),
+            })
+        })
+        .collect();
+    MuxResponse::Batch { responses }
+}
+
+/// Stream events to a subscribed connection until the client goes away.
+fn stream_events(stream: &mut dyn ReadWrite) -> Result<()> {
+    let events = super::server::events::subscribe();
+    ipc::write_json(stream, &MuxResponse::Ok)?;
+    while let Ok(event) = events.recv() {
+        let mut batch = vec![event];
+        batch.extend(events.try_iter());
+        for event in coalesce_output(batch) {
+            ipc::write_json(stream, &MuxResponse::Event { event })?;
+        }
     }
+    Ok(())
+}
+
+/// Merge each run of output events into one event per window, keeping the latest end.
+fn coalesce_output(events: Vec<MuxEvent>) -> Vec<MuxEvent> {
+    let mut coalesced: Vec<MuxEvent> = Vec::with_capacity(events.len());
+    for event in events {
+        if let MuxEvent::Output {
+            session,
+            window,
+            end,
+        } = &event
+            && let Some(MuxEvent::Output {
+                end: merged_end, ..
+            }) = coalesced
+                .iter_mut()
+                .rev()
+                .take_while(|queued| matches!(queued, MuxEvent::Output { .. }))
+                .find(|queued| {
+                    matches!(queued, MuxEvent::Output { session: s, window: w, .. } if s == session && w == window)
+                })
+        {
+            *merged_end = *end;
+            continue;
+        }
+        coalesced.push(event);
+    }
+    coalesced
 }
 
 fn handle_ping() -> MuxResponse {
@@ -194,6 +272,9 @@ fn handle_list_sessions() -> MuxResponse {
             name: s.name,
             created: s.created,
             attached: s.attached,
+            working_dir: s.working_dir.map(|dir| dir.to_string_lossy().into_owned()),
+            exit_code: s.exit_code,
+            owner: s.owner,
         })
         .collect();
     MuxResponse::Sessions { sessions }
@@ -205,14 +286,19 @@ fn handle_session_exists(name: &str) -> MuxResponse {
     }
 }
 
-fn handle_create_session(name: &str, working_dir: &str, command: &[String]) -> Result<MuxResponse> {
+fn handle_create_session(
+    name: &str,
+    working_dir: &str,
+    command: &[String],
+    owner: Option<String>,
+) -> Result<MuxResponse> {
     let dir = Path::new(working_dir);
     let command = if command.is_empty() {
         None
     } else {
         Some(command)
     };
-    super::server::SessionManager::create(name, dir, command)?;
+    super::server::SessionManager::create(name, dir, command, owner)?;
     Ok(MuxResponse::Ok)
 }
 
@@ -232,6 +318,9 @@ fn handle_list_windows(session: &str) -> Result<MuxResponse> {
         .map(|w| WindowInfo {
             index: w.index,
             name: w.name,
+            title: Some(w.title).filter(|title| !title.is_empty()),
+            pane_title: Some(w.pane_title).filter(|title| !title.is_empty()),
+            exit_code: w.exit_code,
         })
         .collect();
     Ok(MuxResponse::Windows { windows })
@@ -258,6 +347,16 @@ fn handle_kill_window(session: &str, window_index: u32) -> Result<MuxResponse> {
     Ok(MuxResponse::Ok)
 }
 
+fn handle_respawn_window(target: &str, command: &[String]) -> Result<MuxResponse> {
+    let command = if command.is_empty() {
+        None
+    } else {
+        Some(command)
+    };
+    super::server::SessionManager::respawn_window(target, command)?;
+    Ok(MuxResponse::Ok)
+}
+
 fn handle_rename_window(session: &str, window_index: u32, new_name: &str) -> Result<MuxResponse> {
     super::server::SessionManager::rename_window(session, window_index, new_name)?;
     Ok(MuxResponse::Ok)
@@ -307,6 +406,21 @@ fn handle_pane_current_command(target: &str) -> Result<MuxResponse> {
     Ok(MuxResponse::Text { text: cmd })
 }
 
+fn handle_window_alive(target: &str) -> Result<MuxResponse> {
+    let value = super::server::SessionManager::window_alive(target)?;
+    Ok(MuxResponse::Bool { value })
+}
+
+fn handle_pane_title(target: &str) -> Result<MuxResponse> {
+    let title = super::server::OutputCapture::pane_title(target)?;
+    Ok(MuxResponse::Text { text: title })
+}
+
+fn handle_set_title(target: &str, title: &str) -> Result<MuxResponse> {
+    super::server::SessionManager::set_title(target, title)?;
+    Ok(MuxResponse::Ok)
+}
+
 fn handle_tail(target: &str, lines: u32) -> Result<String> {
     let lines = usize::try_from(lines).map_or(usize::MAX, |value| value);
     Ok(super::server::OutputCapture::tail(target, lines)?.join("\n"))
@@ -317,7 +431,7 @@ enum ReadResult {
     Reset { start: u64, checkpoint: Vec<u8> },
 }
 
-fn handle_read_output(target: &str, after: u64, max_bytes: u32) -> Result<MuxResponse> {
+fn handle_read_output(target: &str, after: u64, max_bytes: u32, raw: bool) -> Result<MuxResponse> {
     use base64::engine::general_purpose::STANDARD as BASE64;
 
     let max_bytes = usize::try_from(max_bytes).unwrap_or(usize::MAX);
@@ -367,6 +481,13 @@ fn handle_read_output(target: &str, after: u64, max_bytes: u32) -> Result<MuxRes
     };
 
     match result {
+        ReadResult::Chunk { start, data } if raw => {
+            let end = start.saturating_add(u64::try_from(data.len()).unwrap_or(u64::MAX));
+            Ok(MuxResponse::RawOutputChunk { start, end, data })
+        }
+        ReadResult::Reset { start, checkpoint } if raw => {
+            Ok(MuxResponse::RawOutputReset { start, checkpoint })
+        }
         ReadResult::Chunk { start, data } => {
             let end = start.saturating_add(u64::try_from(data.len()).unwrap_or(u64::MAX));
             Ok(MuxResponse::OutputChunk {
@@ -403,3 +524,29 @@ fn handle_list_pids(session: &str) -> Result<MuxResponse> {
     let pids = super::server::SessionManager::list_pane_pids(session)?;
     Ok(MuxResponse::Pids { pids })
 }
+
+/// Process ID of the program running in `target`.
+fn window_pid(target: &str) -> Result<u32> {
+    let window = super::backend::resolve_window(target)?;
+    let pid = window
+        .lock()
+        .child
+        .process_id()
+        .filter(|pid| *pid != 0)
+        .context(format!("Target '{target}' has no running process"))?;
+    Ok(pid)
+}
+
+fn handle_process_usages(targets: &[String]) -> Result<MuxResponse> {
+    let table = super::usage::ProcessTable::read()?;
+    let usages = targets
+        .iter()
+        .map(|target| window_pid(target).ok().map(|pid| table.tree_usage(pid)))
+        .collect();
+    Ok(MuxResponse::Usages { usages })
+}
+
+fn handle_freeze_processes(target: &str, frozen: bool) -> Result<MuxResponse> {
+    super::usage::freeze_process_tree(window_pid(target)?, frozen)?;
+    Ok(MuxResponse::Ok)
+}
@@ -1,5 +1,6 @@
 //! IPC framing helpers for the mux daemon.
 
+use super::protocol::MuxResponse;
 use anyhow::{Context, Result, bail};
 use serde::Serialize;
 use serde::de::DeserializeOwned;
@@ -46,12 +47,12 @@ fn write_len_prefixed_payload(writer: &mut dyn Write, payload: &[u8]) -> Result<
     write_len_prefixed_payload_with_len(writer, payload.len(), Some(payload))
 }
 
-/// Read a length-prefixed JSON message.
+/// Read a length-prefixed binary frame.
 ///
 /// # Errors
 ///
-/// Returns an error if the stream cannot be read or the JSON cannot be decoded.
-pub fn read_json<T: DeserializeOwned>(reader: &mut dyn Read) -> Result<T> {
+/// Returns an error if the stream cannot be read or the frame is too large.
+pub fn read_frame(reader: &mut dyn Read) -> Result<Vec<u8>> {
     let mut len_bytes = [0u8; 4];
     reader
         .read_exact(&mut len_bytes)
@@ -65,6 +66,25 @@ pub fn read_json<T: DeserializeOwned>(reader: &mut dyn Read) -> Result<T> {
     reader
         .read_exact(&mut buf)
         .context("Failed to read message")?;
+    Ok(buf)
+}
+
+/// Write a length-prefixed binary frame.
+///
+/// # Errors
+///
+/// Returns an error if the frame is too large or cannot be written.
+pub fn write_frame(writer: &mut dyn Write, payload: &[u8]) -> Result<()> {
+    write_len_prefixed_payload(writer, payload)
+}
+
+/// Read a length-prefixed JSON message.
+///
+/// # Errors
+///
+/// Returns an error if the stream cannot be read or the JSON cannot be decoded.
+pub fn read_json<T: DeserializeOwned>(reader: &mut dyn Read) -> Result<T> {
+    let buf = read_frame(reader)?;
     serde_json::from_slice(&buf).context("Failed to decode JSON message")
 }
 
@@ -77,3 +97,31 @@ pub fn write_json<T: Serialize>(writer: &mut dyn Write, value: &T) -> Result<()>
     let buf = serde_json::to_vec(value).context("Failed to encode JSON message")?;
     write_len_prefixed_payload(writer, &buf)
 }
+
+/// Read a response, followed by a binary frame for each of its raw payloads.
+///
+/// # Errors
+///
+/// Returns an error if the stream cannot be read or the response cannot be decoded.
+pub fn read_response(reader: &mut dyn Read) -> Result<MuxResponse> {
+    let mut response: MuxResponse = read_json(reader)?;
+    for payload in response.raw_payloads_mut() {
+        *payload = read_frame(reader)?;
+    }
+    Ok(response)
+}
+
+/// Write a response, followed by a binary frame for each of its raw payloads.
+///
+/// Raw payloads skip JSON and base64 encoding, which matters for large captures.
+///
+/// # Errors
+///
+/// Returns an error if the response cannot be encoded or written.
+pub fn write_response(writer: &mut dyn Write, response: &MuxResponse) -> Result<()> {
+    write_json(writer, response)?;
+    for payload in response.raw_payloads() {
+        write_frame(writer, payload)?;
+    }
+    Ok(())
+}
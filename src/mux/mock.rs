@@ -0,0 +1,484 @@
+//! In-memory stand-in for the mux daemon, for integration tests.
+//!
+//! While a [`MockBackend`] is installed, every mux request ([`SessionManager`],
+//! [`OutputCapture`], [`OutputStream`]) is answered from memory instead of the daemon, so no
+//! process is spawned and no socket is opened. Windows run nothing: their output is whatever
+//! the test scripts, and the input sent to them is recorded for assertions.
+//!
+//! [`SessionManager`]: super::SessionManager
+//! [`OutputCapture`]: super::OutputCapture
+//! [`OutputStream`]: super::OutputStream
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use base64::Engine as _;
+use parking_lot::{Mutex, MutexGuard};
+
+use super::backend::{DEFAULT_COLS, DEFAULT_ROWS, parse_target};
+use super::protocol::{
+    CaptureKind, MuxRequest, MuxResponse, ProcessUsage, SessionInfo, WindowInfo,
+};
+
+/// The installed backend, if any.
+static INSTALLED: Mutex<Option<Arc<Mutex<MockState>>>> = Mutex::new(None);
+
+/// Held by the installed backend so tests using it run one at a time.
+static EXCLUSIVE: Mutex<()> = Mutex::new(());
+
+/// Sessions of the mock, and the output scripted for programs.
+#[derive(Debug, Default)]
+struct MockState {
+    sessions: BTreeMap<String, MockSession>,
+    scripts: Vec<(String, String)>,
+    created: i64,
+}
+
+#[derive(Debug)]
+struct MockSession {
+    created: i64,
+    working_dir: String,
+    owner: Option<String>,
+    windows: Vec<MockWindow>,
+    next_window_index: u32,
+}
+
+#[derive(Debug)]
+struct MockWindow {
+    index: u32,
+    name: String,
+    title: String,
+    command: Vec<String>,
+    output: Vec<u8>,
+    input: Vec<u8>,
+    exit_code: Option<u32>,
+    cols: u16,
+    rows: u16,
+}
+
+/// An in-memory mux, answering requests while it is installed.
+///
+/// Installing waits for any other installed backend to be dropped, so tests that share a
+/// process do not see each other's sessions.
+#[derive(Debug)]
+pub struct MockBackend {
+    state: Arc<Mutex<MockState>>,
+    _exclusive: MutexGuard<'static, ()>,
+}
+
+impl MockBackend {
+    /// Install an empty backend for the rest of its lifetime.
+    #[must_use]
+    pub fn install() -> Self {
+        let exclusive = EXCLUSIVE.lock();
+        let state = Arc::new(Mutex::new(MockState::default()));
+        *INSTALLED.lock() = Some(Arc::clone(&state));
+        Self {
+            state,
+            _exclusive: exclusive,
+        }
+    }
+
+    /// Give windows whose command runs `program` (by name, wherever it appears in the argv,
+    /// so sandbox and Docker wrappers still match) this initial `output`.
+    pub fn script(&self, program: &str, output: &str) {
+        self.state
+            .lock()
+            .scripts
+            .push((program.to_string(), output.to_string()));
+    }
+
+    /// Append `output` to the window at `target` (`session` or `session:index`), as if its
+    /// program printed it. Returns `false` when there is no such window.
+    #[must_use]
+    pub fn push_output(&self, target: &str, output: &str) -> bool {
+        self.with_window(target, |window| {
+            window.output.extend_from_slice(output.as_bytes());
+        })
+        .is_some()
+    }
+
+    /// End the program of the window at `target`, as if it exited with `code`. Returns
+    /// `false` when there is no such window.
+    #[must_use]
+    pub fn exit(&self, target: &str, code: u32) -> bool {
+        self.with_window(target, |window| window.exit_code = Some(code))
+            .is_some()
+    }
+
+    /// Input sent to the window at `target` so far.
+    #[must_use]
+    pub fn input(&self, target: &str) -> Option<String> {
+        self.with_window(target, |window| {
+            String::from_utf8_lossy(&window.input).into_owned()
+        })
+    }
+
+    /// Command each window of `session` was created with, in window order.
+    #[must_use]
+    pub fn commands(&self, session: &str) -> Vec<Vec<String>> {
+        self.state
+            .lock()
+            .sessions
+            .get(session)
+            .map(|session| {
+                session
+                    .windows
+                    .iter()
+                    .map(|window| window.command.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Names of the sessions.
+    #[must_use]
+    pub fn sessions(&self) -> Vec<String> {
+        self.state.lock().sessions.keys().cloned().collect()
+    }
+
+    fn with_window<T>(&self, target: &str, f: impl FnOnce(&mut MockWindow) -> T) -> Option<T> {
+        self.state.lock().window(target).map(f)
+    }
+}
+
+impl Drop for MockBackend {
+    fn drop(&mut self) {
+        *INSTALLED.lock() = None;
+    }
+}
+
+/// Whether a backend is installed.
+pub(super) fn is_installed() -> bool {
+    INSTALLED.lock().is_some()
+}
+
+/// Answer `request` from the installed backend, `None` when none is installed.
+pub(super) fn handle(request: &MuxRequest) -> Option<MuxResponse> {
+    let state = INSTALLED.lock().clone()?;
+    let mut state = state.lock();
+    let mut answer = |request: &MuxRequest| {
+        state
+            .handle(request)
+            .unwrap_or_else(|message| MuxResponse::Err { message })
+    };
+    Some(match request {
+        MuxRequest::Batch { requests } => MuxResponse::Batch {
+            responses: requests.iter().map(&mut answer).collect(),
+        },
+        request => answer(request),
+    })
+}
+
+impl MockState {
+    fn window(&mut self, target: &str) -> Option<&mut MockWindow> {
+        let target = parse_target(target).ok()?;
+        self.sessions
+            .get_mut(&target.session)?
+            .windows
+            .iter_mut()
+            .find(|window| window.index == target.window_index)
+    }
+
+    fn existing_window(&mut self, target: &str) -> Result<&mut MockWindow, String> {
+        self.window(target)
+            .ok_or_else(|| format!("Target '{target}' not found"))
+    }
+
+    fn new_window(&self, index: u32, name: &str, command: &[String]) -> MockWindow {
+        let output = self
+            .scripts
+            .iter()
+            .rev()
+            .find(|(program, _)| {
+                command
+                    .iter()
+                    .any(|arg| arg.rsplit('/').next() == Some(program.as_str()))
+            })
+            .map(|(_, output)| output.clone().into_bytes())
+            .unwrap_or_default();
+        MockWindow {
+            index,
+            name: name.to_string(),
+            title: String::new(),
+            command: command.to_vec(),
+            output,
+            input: Vec::new(),
+            exit_code: None,
+            cols: DEFAULT_COLS,
+            rows: DEFAULT_ROWS,
+        }
+    }
+
+    fn handle(&mut self, request: &MuxRequest) -> Result<MuxResponse, String> {
+        let response = match request {
+            MuxRequest::Ping => MuxResponse::Pong {
+                version: super::version(),
+            },
+            MuxRequest::ListSessions => MuxResponse::Sessions {
+                sessions: self
+                    .sessions
+                    .iter()
+                    .map(|(name, session)| SessionInfo {
+                        name: name.clone(),
+                        created: session.created,
+                        attached: false,
+                        working_dir: Some(session.working_dir.clone()),
+                        exit_code: session.windows.first().and_then(|window| window.exit_code),
+                        owner: session.owner.clone(),
+                    })
+                    .collect(),
+            },
+            MuxRequest::SessionExists { name } => MuxResponse::Bool {
+                value: self.sessions.contains_key(name),
+            },
+            MuxRequest::CreateSession {
+                name,
+                working_dir,
+                command,
+                owner,
+                ..
+            } => {
+                if self.sessions.contains_key(name) {
+                    return Err(format!("Session '{name}' already exists"));
+                }
+                self.created += 1;
+                let window = self.new_window(0, name, command);
+                self.sessions.insert(
+                    name.clone(),
+                    MockSession {
+                        created: self.created,
+                        working_dir: working_dir.clone(),
+                        owner: owner.clone(),
+                        windows: vec![window],
+                        next_window_index: 1,
+                    },
+                );
+                MuxResponse::Ok
+            }
+            MuxRequest::KillSession { name } => {
+                self.sessions
+                    .remove(name)
+                    .ok_or_else(|| format!("Session '{name}' not found"))?;
+                MuxResponse::Ok
+            }
+            MuxRequest::RenameSession { old_name, new_name } => {
+                let session = self
+                    .sessions
+                    .remove(old_name)
+                    .ok_or_else(|| format!("Session '{old_name}' not found"))?;
+                self.sessions.insert(new_name.clone(), session);
+                MuxResponse::Ok
+            }
+            MuxRequest::ListWindows { session } => MuxResponse::Windows {
+                windows: self
+                    .sessions
+                    .get(session)
+                    .ok_or_else(|| format!("Session '{session}' not found"))?
+                    .windows
+                    .iter()
+                    .map(|window| WindowInfo {
+                        index: window.index,
+                        name: window.name.clone(),
+                        title: (!window.title.is_empty()).then(|| window.title.clone()),
+                        pane_title: None,
+                        exit_code: window.exit_code,
+                    })
+                    .collect(),
+            },
+            MuxRequest::CreateWindow {
+                session,
+                window_name,
+                command,
+                ..
+            } => {
+                let index = self
+                    .sessions
+                    .get(session)
+                    .ok_or_else(|| format!("Session '{session}' not found"))?
+                    .next_window_index;
+                let window = self.new_window(index, window_name, command);
+                if let Some(session) = self.sessions.get_mut(session) {
+                    session.windows.push(window);
+                    session.next_window_index += 1;
+                }
+                MuxResponse::WindowCreated { index }
+            }
+            request => self.edit_window(request)?,
+        };
+        Ok(response)
+    }
+
+    /// Answer the requests that change a window.
+    fn edit_window(&mut self, request: &MuxRequest) -> Result<MuxResponse, String> {
+        let response = match request {
+            MuxRequest::KillWindow {
+                session,
+                window_index,
+            } => {
+                let session = self
+                    .sessions
+                    .get_mut(session)
+                    .ok_or_else(|| format!("Session '{session}' not found"))?;
+                session
+                    .windows
+                    .retain(|window| window.index != *window_index);
+                MuxResponse::Ok
+            }
+            MuxRequest::RenameWindow {
+                session,
+                window_index,
+                new_name,
+            } => {
+                new_name.clone_into(
+                    &mut self
+                        .existing_window(&format!("{session}:{window_index}"))?
+                        .name,
+                );
+                MuxResponse::Ok
+            }
+            MuxRequest::RespawnWindow { target, command } => {
+                let window = self.existing_window(target)?;
+                let (index, name) = (window.index, window.name.clone());
+                let fresh = self.new_window(index, &name, command);
+                let window = self.existing_window(target)?;
+                *window = MockWindow {
+                    cols: window.cols,
+                    rows: window.rows,
+                    ..fresh
+                };
+                MuxResponse::Ok
+            }
+            MuxRequest::Resize { target, cols, rows } => {
+                let window = self.existing_window(target)?;
+                window.cols = *cols;
+                window.rows = *rows;
+                MuxResponse::Ok
+            }
+            MuxRequest::SendInput { target, data } => {
+                self.existing_window(target)?.input.extend_from_slice(data);
+                MuxResponse::Ok
+            }
+            MuxRequest::SetTitle { target, title } => {
+                title.clone_into(&mut self.existing_window(target)?.title);
+                MuxResponse::Ok
+            }
+            // Mock windows have no processes to stop.
+            MuxRequest::FreezeProcesses { target, .. } => {
+                self.existing_window(target)?;
+                MuxResponse::Ok
+            }
+            request => self.read(request)?,
+        };
+        Ok(response)
+    }
+
+    /// Answer the requests that only read a window.
+    fn read(&mut self, request: &MuxRequest) -> Result<MuxResponse, String> {
+        let response = match request {
+            MuxRequest::Capture { target, kind, .. } => {
+                let window = self.existing_window(target)?;
+                let text = String::from_utf8_lossy(&window.output).into_owned();
+                let lines: Vec<&str> = text.lines().collect();
+                let keep = match kind {
+                    CaptureKind::Visible => usize::from(window.rows),
+                    CaptureKind::History { lines } => usize::try_from(*lines).unwrap_or(usize::MAX),
+                    CaptureKind::FullHistory => lines.len(),
+                };
+                MuxResponse::Text {
+                    text: lines[lines.len().saturating_sub(keep)..].join("\n"),
+                }
+            }
+            MuxRequest::Tail { target, lines } => {
+                let window = self.existing_window(target)?;
+                let text = String::from_utf8_lossy(&window.output).into_owned();
+                let non_empty: Vec<&str> = text
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .collect();
+                let keep = usize::try_from(*lines).unwrap_or(usize::MAX);
+                MuxResponse::Text {
+                    text: non_empty[non_empty.len().saturating_sub(keep)..].join("\n"),
+                }
+            }
+            MuxRequest::PaneSize { target } => {
+                let window = self.existing_window(target)?;
+                MuxResponse::Size {
+                    cols: window.cols,
+                    rows: window.rows,
+                }
+            }
+            MuxRequest::CursorPosition { target } => {
+                let window = self.existing_window(target)?;
+                let row = String::from_utf8_lossy(&window.output).lines().count();
+                MuxResponse::Position {
+                    x: 0,
+                    y: u16::try_from(row)
+                        .unwrap_or(u16::MAX)
+                        .min(window.rows.saturating_sub(1)),
+                    hidden: false,
+                }
+            }
+            MuxRequest::PaneCurrentCommand { target } => MuxResponse::Text {
+                text: self
+                    .existing_window(target)?
+                    .command
+                    .first()
+                    .and_then(|program| program.rsplit('/').next())
+                    .unwrap_or_default()
+                    .to_string(),
+            },
+            MuxRequest::WindowAlive { target } => MuxResponse::Bool {
+                value: self
+                    .window(target)
+                    .is_some_and(|window| window.exit_code.is_none()),
+            },
+            MuxRequest::PaneTitle { target } => {
+                self.existing_window(target)?;
+                MuxResponse::Text {
+                    text: String::new(),
+                }
+            }
+            MuxRequest::ReadOutput {
+                target,
+                after,
+                max_bytes,
+                ..
+            } => {
+                let output = &self.existing_window(target)?.output;
+                let start = usize::try_from(*after)
+                    .unwrap_or(usize::MAX)
+                    .min(output.len());
+                let end = start
+                    .saturating_add(usize::try_from(*max_bytes).unwrap_or(usize::MAX))
+                    .min(output.len());
+                MuxResponse::OutputChunk {
+                    start: u64::try_from(start).unwrap_or(u64::MAX),
+                    end: u64::try_from(end).unwrap_or(u64::MAX),
+                    data_b64: base64::engine::general_purpose::STANDARD.encode(&output[start..end]),
+                }
+            }
+            MuxRequest::OutputCursor { target } => MuxResponse::OutputCursor {
+                start: 0,
+                end: u64::try_from(self.existing_window(target)?.output.len()).unwrap_or(u64::MAX),
+            },
+            request => self.processes(request)?,
+        };
+        Ok(response)
+    }
+
+    /// Answer the requests about a window's processes. Mock windows have none.
+    fn processes(&mut self, request: &MuxRequest) -> Result<MuxResponse, String> {
+        let response = match request {
+            MuxRequest::ListPanePids { .. } => MuxResponse::Pids { pids: Vec::new() },
+            MuxRequest::ProcessUsages { targets } => MuxResponse::Usages {
+                usages: targets
+                    .iter()
+                    .map(|target| self.window(target).map(|_| ProcessUsage::default()))
+                    .collect(),
+            },
+            request => return Err(format!("Unsupported mock mux request: {request:?}")),
+        };
+        Ok(response)
+    }
+}
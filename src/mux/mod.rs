@@ -7,6 +7,8 @@ mod daemon;
 mod discovery;
 mod endpoint;
 mod ipc;
+#[cfg(feature = "mock-mux")]
+mod mock;
 mod output;
 #[cfg(not(target_os = "linux"))]
 mod pidfile;
@@ -14,11 +16,17 @@ mod protocol;
 pub(crate) mod render;
 mod server;
 mod session;
+mod usage;
 
-pub use capture::Capture as OutputCapture;
+pub use capture::{Capture as OutputCapture, PaneSnapshot};
+pub use client::{EventStream, subscribe};
 pub use endpoint::{SocketEndpoint, set_socket_override, socket_endpoint};
+#[cfg(feature = "mock-mux")]
+pub use mock::MockBackend;
 pub use output::{OutputCursor, OutputRead, OutputStream};
+pub use protocol::MuxEvent;
 pub use session::{Manager as SessionManager, Session, Window};
+pub use usage::ProcessUsage;
 
 use anyhow::{Context, Result, bail};
 use interprocess::local_socket::Stream;
@@ -40,6 +48,11 @@ pub const fn is_available() -> bool {
 /// Check if the mux daemon is currently running.
 #[must_use]
 pub fn is_server_running() -> bool {
+    #[cfg(feature = "mock-mux")]
+    if mock::is_installed() {
+        return true;
+    }
+
     let Ok(endpoint) = endpoint::socket_endpoint() else {
         return false;
     };
@@ -57,6 +70,11 @@ pub fn is_server_running() -> bool {
 ///
 /// Returns an error if the mux endpoint cannot be resolved or the daemon responds with an error.
 pub fn running_daemon_version() -> Result<Option<String>> {
+    #[cfg(feature = "mock-mux")]
+    if mock::is_installed() {
+        return Ok(Some(version()));
+    }
+
     let endpoint = client::endpoint()?;
     let Ok(mut stream) = Stream::connect(endpoint.name.clone()) else {
         return Ok(None);
@@ -178,7 +196,7 @@ pub(crate) fn terminate_mux_daemon_for_socket(socket: &str) -> Result<()> {
 #[must_use]
 pub fn version() -> String {
     // Bump this when Tenex makes incompatible changes to mux IPC payloads.
-    const MUX_PROTOCOL_VERSION: u32 = 3;
+    const MUX_PROTOCOL_VERSION: u32 = 16;
 
     format!(
         "tenex-mux/{}/proto-{}",
@@ -63,6 +63,7 @@ impl OutputStream {
             target: target.to_string(),
             after,
             max_bytes,
+            raw: true,
         })?;
         decode_read_output_response(response)
     }
@@ -111,6 +112,12 @@ fn decode_read_output_response(response: MuxResponse) -> Result<OutputRead> {
             };
             Ok(OutputRead::Reset(OutputReset { start, checkpoint }))
         }
+        MuxResponse::RawOutputChunk { start, end, data } => {
+            Ok(OutputRead::Chunk(OutputChunk { start, end, data }))
+        }
+        MuxResponse::RawOutputReset { start, checkpoint } => {
+            Ok(OutputRead::Reset(OutputReset { start, checkpoint }))
+        }
         MuxResponse::Err { message } => bail!("{message}"),
         other => bail!("Unexpected response: {other:?}"),
     }
@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+pub use super::usage::ProcessUsage;
+
 /// A running mux session.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionInfo {
@@ -11,6 +13,15 @@ pub struct SessionInfo {
     pub created: i64,
     /// Whether a client is attached (reserved for future use).
     pub attached: bool,
+    /// Working directory of the session's first window (absent from older daemons).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub working_dir: Option<String>,
+    /// Exit code of the root window's process, once it has exited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<u32>,
+    /// State file of the Tenex instance that created the session (absent from older daemons).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
 }
 
 /// A mux window inside a session.
@@ -20,6 +31,15 @@ pub struct WindowInfo {
     pub index: u32,
     /// Window title/name.
     pub name: String,
+    /// Descriptive title set by Tenex (absent when unset or from older daemons).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Title the window's program last set with OSC 0/2 (absent when unset).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pane_title: Option<String>,
+    /// Exit code of the window's process, once it has exited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<u32>,
 }
 
 /// Which capture to perform.
@@ -60,6 +80,9 @@ pub enum MuxRequest {
         cols: u16,
         /// Rows for initial PTY size.
         rows: u16,
+        /// State file of the Tenex instance creating the session.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        owner: Option<String>,
     },
     /// Kill a session (and all windows).
     KillSession {
@@ -100,6 +123,13 @@ pub enum MuxRequest {
         /// Window index.
         window_index: u32,
     },
+    /// Replace the process of a window, keeping its index, name, directory, and size.
+    RespawnWindow {
+        /// Target string.
+        target: String,
+        /// Command argv (empty means the platform default shell).
+        command: Vec<String>,
+    },
     /// Rename a window by index.
     RenameWindow {
         /// Session name.
@@ -131,6 +161,11 @@ pub enum MuxRequest {
         target: String,
         /// Capture kind.
         kind: CaptureKind,
+        /// Ask for the text in a binary frame ([`MuxResponse::RawText`]) instead of JSON.
+        ///
+        /// Daemons that predate binary frames ignore this and answer [`MuxResponse::Text`].
+        #[serde(default)]
+        raw: bool,
     },
     /// Get the pane size for a target.
     PaneSize {
@@ -147,6 +182,23 @@ pub enum MuxRequest {
         /// Target string.
         target: String,
     },
+    /// Check whether the target's process is still running.
+    WindowAlive {
+        /// Target string.
+        target: String,
+    },
+    /// Return the title the target's program last set with OSC 0/2.
+    PaneTitle {
+        /// Target string.
+        target: String,
+    },
+    /// Set the descriptive title of a target (an empty title clears it).
+    SetTitle {
+        /// Target string.
+        target: String,
+        /// New title.
+        title: String,
+    },
     /// Return the last N non-empty lines from a target.
     Tail {
         /// Target string.
@@ -164,6 +216,12 @@ pub enum MuxRequest {
         after: u64,
         /// Maximum number of bytes to return (before base64 encoding).
         max_bytes: u32,
+        /// Ask for the bytes in a binary frame ([`MuxResponse::RawOutputChunk`] or
+        /// [`MuxResponse::RawOutputReset`]) instead of base64.
+        ///
+        /// Daemons that predate binary frames ignore this and answer with base64.
+        #[serde(default)]
+        raw: bool,
     },
     /// Return raw output sequence bounds for a target.
     OutputCursor {
@@ -175,6 +233,85 @@ pub enum MuxRequest {
         /// Session name.
         session: String,
     },
+    /// Sample CPU and memory usage of several targets' process trees from one read of the
+    /// process table.
+    ProcessUsages {
+        /// Target strings.
+        targets: Vec<String>,
+    },
+    /// Stop or continue every process in a target's process tree.
+    FreezeProcesses {
+        /// Target string.
+        target: String,
+        /// Stop the processes (`SIGSTOP`) when true; continue them (`SIGCONT`) when false.
+        frozen: bool,
+    },
+    /// Turn this connection into an event stream.
+    ///
+    /// The daemon answers [`MuxResponse::Ok`] and then sends a [`MuxResponse::Event`] for each
+    /// [`MuxEvent`] until the client disconnects; no further requests are read.
+    Subscribe,
+    /// Run several requests in one round trip, in order.
+    ///
+    /// The daemon answers [`MuxResponse::Batch`] with one response per request. A failing
+    /// request gets a [`MuxResponse::Err`] and does not stop the rest. Nested batches and
+    /// [`MuxRequest::Subscribe`] are rejected.
+    Batch {
+        /// Requests to run.
+        requests: Vec<Self>,
+    },
+}
+
+/// A change in the daemon's sessions, streamed after [`MuxRequest::Subscribe`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MuxEvent {
+    /// A session (and its root window) was created.
+    SessionCreated {
+        /// Session name.
+        session: String,
+    },
+    /// A window was created in a session.
+    WindowCreated {
+        /// Session name.
+        session: String,
+        /// Window index.
+        window: u32,
+        /// Window title/name.
+        name: String,
+    },
+    /// A window produced output. Consecutive output is coalesced into one event.
+    Output {
+        /// Session name.
+        session: String,
+        /// Window index.
+        window: u32,
+        /// Sequence number after the last byte produced (see [`MuxRequest::ReadOutput`]).
+        end: u64,
+    },
+    /// A window's program set a new title with OSC 0/2.
+    TitleChanged {
+        /// Session name.
+        session: String,
+        /// Window index.
+        window: u32,
+        /// The new title.
+        title: String,
+    },
+    /// A window's process exited. The window stays, with its output, until it is killed.
+    Exited {
+        /// Session name.
+        session: String,
+        /// Window index.
+        window: u32,
+        /// Exit code of the process, if it could be read.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        code: Option<u32>,
+    },
+    /// A session was killed.
+    SessionKilled {
+        /// Session name.
+        session: String,
+    },
 }
 
 /// A response sent from the daemon to the client.
@@ -207,6 +344,12 @@ pub enum MuxResponse {
         /// Text payload.
         text: String,
     },
+    /// A UTF-8 string payload, sent in a binary frame after this response.
+    RawText {
+        /// Text payload.
+        #[serde(skip)]
+        text: Vec<u8>,
+    },
     /// Raw bytes payload.
     Bytes {
         /// Bytes payload.
@@ -255,6 +398,25 @@ pub enum MuxResponse {
         /// Base64-encoded checkpoint stream for the restart point.
         checkpoint_b64: String,
     },
+    /// Output chunk payload, with the bytes sent in a binary frame after this response.
+    RawOutputChunk {
+        /// First sequence number included in this chunk.
+        start: u64,
+        /// Sequence number after the last byte included in this chunk.
+        end: u64,
+        /// Output bytes.
+        #[serde(skip)]
+        data: Vec<u8>,
+    },
+    /// Client must reset its local output state; the checkpoint is sent in a binary frame
+    /// after this response.
+    RawOutputReset {
+        /// Sequence number to restart from.
+        start: u64,
+        /// Checkpoint stream for the restart point.
+        #[serde(skip)]
+        checkpoint: Vec<u8>,
+    },
     /// Output sequence bounds payload.
     OutputCursor {
         /// First sequence number still retained by the daemon.
@@ -262,9 +424,59 @@ pub enum MuxResponse {
         /// Sequence number after the last observed byte.
         end: u64,
     },
+    /// Process-tree resource usage of several targets.
+    Usages {
+        /// Usage of each requested target, in request order; `None` for a target without a
+        /// running process.
+        usages: Vec<Option<ProcessUsage>>,
+    },
+    /// An event on a subscribed connection.
+    Event {
+        /// The event.
+        event: MuxEvent,
+    },
+    /// Responses to a [`MuxRequest::Batch`], in request order.
+    Batch {
+        /// One response per request.
+        responses: Vec<Self>,
+    },
     /// Error response.
     Err {
         /// Human-readable error message.
         message: String,
     },
 }
+
+impl MuxResponse {
+    /// Payloads that travel in binary frames after the JSON of this response, in order.
+    #[must_use]
+    pub fn raw_payloads(&self) -> Vec<&[u8]> {
+        match self {
+            Self::RawText { text: payload }
+            | Self::RawOutputChunk { data: payload, .. }
+            | Self::RawOutputReset {
+                checkpoint: payload,
+                ..
+            } => vec![payload.as_slice()],
+            Self::Batch { responses } => responses.iter().flat_map(Self::raw_payloads).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Slots for the payloads of [`Self::raw_payloads`], to fill from the frames that follow.
+    pub fn raw_payloads_mut(&mut self) -> Vec<&mut Vec<u8>> {
+        match self {
+            Self::RawText { text: payload }
+            | Self::RawOutputChunk { data: payload, .. }
+            | Self::RawOutputReset {
+                checkpoint: payload,
+                ..
+            } => vec![payload],
+            Self::Batch { responses } => responses
+                .iter_mut()
+                .flat_map(Self::raw_payloads_mut)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
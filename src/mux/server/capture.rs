@@ -95,6 +95,17 @@ impl Capture {
         Ok(command.unwrap_or_default())
     }
 
+    /// Get the title the pane's program last set with OSC 0/2.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the target cannot be resolved.
+    pub fn pane_title(session: &str) -> Result<String> {
+        let window = super::super::backend::resolve_window(session)?;
+        let title = window.lock().pane_title.clone();
+        Ok(title)
+    }
+
     /// Get the last N lines from the pane.
     ///
     /// # Errors
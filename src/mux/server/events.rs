@@ -0,0 +1,42 @@
+//! Fan-out of mux events to subscribed connections (server-side).
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+
+use parking_lot::Mutex;
+
+use super::super::protocol::MuxEvent;
+
+/// Events buffered per subscriber. A subscriber that falls further behind misses events rather
+/// than slowing down the PTY reader threads.
+const SUBSCRIBER_BUFFER: usize = 1024;
+
+static SUBSCRIBERS: Mutex<Vec<SyncSender<MuxEvent>>> = Mutex::new(Vec::new());
+
+/// Fast check so reader threads skip building output events when nobody listens.
+static HAS_SUBSCRIBERS: AtomicBool = AtomicBool::new(false);
+
+/// Register a subscriber and return the receiving end of its event queue.
+pub fn subscribe() -> Receiver<MuxEvent> {
+    let (sender, receiver) = mpsc::sync_channel(SUBSCRIBER_BUFFER);
+    SUBSCRIBERS.lock().push(sender);
+    HAS_SUBSCRIBERS.store(true, Ordering::Relaxed);
+    receiver
+}
+
+/// Send the event built by `event` to every subscriber, dropping subscribers that went away.
+///
+/// `event` is only called when there is at least one subscriber.
+pub fn publish(event: impl FnOnce() -> MuxEvent) {
+    if !HAS_SUBSCRIBERS.load(Ordering::Relaxed) {
+        return;
+    }
+
+    let event = event();
+    let mut subscribers = SUBSCRIBERS.lock();
+    subscribers.retain(|sender| match sender.try_send(event.clone()) {
+        Ok(()) | Err(TrySendError::Full(_)) => true,
+        Err(TrySendError::Disconnected(_)) => false,
+    });
+    HAS_SUBSCRIBERS.store(!subscribers.is_empty(), Ordering::Relaxed);
+}
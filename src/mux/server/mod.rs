@@ -1,6 +1,7 @@
 //! Server-side PTY mux implementation.
 
 pub(super) mod capture;
+pub(super) mod events;
 pub(super) mod session;
 
 pub(super) use capture::Capture as OutputCapture;
@@ -1,29 +1,35 @@
 //! PTY-backed session management (server-side).
 
 use anyhow::{Context, Result, bail};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tracing::{debug, info, warn};
 
 use super::super::backend::{default_pty_size, global_state, spawn_window, unix_timestamp};
+use super::super::protocol::MuxEvent;
 
 /// Manager for mux sessions.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct Manager;
 
 impl Manager {
-    /// Create a new session.
+    /// Create a new session for the Tenex instance with the state file `owner`.
     ///
     /// # Errors
     ///
     /// Returns an error if the session cannot be created.
-    pub fn create(name: &str, working_dir: &Path, command: Option<&[String]>) -> Result<()> {
+    pub fn create(
+        name: &str,
+        working_dir: &Path,
+        command: Option<&[String]>,
+        owner: Option<String>,
+    ) -> Result<()> {
         debug!(name, ?working_dir, ?command, "Creating mux session");
 
         if Self::exists(name) {
             bail!("Session '{name}' already exists");
         }
 
-        let window = spawn_window(0, name, working_dir, command, default_pty_size())?;
+        let window = spawn_window(name, 0, name, working_dir, command, default_pty_size())?;
 
         {
             let mut state = global_state().lock();
@@ -32,13 +38,16 @@ impl Manager {
                 std::sync::Arc::new(parking_lot::Mutex::new(super::super::backend::MuxSession {
                     name: name.to_string(),
                     created: unix_timestamp(),
-                    root_restart_attempts: 0,
-                    last_root_restart: 0,
+                    owner,
                     windows: vec![window],
+                    next_window_index: 1,
                 })),
             );
         }
 
+        super::events::publish(|| MuxEvent::SessionCreated {
+            session: name.to_string(),
+        });
         info!(name, "Mux session created");
         Ok(())
     }
@@ -66,23 +75,20 @@ impl Manager {
             }
         }
 
+        super::events::publish(|| MuxEvent::SessionKilled {
+            session: name.to_string(),
+        });
         info!(name, "Mux session killed");
         Ok(())
     }
 
     /// Check if a session exists.
+    ///
+    /// Sessions whose processes have exited still exist, so their output can be read, until
+    /// they are killed.
     #[must_use]
     pub fn exists(name: &str) -> bool {
-        let session = {
-            let state = global_state().lock();
-            state.sessions.get(name).cloned()
-        };
-
-        let Some(session) = session else {
-            return false;
-        };
-
-        is_session_alive(&session)
+        global_state().lock().sessions.contains_key(name)
     }
 
     /// List all sessions.
@@ -93,28 +99,32 @@ impl Manager {
             state.sessions.values().cloned().collect::<Vec<_>>()
         };
 
-        let mut dead_names = Vec::new();
-        let mut result = Vec::new();
-
-        for session in sessions {
-            if !is_session_alive(&session) {
-                dead_names.push(session.lock().name.clone());
-                continue;
-            }
-
-            let guard = session.lock();
-            result.push(Session {
-                name: guard.name.clone(),
-                created: guard.created,
-                attached: false,
-            });
-        }
-
-        for name in dead_names {
-            let _ = Self::kill(&name);
-        }
-
-        result
+        sessions
+            .into_iter()
+            .map(|session| {
+                let (name, created, owner, root) = {
+                    let guard = session.lock();
+                    (
+                        guard.name.clone(),
+                        guard.created,
+                        guard.owner.clone(),
+                        guard.windows.first().cloned(),
+                    )
+                };
+                let (working_dir, exit_code) = root.map_or((None, None), |window| {
+                    let mut guard = window.lock();
+                    (Some(guard.working_dir.clone()), guard.exit_code())
+                });
+                Session {
+                    name,
+                    created,
+                    attached: false,
+                    working_dir,
+                    exit_code,
+                    owner,
+                }
+            })
+            .collect()
     }
 
     /// Send raw input bytes to a target.
@@ -145,15 +155,18 @@ impl Manager {
         };
 
         let new_name = new_name.to_string();
-        let root = {
+        let windows = {
             let mut guard = session.lock();
             guard.name.clone_from(&new_name);
-            guard.windows.first().cloned()
+            guard.windows.clone()
         };
 
-        if let Some(root) = root {
-            let mut guard = root.lock();
-            guard.name.clone_from(&new_name);
+        for (idx, window) in windows.iter().enumerate() {
+            let mut guard = window.lock();
+            guard.session.clone_from(&new_name);
+            if idx == 0 {
+                guard.name.clone_from(&new_name);
+            }
         }
 
         {
@@ -185,17 +198,34 @@ impl Manager {
                 .context(format!("Session '{session}' not found"))?
         };
 
-        let window_count = session_ref.lock().windows.len();
-
-        let index = u32::try_from(window_count).context("Mux session has too many windows")?;
+        let index = {
+            let mut guard = session_ref.lock();
+            let index = guard.next_window_index;
+            guard.next_window_index = index
+                .checked_add(1)
+                .context("Mux session has too many windows")?;
+            index
+        };
 
-        let window = spawn_window(index, window_name, working_dir, command, default_pty_size())?;
+        let window = spawn_window(
+            session,
+            index,
+            window_name,
+            working_dir,
+            command,
+            default_pty_size(),
+        )?;
 
         {
             let mut guard = session_ref.lock();
             guard.windows.push(window);
         }
 
+        super::events::publish(|| MuxEvent::WindowCreated {
+            session: session.to_string(),
+            window: index,
+            name: window_name.to_string(),
+        });
         info!(
             session,
             window_name,
@@ -205,8 +235,65 @@ impl Manager {
         Ok(index)
     }
 
+    /// Replace the process of a window with `command`, keeping the window's index, name,
+    /// working directory, and size. The old process is killed if it is still running, and the
+    /// window's output starts over.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the target cannot be resolved or the new process cannot be spawned.
+    pub fn respawn_window(target: &str, command: Option<&[String]>) -> Result<()> {
+        let parsed = super::super::backend::parse_target(target)?;
+        let session_ref = {
+            let state = global_state().lock();
+            state
+                .sessions
+                .get(&parsed.session)
+                .cloned()
+                .context(format!("Session '{}' not found", parsed.session))?
+        };
+        let old = session_ref
+            .lock()
+            .window(parsed.window_index)
+            .context(format!("Window '{}' not found", parsed.window_index))?;
+        let (name, working_dir, size) = {
+            let guard = old.lock();
+            (guard.name.clone(), guard.working_dir.clone(), guard.size)
+        };
+
+        if window_is_alive(&old)
+            && let Err(err) = kill_window_handle(&old)
+        {
+            warn!(target, error = %err, "Failed to kill mux window before respawning it");
+        }
+        let window = spawn_window(
+            &parsed.session,
+            parsed.window_index,
+            &name,
+            &working_dir,
+            command,
+            size,
+        )?;
+
+        let mut guard = session_ref.lock();
+        let Some(position) = guard.window_position(parsed.window_index) else {
+            drop(guard);
+            let _ = kill_window_handle(&window);
+            bail!(
+                "Window '{}' was closed while respawning",
+                parsed.window_index
+            );
+        };
+        guard.windows[position] = window;
+        drop(guard);
+        info!(target, "Mux window respawned");
+        Ok(())
+    }
+
     /// Kill a specific window in a session.
     ///
+    /// Other windows keep their indices.
+    ///
     /// # Errors
     ///
     /// Returns an error if the window cannot be killed.
@@ -222,18 +309,15 @@ impl Manager {
 
         let window = {
             let mut guard = session_ref.lock();
-            let idx = window_index as usize;
-            if idx >= guard.windows.len() {
+            let Some(position) = guard.window_position(window_index) else {
                 bail!("Window '{window_index}' not found");
-            }
-            guard.windows.remove(idx)
+            };
+            guard.windows.remove(position)
         };
 
         if let Err(err) = kill_window_handle(&window) {
             warn!(error = %err, "Failed to kill mux window");
         }
-
-        renumber_windows(&session_ref);
         Ok(())
     }
 
@@ -260,10 +344,13 @@ impl Manager {
         Ok(windows
             .into_iter()
             .map(|window| {
-                let window = window.lock();
+                let mut window = window.lock();
                 Window {
                     index: window.index,
                     name: window.name.clone(),
+                    title: window.title.clone(),
+                    pane_title: window.pane_title.clone(),
+                    exit_code: window.exit_code(),
                 }
             })
             .collect())
@@ -324,6 +411,27 @@ impl Manager {
         Ok(())
     }
 
+    /// Check whether a window's process is still running.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the target cannot be resolved.
+    pub fn window_alive(target: &str) -> Result<bool> {
+        let window = super::super::backend::resolve_window(target)?;
+        Ok(window_is_alive(&window))
+    }
+
+    /// Set the descriptive title of a window.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the target cannot be resolved.
+    pub fn set_title(target: &str, title: &str) -> Result<()> {
+        let window = super::super::backend::resolve_window(target)?;
+        window.lock().title = title.to_string();
+        Ok(())
+    }
+
     /// Rename a window in a session.
     ///
     /// # Errors
@@ -339,15 +447,10 @@ impl Manager {
                 .context(format!("Session '{session}' not found"))?
         };
 
-        let window = {
-            let guard = session_ref.lock();
-            let idx = window_index as usize;
-            guard
-                .windows
-                .get(idx)
-                .cloned()
-                .context(format!("Window '{window_index}' not found"))?
-        };
+        let window = session_ref
+            .lock()
+            .window(window_index)
+            .context(format!("Window '{window_index}' not found"))?;
 
         {
             let mut guard = window.lock();
@@ -366,6 +469,12 @@ pub struct Session {
     pub created: i64,
     /// Whether a client is attached to this session.
     pub attached: bool,
+    /// Working directory of the session's first window.
+    pub working_dir: Option<PathBuf>,
+    /// Exit code of the root window's process, once it has exited.
+    pub exit_code: Option<u32>,
+    /// State file of the Tenex instance that created the session.
+    pub owner: Option<String>,
 }
 
 /// Information about a window.
@@ -375,6 +484,12 @@ pub struct Window {
     pub index: u32,
     /// Window name.
     pub name: String,
+    /// Descriptive title set by Tenex (empty when unset).
+    pub title: String,
+    /// Title the program last set with OSC 0/2 (empty when unset).
+    pub pane_title: String,
+    /// Exit code of the window's process, once it has exited.
+    pub exit_code: Option<u32>,
 }
 
 fn enqueue_to_target(target: &str, payload: &[u8]) -> Result<()> {
@@ -402,184 +517,18 @@ fn kill_window_handle(
 
     {
         let mut guard = window.lock();
-        guard
-            .child
-            .kill()
-            .context("Failed to terminate PTY child")?;
-    }
-    Ok(())
-}
-
-fn renumber_windows(
-    session: &std::sync::Arc<parking_lot::Mutex<super::super::backend::MuxSession>>,
-) {
-    let guard = session.lock();
-    for (idx, window) in guard.windows.iter().enumerate() {
-        let mut window = window.lock();
-        window.index = u32::try_from(idx).unwrap_or(u32::MAX);
-    }
-}
-
-fn is_session_alive(
-    session: &std::sync::Arc<parking_lot::Mutex<super::super::backend::MuxSession>>,
-) -> bool {
-    const ROOT_RESTART_MAX_ATTEMPTS: u32 = 3;
-    const ROOT_RESTART_COOLDOWN_SECS: i64 = 1;
-
-    let (session_name, windows, root_restart_attempts, last_root_restart) = {
-        let guard = session.lock();
-        (
-            guard.name.clone(),
-            guard.windows.clone(),
-            guard.root_restart_attempts,
-            guard.last_root_restart,
-        )
-    };
-
-    let Some(root) = windows.first().cloned() else {
-        return false;
-    };
-
-    let root_alive = window_is_alive(&root);
-    if root_alive {
-        return true;
-    }
-
-    let non_root_alive = windows.iter().skip(1).any(window_is_alive);
-    if non_root_alive {
-        if should_restart_root_window(
-            root_restart_attempts,
-            unix_timestamp(),
-            last_root_restart,
-            ROOT_RESTART_MAX_ATTEMPTS,
-            ROOT_RESTART_COOLDOWN_SECS,
-        ) {
-            restart_root_window(session, &session_name);
+        if guard.exit_code().is_none() {
+            guard
+                .child
+                .kill()
+                .context("Failed to terminate PTY child")?;
         }
-        return true;
     }
-
-    let now = unix_timestamp();
-    if !should_restart_root_window(
-        root_restart_attempts,
-        now,
-        last_root_restart,
-        ROOT_RESTART_MAX_ATTEMPTS,
-        ROOT_RESTART_COOLDOWN_SECS,
-    ) {
-        return false;
-    }
-
-    restart_root_window(session, &session_name)
+    Ok(())
 }
 
 fn window_is_alive(
     window: &std::sync::Arc<parking_lot::Mutex<super::super::backend::MuxWindow>>,
 ) -> bool {
-    let mut guard = window.lock();
-    match guard.child.try_wait() {
-        Ok(None) | Err(_) => true,
-        Ok(Some(_)) => false,
-    }
-}
-
-const fn should_restart_root_window(
-    attempts: u32,
-    now: i64,
-    last_restart: i64,
-    max_attempts: u32,
-    cooldown_secs: i64,
-) -> bool {
-    if attempts >= max_attempts {
-        return false;
-    }
-
-    let since_last = now.saturating_sub(last_restart);
-    since_last >= cooldown_secs
-}
-
-fn restart_root_window(
-    session: &std::sync::Arc<parking_lot::Mutex<super::super::backend::MuxSession>>,
-    session_name: &str,
-) -> bool {
-    if !session_still_registered(session_name, session) {
-        return false;
-    }
-
-    let (working_dir, command, size) = {
-        let root = {
-            let guard = session.lock();
-            guard.windows.first().cloned()
-        };
-
-        let Some(root) = root else {
-            return false;
-        };
-
-        let guard = root.lock();
-        (guard.working_dir.clone(), guard.command.clone(), guard.size)
-    };
-
-    let new_root = match spawn_window(
-        0,
-        session_name,
-        &working_dir,
-        if command.is_empty() {
-            None
-        } else {
-            Some(&command)
-        },
-        size,
-    ) {
-        Ok(window) => window,
-        Err(err) => {
-            warn!(
-                session = session_name,
-                error = %err,
-                "Failed to restart root mux window"
-            );
-            return false;
-        }
-    };
-
-    let now = unix_timestamp();
-    apply_root_restart(session, session_name, new_root, now)
-}
-
-fn apply_root_restart(
-    session: &std::sync::Arc<parking_lot::Mutex<super::super::backend::MuxSession>>,
-    session_name: &str,
-    new_root: std::sync::Arc<parking_lot::Mutex<super::super::backend::MuxWindow>>,
-    now: i64,
-) -> bool {
-    if !session_still_registered(session_name, session) {
-        let _ = kill_window_handle(&new_root);
-        return false;
-    }
-
-    {
-        let mut guard = session.lock();
-        if guard.windows.is_empty() {
-            let _ = kill_window_handle(&new_root);
-            return false;
-        }
-
-        guard.windows[0] = new_root;
-        guard.root_restart_attempts = guard.root_restart_attempts.saturating_add(1);
-        guard.last_root_restart = now;
-    }
-
-    info!(session = session_name, "Restarted root mux window");
-    true
-}
-
-fn session_still_registered(
-    session_name: &str,
-    session: &std::sync::Arc<parking_lot::Mutex<super::super::backend::MuxSession>>,
-) -> bool {
-    let state = global_state().lock();
-    state
-        .sessions
-        .get(session_name)
-        .is_some_and(|stored| std::sync::Arc::ptr_eq(stored, session))
+    window.lock().exit_code().is_none()
 }
@@ -77,6 +77,11 @@ impl Manager {
             command,
             cols: super::backend::DEFAULT_COLS,
             rows: super::backend::DEFAULT_ROWS,
+            owner: Some(
+                crate::config::Config::state_path()
+                    .to_string_lossy()
+                    .into_owned(),
+            ),
         })? {
             MuxResponse::Ok => Ok(()),
             MuxResponse::Err { message } => bail!("{message}"),
@@ -141,6 +146,9 @@ impl Manager {
             name: info.name,
             created: info.created,
             attached: info.attached,
+            working_dir: info.working_dir.map(PathBuf::from),
+            exit_code: info.exit_code,
+            owner: info.owner.map(PathBuf::from),
         }
     }
 
@@ -368,6 +376,9 @@ impl Manager {
                 .map(|w| Window {
                     index: w.index,
                     name: w.name,
+                    title: w.title,
+                    pane_title: w.pane_title,
+                    exit_code: w.exit_code,
                 })
                 .collect()),
             MuxResponse::Err { message } => bail!("{message}"),
@@ -390,6 +401,38 @@ impl Manager {
         }
     }
 
+    /// Sample CPU and memory usage of each of `targets`' process trees, reading the process
+    /// table once. A target without a running process has no sample.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the process table cannot be read.
+    pub fn process_usages(&self, targets: &[String]) -> Result<Vec<Option<super::ProcessUsage>>> {
+        match super::client::request(&MuxRequest::ProcessUsages {
+            targets: targets.to_vec(),
+        })? {
+            MuxResponse::Usages { usages } => Ok(usages),
+            MuxResponse::Err { message } => bail!("{message}"),
+            other => bail!("Unexpected response: {other:?}"),
+        }
+    }
+
+    /// Stop every process in a target's process tree (`frozen`), or let them continue.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the target does not exist or the processes cannot be signalled.
+    pub fn freeze_processes(&self, target: &str, frozen: bool) -> Result<()> {
+        match super::client::request(&MuxRequest::FreezeProcesses {
+            target: target.to_string(),
+            frozen,
+        })? {
+            MuxResponse::Ok => Ok(()),
+            MuxResponse::Err { message } => bail!("{message}"),
+            other => bail!("Unexpected response: {other:?}"),
+        }
+    }
+
     /// Resize a window to specific dimensions.
     ///
     /// # Errors
@@ -407,6 +450,87 @@ impl Manager {
         }
     }
 
+    /// Resize several windows to the same dimensions in one round trip.
+    ///
+    /// Returns one result per target, in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mux daemon cannot be reached.
+    pub fn resize_windows(
+        &self,
+        targets: &[String],
+        width: u16,
+        height: u16,
+    ) -> Result<Vec<Result<()>>> {
+        let requests: Vec<MuxRequest> = targets
+            .iter()
+            .map(|target| MuxRequest::Resize {
+                target: target.clone(),
+                cols: width,
+                rows: height,
+            })
+            .collect();
+        Ok(super::client::request_batch(&requests)?
+            .into_iter()
+            .map(|response| match response {
+                MuxResponse::Ok => Ok(()),
+                MuxResponse::Err { message } => bail!("{message}"),
+                other => bail!("Unexpected response: {other:?}"),
+            })
+            .collect())
+    }
+
+    /// Replace the process of a target with `command` (the default shell when `None`), in the
+    /// same window and working directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the target does not exist or the process cannot be spawned.
+    pub fn respawn_window(&self, target: &str, command: Option<&[String]>) -> Result<()> {
+        match super::client::request(&MuxRequest::RespawnWindow {
+            target: target.to_string(),
+            command: command.map(<[String]>::to_vec).unwrap_or_default(),
+        })? {
+            MuxResponse::Ok => Ok(()),
+            MuxResponse::Err { message } => bail!("{message}"),
+            other => bail!("Unexpected response: {other:?}"),
+        }
+    }
+
+    /// Check whether a target's process is still running.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the target does not exist or the mux cannot be reached.
+    pub fn window_alive(&self, target: &str) -> Result<bool> {
+        match super::client::request(&MuxRequest::WindowAlive {
+            target: target.to_string(),
+        })? {
+            MuxResponse::Bool { value } => Ok(value),
+            MuxResponse::Err { message } => bail!("{message}"),
+            other => bail!("Unexpected response: {other:?}"),
+        }
+    }
+
+    /// Set the descriptive title of a target (`session` or `session:index`).
+    ///
+    /// An empty title clears it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the title cannot be set.
+    pub fn set_title(&self, target: &str, title: &str) -> Result<()> {
+        match super::client::request(&MuxRequest::SetTitle {
+            target: target.to_string(),
+            title: title.to_string(),
+        })? {
+            MuxResponse::Ok => Ok(()),
+            MuxResponse::Err { message } => bail!("{message}"),
+            other => bail!("Unexpected response: {other:?}"),
+        }
+    }
+
     /// Rename a window in a session.
     ///
     /// # Errors
@@ -488,6 +612,12 @@ pub struct Session {
     pub created: i64,
     /// Whether a client is attached (reserved for future use).
     pub attached: bool,
+    /// Working directory of the session's first window, if the daemon reports it.
+    pub working_dir: Option<PathBuf>,
+    /// Exit code of the root window's process, once it has exited.
+    pub exit_code: Option<u32>,
+    /// State file of the Tenex instance that created the session, if the daemon reports it.
+    pub owner: Option<PathBuf>,
 }
 
 /// Information about a mux window.
@@ -497,4 +627,10 @@ pub struct Window {
     pub index: u32,
     /// Window name.
     pub name: String,
+    /// Descriptive title set by Tenex, if any.
+    pub title: Option<String>,
+    /// Title the window's program last set with OSC 0/2, if any.
+    pub pane_title: Option<String>,
+    /// Exit code of the window's process, once it has exited.
+    pub exit_code: Option<u32>,
 }
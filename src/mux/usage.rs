@@ -0,0 +1,154 @@
+//! Process-tree resource sampling, and stopping and continuing process trees, for mux windows.
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::process::Command;
+
+/// Resource usage summed across a window's process tree.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessUsage {
+    /// Cumulative CPU time (user + system) in milliseconds.
+    pub cpu_time_ms: u64,
+    /// Resident set size in bytes.
+    pub rss_bytes: u64,
+    /// Number of processes in the tree.
+    pub processes: u32,
+}
+
+struct PsRow {
+    pid: u32,
+    ppid: u32,
+    rss_kib: u64,
+    cpu_time_ms: u64,
+}
+
+/// Parse `ps` CPU time (`[[dd-]hh:]mm:ss[.cc]`) into milliseconds.
+fn parse_cpu_time_ms(raw: &str) -> Option<u64> {
+    let (days, rest) = raw.split_once('-').map_or((0, raw), |(days, rest)| {
+        (days.parse::<u64>().unwrap_or(0), rest)
+    });
+
+    let mut total_ms = 0_u64;
+    for part in rest.split(':') {
+        let (whole, fraction) = part.split_once('.').unwrap_or((part, ""));
+        let whole = whole.parse::<u64>().ok()?;
+        total_ms = total_ms * 60 + whole * 1000;
+        if !fraction.is_empty() {
+            let digits = fraction.get(..fraction.len().min(3)).unwrap_or("");
+            let scale = 10_u64.pow(u32::try_from(3 - digits.len()).unwrap_or(0));
+            total_ms += digits.parse::<u64>().ok()? * scale;
+        }
+    }
+
+    Some(days * 86_400_000 + total_ms)
+}
+
+fn parse_ps_row(line: &str) -> Option<PsRow> {
+    let mut fields = line.split_whitespace();
+    Some(PsRow {
+        pid: fields.next()?.parse().ok()?,
+        ppid: fields.next()?.parse().ok()?,
+        rss_kib: fields.next()?.parse().ok()?,
+        cpu_time_ms: parse_cpu_time_ms(fields.next()?)?,
+    })
+}
+
+fn list_processes() -> Result<Vec<PsRow>> {
+    let output = Command::new("ps")
+        .args([
+            "-A", "-o", "pid=", "-o", "ppid=", "-o", "rss=", "-o", "time=",
+        ])
+        .output()
+        .context("Failed to run ps")?;
+    if !output.status.success() {
+        bail!("ps exited with {}", output.status);
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(parse_ps_row)
+        .collect())
+}
+
+/// The process table, read once so that many process trees can be summed from one `ps` run.
+pub struct ProcessTable {
+    rows: Vec<PsRow>,
+    children: HashMap<u32, Vec<usize>>,
+}
+
+impl ProcessTable {
+    /// Read the process table.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `ps` cannot be run.
+    pub fn read() -> Result<Self> {
+        let rows = list_processes()?;
+        let mut children: HashMap<u32, Vec<usize>> = HashMap::new();
+        for (idx, row) in rows.iter().enumerate() {
+            children.entry(row.ppid).or_default().push(idx);
+        }
+        Ok(Self { rows, children })
+    }
+
+    /// The rows of `root_pid` and all of its descendants, root first.
+    fn tree(&self, root_pid: u32) -> Vec<&PsRow> {
+        let mut tree = Vec::new();
+        let mut stack: Vec<usize> = self
+            .rows
+            .iter()
+            .position(|row| row.pid == root_pid)
+            .into_iter()
+            .collect();
+        while let Some(idx) = stack.pop() {
+            let Some(row) = self.rows.get(idx) else {
+                continue;
+            };
+            tree.push(row);
+            if let Some(kids) = self.children.get(&row.pid) {
+                stack.extend(kids.iter().copied().filter(|&kid| kid != idx));
+            }
+        }
+        tree
+    }
+
+    /// Resource usage summed over `root_pid` and all of its descendants.
+    #[must_use]
+    pub fn tree_usage(&self, root_pid: u32) -> ProcessUsage {
+        let mut usage = ProcessUsage::default();
+        for row in self.tree(root_pid) {
+            usage.cpu_time_ms += row.cpu_time_ms;
+            usage.rss_bytes += row.rss_kib * 1024;
+            usage.processes += 1;
+        }
+        usage
+    }
+}
+
+/// Stop (`SIGSTOP`) `root_pid` and all of its descendants, or continue (`SIGCONT`) them.
+///
+/// # Errors
+///
+/// Returns an error if the process table cannot be read or the signal cannot be sent.
+pub fn freeze_process_tree(root_pid: u32, frozen: bool) -> Result<()> {
+    let table = ProcessTable::read()?;
+    let pids: Vec<String> = table
+        .tree(root_pid)
+        .into_iter()
+        .map(|row| row.pid.to_string())
+        .collect();
+    if pids.is_empty() {
+        return Ok(());
+    }
+    let signal = if frozen { "-STOP" } else { "-CONT" };
+    let status = Command::new("kill")
+        .arg(signal)
+        .args(&pids)
+        .status()
+        .context("Failed to run kill")?;
+    if !status.success() {
+        bail!("kill {signal} exited with {status}");
+    }
+    Ok(())
+}
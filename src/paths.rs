@@ -10,12 +10,27 @@ pub fn log_path() -> PathBuf {
     std::env::temp_dir().join("tenex.log")
 }
 
+/// Path to the mux daemon's debug log file, next to [`log_path`].
+#[must_use]
+pub fn daemon_log_path() -> PathBuf {
+    std::env::temp_dir().join("tenex-muxd.log")
+}
+
 /// Locate the user's home directory without pulling in external crates.
 #[must_use]
 pub fn home_dir() -> Option<PathBuf> {
     std::env::var_os("HOME").map(PathBuf::from)
 }
 
+/// Resolve the user's configuration directory (`$XDG_CONFIG_HOME`, or `~/.config`).
+#[must_use]
+pub fn config_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|home| home.join(".config")))
+}
+
 /// Resolve the local application data directory for the current platform.
 #[must_use]
 pub fn data_local_dir() -> Option<PathBuf> {
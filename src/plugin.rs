@@ -0,0 +1,227 @@
+//! Plugins: external programs that add palette commands, sidebar columns, and detail panels.
+//!
+//! Each plugin is a directory under the instance's `plugins/` directory with a `plugin.json`
+//! manifest. For every request Tenex runs the manifest's `command` in the plugin directory,
+//! passes the request as JSON in `TENEX_PLUGIN_REQUEST`, and reads a JSON
+//! [`PluginResponse`] from stdout. A plugin may be written in any language.
+
+use crate::agent::Agent;
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::AtomicBool;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Manifest file in each plugin directory.
+pub const MANIFEST_FILE: &str = "plugin.json";
+
+/// Environment variable carrying the JSON request.
+pub const REQUEST_ENV: &str = "TENEX_PLUGIN_REQUEST";
+
+/// A palette command contributed by a plugin.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct PluginCommand {
+    /// Command name as shown in the palette; a missing leading `/` is added on load.
+    pub name: String,
+    /// Description shown in the palette.
+    #[serde(default)]
+    pub description: String,
+}
+
+/// A plugin's `plugin.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct PluginManifest {
+    /// Plugin name, shown in errors and panel headings.
+    pub name: String,
+    /// Command line run for every request; a relative program path resolves against the
+    /// plugin directory.
+    pub command: String,
+    /// Palette commands the plugin handles.
+    #[serde(default)]
+    pub commands: Vec<PluginCommand>,
+    /// Whether the plugin adds a value to each agent's sidebar row.
+    #[serde(default)]
+    pub column: bool,
+    /// Titles of the panels the plugin adds to the agent details modal.
+    #[serde(default)]
+    pub panels: Vec<String>,
+}
+
+/// A loaded plugin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plugin {
+    /// Directory holding the manifest; requests run here.
+    pub dir: PathBuf,
+    /// The parsed manifest.
+    pub manifest: PluginManifest,
+}
+
+/// What a plugin is told about an agent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AgentContext {
+    /// Agent id.
+    pub id: Uuid,
+    /// Agent title.
+    pub title: String,
+    /// Program the agent runs.
+    pub program: String,
+    /// Status label.
+    pub status: String,
+    /// The agent's branch.
+    pub branch: String,
+    /// Directory the agent works in.
+    pub workdir: PathBuf,
+    /// Repository the agent belongs to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repo_root: Option<PathBuf>,
+    /// Ticket the agent works on.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ticket: Option<String>,
+    /// Parent agent id (`None` for roots).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<Uuid>,
+}
+
+impl From<&Agent> for AgentContext {
+    fn from(agent: &Agent) -> Self {
+        Self {
+            id: agent.id,
+            title: agent.title.clone(),
+            program: agent.program.clone(),
+            status: agent.status.to_string(),
+            branch: agent.branch.clone(),
+            workdir: agent.worktree_path.clone(),
+            repo_root: agent.repo_root.clone(),
+            ticket: agent.ticket.clone(),
+            parent_id: agent.parent_id,
+        }
+    }
+}
+
+/// A request sent to a plugin.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum PluginRequest {
+    /// Run palette command `name`.
+    Command {
+        /// Command name without the leading `/`.
+        name: String,
+        /// The selected agent, if any.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        agent: Option<AgentContext>,
+    },
+    /// Render detail panel `name` for `agent`.
+    Panel {
+        /// Panel title from the manifest.
+        name: String,
+        /// The agent whose details are shown.
+        agent: AgentContext,
+    },
+    /// Compute the sidebar column for `agents`.
+    Column {
+        /// Every stored agent.
+        agents: Vec<AgentContext>,
+    },
+}
+
+/// A plugin's answer. Every field is optional.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct PluginResponse {
+    /// Status bar message (commands).
+    #[serde(default)]
+    pub status: Option<String>,
+    /// Text lines (a command's modal, or a panel's body).
+    #[serde(default)]
+    pub lines: Vec<String>,
+    /// Column values keyed by agent id (columns).
+    #[serde(default)]
+    pub values: HashMap<String, String>,
+    /// Error message; the request failed when set.
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// Plugins installed under `dir`, sorted by name. Directories without a readable manifest are
+/// skipped with a warning.
+#[must_use]
+pub fn load_plugins(dir: &Path) -> Vec<Plugin> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut plugins: Vec<Plugin> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.join(MANIFEST_FILE).is_file())
+        .filter_map(|dir| match load_manifest(&dir) {
+            Ok(manifest) => Some(Plugin { dir, manifest }),
+            Err(err) => {
+                warn!(path = %dir.display(), error = %err, "Skipping plugin");
+                None
+            }
+        })
+        .collect();
+    plugins.sort_by(|a, b| a.manifest.name.cmp(&b.manifest.name));
+    plugins
+}
+
+fn load_manifest(dir: &Path) -> Result<PluginManifest> {
+    let path = dir.join(MANIFEST_FILE);
+    let contents =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut manifest: PluginManifest = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    for command in &mut manifest.commands {
+        if !command.name.starts_with('/') {
+            command.name.insert(0, '/');
+        }
+    }
+    Ok(manifest)
+}
+
+/// Send `request` to `plugin` and wait for its response, killing it once `cancelled` is set.
+///
+/// # Errors
+///
+/// Returns an error if the plugin cannot be run, exits unsuccessfully, prints something other
+/// than a JSON response, or reports an error.
+pub fn invoke(
+    plugin: &Plugin,
+    request: &PluginRequest,
+    cancelled: &AtomicBool,
+) -> Result<PluginResponse> {
+    let name = &plugin.manifest.name;
+    let command_line = crate::command::parse_command_line(&plugin.manifest.command)
+        .with_context(|| format!("Invalid command for plugin {name}"))?;
+    let Some((program, args)) = command_line.split_first() else {
+        bail!("Plugin {name} has no command");
+    };
+    let program = if program.contains('/') && Path::new(program).is_relative() {
+        plugin.dir.join(program)
+    } else {
+        PathBuf::from(program)
+    };
+    let request = serde_json::to_string(request).context("Failed to serialize plugin request")?;
+
+    let mut command = Command::new(&program);
+    command
+        .args(args)
+        .current_dir(&plugin.dir)
+        .env(REQUEST_ENV, request);
+    let output = crate::git::output_cancellable(&mut command, cancelled)
+        .with_context(|| format!("Failed to run plugin {name}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("Plugin {name} failed: {}", stderr.trim());
+    }
+
+    let response: PluginResponse = serde_json::from_slice(&output.stdout)
+        .with_context(|| format!("Plugin {name} did not print a JSON response"))?;
+    if let Some(error) = &response.error {
+        bail!("Plugin {name}: {error}");
+    }
+    Ok(response)
+}
@@ -88,6 +88,70 @@ pub fn build_review_prompt(base_branch: &str) -> String {
     REVIEW_PREAMBLE.replace("$BASE_BRANCH", base_branch)
 }
 
+/// Built-in review focuses, as `(name, instructions)`
+pub const DEFAULT_REVIEW_FOCUSES: &[(&str, &str)] = &[
+    (
+        "Security",
+        "Concentrate on security: untrusted input, injection, authentication and authorization, \
+         secrets and sensitive data, unsafe defaults, and dependencies with known issues.",
+    ),
+    (
+        "Performance",
+        "Concentrate on performance: algorithmic complexity, work on hot paths, allocations and \
+         copies, blocking calls, I/O and query patterns, and caching.",
+    ),
+    (
+        "Tests",
+        "Concentrate on testing: whether the changed behavior is covered, missing edge and error \
+         cases, brittle or flaky tests, and tests that do not assert what they claim to.",
+    ),
+    (
+        "API design",
+        "Concentrate on API design: naming, consistency with existing interfaces, backwards \
+         compatibility, error types and messages, and how easy the interfaces are to misuse.",
+    ),
+];
+
+/// Built-in canned responses, as `(name, text)`
+pub const DEFAULT_CANNED_RESPONSES: &[(&str, &str)] = &[
+    ("approve", "Looks good. Go ahead."),
+    (
+        "write tests too",
+        "Also write tests that cover this change, and make sure they pass.",
+    ),
+    (
+        "stop and summarize",
+        "Stop here and summarize what you have done, what is left, and anything that is \
+         blocking you.",
+    ),
+    (
+        "commit",
+        "Commit your work with a clear message describing the change.",
+    ),
+];
+
+/// Give a review prompt a focus, so reviewers in the same swarm cover different ground
+#[must_use]
+pub fn build_review_focus_prompt(prompt: &str, name: &str, instructions: &str) -> String {
+    format!(
+        "{}\n\n**Review Focus: {name}**\n{instructions} Other reviewers cover other areas, so go \
+         deep on this one; mention anything else only if it is serious.",
+        prompt.trim_end()
+    )
+}
+
+/// Point a review prompt at the patch of the change set written for the reviewers
+#[must_use]
+pub fn build_review_packet_prompt(prompt: &str, patch_path: &str) -> String {
+    format!(
+        "{}\n\n**Review Packet:** {patch_path} holds the full diff of this branch against the \
+         base branch, including uncommitted changes, as of when you were started. Read it first \
+         instead of rediscovering the changes with git; use git only to check anything that \
+         changed since.",
+        prompt.trim_end()
+    )
+}
+
 /// Preamble for planning-only child agents
 ///
 /// This prompt instructs agents to focus on research and planning
@@ -125,6 +189,171 @@ pub fn build_plan_prompt(task: &str) -> String {
     format!("{}\n{}", PLAN_PREAMBLE, task.trim())
 }
 
+/// Default note sent ahead of a task revised with `EditTask`
+pub const REVISED_TASK_PREAMBLE: &str = "Your task has been revised. These instructions replace your original task; keep any work that still applies:";
+
+/// Build the message that sends a revised task, with `preamble` (if any) ahead of it
+#[must_use]
+pub fn build_revised_task_prompt(preamble: &str, task: &str) -> String {
+    let preamble = preamble.trim();
+    if preamble.is_empty() {
+        task.trim().to_string()
+    } else {
+        format!("{preamble}\n{}", task.trim())
+    }
+}
+
+/// Heading for the list of files the user attached to a new agent's prompt
+pub const CONTEXT_FILES_HEADER: &str =
+    "The user attached these files for context. Read them before you start:";
+
+/// Build a prompt that ends with references to the attached context `files`
+#[must_use]
+pub fn build_context_files_prompt(prompt: Option<&str>, files: &[String]) -> String {
+    let list = files
+        .iter()
+        .map(|file| format!("- {file}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let note = format!("{CONTEXT_FILES_HEADER}\n{list}");
+    match prompt.map(str::trim) {
+        Some(prompt) if !prompt.is_empty() => format!("{prompt}\n\n{note}"),
+        _ => note,
+    }
+}
+
+/// Build the prompt for an agent working on GitHub issue `number`, ending with a reminder to
+/// link the pull request back to the issue
+#[must_use]
+pub fn build_issue_prompt(number: u64, title: &str, url: &str, body: &str) -> String {
+    let mut heading = format!("Resolve GitHub issue #{number}: {}", title.trim());
+    if !url.trim().is_empty() {
+        heading = format!("{heading}\n{}", url.trim());
+    }
+    let mut sections = vec![heading];
+    if !body.trim().is_empty() {
+        sections.push(body.trim().to_string());
+    }
+    sections.push(format!(
+        "When you open a pull request for this work, include \"Closes #{number}\" in its description so it links back to the issue."
+    ));
+    sections.join("\n\n")
+}
+
+/// Location of a swarm's shared scratchpad, relative to the root worktree
+pub const SCRATCHPAD_PATH: &str = ".tenex/scratchpad.md";
+
+/// Note appended to child prompts pointing at the swarm's shared scratchpad
+pub const SCRATCHPAD_NOTE: &str = "Shared scratchpad: .tenex/scratchpad.md in this worktree is shared by every agent in this swarm and the user. Read it before you start and check it as you work. Add short notes there (what you are taking on, findings, blockers) so others can coordinate with you, and append rather than rewrite other people's notes.";
+
+/// Append the shared scratchpad note to a child agent's prompt
+#[must_use]
+pub fn build_scratchpad_prompt(prompt: &str) -> String {
+    format!("{}\n\n{SCRATCHPAD_NOTE}", prompt.trim_end())
+}
+
+/// Tell an agent to keep its changes within `scope`, a directory of the repository
+#[must_use]
+pub fn build_scope_note(scope: &str) -> String {
+    format!(
+        "Scope: work only in {scope} of this repository. Read anything you need, but do not \
+         change files outside {scope}. If the task needs changes elsewhere, say so and stop \
+         instead of making them."
+    )
+}
+
+/// Tell an agent that its scope was cleared
+pub const SCOPE_CLEARED_NOTE: &str = "Scope: you may now change files anywhere in this repository.";
+
+/// Append the scope note for `scope` to an agent's prompt
+#[must_use]
+pub fn build_scope_prompt(prompt: &str, scope: &str) -> String {
+    format!("{}\n\n{}", prompt.trim_end(), build_scope_note(scope))
+}
+
+/// Name of a root agent's handoff notes file, in its worktree's `.tenex` directory
+pub const HANDOFF_FILE_NAME: &str = "handoff.md";
+
+/// Ask an agent to write its handoff notes to `path` before it is stopped
+#[must_use]
+pub fn build_handoff_request(path: &str) -> String {
+    format!(
+        "You are about to be stopped. Summarize your progress into {path} so the next agent can \
+         pick up where you left off: the goal, what you did, what is left, and anything you \
+         learned that is not obvious from the code. Write the whole file at once, then stop."
+    )
+}
+
+/// Name a consumed handoff notes file is moved to, so a later agent does not pick it up again
+pub const PREVIOUS_HANDOFF_FILE_NAME: &str = "handoff-previous.md";
+
+/// Intro of the notes a replacement agent resumes from
+pub const HANDOFF_RESUME_PREAMBLE: &str = "You are taking over from an earlier agent that worked in this worktree. It left these handoff notes; resume from where it stopped rather than starting over.";
+
+/// Prepend the handoff notes of an earlier agent to a replacement agent's `prompt`
+#[must_use]
+pub fn build_handoff_resume_prompt(notes: &str, prompt: Option<&str>) -> String {
+    let mut combined = format!(
+        "{HANDOFF_RESUME_PREAMBLE}\n\n<handoff>\n{}\n</handoff>",
+        notes.trim()
+    );
+    if let Some(prompt) = prompt.map(str::trim).filter(|prompt| !prompt.is_empty()) {
+        combined.push_str("\n\n");
+        combined.push_str(prompt);
+    }
+    combined
+}
+
+/// Ask an agent to summarize its work to `path` before it is restarted with a fresh context
+#[must_use]
+pub fn build_compact_request(path: &str) -> String {
+    format!(
+        "Your context is about to be reset. Summarize your work into {path}: the goal, what you \
+         did, what is left, the files that matter, and anything you learned that is not obvious \
+         from the code. You will be restarted with only this summary, so include everything you \
+         need to continue. Write the whole file at once, then stop."
+    )
+}
+
+/// Intro of the summary an agent restarted by a compact resumes from
+pub const COMPACT_RESUME_PREAMBLE: &str = "Your session was restarted with a fresh context to free up room. Before the restart you wrote this summary of your work; continue from where you stopped rather than starting over.";
+
+/// The opening prompt of an agent restarted from its own `summary`
+#[must_use]
+pub fn build_compact_resume_prompt(summary: &str) -> String {
+    format!(
+        "{COMPACT_RESUME_PREAMBLE}\n\n<summary>\n{}\n</summary>",
+        summary.trim()
+    )
+}
+
+/// Ask an agent that reached its maximum runtime of `minutes` to wrap up before it is stopped
+#[must_use]
+pub fn build_wrap_up_request(minutes: u64) -> String {
+    format!(
+        "You have reached your time limit of {minutes} minutes and will be stopped shortly. Wrap \
+         up now: commit or save your work, then summarize what you did, what is left, and \
+         anything the next person needs to know. Do not start anything new."
+    )
+}
+
+/// Split a prompt built by [`build_plan_prompt`] or [`build_review_prompt`] into the name of
+/// its preamble, the preamble itself, and the task that follows it.
+///
+/// Returns `None` for prompts that don't start with a Tenex preamble.
+#[must_use]
+pub fn split_preamble(prompt: &str) -> Option<(&'static str, &str, &str)> {
+    if let Some(task) = prompt.strip_prefix(PLAN_PREAMBLE) {
+        return Some(("Planning", PLAN_PREAMBLE, task.trim()));
+    }
+    let review_intro = REVIEW_PREAMBLE
+        .split_once("$BASE_BRANCH")
+        .map_or(REVIEW_PREAMBLE, |(intro, _)| intro);
+    prompt
+        .starts_with(review_intro)
+        .then_some(("Review", prompt, ""))
+}
+
 /// Synthesis prompt template for aggregating child agent findings
 pub const SYNTHESIS_TEMPLATE: &str = r"Here are findings from $COUNT parallel research sessions:
 
@@ -136,6 +365,17 @@ Please synthesize these findings and proceed with implementation. Focus on:
 3. Any contradictions and how to resolve them
 4. A unified implementation approach based on the collective research";
 
+/// Ask an agent program to summarize the transcript of agent `title` saved at `path`, for a
+/// synthesis
+#[must_use]
+pub fn build_synthesis_summary_request(path: &str, title: &str) -> String {
+    format!(
+        "Read {path}. It is the terminal transcript of the agent \"{title}\", which is being \
+         merged back into its parent. Summarize what the agent was asked to do, what it did and \
+         found, what it changed, and what is left unfinished. Reply with the summary only."
+    )
+}
+
 /// Build a synthesis prompt from multiple agent findings
 #[must_use]
 pub fn build_synthesis_prompt(findings: &[(String, String)]) -> String {
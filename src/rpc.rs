@@ -0,0 +1,318 @@
+//! JSON-RPC server for editor integrations (`tenex rpc`).
+//!
+//! An editor extension starts `tenex rpc` and exchanges JSON-RPC 2.0 messages with it over
+//! stdin and stdout, one message per line. Methods:
+//!
+//! - `agents.list`: every agent, with its id, title, status, branch, and worktree.
+//! - `agents.worktree` (`id`): the directory to open for an agent.
+//! - `agents.files` (`id`, optional `lines`): files mentioned in the agent's recent output that
+//!   exist in its worktree, newest first, with line and column when given.
+//! - `agents.send` (`id`, `prompt`): type `prompt` into the agent and submit it.
+//!
+//! Agent state is re-read before each request, so agents created in the TUI show up right away.
+
+use std::collections::HashSet;
+use std::io::{BufRead, Write};
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::agent::Agent;
+use crate::api::Tenex;
+
+/// Output lines scanned for file mentions when the request does not say.
+const DEFAULT_FILE_SCAN_LINES: usize = 500;
+
+/// Most file mentions returned by `agents.files`.
+const MAX_FILE_MENTIONS: usize = 100;
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SERVER_ERROR: i64 = -32000;
+
+#[derive(Debug, Deserialize)]
+struct Request {
+    /// Absent for notifications, which get no response.
+    #[serde(default)]
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Debug, Serialize)]
+struct Response {
+    jsonrpc: &'static str,
+    id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Serialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AgentParams {
+    id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+struct FilesParams {
+    id: Uuid,
+    #[serde(default)]
+    lines: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SendParams {
+    id: Uuid,
+    prompt: String,
+}
+
+/// What `agents.list` reports about an agent.
+#[derive(Debug, Serialize)]
+struct AgentSummary<'a> {
+    id: Uuid,
+    title: &'a str,
+    status: String,
+    program: &'a str,
+    branch: &'a str,
+    worktree: &'a Path,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    repo_root: Option<&'a Path>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_id: Option<Uuid>,
+}
+
+impl<'a> From<&'a Agent> for AgentSummary<'a> {
+    fn from(agent: &'a Agent) -> Self {
+        Self {
+            id: agent.id,
+            title: &agent.title,
+            status: agent.status.to_string(),
+            program: &agent.program,
+            branch: &agent.branch,
+            worktree: &agent.worktree_path,
+            repo_root: agent.repo_root.as_deref(),
+            parent_id: agent.parent_id,
+        }
+    }
+}
+
+/// A file named in an agent's output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileMention {
+    /// Absolute path of the file.
+    pub path: PathBuf,
+    /// 1-based line, when the mention had one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    /// 1-based column, when the mention had one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<u32>,
+}
+
+/// Serve JSON-RPC requests from stdin until it closes.
+///
+/// # Errors
+///
+/// Returns an error if the instance cannot be opened or stdin/stdout fail.
+pub fn serve() -> Result<()> {
+    let mut tenex = Tenex::open()?;
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout().lock();
+
+    for line in stdin.lock().lines() {
+        let line = line.context("Failed to read JSON-RPC request")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = handle_line(&mut tenex, &line) {
+            let mut encoded =
+                serde_json::to_string(&response).context("Failed to encode JSON-RPC response")?;
+            encoded.push('\n');
+            stdout
+                .write_all(encoded.as_bytes())
+                .and_then(|()| stdout.flush())
+                .context("Failed to write JSON-RPC response")?;
+        }
+    }
+    Ok(())
+}
+
+fn handle_line(tenex: &mut Tenex, line: &str) -> Option<Response> {
+    let value: Value = match serde_json::from_str(line) {
+        Ok(value) => value,
+        Err(err) => {
+            return Some(error_response(
+                Value::Null,
+                RpcError::new(PARSE_ERROR, err.to_string()),
+            ));
+        }
+    };
+    let request: Request = match serde_json::from_value(value) {
+        Ok(request) => request,
+        Err(err) => {
+            return Some(error_response(
+                Value::Null,
+                RpcError::new(INVALID_REQUEST, err.to_string()),
+            ));
+        }
+    };
+
+    let result = dispatch(tenex, &request.method, request.params);
+    let id = request.id?;
+    Some(match result {
+        Ok(result) => Response {
+            jsonrpc: "2.0",
+            id,
+            result: Some(result),
+            error: None,
+        },
+        Err(error) => error_response(id, error),
+    })
+}
+
+const fn error_response(id: Value, error: RpcError) -> Response {
+    Response {
+        jsonrpc: "2.0",
+        id,
+        result: None,
+        error: Some(error),
+    }
+}
+
+fn dispatch(tenex: &mut Tenex, method: &str, params: Value) -> Result<Value, RpcError> {
+    tenex.reload().map_err(|err| server_error(&err))?;
+    match method {
+        "agents.list" => {
+            let agents: Vec<AgentSummary<'_>> = tenex.agents().map(AgentSummary::from).collect();
+            Ok(json!(agents))
+        }
+        "agents.worktree" => {
+            let AgentParams { id } = parse_params(params)?;
+            let agent = require_agent(tenex, id)?;
+            Ok(json!({ "path": agent.worktree_path }))
+        }
+        "agents.files" => {
+            let FilesParams { id, lines } = parse_params(params)?;
+            let workdir = require_agent(tenex, id)?.worktree_path.clone();
+            let output = tenex
+                .output(id, lines.unwrap_or(DEFAULT_FILE_SCAN_LINES))
+                .map_err(|err| server_error(&err))?;
+            Ok(json!(file_mentions(&output, &workdir)))
+        }
+        "agents.send" => {
+            let SendParams { id, prompt } = parse_params(params)?;
+            require_agent(tenex, id)?;
+            tenex.send(id, &prompt).map_err(|err| server_error(&err))?;
+            Ok(Value::Null)
+        }
+        other => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("Unknown method: {other}"),
+        )),
+    }
+}
+
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    serde_json::from_value(params).map_err(|err| RpcError::new(INVALID_PARAMS, err.to_string()))
+}
+
+fn require_agent(tenex: &Tenex, id: Uuid) -> Result<&Agent, RpcError> {
+    tenex
+        .agent(id)
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, format!("Agent {id} not found")))
+}
+
+fn server_error(err: &anyhow::Error) -> RpcError {
+    RpcError::new(SERVER_ERROR, format!("{err:#}"))
+}
+
+/// Files under `workdir` that `output` mentions, newest mention first and without duplicates.
+///
+/// Mentions look like `src/main.rs`, `src/main.rs:42`, or `src/main.rs:42:7`, relative to
+/// `workdir` or absolute.
+#[must_use]
+pub fn file_mentions(output: &[String], workdir: &Path) -> Vec<FileMention> {
+    let mut seen = HashSet::new();
+    let mut mentions = Vec::new();
+    for token in output
+        .iter()
+        .rev()
+        .flat_map(|line| line.split(is_separator).rev())
+    {
+        let Some(mention) = parse_mention(token, workdir) else {
+            continue;
+        };
+        if seen.insert((mention.path.clone(), mention.line)) {
+            mentions.push(mention);
+            if mentions.len() == MAX_FILE_MENTIONS {
+                break;
+            }
+        }
+    }
+    mentions
+}
+
+const fn is_separator(c: char) -> bool {
+    c.is_whitespace()
+        || matches!(
+            c,
+            '"' | '\'' | '`' | '(' | ')' | '[' | ']' | '<' | '>' | ','
+        )
+}
+
+fn parse_mention(token: &str, workdir: &Path) -> Option<FileMention> {
+    let token = token.trim_end_matches(['.', ':', ';']);
+    let mut path = token;
+    let mut numbers = Vec::new();
+    while numbers.len() < 2 {
+        if let Some((rest, number)) = path.rsplit_once(':')
+            && let Ok(number) = number.parse::<u32>()
+        {
+            numbers.push(number);
+            path = rest;
+        } else {
+            break;
+        }
+    }
+    numbers.reverse();
+
+    if path.is_empty() || !path.contains(['/', '.']) {
+        return None;
+    }
+    let path = workdir.join(path.strip_prefix("./").unwrap_or(path));
+    // `starts_with` compares components as written, so `..` could climb out of `workdir`.
+    let escapes = path
+        .components()
+        .any(|component| component == Component::ParentDir);
+    if escapes || !path.starts_with(workdir) || !path.is_file() {
+        return None;
+    }
+    Some(FileMention {
+        path,
+        line: numbers.first().copied(),
+        column: numbers.get(1).copied(),
+    })
+}
@@ -0,0 +1,177 @@
+//! Devcontainer discovery for Docker-backed agents.
+//!
+//! When a worktree ships a `devcontainer.json`, Docker roots use its image (or build its
+//! Dockerfile) instead of the shipped Tenex worker image so agents get the project's toolchain.
+
+use std::path::{Path, PathBuf};
+
+use tracing::warn;
+
+/// Where the image for a Docker root should come from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum ImageSource {
+    /// A prebuilt image reference to pull when missing.
+    Image(String),
+    /// A Dockerfile to build, with its build context.
+    Build {
+        dockerfile: PathBuf,
+        context: PathBuf,
+    },
+}
+
+const CONFIG_CANDIDATES: &[&str] = &[".devcontainer/devcontainer.json", ".devcontainer.json"];
+
+/// Find the devcontainer image source for a worktree, if it has a usable config.
+pub(super) fn find(worktree: &Path) -> Option<ImageSource> {
+    CONFIG_CANDIDATES.iter().find_map(|candidate| {
+        let path = worktree.join(candidate);
+        let contents = std::fs::read_to_string(&path).ok()?;
+        let config_dir = path.parent()?;
+        let value = match serde_json::from_str(&strip_jsonc(&contents)) {
+            Ok(value) => value,
+            Err(err) => {
+                warn!(path = %path.display(), error = %err, "Ignoring devcontainer config that can't be parsed");
+                return None;
+            }
+        };
+        parse(&value, config_dir)
+    })
+}
+
+fn parse(value: &serde_json::Value, config_dir: &Path) -> Option<ImageSource> {
+    if let Some(image) = value
+        .get("image")
+        .and_then(serde_json::Value::as_str)
+        .map(str::trim)
+        .filter(|image| !image.is_empty())
+    {
+        return Some(ImageSource::Image(image.to_string()));
+    }
+
+    let build = value.get("build");
+    let dockerfile = build
+        .and_then(|build| build.get("dockerfile"))
+        .or_else(|| value.get("dockerFile"))
+        .and_then(serde_json::Value::as_str)?;
+    let context = build
+        .and_then(|build| build.get("context"))
+        .or_else(|| value.get("context"))
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or(".");
+
+    Some(ImageSource::Build {
+        dockerfile: config_dir.join(dockerfile),
+        context: config_dir.join(context),
+    })
+}
+
+/// Strip `//` and `/* */` comments, then trailing commas, so `serde_json` accepts JSONC.
+fn strip_jsonc(contents: &str) -> String {
+    strip_trailing_commas(&strip_comments(contents))
+}
+
+/// `contents` without `//` and `/* */` comments outside strings.
+fn strip_comments(contents: &str) -> String {
+    let mut out = String::with_capacity(contents.len());
+    let mut chars = contents.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(ch) = chars.next() {
+        if in_string {
+            out.push(ch);
+            match ch {
+                '\\' => out.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match (ch, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                out.push(ch);
+            }
+            ('/', Some('/')) => {
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = '\0';
+                for next in chars.by_ref() {
+                    if prev == '*' && next == '/' {
+                        break;
+                    }
+                    prev = next;
+                }
+            }
+            _ => out.push(ch),
+        }
+    }
+
+    out
+}
+
+/// `contents` without commas directly before a closing `}` or `]` outside strings.
+fn strip_trailing_commas(contents: &str) -> String {
+    let mut out = String::with_capacity(contents.len());
+    let mut chars = contents.chars();
+    let mut in_string = false;
+
+    while let Some(ch) = chars.next() {
+        if in_string {
+            out.push(ch);
+            match ch {
+                '\\' => out.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match ch {
+            '"' => {
+                in_string = true;
+                out.push(ch);
+            }
+            ',' => {
+                let rest = chars.clone().find(|c| !c.is_whitespace());
+                if !matches!(rest, Some('}' | ']')) {
+                    out.push(ch);
+                }
+            }
+            _ => out.push(ch),
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_jsonc_drops_a_trailing_comma_before_a_comment() -> anyhow::Result<()> {
+        let contents =
+            "{\n  \"image\": \"rust:1\", // the toolchain\n  \"x\": [1, /* two */],\n}\n";
+        let value: serde_json::Value = serde_json::from_str(&strip_jsonc(contents))?;
+        assert_eq!(value["image"], "rust:1");
+        assert_eq!(value["x"], serde_json::json!([1]));
+        Ok(())
+    }
+
+    #[test]
+    fn strip_jsonc_keeps_comment_markers_and_commas_inside_strings() -> anyhow::Result<()> {
+        let contents = r#"{"image": "host//a,}", "note": "/* not a comment */"}"#;
+        let value: serde_json::Value = serde_json::from_str(&strip_jsonc(contents))?;
+        assert_eq!(value["image"], "host//a,}");
+        assert_eq!(value["note"], "/* not a comment */");
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 //! Docker runtime support for agent processes.
 
+use super::devcontainer::ImageSource;
 use crate::agent::Agent;
 use crate::app::Settings;
 use crate::paths;
@@ -117,7 +118,7 @@ fn ensure_container_with_paths(
     host_paths: &DockerHostPaths,
 ) -> Result<()> {
     check_available()?;
-    ensure_image_ready(settings, &agent.program)?;
+    let image = resolve_agent_image(agent, settings)?;
     let name = container_name(agent);
     let inspect = run_command(
         docker_command().args(["inspect", "--format", "{{.State.Running}}", &name]),
@@ -167,7 +168,6 @@ fn ensure_container_with_paths(
         }
     }
 
-    let image = worker_image_tag(settings);
     let worktree = &agent.worktree_path;
     let worktree_target = container_target_path(worktree);
     let mut cmd = docker_command();
@@ -191,7 +191,7 @@ fn ensure_container_with_paths(
     cmd.args(["-w", &display_path(&worktree_target)]);
     add_bind_mount(&mut cmd, worktree, &worktree_target, false);
 
-    cmd.arg(image);
+    cmd.arg(&image);
     cmd.arg("sleep");
     cmd.arg("infinity");
 
@@ -199,6 +199,85 @@ fn ensure_container_with_paths(
     Ok(())
 }
 
+/// Pick the image for an agent's container, preparing it if needed.
+///
+/// A worktree's devcontainer config wins over the `docker_image` setting, which wins over the
+/// shipped Tenex worker image.
+fn resolve_agent_image(agent: &Agent, settings: &Settings) -> Result<String> {
+    match super::devcontainer::find(&agent.worktree_path) {
+        Some(ImageSource::Image(image)) => {
+            ensure_custom_image_present(&image)?;
+            Ok(image)
+        }
+        Some(ImageSource::Build {
+            dockerfile,
+            context,
+        }) => build_devcontainer_image(&dockerfile, &context),
+        None => {
+            if let Some(image) = settings
+                .docker_image
+                .as_deref()
+                .map(str::trim)
+                .filter(|image| !image.is_empty())
+            {
+                ensure_custom_image_present(image)?;
+                return Ok(image.to_string());
+            }
+            ensure_image_ready(settings, &agent.program)?;
+            Ok(worker_image_tag(settings).to_string())
+        }
+    }
+}
+
+fn image_exists(image: &str) -> bool {
+    docker_command()
+        .args(["image", "inspect", image])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+fn ensure_custom_image_present(image: &str) -> Result<()> {
+    if image_exists(image) {
+        return Ok(());
+    }
+
+    run_command(
+        docker_command().args(["pull", image]),
+        "Failed to pull Docker image",
+    )?;
+    Ok(())
+}
+
+/// Build the devcontainer image for `dockerfile` and `context`, tagged after both.
+///
+/// The build runs every time, so a change to a file the Dockerfile copies from the context is
+/// picked up; Docker's layer cache keeps an unchanged build quick.
+fn build_devcontainer_image(dockerfile: &Path, context: &Path) -> Result<String> {
+    let contents = std::fs::read(dockerfile).with_context(|| {
+        format!(
+            "Failed to read devcontainer Dockerfile {}",
+            dockerfile.display()
+        )
+    })?;
+    let mut key = contents;
+    key.extend_from_slice(display_path(context).as_bytes());
+    let image = format!("tenex-devcontainer:{:016x}", fnv1a64(&key));
+    run_command(
+        docker_command().args([
+            "build",
+            "--tag",
+            &image,
+            "--file",
+            &display_path(dockerfile),
+            &display_path(context),
+        ]),
+        "Failed to build devcontainer image",
+    )?;
+    Ok(image)
+}
+
 pub(super) fn remove_container(agent: &Agent) -> Result<()> {
     remove_container_by_name(&container_name(agent))
 }
@@ -1,6 +1,8 @@
 //! Runtime-specific command construction and setup.
 
+mod devcontainer;
 mod docker;
+mod sandbox;
 
 use crate::agent::{Agent, AgentRuntime};
 use crate::app::Settings;
@@ -94,8 +96,9 @@ pub fn build_agent_command(
         }
     };
 
+    // Docker containers already isolate the agent, so the sandbox only wraps host processes.
     match agent.runtime {
-        AgentRuntime::Host => Ok(base),
+        AgentRuntime::Host => Ok(sandbox::wrap(agent, &base)),
         AgentRuntime::Docker => Ok(docker::wrap_exec(agent, settings, &base)),
     }
 }
@@ -132,10 +135,12 @@ pub fn build_terminal_command(
 ///
 /// # Errors
 ///
-/// Returns an error if the Docker container cannot be inspected, started, or created.
+/// Returns an error if the Docker container cannot be inspected, started, or created, or if
+/// the agent is sandboxed and bubblewrap is unavailable.
 pub fn ensure_runtime_ready(agent: &Agent, settings: &Settings) -> Result<()> {
     match agent.runtime {
-        AgentRuntime::Host => Ok(()),
+        AgentRuntime::Host if agent.sandbox.is_off() || agent.is_terminal_agent() => Ok(()),
+        AgentRuntime::Host => sandbox::check_available(),
         AgentRuntime::Docker => docker::ensure_container(agent, settings),
     }
 }
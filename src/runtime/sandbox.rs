@@ -0,0 +1,103 @@
+//! Bubblewrap sandbox support for host agent processes.
+
+use crate::agent::{Agent, AgentSandbox};
+use crate::paths;
+use anyhow::{Result, bail};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+const BWRAP_PROGRAM: &str = "bwrap";
+
+/// Agent CLI state that must stay writable so sessions can be saved and resumed.
+const WRITABLE_HOME_ENTRIES: &[&str] = &[".claude", ".claude.json", ".codex", ".cache"];
+
+pub(super) fn check_available() -> Result<()> {
+    if !cfg!(target_os = "linux") {
+        bail!("Agent sandboxing requires bubblewrap, which is only available on Linux");
+    }
+
+    let status = Command::new(BWRAP_PROGRAM)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    match status {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => bail!("`{BWRAP_PROGRAM} --version` failed ({status})"),
+        Err(err) => bail!(
+            "Agent sandboxing requires bubblewrap (`{BWRAP_PROGRAM}`) on PATH: {err}. Install it or turn the sandbox off with /sandbox"
+        ),
+    }
+}
+
+/// Paths the sandboxed agent may write to.
+fn writable_paths(agent: &Agent) -> Vec<PathBuf> {
+    let mut paths = vec![agent.worktree_path.clone()];
+
+    // Commits from a linked worktree write objects and refs into the main repository's git dir.
+    if agent.is_git_workspace()
+        && let Some(git_dir) = agent
+            .repo_root
+            .as_deref()
+            .map(|root| root.join(".git"))
+            .filter(|git_dir| git_dir.is_dir())
+    {
+        paths.push(git_dir);
+    }
+
+    if let Some(home) = paths::home_dir() {
+        paths.extend(
+            WRITABLE_HOME_ENTRIES
+                .iter()
+                .map(|entry| home.join(entry))
+                .filter(|path| path.exists()),
+        );
+    }
+
+    if let Some(codex_home) = std::env::var_os("CODEX_HOME").map(PathBuf::from)
+        && codex_home.exists()
+    {
+        paths.push(codex_home);
+    }
+
+    paths
+}
+
+fn push_bind(argv: &mut Vec<String>, flag: &str, path: &Path) {
+    let path = path.to_string_lossy().into_owned();
+    argv.push(flag.to_string());
+    argv.push(path.clone());
+    argv.push(path);
+}
+
+pub(super) fn wrap(agent: &Agent, command: &[String]) -> Vec<String> {
+    if agent.sandbox == AgentSandbox::Off {
+        return command.to_vec();
+    }
+
+    let mut argv = vec![
+        BWRAP_PROGRAM.to_string(),
+        "--die-with-parent".to_string(),
+        "--ro-bind".to_string(),
+        "/".to_string(),
+        "/".to_string(),
+        "--dev".to_string(),
+        "/dev".to_string(),
+        "--proc".to_string(),
+        "/proc".to_string(),
+        "--tmpfs".to_string(),
+        "/tmp".to_string(),
+    ];
+    for path in writable_paths(agent) {
+        push_bind(&mut argv, "--bind", &path);
+    }
+    if agent.sandbox.denies_network() {
+        argv.push("--unshare-net".to_string());
+    }
+    argv.push("--chdir".to_string());
+    argv.push(agent.worktree_path.to_string_lossy().into_owned());
+    argv.push("--".to_string());
+    argv.extend(command.iter().cloned());
+    argv
+}
@@ -0,0 +1,5 @@
+//! Add repository mode state type (new architecture).
+
+/// Add repository mode - typing the path of a repository to add to the workspace.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AddRepoMode;
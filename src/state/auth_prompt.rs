@@ -0,0 +1,19 @@
+//! Auth prompt mode state type (new architecture).
+
+/// Auth prompt mode - git or ssh is asking for credentials during a background push.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuthPromptMode {
+    /// The prompt git or ssh asked, such as `Password for 'https://github.com':`.
+    pub prompt: String,
+}
+
+impl AuthPromptMode {
+    /// Whether the answer should be hidden while it is typed (passwords, passphrases, PINs).
+    #[must_use]
+    pub fn is_secret(&self) -> bool {
+        self.prompt
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .any(|word| matches!(word, "password" | "passphrase" | "pin" | "token"))
+    }
+}
@@ -0,0 +1,5 @@
+//! Auto-connect picker mode state type (new architecture).
+
+/// Auto-connect picker mode - choosing which agent-less worktrees to reconnect on startup.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AutoConnectPickerMode;
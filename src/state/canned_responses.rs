@@ -0,0 +1,5 @@
+//! Canned responses mode state type (new architecture).
+
+/// Canned responses mode - picking a stored message to send to agents.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CannedResponsesMode;
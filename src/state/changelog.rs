@@ -1,6 +1,7 @@
 //! Changelog / "What's New" mode state type (new architecture).
 
 use semver::Version;
+use uuid::Uuid;
 
 /// Changelog mode - displays release notes in a scrollable modal.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -11,4 +12,6 @@ pub struct ChangelogMode {
     pub lines: Vec<String>,
     /// If set, mark this version as "seen" when the modal is dismissed.
     pub mark_seen_version: Option<Version>,
+    /// Agent whose details are shown; `n` cycles its notification preference.
+    pub agent_id: Option<Uuid>,
 }
@@ -7,6 +7,8 @@ pub enum ConfirmAction {
     Kill,
     /// Send Ctrl+C to the selected agent (may terminate it).
     InterruptAgent,
+    /// Restart the selected agent from a summary of its conversation.
+    Compact,
     /// Reset all state.
     Reset,
     /// Restart the mux daemon (kills all agent sessions).
@@ -19,6 +21,8 @@ pub enum ConfirmAction {
     WorktreeConflict,
     /// Switch branches (kills root agent tree and restarts).
     SwitchBranch,
+    /// Let the agents paused over budget go on.
+    ResumeOverBudget,
 }
 
 /// Confirming mode - yes/no (or special) confirmations for various actions.
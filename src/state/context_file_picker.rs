@@ -0,0 +1,5 @@
+//! Context file picker mode state type (new architecture).
+
+/// Context file picker mode - attaching files to a new agent's prompt.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ContextFilePickerMode;
@@ -0,0 +1,5 @@
+//! Dependency message mode state type (new architecture).
+
+/// Dependency message mode - writing the message held until the dependency is met.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DependencyMessageMode;
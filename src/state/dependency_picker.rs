@@ -0,0 +1,5 @@
+//! Dependency picker mode state type (new architecture).
+
+/// Dependency picker mode - choosing the agent the selected agent waits for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DependencyPickerMode;
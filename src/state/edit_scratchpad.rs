@@ -0,0 +1,5 @@
+//! Edit scratchpad mode state type (new architecture).
+
+/// Edit scratchpad mode - editing the shared scratchpad of the selected agent's swarm.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EditScratchpadMode;
@@ -0,0 +1,5 @@
+//! Edit task mode state type (new architecture).
+
+/// Edit task mode - revising the selected agent's task before re-sending it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EditTaskMode;
@@ -1,8 +1,20 @@
 //! Error modal state type (new architecture).
 
+use crate::error::TenexError;
+
 /// Error modal mode - displaying an error message.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ErrorModalMode {
-    /// Error message shown in the modal.
-    pub message: String,
+    /// Error shown in the modal, with its help text.
+    pub error: TenexError,
+}
+
+impl ErrorModalMode {
+    /// Show `error`; plain messages become [`TenexError::Other`].
+    #[must_use]
+    pub fn new(error: impl Into<TenexError>) -> Self {
+        Self {
+            error: error.into(),
+        }
+    }
 }
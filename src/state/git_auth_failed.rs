@@ -0,0 +1,21 @@
+//! Git auth failed mode state type (new architecture).
+
+/// The git operation to run again once authentication is fixed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthRetry {
+    /// Push the branch (`Ctrl+p`).
+    Push,
+    /// Push the branch, then open a PR (`Ctrl+o`).
+    PushForPr,
+    /// Fetch every repository (`/fetch`).
+    Fetch,
+}
+
+/// Git auth failed mode - a push or fetch could not authenticate; shows guidance and can retry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitAuthFailedMode {
+    /// The failure, the remote, and steps to fix it.
+    pub message: String,
+    /// What `r` runs again.
+    pub retry: AuthRetry,
+}
@@ -0,0 +1,5 @@
+//! Inbox mode state type (new architecture).
+
+/// Inbox mode - browsing the questions agents are waiting to have answered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InboxMode;
@@ -0,0 +1,5 @@
+//! Issue picker mode state type (new architecture).
+
+/// Issue picker mode - choosing a GitHub issue to create an agent for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IssuePickerMode;
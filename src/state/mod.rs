@@ -1,7 +1,11 @@
 //! Compile-time state types (new architecture).
 
+mod add_repo;
+mod auth_prompt;
+mod auto_connect_picker;
 mod branch_selector;
 mod broadcasting;
+mod canned_responses;
 mod changelog;
 mod child_count;
 mod child_prompt;
@@ -9,34 +13,57 @@ mod command_palette;
 mod confirm_push;
 mod confirm_push_for_pr;
 mod confirming;
+mod context_file_picker;
 mod creating;
 mod custom_agent_cmd;
+mod dependency_message;
+mod dependency_picker;
 mod diff_focused;
+mod edit_scratchpad;
+mod edit_task;
 mod error_modal;
+mod git_auth_failed;
 mod help;
+mod inbox;
+mod issue_picker;
 mod keyboard_remap_prompt;
 mod merge_branch_selector;
 mod model_selector;
 mod normal;
+mod pre_push_failed;
 mod preparing_docker;
 mod preview_focused;
 mod prompting;
 mod rebase_branch_selector;
 mod reconnect_prompt;
+mod recover_picker;
 mod rename_branch;
+mod reply;
+mod repo_picker;
 mod review_child_count;
+mod review_focus;
 mod review_info;
+mod scope;
 mod scrolling;
+mod send_to;
 mod settings_menu;
 mod success_modal;
 mod switch_branch_selector;
+mod switch_model;
+mod synthesis_browser;
 mod synthesis_prompt;
+mod synthesis_strategy;
 mod terminal_prompt;
+mod tmux_import_picker;
 mod update_prompt;
 mod update_requested;
 
+pub use add_repo::AddRepoMode;
+pub use auth_prompt::AuthPromptMode;
+pub use auto_connect_picker::AutoConnectPickerMode;
 pub use branch_selector::BranchSelectorMode;
 pub use broadcasting::BroadcastingMode;
+pub use canned_responses::CannedResponsesMode;
 pub use changelog::ChangelogMode;
 pub use child_count::ChildCountMode;
 pub use child_prompt::ChildPromptMode;
@@ -44,29 +71,48 @@ pub use command_palette::CommandPaletteMode;
 pub use confirm_push::ConfirmPushMode;
 pub use confirm_push_for_pr::ConfirmPushForPRMode;
 pub use confirming::{ConfirmAction, ConfirmingMode};
+pub use context_file_picker::ContextFilePickerMode;
 pub use creating::CreatingMode;
 pub use custom_agent_cmd::CustomAgentCommandMode;
+pub use dependency_message::DependencyMessageMode;
+pub use dependency_picker::DependencyPickerMode;
 pub use diff_focused::DiffFocusedMode;
+pub use edit_scratchpad::EditScratchpadMode;
+pub use edit_task::EditTaskMode;
 pub use error_modal::ErrorModalMode;
+pub use git_auth_failed::{AuthRetry, GitAuthFailedMode};
 pub use help::HelpMode;
+pub use inbox::InboxMode;
+pub use issue_picker::IssuePickerMode;
 pub use keyboard_remap_prompt::KeyboardRemapPromptMode;
 pub use merge_branch_selector::MergeBranchSelectorMode;
 pub use model_selector::ModelSelectorMode;
 pub use normal::NormalMode;
+pub use pre_push_failed::PrePushFailedMode;
 pub use preparing_docker::PreparingDockerMode;
 pub use preview_focused::PreviewFocusedMode;
 pub use prompting::PromptingMode;
 pub use rebase_branch_selector::RebaseBranchSelectorMode;
 pub use reconnect_prompt::ReconnectPromptMode;
+pub use recover_picker::RecoverPickerMode;
 pub use rename_branch::RenameBranchMode;
+pub use reply::ReplyMode;
+pub use repo_picker::RepoPickerMode;
 pub use review_child_count::ReviewChildCountMode;
+pub use review_focus::ReviewFocusMode;
 pub use review_info::ReviewInfoMode;
+pub use scope::ScopeMode;
 pub use scrolling::ScrollingMode;
+pub use send_to::SendToMode;
 pub use settings_menu::SettingsMenuMode;
 pub use success_modal::SuccessModalMode;
 pub use switch_branch_selector::SwitchBranchSelectorMode;
+pub use switch_model::SwitchModelMode;
+pub use synthesis_browser::SynthesisBrowserMode;
 pub use synthesis_prompt::SynthesisPromptMode;
+pub use synthesis_strategy::SynthesisStrategyMode;
 pub use terminal_prompt::TerminalPromptMode;
+pub use tmux_import_picker::TmuxImportPickerMode;
 pub use update_prompt::UpdatePromptMode;
 pub use update_requested::UpdateRequestedMode;
 
@@ -91,12 +137,30 @@ pub enum AppMode {
     TerminalPrompt(TerminalPromptMode),
     /// Custom agent command mode.
     CustomAgentCommand(CustomAgentCommandMode),
+    /// Synthesis strategy picker mode.
+    SynthesisStrategy(SynthesisStrategyMode),
     /// Synthesis prompt mode.
     SynthesisPrompt(SynthesisPromptMode),
+    /// Edit task mode.
+    EditTask(EditTaskMode),
+    /// Edit scratchpad mode.
+    EditScratchpad(EditScratchpadMode),
+    /// Dependency message mode.
+    DependencyMessage(DependencyMessageMode),
+    /// Reply mode.
+    Reply(ReplyMode),
+    /// Add repository mode.
+    AddRepo(AddRepoMode),
+    /// Typing the directory the selected agent keeps its changes to
+    Scope(ScopeMode),
+    /// Typing the program the selected agent is relaunched with
+    SwitchModel(SwitchModelMode),
     /// Child count picker mode.
     ChildCount(ChildCountMode),
     /// Review child count picker mode.
     ReviewChildCount(ReviewChildCountMode),
+    /// Review focus picker mode.
+    ReviewFocus(ReviewFocusMode),
     /// Review info mode.
     ReviewInfo(ReviewInfoMode),
     /// Branch selector mode.
@@ -109,6 +173,28 @@ pub enum AppMode {
     SwitchBranchSelector(SwitchBranchSelectorMode),
     /// Model selector mode.
     ModelSelector(ModelSelectorMode),
+    /// Send-to picker mode.
+    SendTo(SendToMode),
+    /// Dependency picker mode.
+    DependencyPicker(DependencyPickerMode),
+    /// Questions inbox mode.
+    Inbox(InboxMode),
+    /// Canned responses picker mode.
+    CannedResponses(CannedResponsesMode),
+    /// Repository picker mode.
+    RepoPicker(RepoPickerMode),
+    /// Context file picker mode.
+    ContextFilePicker(ContextFilePickerMode),
+    /// GitHub issue picker mode.
+    IssuePicker(IssuePickerMode),
+    /// Branch recovery picker mode.
+    RecoverPicker(RecoverPickerMode),
+    /// Startup auto-connect picker mode.
+    AutoConnectPicker(AutoConnectPickerMode),
+    /// tmux import picker mode.
+    TmuxImportPicker(TmuxImportPickerMode),
+    /// Synthesis browser mode.
+    SynthesisBrowser(SynthesisBrowserMode),
     /// Settings menu mode.
     SettingsMenu(SettingsMenuMode),
     /// Command palette mode.
@@ -119,6 +205,12 @@ pub enum AppMode {
     ConfirmPush(ConfirmPushMode),
     /// Confirm push for PR mode.
     ConfirmPushForPR(ConfirmPushForPRMode),
+    /// A pre-push command failed (carries whether the push was for a PR).
+    PrePushFailed(PrePushFailedMode),
+    /// Git or ssh is asking for credentials during a push.
+    AuthPrompt(AuthPromptMode),
+    /// A push or fetch could not authenticate (guidance and a retry).
+    GitAuthFailed(GitAuthFailedMode),
     /// Rename branch mode.
     RenameBranch(RenameBranchMode),
     /// Keyboard remap prompt mode.
@@ -217,12 +309,84 @@ impl From<CustomAgentCommandMode> for AppMode {
     }
 }
 
+impl From<SynthesisStrategyMode> for AppMode {
+    fn from(_: SynthesisStrategyMode) -> Self {
+        Self::SynthesisStrategy(SynthesisStrategyMode)
+    }
+}
+
 impl From<SynthesisPromptMode> for AppMode {
     fn from(_: SynthesisPromptMode) -> Self {
         Self::SynthesisPrompt(SynthesisPromptMode)
     }
 }
 
+impl From<EditTaskMode> for AppMode {
+    fn from(_: EditTaskMode) -> Self {
+        Self::EditTask(EditTaskMode)
+    }
+}
+
+impl From<EditScratchpadMode> for AppMode {
+    fn from(_: EditScratchpadMode) -> Self {
+        Self::EditScratchpad(EditScratchpadMode)
+    }
+}
+
+impl From<DependencyMessageMode> for AppMode {
+    fn from(_: DependencyMessageMode) -> Self {
+        Self::DependencyMessage(DependencyMessageMode)
+    }
+}
+
+impl From<DependencyPickerMode> for AppMode {
+    fn from(_: DependencyPickerMode) -> Self {
+        Self::DependencyPicker(DependencyPickerMode)
+    }
+}
+
+impl From<CannedResponsesMode> for AppMode {
+    fn from(_: CannedResponsesMode) -> Self {
+        Self::CannedResponses(CannedResponsesMode)
+    }
+}
+
+impl From<RepoPickerMode> for AppMode {
+    fn from(_: RepoPickerMode) -> Self {
+        Self::RepoPicker(RepoPickerMode)
+    }
+}
+
+impl From<InboxMode> for AppMode {
+    fn from(_: InboxMode) -> Self {
+        Self::Inbox(InboxMode)
+    }
+}
+
+impl From<ReplyMode> for AppMode {
+    fn from(_: ReplyMode) -> Self {
+        Self::Reply(ReplyMode)
+    }
+}
+
+impl From<AddRepoMode> for AppMode {
+    fn from(_: AddRepoMode) -> Self {
+        Self::AddRepo(AddRepoMode)
+    }
+}
+
+impl From<ScopeMode> for AppMode {
+    fn from(_: ScopeMode) -> Self {
+        Self::Scope(ScopeMode)
+    }
+}
+
+impl From<SwitchModelMode> for AppMode {
+    fn from(_: SwitchModelMode) -> Self {
+        Self::SwitchModel(SwitchModelMode)
+    }
+}
+
 impl From<ChildCountMode> for AppMode {
     fn from(_: ChildCountMode) -> Self {
         Self::ChildCount(ChildCountMode)
@@ -235,6 +399,12 @@ impl From<ReviewChildCountMode> for AppMode {
     }
 }
 
+impl From<ReviewFocusMode> for AppMode {
+    fn from(_: ReviewFocusMode) -> Self {
+        Self::ReviewFocus(ReviewFocusMode)
+    }
+}
+
 impl From<ReviewInfoMode> for AppMode {
     fn from(_: ReviewInfoMode) -> Self {
         Self::ReviewInfo(ReviewInfoMode)
@@ -271,6 +441,48 @@ impl From<ModelSelectorMode> for AppMode {
     }
 }
 
+impl From<ContextFilePickerMode> for AppMode {
+    fn from(_: ContextFilePickerMode) -> Self {
+        Self::ContextFilePicker(ContextFilePickerMode)
+    }
+}
+
+impl From<IssuePickerMode> for AppMode {
+    fn from(_: IssuePickerMode) -> Self {
+        Self::IssuePicker(IssuePickerMode)
+    }
+}
+
+impl From<RecoverPickerMode> for AppMode {
+    fn from(_: RecoverPickerMode) -> Self {
+        Self::RecoverPicker(RecoverPickerMode)
+    }
+}
+
+impl From<AutoConnectPickerMode> for AppMode {
+    fn from(_: AutoConnectPickerMode) -> Self {
+        Self::AutoConnectPicker(AutoConnectPickerMode)
+    }
+}
+
+impl From<TmuxImportPickerMode> for AppMode {
+    fn from(_: TmuxImportPickerMode) -> Self {
+        Self::TmuxImportPicker(TmuxImportPickerMode)
+    }
+}
+
+impl From<SynthesisBrowserMode> for AppMode {
+    fn from(_: SynthesisBrowserMode) -> Self {
+        Self::SynthesisBrowser(SynthesisBrowserMode)
+    }
+}
+
+impl From<SendToMode> for AppMode {
+    fn from(_: SendToMode) -> Self {
+        Self::SendTo(SendToMode)
+    }
+}
+
 impl From<SettingsMenuMode> for AppMode {
     fn from(_: SettingsMenuMode) -> Self {
         Self::SettingsMenu(SettingsMenuMode)
@@ -301,6 +513,24 @@ impl From<ConfirmPushForPRMode> for AppMode {
     }
 }
 
+impl From<AuthPromptMode> for AppMode {
+    fn from(state: AuthPromptMode) -> Self {
+        Self::AuthPrompt(state)
+    }
+}
+
+impl From<GitAuthFailedMode> for AppMode {
+    fn from(state: GitAuthFailedMode) -> Self {
+        Self::GitAuthFailed(state)
+    }
+}
+
+impl From<PrePushFailedMode> for AppMode {
+    fn from(state: PrePushFailedMode) -> Self {
+        Self::PrePushFailed(state)
+    }
+}
+
 impl From<RenameBranchMode> for AppMode {
     fn from(_: RenameBranchMode) -> Self {
         Self::RenameBranch(RenameBranchMode)
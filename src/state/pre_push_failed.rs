@@ -0,0 +1,8 @@
+//! Pre-push failed mode state type (new architecture).
+
+/// Pre-push failed mode - a pre-push command failed; push anyway or cancel.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PrePushFailedMode {
+    /// Whether the push was for opening a PR (`Ctrl+o`), which opens once the push succeeds.
+    pub for_pr: bool,
+}
@@ -0,0 +1,5 @@
+//! Recovery picker mode state type (new architecture).
+
+/// Recovery picker mode - choosing a lost commit to recreate a killed agent's branch at.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RecoverPickerMode;
@@ -0,0 +1,5 @@
+//! Reply mode state type (new architecture).
+
+/// Reply mode - typing a short answer sent straight to the selected agent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReplyMode;
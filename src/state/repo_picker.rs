@@ -0,0 +1,5 @@
+//! Repository picker mode state type (new architecture).
+
+/// Repository picker mode - switching between the repositories of the workspace.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RepoPickerMode;
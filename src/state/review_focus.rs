@@ -0,0 +1,5 @@
+//! Review focus mode state type (new architecture).
+
+/// Review focus mode - assigning a focus to each reviewer in a review swarm.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReviewFocusMode;
@@ -0,0 +1,5 @@
+//! Scope mode state type (new architecture).
+
+/// Scope mode - typing the directory the selected agent keeps its changes to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScopeMode;
@@ -0,0 +1,5 @@
+//! Send-to mode state type (new architecture).
+
+/// Send-to mode - choosing which agent receives the selected agent's output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SendToMode;
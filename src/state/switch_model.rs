@@ -0,0 +1,5 @@
+//! Switch model mode state type (new architecture).
+
+/// Switch model mode - typing the program the selected agent is relaunched with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SwitchModelMode;
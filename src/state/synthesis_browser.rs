@@ -0,0 +1,5 @@
+//! Synthesis browser mode state type (new architecture).
+
+/// Synthesis browser mode - browsing an agent's past synthesis files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SynthesisBrowserMode;
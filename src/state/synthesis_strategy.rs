@@ -0,0 +1,5 @@
+//! Synthesis strategy mode state type (new architecture).
+
+/// Synthesis strategy mode - choosing what a synthesize operation collects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SynthesisStrategyMode;
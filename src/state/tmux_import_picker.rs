@@ -0,0 +1,5 @@
+//! tmux import picker mode state type (new architecture).
+
+/// tmux import picker mode - choosing which outside tmux sessions to register as agents.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TmuxImportPickerMode;
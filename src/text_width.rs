@@ -0,0 +1,162 @@
+//! Display width of text in terminal columns.
+//!
+//! CJK characters and most emoji take two columns and combining marks take none, so neither a
+//! byte offset nor a character count is a column. Cursor movement, wrapping, and mouse
+//! selection go through these helpers instead.
+
+use unicode_width::UnicodeWidthChar as _;
+
+/// Columns `c` takes in a terminal. Control characters take none.
+#[must_use]
+pub fn char_width(c: char) -> usize {
+    c.width().unwrap_or(0)
+}
+
+/// Columns `text` takes in a terminal.
+#[must_use]
+pub fn width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+/// Zero-width joiner, which draws the characters on both sides of it as one emoji.
+const ZERO_WIDTH_JOINER: char = '\u{200d}';
+
+/// Emoji skin tone modifiers, drawn as part of the emoji before them.
+const SKIN_TONES: std::ops::RangeInclusive<char> = '\u{1f3fb}'..='\u{1f3ff}';
+
+/// Whether `c` is drawn as part of `previous`, such as a combining accent, a variation
+/// selector, a skin tone, or either side of a zero-width joiner.
+fn joins(previous: char, c: char) -> bool {
+    previous == ZERO_WIDTH_JOINER
+        || SKIN_TONES.contains(&c)
+        || (!c.is_control() && char_width(c) == 0)
+}
+
+/// Byte offset of the boundary before `index` in `text`, skipping back over combining marks
+/// and joined emoji so they are never split from the character they belong to.
+#[must_use]
+pub fn previous_boundary(text: &str, index: usize) -> usize {
+    let mut chars = text[..index].char_indices().rev().peekable();
+    while let Some((offset, c)) = chars.next() {
+        match chars.peek() {
+            Some(&(_, previous)) if joins(previous, c) => {}
+            _ => return offset,
+        }
+    }
+    0
+}
+
+/// Byte offset of the boundary after `index` in `text`, skipping over combining marks and
+/// joined emoji, or `text.len()` at the end.
+#[must_use]
+pub fn next_boundary(text: &str, index: usize) -> usize {
+    let mut previous = None;
+    for (offset, c) in text[index..].char_indices() {
+        if previous.is_some_and(|previous| !joins(previous, c)) {
+            return index + offset;
+        }
+        previous = Some(c);
+    }
+    text.len()
+}
+
+/// Byte offset of the first character in `text` that does not fit in `column` columns, or
+/// `text.len()` if all of it fits.
+///
+/// The offset is always a character boundary, and never splits a character from the combining
+/// marks that follow it, so it is safe for placing a cursor at a column of another line.
+#[must_use]
+pub fn byte_index_for_column(text: &str, column: usize) -> usize {
+    let mut current = 0_usize;
+    for (index, c) in text.char_indices() {
+        current = current.saturating_add(char_width(c));
+        if current > column {
+            return index;
+        }
+    }
+    text.len()
+}
+
+/// Byte range of the characters drawn in columns `start..=end_inclusive` of `text`.
+///
+/// A wide character is included if either of its columns is, and combining marks go with the
+/// character before them. Returns `None` if no character is drawn in those columns.
+#[must_use]
+pub fn byte_range_for_columns(
+    text: &str,
+    start: usize,
+    end_inclusive: usize,
+) -> Option<(usize, usize)> {
+    let mut char_start = 0_usize;
+    let mut char_end = 0_usize;
+    let mut range: Option<(usize, usize)> = None;
+    for (index, c) in text.char_indices() {
+        let w = char_width(c);
+        if w > 0 {
+            char_start = char_end;
+            char_end = char_end.saturating_add(w);
+        }
+        if char_start > end_inclusive {
+            break;
+        }
+        if char_end > start {
+            let end = index.saturating_add(c.len_utf8());
+            range = Some(range.map_or((index, end), |(first, _)| (first, end)));
+        }
+    }
+    range
+}
+
+/// The longest prefix of `text` that fits in `max_width` columns.
+#[must_use]
+pub fn truncate_to_width(text: &str, max_width: usize) -> &str {
+    &text[..byte_index_for_column(text, max_width)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn width_counts_columns() {
+        assert_eq!(width("abc"), 3);
+        assert_eq!(width("日本語"), 6);
+        assert_eq!(width("e\u{301}"), 1);
+        assert_eq!(width("😀"), 2);
+    }
+
+    #[test]
+    fn byte_index_for_column_stops_before_a_character_that_does_not_fit() {
+        assert_eq!(byte_index_for_column("日本語", 3), "日".len());
+        assert_eq!(byte_index_for_column("日本語", 4), "日本".len());
+        assert_eq!(byte_index_for_column("e\u{301}x", 1), "e\u{301}".len());
+        assert_eq!(byte_index_for_column("ab", 10), 2);
+        assert_eq!(truncate_to_width("a😀b", 2), "a");
+    }
+
+    #[test]
+    fn byte_range_for_columns_includes_half_selected_wide_characters() {
+        assert_eq!(
+            byte_range_for_columns("a日b", 2, 2),
+            Some((1, 1 + "日".len()))
+        );
+        assert_eq!(
+            byte_range_for_columns("e\u{301}x", 0, 0),
+            Some((0, "e\u{301}".len()))
+        );
+        assert_eq!(byte_range_for_columns("ab", 5, 6), None);
+    }
+
+    #[test]
+    fn boundaries_skip_combining_marks_and_joined_emoji() {
+        let text = "e\u{301}\u{1f469}\u{200d}\u{1f4bb}!";
+        let emoji_start = "e\u{301}".len();
+        let emoji_end = text.len() - 1;
+        assert_eq!(next_boundary(text, 0), emoji_start);
+        assert_eq!(next_boundary(text, emoji_start), emoji_end);
+        assert_eq!(previous_boundary(text, emoji_end), emoji_start);
+        assert_eq!(previous_boundary(text, emoji_start), 0);
+        assert_eq!(next_boundary(text, text.len()), text.len());
+        assert_eq!(previous_boundary(text, 0), 0);
+    }
+}
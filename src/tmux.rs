@@ -0,0 +1,145 @@
+//! Importing tmux sessions that were started outside Tenex.
+//!
+//! Tenex cannot take over a process running in another terminal multiplexer, so an imported
+//! session is driven through a tmux client attached inside a Tenex mux session. Preview,
+//! broadcast, and keystrokes reach the tmux session through that client.
+
+use anyhow::{Context, Result, bail};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// Session name pattern used when `tmux_import_pattern` is unset.
+pub const DEFAULT_IMPORT_PATTERN: &str = "*";
+
+/// A tmux session found by [`list_sessions`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TmuxSession {
+    /// Session name.
+    pub name: String,
+    /// Working directory of the session's active pane.
+    pub path: PathBuf,
+    /// Command running in the session's active pane.
+    pub command: String,
+}
+
+fn tmux_command() -> Command {
+    let mut command = Command::new("tmux");
+    // Inside tmux, `TMUX` makes the client refuse to nest; the server socket does not depend on it.
+    command.env_remove("TMUX").stdin(Stdio::null());
+    command
+}
+
+/// Exact-match target for session `name` (tmux otherwise matches name prefixes).
+fn exact_target(name: &str) -> String {
+    format!("={name}")
+}
+
+/// Running tmux sessions, in tmux's order. No tmux server means no sessions.
+///
+/// # Errors
+///
+/// Returns an error if tmux is not installed or cannot list its sessions.
+pub fn list_sessions() -> Result<Vec<TmuxSession>> {
+    let output = tmux_command()
+        .args([
+            "list-sessions",
+            "-F",
+            "#{session_name}\t#{pane_current_path}\t#{pane_current_command}",
+        ])
+        .output()
+        .context("Failed to run tmux; is it installed?")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("no server running") || stderr.contains("error connecting") {
+            return Ok(Vec::new());
+        }
+        bail!("tmux list-sessions failed: {}", stderr.trim());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let name = fields.next()?.to_string();
+            let path = PathBuf::from(fields.next()?);
+            let command = fields.next().unwrap_or_default().to_string();
+            Some(TmuxSession {
+                name,
+                path,
+                command,
+            })
+        })
+        .collect())
+}
+
+/// Whether tmux session `name` is still running.
+#[must_use]
+pub fn session_exists(name: &str) -> bool {
+    tmux_command()
+        .args(["has-session", "-t", &exact_target(name)])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Kill tmux session `name` (a no-op when it is already gone).
+///
+/// # Errors
+///
+/// Returns an error if the session exists but cannot be killed.
+pub fn kill_session(name: &str) -> Result<()> {
+    if !session_exists(name) {
+        return Ok(());
+    }
+    let output = tmux_command()
+        .args(["kill-session", "-t", &exact_target(name)])
+        .output()
+        .context("Failed to run tmux kill-session")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("tmux kill-session {name} failed: {}", stderr.trim());
+    }
+    Ok(())
+}
+
+/// Command line that attaches a tmux client to session `name`, used as the program of an
+/// imported agent.
+#[must_use]
+pub fn attach_program(name: &str) -> String {
+    let target = exact_target(name);
+    shell_words::join(["env", "-u", "TMUX", "tmux", "attach-session", "-t", &target])
+}
+
+/// Whether session name `name` matches `pattern`, where `*` matches any run of characters
+/// and `?` matches one character.
+#[must_use]
+pub fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    // Iterative wildcard matching that backtracks to the most recent `*`.
+    let (mut p, mut n) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    star = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
@@ -0,0 +1,123 @@
+//! Restoring the terminal and writing a crash report when the TUI panics.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::io::{self, Write as _};
+use std::panic::PanicHookInfo;
+use std::path::PathBuf;
+
+use parking_lot::Mutex;
+use ratatui::crossterm::{
+    cursor::Show,
+    event::{DisableMouseCapture, KeyCode, KeyModifiers, PopKeyboardEnhancementFlags},
+    execute,
+    terminal::{LeaveAlternateScreen, disable_raw_mode},
+};
+use tracing::error;
+
+use crate::state::AppMode;
+
+/// Input events kept for the crash report.
+const HISTORY_LEN: usize = 50;
+
+/// The most recent input events, oldest first.
+static HISTORY: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Remember `event` for the crash report.
+pub(super) fn record(event: String) {
+    let mut history = HISTORY.lock();
+    if history.len() == HISTORY_LEN {
+        history.pop_front();
+    }
+    history.push_back(event);
+}
+
+/// Remember a key pressed in `mode`. Characters typed into an authentication prompt are
+/// hidden.
+pub(super) fn record_key(mode: &AppMode, code: KeyCode, modifiers: KeyModifiers) {
+    let mode_name = super::mode_name(mode);
+    let key = match code {
+        KeyCode::Char(_) if matches!(mode, AppMode::AuthPrompt(_)) => "Char(•)".to_string(),
+        code => format!("{code:?}"),
+    };
+    if modifiers.is_empty() {
+        record(format!("{mode_name}: {key}"));
+    } else {
+        record(format!("{mode_name}: {modifiers:?}+{key}"));
+    }
+}
+
+/// Restore the terminal before the default panic message is printed, then write a crash
+/// report and say where it is.
+///
+/// A panic on another thread only ends Tenex when panics abort; otherwise the TUI keeps
+/// running and the panic gets the default handling.
+pub(super) fn install_panic_hook() {
+    let previous = std::panic::take_hook();
+    let tui_thread = std::thread::current().id();
+    std::panic::set_hook(Box::new(move |info| {
+        if std::thread::current().id() != tui_thread && !cfg!(panic = "abort") {
+            previous(info);
+            return;
+        }
+        restore_terminal();
+        previous(info);
+        error!(%info, "Tenex panicked");
+        let mut stderr = io::stderr();
+        let _ = match write_report(info) {
+            Ok(path) => writeln!(
+                stderr,
+                "\nTenex crashed. A crash report was written to {}\nPlease attach it when reporting the crash.",
+                path.display()
+            ),
+            Err(err) => writeln!(
+                stderr,
+                "\nTenex crashed. Writing a crash report failed: {err}"
+            ),
+        };
+    }));
+}
+
+/// Leave raw mode, the alternate screen, and mouse capture so the shell is usable again.
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(
+        io::stdout(),
+        PopKeyboardEnhancementFlags,
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        Show
+    );
+}
+
+/// Write the panic, its backtrace, and the recent input events next to the log file.
+fn write_report(info: &PanicHookInfo<'_>) -> io::Result<PathBuf> {
+    let now = chrono::Local::now();
+    let path = crate::paths::log_path()
+        .with_file_name(format!("tenex-crash-{}.log", now.format("%Y%m%d-%H%M%S")));
+
+    let mut report = String::new();
+    let _ = writeln!(report, "Tenex {} crash report", env!("CARGO_PKG_VERSION"));
+    let _ = writeln!(report, "Time: {}", now.to_rfc3339());
+    let _ = writeln!(
+        report,
+        "Thread: {}",
+        std::thread::current().name().unwrap_or("<unnamed>")
+    );
+    let _ = writeln!(report, "\n{info}");
+    let _ = writeln!(
+        report,
+        "\nBacktrace:\n{}",
+        std::backtrace::Backtrace::force_capture()
+    );
+    let _ = writeln!(report, "Recent events (oldest first):");
+    // The panic may have happened while recording an event.
+    if let Some(history) = HISTORY.try_lock() {
+        for event in history.iter() {
+            let _ = writeln!(report, "  {event}");
+        }
+    }
+
+    std::fs::write(&path, report)?;
+    Ok(path)
+}
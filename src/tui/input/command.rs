@@ -1,6 +1,7 @@
 //! Slash command palette and related pickers
 
 use crate::app::App;
+use crate::state::AppMode;
 use anyhow::Result;
 use ratatui::crossterm::event::KeyCode;
 
@@ -14,6 +15,30 @@ pub fn handle_model_selector_mode(app: &mut App, code: KeyCode) -> Result<()> {
     crate::action::dispatch_model_selector_mode(app, code)
 }
 
+/// Handle key events in the list pickers (`SendTo`, `DependencyPicker`, `Inbox`,
+/// `CannedResponses`, `RepoPicker`, `ContextFilePicker`, `IssuePicker`, `RecoverPicker`, `AutoConnectPicker`, `TmuxImportPicker`,
+/// `SynthesisBrowser`)
+pub fn handle_list_picker_mode(app: &mut App, code: KeyCode) -> Result<()> {
+    match app.mode {
+        AppMode::SendTo(_) => crate::action::dispatch_send_to_mode(app, code),
+        AppMode::DependencyPicker(_) => crate::action::dispatch_dependency_picker_mode(app, code),
+        AppMode::Inbox(_) => crate::action::dispatch_inbox_mode(app, code),
+        AppMode::CannedResponses(_) => crate::action::dispatch_canned_responses_mode(app, code),
+        AppMode::RepoPicker(_) => crate::action::dispatch_repo_picker_mode(app, code),
+        AppMode::ContextFilePicker(_) => {
+            crate::action::dispatch_context_file_picker_mode(app, code)
+        }
+        AppMode::IssuePicker(_) => crate::action::dispatch_issue_picker_mode(app, code),
+        AppMode::RecoverPicker(_) => crate::action::dispatch_recover_picker_mode(app, code),
+        AppMode::AutoConnectPicker(_) => {
+            crate::action::dispatch_auto_connect_picker_mode(app, code)
+        }
+        AppMode::TmuxImportPicker(_) => crate::action::dispatch_tmux_import_picker_mode(app, code),
+        AppMode::SynthesisBrowser(_) => crate::action::dispatch_synthesis_browser_mode(app, code),
+        _ => Ok(()),
+    }
+}
+
 /// Handle key events in `SettingsMenu` mode
 pub fn handle_settings_menu_mode(app: &mut App, code: KeyCode) -> Result<()> {
     crate::action::dispatch_settings_menu_mode(app, code)
@@ -3,15 +3,18 @@
 //! Handles key events for various confirmation dialogs:
 //! - `ConfirmPush` (push branch to remote)
 //! - `ConfirmPushForPR` (push and open PR)
+//! - `PrePushFailed` (push anyway after a failed pre-push command)
+//! - `AuthPrompt` (credentials git or ssh asks for during a push)
+//! - `GitAuthFailed` (guidance after a push or fetch could not authenticate)
 //! - `RenameBranch` (rename agent/branch)
 //! - `Confirming` (general yes/no confirmations)
 //! - `UpdatePrompt` (self-update prompt on startup)
 
 use crate::app::App;
-use crate::state::ConfirmAction;
+use crate::state::{AppMode, ConfirmAction};
 use crate::update::UpdateInfo;
 use anyhow::Result;
-use ratatui::crossterm::event::KeyCode;
+use ratatui::crossterm::event::{KeyCode, KeyModifiers};
 
 /// Handle key events in `ConfirmPush` mode
 pub fn handle_confirm_push_mode(app: &mut App, code: KeyCode) -> Result<()> {
@@ -23,6 +26,25 @@ pub fn handle_confirm_push_for_pr_mode(app: &mut App, code: KeyCode) -> Result<(
     crate::action::dispatch_confirm_push_for_pr_mode(app, code)
 }
 
+/// Handle key events in the modes a running push or fetch opens: `PrePushFailed`,
+/// `AuthPrompt`, and `GitAuthFailed`
+pub fn handle_git_op_prompt_mode(
+    app: &mut App,
+    code: KeyCode,
+    modifiers: KeyModifiers,
+) -> Result<()> {
+    match &app.mode {
+        AppMode::PrePushFailed(state) => {
+            crate::action::dispatch_pre_push_failed_mode(app, *state, code)
+        }
+        AppMode::AuthPrompt(_) => crate::action::dispatch_auth_prompt_mode(app, code, modifiers),
+        AppMode::GitAuthFailed(state) => {
+            crate::action::dispatch_git_auth_failed_mode(app, state.clone(), code)
+        }
+        _ => Ok(()),
+    }
+}
+
 /// Handle key events in `RenameBranch` mode
 pub fn handle_rename_branch_mode(app: &mut App, code: KeyCode) -> Result<()> {
     crate::action::dispatch_rename_branch_mode(app, code)
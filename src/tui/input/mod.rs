@@ -36,33 +36,36 @@ pub fn handle_key_event(
         | AppMode::ReconnectPrompt(_)
         | AppMode::TerminalPrompt(_)
         | AppMode::CustomAgentCommand(_)
-        | AppMode::SynthesisPrompt(_) => {
+        | AppMode::SynthesisPrompt(_)
+        | AppMode::EditTask(_)
+        | AppMode::EditScratchpad(_)
+        | AppMode::DependencyMessage(_)
+        | AppMode::Reply(_)
+        | AppMode::AddRepo(_)
+        | AppMode::Scope(_)
+        | AppMode::SwitchModel(_) => {
             text_input::handle_text_input_mode(app, code, modifiers)?;
         }
 
         // Count picker modes
-        AppMode::ChildCount(_) => {
-            picker::handle_child_count_mode(app, code)?;
-        }
-        AppMode::ReviewChildCount(_) => {
-            picker::handle_review_child_count_mode(app, code)?;
-        }
-        AppMode::ReviewInfo(_) => {
-            picker::handle_review_info_mode(app)?;
+        AppMode::ChildCount(_) => picker::handle_child_count_mode(app, code)?,
+        AppMode::ReviewChildCount(_) | AppMode::ReviewFocus(_) => {
+            picker::handle_review_picker_mode(app, code)?;
         }
+        AppMode::ReviewInfo(_) => picker::handle_review_info_mode(app)?,
+        AppMode::SynthesisStrategy(_) => picker::handle_synthesis_strategy_mode(app, code)?,
 
         // Branch selector mode
-        AppMode::BranchSelector(_) => {
-            picker::handle_branch_selector_mode(app, code)?;
-        }
+        AppMode::BranchSelector(_) => picker::handle_branch_selector_mode(app, code)?,
 
         // Git operation confirmation modes
-        AppMode::ConfirmPush(_) => {
-            confirm::handle_confirm_push_mode(app, code)?;
-        }
+        AppMode::ConfirmPush(_) => confirm::handle_confirm_push_mode(app, code)?,
         AppMode::ConfirmPushForPR(_) => {
             confirm::handle_confirm_push_for_pr_mode(app, code)?;
         }
+        AppMode::PrePushFailed(_) | AppMode::AuthPrompt(_) | AppMode::GitAuthFailed(_) => {
+            confirm::handle_git_op_prompt_mode(app, code, modifiers)?;
+        }
         AppMode::RenameBranch(_) => {
             confirm::handle_rename_branch_mode(app, code)?;
         }
@@ -96,22 +99,14 @@ pub fn handle_key_event(
         AppMode::UpdateRequested(_) | AppMode::PreparingDocker(_) => {}
 
         // Help, error, and success modes
-        AppMode::Changelog(state) => {
-            let mark_seen_version = state.mark_seen_version.clone();
-            let max_scroll = crate::action::changelog_max_scroll(&app.data, state);
-            crate::action::dispatch_changelog_mode(
-                app,
-                mark_seen_version,
-                max_scroll,
-                code,
-                modifiers,
-            )?;
+        AppMode::Changelog(_) => {
+            handle_changelog_mode(app, code, modifiers)?;
         }
         AppMode::Help(_) => {
             crate::action::dispatch_help_mode(app, code, modifiers)?;
         }
         AppMode::ErrorModal(state) => {
-            crate::action::dispatch_error_modal_mode(app, state.message.clone())?;
+            crate::action::dispatch_error_modal_mode(app, state.clone(), code)?;
         }
         AppMode::SuccessModal(state) => {
             crate::action::dispatch_success_modal_mode(app, state.message.clone())?;
@@ -129,6 +124,19 @@ pub fn handle_key_event(
         AppMode::SettingsMenu(_) => {
             command::handle_settings_menu_mode(app, code)?;
         }
+        AppMode::SendTo(_)
+        | AppMode::DependencyPicker(_)
+        | AppMode::Inbox(_)
+        | AppMode::CannedResponses(_)
+        | AppMode::RepoPicker(_)
+        | AppMode::ContextFilePicker(_)
+        | AppMode::IssuePicker(_)
+        | AppMode::RecoverPicker(_)
+        | AppMode::AutoConnectPicker(_)
+        | AppMode::TmuxImportPicker(_)
+        | AppMode::SynthesisBrowser(_) => {
+            command::handle_list_picker_mode(app, code)?;
+        }
 
         // Preview focused mode (forwards keys to the mux backend)
         AppMode::PreviewFocused(_) => {
@@ -147,6 +155,16 @@ pub fn handle_key_event(
     Ok(())
 }
 
+/// Handle key events in `Changelog` mode
+fn handle_changelog_mode(app: &mut App, code: KeyCode, modifiers: KeyModifiers) -> Result<()> {
+    let AppMode::Changelog(state) = &app.mode else {
+        return Ok(());
+    };
+    let mark_seen_version = state.mark_seen_version.clone();
+    let max_scroll = crate::action::changelog_max_scroll(&app.data, state);
+    crate::action::dispatch_changelog_mode(app, mark_seen_version, max_scroll, code, modifiers)
+}
+
 /// Handle a mouse event based on the current mode and layout.
 ///
 /// `frame_area` should be the terminal viewport (`Rect::new(0, 0, width, height)`).
@@ -357,6 +357,10 @@ fn handle_left_up(app: &mut App, x: u16, y: u16, frame_area: Rect) {
     let (start, end) = normalize_preview_selection_points(anchor, cursor);
 
     if let Some(text) = preview_selection_text(app, start, end) {
+        app.data.ui.last_preview_selection = app
+            .data
+            .selected_agent()
+            .map(|agent| (agent.id, text.clone()));
         app.data.ui.pending_clipboard = Some(text);
     }
 
@@ -485,7 +489,7 @@ fn preview_selection_text(
         };
 
         if let Some((start_byte, end_byte)) =
-            byte_range_for_char_range_inclusive(&line_text, slice_start, slice_end)
+            crate::text_width::byte_range_for_columns(&line_text, slice_start, slice_end)
         {
             out.push_str(&line_text[start_byte..end_byte]);
         }
@@ -494,38 +498,6 @@ fn preview_selection_text(
     if out.is_empty() { None } else { Some(out) }
 }
 
-fn byte_range_for_char_range_inclusive(
-    text: &str,
-    start_col: usize,
-    end_col_inclusive: usize,
-) -> Option<(usize, usize)> {
-    let char_len = text.chars().count();
-    if char_len == 0 {
-        return Some((0, 0));
-    }
-
-    let start = start_col.min(char_len);
-    let end_inclusive = end_col_inclusive.min(char_len.saturating_sub(1));
-    if start > end_inclusive {
-        return None;
-    }
-
-    let start_byte = byte_index_for_char(text, start);
-    let end_byte = byte_index_for_char(text, end_inclusive.saturating_add(1));
-    Some((start_byte, end_byte))
-}
-
-fn byte_index_for_char(text: &str, char_idx: usize) -> usize {
-    if char_idx == 0 {
-        return 0;
-    }
-
-    match text.char_indices().nth(char_idx) {
-        Some((byte_idx, _)) => byte_idx,
-        None => text.len(),
-    }
-}
-
 fn handle_agent_list_click(app: &mut App, x: u16, y: u16, area: Rect) {
     let inner = Rect {
         x: area.x.saturating_add(1),
@@ -602,7 +574,7 @@ fn handle_content_pane_click(app: &mut App, x: u16, y: u16, area: Rect) {
                 app.apply_mode(ScrollingMode.into());
             }
         }
-        Tab::Commits => {
+        Tab::Commits | Tab::Scratchpad => {
             app.apply_mode(ScrollingMode.into());
         }
     }
@@ -644,7 +616,7 @@ fn handle_tab_bar_click(app: &mut App, x: u16, tab_bar_area: Rect) {
                 app.apply_mode(ScrollingMode.into());
             }
         }
-        Tab::Commits => {
+        Tab::Commits | Tab::Scratchpad => {
             app.apply_mode(ScrollingMode.into());
         }
     }
@@ -3,13 +3,16 @@
 //! Handles key events for modes that involve picking/selecting:
 //! - `ChildCount` (selecting number of child agents)
 //! - `ReviewChildCount` (selecting number of review agents)
+//! - `ReviewFocus` (assigning a focus to each reviewer)
 //! - `ReviewInfo` (info popup before review)
+//! - `SynthesisStrategy` (choosing what a synthesis collects)
 //! - `BranchSelector` (selecting a branch)
 //! - `RebaseBranchSelector` (selecting a rebase target)
 //! - `MergeBranchSelector` (selecting a merge source)
 //! - `SwitchBranchSelector` (selecting a branch to switch to)
 
 use crate::app::App;
+use crate::state::AppMode;
 use anyhow::Result;
 use ratatui::crossterm::event::KeyCode;
 
@@ -18,9 +21,12 @@ pub fn handle_child_count_mode(app: &mut App, code: KeyCode) -> Result<()> {
     crate::action::dispatch_child_count_mode(app, code)
 }
 
-/// Handle key events in `ReviewChildCount` mode
-pub fn handle_review_child_count_mode(app: &mut App, code: KeyCode) -> Result<()> {
-    crate::action::dispatch_review_child_count_mode(app, code)
+/// Handle key events in the review swarm pickers (`ReviewChildCount`, `ReviewFocus`)
+pub fn handle_review_picker_mode(app: &mut App, code: KeyCode) -> Result<()> {
+    match app.mode {
+        AppMode::ReviewFocus(_) => crate::action::dispatch_review_focus_mode(app, code),
+        _ => crate::action::dispatch_review_child_count_mode(app, code),
+    }
 }
 
 /// Handle key events in `ReviewInfo` mode (any key dismisses)
@@ -28,6 +34,11 @@ pub fn handle_review_info_mode(app: &mut App) -> Result<()> {
     crate::action::dispatch_review_info_mode(app)
 }
 
+/// Handle key events in `SynthesisStrategy` mode
+pub fn handle_synthesis_strategy_mode(app: &mut App, code: KeyCode) -> Result<()> {
+    crate::action::dispatch_synthesis_strategy_mode(app, code)
+}
+
 /// Handle key events in `BranchSelector` mode
 pub fn handle_branch_selector_mode(app: &mut App, code: KeyCode) -> Result<()> {
     crate::action::dispatch_branch_selector_mode(app, code)
@@ -8,6 +8,12 @@
 //! - `ReconnectPrompt` (reconnect with edited prompt)
 //! - `TerminalPrompt` (terminal startup command)
 //! - `SynthesisPrompt` (extra synthesis instructions)
+//! - `EditTask` (revised task for the selected agent)
+//! - `EditScratchpad` (shared scratchpad of the selected agent's swarm)
+//! - `DependencyMessage` (message held until another agent finishes)
+//! - `Reply` (short answer sent straight to the selected agent)
+//! - `AddRepo` (path of a repository to add to the workspace)
+//! - `Scope` (directory the selected agent keeps its changes to)
 
 use crate::app::App;
 use crate::state::AppMode;
@@ -35,6 +41,21 @@ pub fn handle_text_input_mode(app: &mut App, code: KeyCode, modifiers: KeyModifi
         AppMode::SynthesisPrompt(_) => {
             crate::action::dispatch_synthesis_prompt_mode(app, code, modifiers)?;
         }
+        AppMode::EditTask(_) => crate::action::dispatch_edit_task_mode(app, code, modifiers)?,
+        AppMode::EditScratchpad(_) => {
+            crate::action::dispatch_edit_scratchpad_mode(app, code, modifiers)?;
+        }
+        AppMode::DependencyMessage(_) => {
+            crate::action::dispatch_dependency_message_mode(app, code, modifiers)?;
+        }
+        AppMode::Reply(_) => crate::action::dispatch_reply_mode(app, code, modifiers)?,
+        AppMode::AddRepo(_) => {
+            crate::action::dispatch_add_repo_mode(app, code, modifiers)?;
+        }
+        AppMode::Scope(_) => crate::action::dispatch_scope_mode(app, code, modifiers)?,
+        AppMode::SwitchModel(_) => {
+            crate::action::dispatch_switch_model_mode(app, code, modifiers)?;
+        }
         _ => {}
     }
     Ok(())
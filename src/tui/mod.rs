@@ -1,7 +1,11 @@
 //! Terminal User Interface for Tenex
 
+mod crash;
 mod input;
+pub mod recording;
 mod render;
+pub mod replay;
+pub mod snapshot;
 
 use crate::app::{Actions, App, Event, Handler, Tab};
 use crate::state::AppMode;
@@ -19,8 +23,8 @@ use ratatui::{
         },
         execute,
         terminal::{
-            EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
-            supports_keyboard_enhancement,
+            EnterAlternateScreen, LeaveAlternateScreen, SetTitle, disable_raw_mode,
+            enable_raw_mode, supports_keyboard_enhancement,
         },
     },
     layout::Rect,
@@ -37,6 +41,10 @@ const AGENT_STATUS_SYNC_INTERVAL_MS: u64 = 500;
 const MIN_OUTPUT_REFRESH_MS: u64 = 16;
 const MIN_PANE_ACTIVITY_SYNC_MS: u64 = 500;
 const STATE_FILE_SYNC_INTERVAL_MS: u64 = 250;
+const RESOURCE_USAGE_SYNC_INTERVAL_MS: u64 = 5000;
+const PLUGIN_COLUMN_SYNC_INTERVAL_MS: u64 = 30_000;
+const CHANGE_ALERT_SYNC_INTERVAL_MS: u64 = 15_000;
+const CONTEXT_USAGE_SYNC_INTERVAL_MS: u64 = 10_000;
 const OSC52_MAX_BYTES: usize = 100_000;
 
 type DrainedEvents = (Vec<String>, Option<(u16, u16)>, bool);
@@ -74,6 +82,12 @@ impl EventReader for Handler {
     }
 }
 
+/// The name of `mode`'s variant, such as `ErrorModal`.
+fn mode_name(mode: &AppMode) -> String {
+    let debug = format!("{mode:?}");
+    debug.split('(').next().unwrap_or_default().to_string()
+}
+
 fn mouse_capture_enabled() -> bool {
     !env_var_truthy(std::env::var("TENEX_DISABLE_MOUSE").ok().as_deref())
 }
@@ -133,6 +147,35 @@ fn flush_pending_clipboard(stdout: &mut dyn io::Write, app: &mut App) {
     }
 }
 
+/// Title for the host terminal: the selected agent's title and status, plus the title its
+/// program set for itself, if any.
+fn terminal_title(app: &App) -> String {
+    let Some(agent) = app.selected_agent() else {
+        return "Tenex".to_string();
+    };
+    let mut title = agent.window_title();
+    if let Some(pane_title) = &app.data.ui.preview_pane_title {
+        title.push_str(" - ");
+        title.push_str(pane_title);
+    }
+    title.push_str(" - Tenex");
+    title.retain(|c| !c.is_control());
+    title
+}
+
+fn flush_terminal_title(stdout: &mut dyn io::Write, app: &mut App) {
+    let title = terminal_title(app);
+    if app.data.ui.terminal_title.as_ref() == Some(&title) {
+        return;
+    }
+
+    let mut stdout = DynWrite { inner: stdout };
+    if let Err(err) = execute!(&mut stdout, SetTitle(&title)) {
+        warn!(error = %err, "Failed to set terminal title");
+    }
+    app.data.ui.terminal_title = Some(title);
+}
+
 fn write_osc52_clipboard(stdout: &mut dyn io::Write, content: &str) -> io::Result<()> {
     use base64::Engine as _;
     use base64::engine::general_purpose::STANDARD;
@@ -260,6 +303,7 @@ impl StateFileTracker {
 /// alternate screen), or if the main event loop fails to poll input
 /// or render frames.
 pub fn run(mut app: App) -> Result<Option<UpdateInfo>> {
+    crash::install_panic_hook();
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     enter_tui_screen(&mut stdout, mouse_capture_enabled())?;
@@ -272,6 +316,9 @@ pub fn run(mut app: App) -> Result<Option<UpdateInfo>> {
     apply_startup_modals(&mut app);
 
     let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+    let size = terminal.size()?;
+    recording::begin(&app, size.width, size.height);
+    crate::metrics::set_enabled(app.data.settings.metrics.is_on());
 
     let event_handler = Handler::new(UI_FRAME_INTERVAL_MS);
     let action_handler = Actions::new();
@@ -284,6 +331,8 @@ pub fn run(mut app: App) -> Result<Option<UpdateInfo>> {
         action_handler,
         &mut clipboard_out,
     );
+    recording::finish();
+    crate::metrics::flush();
 
     pop_keyboard_enhancement(terminal.backend_mut(), keyboard_enhancement_enabled);
 
@@ -426,16 +475,28 @@ fn drain_events(
     let mut frame_area = Rect::new(0, 0, size.width, size.height);
 
     loop {
-        match event_handler.next_event()? {
+        let event = event_handler.next_event()?;
+        // Modes can also change between events, when background work finishes.
+        recording::mode(&app.mode);
+        match event {
             Event::Tick => {
                 break;
             }
             Event::Key(key) => {
                 if matches!(key.kind, KeyEventKind::Press | KeyEventKind::Repeat) {
+                    crash::record_key(&app.mode, key.code, key.modifiers);
+                    recording::key(&app.mode, key.code, key.modifiers);
                     input::handle_key_event(app, key.code, key.modifiers, &mut batched_keys)?;
                 }
             }
             Event::Mouse(mouse) => {
+                recording::mouse(mouse.kind, mouse.column, mouse.row, mouse.modifiers);
+                if let MouseEventKind::Down(button) = mouse.kind {
+                    crash::record(format!(
+                        "Mouse {button:?} click at {},{}",
+                        mouse.column, mouse.row
+                    ));
+                }
                 // If we're attached and have batched keys, flush them before applying any
                 // click-driven selection changes so keys go to the intended agent.
                 if !batched_keys.is_empty() && mouse.kind == MouseEventKind::Down(MouseButton::Left)
@@ -448,10 +509,13 @@ fn drain_events(
                 input::handle_mouse_event(app, mouse, frame_area, &mut batched_keys);
             }
             Event::Resize(w, h) => {
+                crash::record(format!("Resize to {w}x{h}"));
+                recording::resize(w, h);
                 last_resize = Some((w, h));
                 frame_area = Rect::new(0, 0, w, h);
             }
         }
+        recording::mode(&app.mode);
 
         if !event_handler.poll_immediate()? {
             break;
@@ -594,6 +658,13 @@ fn run_loop(
     let mut last_commits_update = Instant::now();
     let mut last_status_sync = Instant::now();
     let mut last_pane_activity_sync = Instant::now();
+    let mut last_background_status_sync: Option<Instant> = None;
+    let mut last_background_pane_sync: Option<Instant> = None;
+    let mut last_resource_usage_sync = Instant::now();
+    let mut last_auto_fetch = Instant::now();
+    let mut last_plugin_column_sync: Option<Instant> = None;
+    let mut last_change_alert_sync: Option<Instant> = None;
+    let mut last_context_usage_sync: Option<Instant> = None;
 
     loop {
         // If we returned to normal mode and still need to show the keyboard prompt,
@@ -666,6 +737,9 @@ fn run_loop(
         }
 
         let commits_due = last_commits_update.elapsed() >= commits_refresh_interval;
+        if app.data.active_tab == Tab::Scratchpad && (needs_content_update || commits_due) {
+            Actions::update_scratchpad(app);
+        }
         if should_refresh_commits(app.data.active_tab, needs_content_update, commits_due) {
             if app.data.active_tab == Tab::Commits {
                 let _ = action_handler.update_commits(app);
@@ -677,8 +751,13 @@ fn run_loop(
 
         needs_content_update = false;
 
+        app.finish_background_ops();
+        app.show_auth_prompt();
+        app.data.ui.notifications.tick(Instant::now());
+
         // Draw ONCE after draining all queued events
         terminal.draw(app)?;
+        flush_terminal_title(clipboard_out, app);
 
         if maybe_finish_preparing_docker(app) {
             continue;
@@ -691,17 +770,84 @@ fn run_loop(
                 .poll_interval_ms
                 .max(MIN_PANE_ACTIVITY_SYNC_MS),
         );
+        // Off-screen idle agents are only polled every `background_sync_secs`.
+        let background_interval = app.data.settings.background_sync_interval();
+        if std::mem::take(&mut app.data.ui.full_sync_requested) {
+            last_background_status_sync = None;
+            last_background_pane_sync = None;
+        }
         if last_pane_activity_sync.elapsed() >= pane_activity_interval {
-            let _ = action_handler.sync_agent_pane_activity(app);
+            if last_background_pane_sync.is_none_or(|last| last.elapsed() >= background_interval) {
+                let _ = action_handler.sync_agent_pane_activity(app);
+                last_background_pane_sync = Some(Instant::now());
+            } else {
+                let _ = action_handler.sync_foreground_pane_activity(app);
+            }
+            Actions::update_pause(&mut app.data);
+            action_handler.release_dependencies(&mut app.data);
+            action_handler.sync_questions(&mut app.data);
             last_pane_activity_sync = Instant::now();
         }
 
         // Sync agent status less frequently (session listing is relatively expensive).
         if last_status_sync.elapsed() >= Duration::from_millis(AGENT_STATUS_SYNC_INTERVAL_MS) {
-            let _ = action_handler.sync_agent_status(app);
+            let result = if last_background_status_sync
+                .is_none_or(|last| last.elapsed() >= background_interval)
+            {
+                last_background_status_sync = Some(Instant::now());
+                action_handler.sync_agent_status(app)
+            } else {
+                action_handler.sync_foreground_agent_status(app)
+            };
+            if let Err(err) = result {
+                app.notify_error(format!("Failed to sync agent status: {err:#}"));
+            }
+            action_handler.sync_window_titles(app);
             last_status_sync = Instant::now();
         }
 
+        // Sampling the process table is comparatively slow; keep it well below the frame rate.
+        // The sample is taken on a worker thread and recorded once it arrives.
+        Actions::apply_agent_resource_usage(app);
+        if last_resource_usage_sync.elapsed()
+            >= Duration::from_millis(RESOURCE_USAGE_SYNC_INTERVAL_MS)
+        {
+            action_handler.sample_agent_resource_usage(app);
+            last_resource_usage_sync = Instant::now();
+        }
+
+        if let Some(interval) = app.data.settings.auto_fetch_interval()
+            && last_auto_fetch.elapsed() >= interval
+        {
+            Actions::fetch_remotes(&mut app.data, false);
+            last_auto_fetch = Instant::now();
+        }
+
+        // Listing each worktree's changes runs git, so scan for flagged changes only now and then.
+        if last_change_alert_sync.is_none_or(|last| {
+            last.elapsed() >= Duration::from_millis(CHANGE_ALERT_SYNC_INTERVAL_MS)
+        }) {
+            Actions::sync_change_alerts(&mut app.data);
+            last_change_alert_sync = Some(Instant::now());
+        }
+
+        // Transcripts only change once per model turn; reading their tails now and then is enough.
+        if last_context_usage_sync.is_none_or(|last| {
+            last.elapsed() >= Duration::from_millis(CONTEXT_USAGE_SYNC_INTERVAL_MS)
+        }) {
+            Actions::sync_context_usage(&mut app.data);
+            action_handler.sync_budgets(&mut app.data);
+            last_context_usage_sync = Some(Instant::now());
+        }
+
+        // Plugins are external processes; ask them for sidebar values only now and then.
+        if last_plugin_column_sync.is_none_or(|last| {
+            last.elapsed() >= Duration::from_millis(PLUGIN_COLUMN_SYNC_INTERVAL_MS)
+        }) {
+            Actions::refresh_plugin_columns(&mut app.data);
+            last_plugin_column_sync = Some(Instant::now());
+        }
+
         if let AppMode::UpdateRequested(state) = &app.mode {
             return Ok(Some(state.info.clone()));
         }
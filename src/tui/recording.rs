@@ -0,0 +1,361 @@
+//! Recording the input of a TUI session to a file that `tenex replay` can play back.
+//!
+//! A recording is JSON lines: a [`RecordedEvent::Start`] with the agents and settings the
+//! session started with, then every key press, mouse event, and resize, with a
+//! [`RecordedEvent::Mode`] line whenever the mode changed. Characters typed into an
+//! authentication prompt are replaced by `•`.
+//!
+//! When the session ends, the recording is compressed to `<file>.zst`. [`load`] reads either
+//! form, and given the original name it finds the compressed file.
+
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{BufRead as _, BufWriter, Write as _};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use parking_lot::Mutex;
+use ratatui::crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEventKind};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::agent::Storage;
+use crate::app::{App, Settings};
+use crate::state::AppMode;
+
+/// Modifier names, in the order they are written.
+const MODIFIERS: [(&str, KeyModifiers); 6] = [
+    ("Ctrl", KeyModifiers::CONTROL),
+    ("Alt", KeyModifiers::ALT),
+    ("Shift", KeyModifiers::SHIFT),
+    ("Super", KeyModifiers::SUPER),
+    ("Hyper", KeyModifiers::HYPER),
+    ("Meta", KeyModifiers::META),
+];
+
+/// Keys written by name. Characters are written as themselves and function keys as `F1`.
+const NAMED_KEYS: [(&str, KeyCode); 22] = [
+    ("Backspace", KeyCode::Backspace),
+    ("Enter", KeyCode::Enter),
+    ("Left", KeyCode::Left),
+    ("Right", KeyCode::Right),
+    ("Up", KeyCode::Up),
+    ("Down", KeyCode::Down),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+    ("Tab", KeyCode::Tab),
+    ("BackTab", KeyCode::BackTab),
+    ("Delete", KeyCode::Delete),
+    ("Insert", KeyCode::Insert),
+    ("Esc", KeyCode::Esc),
+    ("Null", KeyCode::Null),
+    ("CapsLock", KeyCode::CapsLock),
+    ("ScrollLock", KeyCode::ScrollLock),
+    ("NumLock", KeyCode::NumLock),
+    ("PrintScreen", KeyCode::PrintScreen),
+    ("Pause", KeyCode::Pause),
+    ("Menu", KeyCode::Menu),
+];
+
+/// One line of a recording.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum RecordedEvent {
+    /// The session the recording starts from; always the first line.
+    Start(Box<RecordingStart>),
+    /// A key press, such as `Ctrl+c`, `Enter`, or `F5`.
+    Key {
+        /// Modifiers and key, joined by `+`.
+        key: String,
+    },
+    /// A mouse event.
+    Mouse {
+        /// What happened, such as `Down(Left)` or `ScrollUp`.
+        kind: String,
+        /// Column of the pointer.
+        column: u16,
+        /// Row of the pointer.
+        row: u16,
+        /// Modifiers held, joined by `+`.
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        modifiers: String,
+    },
+    /// The terminal was resized.
+    Resize {
+        /// New width in columns.
+        width: u16,
+        /// New height in rows.
+        height: u16,
+    },
+    /// The app entered a mode, named like `Help` or `ErrorModal`.
+    Mode {
+        /// Name of the mode.
+        mode: String,
+    },
+}
+
+/// The session a recording starts from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingStart {
+    /// Tenex version that made the recording.
+    pub version: String,
+    /// Terminal width in columns.
+    pub width: u16,
+    /// Terminal height in rows.
+    pub height: u16,
+    /// Whether the terminal reported keys with the keyboard enhancement protocol.
+    pub keyboard_enhancement: bool,
+    /// Settings of the session.
+    pub settings: Settings,
+    /// Agents of the session.
+    pub state: Storage,
+}
+
+/// Read the events of the recording at `path`, which may be compressed.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or a line is not a recorded event.
+pub fn load(path: &Path) -> Result<Vec<RecordedEvent>> {
+    let reader = crate::compression::open(path)?;
+    let mut events = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.with_context(|| format!("Failed to read {}", path.display()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let event = serde_json::from_str(&line).with_context(|| {
+            format!(
+                "Line {} of {} is not a recorded event",
+                index + 1,
+                path.display()
+            )
+        })?;
+        events.push(event);
+    }
+    Ok(events)
+}
+
+/// The recording being written, if `tenex --record` was given.
+static RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+
+#[derive(Debug)]
+struct Recorder {
+    path: PathBuf,
+    out: BufWriter<File>,
+    /// Mode of the last [`RecordedEvent::Mode`] line.
+    mode: String,
+}
+
+/// Record the TUI session about to start to `path`, replacing the file.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be created.
+pub(crate) fn record_to(path: &Path) -> Result<()> {
+    let file =
+        File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    *RECORDER.lock() = Some(Recorder {
+        path: path.to_path_buf(),
+        out: BufWriter::new(file),
+        mode: String::new(),
+    });
+    Ok(())
+}
+
+/// Write the start of the recording once the terminal is set up.
+pub(super) fn begin(app: &App, width: u16, height: u16) {
+    write(&RecordedEvent::Start(Box::new(RecordingStart {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        width,
+        height,
+        keyboard_enhancement: app.data.keyboard_enhancement_supported,
+        settings: app.data.settings.clone(),
+        state: app.data.storage.clone(),
+    })));
+    mode(&app.mode);
+}
+
+/// Close the recording once the session is over and compress it.
+///
+/// If compression fails, the uncompressed recording is kept.
+pub(super) fn finish() {
+    let Some(recorder) = RECORDER.lock().take() else {
+        return;
+    };
+    let Recorder { path, out, .. } = recorder;
+    if let Err(err) = out
+        .into_inner()
+        .map_err(std::io::IntoInnerError::into_error)
+    {
+        warn!(error = %err, "Failed to write the input recording");
+        return;
+    }
+    match crate::compression::compress_file(&path) {
+        Ok(compressed) => info!(path = %compressed.display(), "Saved the input recording"),
+        Err(err) => warn!(error = %err, "Failed to compress the input recording"),
+    }
+}
+
+/// Record a key pressed in `mode`.
+pub(super) fn key(mode: &AppMode, code: KeyCode, modifiers: KeyModifiers) {
+    let code = match code {
+        KeyCode::Char(_) if matches!(mode, AppMode::AuthPrompt(_)) => KeyCode::Char('•'),
+        code => code,
+    };
+    write(&RecordedEvent::Key {
+        key: key_text(code, modifiers),
+    });
+}
+
+/// Record a mouse event. Moves are skipped: nothing handles them, and there are many.
+pub(super) fn mouse(kind: MouseEventKind, column: u16, row: u16, modifiers: KeyModifiers) {
+    if kind == MouseEventKind::Moved {
+        return;
+    }
+    let mut modifiers = modifiers_text(modifiers);
+    modifiers.pop();
+    write(&RecordedEvent::Mouse {
+        kind: format!("{kind:?}"),
+        column,
+        row,
+        modifiers,
+    });
+}
+
+/// Record a resize of the terminal.
+pub(super) fn resize(width: u16, height: u16) {
+    write(&RecordedEvent::Resize { width, height });
+}
+
+/// Record `mode` if the app entered it since the last recorded mode.
+pub(super) fn mode(mode: &AppMode) {
+    if let Some(recorder) = RECORDER.lock().as_mut() {
+        let name = super::mode_name(mode);
+        if recorder.mode != name {
+            recorder.mode.clone_from(&name);
+            write_line(recorder, &RecordedEvent::Mode { mode: name });
+        }
+    }
+}
+
+fn write(event: &RecordedEvent) {
+    if let Some(recorder) = RECORDER.lock().as_mut() {
+        write_line(recorder, event);
+    }
+}
+
+/// Write `event` and flush it, so a crash keeps everything before it.
+fn write_line(recorder: &mut Recorder, event: &RecordedEvent) {
+    let result = serde_json::to_writer(&mut recorder.out, event)
+        .map_err(std::io::Error::from)
+        .and_then(|()| writeln!(recorder.out))
+        .and_then(|()| recorder.out.flush());
+    if let Err(err) = result {
+        warn!(error = %err, "Failed to write the input recording");
+    }
+}
+
+/// `key` with its modifiers, such as `Ctrl+Shift+Up`.
+#[must_use]
+pub fn key_text(code: KeyCode, modifiers: KeyModifiers) -> String {
+    let mut text = modifiers_text(modifiers);
+    match code {
+        KeyCode::Char(c) => text.push(c),
+        KeyCode::F(number) => {
+            let _ = write!(text, "F{number}");
+        }
+        code => match NAMED_KEYS.iter().find(|(_, named)| *named == code) {
+            Some((name, _)) => text.push_str(name),
+            None => {
+                let _ = write!(text, "{code:?}");
+            }
+        },
+    }
+    text
+}
+
+/// Parse a key written by [`key_text`].
+#[must_use]
+pub fn parse_key(text: &str) -> Option<(KeyCode, KeyModifiers)> {
+    let (modifiers, key) = split_modifiers(text);
+    let mut chars = key.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) => KeyCode::Char(c),
+        _ => key
+            .strip_prefix('F')
+            .and_then(|number| number.parse().ok())
+            .map(KeyCode::F)
+            .or_else(|| {
+                NAMED_KEYS
+                    .iter()
+                    .find(|(name, _)| *name == key)
+                    .map(|(_, code)| *code)
+            })?,
+    };
+    Some((code, modifiers))
+}
+
+/// Parse a mouse event kind and its modifiers, as written to a recording.
+#[must_use]
+pub fn parse_mouse(kind: &str, modifiers: &str) -> Option<(MouseEventKind, KeyModifiers)> {
+    let button = |name: &str| match name {
+        "Left" => Some(MouseButton::Left),
+        "Right" => Some(MouseButton::Right),
+        "Middle" => Some(MouseButton::Middle),
+        _ => None,
+    };
+    let kind = match kind {
+        "Moved" => MouseEventKind::Moved,
+        "ScrollDown" => MouseEventKind::ScrollDown,
+        "ScrollUp" => MouseEventKind::ScrollUp,
+        "ScrollLeft" => MouseEventKind::ScrollLeft,
+        "ScrollRight" => MouseEventKind::ScrollRight,
+        kind => {
+            let (action, rest) = kind.split_once('(')?;
+            let button = button(rest.strip_suffix(')')?)?;
+            match action {
+                "Down" => MouseEventKind::Down(button),
+                "Up" => MouseEventKind::Up(button),
+                "Drag" => MouseEventKind::Drag(button),
+                _ => return None,
+            }
+        }
+    };
+    if modifiers.is_empty() {
+        return Some((kind, KeyModifiers::NONE));
+    }
+    let text = format!("{modifiers}+");
+    let (modifiers, rest) = split_modifiers(&text);
+    rest.is_empty().then_some((kind, modifiers))
+}
+
+/// The names of `modifiers`, each followed by `+`.
+fn modifiers_text(modifiers: KeyModifiers) -> String {
+    let mut text = String::new();
+    for (name, modifier) in MODIFIERS {
+        if modifiers.contains(modifier) {
+            text.push_str(name);
+            text.push('+');
+        }
+    }
+    text
+}
+
+/// Split leading `Name+` modifiers off `text`. A lone `+` is a key, not a separator.
+fn split_modifiers(text: &str) -> (KeyModifiers, &str) {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = text;
+    'outer: loop {
+        for (name, modifier) in MODIFIERS {
+            if let Some(after) = rest.strip_prefix(name).and_then(|r| r.strip_prefix('+')) {
+                modifiers |= modifier;
+                rest = after;
+                continue 'outer;
+            }
+        }
+        return (modifiers, rest);
+    }
+}
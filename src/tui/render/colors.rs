@@ -1,8 +1,10 @@
 //! Color palette for the TUI
 //!
 //! `StarCraft` II-inspired sci-fi palette: dark metal surfaces with cyan glow accents.
+//! The high-contrast theme swaps each palette color for a basic terminal color after a frame
+//! is drawn.
 
-use ratatui::{style::Color, widgets::BorderType};
+use ratatui::{buffer::Buffer, style::Color, widgets::BorderType};
 
 /// Border style used across the app.
 pub const BORDER_TYPE: BorderType = BorderType::Double;
@@ -23,6 +25,8 @@ pub const STATUS_RUNNING: Color = Color::Rgb(0, 220, 140);
 pub const STATUS_STARTING: Color = Color::Rgb(255, 200, 60);
 pub const STATUS_WAITING: Color = Color::Rgb(255, 90, 90);
 pub const DOCKER_BADGE: Color = Color::Rgb(80, 160, 255);
+pub const SANDBOX_BADGE: Color = Color::Rgb(120, 200, 140);
+pub const TICKET_BADGE: Color = Color::Rgb(200, 150, 255);
 
 // Diff
 pub const DIFF_ADD: Color = Color::Rgb(0, 200, 120);
@@ -41,3 +45,44 @@ pub const INPUT_BG: Color = Color::Rgb(14, 24, 38);
 pub const ACCENT_POSITIVE: Color = Color::Rgb(0, 220, 140);
 pub const ACCENT_NEGATIVE: Color = Color::Rgb(255, 90, 90);
 pub const ACCENT_WARNING: Color = Color::Rgb(255, 180, 60);
+
+/// Palette colors and what the high-contrast theme draws instead.
+const HIGH_CONTRAST: &[(Color, Color)] = &[
+    (BORDER, Color::White),
+    (SELECTED, Color::LightYellow),
+    (SURFACE, Color::Black),
+    (SURFACE_HIGHLIGHT, Color::Blue),
+    (TEXT_PRIMARY, Color::White),
+    (TEXT_DIM, Color::White),
+    (TEXT_MUTED, Color::Gray),
+    (STATUS_RUNNING, Color::LightGreen),
+    (STATUS_STARTING, Color::LightYellow),
+    (STATUS_WAITING, Color::LightRed),
+    (DOCKER_BADGE, Color::LightCyan),
+    (SANDBOX_BADGE, Color::LightGreen),
+    (TICKET_BADGE, Color::LightMagenta),
+    (DIFF_ADD, Color::LightGreen),
+    (DIFF_HUNK, Color::LightCyan),
+    (DIFF_SELECTION_BG, Color::DarkGray),
+    (DIFF_CURSOR_BG, Color::Blue),
+    (MODAL_BG, Color::Black),
+    (MODAL_BORDER_WARNING, Color::LightYellow),
+    (INPUT_BG, Color::Black),
+];
+
+/// The high-contrast color drawn for palette color `color`; other colors stay as they are.
+#[must_use]
+pub fn high_contrast(color: Color) -> Color {
+    HIGH_CONTRAST
+        .iter()
+        .find(|(palette, _)| *palette == color)
+        .map_or(color, |(_, replacement)| *replacement)
+}
+
+/// Redraw every palette color in `buffer` in its high-contrast color.
+pub fn apply_high_contrast(buffer: &mut Buffer) {
+    for cell in &mut buffer.content {
+        cell.fg = high_contrast(cell.fg);
+        cell.bg = high_contrast(cell.bg);
+    }
+}
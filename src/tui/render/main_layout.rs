@@ -1,13 +1,15 @@
 //! Main layout rendering: agent list, content pane, status bar, tabs
 
-use crate::agent::{AgentRuntime, Status, WorkspaceKind};
-use crate::app::{App, DiffLineMeta, PreviewSelectionPoint, Tab};
+use crate::agent::{Agent, AgentRuntime, Status, WorkspaceKind};
+use crate::app::{
+    App, DiffLineMeta, Notification, NotificationSeverity, PreviewSelectionPoint, Tab,
+};
 use crate::app::{SidebarAgentInfo, SidebarItem, SidebarProject};
 use crate::state::AppMode;
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
-    style::{Modifier, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span, Text},
     widgets::{
         Block, Borders, List, ListItem, ListState, Paragraph, Scrollbar, ScrollbarOrientation,
@@ -16,27 +18,39 @@ use ratatui::{
 };
 
 use super::colors;
-
-fn agent_list_item<'a>(
-    app: &App,
-    idx: usize,
-    sidebar_agent: &SidebarAgentInfo<'a>,
-) -> ListItem<'a> {
-    let info = &sidebar_agent.info;
-    let (status_symbol, status_color) = match info.agent.status {
-        Status::Starting => (info.agent.status.symbol(), colors::STATUS_STARTING),
+use crate::text_width;
+
+/// Symbol and color of an agent's status in the agent list.
+fn status_indicator(app: &App, agent: &Agent) -> (&'static str, Color) {
+    match agent.status {
+        Status::Starting => (agent.status.symbol(), colors::STATUS_STARTING),
+        Status::Finished(0) => (agent.status.symbol(), colors::TEXT_MUTED),
+        Status::Finished(_) | Status::TimedOut => (agent.status.symbol(), colors::STATUS_WAITING),
         Status::Running => {
-            if app.data.ui.agent_is_waiting_for_input(info.agent.id) {
-                if app.data.ui.agent_has_unseen_waiting_output(info.agent.id) {
+            if agent.budget.is_paused() {
+                ("⏸", colors::STATUS_WAITING)
+            } else if app.data.ui.agent_is_waiting_for_input(agent.id) {
+                if app.data.ui.agent_has_unseen_waiting_output(agent.id) {
                     ("◐", colors::STATUS_STARTING)
                 } else {
                     ("○", colors::STATUS_WAITING)
                 }
+            } else if app.data.ui.agent_has_finished(agent.id) {
+                ("✓", colors::STATUS_WAITING)
             } else {
-                (info.agent.status.symbol(), colors::STATUS_RUNNING)
+                (agent.status.symbol(), colors::STATUS_RUNNING)
             }
         }
-    };
+    }
+}
+
+fn agent_list_item<'a>(
+    app: &App,
+    idx: usize,
+    sidebar_agent: &SidebarAgentInfo<'a>,
+) -> ListItem<'a> {
+    let info = &sidebar_agent.info;
+    let (status_symbol, status_color) = status_indicator(app, info.agent);
 
     let style = if idx == app.data.selected {
         Style::default()
@@ -76,6 +90,12 @@ fn agent_list_item<'a>(
             Style::default().fg(colors::DOCKER_BADGE),
         ));
     }
+    if !info.agent.sandbox.is_off() && info.agent.runtime == AgentRuntime::Host {
+        spans.push(Span::styled(
+            "[S] ",
+            Style::default().fg(colors::SANDBOX_BADGE),
+        ));
+    }
     if sidebar_agent.synthesis_marked {
         spans.push(Span::styled(
             "[m] ",
@@ -90,6 +110,20 @@ fn agent_list_item<'a>(
             Style::default().fg(colors::SELECTED),
         ));
     }
+    if let Some(ticket) = &info.agent.ticket {
+        spans.push(Span::styled(
+            format!("[{ticket}] "),
+            Style::default().fg(colors::TICKET_BADGE),
+        ));
+    }
+    spans.extend(dependency_spans(app, info.agent));
+    spans.extend(swarm_link_spans(app, info.agent));
+    if let Some(alerts) = app.data.ui.change_alerts_by_agent.get(&info.agent.id) {
+        spans.push(Span::styled(
+            format!("[⚠{}] ", alerts.len()),
+            Style::default().fg(colors::ACCENT_WARNING),
+        ));
+    }
     spans.push(Span::styled(&info.agent.title, style));
     if info.agent.workspace_kind == WorkspaceKind::PlainDir {
         spans.push(Span::styled(
@@ -101,14 +135,163 @@ fn agent_list_item<'a>(
         count_indicator,
         Style::default().fg(colors::TEXT_DIM),
     ));
-    spans.push(Span::styled(
-        format!(" ({})", info.agent.age_string()),
-        Style::default().fg(colors::TEXT_MUTED),
-    ));
+    spans.extend(agent_time_and_usage_spans(app, info.agent));
 
     ListItem::new(Line::from(spans)).style(style)
 }
 
+/// Dependency badges: the agent the sidebar agent waits for (`/depends_on`), and how many
+/// agents wait for it.
+fn dependency_spans<'a>(app: &App, agent: &crate::agent::Agent) -> Vec<Span<'a>> {
+    let mut spans = Vec::new();
+    if let Some(dependency) = &agent.blocked_by {
+        let upstream = app
+            .data
+            .storage
+            .get(dependency.agent_id)
+            .map_or_else(|| "gone".to_string(), |upstream| upstream.title.clone());
+        spans.push(Span::styled(
+            format!("[⧗ {upstream}] "),
+            Style::default().fg(colors::ACCENT_WARNING),
+        ));
+    }
+    let waiting = app
+        .data
+        .storage
+        .iter()
+        .filter(|other| {
+            other
+                .blocked_by
+                .as_ref()
+                .is_some_and(|dependency| dependency.agent_id == agent.id)
+        })
+        .count();
+    if waiting > 0 {
+        spans.push(Span::styled(
+            format!("[→{waiting}] "),
+            Style::default().fg(colors::ACCENT_WARNING),
+        ));
+    }
+    spans
+}
+
+/// Cross-repo swarm badges: the swarm root a root in another repository works for, and how
+/// many roots in other repositories work for the sidebar agent.
+fn swarm_link_spans<'a>(app: &App, agent: &crate::agent::Agent) -> Vec<Span<'a>> {
+    let mut spans = Vec::new();
+    if let Some(swarm_root) = agent.swarm_root {
+        let title = app
+            .data
+            .storage
+            .get(swarm_root)
+            .map_or_else(|| "gone".to_string(), |root| root.title.clone());
+        spans.push(Span::styled(
+            format!("[⇄ {title}] "),
+            Style::default().fg(colors::TICKET_BADGE),
+        ));
+    }
+    let linked = app.data.storage.linked_roots(agent.id).len();
+    if linked > 0 {
+        spans.push(Span::styled(
+            format!("[⇄{linked}] "),
+            Style::default().fg(colors::TICKET_BADGE),
+        ));
+    }
+    spans
+}
+
+/// Trailing sidebar spans: elapsed/active time, sampled resource usage, and plugin columns.
+fn agent_time_and_usage_spans<'a>(app: &App, agent: &crate::agent::Agent) -> Vec<Span<'a>> {
+    let time = if agent.active_ms > 0 {
+        format!(
+            " ({}, {} active)",
+            agent.age_string(),
+            agent.active_string()
+        )
+    } else {
+        format!(" ({})", agent.age_string())
+    };
+    let mut spans = vec![Span::styled(time, Style::default().fg(colors::TEXT_MUTED))];
+
+    if let Some(usage) = app.data.ui.resource_usage_by_agent.get(&agent.id) {
+        let usage_color = if usage.runaway {
+            colors::STATUS_WAITING
+        } else {
+            colors::TEXT_MUTED
+        };
+        let flag = if usage.runaway { " (high)" } else { "" };
+        spans.push(Span::styled(
+            format!(" {}% {}{flag}", usage.cpu_percent, usage.memory_label()),
+            Style::default().fg(usage_color),
+        ));
+    }
+
+    // Context fill is only worth sidebar space once it nears the limit.
+    if let Some(percent) = app.data.settings.context_warning_percent()
+        && let Some(usage) = app.data.ui.context_usage_by_agent.get(&agent.id)
+        && usage.is_near_limit(percent)
+    {
+        spans.push(Span::styled(
+            format!(" ctx {}%", usage.percent().unwrap_or_default()),
+            Style::default().fg(colors::ACCENT_WARNING),
+        ));
+    }
+
+    if let Some(divergence) = app.data.ui.branch_divergence_by_agent.get(&agent.id) {
+        spans.extend(branch_divergence_spans(
+            divergence,
+            app.data.settings.stale_base_commits(),
+        ));
+    }
+
+    if let Some(values) = app.data.ui.plugin_columns_by_agent.get(&agent.id) {
+        spans.extend(
+            values.iter().map(|value| {
+                Span::styled(format!(" {value}"), Style::default().fg(colors::TEXT_DIM))
+            }),
+        );
+    }
+
+    spans
+}
+
+/// Ahead/behind counts against the base branch, flagging stale bases and deleted remotes.
+fn branch_divergence_spans<'a>(
+    divergence: &crate::git::BranchDivergence,
+    stale_after: u32,
+) -> Vec<Span<'a>> {
+    let mut spans = Vec::new();
+    if divergence.ahead > 0 || divergence.behind > 0 {
+        spans.push(Span::styled(
+            format!(" ↑{}", divergence.ahead),
+            Style::default().fg(colors::TEXT_MUTED),
+        ));
+        // Any commits on the base the agent doesn't have yet: `U` updates from base.
+        let behind_color = if divergence.behind > 0 {
+            colors::ACCENT_WARNING
+        } else {
+            colors::TEXT_MUTED
+        };
+        spans.push(Span::styled(
+            format!("↓{}", divergence.behind),
+            Style::default().fg(behind_color),
+        ));
+    }
+    if divergence.behind >= stale_after {
+        spans.push(Span::styled(
+            " base moved",
+            Style::default().fg(colors::STATUS_WAITING),
+        ));
+    }
+    if divergence.upstream_gone {
+        spans.push(Span::styled(
+            " remote gone",
+            Style::default().fg(colors::STATUS_WAITING),
+        ));
+    }
+    spans
+}
+
 fn project_list_item<'a>(app: &App, idx: usize, project: &'a SidebarProject) -> ListItem<'a> {
     let style = if idx == app.data.selected {
         Style::default()
@@ -215,6 +398,7 @@ pub fn render_content_pane(frame: &mut Frame<'_>, app: &App, area: Rect) {
         Tab::Preview => render_preview(frame, app, area),
         Tab::Diff => render_diff(frame, app, area),
         Tab::Commits => render_commits(frame, app, area),
+        Tab::Scratchpad => render_scratchpad(frame, app, area),
     }
 }
 
@@ -224,7 +408,7 @@ fn tab_bar_tab_has_unseen_changes(app: &App, tab: Tab) -> bool {
     };
 
     match tab {
-        Tab::Preview => false,
+        Tab::Preview | Tab::Scratchpad => false,
         Tab::Diff => {
             if app.data.active_tab == Tab::Diff {
                 return false;
@@ -245,7 +429,7 @@ fn tab_bar_tab_has_unseen_changes(app: &App, tab: Tab) -> bool {
 }
 
 fn tab_bar_tab_width(label: &str, has_unseen_changes: bool) -> u16 {
-    let label_width = u16::try_from(label.chars().count()).unwrap_or(0);
+    let label_width = u16::try_from(text_width::width(label)).unwrap_or(0);
     let decoration_width = if has_unseen_changes { 4 } else { 2 };
     label_width.saturating_add(decoration_width)
 }
@@ -260,6 +444,7 @@ pub fn tab_for_tab_bar_offset(app: &App, offset_x: u16) -> Option<Tab> {
     let diff_start = preview_w;
     let commits_start = diff_start.saturating_add(diff_w);
     let commits_end = commits_start.saturating_add(commits_w);
+    let scratchpad_end = commits_end.saturating_add(tab_bar_tab_width("Scratchpad", false));
 
     if offset_x < diff_start {
         return Some(Tab::Preview);
@@ -273,6 +458,10 @@ pub fn tab_for_tab_bar_offset(app: &App, offset_x: u16) -> Option<Tab> {
         return Some(Tab::Commits);
     }
 
+    if offset_x < scratchpad_end {
+        return Some(Tab::Scratchpad);
+    }
+
     None
 }
 
@@ -319,6 +508,8 @@ fn tab_bar_line(app: &App) -> Line<'static> {
 
     push_tab("Commits", commits_active, commits_has_unseen_changes);
 
+    push_tab("Scratchpad", app.data.active_tab == Tab::Scratchpad, false);
+
     Line::from(spans)
 }
 
@@ -336,11 +527,17 @@ pub fn render_preview(frame: &mut Frame<'_>, app: &App, area: Rect) {
     let line_count = full_text.lines.len();
 
     // Use highlighted border when focused, show exit hint in title.
-    let title = if is_focused {
-        " Terminal Output (ATTACHED) [Ctrl+q detach] "
+    let mut title = if is_focused {
+        " Terminal Output (ATTACHED) [Ctrl+q detach] ".to_string()
     } else {
-        " Terminal Output (read-only) "
+        " Terminal Output (read-only) ".to_string()
     };
+    // Show the title the agent's program set for itself (OSC 0/2), if any.
+    if let Some(pane_title) = &app.data.ui.preview_pane_title {
+        title.push_str("- ");
+        title.extend(pane_title.chars().filter(|c| !c.is_control()));
+        title.push(' ');
+    }
 
     let border_color = if is_focused || matches!(&app.mode, AppMode::Scrolling(_)) {
         colors::SELECTED
@@ -458,7 +655,7 @@ fn apply_preview_selection_to_line(
     let line_len = line
         .spans
         .iter()
-        .map(|span| span.content.as_ref().chars().count())
+        .map(|span| text_width::width(span.content.as_ref()))
         .sum::<usize>();
     if line_len == 0 {
         return;
@@ -493,7 +690,7 @@ fn apply_preview_selection_to_line(
 
     for span in original_spans {
         let text = span.content.as_ref();
-        let span_len = text.chars().count();
+        let span_len = text_width::width(text);
         if span_len == 0 {
             new_spans.push(span);
             continue;
@@ -520,10 +717,14 @@ fn apply_preview_selection_to_line(
             .saturating_sub(span_start)
             .min(span_len.saturating_sub(1));
 
-        let start_byte = byte_index_for_char(text, overlap_start);
-        let end_byte = byte_index_for_char(text, overlap_end.saturating_add(1));
+        let Some((start_byte, end_byte)) =
+            text_width::byte_range_for_columns(text, overlap_start, overlap_end)
+        else {
+            new_spans.push(span);
+            continue;
+        };
 
-        if overlap_start > 0 {
+        if start_byte > 0 {
             new_spans.push(Span::styled(text[..start_byte].to_string(), span.style));
         }
 
@@ -540,17 +741,6 @@ fn apply_preview_selection_to_line(
     line.spans = new_spans;
 }
 
-fn byte_index_for_char(text: &str, char_idx: usize) -> usize {
-    if char_idx == 0 {
-        return 0;
-    }
-
-    match text.char_indices().nth(char_idx) {
-        Some((byte_idx, _)) => byte_idx,
-        None => text.len(),
-    }
-}
-
 fn render_preview_cursor(
     frame: &mut Frame<'_>,
     app: &App,
@@ -824,6 +1014,74 @@ pub fn render_commits(frame: &mut Frame<'_>, app: &App, area: Rect) {
     );
 }
 
+/// Render the shared scratchpad pane
+pub fn render_scratchpad(frame: &mut Frame<'_>, app: &App, area: Rect) {
+    let content = &app.data.ui.scratchpad_content;
+
+    let border_color = if matches!(&app.mode, AppMode::Scrolling(_)) {
+        colors::SELECTED
+    } else {
+        colors::BORDER
+    };
+
+    let block = Block::default()
+        .title(" Scratchpad [Enter edit] ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color))
+        .border_type(colors::BORDER_TYPE)
+        .style(Style::default().bg(colors::SURFACE));
+    frame.render_widget(block.clone(), area);
+
+    let inner = block.inner(area);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .split(inner);
+
+    render_tab_bar(frame, app, chunks[0]);
+
+    let content_area = chunks[1];
+    let visible_height = usize::from(content_area.height);
+    let total_lines = app.data.ui.scratchpad_line_ranges.len();
+    let max_scroll = total_lines.saturating_sub(visible_height);
+    let scroll = app.data.ui.scratchpad_scroll.min(max_scroll);
+    let end_line = (scroll + visible_height).min(total_lines);
+
+    let lines: Vec<Line<'_>> = app.data.ui.scratchpad_line_ranges[scroll..end_line]
+        .iter()
+        .map(|&(start, end)| {
+            let line = &content[start..end];
+            if line.starts_with('#') {
+                Line::styled(
+                    line,
+                    Style::default()
+                        .fg(colors::TEXT_PRIMARY)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else if line.starts_with('(') && line.ends_with(')') {
+                Line::styled(line, Style::default().fg(colors::TEXT_MUTED))
+            } else {
+                Line::styled(line, Style::default().fg(colors::TEXT_PRIMARY))
+            }
+        })
+        .collect();
+
+    let paragraph = Paragraph::new(Text::from(lines))
+        .wrap(Wrap { trim: false })
+        .style(Style::default().bg(colors::SURFACE));
+    frame.render_widget(paragraph, content_area);
+
+    render_commits_scrollbar(
+        frame,
+        area,
+        content_area,
+        total_lines,
+        visible_height,
+        max_scroll,
+        scroll,
+    );
+}
+
 fn render_commits_scrollbar(
     frame: &mut Frame<'_>,
     area: Rect,
@@ -900,33 +1158,68 @@ fn render_diff_scrollbar(
     frame.render_stateful_widget(scrollbar, scrollbar_area, &mut scrollbar_state);
 }
 
+fn notification_span(notification: &Notification) -> Span<'static> {
+    match notification.severity {
+        NotificationSeverity::Info => Span::styled(
+            format!(" {} ", notification.message),
+            Style::default().fg(colors::STATUS_RUNNING),
+        ),
+        NotificationSeverity::Warning => Span::styled(
+            format!(" Warning: {} ", notification.message),
+            Style::default()
+                .fg(colors::ACCENT_WARNING)
+                .add_modifier(Modifier::BOLD),
+        ),
+        NotificationSeverity::Error => Span::styled(
+            format!(" Error: {} ", notification.message),
+            Style::default()
+                .fg(colors::DIFF_REMOVE)
+                .add_modifier(Modifier::BOLD),
+        ),
+    }
+}
+
 /// Render the status bar
 pub fn render_status_bar(frame: &mut Frame<'_>, app: &App, area: Rect) {
     // Don't show error in status bar when error modal is displayed
     let showing_error_modal = matches!(&app.mode, AppMode::ErrorModal(_));
 
-    let left_content = match (
-        &app.data.ui.last_error,
-        &app.data.ui.status_message,
-        showing_error_modal,
-    ) {
+    let notification = app.data.ui.notifications.current();
+    let left_content = match (&app.data.ui.last_error, notification, showing_error_modal) {
         (Some(error), _, false) => Span::styled(
             format!(" Error: {error} "),
             Style::default()
                 .fg(colors::DIFF_REMOVE)
                 .add_modifier(Modifier::BOLD),
         ),
-        (_, Some(status), _) => Span::styled(
-            format!(" {status} "),
-            Style::default().fg(colors::STATUS_RUNNING),
-        ),
+        (_, Some(notification), _) => notification_span(notification),
         _ => {
             let running = app.running_agent_count();
             let hints = crate::config::status_hints();
-            Span::styled(
-                format!(" {running} running | {hints} "),
-                Style::default().fg(colors::TEXT_DIM),
-            )
+            let questions = app.data.inbox.entries.len();
+            let paused = if app.data.pause.is_quiet_hours() {
+                "quiet hours | "
+            } else if app.data.pause.is_paused() {
+                "paused | "
+            } else {
+                ""
+            };
+            if questions > 0 {
+                Span::styled(
+                    format!(" {running} running | {paused}{questions} question(s) [!] | {hints} "),
+                    Style::default().fg(colors::ACCENT_WARNING),
+                )
+            } else if paused.is_empty() {
+                Span::styled(
+                    format!(" {running} running | {hints} "),
+                    Style::default().fg(colors::TEXT_DIM),
+                )
+            } else {
+                Span::styled(
+                    format!(" {running} running | {paused}{hints} "),
+                    Style::default().fg(colors::ACCENT_WARNING),
+                )
+            }
         }
     };
 
@@ -935,6 +1228,10 @@ pub fn render_status_bar(frame: &mut Frame<'_>, app: &App, area: Rect) {
     } else {
         "Keys → Tenex"
     };
+    let key_routing = app.data.settings.active_profile().map_or_else(
+        || key_routing.to_string(),
+        |profile| format!("Profile: {profile} | {key_routing}"),
+    );
     let key_routing_style = if matches!(&app.mode, AppMode::PreviewFocused(_)) {
         Style::default()
             .fg(colors::TEXT_PRIMARY)
@@ -944,21 +1241,48 @@ pub fn render_status_bar(frame: &mut Frame<'_>, app: &App, area: Rect) {
     };
     let key_routing_span = Span::styled(format!(" {key_routing} "), key_routing_style);
 
-    let key_routing_width = u16::try_from(key_routing.chars().count().saturating_add(2))
+    let now = std::time::Instant::now();
+    let progress = app
+        .data
+        .background_ops
+        .in_flight()
+        .iter()
+        .map(|op| op.progress_line(now, app.data.settings.motion.animates()))
+        .collect::<Vec<_>>()
+        .join(" · ");
+    let progress_width = if progress.is_empty() {
+        0
+    } else {
+        u16::try_from(text_width::width(&progress).saturating_add(2)).unwrap_or(u16::MAX)
+    };
+
+    let key_routing_width = u16::try_from(text_width::width(&key_routing).saturating_add(2))
         .unwrap_or(0)
         .min(area.width);
+    let progress_width = progress_width.min(area.width.saturating_sub(key_routing_width) / 2);
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Min(0), Constraint::Length(key_routing_width)])
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(progress_width),
+            Constraint::Length(key_routing_width),
+        ])
         .split(area);
 
     let left = Paragraph::new(Line::from(left_content)).style(Style::default().bg(colors::SURFACE));
     frame.render_widget(left, chunks[0]);
 
+    let progress = Paragraph::new(Line::from(Span::styled(
+        format!(" {progress} "),
+        Style::default().fg(colors::STATUS_STARTING),
+    )))
+    .style(Style::default().bg(colors::SURFACE));
+    frame.render_widget(progress, chunks[1]);
+
     let right = Paragraph::new(Line::from(key_routing_span))
         .style(Style::default().bg(colors::SURFACE))
         .alignment(Alignment::Right);
-    frame.render_widget(right, chunks[1]);
+    frame.render_widget(right, chunks[2]);
 }
 
 /// Calculate the inner dimensions of the preview pane (content area without borders)
@@ -9,8 +9,10 @@ pub mod colors;
 pub mod main_layout;
 pub mod modals;
 
+use crate::agent::Status;
 use crate::app::AgentRole;
 use crate::app::App;
+use crate::app::Theme;
 use crate::state::{AppMode, ConfirmAction};
 use ratatui::{
     Frame,
@@ -40,36 +42,51 @@ pub fn render(frame: &mut Frame<'_>, app: &App) {
         AppMode::Changelog(state) => modals::render_changelog_overlay(frame, app, state),
         AppMode::Help(_) => modals::render_help_overlay(frame, app),
         AppMode::CommandPalette(_) => modals::render_command_palette_overlay(frame, app),
+        AppMode::Creating(_) | AppMode::Prompting(_) if app.data.editing_new_agent_command() => {
+            modals::render_input_overlay(
+                frame,
+                "New Agent: Command",
+                "Command for this agent (Enter to use it, Esc to discard):",
+                &app.data.input.buffer,
+                app.data.input.cursor,
+            );
+        }
         AppMode::Creating(_) => {
             modals::render_input_overlay(
                 frame,
-                "New Agent",
-                "Enter agent name:",
+                &format!("New Agent: {}", app.data.new_agent_program_label()),
+                "Enter agent name (Ctrl+P to edit the command):",
                 &app.data.input.buffer,
                 app.data.input.cursor,
             );
         }
-        AppMode::Prompting(_) => modals::render_input_overlay(
+        AppMode::Prompting(_) => modals::render_prompt_overlay(
             frame,
-            "New Agent with Prompt",
-            "Enter prompt:",
+            &format!(
+                "New Agent with Prompt: {}",
+                app.data.new_agent_program_label()
+            ),
+            &context_files_label(app, "Enter prompt"),
             &app.data.input.buffer,
             app.data.input.cursor,
+            app.data.prompt_token_estimate(false),
         ),
         AppMode::ChildCount(_) => modals::render_count_picker_overlay(frame, app),
-        AppMode::ChildPrompt(_) => modals::render_input_overlay(
+        AppMode::ChildPrompt(_) => modals::render_prompt_overlay(
             frame,
             "Spawn Children",
-            "Enter task for children:",
+            &context_files_label(app, "Enter task for children"),
             &app.data.input.buffer,
             app.data.input.cursor,
+            app.data.prompt_token_estimate(false),
         ),
-        AppMode::Broadcasting(_) => modals::render_input_overlay(
+        AppMode::Broadcasting(_) => modals::render_prompt_overlay(
             frame,
             "Broadcast Message",
             "Enter message to broadcast to leaf agents:",
             &app.data.input.buffer,
             app.data.input.cursor,
+            app.data.prompt_token_estimate(true),
         ),
         AppMode::ReconnectPrompt(_) => {
             let title = app
@@ -84,12 +101,13 @@ pub fn render(frame: &mut Frame<'_>, app: &App) {
                         "Reconnect Agent"
                     }
                 });
-            modals::render_input_overlay(
+            modals::render_prompt_overlay(
                 frame,
                 title,
                 "Edit prompt (or leave empty):",
                 &app.data.input.buffer,
                 app.data.input.cursor,
+                app.data.prompt_token_estimate(false),
             );
         }
         AppMode::TerminalPrompt(_) => modals::render_input_overlay(
@@ -123,12 +141,82 @@ pub fn render(frame: &mut Frame<'_>, app: &App) {
                 app.data.input.cursor,
             );
         }
-        AppMode::SynthesisPrompt(_) => modals::render_input_overlay(
+        AppMode::SynthesisPrompt(_) => modals::render_prompt_overlay(
             frame,
             "Synthesize",
             "Add extra instructions for the parent agent (optional):",
             &app.data.input.buffer,
             app.data.input.cursor,
+            app.data.prompt_token_estimate(true),
+        ),
+        AppMode::EditTask(_) => modals::render_prompt_overlay(
+            frame,
+            "Edit Task",
+            "Revise the task to send to the agent:",
+            &app.data.input.buffer,
+            app.data.input.cursor,
+            app.data.prompt_token_estimate(true),
+        ),
+        AppMode::EditScratchpad(_) => modals::render_input_overlay(
+            frame,
+            "Edit Scratchpad",
+            "Notes shared with every agent in this swarm:",
+            &app.data.input.buffer,
+            app.data.input.cursor,
+        ),
+        AppMode::DependencyMessage(_) => modals::render_prompt_overlay(
+            frame,
+            "Wait for Agent",
+            "Message to send once the other agent is done:",
+            &app.data.input.buffer,
+            app.data.input.cursor,
+            app.data.prompt_token_estimate(true),
+        ),
+        AppMode::AddRepo(_) => modals::render_input_overlay(
+            frame,
+            "Add Repository",
+            "Path of the repository to add to the workspace:",
+            &app.data.input.buffer,
+            app.data.input.cursor,
+        ),
+        AppMode::Scope(_) => modals::render_input_overlay(
+            frame,
+            "Scope",
+            "Directory to keep the agent's changes to (empty for the whole repository):",
+            &app.data.input.buffer,
+            app.data.input.cursor,
+        ),
+        AppMode::SwitchModel(_) => modals::render_input_overlay(
+            frame,
+            "Switch Model",
+            "Command to relaunch the agent with:",
+            &app.data.input.buffer,
+            app.data.input.cursor,
+        ),
+        AppMode::AuthPrompt(state) if state.is_secret() => {
+            let input = &app.data.input;
+            let hidden = "•".repeat(input.buffer.chars().count());
+            let cursor = input.buffer[..input.cursor].chars().count() * '•'.len_utf8();
+            modals::render_input_overlay(frame, "Authentication", &state.prompt, &hidden, cursor);
+        }
+        AppMode::AuthPrompt(state) => modals::render_input_overlay(
+            frame,
+            "Authentication",
+            &state.prompt,
+            &app.data.input.buffer,
+            app.data.input.cursor,
+        ),
+        AppMode::Reply(_) => modals::render_prompt_overlay(
+            frame,
+            "Reply",
+            &format!(
+                "Send to {}:",
+                app.selected_agent()
+                    .map_or("the selected agent", |agent| agent.title.as_str())
+            ),
+            &app.data.input.buffer,
+            app.data.input.cursor,
+            app.data.prompt_token_estimate(true),
         ),
         AppMode::Confirming(state) => {
             let action = state.action;
@@ -141,22 +229,28 @@ pub fn render(frame: &mut Frame<'_>, app: &App) {
                         ))]
                     },
                     |agent| {
-                        let warning = if agent.is_root() && agent.is_git_workspace() {
-                            let delete_branch =
-                                agent.branch.starts_with(&app.data.config.branch_prefix)
-                                    || agent.branch.starts_with("tenex/");
-                            if delete_branch {
-                                "This will delete the worktree and branch."
+                        let outcome = if agent.is_root() && agent.is_git_workspace() {
+                            let removes = if app.data.is_managed_branch(agent) {
+                                "worktree and branch"
                             } else {
-                                "This will delete the worktree."
-                            }
-                        } else if agent.is_root() {
-                            "This will close the session and stop the agent."
+                                "worktree"
+                            };
+                            modals::kill_choice_lines(removes)
                         } else {
-                            "This will close the window and stop the agent."
+                            let warning = if agent.imported_tmux_session.is_some() {
+                                "This will kill the tmux session and stop the agent."
+                            } else if agent.is_root() {
+                                "This will close the session and stop the agent."
+                            } else {
+                                "This will close the window and stop the agent."
+                            };
+                            vec![Line::from(Span::styled(
+                                warning,
+                                Style::default().fg(colors::DIFF_REMOVE),
+                            ))]
                         };
 
-                        vec![
+                        let mut lines = vec![
                             Line::from(Span::styled(
                                 "Kill this agent?",
                                 Style::default().fg(colors::TEXT_PRIMARY),
@@ -186,11 +280,9 @@ pub fn render(frame: &mut Frame<'_>, app: &App) {
                                 ),
                             ]),
                             Line::from(""),
-                            Line::from(Span::styled(
-                                warning,
-                                Style::default().fg(colors::DIFF_REMOVE),
-                            )),
-                        ]
+                        ];
+                        lines.extend(outcome);
+                        lines
                     },
                 ),
                 ConfirmAction::InterruptAgent => app.selected_agent().map_or_else(
@@ -240,6 +332,50 @@ pub fn render(frame: &mut Frame<'_>, app: &App) {
                         ]
                     },
                 ),
+                ConfirmAction::Compact => app.selected_agent().map_or_else(
+                    || {
+                        vec![Line::from(Span::styled(
+                            "No agent selected",
+                            Style::default().fg(colors::TEXT_PRIMARY),
+                        ))]
+                    },
+                    |agent| {
+                        vec![
+                            Line::from(Span::styled(
+                                "Compact this agent?",
+                                Style::default()
+                                    .fg(colors::TEXT_PRIMARY)
+                                    .add_modifier(Modifier::BOLD),
+                            )),
+                            Line::from(""),
+                            Line::from(vec![
+                                Span::styled("  Name:    ", Style::default().fg(colors::TEXT_DIM)),
+                                Span::styled(
+                                    &agent.title,
+                                    Style::default()
+                                        .fg(colors::TEXT_PRIMARY)
+                                        .add_modifier(Modifier::BOLD),
+                                ),
+                            ]),
+                            Line::from(vec![
+                                Span::styled("  Program: ", Style::default().fg(colors::TEXT_DIM)),
+                                Span::styled(
+                                    &agent.program,
+                                    Style::default().fg(colors::TEXT_PRIMARY),
+                                ),
+                            ]),
+                            Line::from(""),
+                            Line::from(Span::styled(
+                                "It summarizes its work, then restarts with a",
+                                Style::default().fg(colors::TEXT_DIM),
+                            )),
+                            Line::from(Span::styled(
+                                "fresh context that starts from the summary.",
+                                Style::default().fg(colors::TEXT_DIM),
+                            )),
+                        ]
+                    },
+                ),
                 ConfirmAction::Reset => {
                     vec![Line::from(Span::styled(
                         "Reset all agents?",
@@ -319,10 +455,21 @@ pub fn render(frame: &mut Frame<'_>, app: &App) {
                     )
                 }
                 ConfirmAction::Quit => {
-                    vec![Line::from(Span::styled(
-                        "Quit with running agents?",
-                        Style::default().fg(colors::TEXT_PRIMARY),
-                    ))]
+                    let running = app
+                        .data
+                        .storage
+                        .iter()
+                        .filter(|agent| agent.status == Status::Running)
+                        .count();
+                    let mut lines = vec![
+                        Line::from(Span::styled(
+                            format!("Quit with {running} running agent(s)?"),
+                            Style::default().fg(colors::TEXT_PRIMARY),
+                        )),
+                        Line::from(""),
+                    ];
+                    lines.extend(modals::quit_choice_lines());
+                    lines
                 }
                 ConfirmAction::Synthesize => app.selected_agent().map_or_else(
                     || {
@@ -346,9 +493,9 @@ pub fn render(frame: &mut Frame<'_>, app: &App) {
                             "agents"
                         };
                         let cleanup_warning = if targets.marked {
-                            "Marked descendant subtrees will be terminated."
+                            "Marked descendant subtrees will be terminated unless you keep them."
                         } else {
-                            "All synthesized descendant subtrees will be terminated."
+                            "Synthesized descendant subtrees will be terminated unless you keep them."
                         };
                         vec![
                             Line::from(Span::styled(
@@ -365,7 +512,7 @@ pub fn render(frame: &mut Frame<'_>, app: &App) {
                                 Style::default().fg(colors::TEXT_DIM),
                             )),
                             Line::from(Span::styled(
-                                "You'll be prompted for optional extra instructions.",
+                                "You'll choose what to collect and add optional instructions.",
                                 Style::default().fg(colors::TEXT_DIM),
                             )),
                             Line::from(""),
@@ -373,13 +520,44 @@ pub fn render(frame: &mut Frame<'_>, app: &App) {
                                 cleanup_warning,
                                 Style::default().fg(colors::DIFF_REMOVE),
                             )),
+                            Line::from(""),
                         ]
+                        .into_iter()
+                        .chain(modals::synthesize_choice_lines())
+                        .collect()
                     },
                 ),
                 ConfirmAction::WorktreeConflict => {
                     // This case is handled by render_worktree_conflict_overlay
                     vec![]
                 }
+                ConfirmAction::ResumeOverBudget => {
+                    let paused = app
+                        .data
+                        .storage
+                        .iter()
+                        .filter(|agent| agent.budget.is_paused())
+                        .count();
+                    vec![
+                        Line::from(Span::styled(
+                            "Resume agents paused over budget?",
+                            Style::default().fg(colors::TEXT_PRIMARY),
+                        )),
+                        Line::from(""),
+                        Line::from(vec![
+                            Span::styled("  Paused: ", Style::default().fg(colors::TEXT_DIM)),
+                            Span::styled(
+                                format!("{paused} agent(s)"),
+                                Style::default().fg(colors::TEXT_PRIMARY),
+                            ),
+                        ]),
+                        Line::from(""),
+                        Line::from(Span::styled(
+                            "They keep running past the budget they reached.",
+                            Style::default().fg(colors::DIFF_REMOVE),
+                        )),
+                    ]
+                }
                 ConfirmAction::SwitchBranch => {
                     let from_branch = app.data.git_op.branch_name.clone();
                     let to_branch = app.data.git_op.target_branch.clone();
@@ -427,16 +605,22 @@ pub fn render(frame: &mut Frame<'_>, app: &App) {
             // Special handling for worktree conflict with different buttons
             if matches!(action, ConfirmAction::WorktreeConflict) {
                 modals::render_worktree_conflict_overlay(frame, app);
+            } else if matches!(action, ConfirmAction::Quit | ConfirmAction::Synthesize)
+                || (action == ConfirmAction::Kill && app.data.kill_offers_stop())
+            {
+                modals::render_choice_overlay(frame, lines);
             } else {
                 modals::render_confirm_overlay(frame, lines);
             }
         }
-        AppMode::ErrorModal(state) => modals::render_error_modal(frame, &state.message),
+        AppMode::ErrorModal(state) => modals::render_error_modal(frame, app, &state.error),
         AppMode::PreparingDocker(state) => {
             modals::render_preparing_docker_modal(frame, &state.message);
         }
         AppMode::ReviewInfo(_) => modals::render_review_info_overlay(frame),
         AppMode::ReviewChildCount(_) => modals::render_review_count_picker_overlay(frame, app),
+        AppMode::ReviewFocus(_) => modals::render_review_focus_overlay(frame, app),
+        AppMode::SynthesisStrategy(_) => modals::render_synthesis_strategy_overlay(frame, app),
         AppMode::BranchSelector(_)
         | AppMode::RebaseBranchSelector(_)
         | AppMode::MergeBranchSelector(_)
@@ -444,13 +628,42 @@ pub fn render(frame: &mut Frame<'_>, app: &App) {
             modals::render_branch_selector_overlay(frame, app);
         }
         AppMode::ModelSelector(_) => modals::render_model_selector_overlay(frame, app),
+        AppMode::SendTo(_) => modals::render_send_to_overlay(frame, app),
+        AppMode::DependencyPicker(_) => modals::render_dependency_picker_overlay(frame, app),
+        AppMode::Inbox(_) => modals::render_inbox_overlay(frame, app),
+        AppMode::CannedResponses(_) => modals::render_canned_responses_overlay(frame, app),
+        AppMode::RepoPicker(_) => modals::render_repo_picker_overlay(frame, app),
+        AppMode::ContextFilePicker(_) => modals::render_context_file_picker_overlay(frame, app),
+        AppMode::IssuePicker(_) => modals::render_issue_picker_overlay(frame, app),
+        AppMode::RecoverPicker(_) => modals::render_recover_picker_overlay(frame, app),
+        AppMode::AutoConnectPicker(_) => modals::render_auto_connect_picker_overlay(frame, app),
+        AppMode::TmuxImportPicker(_) => modals::render_tmux_import_picker_overlay(frame, app),
+        AppMode::SynthesisBrowser(_) => modals::render_synthesis_browser_overlay(frame, app),
         AppMode::SettingsMenu(_) => modals::render_settings_menu_overlay(frame, app),
         AppMode::ConfirmPush(_) => modals::render_confirm_push_overlay(frame, app),
         AppMode::RenameBranch(_) => modals::render_rename_overlay(frame, app),
         AppMode::ConfirmPushForPR(_) => modals::render_confirm_push_for_pr_overlay(frame, app),
+        AppMode::PrePushFailed(_) => {
+            modals::render_pre_push_failed_modal(frame, &app.data.git_op.pre_push_failure);
+        }
+        AppMode::GitAuthFailed(state) => {
+            modals::render_git_auth_failed_modal(frame, &state.message);
+        }
         AppMode::SuccessModal(state) => modals::render_success_modal(frame, &state.message),
         AppMode::KeyboardRemapPrompt(_) => modals::render_keyboard_remap_overlay(frame),
         AppMode::UpdatePrompt(state) => modals::render_update_prompt_overlay(frame, &state.info),
         _ => {}
     }
+
+    if app.data.settings.theme == Theme::HighContrast {
+        colors::apply_high_contrast(frame.buffer_mut());
+    }
+}
+
+/// Prompt label for the new-agent prompts, noting attached context files and how to attach.
+fn context_files_label(app: &App, label: &str) -> String {
+    match app.data.spawn.context_files.len() {
+        0 => format!("{label} (Ctrl+O to attach files):"),
+        count => format!("{label} ({count} file(s) attached, Ctrl+O to change):"),
+    }
 }
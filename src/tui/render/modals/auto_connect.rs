@@ -0,0 +1,86 @@
+//! Startup auto-connect picker modal rendering (`auto_connect: "ask"`)
+
+use crate::app::App;
+use ratatui::{
+    Frame,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use super::centered_rect_absolute;
+use crate::tui::render::colors;
+
+/// Render the auto-connect picker overlay
+pub fn render_auto_connect_picker_overlay(frame: &mut Frame<'_>, app: &App) {
+    // 4 lines of chrome + 10 candidates + 2 for borders = 16 lines
+    let max_visible_candidates: usize = 10;
+    let area = centered_rect_absolute(80, 16, frame.area());
+
+    let state = &app.data.auto_connect_picker;
+
+    let mut lines: Vec<Line<'_>> = Vec::new();
+
+    lines.push(Line::from(Span::styled(
+        format!(
+            "{} worktree(s) have no agent. Reconnect the checked ones:",
+            state.candidates.len()
+        ),
+        Style::default().fg(colors::TEXT_DIM),
+    )));
+    lines.push(Line::from(""));
+
+    let scroll_offset = state.selected.saturating_sub(max_visible_candidates - 1);
+    for (idx, candidate) in state
+        .candidates
+        .iter()
+        .enumerate()
+        .skip(scroll_offset)
+        .take(max_visible_candidates)
+    {
+        let is_cursor = idx == state.selected;
+        let row_style = if is_cursor {
+            Style::default()
+                .fg(colors::TEXT_PRIMARY)
+                .bg(colors::SURFACE_HIGHLIGHT)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(colors::TEXT_PRIMARY)
+        };
+        let cursor = if is_cursor { "▶ " } else { "  " };
+        let checkbox = if state.checked.get(idx).copied().unwrap_or(false) {
+            "[x] "
+        } else {
+            "[ ] "
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(cursor, row_style),
+            Span::styled(checkbox, row_style.fg(colors::ACCENT_POSITIVE)),
+            Span::styled(format!("{} ", candidate.title), row_style),
+            Span::styled(
+                candidate.worktree_path.display().to_string(),
+                row_style.fg(colors::TEXT_DIM),
+            ),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "↑/↓ select • Space toggle • a all • Enter connect checked • Esc skip",
+        Style::default().fg(colors::TEXT_MUTED),
+    )));
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Reconnect Worktrees ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(colors::SELECTED))
+                .border_type(colors::BORDER_TYPE),
+        )
+        .style(Style::default().bg(colors::MODAL_BG));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
@@ -1,6 +1,6 @@
 //! Branch selector modal rendering
 
-use crate::app::App;
+use crate::app::{Actions, App};
 use crate::state::AppMode;
 use ratatui::{
     Frame,
@@ -29,9 +29,18 @@ pub fn render_branch_selector_overlay(frame: &mut Frame<'_>, app: &App) {
         _ => " Select Base Branch ",
     };
 
+    // Files flagged as outside an agent's scope or protected, shown before merging
+    let flagged = match &app.mode {
+        AppMode::MergeBranchSelector(_) => app.data.git_op.agent_id.map_or_else(Vec::new, |id| {
+            Actions::worktree_change_alerts(&app.data, id)
+        }),
+        _ => Vec::new(),
+    };
+
     // Calculate how many branches we can display
     let max_visible_branches: usize = 10;
-    let header_lines: u16 = 7; // Title + instruction + search box + section headers
+    // Title + instruction + search box + section headers, and the flagged files warning
+    let header_lines: u16 = 7 + u16::from(!flagged.is_empty());
     let footer_lines: u16 = 3; // Instructions + border
     // Safe cast: max_visible_branches is a small constant (10)
     #[expect(
@@ -106,6 +115,23 @@ pub fn render_branch_selector_overlay(frame: &mut Frame<'_>, app: &App) {
             )));
         }
     }
+    if !flagged.is_empty() {
+        let names: Vec<String> = flagged
+            .iter()
+            .take(2)
+            .map(|path| path.display().to_string())
+            .collect();
+        let more = flagged.len().saturating_sub(names.len());
+        let more = if more > 0 {
+            format!(" (+{more} more)")
+        } else {
+            String::new()
+        };
+        lines.push(Line::from(Span::styled(
+            format!("⚠ Flagged changes: {}{more}", names.join(", ")),
+            Style::default().fg(colors::ACCENT_WARNING),
+        )));
+    }
     lines.push(Line::from(""));
 
     // Search box
@@ -0,0 +1,99 @@
+//! Canned responses modal rendering (`c`)
+
+use crate::app::App;
+use ratatui::{
+    Frame,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use super::centered_rect_absolute;
+use crate::tui::render::colors;
+
+/// Render the canned responses overlay
+pub fn render_canned_responses_overlay(frame: &mut Frame<'_>, app: &App) {
+    // 6 lines of chrome + 10 responses + 2 for borders = 18 lines
+    let max_visible_responses: usize = 10;
+    let area = centered_rect_absolute(80, 18, frame.area());
+
+    let state = &app.data.canned_responses;
+    let title = app
+        .data
+        .selected_agent()
+        .map_or_else(String::new, |agent| agent.title.clone());
+    let target = if state.broadcast {
+        format!("{title}'s leaf agents")
+    } else {
+        title
+    };
+
+    let mut lines: Vec<Line<'_>> = vec![
+        Line::from(vec![
+            Span::styled("Send to: ", Style::default().fg(colors::TEXT_DIM)),
+            Span::styled("◀ ", Style::default().fg(colors::TEXT_MUTED)),
+            Span::styled(
+                target,
+                Style::default()
+                    .fg(colors::ACCENT_WARNING)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" ▶", Style::default().fg(colors::TEXT_MUTED)),
+        ]),
+        Line::from(""),
+    ];
+
+    let scroll_offset = state.selected.saturating_sub(max_visible_responses - 1);
+    for (idx, response) in state
+        .responses
+        .iter()
+        .enumerate()
+        .skip(scroll_offset)
+        .take(max_visible_responses)
+    {
+        let is_cursor = idx == state.selected;
+        let row_style = if is_cursor {
+            Style::default()
+                .fg(colors::TEXT_PRIMARY)
+                .bg(colors::SURFACE_HIGHLIGHT)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(colors::TEXT_PRIMARY)
+        };
+        let cursor = if is_cursor { "▶ " } else { "  " };
+        let number = if idx < 9 {
+            format!("{}. ", idx + 1)
+        } else {
+            "   ".to_string()
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(cursor, row_style),
+            Span::styled(number, row_style.fg(colors::TEXT_DIM)),
+            Span::styled(
+                format!("{} ", response.name),
+                row_style.fg(colors::ACCENT_WARNING),
+            ),
+            Span::styled(response.text.clone(), row_style.fg(colors::TEXT_DIM)),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "↑/↓ select • ←/→ change target • Enter or 1-9 send • Esc cancel",
+        Style::default().fg(colors::TEXT_MUTED),
+    )));
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Canned Responses ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(colors::SELECTED))
+                .border_type(colors::BORDER_TYPE),
+        )
+        .style(Style::default().bg(colors::MODAL_BG));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
@@ -3,12 +3,14 @@
 use crate::app::App;
 use ratatui::{
     Frame,
+    layout::Margin,
     style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
 };
 
-use super::centered_rect_absolute;
+use super::{centered_rect_absolute, input};
+use crate::text_width;
 use crate::tui::render::colors;
 
 /// Render the command palette overlay
@@ -100,4 +102,13 @@ pub fn render_command_palette_overlay(frame: &mut Frame<'_>, app: &App) {
 
     frame.render_widget(Clear, area);
     frame.render_widget(paragraph, area);
+    input::place_terminal_cursor(
+        frame,
+        area.inner(Margin {
+            vertical: 1,
+            horizontal: 1,
+        }),
+        0,
+        text_width::width("Command: ") + text_width::width(&input[..cursor_pos.min(input.len())]),
+    );
 }
@@ -4,7 +4,7 @@ use crate::app::App;
 use crate::update::UpdateInfo;
 use ratatui::{
     Frame,
-    style::{Modifier, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
 };
@@ -12,6 +12,83 @@ use ratatui::{
 use super::centered_rect_absolute;
 use crate::tui::render::colors;
 
+/// A `[K]ey` choice line: the bracketed key in `color`, followed by `rest` of the label.
+fn choice_line(key: &'static str, rest: String, color: Color) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(key, Style::default().fg(color).add_modifier(Modifier::BOLD)),
+        Span::styled(rest, Style::default().fg(colors::TEXT_PRIMARY)),
+    ])
+}
+
+fn cancel_line() -> Line<'static> {
+    Line::from(vec![
+        Span::styled(
+            "[Esc]",
+            Style::default()
+                .fg(colors::TEXT_MUTED)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" Cancel", Style::default().fg(colors::TEXT_MUTED)),
+    ])
+}
+
+/// Choice lines for killing a root agent with a worktree: stop it and keep its workspace,
+/// delete everything (`removes` names what goes), or cancel.
+#[must_use]
+pub fn kill_choice_lines(removes: &str) -> Vec<Line<'static>> {
+    vec![
+        choice_line(
+            "[S]",
+            "top agent (keep branch & worktree)".to_string(),
+            colors::ACCENT_POSITIVE,
+        ),
+        choice_line(
+            "[D]",
+            format!("elete everything ({removes})"),
+            colors::ACCENT_NEGATIVE,
+        ),
+        cancel_line(),
+    ]
+}
+
+/// Choice lines for quitting with running agents: detach and leave them running, stop them
+/// all, or cancel.
+#[must_use]
+pub fn quit_choice_lines() -> Vec<Line<'static>> {
+    vec![
+        choice_line(
+            "[D]",
+            "etach (leave agents running)".to_string(),
+            colors::ACCENT_POSITIVE,
+        ),
+        choice_line(
+            "[S]",
+            "top all agents (keep worktrees)".to_string(),
+            colors::ACCENT_NEGATIVE,
+        ),
+        cancel_line(),
+    ]
+}
+
+/// Choice lines for synthesizing: terminate the synthesized agents, keep them running, or
+/// cancel.
+#[must_use]
+pub fn synthesize_choice_lines() -> Vec<Line<'static>> {
+    vec![
+        choice_line(
+            "[Y]",
+            "es (terminate the synthesized agents)".to_string(),
+            colors::ACCENT_NEGATIVE,
+        ),
+        choice_line(
+            "[K]",
+            "eep the agents running".to_string(),
+            colors::ACCENT_POSITIVE,
+        ),
+        cancel_line(),
+    ]
+}
+
 /// Render a confirmation overlay with yes/no buttons
 pub fn render_confirm_overlay(frame: &mut Frame<'_>, mut lines: Vec<Line<'_>>) {
     // Add the yes/no prompt at the end
@@ -33,13 +110,16 @@ pub fn render_confirm_overlay(frame: &mut Frame<'_>, mut lines: Vec<Line<'_>>) {
         Span::styled("o", Style::default().fg(colors::TEXT_PRIMARY)),
     ]));
 
+    render_choice_overlay(frame, lines);
+}
+
+/// Render a confirmation overlay whose `lines` already end with their own choices
+pub fn render_choice_overlay(frame: &mut Frame<'_>, lines: Vec<Line<'_>>) {
     // Height: content lines + 2 for borders
     let height = u16::try_from(lines.len() + 2).unwrap_or(u16::MAX);
     let area = centered_rect_absolute(50, height, frame.area());
 
-    let text = lines;
-
-    let paragraph = Paragraph::new(text)
+    let paragraph = Paragraph::new(lines)
         .block(
             Block::default()
                 .title(" Confirm ")
@@ -0,0 +1,97 @@
+//! Context file picker modal rendering (`Ctrl+O` in the new-agent prompts)
+
+use crate::app::App;
+use ratatui::{
+    Frame,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use super::centered_rect_absolute;
+use crate::tui::render::colors;
+
+/// Render the context file picker overlay
+pub fn render_context_file_picker_overlay(frame: &mut Frame<'_>, app: &App) {
+    // 6 lines of chrome + 12 files + 2 for borders = 20 lines
+    let max_visible_files: usize = 12;
+    let area = centered_rect_absolute(70, 20, frame.area());
+
+    let state = &app.data.context_picker;
+    let filtered = state.filtered_candidates();
+    let attached = &app.data.spawn.context_files;
+
+    let mut lines: Vec<Line<'_>> = Vec::new();
+
+    lines.push(Line::from(vec![
+        Span::styled("Attached: ", Style::default().fg(colors::TEXT_DIM)),
+        Span::styled(
+            format!("{} file(s)", attached.len()),
+            Style::default()
+                .fg(colors::ACCENT_POSITIVE)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ]));
+    lines.push(Line::from(""));
+
+    lines.push(Line::from(vec![
+        Span::styled("Filter: ", Style::default().fg(colors::TEXT_DIM)),
+        Span::styled(
+            format!("{}_", state.filter),
+            Style::default().fg(colors::TEXT_PRIMARY),
+        ),
+    ]));
+    lines.push(Line::from(""));
+
+    if filtered.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No matching files (type a path starting with / or ~/ for files elsewhere)",
+            Style::default().fg(colors::TEXT_MUTED),
+        )));
+    } else {
+        let scroll_offset = state.selected.saturating_sub(max_visible_files - 1);
+        for (idx, candidate) in filtered
+            .iter()
+            .enumerate()
+            .skip(scroll_offset)
+            .take(max_visible_files)
+        {
+            let is_cursor = idx == state.selected;
+            let is_attached = attached.contains(&state.resolve(candidate));
+            let row_style = if is_cursor {
+                Style::default()
+                    .fg(colors::TEXT_PRIMARY)
+                    .bg(colors::SURFACE_HIGHLIGHT)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(colors::TEXT_PRIMARY)
+            };
+            let cursor = if is_cursor { "▶ " } else { "  " };
+            let check = if is_attached { "✓ " } else { "  " };
+
+            lines.push(Line::from(Span::styled(
+                format!("{cursor}{check}{candidate}"),
+                row_style,
+            )));
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "↑/↓ select • Enter attach/detach • Esc back • Type to filter",
+        Style::default().fg(colors::TEXT_MUTED),
+    )));
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Attach Context File ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(colors::SELECTED))
+                .border_type(colors::BORDER_TYPE),
+        )
+        .style(Style::default().bg(colors::MODAL_BG));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
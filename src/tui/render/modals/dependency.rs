@@ -0,0 +1,105 @@
+//! Dependency picker modal rendering (`/depends_on`)
+
+use crate::app::App;
+use ratatui::{
+    Frame,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use super::centered_rect_absolute;
+use crate::tui::render::colors;
+
+/// Render the dependency picker overlay
+pub fn render_dependency_picker_overlay(frame: &mut Frame<'_>, app: &App) {
+    // 8 lines of chrome + 8 agents + 2 for borders = 18 lines
+    let max_visible_candidates: usize = 8;
+    let area = centered_rect_absolute(60, 18, frame.area());
+
+    let state = &app.data.dependency_picker;
+    let filtered = state.filtered_candidates();
+    let agent = state.agent_id.and_then(|id| app.data.storage.get(id));
+
+    let mut lines: Vec<Line<'_>> = Vec::new();
+
+    lines.push(Line::from(vec![
+        Span::styled(
+            agent.map_or_else(String::new, |agent| agent.title.clone()),
+            Style::default()
+                .fg(colors::ACCENT_POSITIVE)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" waits until:", Style::default().fg(colors::TEXT_DIM)),
+    ]));
+    lines.push(Line::from(""));
+
+    lines.push(Line::from(vec![
+        Span::styled("Filter: ", Style::default().fg(colors::TEXT_DIM)),
+        Span::styled(
+            format!("{}_", state.filter),
+            Style::default().fg(colors::TEXT_PRIMARY),
+        ),
+    ]));
+    lines.push(Line::from(""));
+
+    if filtered.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No matching agents",
+            Style::default().fg(colors::TEXT_MUTED),
+        )));
+    } else {
+        let scroll_offset = state.selected.saturating_sub(max_visible_candidates - 1);
+        for (idx, candidate) in filtered
+            .iter()
+            .enumerate()
+            .skip(scroll_offset)
+            .take(max_visible_candidates)
+        {
+            let is_cursor = idx == state.selected;
+            let row_style = if is_cursor {
+                Style::default()
+                    .fg(colors::TEXT_PRIMARY)
+                    .bg(colors::SURFACE_HIGHLIGHT)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(colors::TEXT_PRIMARY)
+            };
+            let cursor = if is_cursor { "▶ " } else { "  " };
+
+            lines.push(Line::from(Span::styled(
+                format!("{cursor}{}", candidate.label),
+                row_style,
+            )));
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("Trigger: ", Style::default().fg(colors::TEXT_DIM)),
+        Span::styled(
+            format!("◀ {} ▶", state.trigger.label()),
+            Style::default()
+                .fg(colors::ACCENT_WARNING)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ]));
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "↑/↓ select • ←/→ trigger • Enter next • Esc cancel",
+        Style::default().fg(colors::TEXT_MUTED),
+    )));
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Wait for Agent ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(colors::SELECTED))
+                .border_type(colors::BORDER_TYPE),
+        )
+        .style(Style::default().bg(colors::MODAL_BG));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
@@ -8,54 +8,98 @@ use ratatui::{
 };
 
 use super::centered_rect_absolute;
+use crate::app::App;
+use crate::error::TenexError;
+use crate::text_width;
 use crate::tui::render::colors;
 
-/// Render an error modal with word-wrapped message
-pub fn render_error_modal(frame: &mut Frame<'_>, message: &str) {
-    // Wrap the error message to fit within the modal width (44 chars after padding)
-    let max_line_width = 44;
-    let mut lines: Vec<Line<'_>> = Vec::new();
+/// Message lines shown before the rest of a long error is folded behind `[d]`.
+const SUMMARY_LINES: usize = 6;
 
-    // Add error icon and header
-    lines.push(Line::from(Span::styled(
-        "✖ Error",
-        Style::default()
-            .fg(colors::MODAL_BORDER_ERROR)
-            .add_modifier(Modifier::BOLD),
-    )));
-    lines.push(Line::from(""));
+/// Rows of the error modal besides the message: borders, header, and footer.
+const ERROR_MODAL_CHROME: usize = 10;
 
-    // Word-wrap the message
-    let words: Vec<&str> = message.split_whitespace().collect();
-    let mut current_line = String::new();
+/// Lines of the error modal for `error`: a summary, or the scrolled full message when the
+/// details are expanded, then the help for its category, the log location, and key hints.
+pub(super) fn error_modal_lines(
+    app: &App,
+    error: &TenexError,
+    frame_height: u16,
+) -> Vec<Line<'static>> {
+    let ui = &app.data.ui;
+    let wrapped = message_lines(&error.to_string(), 44);
+    let total = wrapped.len();
+    let muted = Style::default().fg(colors::TEXT_MUTED);
 
-    for word in words {
-        if current_line.is_empty() {
-            current_line = word.to_string();
-        } else if current_line.len() + 1 + word.len() <= max_line_width {
-            current_line.push(' ');
-            current_line.push_str(word);
-        } else {
+    let mut lines = vec![
+        Line::from(Span::styled(
+            "✖ Error",
+            Style::default()
+                .fg(colors::MODAL_BORDER_ERROR)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+
+    let foldable = total > SUMMARY_LINES;
+    if foldable && ui.error_details_expanded {
+        let visible = usize::from(frame_height)
+            .saturating_sub(ERROR_MODAL_CHROME)
+            .max(SUMMARY_LINES);
+        let scroll = ui.error_details_scroll.min(total.saturating_sub(visible));
+        let end = (scroll + visible).min(total);
+        lines.extend(wrapped.into_iter().skip(scroll).take(visible));
+        if total > visible {
             lines.push(Line::from(Span::styled(
-                current_line.clone(),
-                Style::default().fg(colors::TEXT_PRIMARY),
+                format!("Lines {}–{end} of {total} (↑/↓ to scroll)", scroll + 1),
+                muted,
             )));
-            current_line = word.to_string();
         }
-    }
-    if !current_line.is_empty() {
+    } else if foldable {
+        lines.extend(wrapped.into_iter().take(SUMMARY_LINES));
         lines.push(Line::from(Span::styled(
-            current_line,
-            Style::default().fg(colors::TEXT_PRIMARY),
+            format!("… {} more lines", total - SUMMARY_LINES),
+            muted,
         )));
+    } else {
+        lines.extend(wrapped);
+    }
+
+    if let Some(help) = error.help() {
+        lines.push(Line::from(""));
+        let help_style = Style::default().fg(colors::ACCENT_POSITIVE);
+        lines.extend(
+            message_lines(&format!("→ {help}"), 44)
+                .into_iter()
+                .map(|line| line.patch_style(help_style)),
+        );
     }
 
-    // Add dismiss hint
     lines.push(Line::from(""));
+    let log_path = crate::paths::log_path();
     lines.push(Line::from(Span::styled(
-        "Press any key to dismiss",
-        Style::default().fg(colors::TEXT_MUTED),
+        ui.error_log_line.map_or_else(
+            || format!("Log: set DEBUG=1 to write {}", log_path.display()),
+            |line| format!("Log: {}:{line}", log_path.display()),
+        ),
+        muted,
+    )));
+    lines.push(Line::from(Span::styled(
+        if !foldable {
+            "[c] Copy  any other key to dismiss"
+        } else if ui.error_details_expanded {
+            "[d] Summary  [c] Copy  any other key to dismiss"
+        } else {
+            "[d] Details  [c] Copy  any other key to dismiss"
+        },
+        muted,
     )));
+    lines
+}
+
+/// Render an error modal with word-wrapped message and help
+pub fn render_error_modal(frame: &mut Frame<'_>, app: &App, error: &TenexError) {
+    let lines = error_modal_lines(app, error, frame.area().height);
 
     // Height: content lines + 2 for borders, min 7 lines
     let height = u16::try_from(lines.len() + 2).unwrap_or(u16::MAX).max(7);
@@ -76,6 +120,121 @@ pub fn render_error_modal(frame: &mut Frame<'_>, message: &str) {
     frame.render_widget(paragraph, area);
 }
 
+/// Render the failure of a pre-push command, offering to push anyway
+pub fn render_pre_push_failed_modal(frame: &mut Frame<'_>, report: &str) {
+    render_failure_modal(
+        frame,
+        " Pre-push Check Failed ",
+        "✖ Pre-push check failed",
+        report,
+        ("[p]", " Push anyway  "),
+    );
+}
+
+/// Render guidance for a push or fetch that could not authenticate, offering to retry
+pub fn render_git_auth_failed_modal(frame: &mut Frame<'_>, message: &str) {
+    render_failure_modal(
+        frame,
+        " Authentication Failed ",
+        "✖ Authentication failed",
+        message,
+        ("[r]", " Retry  "),
+    );
+}
+
+/// Render a failure with a word-wrapped `message`, the `action` it offers, and `[Esc]` to
+/// cancel.
+fn render_failure_modal(
+    frame: &mut Frame<'_>,
+    title: &str,
+    heading: &str,
+    message: &str,
+    (key, action): (&str, &str),
+) {
+    let mut lines: Vec<Line<'_>> = vec![
+        Line::from(Span::styled(
+            heading.to_string(),
+            Style::default()
+                .fg(colors::MODAL_BORDER_ERROR)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+    lines.extend(message_lines(message, 44));
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled(
+            key.to_string(),
+            Style::default()
+                .fg(colors::ACCENT_WARNING)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            action.to_string(),
+            Style::default().fg(colors::TEXT_PRIMARY),
+        ),
+        Span::styled(
+            "[Esc]",
+            Style::default()
+                .fg(colors::TEXT_MUTED)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" Cancel", Style::default().fg(colors::TEXT_PRIMARY)),
+    ]));
+
+    // Height: content lines + 2 for borders, min 7 lines
+    let height = u16::try_from(lines.len() + 2).unwrap_or(u16::MAX).max(7);
+    let area = centered_rect_absolute(50, height, frame.area());
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(title.to_string())
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(colors::MODAL_BORDER_ERROR))
+                .border_type(colors::BORDER_TYPE),
+        )
+        .style(Style::default().bg(colors::MODAL_BG))
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+/// Word-wrap each line of `message` to `max_line_width`, keeping its line breaks
+fn message_lines(message: &str, max_line_width: usize) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    for paragraph in message.split('\n') {
+        if paragraph.trim().is_empty() {
+            lines.push(Line::from(""));
+            continue;
+        }
+        let mut current_line = String::new();
+        for word in paragraph.split_whitespace() {
+            if current_line.is_empty() {
+                current_line = word.to_string();
+            } else if text_width::width(&current_line) + 1 + text_width::width(word)
+                <= max_line_width
+            {
+                current_line.push(' ');
+                current_line.push_str(word);
+            } else {
+                lines.push(Line::from(Span::styled(
+                    current_line.clone(),
+                    Style::default().fg(colors::TEXT_PRIMARY),
+                )));
+                current_line = word.to_string();
+            }
+        }
+        lines.push(Line::from(Span::styled(
+            current_line,
+            Style::default().fg(colors::TEXT_PRIMARY),
+        )));
+    }
+
+    lines
+}
+
 /// Render a success modal with word-wrapped message
 pub fn render_success_modal(frame: &mut Frame<'_>, message: &str) {
     // Wrap the message to fit within the modal width (44 chars after padding)
@@ -98,7 +257,7 @@ pub fn render_success_modal(frame: &mut Frame<'_>, message: &str) {
     for word in words {
         if current_line.is_empty() {
             current_line = word.to_string();
-        } else if current_line.len() + 1 + word.len() <= max_line_width {
+        } else if text_width::width(&current_line) + 1 + text_width::width(word) <= max_line_width {
             current_line.push(' ');
             current_line.push_str(word);
         } else {
@@ -0,0 +1,90 @@
+//! Questions inbox modal rendering (`!`)
+
+use crate::app::App;
+use ratatui::{
+    Frame,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use super::centered_rect_absolute;
+use crate::tui::render::colors;
+
+/// Render the questions inbox overlay
+pub fn render_inbox_overlay(frame: &mut Frame<'_>, app: &App) {
+    // 4 lines of chrome + 12 questions + 2 for borders = 18 lines
+    let max_visible_entries: usize = 12;
+    let area = centered_rect_absolute(80, 18, frame.area());
+
+    let state = &app.data.inbox;
+
+    let mut lines: Vec<Line<'_>> = Vec::new();
+
+    lines.push(Line::from(Span::styled(
+        format!("{} agent(s) waiting on an answer:", state.entries.len()),
+        Style::default().fg(colors::TEXT_DIM),
+    )));
+    lines.push(Line::from(""));
+
+    if state.entries.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No questions waiting",
+            Style::default().fg(colors::TEXT_MUTED),
+        )));
+    } else {
+        let scroll_offset = state.selected.saturating_sub(max_visible_entries - 1);
+        for (idx, entry) in state
+            .entries
+            .iter()
+            .enumerate()
+            .skip(scroll_offset)
+            .take(max_visible_entries)
+        {
+            let is_cursor = idx == state.selected;
+            let row_style = if is_cursor {
+                Style::default()
+                    .fg(colors::TEXT_PRIMARY)
+                    .bg(colors::SURFACE_HIGHLIGHT)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(colors::TEXT_PRIMARY)
+            };
+            let cursor = if is_cursor { "▶ " } else { "  " };
+            let title = app
+                .data
+                .storage
+                .get(entry.agent_id)
+                .map_or_else(String::new, |agent| agent.title.clone());
+
+            lines.push(Line::from(vec![
+                Span::styled(cursor, row_style),
+                Span::styled(
+                    entry.asked_at.format("%H:%M ").to_string(),
+                    row_style.fg(colors::TEXT_DIM),
+                ),
+                Span::styled(format!("{title}: "), row_style.fg(colors::ACCENT_WARNING)),
+                Span::styled(entry.question.clone(), row_style),
+            ]));
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "↑/↓ select • Enter jump to agent • r reply • d dismiss • Esc close",
+        Style::default().fg(colors::TEXT_MUTED),
+    )));
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Questions Inbox ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(colors::SELECTED))
+                .border_type(colors::BORDER_TYPE),
+        )
+        .style(Style::default().bg(colors::MODAL_BG));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
@@ -1,66 +1,109 @@
 //! Input modal rendering
 
-use crate::app::App;
+use crate::app::{App, TokenEstimate};
 use ratatui::{
     Frame,
-    layout::Rect,
+    layout::{Margin, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
 };
 
 use super::centered_rect_absolute;
+use crate::text_width;
 use crate::tui::render::colors;
 
-/// Wrap input text at the given width, returning wrapped lines and cursor line index.
-pub fn wrap_input_with_cursor(text: &str, max_width: usize) -> (Vec<String>, usize) {
+/// Wrap input text at the given width in columns, returning wrapped lines, cursor line index,
+/// and cursor column. A wide character that would straddle the edge starts the next line.
+pub fn wrap_input_with_cursor(text: &str, max_width: usize) -> (Vec<String>, usize, usize) {
     let width = max_width.max(1);
     let mut lines = Vec::new();
     let mut current = String::new();
-    let mut current_len = 0_usize;
+    let mut current_width = 0_usize;
     let mut cursor_line = 0_usize;
+    let mut cursor_column = 0_usize;
     let mut line_index = 0_usize;
 
     for ch in text.chars() {
         if ch == '\n' {
             lines.push(current);
             current = String::new();
-            current_len = 0;
+            current_width = 0;
             line_index = line_index.saturating_add(1);
             continue;
         }
 
-        if current_len >= width {
+        let ch_width = text_width::char_width(ch);
+        if ch_width > 0 && current_width.saturating_add(ch_width) > width && !current.is_empty() {
             lines.push(current);
             current = String::new();
-            current_len = 0;
+            current_width = 0;
             line_index = line_index.saturating_add(1);
         }
 
-        current.push(ch);
-        current_len = current_len.saturating_add(1);
-
         if ch == '│' {
             cursor_line = line_index;
+            cursor_column = current_width;
         }
+
+        current.push(ch);
+        current_width = current_width.saturating_add(ch_width);
     }
 
     lines.push(current);
-    (lines, cursor_line)
+    (lines, cursor_line, cursor_column)
+}
+
+/// Put the terminal cursor on the input cursor marker at `column` of `row` in `area`.
+///
+/// The marker is drawn by Tenex, but input methods draw their composition text at the
+/// terminal cursor, so the terminal cursor has to follow the marker for them to work.
+pub fn place_terminal_cursor(frame: &mut Frame<'_>, area: Rect, row: usize, column: usize) {
+    let (Ok(row), Ok(column)) = (u16::try_from(row), u16::try_from(column)) else {
+        return;
+    };
+    if row >= area.height || column >= area.width {
+        return;
+    }
+    frame.set_cursor_position((area.x.saturating_add(column), area.y.saturating_add(row)));
 }
 
 /// Render a text input overlay
+pub fn render_input_overlay(
+    frame: &mut Frame<'_>,
+    title: &str,
+    prompt: &str,
+    input: &str,
+    cursor_pos: usize,
+) {
+    render_overlay(frame, title, prompt, input, cursor_pos, None);
+}
+
+/// Render a text input overlay for a prompt sent to an agent, with its estimated token count
+/// under the input.
+pub fn render_prompt_overlay(
+    frame: &mut Frame<'_>,
+    title: &str,
+    prompt: &str,
+    input: &str,
+    cursor_pos: usize,
+    tokens: TokenEstimate,
+) {
+    render_overlay(frame, title, prompt, input, cursor_pos, Some(tokens));
+}
+
 #[expect(
     clippy::too_many_lines,
     clippy::cast_possible_truncation,
     reason = "Complex but cohesive rendering; casts are bounded by max_input_height=20"
 )]
-pub fn render_input_overlay(
+fn render_overlay(
     frame: &mut Frame<'_>,
     title: &str,
     prompt: &str,
     input: &str,
     cursor_pos: usize,
+    tokens: Option<TokenEstimate>,
 ) {
     // Insert cursor marker at cursor position
     let text_with_cursor = if cursor_pos >= input.len() {
@@ -76,7 +119,7 @@ pub fn render_input_overlay(
     let max_input_height = 20_usize;
     let modal_width = centered_rect_absolute(60, 1, frame.area()).width;
     let mut inner_width = modal_width.saturating_sub(2).max(1);
-    let (mut input_lines, mut cursor_line) =
+    let (mut input_lines, mut cursor_line, mut cursor_column) =
         wrap_input_with_cursor(&text_with_cursor, usize::from(inner_width));
     let mut input_area_height = input_lines.len().clamp(min_input_height, max_input_height);
     let mut needs_scrollbar = input_lines.len() > input_area_height;
@@ -86,6 +129,7 @@ pub fn render_input_overlay(
         let wrapped = wrap_input_with_cursor(&text_with_cursor, usize::from(inner_width));
         input_lines = wrapped.0;
         cursor_line = wrapped.1;
+        cursor_column = wrapped.2;
         input_area_height = input_lines.len().clamp(min_input_height, max_input_height);
         needs_scrollbar = input_lines.len() > input_area_height;
     }
@@ -97,7 +141,8 @@ pub fn render_input_overlay(
         0
     };
 
-    // Total height: borders(2) + prompt(1) + empty(1) + input area + empty(1) + help(1)
+    // Total height: borders(2) + prompt(1) + empty(1) + input area + tokens or empty(1) +
+    // help(1)
     let total_height = (6 + input_area_height) as u16;
     let area = centered_rect_absolute(60, total_height, frame.area());
 
@@ -129,7 +174,7 @@ pub fn render_input_overlay(
         })
         .collect();
 
-    // Build content: prompt, empty, (input rendered separately), empty, help
+    // Build content: prompt, empty, (input rendered separately), tokens or empty, help
     let header = vec![
         Line::from(Span::styled(
             prompt,
@@ -143,7 +188,17 @@ pub fn render_input_overlay(
     for _ in 0..input_area_height {
         content_lines.push(Line::from(""));
     }
-    content_lines.push(Line::from(""));
+    content_lines.push(tokens.map_or_else(
+        || Line::from(""),
+        |tokens| {
+            let color = if tokens.near_limit() {
+                colors::ACCENT_WARNING
+            } else {
+                colors::TEXT_MUTED
+            };
+            Line::from(Span::styled(tokens.label(), Style::default().fg(color)))
+        },
+    ));
     content_lines.push(Line::from(Span::styled(
         "Enter: submit | Alt+Enter: newline | ←→↑↓: move | Esc: cancel",
         Style::default().fg(colors::TEXT_MUTED),
@@ -166,6 +221,12 @@ pub fn render_input_overlay(
     let input_paragraph =
         Paragraph::new(visible_lines).style(Style::default().bg(colors::INPUT_BG));
     frame.render_widget(input_paragraph, inner_area);
+    place_terminal_cursor(
+        frame,
+        inner_area,
+        cursor_line.saturating_sub(scroll_offset),
+        cursor_column,
+    );
 
     // Render scrollbar if needed
     if needs_scrollbar {
@@ -261,4 +322,13 @@ pub fn render_rename_overlay(frame: &mut Frame<'_>, app: &App) {
 
     frame.render_widget(Clear, area);
     frame.render_widget(paragraph, area);
+    place_terminal_cursor(
+        frame,
+        area.inner(Margin {
+            vertical: 1,
+            horizontal: 1,
+        }),
+        4,
+        text_width::width(&app.data.input.buffer),
+    );
 }
@@ -0,0 +1,93 @@
+//! Issue picker modal rendering (`#`)
+
+use crate::app::App;
+use ratatui::{
+    Frame,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use super::centered_rect_absolute;
+use crate::tui::render::colors;
+
+/// Render the GitHub issue picker overlay
+pub fn render_issue_picker_overlay(frame: &mut Frame<'_>, app: &App) {
+    // 6 lines of chrome + 10 issues + 2 for borders = 18 lines
+    let max_visible_issues: usize = 10;
+    let area = centered_rect_absolute(70, 18, frame.area());
+
+    let state = &app.data.issue_picker;
+    let filtered = state.filtered_issues();
+
+    let mut lines: Vec<Line<'_>> = Vec::new();
+
+    lines.push(Line::from(Span::styled(
+        format!("{} open issue(s). Create an agent for:", state.issues.len()),
+        Style::default().fg(colors::TEXT_DIM),
+    )));
+    lines.push(Line::from(""));
+
+    lines.push(Line::from(vec![
+        Span::styled("Filter: ", Style::default().fg(colors::TEXT_DIM)),
+        Span::styled(
+            format!("{}_", state.filter),
+            Style::default().fg(colors::TEXT_PRIMARY),
+        ),
+    ]));
+    lines.push(Line::from(""));
+
+    if filtered.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No matching issues",
+            Style::default().fg(colors::TEXT_MUTED),
+        )));
+    } else {
+        let scroll_offset = state.selected.saturating_sub(max_visible_issues - 1);
+        for (idx, issue) in filtered
+            .iter()
+            .enumerate()
+            .skip(scroll_offset)
+            .take(max_visible_issues)
+        {
+            let is_cursor = idx == state.selected;
+            let row_style = if is_cursor {
+                Style::default()
+                    .fg(colors::TEXT_PRIMARY)
+                    .bg(colors::SURFACE_HIGHLIGHT)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(colors::TEXT_PRIMARY)
+            };
+            let cursor = if is_cursor { "▶ " } else { "  " };
+
+            lines.push(Line::from(vec![
+                Span::styled(cursor, row_style),
+                Span::styled(
+                    format!("#{} ", issue.number),
+                    row_style.fg(colors::ACCENT_POSITIVE),
+                ),
+                Span::styled(issue.title.clone(), row_style),
+            ]));
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "↑/↓ select • Enter create agent • Esc cancel • Type to filter",
+        Style::default().fg(colors::TEXT_MUTED),
+    )));
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" GitHub Issues ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(colors::SELECTED))
+                .border_type(colors::BORDER_TYPE),
+        )
+        .style(Style::default().bg(colors::MODAL_BG));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
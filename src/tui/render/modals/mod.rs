@@ -3,34 +3,61 @@
 //! This module provides a unified way to render modal dialogs, reducing
 //! duplication across the various overlay rendering functions.
 
+mod auto_connect;
 mod branch;
+mod canned_responses;
 mod changelog;
 mod command_palette;
 mod confirm;
+mod context_files;
+mod dependency;
 mod error;
 mod help;
+mod inbox;
 mod input;
+mod issues;
 mod models;
 mod picker;
 mod progress;
+mod recover;
+mod repos;
+mod send_to;
 mod settings_menu;
+mod syntheses;
+mod tmux_import;
 
+pub use auto_connect::render_auto_connect_picker_overlay;
 pub use branch::render_branch_selector_overlay;
+pub use canned_responses::render_canned_responses_overlay;
 pub use changelog::render_changelog_overlay;
 pub use command_palette::render_command_palette_overlay;
 pub use confirm::{
-    render_confirm_overlay, render_confirm_push_for_pr_overlay, render_confirm_push_overlay,
-    render_keyboard_remap_overlay, render_update_prompt_overlay, render_worktree_conflict_overlay,
+    kill_choice_lines, quit_choice_lines, render_choice_overlay, render_confirm_overlay,
+    render_confirm_push_for_pr_overlay, render_confirm_push_overlay, render_keyboard_remap_overlay,
+    render_update_prompt_overlay, render_worktree_conflict_overlay, synthesize_choice_lines,
+};
+pub use context_files::render_context_file_picker_overlay;
+pub use dependency::render_dependency_picker_overlay;
+pub use error::{
+    render_error_modal, render_git_auth_failed_modal, render_pre_push_failed_modal,
+    render_success_modal,
 };
-pub use error::{render_error_modal, render_success_modal};
 pub use help::render_help_overlay;
-pub use input::{render_input_overlay, render_rename_overlay};
+pub use inbox::render_inbox_overlay;
+pub use input::{render_input_overlay, render_prompt_overlay, render_rename_overlay};
+pub use issues::render_issue_picker_overlay;
 pub use models::render_model_selector_overlay;
 pub use picker::{
-    render_count_picker_overlay, render_review_count_picker_overlay, render_review_info_overlay,
+    render_count_picker_overlay, render_review_count_picker_overlay, render_review_focus_overlay,
+    render_review_info_overlay, render_synthesis_strategy_overlay,
 };
 pub use progress::render_preparing_docker_modal;
+pub use recover::render_recover_picker_overlay;
+pub use repos::render_repo_picker_overlay;
+pub use send_to::render_send_to_overlay;
 pub use settings_menu::render_settings_menu_overlay;
+pub use syntheses::render_synthesis_browser_overlay;
+pub use tmux_import::render_tmux_import_picker_overlay;
 
 use crate::app::App;
 use crate::config::Action;
@@ -76,16 +103,38 @@ pub fn modal_rect_for_mode(app: &App, frame_area: Rect) -> Option<Rect> {
         | AppMode::ReconnectPrompt(_)
         | AppMode::TerminalPrompt(_)
         | AppMode::CustomAgentCommand(_)
-        | AppMode::SynthesisPrompt(_) => Some(text_input_rect(app, frame_area)),
+        | AppMode::SynthesisPrompt(_)
+        | AppMode::EditTask(_)
+        | AppMode::EditScratchpad(_)
+        | AppMode::DependencyMessage(_)
+        | AppMode::Reply(_)
+        | AppMode::AddRepo(_)
+        | AppMode::Scope(_)
+        | AppMode::SwitchModel(_)
+        | AppMode::AuthPrompt(_) => Some(text_input_rect(app, frame_area)),
         AppMode::ChildCount(_) | AppMode::ReviewChildCount(_) => {
             Some(centered_rect_absolute(40, 12, frame_area))
         }
         AppMode::ReviewInfo(_) => Some(centered_rect_absolute(50, 9, frame_area)),
+        AppMode::ReviewFocus(_) => Some(centered_rect_absolute(50, 15, frame_area)),
+        AppMode::SynthesisStrategy(_) => Some(centered_rect_absolute(60, 11, frame_area)),
         AppMode::BranchSelector(_)
         | AppMode::RebaseBranchSelector(_)
         | AppMode::MergeBranchSelector(_)
         | AppMode::SwitchBranchSelector(_) => Some(centered_rect_absolute(60, 20, frame_area)),
         AppMode::ModelSelector(_) => Some(centered_rect_absolute(55, 12, frame_area)),
+        AppMode::SendTo(_) | AppMode::DependencyPicker(_) | AppMode::RepoPicker(_) => {
+            Some(centered_rect_absolute(60, 18, frame_area))
+        }
+        AppMode::ContextFilePicker(_) => Some(centered_rect_absolute(70, 20, frame_area)),
+        AppMode::IssuePicker(_) => Some(centered_rect_absolute(70, 18, frame_area)),
+        AppMode::RecoverPicker(_) | AppMode::Inbox(_) | AppMode::CannedResponses(_) => {
+            Some(centered_rect_absolute(80, 18, frame_area))
+        }
+        AppMode::SynthesisBrowser(_) => Some(centered_rect_absolute(90, 27, frame_area)),
+        AppMode::AutoConnectPicker(_) | AppMode::TmuxImportPicker(_) => {
+            Some(centered_rect_absolute(80, 16, frame_area))
+        }
         AppMode::SettingsMenu(_) => Some(centered_rect_absolute(60, 9, frame_area)),
         AppMode::ConfirmPush(_) => Some(confirm_push_rect(app, frame_area)),
         AppMode::RenameBranch(_) => Some(centered_rect_absolute(55, 9, frame_area)),
@@ -94,8 +143,24 @@ pub fn modal_rect_for_mode(app: &App, frame_area: Rect) -> Option<Rect> {
         }
         AppMode::KeyboardRemapPrompt(_) => Some(centered_rect_absolute(55, 16, frame_area)),
         AppMode::PreparingDocker(state) => Some(success_modal_rect(&state.message, frame_area)),
-        AppMode::ErrorModal(state) => Some(error_modal_rect(&state.message, frame_area)),
+        AppMode::ErrorModal(state) => {
+            let lines = error::error_modal_lines(app, &state.error, frame_area.height);
+            let height = u16::try_from(lines.len() + 2).unwrap_or(u16::MAX).max(7);
+            Some(centered_rect_absolute(50, height, frame_area))
+        }
+        AppMode::GitAuthFailed(state) => Some(error_modal_rect(&state.message, frame_area)),
+        AppMode::PrePushFailed(_) => Some(error_modal_rect(
+            &app.data.git_op.pre_push_failure,
+            frame_area,
+        )),
         AppMode::SuccessModal(state) => Some(success_modal_rect(&state.message, frame_area)),
+        // The conflict overlay draws nothing once its conflict is gone.
+        AppMode::Confirming(state)
+            if state.action == ConfirmAction::WorktreeConflict
+                && app.data.spawn.worktree_conflict.is_none() =>
+        {
+            None
+        }
         AppMode::Confirming(state) => Some(confirming_rect(app, state.action, frame_area)),
         _ => None,
     }
@@ -219,7 +284,10 @@ fn confirm_push_rect(app: &App, frame_area: Rect) -> Rect {
 }
 
 fn error_modal_rect(message: &str, frame_area: Rect) -> Rect {
-    let wrapped = word_wrap_line_count(message, 44);
+    let wrapped: usize = message
+        .split('\n')
+        .map(|paragraph| word_wrap_line_count(paragraph, 44).max(1))
+        .sum();
     let lines = wrapped.saturating_add(4);
     let height = u16::try_from(lines + 2).unwrap_or(u16::MAX).max(7);
     centered_rect_absolute(50, height, frame_area)
@@ -237,7 +305,7 @@ fn word_wrap_line_count(message: &str, max_line_width: usize) -> usize {
     let mut current_len = 0usize;
 
     for word in message.split_whitespace() {
-        let word_len = word.len();
+        let word_len = crate::text_width::width(word);
         if current_len == 0 {
             current_len = word_len;
             line_count = line_count.saturating_add(1);
@@ -264,7 +332,7 @@ fn confirming_rect(app: &App, action: ConfirmAction, frame_area: Rect) -> Rect {
             let height = u16::try_from(base_lines + 2).unwrap_or(u16::MAX);
             centered_rect_absolute(60, height, frame_area)
         }
-        ConfirmAction::Kill | ConfirmAction::InterruptAgent => {
+        ConfirmAction::Kill | ConfirmAction::InterruptAgent | ConfirmAction::Compact => {
             let lines = if app.data.selected_agent().is_some() {
                 7
             } else {
@@ -272,7 +340,10 @@ fn confirming_rect(app: &App, action: ConfirmAction, frame_area: Rect) -> Rect {
             };
             confirm_overlay_rect(lines, frame_area)
         }
-        ConfirmAction::Reset | ConfirmAction::Quit => confirm_overlay_rect(1, frame_area),
+        ConfirmAction::Reset => confirm_overlay_rect(1, frame_area),
+        // Prompt, blank line and three choices; `confirm_overlay_rect` adds two for the
+        // yes/no prompt, which the choices replace.
+        ConfirmAction::Quit => confirm_overlay_rect(3, frame_area),
         ConfirmAction::RestartMuxDaemon => {
             let lines = app
                 .data
@@ -284,18 +355,32 @@ fn confirming_rect(app: &App, action: ConfirmAction, frame_area: Rect) -> Rect {
                 });
             confirm_overlay_rect(lines, frame_area)
         }
+        // Description, blank line and three choices; the choices replace the yes/no prompt
+        // that `confirm_overlay_rect` makes room for. Without descendants it is one line.
         ConfirmAction::Synthesize => {
-            let lines = if app.data.selected_agent().is_some() {
-                6
+            let has_targets = app.data.selected_agent().is_some_and(|agent| {
+                !app.data
+                    .synthesis_targets_for(agent.id)
+                    .capture_agent_ids
+                    .is_empty()
+            });
+            if has_targets {
+                confirm_overlay_rect(9, frame_area)
             } else {
-                1
-            };
-            confirm_overlay_rect(lines, frame_area)
+                choice_overlay_rect(1, frame_area)
+            }
         }
         ConfirmAction::SwitchBranch => confirm_overlay_rect(7, frame_area),
+        ConfirmAction::ResumeOverBudget => confirm_overlay_rect(5, frame_area),
     }
 }
 
+fn choice_overlay_rect(lines: usize, frame_area: Rect) -> Rect {
+    // `render_choice_overlay` adds only the 2 border lines.
+    let height = u16::try_from(lines + 2).unwrap_or(u16::MAX);
+    centered_rect_absolute(50, height, frame_area)
+}
+
 fn confirm_overlay_rect(base_lines: usize, frame_area: Rect) -> Rect {
     // `render_confirm_overlay` appends 2 lines for the prompt, then adds 2 border lines.
     let lines = base_lines.saturating_add(2);
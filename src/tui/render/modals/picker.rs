@@ -1,6 +1,6 @@
-//! Picker modal rendering (count pickers, review info)
+//! Picker modal rendering (count pickers, review info, review focuses, synthesis strategy)
 
-use crate::app::App;
+use crate::app::{App, SynthesisStrategy};
 use ratatui::{
     Frame,
     style::{Modifier, Style},
@@ -168,3 +168,123 @@ pub fn render_review_count_picker_overlay(frame: &mut Frame<'_>, app: &App) {
     frame.render_widget(Clear, area);
     frame.render_widget(paragraph, area);
 }
+
+/// Render the review focus picker overlay
+pub fn render_review_focus_overlay(frame: &mut Frame<'_>, app: &App) {
+    // Header + blank + 8 reviewers + blank + 2 help lines = 13, plus borders = 15.
+    let max_visible_reviewers: usize = 8;
+    let area = centered_rect_absolute(50, 15, frame.area());
+
+    let review = &app.data.review;
+    let mut lines: Vec<Line<'_>> = vec![
+        Line::from(Span::styled(
+            "What should each reviewer focus on?",
+            Style::default().fg(colors::TEXT_DIM),
+        )),
+        Line::from(""),
+    ];
+
+    let scroll_offset = review
+        .selected_reviewer
+        .saturating_sub(max_visible_reviewers - 1);
+    for reviewer in (0..review.assignments.len())
+        .skip(scroll_offset)
+        .take(max_visible_reviewers)
+    {
+        let is_selected = reviewer == review.selected_reviewer;
+        let style = if is_selected {
+            Style::default()
+                .fg(colors::TEXT_PRIMARY)
+                .bg(colors::SURFACE_HIGHLIGHT)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(colors::TEXT_PRIMARY)
+        };
+
+        let prefix = if is_selected { "▶ " } else { "  " };
+        let focus = review
+            .focus_for(reviewer)
+            .map_or("General", |focus| focus.name.as_str());
+        lines.push(Line::from(vec![
+            Span::styled(format!("{prefix}Reviewer {:<4}", reviewer + 1), style),
+            Span::styled(format!("◀ {focus} ▶"), style.fg(colors::ACCENT_POSITIVE)),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "↑/↓ select reviewer • ←/→ change focus",
+        Style::default().fg(colors::TEXT_MUTED),
+    )));
+    lines.push(Line::from(Span::styled(
+        "Enter to continue, Esc to cancel",
+        Style::default().fg(colors::TEXT_MUTED),
+    )));
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Review Focuses ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(colors::SELECTED))
+                .border_type(colors::BORDER_TYPE),
+        )
+        .style(Style::default().bg(colors::MODAL_BG));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
+
+/// Render the synthesis strategy picker overlay
+pub fn render_synthesis_strategy_overlay(frame: &mut Frame<'_>, app: &App) {
+    // Header + blank + 5 strategies + blank + help = 9, plus borders = 11.
+    let area = centered_rect_absolute(60, 11, frame.area());
+
+    let mut lines: Vec<Line<'_>> = vec![
+        Line::from(Span::styled(
+            "What should the parent get from each agent?",
+            Style::default().fg(colors::TEXT_DIM),
+        )),
+        Line::from(""),
+    ];
+
+    for strategy in SynthesisStrategy::ALL.iter().copied() {
+        let is_selected = strategy == app.data.synthesis_options.strategy;
+        let style = if is_selected {
+            Style::default()
+                .fg(colors::TEXT_PRIMARY)
+                .bg(colors::SURFACE_HIGHLIGHT)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(colors::TEXT_PRIMARY)
+        };
+
+        let prefix = if is_selected { "▶ " } else { "  " };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{prefix}{:<16}", strategy.label()), style),
+            Span::styled(
+                strategy.description(),
+                Style::default().fg(colors::TEXT_MUTED),
+            ),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "↑/↓ select • Enter continue • Esc cancel",
+        Style::default().fg(colors::TEXT_MUTED),
+    )));
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Synthesize ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(colors::SELECTED))
+                .border_type(colors::BORDER_TYPE),
+        )
+        .style(Style::default().bg(colors::MODAL_BG));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
@@ -0,0 +1,111 @@
+//! Branch recovery picker modal rendering (`D`)
+
+use crate::app::App;
+use chrono::{Local, TimeZone};
+use ratatui::{
+    Frame,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use super::centered_rect_absolute;
+use crate::tui::render::colors;
+
+/// Render the branch recovery picker overlay
+pub fn render_recover_picker_overlay(frame: &mut Frame<'_>, app: &App) {
+    // 6 lines of chrome + 10 candidates + 2 for borders = 18 lines
+    let max_visible_candidates: usize = 10;
+    let area = centered_rect_absolute(80, 18, frame.area());
+
+    let state = &app.data.recover_picker;
+    let filtered = state.filtered_candidates();
+
+    let mut lines: Vec<Line<'_>> = Vec::new();
+
+    lines.push(Line::from(Span::styled(
+        format!(
+            "{} lost commit(s) from killed agents. Recreate the branch at:",
+            state.candidates.len()
+        ),
+        Style::default().fg(colors::TEXT_DIM),
+    )));
+    lines.push(Line::from(""));
+
+    lines.push(Line::from(vec![
+        Span::styled("Filter: ", Style::default().fg(colors::TEXT_DIM)),
+        Span::styled(
+            format!("{}_", state.filter),
+            Style::default().fg(colors::TEXT_PRIMARY),
+        ),
+    ]));
+    lines.push(Line::from(""));
+
+    if filtered.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No matching commits",
+            Style::default().fg(colors::TEXT_MUTED),
+        )));
+    } else {
+        let scroll_offset = state.selected.saturating_sub(max_visible_candidates - 1);
+        for (idx, candidate) in filtered
+            .iter()
+            .enumerate()
+            .skip(scroll_offset)
+            .take(max_visible_candidates)
+        {
+            let is_cursor = idx == state.selected;
+            let row_style = if is_cursor {
+                Style::default()
+                    .fg(colors::TEXT_PRIMARY)
+                    .bg(colors::SURFACE_HIGHLIGHT)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(colors::TEXT_PRIMARY)
+            };
+            let cursor = if is_cursor { "▶ " } else { "  " };
+            let when = Local
+                .timestamp_opt(candidate.commit.time, 0)
+                .single()
+                .map(|time| time.format("%m-%d %H:%M ").to_string())
+                .unwrap_or_default();
+
+            lines.push(Line::from(vec![
+                Span::styled(cursor, row_style),
+                Span::styled(
+                    format!("{} ", candidate.branch),
+                    row_style.fg(colors::ACCENT_POSITIVE),
+                ),
+                Span::styled(
+                    format!("{} ", candidate.commit.short_commit()),
+                    row_style.fg(colors::TEXT_DIM),
+                ),
+                Span::styled(when, row_style.fg(colors::TEXT_DIM)),
+                Span::styled(
+                    format!("({}) ", candidate.commit.source.label()),
+                    row_style.fg(colors::TEXT_MUTED),
+                ),
+                Span::styled(candidate.commit.summary.clone(), row_style),
+            ]));
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "↑/↓ select • Enter recreate branch • Esc cancel • Type to filter",
+        Style::default().fg(colors::TEXT_MUTED),
+    )));
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Recover Killed Agent ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(colors::SELECTED))
+                .border_type(colors::BORDER_TYPE),
+        )
+        .style(Style::default().bg(colors::MODAL_BG));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
@@ -0,0 +1,79 @@
+//! Repository picker modal rendering (`/repos`)
+
+use crate::app::App;
+use ratatui::{
+    Frame,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use super::centered_rect_absolute;
+use crate::tui::render::colors;
+
+/// Render the repository picker overlay
+pub fn render_repo_picker_overlay(frame: &mut Frame<'_>, app: &App) {
+    // 4 lines of chrome + 12 repositories + 2 for borders = 18 lines
+    let max_visible_repos: usize = 12;
+    let area = centered_rect_absolute(60, 18, frame.area());
+
+    let state = &app.data.repo_picker;
+
+    let mut lines: Vec<Line<'_>> = vec![
+        Line::from(Span::styled(
+            "Switch to a repository; new agents start in it:",
+            Style::default().fg(colors::TEXT_DIM),
+        )),
+        Line::from(""),
+    ];
+
+    let scroll_offset = state.selected.saturating_sub(max_visible_repos - 1);
+    for (idx, repo) in state
+        .repos
+        .iter()
+        .enumerate()
+        .skip(scroll_offset)
+        .take(max_visible_repos)
+    {
+        let is_cursor = idx == state.selected;
+        let row_style = if is_cursor {
+            Style::default()
+                .fg(colors::TEXT_PRIMARY)
+                .bg(colors::SURFACE_HIGHLIGHT)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(colors::TEXT_PRIMARY)
+        };
+        let cursor = if is_cursor { "▶ " } else { "  " };
+        let marker = if repo.registered { "+ " } else { "  " };
+
+        lines.push(Line::from(vec![
+            Span::styled(cursor, row_style),
+            Span::styled(marker, row_style.fg(colors::ACCENT_WARNING)),
+            Span::styled(repo.label.clone(), row_style),
+            Span::styled(
+                format!(" ({} agent(s))", repo.agent_count),
+                row_style.fg(colors::TEXT_DIM),
+            ),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "↑/↓ select • Enter switch • a add • d remove (+) • Esc close",
+        Style::default().fg(colors::TEXT_MUTED),
+    )));
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Repositories ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(colors::SELECTED))
+                .border_type(colors::BORDER_TYPE),
+        )
+        .style(Style::default().bg(colors::MODAL_BG));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
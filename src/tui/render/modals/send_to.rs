@@ -0,0 +1,109 @@
+//! Send-to picker modal rendering (`>`)
+
+use crate::app::App;
+use ratatui::{
+    Frame,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use super::centered_rect_absolute;
+use crate::tui::render::colors;
+
+/// Render the send-to picker overlay
+pub fn render_send_to_overlay(frame: &mut Frame<'_>, app: &App) {
+    // 6 lines of chrome + 10 targets + 2 for borders = 18 lines
+    let max_visible_targets: usize = 10;
+    let area = centered_rect_absolute(60, 18, frame.area());
+
+    let state = &app.data.send_to;
+    let filtered = state.filtered_targets();
+    let source = state.source.and_then(|id| app.data.storage.get(id));
+    let sends_selection = app
+        .data
+        .ui
+        .last_preview_selection
+        .as_ref()
+        .is_some_and(|(agent_id, _)| Some(*agent_id) == state.source);
+
+    let mut lines: Vec<Line<'_>> = Vec::new();
+
+    lines.push(Line::from(vec![
+        Span::styled(
+            if sends_selection {
+                "Send selected output from "
+            } else {
+                "Send recent output from "
+            },
+            Style::default().fg(colors::TEXT_DIM),
+        ),
+        Span::styled(
+            source.map_or_else(String::new, |agent| agent.title.clone()),
+            Style::default()
+                .fg(colors::ACCENT_POSITIVE)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" to:", Style::default().fg(colors::TEXT_DIM)),
+    ]));
+    lines.push(Line::from(""));
+
+    lines.push(Line::from(vec![
+        Span::styled("Filter: ", Style::default().fg(colors::TEXT_DIM)),
+        Span::styled(
+            format!("{}_", state.filter),
+            Style::default().fg(colors::TEXT_PRIMARY),
+        ),
+    ]));
+    lines.push(Line::from(""));
+
+    if filtered.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No matching agents",
+            Style::default().fg(colors::TEXT_MUTED),
+        )));
+    } else {
+        let scroll_offset = state.selected.saturating_sub(max_visible_targets - 1);
+        for (idx, target) in filtered
+            .iter()
+            .enumerate()
+            .skip(scroll_offset)
+            .take(max_visible_targets)
+        {
+            let is_cursor = idx == state.selected;
+            let row_style = if is_cursor {
+                Style::default()
+                    .fg(colors::TEXT_PRIMARY)
+                    .bg(colors::SURFACE_HIGHLIGHT)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(colors::TEXT_PRIMARY)
+            };
+            let cursor = if is_cursor { "▶ " } else { "  " };
+
+            lines.push(Line::from(Span::styled(
+                format!("{cursor}{}", target.label),
+                row_style,
+            )));
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "↑/↓ select • Enter send • Esc cancel • Type to filter",
+        Style::default().fg(colors::TEXT_MUTED),
+    )));
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Send Output To ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(colors::SELECTED))
+                .border_type(colors::BORDER_TYPE),
+        )
+        .style(Style::default().bg(colors::MODAL_BG));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
@@ -0,0 +1,106 @@
+//! Synthesis browser modal rendering (`/syntheses`)
+
+use crate::app::App;
+use chrono::{DateTime, Local};
+use ratatui::{
+    Frame,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use super::centered_rect_absolute;
+use crate::tui::render::colors;
+
+/// Render the synthesis browser overlay
+pub fn render_synthesis_browser_overlay(frame: &mut Frame<'_>, app: &App) {
+    // 5 lines of chrome + 8 files + 12 preview lines + 2 for borders = 27 lines
+    let max_visible_files: usize = 8;
+    let preview_lines: usize = 12;
+    let area = centered_rect_absolute(90, 27, frame.area());
+
+    let state = &app.data.synthesis_browser;
+    let title = state
+        .agent_id
+        .and_then(|agent_id| app.data.storage.get(agent_id))
+        .map_or("this agent", |agent| agent.title.as_str());
+
+    let mut lines: Vec<Line<'_>> = Vec::new();
+
+    lines.push(Line::from(Span::styled(
+        format!("{} synthesis file(s) for {title}:", state.files.len()),
+        Style::default().fg(colors::TEXT_DIM),
+    )));
+    lines.push(Line::from(""));
+
+    let scroll_offset = state.selected.saturating_sub(max_visible_files - 1);
+    for (idx, file) in state
+        .files
+        .iter()
+        .enumerate()
+        .skip(scroll_offset)
+        .take(max_visible_files)
+    {
+        let is_cursor = idx == state.selected;
+        let row_style = if is_cursor {
+            Style::default()
+                .fg(colors::TEXT_PRIMARY)
+                .bg(colors::SURFACE_HIGHLIGHT)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(colors::TEXT_PRIMARY)
+        };
+        let cursor = if is_cursor { "▶ " } else { "  " };
+        let when = DateTime::<Local>::from(file.modified)
+            .format("%m-%d %H:%M ")
+            .to_string();
+
+        lines.push(Line::from(vec![
+            Span::styled(cursor, row_style),
+            Span::styled(
+                format!("{} ", file.short_id()),
+                row_style.fg(colors::ACCENT_POSITIVE),
+            ),
+            Span::styled(when, row_style.fg(colors::TEXT_DIM)),
+            Span::styled(
+                format!("{} KB", file.size.div_ceil(1024)),
+                row_style.fg(colors::TEXT_MUTED),
+            ),
+        ]));
+    }
+    for _ in state.files.len().min(max_visible_files)..max_visible_files {
+        lines.push(Line::from(""));
+    }
+
+    lines.push(Line::from(""));
+    let preview = state
+        .selected_file()
+        .map(|file| file.preview.as_slice())
+        .unwrap_or_default();
+    for idx in 0..preview_lines {
+        let text = preview.get(idx).map_or("", String::as_str);
+        lines.push(Line::from(Span::styled(
+            text,
+            Style::default().fg(colors::TEXT_DIM),
+        )));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "↑/↓ select • Enter send to agent again • e export • d delete • Esc close",
+        Style::default().fg(colors::TEXT_MUTED),
+    )));
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Synthesis Files ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(colors::SELECTED))
+                .border_type(colors::BORDER_TYPE),
+        )
+        .style(Style::default().bg(colors::MODAL_BG));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
@@ -0,0 +1,90 @@
+//! tmux import picker modal rendering (`/import_tmux`)
+
+use crate::app::App;
+use ratatui::{
+    Frame,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use super::centered_rect_absolute;
+use crate::tui::render::colors;
+
+/// Render the tmux import picker overlay
+pub fn render_tmux_import_picker_overlay(frame: &mut Frame<'_>, app: &App) {
+    // 4 lines of chrome + 10 sessions + 2 for borders = 16 lines
+    let max_visible_sessions: usize = 10;
+    let area = centered_rect_absolute(80, 16, frame.area());
+
+    let state = &app.data.tmux_import_picker;
+
+    let mut lines: Vec<Line<'_>> = Vec::new();
+
+    lines.push(Line::from(Span::styled(
+        format!(
+            "{} tmux session(s) found. Import the checked ones as agents:",
+            state.sessions.len()
+        ),
+        Style::default().fg(colors::TEXT_DIM),
+    )));
+    lines.push(Line::from(""));
+
+    let scroll_offset = state.selected.saturating_sub(max_visible_sessions - 1);
+    for (idx, session) in state
+        .sessions
+        .iter()
+        .enumerate()
+        .skip(scroll_offset)
+        .take(max_visible_sessions)
+    {
+        let is_cursor = idx == state.selected;
+        let row_style = if is_cursor {
+            Style::default()
+                .fg(colors::TEXT_PRIMARY)
+                .bg(colors::SURFACE_HIGHLIGHT)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(colors::TEXT_PRIMARY)
+        };
+        let cursor = if is_cursor { "▶ " } else { "  " };
+        let checkbox = if state.checked.get(idx).copied().unwrap_or(false) {
+            "[x] "
+        } else {
+            "[ ] "
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(cursor, row_style),
+            Span::styled(checkbox, row_style.fg(colors::ACCENT_POSITIVE)),
+            Span::styled(format!("{} ", session.name), row_style),
+            Span::styled(
+                format!("{} ", session.command),
+                row_style.fg(colors::TEXT_MUTED),
+            ),
+            Span::styled(
+                session.path.display().to_string(),
+                row_style.fg(colors::TEXT_DIM),
+            ),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "↑/↓ select • Space toggle • a all • Enter import checked • Esc cancel",
+        Style::default().fg(colors::TEXT_MUTED),
+    )));
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(" Import tmux Sessions ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(colors::SELECTED))
+                .border_type(colors::BORDER_TYPE),
+        )
+        .style(Style::default().bg(colors::MODAL_BG));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(paragraph, area);
+}
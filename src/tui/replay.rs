@@ -0,0 +1,116 @@
+//! Playing a recording back through the app without a terminal.
+//!
+//! [`replay`] feeds each recorded event to the same input handlers the TUI uses, draws a frame
+//! after each one, and compares the modes the app passes through with the recorded ones. Input
+//! meant for agents is dropped, and background operations such as a push are not waited for,
+//! so recordings that depend on them can diverge.
+
+use anyhow::{Context, Result, bail};
+use ratatui::crossterm::event::MouseEvent;
+use ratatui::layout::Rect;
+
+use super::recording::{RecordedEvent, RecordingStart, parse_key, parse_mouse};
+use super::snapshot::{self, Snapshot};
+use crate::app::App;
+use crate::config::Config;
+
+/// What a replay did.
+#[derive(Debug, Clone)]
+pub struct Replay {
+    /// Events played back.
+    pub events: usize,
+    /// Where the app's mode differed from the recorded one, one message per event.
+    pub divergences: Vec<String>,
+    /// The last frame drawn.
+    pub frame: Snapshot,
+}
+
+/// An app in the state `start` describes, with its state saved to the current instance.
+#[must_use]
+pub fn app(start: &RecordingStart) -> App {
+    let mut config = Config::default();
+    config.apply_settings(&start.settings);
+    let mut storage = start.state.clone();
+    storage.state_path = Some(Config::state_path());
+    let mut app = App::new(
+        config,
+        storage,
+        start.settings.clone(),
+        start.keyboard_enhancement,
+    );
+    let cwd_project_root = std::env::current_dir()
+        .ok()
+        .map(|cwd| crate::git::repository_workspace_root(&cwd).unwrap_or(cwd));
+    app.set_cwd_project_root(cwd_project_root);
+    app.set_terminal_dimensions(start.width, start.height);
+    app
+}
+
+/// Play `events` back through `app`, stopping early if the app quits.
+///
+/// # Errors
+///
+/// Returns an error if an event cannot be parsed, a handler fails, or a frame cannot be drawn.
+pub fn replay(app: &mut App, events: &[RecordedEvent]) -> Result<Replay> {
+    let (width, height) = app.data.ui.terminal_dimensions.unwrap_or((80, 24));
+    let mut area = Rect::new(0, 0, width, height);
+    let mut divergences = Vec::new();
+    let mut played = 0;
+
+    for (index, event) in events.iter().enumerate() {
+        let number = index + 1;
+        played = number;
+        match event {
+            RecordedEvent::Start(start) => {
+                area = Rect::new(0, 0, start.width, start.height);
+                app.set_terminal_dimensions(start.width, start.height);
+            }
+            RecordedEvent::Key { key } => {
+                let (code, modifiers) =
+                    parse_key(key).with_context(|| format!("Event {number}: unknown key {key}"))?;
+                super::input::handle_key_event(app, code, modifiers, &mut Vec::new())?;
+            }
+            RecordedEvent::Mouse {
+                kind,
+                column,
+                row,
+                modifiers,
+            } => {
+                let Some((kind, modifiers)) = parse_mouse(kind, modifiers) else {
+                    bail!("Event {number}: unknown mouse event {kind}");
+                };
+                let mouse = MouseEvent {
+                    kind,
+                    column: *column,
+                    row: *row,
+                    modifiers,
+                };
+                super::input::handle_mouse_event(app, mouse, area, &mut Vec::new());
+            }
+            RecordedEvent::Resize { width, height } => {
+                area = Rect::new(0, 0, *width, *height);
+                app.set_terminal_dimensions(*width, *height);
+            }
+            RecordedEvent::Mode { mode } => {
+                let actual = super::mode_name(&app.mode);
+                if actual != *mode {
+                    divergences.push(format!(
+                        "Event {number}: recorded mode {mode}, replay is in {actual}"
+                    ));
+                }
+                continue;
+            }
+        }
+        // Draw like the TUI does after input, so rendering panics reproduce too.
+        snapshot::render(app, area.width, area.height)?;
+        if app.data.should_quit {
+            break;
+        }
+    }
+
+    Ok(Replay {
+        events: played,
+        divergences,
+        frame: snapshot::render(app, area.width, area.height)?,
+    })
+}
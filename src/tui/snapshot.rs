@@ -0,0 +1,266 @@
+//! Plain-text snapshots of rendered frames, for tests that catch rendering regressions.
+//!
+//! [`render`] draws an [`App`] off-screen exactly as the TUI would and returns the text of the
+//! frame. [`every_mode`] lists one state of each mode, so a test can snapshot every overlay
+//! and check each one with [`Snapshot::problems`].
+
+use anyhow::Result;
+use ratatui::{Terminal, backend::TestBackend, buffer::Buffer, layout::Rect, text::Span};
+
+use crate::app::App;
+use crate::error::TenexError;
+use crate::state::{
+    AddRepoMode, AppMode, AuthPromptMode, AuthRetry, AutoConnectPickerMode, BranchSelectorMode,
+    BroadcastingMode, CannedResponsesMode, ChangelogMode, ChildCountMode, ChildPromptMode,
+    CommandPaletteMode, ConfirmAction, ConfirmPushForPRMode, ConfirmPushMode, ConfirmingMode,
+    ContextFilePickerMode, CreatingMode, CustomAgentCommandMode, DependencyMessageMode,
+    DependencyPickerMode, DiffFocusedMode, EditScratchpadMode, EditTaskMode, ErrorModalMode,
+    GitAuthFailedMode, HelpMode, InboxMode, IssuePickerMode, KeyboardRemapPromptMode,
+    MergeBranchSelectorMode, ModelSelectorMode, NormalMode, PrePushFailedMode, PreparingDockerMode,
+    PreviewFocusedMode, PromptingMode, RebaseBranchSelectorMode, ReconnectPromptMode,
+    RecoverPickerMode, RenameBranchMode, ReplyMode, RepoPickerMode, ReviewChildCountMode,
+    ReviewFocusMode, ReviewInfoMode, ScopeMode, ScrollingMode, SendToMode, SettingsMenuMode,
+    SuccessModalMode, SwitchBranchSelectorMode, SwitchModelMode, SynthesisBrowserMode,
+    SynthesisPromptMode, SynthesisStrategyMode, TerminalPromptMode, TmuxImportPickerMode,
+    UpdatePromptMode, UpdateRequestedMode,
+};
+use crate::update::UpdateInfo;
+
+/// Corner glyphs a modal's border can start with.
+const BORDER_CORNERS: [&str; 4] = ["┌", "╭", "╔", "┏"];
+
+/// One rendered frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    /// The frame, one line per row, without trailing spaces.
+    pub text: String,
+    /// Where the open modal is, as used for clicks, if one is open.
+    pub modal: Option<Rect>,
+    /// Size of the frame.
+    pub area: Rect,
+    /// Top-left symbol of `modal` as drawn.
+    modal_corner: Option<String>,
+}
+
+impl Snapshot {
+    /// Layout problems in the frame: a modal that does not fit on screen, or one drawn
+    /// somewhere other than where clicks expect it.
+    #[must_use]
+    pub fn problems(&self) -> Vec<String> {
+        let Some(modal) = self.modal else {
+            return Vec::new();
+        };
+        let mut problems = Vec::new();
+        if modal.right() > self.area.right() || modal.bottom() > self.area.bottom() {
+            problems.push(format!(
+                "modal {modal:?} does not fit in the {}x{} frame",
+                self.area.width, self.area.height
+            ));
+        }
+        let corner = self.modal_corner.as_deref().unwrap_or_default();
+        if !BORDER_CORNERS.contains(&corner) {
+            problems.push(format!(
+                "modal {modal:?} has no border at its top-left corner (found {corner:?}); its \
+                 click area and its drawing disagree"
+            ));
+        }
+        problems
+    }
+}
+
+/// Render `app` into a `width` by `height` frame.
+///
+/// # Errors
+///
+/// Returns an error if the frame cannot be drawn.
+pub fn render(app: &App, width: u16, height: u16) -> Result<Snapshot> {
+    let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+    terminal.draw(|frame| super::render::render(frame, app))?;
+    let buffer = terminal.backend().buffer();
+    let area = buffer.area;
+    let modal = super::render::modals::modal_rect_for_mode(app, area);
+    Ok(Snapshot {
+        text: buffer_text(buffer),
+        modal,
+        area,
+        modal_corner: modal
+            .and_then(|modal| buffer.cell((modal.x, modal.y)))
+            .map(|cell| cell.symbol().to_string()),
+    })
+}
+
+/// Render `app` in each of `modes`, named by their labels. The app is left in normal mode.
+///
+/// # Errors
+///
+/// Returns an error if a frame cannot be drawn.
+pub fn render_modes(
+    app: &mut App,
+    modes: Vec<(String, AppMode)>,
+    width: u16,
+    height: u16,
+) -> Result<Vec<(String, Snapshot)>> {
+    let mut snapshots = Vec::with_capacity(modes.len());
+    for (label, mode) in modes {
+        app.mode = mode;
+        snapshots.push((label, render(app, width, height)?));
+    }
+    app.mode = AppMode::normal();
+    Ok(snapshots)
+}
+
+/// One labeled state of every mode, for [`render_modes`]. Modes that list agents, branches,
+/// or files show whatever the app holds.
+#[must_use]
+pub fn every_mode() -> Vec<(String, AppMode)> {
+    let mut modes: Vec<AppMode> = vec![
+        NormalMode.into(),
+        ScrollingMode.into(),
+        PreviewFocusedMode.into(),
+        DiffFocusedMode.into(),
+        HelpMode.into(),
+        CommandPaletteMode.into(),
+        SettingsMenuMode.into(),
+    ];
+    modes.extend(text_input_modes());
+    modes.extend(picker_modes());
+    modes.extend(message_modes());
+
+    let mut labeled: Vec<(String, AppMode)> = modes
+        .into_iter()
+        .map(|mode| (super::mode_name(&mode), mode))
+        .collect();
+    labeled.extend(
+        [
+            ConfirmAction::Kill,
+            ConfirmAction::InterruptAgent,
+            ConfirmAction::Compact,
+            ConfirmAction::Reset,
+            ConfirmAction::RestartMuxDaemon,
+            ConfirmAction::Quit,
+            ConfirmAction::Synthesize,
+            ConfirmAction::WorktreeConflict,
+            ConfirmAction::SwitchBranch,
+            ConfirmAction::ResumeOverBudget,
+        ]
+        .into_iter()
+        .map(|action| {
+            (
+                format!("Confirming{action:?}"),
+                ConfirmingMode { action }.into(),
+            )
+        }),
+    );
+    labeled
+}
+
+fn text_input_modes() -> Vec<AppMode> {
+    vec![
+        CreatingMode.into(),
+        PromptingMode.into(),
+        ChildPromptMode.into(),
+        BroadcastingMode.into(),
+        ReconnectPromptMode.into(),
+        TerminalPromptMode.into(),
+        CustomAgentCommandMode.into(),
+        SynthesisPromptMode.into(),
+        EditTaskMode.into(),
+        EditScratchpadMode.into(),
+        DependencyMessageMode.into(),
+        ReplyMode.into(),
+        AddRepoMode.into(),
+        ScopeMode.into(),
+        SwitchModelMode.into(),
+        RenameBranchMode.into(),
+        AuthPromptMode {
+            prompt: "Password for 'https://github.com':".to_string(),
+        }
+        .into(),
+    ]
+}
+
+fn picker_modes() -> Vec<AppMode> {
+    vec![
+        ChildCountMode.into(),
+        ReviewChildCountMode.into(),
+        ReviewFocusMode.into(),
+        ReviewInfoMode.into(),
+        SynthesisStrategyMode.into(),
+        BranchSelectorMode.into(),
+        RebaseBranchSelectorMode.into(),
+        MergeBranchSelectorMode.into(),
+        SwitchBranchSelectorMode.into(),
+        ModelSelectorMode.into(),
+        SendToMode.into(),
+        DependencyPickerMode.into(),
+        InboxMode.into(),
+        CannedResponsesMode.into(),
+        RepoPickerMode.into(),
+        ContextFilePickerMode.into(),
+        IssuePickerMode.into(),
+        RecoverPickerMode.into(),
+        AutoConnectPickerMode.into(),
+        TmuxImportPickerMode.into(),
+        SynthesisBrowserMode.into(),
+    ]
+}
+
+fn message_modes() -> Vec<AppMode> {
+    let update = UpdateInfo {
+        current_version: semver::Version::new(1, 0, 0),
+        latest_version: semver::Version::new(1, 1, 0),
+    };
+    vec![
+        ConfirmPushMode.into(),
+        ConfirmPushForPRMode.into(),
+        PrePushFailedMode { for_pr: false }.into(),
+        GitAuthFailedMode {
+            message: "Push failed: the SSH server rejected your key.".to_string(),
+            retry: AuthRetry::Push,
+        }
+        .into(),
+        KeyboardRemapPromptMode.into(),
+        UpdatePromptMode {
+            info: update.clone(),
+        }
+        .into(),
+        UpdateRequestedMode { info: update }.into(),
+        PreparingDockerMode {
+            message: "Preparing Docker image".to_string(),
+        }
+        .into(),
+        ChangelogMode {
+            title: "What's New".to_string(),
+            lines: vec!["- Something changed".to_string()],
+            mark_seen_version: None,
+            agent_id: None,
+        }
+        .into(),
+        ErrorModalMode::new(TenexError::NoAgentSelected { action: "push" }).into(),
+        SuccessModalMode {
+            message: "Pushed".to_string(),
+        }
+        .into(),
+    ]
+}
+
+/// The text of `buffer`, skipping the cells covered by wide characters.
+fn buffer_text(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut lines = Vec::with_capacity(usize::from(area.height));
+    for y in area.top()..area.bottom() {
+        let mut line = String::new();
+        let mut skip = 0;
+        for x in area.left()..area.right() {
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            let symbol = buffer.cell((x, y)).map_or(" ", |cell| cell.symbol());
+            skip = Span::raw(symbol).width().saturating_sub(1);
+            line.push_str(symbol);
+        }
+        lines.push(line.trim_end().to_string());
+    }
+    lines.join("\n")
+}
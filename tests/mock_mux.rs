@@ -0,0 +1,106 @@
+//! A swarm driven through [`tenex::api::Tenex`] against the in-memory mux, so no daemon runs
+//! and no agent program starts.
+//!
+//! Run with `cargo test --features mock-mux`.
+
+#![cfg(feature = "mock-mux")]
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use tenex::Config;
+use tenex::agent::Storage;
+use tenex::api::Tenex;
+use tenex::app::Settings;
+use tenex::git::Repository;
+use tenex::mux::MockBackend;
+
+/// A scratch directory removed when dropped.
+struct ScratchDir(PathBuf);
+
+impl ScratchDir {
+    fn new(name: &str) -> Result<Self> {
+        let path = std::env::temp_dir().join(format!("tenex-test-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path)?;
+        Ok(Self(path))
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// A repository with one commit.
+fn init_repository(dir: &Path) -> Result<()> {
+    let repo = Repository::init(dir)?;
+    std::fs::write(dir.join("README.md"), "# Scratch\n")?;
+    let mut index = repo.index()?;
+    index.add_path(Path::new("README.md"))?;
+    index.write()?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let signature = git2::Signature::now("Test", "test@example.com")?;
+    repo.commit(Some("HEAD"), &signature, &signature, "Initial", &tree, &[])?;
+    Ok(())
+}
+
+/// A Tenex whose state and worktrees live under `scratch`.
+fn scratch_tenex(scratch: &Path) -> Tenex {
+    let mut config = Config::default();
+    config.worktree_dir = scratch.join("worktrees");
+    config.clone_dir = scratch.join("clones");
+    let mut storage = Storage::new();
+    storage.state_path = Some(scratch.join("state.json"));
+    Tenex::with_state(config, storage, Settings::default())
+}
+
+#[test]
+fn swarm_runs_on_the_mock_mux() -> Result<()> {
+    let scratch = ScratchDir::new("mock-mux-swarm")?;
+    let repo = scratch.0.join("repo");
+    std::fs::create_dir_all(&repo)?;
+    init_repository(&repo)?;
+    let mut tenex = scratch_tenex(&scratch.0);
+
+    let mux = MockBackend::install();
+    mux.script("claude", "Done: fixed the redirect\n");
+    let root = tenex.spawn(&repo, "Fix login", Some("Fix the login redirect"))?;
+    assert_eq!(tenex.output(root, 1)?, ["Done: fixed the redirect"]);
+
+    let children = tenex.spawn_children(root, 2, "Write the tests")?;
+    assert_eq!(children.len(), 2);
+    let root_session = &tenex
+        .agent(root)
+        .context("Root agent is missing")?
+        .mux_session;
+    for child in &children {
+        let child = tenex.agent(*child).context("Child agent is missing")?;
+        assert_eq!(child.parent_id, Some(root));
+        assert_eq!(&child.mux_session, root_session);
+    }
+    let sessions = mux.sessions();
+    assert!(sessions.contains(root_session), "{sessions:?}");
+
+    tenex.broadcast(root, "Report your progress")?;
+    for child in &children {
+        let child = tenex.agent(*child).context("Child agent is missing")?;
+        let target = format!(
+            "{}:{}",
+            child.mux_session,
+            child.window_index.unwrap_or_default()
+        );
+        let input = mux.input(&target).unwrap_or_default();
+        assert!(
+            input.contains("Report your progress"),
+            "{target}: {input:?}"
+        );
+    }
+
+    tenex.kill(root, false)?;
+    assert_eq!(tenex.agents().count(), 0);
+    assert!(mux.sessions().is_empty(), "{:?}", mux.sessions());
+    drop(mux);
+    Ok(())
+}
@@ -0,0 +1,28 @@
+//! File mentions found in agent output.
+
+use std::fs;
+
+use anyhow::Result;
+use tenex::rpc::file_mentions;
+
+#[test]
+fn mentions_outside_the_workdir_are_ignored() -> Result<()> {
+    let root = std::env::temp_dir().join(format!("tenex-test-mentions-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    let workdir = root.join("worktree");
+    fs::create_dir_all(workdir.join("src"))?;
+    fs::write(workdir.join("src/main.rs"), "fn main() {}\n")?;
+    fs::write(root.join("secret.txt"), "secret\n")?;
+
+    let output = vec![
+        "see src/main.rs:3 and ../secret.txt:1".to_string(),
+        "also src/../../secret.txt".to_string(),
+    ];
+    let mentions = file_mentions(&output, &workdir);
+    let _ = fs::remove_dir_all(&root);
+
+    let paths: Vec<_> = mentions.iter().map(|mention| &mention.path).collect();
+    assert_eq!(paths, vec![&workdir.join("src/main.rs")]);
+    assert_eq!(mentions.first().and_then(|mention| mention.line), Some(3));
+    Ok(())
+}
@@ -0,0 +1,63 @@
+//! Rendering regression test: every mode is drawn off-screen and compared with its stored frame
+//! in `tests/snapshots`.
+//!
+//! After an intended rendering change, run `TENEX_UPDATE_SNAPSHOTS=1 cargo test --test
+//! snapshots` to rewrite the stored frames, and review the diff.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use tenex::agent::Storage;
+use tenex::app::Settings;
+use tenex::tui::snapshot::{every_mode, render_modes};
+use tenex::{App, Config};
+
+/// Frame size the snapshots are drawn at.
+const WIDTH: u16 = 80;
+const HEIGHT: u16 = 24;
+
+fn snapshot_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots")
+}
+
+#[test]
+fn every_mode_matches_its_snapshot() -> Result<()> {
+    let mut app = App::new(
+        Config::default(),
+        Storage::new(),
+        Settings::default(),
+        false,
+    );
+    let dir = snapshot_dir();
+    let update = std::env::var_os("TENEX_UPDATE_SNAPSHOTS").is_some();
+    let mut mismatched = Vec::new();
+
+    for (mode, snapshot) in render_modes(&mut app, every_mode(), WIDTH, HEIGHT)? {
+        assert!(
+            snapshot.problems().is_empty(),
+            "{mode}: {:?}",
+            snapshot.problems()
+        );
+        let path = dir.join(format!("{mode}.txt"));
+        let text = format!("{}\n", snapshot.text);
+        if update {
+            std::fs::create_dir_all(&dir)?;
+            std::fs::write(&path, text)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            continue;
+        }
+        let stored = std::fs::read_to_string(&path).unwrap_or_default();
+        if stored != text {
+            mismatched.push(mode);
+        }
+    }
+
+    assert!(
+        mismatched.is_empty(),
+        "Frames differ from tests/snapshots for {mismatched:?}. If the change is intended, \
+         rerun with TENEX_UPDATE_SNAPSHOTS=1 and review the diff."
+    );
+    Ok(())
+}
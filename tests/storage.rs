@@ -0,0 +1,116 @@
+//! Agent state saved to and reloaded from `state.json`.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use tenex::agent::{Agent, AgentDependency, AgentNotifications, DependencyTrigger, Storage};
+use uuid::Uuid;
+
+/// A state file path in a scratch directory removed when dropped.
+struct ScratchState(PathBuf);
+
+impl ScratchState {
+    fn new(name: &str) -> Result<Self> {
+        let dir = std::env::temp_dir().join(format!("tenex-test-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self(dir.join("state.json")))
+    }
+}
+
+impl Drop for ScratchState {
+    fn drop(&mut self) {
+        if let Some(dir) = self.0.parent() {
+            let _ = std::fs::remove_dir_all(dir);
+        }
+    }
+}
+
+/// Save one agent, change it with `change`, save again and reload it.
+fn reload_after(name: &str, change: impl FnOnce(&mut Agent)) -> Result<Agent> {
+    let state = ScratchState::new(name)?;
+    let mut storage = Storage::new();
+    let agent = Agent::new(
+        "Waiting".to_string(),
+        "claude".to_string(),
+        "agent/waiting".to_string(),
+        PathBuf::from("/tmp/waiting"),
+    );
+    let id = agent.id;
+    storage.add(agent);
+    storage.save_to(&state.0)?;
+
+    change(storage.get_mut(id).context("Agent is missing")?);
+    storage.save_to(&state.0)?;
+    let in_memory = storage.get(id).context("Agent is missing after saving")?;
+    let reloaded = Storage::load_from(&state.0)?;
+    let on_disk = reloaded
+        .get(id)
+        .context("Agent is missing after reloading")?;
+    assert_eq!(in_memory, on_disk);
+    Ok(on_disk.clone())
+}
+
+#[test]
+fn dependency_survives_save_and_reload() -> Result<()> {
+    let dependency = AgentDependency {
+        agent_id: Uuid::new_v4(),
+        trigger: DependencyTrigger::Waiting,
+        message: "Your dependency is done".to_string(),
+    };
+    let expected = dependency.clone();
+    let agent = reload_after("blocked-by", |agent| agent.blocked_by = Some(dependency))?;
+    assert_eq!(agent.blocked_by, Some(expected));
+    Ok(())
+}
+
+#[test]
+fn notification_mode_survives_save_and_reload() -> Result<()> {
+    let agent = reload_after("notifications", |agent| {
+        agent.notifications = AgentNotifications::Muted;
+    })?;
+    assert_eq!(agent.notifications, AgentNotifications::Muted);
+    Ok(())
+}
+
+#[test]
+fn scope_survives_save_and_reload() -> Result<()> {
+    let agent = reload_after("scope", |agent| {
+        agent.scope = Some("services/api/".to_string());
+    })?;
+    assert_eq!(agent.scope.as_deref(), Some("services/api/"));
+    Ok(())
+}
+
+#[test]
+fn swarm_file_survives_save_and_reload() -> Result<()> {
+    let path = PathBuf::from("/tmp/swarm.toml");
+    let expected = path.clone();
+    let agent = reload_after("swarm-file", |agent| agent.swarm_file = Some(path))?;
+    assert_eq!(agent.swarm_file, Some(expected));
+    Ok(())
+}
+
+#[test]
+fn swarm_link_survives_save_and_reload() -> Result<()> {
+    let root = Uuid::new_v4();
+    let agent = reload_after("swarm-root", |agent| agent.swarm_root = Some(root))?;
+    assert_eq!(agent.swarm_root, Some(root));
+    Ok(())
+}
+
+#[test]
+fn ticket_survives_save_and_reload() -> Result<()> {
+    let agent = reload_after("ticket", |agent| agent.ticket = Some("ENG-42".to_string()))?;
+    assert_eq!(agent.ticket.as_deref(), Some("ENG-42"));
+    Ok(())
+}
+
+#[test]
+fn imported_tmux_session_survives_save_and_reload() -> Result<()> {
+    let agent = reload_after("tmux-import", |agent| {
+        agent.imported_tmux_session = Some("work".to_string());
+    })?;
+    assert_eq!(agent.imported_tmux_session.as_deref(), Some("work"));
+    Ok(())
+}